@@ -0,0 +1,41 @@
+//! Attributes understood by deadmod, with no effect on compilation.
+//!
+//! `#[deadmod::keep]` marks an item as intentionally retained: deadmod never
+//! reports it and lists it, with its reason, in a separate "kept" section.
+//! Import the crate under the name `deadmod` so the attribute reads the same
+//! everywhere:
+//!
+//! ```toml
+//! [dependencies]
+//! deadmod = { package = "deadmod-annotations", version = "0.1" }
+//! ```
+//!
+//! The attribute applies to functions, constants and statics, enums (all of
+//! their variants), traits and impl blocks (all of their methods), and
+//! `macro_rules!` definitions. It also covers the generic parameters of the
+//! annotated item and the match arms inside annotated functions.
+
+use proc_macro::TokenStream;
+
+/// Mark an item as intentionally kept, optionally with a reason.
+///
+/// Expands to the item unchanged.
+///
+/// ```
+/// use deadmod_annotations as deadmod;
+///
+/// #[deadmod::keep(reason = "called from the C plugin host")]
+/// extern "C" fn plugin_init() -> i32 {
+///     0
+/// }
+///
+/// #[deadmod::keep]
+/// const PROTOCOL_VERSION: u32 = 2;
+///
+/// assert_eq!(plugin_init(), 0);
+/// assert_eq!(PROTOCOL_VERSION, 2);
+/// ```
+#[proc_macro_attribute]
+pub fn keep(_args: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
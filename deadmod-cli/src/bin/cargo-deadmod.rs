@@ -0,0 +1,105 @@
+//! `cargo deadmod` - runs deadmod as a cargo subcommand.
+//!
+//! Cargo runs `cargo-deadmod deadmod <args>` for `cargo deadmod <args>`.
+//! This wrapper drops the subcommand name, turns cargo's usual
+//! `--manifest-path <FILE>` into `CARGO_MANIFEST_DIR` (the default project
+//! path of `deadmod`), and hands everything else to the `deadmod` binary
+//! installed next to it.
+
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::{exit, Command};
+
+/// Arguments for `deadmod`, and the directory named by `--manifest-path`.
+fn split_args(args: impl IntoIterator<Item = OsString>) -> (Vec<OsString>, Option<PathBuf>) {
+    let mut args = args.into_iter().peekable();
+    if args.peek().is_some_and(|arg| arg == "deadmod") {
+        args.next();
+    }
+
+    let mut rest = Vec::new();
+    let mut manifest = None;
+    while let Some(arg) = args.next() {
+        let text = arg.to_string_lossy();
+        if text == "--" {
+            rest.push(arg);
+            rest.extend(args.by_ref());
+        } else if text == "--manifest-path" {
+            manifest = args.next().map(PathBuf::from);
+        } else if let Some(path) = text.strip_prefix("--manifest-path=") {
+            manifest = Some(PathBuf::from(path));
+        } else {
+            rest.push(arg);
+        }
+    }
+
+    let dir = manifest.map(|path| match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    });
+    (rest, dir)
+}
+
+/// The `deadmod` binary next to this one, else whichever is on `PATH`.
+fn deadmod_binary() -> PathBuf {
+    let name = format!("deadmod{}", env::consts::EXE_SUFFIX);
+    env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(&name)))
+        .filter(|path| path.is_file())
+        .unwrap_or_else(|| PathBuf::from(name))
+}
+
+fn main() {
+    let (args, manifest_dir) = split_args(env::args_os().skip(1));
+    let binary = deadmod_binary();
+
+    let mut command = Command::new(&binary);
+    command.args(args);
+    if let Some(dir) = manifest_dir {
+        command.env("CARGO_MANIFEST_DIR", dir);
+    }
+
+    match command.status() {
+        Ok(status) => exit(status.code().unwrap_or(2)),
+        Err(e) => {
+            eprintln!(
+                "[ERROR] failed to run {}: {}",
+                Path::new(&binary).display(),
+                e
+            );
+            exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(args: &[&str]) -> (Vec<OsString>, Option<PathBuf>) {
+        split_args(args.iter().map(OsString::from))
+    }
+
+    #[test]
+    fn test_split_args() {
+        let (args, dir) = split(&[
+            "deadmod",
+            "--dead-func",
+            "--manifest-path",
+            "app/Cargo.toml",
+        ]);
+        assert_eq!(args, ["--dead-func"]);
+        assert_eq!(dir, Some(PathBuf::from("app")));
+
+        let (args, dir) = split(&["--manifest-path=Cargo.toml", "--json"]);
+        assert_eq!(args, ["--json"]);
+        assert_eq!(dir, Some(PathBuf::from(".")));
+
+        // Invoked directly, and after `--`
+        let (args, dir) = split(&["fix", "--", "--manifest-path"]);
+        assert_eq!(args, ["fix", "--", "--manifest-path"]);
+        assert_eq!(dir, None);
+    }
+}
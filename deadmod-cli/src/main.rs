@@ -8,45 +8,122 @@
 //! - Incremental caching for faster re-analysis
 //! - Graphviz DOT visualization
 
-use anyhow::{anyhow, Context, Result};
-use clap::Parser;
-use std::collections::HashSet;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::info_span;
+
+mod rendering;
+
+use rendering::{
+    print_suppressions_due, render_bench_selftest, render_callpath, render_check_removal,
+    render_constants, render_cycles, render_dead_deps, render_discovery, render_expected_written,
+    render_explain, render_fields, render_file, render_find, render_fix_history, render_functions,
+    render_generics, render_grouped, render_layer_violations, render_macros, render_marked,
+    render_match_arms, render_metrics, render_output_path_error, render_params, render_per_target,
+    render_plan_step, render_plan_written, render_self_check, render_traits, render_variants,
+    render_warm, render_workspace, render_workspace_members, Findings, OutputSink, Render,
+    SelfTest, Stderr, Stdout, TargetMatrix,
+};
 
+use deadmod_core::fix::plan::step_targets;
+use deadmod_core::fix::DEFAULT_CONFIRM_THRESHOLD;
+use deadmod_core::synthetic::{generate as generate_synthetic, SyntheticSpec};
 use deadmod_core::{
-    analyze_workspace, build_graph, cache, discover_modules, extract_call_names, extract_call_usages,
-    extract_callgraph_functions, extract_const_usage, extract_constants,
-    extract_declared_generics, extract_functions, extract_generic_usages, extract_macro_usages,
-    extract_macros, extract_match_arms, extract_match_usages, extract_trait_usages,
-    extract_traits, extract_variant_usage, extract_variants, find_all_crates, find_crate_root,
-    find_dead, find_root_modules, fix_dead_modules, gather_rs_files, generate_html_graph,
-    generate_pixi_graph, get_cluster_tree, init_structured_logging, is_workspace_root, load_config,
-    module_graph_to_visualizer_json, print_json, print_plain, reachable_from_roots, visualize,
-    CallGraph, ConstGraph, DeadArmReason, EnumGraph, FuncGraph, GenericGraph, GenericKind,
-    MacroGraph, MatchGraph, TraitGraph,
+    analyze_file, analyze_workspace_with_roots, build_graph, cache, cache_dir, check_layers,
+    check_removal, combine_crate_modules_with_externs, combined_graph_json, current_author,
+    dead_module_files, dead_names, default_socket_path, dependency_files, discover_modules,
+    drop_in_files, explain, extract_call_names, extract_call_usages_resolved,
+    extract_callgraph_functions, extract_const_usage, extract_constants, extract_declared_generics,
+    extract_field_usages, extract_fields, extract_functions, extract_generic_usages,
+    extract_macro_mentions, extract_macro_usages, extract_macros, extract_match_arms,
+    extract_match_usages, extract_params, extract_trait_usages, extract_traits,
+    extract_variant_usage, extract_variants, find_all_crates, find_crate_root, find_dead,
+    find_dead_dependencies, find_module_cycles, find_root_modules, find_symbol,
+    find_workspace_root, fix, fix_and_verify, fix_dead_modules_with, fix_dead_params,
+    fix_dead_variants, focus_modules, gather_rs_files, gather_rs_files_limited,
+    generate_html_callgraph_with_layout, generate_pixi_callgraph_with_layout, generated_files,
+    group_by_module, ignored_ids, import_module_graph, init_logging, is_workspace_root,
+    join_module_path, list_transactions, load_config, load_coverage, locate_crate_root,
+    member_name, module_graph_to_visualizer_json, module_id, module_metrics, module_path_of,
+    module_reachability_matrix, parse_crate, parse_size, reachable_but_uncovered,
+    reachable_from_roots, record_run, resolve_jobs, resolve_root_modules, roots_for_crate,
+    run_with_threads, split_by_crate, undo_fix, warm_cache, write_outputs, ArchiveSource,
+    Attribution, Badge, CallGraph, CargoMetadata, Confidence, ConstGraph, Daemon, DeadItem,
+    Deadmod, DeadmodError, DetectorToggles, DotClusters, DotOptions, EnumGraph, ExpectedFindings,
+    ExportedMacroPolicy, FieldGraph, FixOptions, FsSource, FuncGraph, GenericGraph, GracePeriod,
+    GroupBy, HiddenApiPolicy, ItemLimit, KeptItem, LayerRules, LayoutOptions, Limits,
+    LivenessRules, LogFormat, MacroGraph, MatchGraph, ModuleInfo, ModuleReport, OutputFormat,
+    OutputSpec, ParamGraph, ParseMode, PathFormatter, PathStyle, Phase, Progress, ProgressEvent,
+    ProgressMode, ProjectModel, PubPolicy, RankDir, ReachabilityMatrix, RecentItem, RemovalPlan,
+    RunCounts, RunDelta, Skipped, Suppression, Suppressions, SymbolTable, Theme, TraitGraph,
+    VerifyOptions, VisualizerGraph, DEFAULT_BADGE_RED_AT, DEFAULT_LAYOUT_ITERATIONS,
+    DEFAULT_LAYOUT_SEED, DEFAULT_PORT,
 };
 
 #[derive(Parser, Debug)]
 #[command(author, version, about = "NASA-grade dead module detector for Rust")]
 pub struct Cli {
-    /// Path to the root of the Rust project
-    #[arg(default_value = ".")]
+    /// Path to the root of the Rust project (`CARGO_MANIFEST_DIR` when cargo
+    /// sets it, else the current directory)
+    #[arg(default_value_t = default_path())]
     path: String,
 
     /// Output results in JSON format
     #[arg(long)]
     json: bool,
 
+    /// Output JSON normalized for snapshot tests: sorted arrays, paths
+    /// relative to the crate root, no dates (implies --json)
+    #[arg(long)]
+    json_normalized: bool,
+
+    /// Write a report in the given format, optionally to a file (repeatable).
+    /// Spec: `format=<plain|json|dot|html|pixi>[,path=<FILE>]`, e.g.
+    /// `--output format=json,path=out.json --output format=html,path=graph.html`
+    #[arg(long, value_name = "SPEC")]
+    output: Vec<OutputSpec>,
+
+    /// Write file paths as absolute, relative (to the current directory) or
+    /// crate (relative to the crate root) paths (overrides deadmod.toml)
+    #[arg(long, value_name = "STYLE")]
+    path_style: Option<PathStyle>,
+
+    /// Report findings on lines added in the last DAYS days (by git blame)
+    /// as information instead of failing (overrides deadmod.toml)
+    #[arg(long, value_name = "DAYS")]
+    grace_period: Option<u32>,
+
+    /// Draw a progress bar on stderr: auto (when stderr is a terminal),
+    /// always or never
+    #[arg(long, value_name = "WHEN", default_value_t = ProgressMode::Auto)]
+    progress: ProgressMode,
+
+    /// List false positives marked more than DAYS days ago for re-review
+    /// (overrides deadmod.toml)
+    #[arg(long, value_name = "DAYS")]
+    review_suppressions: Option<u32>,
+
+    /// Analyze reachability from these modules only, replacing the detected
+    /// entry points (comma-separated; `crate::module` in workspaces)
+    #[arg(long, value_name = "MODULES", value_delimiter = ',')]
+    roots: Vec<String>,
+
     /// Module names or patterns to ignore
     #[arg(long, num_args = 1..)]
     ignore: Vec<String>,
 
-    /// Generate Graphviz DOT output for module dependencies
+    /// Generate Graphviz DOT output for module dependencies (alias for `--output dot`)
     #[arg(long)]
     dot: bool,
 
-    /// Write DOT output to a specified file instead of stdout
+    /// Write DOT output to a specified file (alias for `--output dot,path=FILE`)
     #[arg(long)]
     dot_file: Option<String>,
 
@@ -54,7 +131,14 @@ pub struct Cli {
     #[arg(long)]
     workspace: bool,
 
+    /// Take crates from a rust-project.json instead of Cargo (found
+    /// automatically in a directory without Cargo.toml)
+    #[arg(long, value_name = "PATH")]
+    project_json: Option<String>,
+
     /// Automatically remove dead modules and their declarations
+    /// (with --dead-variants: dead variants and the match arms naming them;
+    /// with --dead-params: rename unused parameters to `_name`)
     #[arg(long)]
     fix: bool,
 
@@ -62,26 +146,99 @@ pub struct Cli {
     #[arg(long)]
     fix_dry_run: bool,
 
-    /// Generate interactive HTML graph visualization
+    /// Confirm `--fix` runs that remove more files than the safety threshold
+    #[arg(long)]
+    yes: bool,
+
+    /// Apply fixes, run `cargo check`, and roll back removals that break the build
+    #[arg(long)]
+    fix_verify: bool,
+
+    /// With `--fix-verify`, also require `cargo test` to pass
+    #[arg(long)]
+    verify_tests: bool,
+
+    /// Write a staged removal plan for the dead modules to FILE instead of fixing
+    #[arg(long, value_name = "FILE")]
+    write_fix_plan: Option<String>,
+
+    /// Apply the next step of a removal plan written by `--write-fix-plan`
+    #[arg(long, value_name = "FILE")]
+    fix_plan: Option<String>,
+
+    /// Most modules removed by one plan step (0: no limit)
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_CONFIRM_THRESHOLD)]
+    plan_batch: usize,
+
+    /// With `--fix-plan`, apply step N instead of the next one
+    #[arg(long, value_name = "N")]
+    plan_step: Option<usize>,
+
+    /// Generate interactive HTML graph visualization (alias for `--output html`)
     #[arg(long)]
     html: bool,
 
-    /// Write HTML graph to a specified file instead of stdout
+    /// Write HTML graph to a specified file (alias for `--output html,path=FILE`)
     #[arg(long)]
     html_file: Option<String>,
 
-    /// Generate PixiJS WebGL interactive graph (GPU-accelerated, for large codebases)
+    /// Generate PixiJS WebGL interactive graph (alias for `--output pixi`)
     #[arg(long)]
     html_pixi: bool,
 
-    /// Write PixiJS WebGL graph to a specified file instead of stdout
+    /// Write PixiJS WebGL graph to a specified file (alias for `--output pixi,path=FILE`)
     #[arg(long)]
     html_pixi_file: Option<String>,
 
+    /// Start the PixiJS graph collapsed into expandable cluster super-nodes
+    /// when it has more than N modules (default: 1500)
+    #[arg(long, value_name = "N")]
+    pixi_cluster_threshold: Option<usize>,
+
+    /// Seed of the initial node layout in HTML and PixiJS graphs; the same
+    /// graph and seed always render the same picture
+    #[arg(long, value_name = "SEED", default_value_t = DEFAULT_LAYOUT_SEED)]
+    layout_seed: u64,
+
+    /// Compute final node positions in Rust (parallel force-directed layout)
+    /// and draw HTML and PixiJS graphs without an in-browser simulation;
+    /// for graphs too large to simulate in a browser
+    #[arg(long)]
+    static_layout: bool,
+
+    /// Simulation steps of --static-layout
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_LAYOUT_ITERATIONS, requires = "static_layout")]
+    layout_iterations: usize,
+
+    /// Color theme of DOT, HTML and PixiJS graphs: dark, light,
+    /// high-contrast, or colorblind-safe (blue and orange instead of green
+    /// and red)
+    #[arg(long, value_name = "THEME", default_value_t = Theme::Dark)]
+    theme: Theme,
+
+    /// Group DOT graph nodes into one box per source directory or per
+    /// workspace crate: none, directory, or crate
+    #[arg(long, value_name = "GROUPING", default_value_t = DotClusters::None)]
+    dot_clusters: DotClusters,
+
+    /// Direction of the DOT layout: LR, TB, RL, or BT
+    #[arg(long, value_name = "DIR", default_value_t = RankDir::LeftRight)]
+    dot_rankdir: RankDir,
+
+    /// Draw at most N modules in DOT graphs, keeping dead modules and the
+    /// best-connected live ones; the rest are counted in one note
+    #[arg(long, value_name = "N")]
+    dot_max_nodes: Option<usize>,
+
     /// Detect dead functions instead of dead modules
     #[arg(long)]
     dead_func: bool,
 
+    /// Detect test helpers no test uses: functions in #[cfg(test)] modules
+    /// (combine with --dead-func to list both)
+    #[arg(long)]
+    dead_test_helpers: bool,
+
     /// Detect dead trait methods instead of dead modules
     #[arg(long)]
     dead_traits: bool,
@@ -90,7 +247,7 @@ pub struct Cli {
     #[arg(long)]
     dead_generics: bool,
 
-    /// Detect unused macro_rules! definitions
+    /// Detect unused macros (macro_rules!, macro 2.0, proc macros)
     #[arg(long)]
     dead_macros: bool,
 
@@ -106,6 +263,82 @@ pub struct Cli {
     #[arg(long)]
     dead_match_arms: bool,
 
+    /// Detect function parameters the body never reads
+    #[arg(long)]
+    dead_params: bool,
+
+    /// Detect struct fields that are never read (write-only or unused)
+    #[arg(long)]
+    dead_fields: bool,
+
+    /// Detect Cargo.toml dependencies no live code uses, and unused
+    /// [workspace.dependencies] and [patch] entries at a workspace root
+    #[arg(long)]
+    dead_deps: bool,
+
+    /// Only report findings at or above this confidence (low, medium, high)
+    #[arg(long, value_name = "LEVEL", default_value = "low")]
+    min_confidence: Confidence,
+
+    /// In plain output, list at most N findings per list and summarize the
+    /// rest (JSON output is never truncated)
+    #[arg(long, value_name = "N")]
+    max_items: Option<usize>,
+
+    /// In plain output, list findings by priority: confidence, then size
+    #[arg(long)]
+    top: bool,
+
+    /// Treat #[doc(hidden)] pub functions as public, downgrade, or exclude (overrides deadmod.toml)
+    #[arg(long, value_name = "POLICY")]
+    doc_hidden: Option<HiddenApiPolicy>,
+
+    /// Whether pub functions are entry points: auto (only for libraries with
+    /// dependents), always, or never (overrides deadmod.toml)
+    #[arg(long, value_name = "POLICY")]
+    assume_pub_reachable: Option<PubPolicy>,
+
+    /// Exported macros unused in their crate: assume-used, report, or
+    /// workspace-check (report unless another member invokes them; overrides deadmod.toml)
+    #[arg(long, value_name = "POLICY")]
+    exported_macros: Option<ExportedMacroPolicy>,
+
+    /// Also read the sources of direct dependencies and of path crates
+    /// depending on this one, for usages only (never reported)
+    #[arg(long)]
+    include_deps_usages: bool,
+
+    /// Skip files larger than SIZE bytes (K/M/G suffixes allowed, overrides deadmod.toml)
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    max_file_size: Option<u64>,
+
+    /// Analyze at most N files, in path order (overrides deadmod.toml)
+    #[arg(long, value_name = "N")]
+    max_files: Option<usize>,
+
+    /// Give up after SECS seconds (overrides deadmod.toml)
+    #[arg(long, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// With `--dead-func`, list reachable functions never executed according to
+    /// llvm-cov export or grcov coveralls JSON
+    #[arg(long, value_name = "FILE", requires = "dead_func")]
+    coverage: Option<PathBuf>,
+
+    /// Report which modules and functions each target (lib, main, src/bin/*,
+    /// tests/, benches/, examples/) uses, separating code dead for all targets from code used by only some
+    #[arg(long)]
+    per_target: bool,
+
+    /// Report findings from all detectors as a tree grouped by owning module (`module`)
+    #[arg(long, value_name = "KEY")]
+    group_by: Option<GroupBy>,
+
+    /// List findings inside dead modules individually instead of folding
+    /// them into the dead module
+    #[arg(long)]
+    no_collapse: bool,
+
     /// Generate function call graph (JSON output)
     #[arg(long)]
     callgraph: bool,
@@ -118,6 +351,18 @@ pub struct Cli {
     #[arg(long)]
     callgraph_viz: bool,
 
+    /// Generate function call graph as folded stacks (for inferno/flamegraph)
+    #[arg(long)]
+    callgraph_folded: bool,
+
+    /// Generate interactive HTML graph of the function call graph
+    #[arg(long)]
+    html_callgraph: bool,
+
+    /// Generate PixiJS WebGL graph of the function call graph
+    #[arg(long)]
+    html_pixi_callgraph: bool,
+
     /// Generate module dependency graph for visualizer (numeric IDs, dead flags)
     #[arg(long)]
     modgraph_viz: bool,
@@ -134,37 +379,1321 @@ pub struct Cli {
     #[arg(long, value_name = "FILE")]
     export_combined: Option<String>,
 
+    /// Write a shields.io endpoint badge counting dead modules and functions
+    /// to FILE
+    #[arg(long, value_name = "FILE")]
+    badge_json: Option<String>,
+
+    /// With `--badge-json`, turn the badge red from N dead modules and
+    /// functions (yellow below, bright green at zero)
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_BADGE_RED_AT, requires = "badge_json")]
+    badge_red_at: usize,
+
+    /// Limit module and call graph exports (DOT, HTML, Pixi, visualizer
+    /// JSON) to the neighborhood of one module or function
+    #[arg(long, value_name = "NODE")]
+    focus: Option<String>,
+
+    /// With `--focus`, keep nodes up to N hops away, in either direction
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "focus")]
+    depth: usize,
+
     /// Discover all modules via filesystem structure (show cluster hierarchy)
     #[arg(long)]
     discover: bool,
+
+    /// Neither load nor save the parse cache (.deadmod/)
+    #[arg(long)]
+    no_cache: bool,
+
+    /// With --no-cache, skip content hashing and read files through memory
+    /// maps (fastest cold run, e.g. in CI)
+    #[arg(long, requires = "no_cache")]
+    fast: bool,
+
+    /// Worker threads for scanning and parsing (default: DEADMOD_JOBS, else one per CPU)
+    #[arg(long, short = 'j', value_name = "N", global = true)]
+    jobs: Option<NonZeroUsize>,
+
+    /// Diagnostic log format on stderr, json or pretty (default:
+    /// DEADMOD_LOG_FORMAT, else json); RUST_LOG selects what is logged
+    #[arg(long, value_name = "FORMAT", global = true)]
+    log_format: Option<LogFormat>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-/// Prints workspace info when running on a workspace root.
-fn print_workspace_info(path: &Path) {
-    if is_workspace_root(path) {
-        // Count member crates
-        if let Ok(entries) = fs::read_dir(path) {
-            let members: Vec<_> = entries
-                .flatten()
-                .filter(|e| {
-                    let p = e.path();
-                    p.is_dir() && p.join("Cargo.toml").exists()
-                })
-                .collect();
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Remove dead modules, or revert a previous fix with --undo
+    Fix(FixArgs),
+    /// List dependency cycles between modules
+    Cycles(CyclesArgs),
+    /// Show coupling metrics per module: fan-in/out, instability, nesting, cycles
+    Metrics(MetricsArgs),
+    /// Check module dependencies against forbidden-dependency rules
+    Layers(LayersArgs),
+    /// Show call chains from one function to another
+    Callpath(CallpathArgs),
+    /// Report dead code located in one file, analyzed in the context of its crate
+    File(FileArgs),
+    /// Serve a warm analysis to other clients over a local socket (JSON-RPC)
+    Daemon(DaemonArgs),
+    /// Serve a warm analysis over an HTTP JSON API for editors and dashboards
+    Serve(ServeArgs),
+    /// Show the evidence behind one finding, by its stable ID
+    Explain(ExplainArgs),
+    /// Check that no string, doc link, macro or config file names a dead item before removing it
+    CheckRemoval(CheckRemovalArgs),
+    /// Find the definitions of a name or path, their liveness and the lines mentioning it
+    Find(FindArgs),
+    /// Record a finding as a false positive, excluded from future reports
+    MarkFalsePositive(MarkFalsePositiveArgs),
+    /// Parse every crate of the workspace into its cache, without a report
+    Warm(WarmArgs),
+    /// Generate and analyze a synthetic project to measure throughput on this machine
+    BenchSelftest(BenchSelftestArgs),
+    /// Analyze a module graph exported with --export-modgraph, without the source tree
+    AnalyzeGraph(AnalyzeGraphArgs),
+    /// Analyze every crate of the workspace and fail on findings missing from deadmod-expected.toml
+    SelfCheck(SelfCheckArgs),
+    /// Analyze a crate snapshot in a .tar, .tar.gz, .crate or .zip archive without extracting it
+    Archive(ArchiveArgs),
+}
+
+#[derive(Args, Debug)]
+struct WarmArgs {
+    /// Path to a crate, or to a workspace to warm all of its crates
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// Output per-crate cache statistics in JSON format
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args, Debug)]
+struct BenchSelftestArgs {
+    /// Functions in the generated project (20 per module, one in ten dead)
+    #[arg(long, value_name = "N", default_value_t = 10_000)]
+    functions: usize,
+
+    /// Output timings and throughput in JSON format
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args, Debug)]
+struct ExplainArgs {
+    /// Finding ID, as printed in reports (`[id: ...]`)
+    id: String,
+
+    /// Path to the root of the Rust project
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// Output the evidence in JSON format
+    #[arg(long)]
+    json: bool,
+
+    /// Write file paths as absolute, relative or crate paths
+    #[arg(long, value_name = "STYLE")]
+    path_style: Option<PathStyle>,
+}
+
+#[derive(Args, Debug)]
+struct CheckRemovalArgs {
+    /// Finding ID, or the dead item's name as reported (`helper`, `Config::load`)
+    item: String,
+
+    /// Path to the root of the Rust project
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// Output the result in JSON format
+    #[arg(long)]
+    json: bool,
+
+    /// Write file paths as absolute, relative or crate paths
+    #[arg(long, value_name = "STYLE")]
+    path_style: Option<PathStyle>,
+}
+
+#[derive(Args, Debug)]
+struct FindArgs {
+    /// Name or path to look up (`connect`, `net::Client::connect`, `crate::net`)
+    query: String,
+
+    /// Path to the root of the Rust project
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// Output the result in JSON format
+    #[arg(long)]
+    json: bool,
+
+    /// Write file paths as absolute, relative or crate paths
+    #[arg(long, value_name = "STYLE")]
+    path_style: Option<PathStyle>,
+}
+
+#[derive(Args, Debug)]
+struct MarkFalsePositiveArgs {
+    /// Finding ID, as printed in reports (`[id: ...]`)
+    id: String,
+
+    /// Path to the root of the Rust project
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// Why the finding is a false positive
+    #[arg(long)]
+    reason: String,
+
+    /// Author to record (default: `git config user.name`)
+    #[arg(long)]
+    author: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct ServeArgs {
+    /// Path to the root of the Rust project
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// Port to listen on
+    #[arg(long, default_value_t = DEFAULT_PORT)]
+    port: u16,
+
+    /// Address to bind (only local clients by default)
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+}
+
+#[derive(Args, Debug)]
+struct DaemonArgs {
+    /// Path to the root of the Rust project
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// Socket to listen on (default: `.deadmod/daemon.sock` in the crate root)
+    #[arg(long, value_name = "FILE")]
+    socket: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct FileArgs {
+    /// Source file to report on
+    file: PathBuf,
+
+    /// Only report findings at or above this confidence (low, medium, high)
+    #[arg(long, value_name = "LEVEL", default_value = "low")]
+    min_confidence: Confidence,
+
+    /// List findings individually even when the whole file is a dead module
+    #[arg(long)]
+    no_collapse: bool,
+
+    /// Output results in JSON format
+    #[arg(long)]
+    json: bool,
+
+    /// Write file paths as absolute, relative or crate paths
+    #[arg(long, value_name = "STYLE")]
+    path_style: Option<PathStyle>,
+
+    /// Report findings on lines added in the last DAYS days as information
+    #[arg(long, value_name = "DAYS")]
+    grace_period: Option<u32>,
+}
+
+#[derive(Args, Debug)]
+struct CallpathArgs {
+    /// Calling function (name or full path, e.g. `main` or `cli::run`)
+    from: String,
+
+    /// Called function (name or full path)
+    to: String,
+
+    /// Path to the root of the Rust project
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// Maximum number of call chains to show
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    max_paths: usize,
+
+    /// Output the call chains as a DOT subgraph
+    #[arg(long, conflicts_with = "json")]
+    dot: bool,
+
+    /// Output results in JSON format
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args, Debug)]
+struct AnalyzeGraphArgs {
+    /// Module graph JSON written by --export-modgraph or --modgraph-viz
+    graph: PathBuf,
+
+    /// Analyze reachability from these modules instead of the roots
+    /// recorded in the export (comma-separated)
+    #[arg(long, value_name = "MODULES", value_delimiter = ',')]
+    roots: Vec<String>,
+
+    /// Show coupling metrics per module instead of dead modules
+    #[arg(long, conflicts_with_all = ["cycles", "layers"])]
+    metrics: bool,
+
+    /// List dependency cycles instead of dead modules
+    #[arg(long, conflicts_with = "layers")]
+    cycles: bool,
+
+    /// Check the graph against the `[[rule]]` entries of a TOML file
+    #[arg(long, value_name = "FILE")]
+    layers: Option<PathBuf>,
+
+    /// Write the report in a format, optionally to a file (repeatable);
+    /// same spec as the main --output
+    #[arg(long, value_name = "SPEC")]
+    output: Vec<OutputSpec>,
+
+    /// Limit graph outputs (DOT, HTML, Pixi) to the neighborhood of a module
+    #[arg(long, value_name = "MODULE")]
+    focus: Option<String>,
+
+    /// With `--focus`, keep modules up to N hops away, in either direction
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "focus")]
+    depth: usize,
+
+    /// Output results in JSON format
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args, Debug)]
+struct SelfCheckArgs {
+    /// Path to the workspace, or to a crate outside one
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// Write the current findings to deadmod-expected.toml instead of checking
+    #[arg(long)]
+    update: bool,
+
+    /// Parse every file, without reading or writing the cache
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Output results in JSON format
+    #[arg(long, conflicts_with = "update")]
+    json: bool,
+}
+
+#[derive(Args, Debug)]
+struct ArchiveArgs {
+    /// Archive holding the crate (the shallowest Cargo.toml in it marks the crate)
+    archive: PathBuf,
+
+    /// Only report findings at or above this confidence (low, medium, high)
+    #[arg(long, value_name = "LEVEL", default_value = "low")]
+    min_confidence: Confidence,
+
+    /// Skip files larger than SIZE bytes (K/M/G suffixes allowed)
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    max_file_size: Option<u64>,
+
+    /// Analyze at most N files, in path order
+    #[arg(long, value_name = "N")]
+    max_files: Option<usize>,
+
+    /// Output results in JSON format
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args, Debug)]
+struct CyclesArgs {
+    /// Path to the root of the Rust project
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// Output results in JSON format
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args, Debug)]
+struct MetricsArgs {
+    /// Path to the root of the Rust project
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// Output results in JSON format
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args, Debug)]
+struct LayersArgs {
+    /// Path to the root of the Rust project
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// TOML file with `[[rule]]` entries (defaults to `[layers]` in deadmod.toml)
+    #[arg(long, value_name = "FILE")]
+    rules: Option<PathBuf>,
+
+    /// Output results in JSON format
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args, Debug)]
+struct FixArgs {
+    /// Path to the root of the Rust project
+    #[arg(default_value_t = default_path())]
+    path: String,
+
+    /// Show what would change without modifying anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Confirm fixes that remove more files than the safety threshold
+    #[arg(long)]
+    yes: bool,
+
+    /// Run `cargo check` after fixing and roll back removals that break the build
+    #[arg(long)]
+    verify: bool,
+
+    /// With `--verify`, also require `cargo test` to pass
+    #[arg(long)]
+    verify_tests: bool,
+
+    /// Revert the latest fix, or the one given with `--undo=ID`
+    #[arg(
+        long,
+        value_name = "ID",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = ""
+    )]
+    undo: Option<String>,
+
+    /// List recorded fix transactions that can be undone
+    #[arg(long)]
+    list: bool,
+
+    /// Write a staged removal plan to FILE instead of fixing
+    #[arg(long, value_name = "FILE")]
+    write_plan: Option<String>,
+
+    /// Apply the next step of a removal plan written by `--write-plan`
+    #[arg(long, value_name = "FILE")]
+    plan: Option<String>,
+
+    /// Most modules removed by one plan step (0: no limit)
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_CONFIRM_THRESHOLD)]
+    batch: usize,
+
+    /// With `--plan`, apply step N instead of the next one
+    #[arg(long, value_name = "N")]
+    step: Option<usize>,
+}
+
+/// Handles `deadmod fix --list` and `deadmod fix --undo[=ID]`.
+fn run_fix_history(args: &FixArgs) -> Result<()> {
+    let root = locate_crate_root(Path::new(&args.path))?;
+
+    if args.list {
+        render_fix_history(&mut Stdout, &list_transactions(&root)?);
+        return Ok(());
+    }
 
-            if !members.is_empty() {
-                eprintln!("INFO: Detected Cargo workspace with {} member(s):", members.len());
-                for m in &members {
-                    eprintln!("  - {}", m.file_name().to_string_lossy());
+    let id = args.undo.as_deref().filter(|id| !id.is_empty());
+    let result = undo_fix(&root, id, args.dry_run)?;
+    std::process::exit(if result.errors.is_empty() { 0 } else { 1 });
+}
+
+/// Builds the function call graph for a crate.
+fn build_call_graph(root: &Path, parse_mode: ParseMode) -> Result<CallGraph> {
+    let files = gather_rs_files(root)?;
+    let mods = parse_crate(root, &files, parse_mode, &Progress::none())?;
+
+    // Extract functions, resolved call paths and re-exports from all files
+    let mut all_functions = Vec::new();
+    let mut usage_map = std::collections::HashMap::new();
+    let mut macro_mentions = HashSet::new();
+    let mut contents = Vec::new();
+
+    // In path order: same-named definitions then always resolve alike, and
+    // visualizations of the graph are stable
+    let mut infos: Vec<&ModuleInfo> = mods.values().collect();
+    infos.sort_by(|a, b| a.path.cmp(&b.path));
+    for info in infos {
+        if let Ok(content) = fs::read_to_string(&info.path) {
+            let functions = extract_callgraph_functions(&info.path, &content);
+            let usages = extract_call_usages_resolved(&info.path, &content);
+
+            all_functions.extend(functions);
+            usage_map.insert(info.path.display().to_string(), usages);
+            macro_mentions.extend(extract_macro_mentions(&content));
+            contents.push((info.path.clone(), content));
+        }
+    }
+
+    // Re-exports are resolved against the full set of definitions
+    let mut symbols = SymbolTable::build(&all_functions);
+    for (path, content) in &contents {
+        symbols.add_file_reexports(path, content);
+    }
+
+    Ok(
+        CallGraph::build_with_symbols(&all_functions, &usage_map, &symbols)
+            .with_macro_mentions(macro_mentions),
+    )
+}
+
+/// Resolves a function name given on the command line to a single full path.
+fn resolve_single_function(graph: &CallGraph, query: &str) -> Result<String> {
+    let mut matches = graph.resolve_function(query);
+    match matches.len() {
+        0 => Err(anyhow!("No function matching '{}'", query)),
+        1 => Ok(matches.remove(0)),
+        _ => Err(anyhow!(
+            "'{}' is ambiguous, use a full path: {}",
+            query,
+            matches.join(", ")
+        )),
+    }
+}
+
+/// Functions within `--depth` calls of the `--focus` function.
+fn focused_functions(cli: &Cli, graph: &CallGraph) -> Result<HashSet<String>> {
+    let focus = cli.focus.as_deref().unwrap_or_default();
+    Ok(graph.neighborhood(&resolve_single_function(graph, focus)?, cli.depth))
+}
+
+/// Node placement for HTML and PixiJS graphs.
+fn layout_options(cli: &Cli) -> LayoutOptions {
+    LayoutOptions::default()
+        .with_seed(cli.layout_seed)
+        .with_static_layout(cli.static_layout)
+        .with_iterations(cli.layout_iterations)
+}
+
+/// Shape of DOT graphs.
+fn dot_options(cli: &Cli) -> DotOptions {
+    let options = DotOptions::default()
+        .with_clusters(cli.dot_clusters)
+        .with_rankdir(cli.dot_rankdir);
+    match cli.dot_max_nodes {
+        Some(max) => options.with_max_nodes(max),
+        None => options,
+    }
+}
+
+/// The call graph for visualizer exports, limited to `--focus` if given.
+/// Dead flags come from the whole graph.
+fn focused_visualizer_graph(cli: &Cli, graph: &CallGraph) -> Result<VisualizerGraph> {
+    let mut viz = graph.to_visualizer_graph();
+    if cli.focus.is_some() {
+        viz.retain(&focused_functions(cli, graph)?);
+    }
+    Ok(viz)
+}
+
+/// The modules graph exports show: all, or the `--focus` neighborhood.
+fn focused_modules<'a>(
+    cli: &Cli,
+    mods: &'a HashMap<String, ModuleInfo>,
+) -> Result<Cow<'a, HashMap<String, ModuleInfo>>> {
+    Ok(match &cli.focus {
+        Some(focus) => Cow::Owned(focus_modules(mods, focus, cli.depth)?),
+        None => Cow::Borrowed(mods),
+    })
+}
+
+/// Module graph in visualizer JSON, with the roots it was analyzed from so
+/// `deadmod analyze-graph` can analyze it again.
+fn modgraph_json(
+    cli: &Cli,
+    root: &Path,
+    mods: &HashMap<String, ModuleInfo>,
+    roots: &HashSet<String>,
+    reachable: &HashSet<&str>,
+) -> Result<serde_json::Value> {
+    let shown = focused_modules(cli, mods)?;
+    let mut json = module_graph_to_visualizer_json(
+        &path_formatter(cli.path_style, root).localize_modules(&shown),
+        reachable,
+    );
+    let mut shown_roots: Vec<&String> = roots
+        .iter()
+        .filter(|name| shown.contains_key(*name))
+        .collect();
+    shown_roots.sort();
+    json["roots"] = serde_json::json!(shown_roots);
+    Ok(json)
+}
+
+/// Handles `deadmod callpath <from> <to>`.
+fn run_callpath(args: &CallpathArgs) -> Result<()> {
+    let root = locate_crate_root(Path::new(&args.path))?;
+    let graph = build_call_graph(&root, ParseMode::Cached)?;
+
+    let from = resolve_single_function(&graph, &args.from)?;
+    let to = resolve_single_function(&graph, &args.to)?;
+    let paths = graph.paths_between(&from, &to, args.max_paths);
+
+    if args.dot {
+        Stdout.write(&graph.paths_to_dot(&paths));
+    } else {
+        let formatter = PathFormatter::default();
+        render_callpath(
+            &mut Stdout,
+            &Render::new(args.json, &formatter),
+            &from,
+            &to,
+            &paths,
+        )?;
+    }
+
+    std::process::exit(if paths.is_empty() { 1 } else { 0 });
+}
+
+/// Write kept items' files in `paths`' style.
+fn localize_kept(kept: &mut [KeptItem], paths: &PathFormatter) {
+    if paths.style().is_some() {
+        for item in kept {
+            item.file = paths.format(&item.file);
+        }
+    }
+}
+
+/// Move detector findings added within `grace` out of `items`, as
+/// [`RecentItem`]s with files in `paths`' style.
+fn take_recent<T>(
+    grace: Option<&GracePeriod>,
+    items: &mut Vec<T>,
+    paths: &PathFormatter,
+    finding: impl Fn(&T) -> DeadItem,
+) -> Vec<RecentItem> {
+    let Some(grace) = grace else {
+        return Vec::new();
+    };
+    let mut recent = Vec::new();
+    items.retain(|item| match grace.recent(&finding(item)) {
+        Some(mut found) => {
+            found.item.file = PathBuf::from(paths.format(&found.item.file));
+            recent.push(found);
+            false
+        }
+        None => true,
+    });
+    recent
+}
+
+/// How much of each finding list plain output shows (`--max-items`, `--top`).
+fn item_limit(cli: &Cli) -> ItemLimit {
+    ItemLimit::new(cli.max_items, cli.top)
+}
+
+/// Rendering settings from the output flags; JSON is normalized against
+/// the crate `root` under `--json-normalized`.
+fn render_settings<'a>(cli: &Cli, root: &'a Path, paths: &'a PathFormatter) -> Render<'a> {
+    Render {
+        json: cli.json,
+        normalize_root: cli.json_normalized.then_some(root),
+        paths,
+        limit: item_limit(cli),
+    }
+}
+
+/// Files of dead modules whose findings are folded into the module
+/// (none with `--no-collapse`).
+fn collapsed_files(cli: &Cli, root: &Path, mods: &HashMap<String, ModuleInfo>) -> HashSet<String> {
+    if cli.no_collapse {
+        HashSet::new()
+    } else {
+        dead_module_files(root, mods, &cli.roots)
+    }
+}
+
+/// This run's `counts` against the last run of `report` recorded in the
+/// cache of the crate at `root`, recording them for the next run. `None`
+/// on a first run or without the cache.
+fn run_delta(
+    parse_mode: ParseMode,
+    root: &Path,
+    report: &str,
+    counts: RunCounts,
+) -> Option<RunDelta> {
+    if parse_mode != ParseMode::Cached {
+        return None;
+    }
+    record_run(&cache_dir(root), report, &counts).unwrap_or_else(|e| {
+        eprintln!("[WARN] Failed to record run counts: {:#}", e);
+        None
+    })
+}
+
+/// Handles `deadmod file <FILE>`.
+fn run_file(args: &FileArgs) -> Result<()> {
+    if !args.file.is_file() {
+        bail!("Not a file: {}", args.file.display());
+    }
+    let root = locate_crate_root(&args.file)?;
+
+    let mut builder = analysis_builder(&root)
+        .min_confidence(args.min_confidence)
+        .collapse(!args.no_collapse);
+    if let Some(days) = grace_days(args.grace_period, &root) {
+        builder = builder.with_grace_period(days);
+    }
+    let mut findings = analyze_file(&args.file, &builder)?;
+    warn_skipped(&findings.skipped);
+    let paths = path_formatter(args.path_style, &root);
+    findings.localize_paths(&paths);
+
+    let render = Render::new(args.json, &paths);
+    render_file(&mut Stdout, &render, &paths.format(&args.file), &findings)?;
+
+    std::process::exit(if findings.has_dead_code() { 1 } else { 0 });
+}
+
+/// Handles `deadmod explain <ID> [PATH]`.
+fn run_explain(args: &ExplainArgs) -> Result<()> {
+    let root = locate_crate_root(Path::new(&args.path))?;
+    let result = analysis_builder(&root).analyze()?;
+    warn_skipped(&result.skipped);
+
+    let Some(evidence) = explain(&result, &args.id, &FsSource::new(&root)) else {
+        bail!(
+            "No finding with ID '{}' (it may have been fixed, or the ID is mistyped)",
+            args.id
+        );
+    };
+
+    let mut evidence = evidence;
+    let paths = path_formatter(args.path_style, &root);
+    evidence.localize_paths(&paths);
+    render_explain(&mut Stdout, &Render::new(args.json, &paths), &evidence)
+}
+
+/// Handles `deadmod check-removal <ITEM> [PATH]`.
+fn run_check_removal(args: &CheckRemovalArgs) -> Result<()> {
+    let root = locate_crate_root(Path::new(&args.path))?;
+    let result = analysis_builder(&root).analyze()?;
+    warn_skipped(&result.skipped);
+
+    let paths = path_formatter(args.path_style, &root);
+    let mut check = check_removal(&result, &args.item, &FsSource::new(&root))?;
+    check.localize_paths(&paths);
+    render_check_removal(&mut Stdout, &Render::new(args.json, &paths), &check)?;
+    std::process::exit(if check.safe { 0 } else { 1 });
+}
+
+/// Handles `deadmod find <QUERY> [PATH]`.
+fn run_find(args: &FindArgs) -> Result<()> {
+    let root = locate_crate_root(Path::new(&args.path))?;
+    let mut builder = analysis_builder(&root);
+    if let Some(days) = grace_days(None, &root) {
+        builder = builder.with_grace_period(days);
+    }
+    let result = builder.analyze()?;
+    warn_skipped(&result.skipped);
+
+    let paths = path_formatter(args.path_style, &root);
+    let mut search = find_symbol(&result, &args.query, &FsSource::new(&root));
+    search.localize_paths(&paths);
+    let found = !(search.definitions.is_empty() && search.references.is_empty());
+
+    render_find(&mut Stdout, &Render::new(args.json, &paths), &search)?;
+    std::process::exit(if found { 0 } else { 1 });
+}
+
+/// Handles `deadmod mark-false-positive <ID> [PATH] --reason "..."`.
+fn run_mark_false_positive(args: &MarkFalsePositiveArgs) -> Result<()> {
+    let root = locate_crate_root(Path::new(&args.path))?;
+    let mut suppressions = Suppressions::load(&root)?;
+    let id = args.id.trim().to_ascii_lowercase();
+
+    // A suppressed finding is no longer reported; update its record in place
+    let (kind, name, file) = match suppressions.get(&id) {
+        Some(existing) => (
+            existing.kind.clone(),
+            existing.name.clone(),
+            existing.file.clone(),
+        ),
+        None => {
+            let result = analysis_builder(&root).analyze()?;
+            let Some(evidence) = explain(&result, &id, &FsSource::new(&root)) else {
+                bail!(
+                    "No finding with ID '{}' (it may have been fixed, or the ID is mistyped)",
+                    args.id
+                );
+            };
+            let file = PathFormatter::new(Some(PathStyle::Crate), &root).format(&evidence.file);
+            (evidence.kind.to_string(), evidence.name, file)
+        }
+    };
+
+    let author = args.author.clone().unwrap_or_else(|| current_author(&root));
+    let suppression = Suppression::new(&id, &kind, &name, &file, &args.reason, &author);
+    let replaced = suppressions.add(suppression.clone());
+    suppressions.save(&root)?;
+    render_marked(&mut Stdout, &suppression, replaced);
+    Ok(())
+}
+
+/// Handles `deadmod self-check [PATH]`.
+fn run_self_check(args: &SelfCheckArgs) -> Result<()> {
+    let path = Path::new(&args.path);
+    let (root, crates) = match find_workspace_root(path) {
+        Some(ws_root) => {
+            let crates = find_all_crates(&ws_root)?;
+            (ws_root, crates)
+        }
+        None => {
+            let root = locate_crate_root(path)?;
+            (root.clone(), vec![root])
+        }
+    };
+    let parse_mode = if args.no_cache {
+        ParseMode::Uncached
+    } else {
+        ParseMode::Cached
+    };
+
+    // Same analysis as `--group-by module`, without grace period or folding
+    let mut results = Vec::with_capacity(crates.len());
+    for crate_root in &crates {
+        let result = analysis_builder(crate_root)
+            .with_parse_mode(parse_mode)
+            .analyze()
+            .with_context(|| format!("Failed to analyze {}", crate_root.display()))?;
+        warn_skipped(&result.skipped);
+        results.push((member_name(crate_root), result));
+    }
+    let actual = ExpectedFindings::from_results(
+        &root,
+        results.iter().map(|(name, result)| (name.as_str(), result)),
+    );
+
+    if args.update {
+        actual.save(&root)?;
+        render_expected_written(&mut Stdout, actual.len(), &ExpectedFindings::path(&root));
+        return Ok(());
+    }
+
+    let diff = ExpectedFindings::load(&root)?.compare(&actual);
+    let paths = PathFormatter::new(None, &root);
+    let render = Render::new(args.json, &paths);
+    render_self_check(&mut Stdout, &render, &diff, actual.len())?;
+
+    std::process::exit(if diff.passed() { 0 } else { 1 });
+}
+
+/// Handles `deadmod archive <ARCHIVE>`.
+///
+/// The snapshot has no directory on disk, so deadmod.toml settings, the
+/// cache and workspace-wide macro scanning do not apply.
+fn run_archive(args: &ArchiveArgs) -> Result<()> {
+    let limits = Limits {
+        max_file_size: args.max_file_size,
+        max_files: args.max_files,
+        timeout: None,
+    };
+    let source = ArchiveSource::open(&args.archive, &limits)?;
+    if !source.crate_dir().as_os_str().is_empty() {
+        eprintln!(
+            "INFO: Analyzing crate {}/ in {}",
+            source.crate_dir().display(),
+            args.archive.display()
+        );
+    }
+
+    let result = Deadmod::new(PathBuf::new())
+        .with_source(source)
+        .all()
+        .min_confidence(args.min_confidence)
+        .analyze()?;
+    warn_skipped(&result.skipped);
+
+    let paths = PathFormatter::new(None, Path::new(""));
+    let render = Render::new(args.json, &paths);
+    render_grouped(
+        &mut Stdout,
+        &render,
+        &group_by_module(&result),
+        &result,
+        &[],
+        None,
+    )?;
+
+    std::process::exit(if result.has_dead_code() { 1 } else { 0 });
+}
+
+/// Handles `deadmod warm [PATH]`.
+fn run_warm(args: &WarmArgs) -> Result<()> {
+    let path = Path::new(&args.path);
+    let crates = match find_workspace_root(path) {
+        Some(ws_root) => find_all_crates(&ws_root)?,
+        None => vec![locate_crate_root(path)?],
+    };
+
+    let mut report = Vec::with_capacity(crates.len());
+    for crate_root in &crates {
+        let files = gather_files(
+            crate_root,
+            &scan_limits(None, crate_root),
+            &Progress::none(),
+        )?;
+        let stats = warm_cache(crate_root, &files)
+            .with_context(|| format!("Failed to warm cache of {}", crate_root.display()))?;
+        report.push((crate_root.clone(), stats));
+    }
+
+    let paths = PathFormatter::default();
+    render_warm(&mut Stdout, &Render::new(args.json, &paths), &report)
+}
+
+/// Handles `deadmod bench-selftest [--functions N]`: full analysis of a
+/// generated project, timed per phase, checked against what was planted.
+fn run_bench_selftest(args: &BenchSelftestArgs) -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("deadmod-selftest-{}", std::process::id()));
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    let result = bench_selftest(args, &dir);
+    if let Err(e) = fs::remove_dir_all(&dir) {
+        eprintln!("[WARN] Failed to remove {}: {}", dir.display(), e);
+    }
+    result
+}
+
+fn bench_selftest(args: &BenchSelftestArgs, dir: &Path) -> Result<()> {
+    let project = generate_synthetic(dir, &SyntheticSpec::new(args.functions))?;
+
+    // Time of each phase, in the order phases first start
+    let phases: Arc<Mutex<Vec<(String, Instant, Duration)>>> = Arc::default();
+    let timings = Arc::clone(&phases);
+    let progress = Progress::new(move |event| {
+        let mut phases = timings.lock().unwrap_or_else(|e| e.into_inner());
+        match event {
+            ProgressEvent::PhaseStart { phase, .. } => {
+                let name = phase.to_string();
+                match phases.iter_mut().find(|(n, ..)| *n == name) {
+                    Some((_, start, _)) => *start = Instant::now(),
+                    None => phases.push((name, Instant::now(), Duration::ZERO)),
                 }
-                eprintln!("TIP: Run on each crate separately for accurate results.");
-                eprintln!();
             }
+            ProgressEvent::PhaseDone { phase } => {
+                let name = phase.to_string();
+                if let Some((_, start, total)) = phases.iter_mut().find(|(n, ..)| *n == name) {
+                    *total += start.elapsed();
+                }
+            }
+            _ => {}
         }
+    });
+
+    let started = Instant::now();
+    let result = Deadmod::new(dir)
+        .all()
+        .with_cache(false)
+        .with_progress(progress)
+        .analyze()?;
+    let elapsed = started.elapsed();
+
+    let found_functions = result
+        .dead_functions
+        .iter()
+        .filter(|f| f.name.starts_with("dead_fn_"))
+        .count();
+    let test = SelfTest {
+        project: &project,
+        threads: rayon::current_num_threads(),
+        phases: phases
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(name, _, total)| (name.clone(), *total))
+            .collect(),
+        elapsed,
+        checks: vec![
+            ("functions", found_functions, project.dead_functions),
+            (
+                "constants",
+                result.dead_constants.len(),
+                project.dead_constants,
+            ),
+            ("variants", result.dead_enums.len(), project.dead_variants),
+            ("modules", result.dead_modules.len(), project.dead_modules),
+        ],
+    };
+    let paths = PathFormatter::default();
+    render_bench_selftest(&mut Stdout, &Render::new(args.json, &paths), &test)?;
+
+    let ok = test.ok();
+    if !ok {
+        bail!("Self-test found different dead code than was planted");
+    }
+    Ok(())
+}
+
+/// Handles `deadmod daemon [--socket FILE]`.
+fn run_daemon(args: &DaemonArgs) -> Result<()> {
+    let root = locate_crate_root(Path::new(&args.path))?;
+    let socket = args
+        .socket
+        .clone()
+        .unwrap_or_else(|| default_socket_path(&root));
+
+    eprintln!("INFO: deadmod daemon listening on {}", socket.display());
+    deadmod_core::daemon::serve(Daemon::new(analysis_builder(&root)), &socket)
+}
+
+/// Handles `deadmod serve [--host ADDR] [--port PORT]`.
+fn run_serve(args: &ServeArgs) -> Result<()> {
+    let root = locate_crate_root(Path::new(&args.path))?;
+    let addr = format!("{}:{}", args.host, args.port);
+
+    eprintln!("INFO: deadmod serving HTTP on http://{}", addr);
+    deadmod_core::http::serve(Daemon::new(analysis_builder(&root)), &addr)
+}
+
+/// Every detector, configured from deadmod.toml alone: the analysis behind
+/// the subcommands, the daemon and the HTTP server.
+fn analysis_builder(root: &Path) -> Deadmod {
+    Deadmod::new(root)
+        .all()
+        .with_detector_toggles(detector_toggles(root))
+        .with_hidden_policy(hidden_policy(None, root))
+        .with_pub_policy(pub_policy(None, root))
+        .with_exported_macro_policy(exported_macro_policy(None, root))
+        .with_liveness(liveness_rules(root))
+        .with_limits(scan_limits(None, root))
+}
+
+/// Handles `deadmod cycles`.
+fn run_cycles(args: &CyclesArgs) -> Result<()> {
+    let root = locate_crate_root(Path::new(&args.path))?;
+    let files = gather_files(&root, &scan_limits(None, &root), &Progress::none())?;
+    let mods = cache::incremental_parse(&root, &files, cache::load_cache(&root))?;
+
+    let cycles = find_module_cycles(&mods);
+    let paths = PathFormatter::default();
+    render_cycles(&mut Stdout, &Render::new(args.json, &paths), &cycles);
+
+    std::process::exit(if cycles.is_empty() { 0 } else { 1 });
+}
+
+/// Handles `deadmod analyze-graph <GRAPH>`: the module detectors and
+/// graph commands over an exported graph instead of a source tree.
+fn run_analyze_graph(args: &AnalyzeGraphArgs) -> Result<()> {
+    let json = fs::read_to_string(&args.graph)
+        .with_context(|| format!("Failed to read module graph {}", args.graph.display()))?;
+    let imported = import_module_graph(&json)
+        .with_context(|| format!("Failed to import module graph {}", args.graph.display()))?;
+    let mods = imported.modules;
+
+    let paths = PathFormatter::default();
+    let render = Render::new(args.json, &paths);
+    if args.metrics {
+        render_metrics(&mut Stdout, &render, &module_metrics(&mods));
+        return Ok(());
+    }
+    if args.cycles {
+        let cycles = find_module_cycles(&mods);
+        render_cycles(&mut Stdout, &render, &cycles);
+        std::process::exit(if cycles.is_empty() { 0 } else { 1 });
+    }
+    if let Some(ref rules) = args.layers {
+        let violations = check_layers(&mods, &LayerRules::load(rules)?);
+        render_layer_violations(&mut Stdout, &render, &violations);
+        std::process::exit(if violations.is_empty() { 0 } else { 1 });
+    }
+
+    let mut roots = if args.roots.is_empty() {
+        imported.roots
+    } else {
+        args.roots.clone()
+    };
+    roots.retain(|name| mods.contains_key(name));
+    let graph = build_graph(&mods);
+    let reachable = reachable_from_roots(&graph, roots.iter().map(String::as_str));
+    let mut dead = dead_names(&find_dead(&mods, &reachable));
+    dead.sort();
+
+    let reachable_owned: HashSet<String> = reachable.iter().map(|s| s.to_string()).collect();
+    let mut report = ModuleReport::new(&mods, &reachable_owned, &dead).with_entry_points(&roots);
+    if let Some(ref focus) = args.focus {
+        report = report.with_focus(focus, args.depth);
+    }
+    let mut specs = validate_output_specs(args.output.clone());
+    if specs.is_empty() || args.json {
+        specs.push(OutputSpec::stdout(if args.json {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Plain
+        }));
+    }
+    write_outputs(&specs, &report)?;
+
+    std::process::exit(if dead.is_empty() { 0 } else { 1 });
+}
+
+/// Handles `deadmod metrics [PATH]`.
+fn run_metrics(args: &MetricsArgs) -> Result<()> {
+    let root = locate_crate_root(Path::new(&args.path))?;
+    let files = gather_files(&root, &scan_limits(None, &root), &Progress::none())?;
+    let mods = cache::incremental_parse(&root, &files, cache::load_cache(&root))?;
+
+    let paths = PathFormatter::default();
+    render_metrics(
+        &mut Stdout,
+        &Render::new(args.json, &paths),
+        &module_metrics(&mods),
+    );
+    Ok(())
+}
+
+/// Handles `deadmod layers [--rules FILE]`.
+fn run_layers(args: &LayersArgs) -> Result<()> {
+    let root = locate_crate_root(Path::new(&args.path))?;
+
+    let rules = match args.rules {
+        Some(ref path) => LayerRules::load(path)?,
+        None => load_config(&root)?
+            .and_then(|cfg| cfg.layers)
+            .ok_or_else(|| {
+                anyhow!("No layer rules: pass --rules or add [layers] to deadmod.toml")
+            })?,
+    };
+
+    let files = gather_files(&root, &scan_limits(None, &root), &Progress::none())?;
+    let mods = cache::incremental_parse(&root, &files, cache::load_cache(&root))?;
+
+    let violations = check_layers(&mods, &rules);
+    let paths = PathFormatter::default();
+    render_layer_violations(&mut Stdout, &Render::new(args.json, &paths), &violations);
+
+    std::process::exit(if violations.is_empty() { 0 } else { 1 });
+}
+
+/// Notes the member crates when running on a workspace root.
+fn print_workspace_info(path: &Path) {
+    if !is_workspace_root(path) {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    let members: Vec<String> = entries
+        .flatten()
+        .filter(|e| {
+            let p = e.path();
+            p.is_dir() && p.join("Cargo.toml").exists()
+        })
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .collect();
+    if !members.is_empty() {
+        render_workspace_members(&mut Stderr, &members);
     }
 }
 
 /// Checks if a module name should be ignored based on patterns.
+/// `--doc-hidden` wins over deadmod.toml.
+fn hidden_policy(flag: Option<HiddenApiPolicy>, root: &Path) -> HiddenApiPolicy {
+    match flag {
+        Some(policy) => policy,
+        None => match load_config(root) {
+            Ok(cfg) => cfg.and_then(|c| c.doc_hidden).unwrap_or_default(),
+            Err(e) => {
+                warn_config_failed(&e);
+                HiddenApiPolicy::default()
+            }
+        },
+    }
+}
+
+/// Default project path: the package cargo points at (`CARGO_MANIFEST_DIR`,
+/// set by `cargo deadmod --manifest-path` and `cargo run`), else `.`.
+fn default_path() -> String {
+    std::env::var("CARGO_MANIFEST_DIR")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .unwrap_or_else(|| ".".to_string())
+}
+
+/// `--path-style` wins over `[output] paths` in deadmod.toml.
+fn path_formatter(flag: Option<PathStyle>, root: &Path) -> PathFormatter {
+    let style = flag.or_else(|| match load_config(root) {
+        Ok(cfg) => cfg.and_then(|c| c.output).and_then(|o| o.paths),
+        Err(e) => {
+            warn_config_failed(&e);
+            None
+        }
+    });
+    PathFormatter::new(style, root)
+}
+
+/// IDs recorded as false positives in the crate at `root`.
+fn suppressed_ids(root: &Path) -> HashSet<String> {
+    match Suppressions::load(root) {
+        Ok(suppressions) => suppressions.ids(),
+        Err(e) => {
+            eprintln!("[WARN] {:#}", e);
+            HashSet::new()
+        }
+    }
+}
+
+/// False positives past their review period, oldest first.
+/// `--review-suppressions` wins over `suppression_review_days` in deadmod.toml.
+fn suppressions_due(flag: Option<u32>, root: &Path) -> Vec<Suppression> {
+    let days = flag.or_else(|| match load_config(root) {
+        Ok(cfg) => cfg.and_then(|c| c.suppression_review_days),
+        Err(e) => {
+            warn_config_failed(&e);
+            None
+        }
+    });
+    let Some(days) = days else {
+        return Vec::new();
+    };
+    Suppressions::load(root)
+        .map(|suppressions| suppressions.due_now(days).into_iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// `--grace-period` wins over `grace_period_days` in deadmod.toml.
+fn grace_days(flag: Option<u32>, root: &Path) -> Option<u32> {
+    flag.or_else(|| match load_config(root) {
+        Ok(cfg) => cfg.and_then(|c| c.grace_period_days),
+        Err(e) => {
+            warn_config_failed(&e);
+            None
+        }
+    })
+}
+
+/// `--assume-pub-reachable` wins over deadmod.toml.
+fn pub_policy(flag: Option<PubPolicy>, root: &Path) -> PubPolicy {
+    match flag {
+        Some(policy) => policy,
+        None => match load_config(root) {
+            Ok(cfg) => cfg.and_then(|c| c.assume_pub_reachable).unwrap_or_default(),
+            Err(e) => {
+                warn_config_failed(&e);
+                PubPolicy::default()
+            }
+        },
+    }
+}
+
+/// Detectors left enabled by the `[detectors]` table of deadmod.toml.
+fn detector_toggles(root: &Path) -> DetectorToggles {
+    match load_config(root) {
+        Ok(cfg) => cfg.and_then(|c| c.detectors).unwrap_or_default(),
+        Err(e) => {
+            warn_config_failed(&e);
+            DetectorToggles::default()
+        }
+    }
+}
+
+/// `--exported-macros` wins over deadmod.toml.
+fn exported_macro_policy(flag: Option<ExportedMacroPolicy>, root: &Path) -> ExportedMacroPolicy {
+    match flag {
+        Some(policy) => policy,
+        None => match load_config(root) {
+            Ok(cfg) => cfg.and_then(|c| c.exported_macros).unwrap_or_default(),
+            Err(e) => {
+                warn_config_failed(&e);
+                ExportedMacroPolicy::default()
+            }
+        },
+    }
+}
+
+/// `--max-file-size`, `--max-files` and `--timeout` win over `[limits]` in deadmod.toml.
+fn scan_limits(cli: Option<&Cli>, root: &Path) -> Limits {
+    let flags = cli.map_or_else(Limits::default, |cli| Limits {
+        max_file_size: cli.max_file_size,
+        max_files: cli.max_files,
+        timeout: cli.timeout,
+    });
+    match load_config(root) {
+        Ok(cfg) => flags.or(cfg.and_then(|c| c.limits).unwrap_or_default()),
+        Err(e) => {
+            warn_config_failed(&e);
+            flags
+        }
+    }
+}
+
+/// Gather .rs files within `limits`, warning about each skipped one.
+fn gather_files(root: &Path, limits: &Limits, progress: &Progress) -> Result<Vec<PathBuf>> {
+    let (files, skipped) =
+        progress.phase(Phase::Scan, None, || gather_rs_files_limited(root, limits))?;
+    warn_skipped(&skipped);
+    Ok(files)
+}
+
+fn warn_skipped(skipped: &[Skipped]) {
+    for entry in skipped {
+        eprintln!("[WARN] Skipped {}", entry);
+    }
+}
+
+/// Exit with code 2 once `secs` have passed, so a pathological input cannot
+/// hang a CI job.
+fn start_watchdog(secs: u64) {
+    std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_secs(secs));
+        eprintln!(
+            "[ERROR] Analysis exceeded --timeout of {}s; narrow the input with --max-files or --max-file-size",
+            secs
+        );
+        std::process::exit(2);
+    });
+}
+
+/// Sources read only for their usages, with `--include-deps-usages`.
+fn dependency_sources(cli: &Cli, root: &Path) -> Vec<(PathBuf, String)> {
+    if !cli.include_deps_usages {
+        return Vec::new();
+    }
+    dependency_files(root)
+        .into_iter()
+        .filter_map(|file| {
+            fs::read_to_string(&file)
+                .ok()
+                .map(|content| (file, content))
+        })
+        .collect()
+}
+
+/// Default liveness rules plus the `[liveness]` section of deadmod.toml.
+fn liveness_rules(root: &Path) -> LivenessRules {
+    match load_config(root) {
+        Ok(cfg) => match cfg.and_then(|c| c.liveness) {
+            Some(extra) => LivenessRules::default().with_extra(extra),
+            None => LivenessRules::default(),
+        },
+        Err(e) => {
+            warn_config_failed(&e);
+            LivenessRules::default()
+        }
+    }
+}
+
 fn is_ignored(module: &str, ignore: &[String]) -> bool {
     ignore
         .iter()
@@ -178,30 +1707,26 @@ fn is_ignored(module: &str, ignore: &[String]) -> bool {
 /// - Paths containing `..` (parent directory traversal)
 /// - Paths with null bytes (injection attacks)
 ///
-/// Returns the validated PathBuf or an error.
-fn validate_output_path(path: &str) -> Result<PathBuf> {
+/// Returns the validated PathBuf or [`DeadmodError::OutputPathRejected`].
+fn validate_output_path(path: &str) -> Result<PathBuf, DeadmodError> {
+    let rejected = |reason: &str| DeadmodError::output_path_rejected(path, reason);
+
     // Security: Check for null bytes (path injection)
     if path.contains('\0') {
-        return Err(anyhow!("Output path contains null bytes"));
+        return Err(rejected("contains null bytes"));
     }
 
     let p = PathBuf::from(path);
 
     // Security: Reject absolute paths
     if p.is_absolute() {
-        return Err(anyhow!(
-            "Output path must be relative, not absolute: {}",
-            path
-        ));
+        return Err(rejected("must be relative, not absolute"));
     }
 
     // Security: Reject path traversal attempts
     for component in p.components() {
         if matches!(component, std::path::Component::ParentDir) {
-            return Err(anyhow!(
-                "Path traversal (..) not allowed in output paths: {}",
-                path
-            ));
+            return Err(rejected("path traversal (..) not allowed"));
         }
     }
 
@@ -210,16 +1735,98 @@ fn validate_output_path(path: &str) -> Result<PathBuf> {
     // would require the directory to exist
     let normalized = path.replace('\\', "/");
     if normalized.contains("/../") || normalized.starts_with("../") {
-        return Err(anyhow!(
-            "Path traversal attempt detected: {}",
-            path
-        ));
+        return Err(rejected("path traversal attempt detected"));
     }
 
     Ok(p)
 }
 
-fn main() -> Result<()> {
+/// Security: `path` validated as an output destination. Exits with code 2
+/// if it is rejected, like an invalid `--output`.
+fn safe_output_path(path: &str) -> PathBuf {
+    validate_output_path(path).unwrap_or_else(|e| {
+        render_output_path_error(&mut Stderr, &e);
+        std::process::exit(2);
+    })
+}
+
+/// The hint of the first [`DeadmodError`] in `err`'s chain that has one.
+fn error_hint(err: &anyhow::Error) -> Option<&'static str> {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<DeadmodError>()?.hint())
+}
+
+/// Prints `err` as a warning about deadmod.toml, with its hint.
+fn warn_config_failed(err: &anyhow::Error) {
+    eprintln!("[WARN] config load failed: {}", err);
+    if let Some(hint) = error_hint(err) {
+        eprintln!("[HINT] {}", hint);
+    }
+}
+
+/// Collects the module report outputs requested on the command line.
+///
+/// Legacy flags (`--json`, `--dot[-file]`, `--html[-file]`, `--html-pixi[-file]`)
+/// are translated into [`OutputSpec`]s and appended to the `--output` list.
+/// Without `--output` or a visualization flag, the text report (plain or JSON)
+/// is printed to stdout, followed by DOT when `--dot` is given.
+fn output_specs(cli: &Cli) -> Vec<OutputSpec> {
+    let mut specs = cli.output.clone();
+
+    let html = cli.html || cli.html_file.is_some();
+    let pixi = cli.html_pixi || cli.html_pixi_file.is_some();
+
+    if pixi {
+        specs.push(OutputSpec {
+            format: OutputFormat::Pixi,
+            path: cli.html_pixi_file.as_ref().map(PathBuf::from),
+        });
+    }
+    if html {
+        specs.push(OutputSpec {
+            format: OutputFormat::Html,
+            path: cli.html_file.as_ref().map(PathBuf::from),
+        });
+    }
+
+    if cli.output.is_empty() && !html && !pixi {
+        let format = if cli.json {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Plain
+        };
+        specs.push(OutputSpec::stdout(format));
+    } else if cli.json {
+        specs.push(OutputSpec::stdout(OutputFormat::Json));
+    }
+
+    if cli.dot || cli.dot_file.is_some() {
+        specs.push(OutputSpec {
+            format: OutputFormat::Dot,
+            path: cli.dot_file.as_ref().map(PathBuf::from),
+        });
+    }
+
+    specs
+}
+
+/// Security: Validates every file destination in the output specs.
+///
+/// Exits with code 2 on the first invalid path (see [`safe_output_path`]),
+/// matching the behavior of the individual `--*-file` flags.
+fn validate_output_specs(specs: Vec<OutputSpec>) -> Vec<OutputSpec> {
+    specs
+        .into_iter()
+        .map(|spec| match spec.path {
+            Some(ref path) => {
+                OutputSpec::file(spec.format, safe_output_path(&path.to_string_lossy()))
+            }
+            None => spec,
+        })
+        .collect()
+}
+
+fn main() {
     // Global panic guard - NASA-grade resilience
     std::panic::set_hook(Box::new(|info| {
         eprintln!("[PANIC] deadmod internal error: {}", info);
@@ -227,250 +1834,470 @@ fn main() -> Result<()> {
         eprintln!("[PANIC] Please report this at: https://github.com/anthropics/deadmod/issues");
     }));
 
-    // Initialize structured logging (JSON to stderr, respects RUST_LOG)
-    init_structured_logging();
+    let cli = Cli::parse();
+
+    // Diagnostic logs on stderr, filtered by RUST_LOG
+    init_logging(cli.log_format.unwrap_or_else(LogFormat::from_env));
+
+    // Dedicated rayon pool when a thread count is configured
+    let jobs = resolve_jobs(cli.jobs.map(NonZeroUsize::get));
+    if let Err(e) = run_with_threads(jobs, move || run(cli)).and_then(|result| result) {
+        eprintln!("Error: {:?}", e);
+        if let Some(hint) = error_hint(&e) {
+            eprintln!("[HINT] {}", hint);
+        }
+        std::process::exit(1);
+    }
+}
+
+fn run(mut cli: Cli) -> Result<()> {
+    cli.json |= cli.json_normalized;
+    let parse_mode = match (cli.no_cache, cli.fast) {
+        (false, _) => ParseMode::Cached,
+        (true, false) => ParseMode::Uncached,
+        (true, true) => ParseMode::Fast,
+    };
+    match cli.command.take() {
+        // `deadmod fix`: history operations, or the regular fix flow
+        Some(Command::Fix(args)) => {
+            if args.list || args.undo.is_some() {
+                return run_fix_history(&args);
+            }
+            cli.path = args.path;
+            cli.fix = !args.dry_run;
+            cli.fix_dry_run = args.dry_run;
+            cli.yes = args.yes;
+            cli.fix_verify = args.verify;
+            cli.verify_tests = args.verify_tests;
+            cli.write_fix_plan = args.write_plan;
+            cli.fix_plan = args.plan;
+            cli.plan_batch = args.batch;
+            cli.plan_step = args.step;
+        }
+        Some(Command::Cycles(args)) => return run_cycles(&args),
+        Some(Command::Metrics(args)) => return run_metrics(&args),
+        Some(Command::Layers(args)) => return run_layers(&args),
+        Some(Command::Callpath(args)) => return run_callpath(&args),
+        Some(Command::File(args)) => return run_file(&args),
+        Some(Command::Daemon(args)) => return run_daemon(&args),
+        Some(Command::Serve(args)) => return run_serve(&args),
+        Some(Command::Explain(args)) => return run_explain(&args),
+        Some(Command::CheckRemoval(args)) => return run_check_removal(&args),
+        Some(Command::Find(args)) => return run_find(&args),
+        Some(Command::MarkFalsePositive(args)) => return run_mark_false_positive(&args),
+        Some(Command::Warm(args)) => return run_warm(&args),
+        Some(Command::BenchSelftest(args)) => return run_bench_selftest(&args),
+        Some(Command::AnalyzeGraph(args)) => return run_analyze_graph(&args),
+        Some(Command::SelfCheck(args)) => return run_self_check(&args),
+        Some(Command::Archive(args)) => return run_archive(&args),
+        None => {}
+    }
 
-    let cli = Cli::parse();
+    // Hard stop for the modes below. `--group-by` skips late detectors on
+    // its own, and a fix is never interrupted halfway through.
+    if cli.group_by.is_none() && !(cli.fix || cli.fix_verify || cli.fix_plan.is_some()) {
+        let timeout = find_crate_root(Path::new(&cli.path))
+            .and_then(|root| scan_limits(Some(&cli), &root).timeout);
+        if let Some(secs) = timeout {
+            start_watchdog(secs);
+        }
+    }
+    let progress = cli.progress.progress();
 
     // Filesystem-based module discovery mode
     if cli.discover {
         let input_path = Path::new(&cli.path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
+        let root = locate_crate_root(input_path)?;
 
         let discovery = discover_modules(&root)?;
+        let paths = path_formatter(cli.path_style, &root);
+        render_discovery(
+            &mut Stdout,
+            &render_settings(&cli, &root, &paths),
+            &root,
+            &discovery,
+        )?;
 
-        if cli.json {
-            let clusters_json: Vec<_> = discovery.clusters.values().map(|c| {
-                serde_json::json!({
-                    "name": c.name,
-                    "path": c.path.display().to_string(),
-                    "relative_path": c.relative_path,
-                    "depth": c.depth,
-                    "has_mod_file": c.mod_file.is_some(),
-                    "modules": c.modules.iter().map(|m| &m.name).collect::<Vec<_>>(),
-                    "children": c.children,
-                    "parent": c.parent,
-                })
-            }).collect();
-
-            let json_output = serde_json::json!({
-                "file_count": discovery.file_count,
-                "cluster_count": discovery.clusters.len(),
-                "crate_roots": discovery.crate_roots.iter().map(|p| p.display().to_string()).collect::<Vec<_>>(),
-                "clusters": clusters_json,
-            });
-            println!("{}", serde_json::to_string_pretty(&json_output)?);
-        } else {
-            println!("=== Filesystem Module Discovery ===\n");
-            println!("Crate root: {}", root.display());
-            println!("Total .rs files: {}", discovery.file_count);
-            println!("Clusters (directories): {}\n", discovery.clusters.len());
-
-            // Print cluster hierarchy as tree
-            let tree = get_cluster_tree(&discovery);
-            println!("CLUSTER HIERARCHY:");
-            for (name, children) in &tree {
-                let indent = "  ".repeat(name.matches("::").count());
-                let icon = if children.is_empty() { "📄" } else { "📁" };
-                println!("{}{}  {}", indent, icon, name);
-
-                // Show modules in this cluster
-                if let Some(cluster) = discovery.clusters.get(name) {
-                    for module in &cluster.modules {
-                        let mod_indent = "  ".repeat(name.matches("::").count() + 1);
-                        let status = if module.is_crate_root { "🎯" } else { "  " };
-                        println!("{}{}  {}", mod_indent, status, module.name);
-                    }
-                }
-            }
+        return Ok(());
+    }
 
-            if !discovery.crate_roots.is_empty() {
-                println!("\nCRATE ROOTS:");
-                for root_file in &discovery.crate_roots {
-                    println!("  🎯 {}", root_file.display());
-                }
+    // Per-target (lib/bin) reachability matrix
+    if cli.per_target {
+        let input_path = Path::new(&cli.path);
+        print_workspace_info(input_path);
+        let root = locate_crate_root(input_path)?;
+
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mut mods = parse_crate(&root, &files, parse_mode, &progress)?;
+        mods.retain(|name, _| !is_ignored(name, &cli.ignore));
+
+        let graph = build_graph(&mods);
+        let roots = resolve_root_modules(&root, &cli.roots);
+        let mut roots: Vec<&str> = roots.iter().map(String::as_str).collect();
+        roots.sort_unstable();
+        let modules = module_reachability_matrix(&graph, roots.iter().copied());
+        // Targets trivially reach themselves: only report the other modules
+        let module_names = || {
+            mods.keys()
+                .map(String::as_str)
+                .filter(|name| !modules.targets.contains_key(*name))
+        };
+        let dead_modules = modules.unreached(module_names());
+        let partial_modules = modules.partially_reached(module_names());
+
+        // Functions are analyzed per target over the files that target reaches,
+        // so each binary's `main` stays distinct. The library exposes the pub
+        // API of every module it reaches; a binary only its own root file.
+        let liveness = liveness_rules(&root);
+        let mut all_funcs = Vec::new();
+        let mut file_calls = std::collections::HashMap::new();
+        let mut registered = HashSet::new();
+        for info in mods.values() {
+            if let Ok(content) = fs::read_to_string(&info.path) {
+                all_funcs.extend(extract_functions(&info.path, &content));
+                file_calls.insert(
+                    info.path.display().to_string(),
+                    extract_call_names(&info.path, &content),
+                );
+                registered.extend(liveness.registered_names(&content));
             }
         }
+        let policy = hidden_policy(cli.doc_hidden, &root);
+        let pub_entry_points = pub_policy(cli.assume_pub_reachable, &root).pub_is_reachable(&root);
+        let qualified = |f: &deadmod_core::FunctionInfo| {
+            join_module_path(&module_path_of(&root, Path::new(&f.file)), &f.full_path)
+        };
+        let file_of = |name: &String| mods.get(name).map(|info| info.path.display().to_string());
+        let functions =
+            ReachabilityMatrix::from_sets(modules.targets.iter().map(|(target, reached)| {
+                let target_files: HashSet<String> = reached.iter().filter_map(file_of).collect();
+                let entry_files: HashSet<String> = if target == "lib" {
+                    target_files.clone()
+                } else {
+                    file_of(target).into_iter().collect()
+                };
+                let funcs: Vec<_> = all_funcs
+                    .iter()
+                    .filter(|f| target_files.contains(&f.file))
+                    .cloned()
+                    .collect();
+                let reachable = FuncGraph::build(&funcs, &file_calls)
+                    .with_hidden_policy(policy)
+                    .with_pub_entry_points(pub_entry_points)
+                    .with_liveness(liveness.clone())
+                    .with_registered(registered.clone())
+                    .compute_reachable_from_files(&entry_files);
+                let used = funcs
+                    .iter()
+                    .filter(|f| reachable.contains(&f.full_path))
+                    .map(qualified)
+                    .collect();
+                (target.clone(), used)
+            }));
+        let func_paths: Vec<String> = all_funcs.iter().map(qualified).collect();
+        let dead_funcs = functions.unreached(func_paths.iter().map(String::as_str));
+        let partial_funcs = functions.partially_reached(func_paths.iter().map(String::as_str));
+
+        let matrix = TargetMatrix {
+            targets: modules.targets.keys().map(String::as_str).collect(),
+            dead_modules: &dead_modules,
+            partial_modules: &partial_modules,
+            dead_funcs: &dead_funcs,
+            partial_funcs: &partial_funcs,
+        };
+        let paths = path_formatter(cli.path_style, &root);
+        render_per_target(&mut Stdout, &render_settings(&cli, &root, &paths), &matrix)?;
+
+        let has_dead = !dead_modules.is_empty() || !dead_funcs.is_empty();
+        std::process::exit(if has_dead { 1 } else { 0 });
+    }
 
-        return Ok(());
+    // All detectors, grouped by owning module
+    if let Some(GroupBy::Module) = cli.group_by {
+        let input_path = Path::new(&cli.path);
+        print_workspace_info(input_path);
+        let root = locate_crate_root(input_path)?;
+
+        let mut builder = Deadmod::new(&root)
+            .all()
+            .with_detector_toggles(detector_toggles(&root))
+            .with_roots(cli.roots.iter().cloned())
+            .ignore_patterns(cli.ignore.iter().cloned())
+            .with_hidden_policy(hidden_policy(cli.doc_hidden, &root))
+            .with_pub_policy(pub_policy(cli.assume_pub_reachable, &root))
+            .with_exported_macro_policy(exported_macro_policy(cli.exported_macros, &root))
+            .with_liveness(liveness_rules(&root))
+            .with_limits(scan_limits(Some(&cli), &root))
+            .with_parse_mode(parse_mode)
+            .min_confidence(cli.min_confidence)
+            .collapse(!cli.no_collapse);
+        if let Some(days) = grace_days(cli.grace_period, &root) {
+            builder = builder.with_grace_period(days);
+        }
+        builder = builder.with_progress(progress.clone());
+        let due = suppressions_due(cli.review_suppressions, &root);
+        let mut result = builder.analyze()?;
+        warn_skipped(&result.skipped);
+        let paths = path_formatter(cli.path_style, &root);
+        result.localize_paths(&paths);
+        let tree = group_by_module(&result);
+        let delta = run_delta(
+            parse_mode,
+            &root,
+            "grouped",
+            RunCounts::from_result(&result),
+        );
+        render_grouped(
+            &mut Stdout,
+            &render_settings(&cli, &root, &paths),
+            &tree,
+            &result,
+            &due,
+            delta.as_ref(),
+        )?;
+
+        std::process::exit(if result.has_dead_code() { 1 } else { 0 });
     }
 
     // Dead function detection mode
-    if cli.dead_func {
+    if cli.dead_func || cli.dead_test_helpers {
         let input_path = Path::new(&cli.path);
         print_workspace_info(input_path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
+        let root = locate_crate_root(input_path)?;
+        let paths = path_formatter(cli.path_style, &root);
+        let grace = grace_days(cli.grace_period, &root).map(GracePeriod::new);
+        let due = suppressions_due(cli.review_suppressions, &root);
 
         // Gather files and parse modules
-        let files = gather_rs_files(&root)?;
-        let cached = cache::load_cache(&root);
-        let mods = cache::incremental_parse(&root, &files, cached)?;
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mods = parse_crate(&root, &files, parse_mode, &progress)?;
 
         // Extract functions and calls from all files
+        let liveness = liveness_rules(&root);
         let mut all_funcs = Vec::new();
         let mut file_calls = std::collections::HashMap::new();
+        let mut macro_mentions = HashSet::new();
+        let mut registered = HashSet::new();
 
+        let mut ignored = suppressed_ids(&root);
+        let detect = Phase::Detect("function analysis");
+        let extract_span = info_span!("extract", detector = %detect).entered();
+        progress.start(detect, Some(mods.len()));
         for info in mods.values() {
+            progress.advance(detect, 1);
             if let Ok(content) = fs::read_to_string(&info.path) {
+                ignored.extend(ignored_ids(&content));
+                registered.extend(liveness.registered_names(&content));
                 let funcs = extract_functions(&info.path, &content);
                 let calls = extract_call_names(&info.path, &content);
 
                 all_funcs.extend(funcs);
                 file_calls.insert(info.path.display().to_string(), calls);
+                macro_mentions.extend(extract_macro_mentions(&content));
             }
         }
+        // Functions called from dependency sources are entry points
+        for (file, content) in dependency_sources(&cli, &root) {
+            registered.extend(extract_call_names(&file, &content));
+        }
 
         // Build function graph and find dead functions
-        let graph = FuncGraph::build(&all_funcs, &file_calls);
-        let result = graph.analyze();
-
-        if cli.json {
-            let json_output = serde_json::json!({
-                "total_functions": result.stats.total_functions,
-                "reachable_functions": result.stats.reachable_count,
-                "dead_functions": result.stats.dead_count,
-                "public_dead": result.stats.public_dead,
-                "private_dead": result.stats.private_dead,
-                "dead": result.dead.iter().map(|f| {
-                    serde_json::json!({
-                        "name": f.name,
-                        "full_path": f.full_path,
-                        "visibility": f.visibility,
-                        "file": f.file,
-                        "is_method": f.is_method,
-                    })
-                }).collect::<Vec<_>>(),
-            });
-            println!("{}", serde_json::to_string_pretty(&json_output)?);
-        } else {
-            println!("=== Dead Function Analysis ===\n");
-            println!("Total functions: {}", result.stats.total_functions);
-            println!("Reachable:       {}", result.stats.reachable_count);
-            println!("Dead:            {}", result.stats.dead_count);
-            println!("  - Public:      {}", result.stats.public_dead);
-            println!("  - Private:     {}", result.stats.private_dead);
-
-            if !result.dead.is_empty() {
-                println!("\nDEAD FUNCTIONS:");
-                for func in &result.dead {
-                    let vis_marker = if func.visibility.starts_with("pub") {
-                        "[pub]"
-                    } else {
-                        "[priv]"
-                    };
-                    println!("  {} {} ({})", vis_marker, func.full_path, func.file);
-                }
-            } else {
-                println!("\nNo dead functions found.");
-            }
+        let mut graph = FuncGraph::build(&all_funcs, &file_calls)
+            .with_macro_mentions(macro_mentions)
+            .with_hidden_policy(hidden_policy(cli.doc_hidden, &root))
+            .with_pub_entry_points(
+                pub_policy(cli.assume_pub_reachable, &root).pub_is_reachable(&root),
+            )
+            .with_liveness(liveness)
+            .with_registered(registered)
+            .with_test_helpers(cli.dead_test_helpers);
+        if !cli.roots.is_empty() {
+            let root_files = cli
+                .roots
+                .iter()
+                .filter_map(|name| mods.get(name))
+                .map(|info| info.path.display().to_string())
+                .collect();
+            graph = graph.with_entry_files(root_files);
         }
-
-        std::process::exit(if result.dead.is_empty() { 0 } else { 1 });
+        let mut result = graph.analyze();
+        progress.done(detect);
+        drop(extract_span);
+        if !cli.dead_func {
+            result.dead.retain(|f| f.test_only);
+            result.dead_impls.clear();
+        }
+        let attr = Attribution::new(&root);
+        result
+            .dead
+            .retain(|f| !ignored.contains(&attr.function(f).id));
+        result
+            .dead_impls
+            .retain(|i| !ignored.contains(&attr.dead_impl(i).id));
+        let collapsed_in = collapsed_files(&cli, &root, &mods);
+        let collapsed = drop_in_files(&mut result.dead, &collapsed_in, |f| &f.file);
+        drop_in_files(&mut result.dead_impls, &collapsed_in, |i| &i.file);
+        let generated = generated_files(&mods);
+        drop_in_files(&mut result.dead, &generated, |f| &f.file);
+        drop_in_files(&mut result.dead_impls, &generated, |i| &i.file);
+        result.retain_confidence(cli.min_confidence);
+        let recent = take_recent(grace.as_ref(), &mut result.dead, &paths, |f| {
+            attr.function(f)
+        });
+        take_recent(grace.as_ref(), &mut result.dead_impls, &paths, |i| {
+            attr.dead_impl(i)
+        });
+        localize_kept(&mut result.kept, &paths);
+
+        let uncovered = match cli.coverage {
+            Some(ref path) => Some(reachable_but_uncovered(
+                &result,
+                &load_coverage(path)?,
+                &root,
+            )),
+            None => None,
+        };
+
+        let delta = run_delta(
+            parse_mode,
+            &root,
+            "functions",
+            RunCounts::new().with("dead_functions", result.dead.len()),
+        );
+        let findings = Findings {
+            attr: &attr,
+            collapsed,
+            kept: &result.kept,
+            recent: &recent,
+            due: &due,
+            delta: delta.as_ref(),
+        };
+        render_functions(
+            &mut Stdout,
+            &render_settings(&cli, &root, &paths),
+            &result,
+            uncovered.as_deref(),
+            &findings,
+        )?;
+
+        std::process::exit(if result.dead.is_empty() && collapsed == 0 {
+            0
+        } else {
+            1
+        });
     }
 
     // Dead trait method detection mode
     if cli.dead_traits {
         let input_path = Path::new(&cli.path);
         print_workspace_info(input_path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
+        let root = locate_crate_root(input_path)?;
+        let paths = path_formatter(cli.path_style, &root);
+        let grace = grace_days(cli.grace_period, &root).map(GracePeriod::new);
+        let due = suppressions_due(cli.review_suppressions, &root);
 
         // Gather files and parse modules
-        let files = gather_rs_files(&root)?;
-        let cached = cache::load_cache(&root);
-        let mods = cache::incremental_parse(&root, &files, cached)?;
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mods = parse_crate(&root, &files, parse_mode, &progress)?;
 
         // Extract traits and usages from all files
         let mut all_extractions = Vec::new();
         let mut all_usages = Vec::new();
+        let mut macro_mentions = HashSet::new();
 
+        let mut ignored = suppressed_ids(&root);
+        let detect = Phase::Detect("trait analysis");
+        let extract_span = info_span!("extract", detector = %detect).entered();
+        progress.start(detect, Some(mods.len()));
         for info in mods.values() {
+            progress.advance(detect, 1);
             if let Ok(content) = fs::read_to_string(&info.path) {
+                ignored.extend(ignored_ids(&content));
                 let extraction = extract_traits(&info.path, &content);
                 let usages = extract_trait_usages(&info.path, &content);
 
                 all_extractions.push(extraction);
                 all_usages.push(usages);
+                macro_mentions.extend(extract_macro_mentions(&content));
             }
         }
+        for (file, content) in dependency_sources(&cli, &root) {
+            all_usages.push(extract_trait_usages(&file, &content));
+        }
 
         // Build trait graph and find dead trait methods
-        let graph = TraitGraph::build(&all_extractions, &all_usages);
-        let result = graph.analyze();
-
-        if cli.json {
-            let json_output = serde_json::json!({
-                "total_trait_methods": result.stats.total_trait_methods,
-                "total_impl_methods": result.stats.total_impl_methods,
-                "dead_trait_methods": result.stats.dead_trait_method_count,
-                "dead_impl_methods": result.stats.dead_impl_method_count,
-                "required_methods": result.stats.required_methods,
-                "provided_methods": result.stats.provided_methods,
-                "dead_traits": result.dead_trait_methods.iter().map(|m| {
-                    serde_json::json!({
-                        "trait_name": m.trait_name,
-                        "method_name": m.method_name,
-                        "full_path": m.full_path,
-                        "visibility": m.visibility,
-                        "is_required": m.is_required,
-                        "file": m.file,
-                    })
-                }).collect::<Vec<_>>(),
-                "dead_impls": result.dead_impl_methods.iter().map(|m| {
-                    serde_json::json!({
-                        "trait_name": m.trait_name,
-                        "type_name": m.type_name,
-                        "method_name": m.method_name,
-                        "full_id": m.full_id,
-                        "file": m.file,
-                    })
-                }).collect::<Vec<_>>(),
+        let graph =
+            TraitGraph::build(&all_extractions, &all_usages).with_macro_mentions(macro_mentions);
+        let mut result = graph.analyze();
+        progress.done(detect);
+        drop(extract_span);
+        let attr = Attribution::new(&root);
+        result
+            .dead_trait_methods
+            .retain(|m| !ignored.contains(&attr.trait_method(m).id));
+        result
+            .dead_impl_methods
+            .retain(|m| !ignored.contains(&attr.impl_method(m).id));
+        result
+            .dead_impls
+            .retain(|i| !ignored.contains(&attr.dead_impl(i).id));
+        let collapsed_in = collapsed_files(&cli, &root, &mods);
+        let collapsed = drop_in_files(&mut result.dead_trait_methods, &collapsed_in, |m| &m.file)
+            + drop_in_files(&mut result.dead_impl_methods, &collapsed_in, |m| &m.file)
+            + drop_in_files(&mut result.dead_inherent_methods, &collapsed_in, |m| {
+                &m.file
             });
-            println!("{}", serde_json::to_string_pretty(&json_output)?);
-        } else {
-            println!("=== Dead Trait Method Analysis ===\n");
-            println!("Total trait methods:  {}", result.stats.total_trait_methods);
-            println!("  - Required:         {}", result.stats.required_methods);
-            println!("  - Provided:         {}", result.stats.provided_methods);
-            println!("Total impl methods:   {}", result.stats.total_impl_methods);
-            println!();
-            println!("Dead trait methods:   {}", result.stats.dead_trait_method_count);
-            println!("Dead impl methods:    {}", result.stats.dead_impl_method_count);
-
-            if !result.dead_trait_methods.is_empty() {
-                println!("\nDEAD TRAIT METHODS:");
-                for method in &result.dead_trait_methods {
-                    let req_marker = if method.is_required {
-                        "[required]"
-                    } else {
-                        "[provided]"
-                    };
-                    println!(
-                        "  {} {}::{} ({})",
-                        req_marker, method.trait_name, method.method_name, method.file
-                    );
-                }
-            }
-
-            if !result.dead_impl_methods.is_empty() {
-                println!("\nDEAD IMPL METHODS:");
-                for method in &result.dead_impl_methods {
-                    println!(
-                        "  impl {} for {} :: {} ({})",
-                        method.trait_name, method.type_name, method.method_name, method.file
-                    );
-                }
-            }
-
-            if result.dead_trait_methods.is_empty() && result.dead_impl_methods.is_empty() {
-                println!("\nNo dead trait methods found.");
-            }
-        }
+        drop_in_files(&mut result.dead_impls, &collapsed_in, |i| &i.file);
+        let generated = generated_files(&mods);
+        drop_in_files(&mut result.dead_trait_methods, &generated, |m| &m.file);
+        drop_in_files(&mut result.dead_impl_methods, &generated, |m| &m.file);
+        drop_in_files(&mut result.dead_inherent_methods, &generated, |m| &m.file);
+        drop_in_files(&mut result.dead_impls, &generated, |i| &i.file);
+        result.retain_confidence(cli.min_confidence);
+        let mut recent = take_recent(
+            grace.as_ref(),
+            &mut result.dead_trait_methods,
+            &paths,
+            |m| attr.trait_method(m),
+        );
+        recent.extend(take_recent(
+            grace.as_ref(),
+            &mut result.dead_impl_methods,
+            &paths,
+            |m| attr.impl_method(m),
+        ));
+        take_recent(grace.as_ref(), &mut result.dead_impls, &paths, |i| {
+            attr.dead_impl(i)
+        });
 
-        let has_dead =
-            !result.dead_trait_methods.is_empty() || !result.dead_impl_methods.is_empty();
+        localize_kept(&mut result.kept, &paths);
+        let delta = run_delta(
+            parse_mode,
+            &root,
+            "traits",
+            RunCounts::new()
+                .with("dead_trait_methods", result.dead_trait_methods.len())
+                .with("dead_impl_methods", result.dead_impl_methods.len()),
+        );
+        let findings = Findings {
+            attr: &attr,
+            collapsed,
+            kept: &result.kept,
+            recent: &recent,
+            due: &due,
+            delta: delta.as_ref(),
+        };
+        render_traits(
+            &mut Stdout,
+            &render_settings(&cli, &root, &paths),
+            &result,
+            &findings,
+        )?;
+
+        let has_dead = !result.dead_trait_methods.is_empty()
+            || !result.dead_impl_methods.is_empty()
+            || !result.dead_impls.is_empty()
+            || collapsed > 0;
         std::process::exit(if has_dead { 1 } else { 0 });
     }
 
@@ -478,20 +2305,27 @@ fn main() -> Result<()> {
     if cli.dead_generics {
         let input_path = Path::new(&cli.path);
         print_workspace_info(input_path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
+        let root = locate_crate_root(input_path)?;
+        let paths = path_formatter(cli.path_style, &root);
+        let grace = grace_days(cli.grace_period, &root).map(GracePeriod::new);
+        let due = suppressions_due(cli.review_suppressions, &root);
 
         // Gather files and parse modules
-        let files = gather_rs_files(&root)?;
-        let cached = cache::load_cache(&root);
-        let mods = cache::incremental_parse(&root, &files, cached)?;
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mods = parse_crate(&root, &files, parse_mode, &progress)?;
 
         // Extract declared generics and usages from all files
         let mut all_extractions = Vec::new();
         let mut all_usages = Vec::new();
 
+        let mut ignored = suppressed_ids(&root);
+        let detect = Phase::Detect("generic analysis");
+        let extract_span = info_span!("extract", detector = %detect).entered();
+        progress.start(detect, Some(mods.len()));
         for info in mods.values() {
+            progress.advance(detect, 1);
             if let Ok(content) = fs::read_to_string(&info.path) {
+                ignored.extend(ignored_ids(&content));
                 let extraction = extract_declared_generics(&info.path, &content);
                 let usage = extract_generic_usages(&info.path, &content);
 
@@ -502,386 +2336,608 @@ fn main() -> Result<()> {
 
         // Build generic graph and find dead generics
         let graph = GenericGraph::new(&all_extractions, &all_usages);
-        let result = graph.analyze();
-
-        if cli.json {
-            let json_output = serde_json::json!({
-                "total_declared_types": result.stats.total_declared_types,
-                "total_declared_lifetimes": result.stats.total_declared_lifetimes,
-                "total_declared_consts": result.stats.total_declared_consts,
-                "dead_types": result.stats.dead_types,
-                "dead_lifetimes": result.stats.dead_lifetimes,
-                "dead_consts": result.stats.dead_consts,
-                "dead": result.dead.iter().map(|d| {
-                    serde_json::json!({
-                        "name": d.name,
-                        "kind": format!("{:?}", d.kind),
-                        "parent": d.parent,
-                        "parent_kind": format!("{:?}", d.parent_kind),
-                        "file": d.file,
-                        "unused_bounds": d.unused_bounds,
-                    })
-                }).collect::<Vec<_>>(),
-            });
-            println!("{}", serde_json::to_string_pretty(&json_output)?);
-        } else {
-            println!("=== Dead Generic Parameter Analysis ===\n");
-            println!(
-                "Declared type parameters:     {}",
-                result.stats.total_declared_types
-            );
-            println!(
-                "Declared lifetimes:           {}",
-                result.stats.total_declared_lifetimes
-            );
-            println!(
-                "Declared const parameters:    {}",
-                result.stats.total_declared_consts
-            );
-            println!();
-            println!("Dead type parameters:         {}", result.stats.dead_types);
-            println!("Dead lifetimes:               {}", result.stats.dead_lifetimes);
-            println!("Dead const parameters:        {}", result.stats.dead_consts);
-
-            if !result.dead.is_empty() {
-                println!("\nDEAD GENERIC PARAMETERS:");
-                for d in &result.dead {
-                    let kind_str = match d.kind {
-                        GenericKind::Type => "type",
-                        GenericKind::Lifetime => "lifetime",
-                        GenericKind::Const => "const",
-                    };
-                    let bounds_str = if !d.unused_bounds.is_empty() {
-                        format!(" (bounds: {})", d.unused_bounds.join(", "))
-                    } else {
-                        String::new()
-                    };
-                    println!(
-                        "  [{}] {} in {}{} ({})",
-                        kind_str, d.name, d.parent, bounds_str, d.file
-                    );
-                }
-            } else {
-                println!("\nNo dead generic parameters found.");
-            }
-        }
+        let mut result = graph.analyze();
+        progress.done(detect);
+        drop(extract_span);
+        let attr = Attribution::new(&root);
+        result
+            .dead
+            .retain(|f| !ignored.contains(&attr.generic(f).id));
+        let collapsed_in = collapsed_files(&cli, &root, &mods);
+        let collapsed = drop_in_files(&mut result.dead, &collapsed_in, |f| &f.file);
+        drop_in_files(&mut result.dead, &generated_files(&mods), |f| &f.file);
+        result.retain_confidence(cli.min_confidence);
+        let recent = take_recent(grace.as_ref(), &mut result.dead, &paths, |g| {
+            attr.generic(g)
+        });
 
-        std::process::exit(if result.dead.is_empty() { 0 } else { 1 });
+        localize_kept(&mut result.kept, &paths);
+        let delta = run_delta(
+            parse_mode,
+            &root,
+            "generics",
+            RunCounts::new().with("dead_generics", result.dead.len()),
+        );
+        let findings = Findings {
+            attr: &attr,
+            collapsed,
+            kept: &result.kept,
+            recent: &recent,
+            due: &due,
+            delta: delta.as_ref(),
+        };
+        render_generics(
+            &mut Stdout,
+            &render_settings(&cli, &root, &paths),
+            &result,
+            &findings,
+        )?;
+
+        std::process::exit(if result.dead.is_empty() && collapsed == 0 {
+            0
+        } else {
+            1
+        });
     }
 
     // Dead macro detection mode
     if cli.dead_macros {
         let input_path = Path::new(&cli.path);
         print_workspace_info(input_path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
+        let root = locate_crate_root(input_path)?;
+        let paths = path_formatter(cli.path_style, &root);
+        let grace = grace_days(cli.grace_period, &root).map(GracePeriod::new);
+        let due = suppressions_due(cli.review_suppressions, &root);
 
         // Gather files and parse modules
-        let files = gather_rs_files(&root)?;
-        let cached = cache::load_cache(&root);
-        let mods = cache::incremental_parse(&root, &files, cached)?;
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mods = parse_crate(&root, &files, parse_mode, &progress)?;
 
         // Extract macros and usages from all files
         let mut all_macros = Vec::new();
         let mut all_usages = Vec::new();
+        let mut macro_mentions = HashSet::new();
 
+        let mut ignored = suppressed_ids(&root);
+        let detect = Phase::Detect("macro analysis");
+        let extract_span = info_span!("extract", detector = %detect).entered();
+        progress.start(detect, Some(mods.len()));
         for info in mods.values() {
+            progress.advance(detect, 1);
             if let Ok(content) = fs::read_to_string(&info.path) {
+                ignored.extend(ignored_ids(&content));
                 let macros = extract_macros(&info.path, &content);
                 let usages = extract_macro_usages(&info.path, &content);
 
                 all_macros.extend(macros);
                 all_usages.push(usages);
+                macro_mentions.extend(extract_macro_mentions(&content));
             }
         }
 
-        // Build macro graph and find dead macros
-        let graph = MacroGraph::new(all_macros, &all_usages);
-        let result = graph.analyze();
-
-        if cli.json {
-            let json_output = serde_json::json!({
-                "total_declared": result.stats.total_declared,
-                "exported_count": result.stats.exported_count,
-                "dead_count": result.stats.dead_count,
-                "dead_exported_count": result.stats.dead_exported_count,
-                "dead": result.dead.iter().map(|m| {
-                    serde_json::json!({
-                        "name": m.name,
-                        "exported": m.exported,
-                        "file": m.file,
-                        "module_path": m.module_path,
-                    })
-                }).collect::<Vec<_>>(),
-            });
-            println!("{}", serde_json::to_string_pretty(&json_output)?);
-        } else {
-            println!("=== Dead Macro Analysis ===\n");
-            println!("Total macros declared:  {}", result.stats.total_declared);
-            println!("  - Exported:           {}", result.stats.exported_count);
-            println!();
-            println!("Dead macros:            {}", result.stats.dead_count);
-            println!("  - Exported dead:      {}", result.stats.dead_exported_count);
-
-            if !result.dead.is_empty() {
-                println!("\nDEAD MACROS:");
-                for m in &result.dead {
-                    let export_marker = if m.exported { "[exported]" } else { "[local]" };
-                    println!("  {} {} ({})", export_marker, m.name, m.file);
+        // Exported macros are used from other crates (`#[macro_use] extern
+        // crate` in 2015 code): scan the rest of the workspace
+        let policy = exported_macro_policy(cli.exported_macros, &root);
+        let mut member_usages = Vec::new();
+        if policy == ExportedMacroPolicy::WorkspaceCheck && all_macros.iter().any(|m| m.exported) {
+            if let Some(ws_root) = find_workspace_root(&root) {
+                for member in find_all_crates(&ws_root)? {
+                    if member.canonicalize().ok().as_deref() == Some(root.as_path()) {
+                        continue;
+                    }
+                    for file in gather_rs_files(&member).unwrap_or_default() {
+                        if let Ok(content) = fs::read_to_string(&file) {
+                            member_usages.push(extract_macro_usages(&file, &content));
+                        }
+                    }
                 }
-            } else {
-                println!("\nNo dead macros found.");
             }
         }
 
-        std::process::exit(if result.dead.is_empty() { 0 } else { 1 });
+        for (file, content) in dependency_sources(&cli, &root) {
+            all_usages.push(extract_macro_usages(&file, &content));
+        }
+
+        // Build macro graph and find dead macros
+        let graph = MacroGraph::new(all_macros, &all_usages)
+            .with_macro_mentions(macro_mentions)
+            .with_exported_policy(policy)
+            .with_workspace_usages(&member_usages);
+        let mut result = graph.analyze();
+        progress.done(detect);
+        drop(extract_span);
+        let attr = Attribution::new(&root);
+        result
+            .dead
+            .retain(|f| !ignored.contains(&attr.macro_def(f).id));
+        let collapsed_in = collapsed_files(&cli, &root, &mods);
+        let collapsed = drop_in_files(&mut result.dead, &collapsed_in, |f| &f.file);
+        drop_in_files(&mut result.dead, &generated_files(&mods), |f| &f.file);
+        result.retain_confidence(cli.min_confidence);
+        let recent = take_recent(grace.as_ref(), &mut result.dead, &paths, |m| {
+            attr.macro_def(m)
+        });
+
+        localize_kept(&mut result.kept, &paths);
+        let delta = run_delta(
+            parse_mode,
+            &root,
+            "macros",
+            RunCounts::new().with("dead_macros", result.dead.len()),
+        );
+        let findings = Findings {
+            attr: &attr,
+            collapsed,
+            kept: &result.kept,
+            recent: &recent,
+            due: &due,
+            delta: delta.as_ref(),
+        };
+        render_macros(
+            &mut Stdout,
+            &render_settings(&cli, &root, &paths),
+            &result,
+            &findings,
+        )?;
+
+        std::process::exit(if result.dead.is_empty() && collapsed == 0 {
+            0
+        } else {
+            1
+        });
     }
 
     // Dead constants detection mode
     if cli.dead_constants {
         let input_path = Path::new(&cli.path);
         print_workspace_info(input_path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
+        let root = locate_crate_root(input_path)?;
+        let paths = path_formatter(cli.path_style, &root);
+        let grace = grace_days(cli.grace_period, &root).map(GracePeriod::new);
+        let due = suppressions_due(cli.review_suppressions, &root);
 
         // Gather files and parse modules
-        let files = gather_rs_files(&root)?;
-        let cached = cache::load_cache(&root);
-        let mods = cache::incremental_parse(&root, &files, cached)?;
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mods = parse_crate(&root, &files, parse_mode, &progress)?;
 
         // Extract constants and usages from all files
+        let liveness = liveness_rules(&root);
         let mut all_constants = Vec::new();
         let mut all_usages = Vec::new();
+        let mut macro_mentions = HashSet::new();
+        let mut registered = HashSet::new();
 
+        let mut ignored = suppressed_ids(&root);
+        let detect = Phase::Detect("constant analysis");
+        let extract_span = info_span!("extract", detector = %detect).entered();
+        progress.start(detect, Some(mods.len()));
         for info in mods.values() {
+            progress.advance(detect, 1);
             if let Ok(content) = fs::read_to_string(&info.path) {
+                ignored.extend(ignored_ids(&content));
+                registered.extend(liveness.registered_names(&content));
                 let constants = extract_constants(&info.path, &content);
                 let usages = extract_const_usage(&info.path, &content);
 
                 all_constants.extend(constants);
                 all_usages.push(usages);
+                macro_mentions.extend(extract_macro_mentions(&content));
             }
         }
 
-        // Build constant graph and find dead constants
-        let graph = ConstGraph::new(all_constants, &all_usages);
-        let result = graph.analyze();
-
-        if cli.json {
-            let json_output = serde_json::json!({
-                "total_declared": result.stats.total_declared,
-                "const_count": result.stats.const_count,
-                "static_count": result.stats.static_count,
-                "dead_count": result.stats.dead_count,
-                "dead_const_count": result.stats.dead_const_count,
-                "dead_static_count": result.stats.dead_static_count,
-                "dead": result.dead.iter().map(|c| {
-                    serde_json::json!({
-                        "name": c.name,
-                        "is_static": c.is_static,
-                        "visibility": c.visibility,
-                        "file": c.file,
-                        "module_path": c.module_path,
-                    })
-                }).collect::<Vec<_>>(),
-            });
-            println!("{}", serde_json::to_string_pretty(&json_output)?);
-        } else {
-            println!("=== Dead Constants/Statics Analysis ===\n");
-            println!("Total declared:     {}", result.stats.total_declared);
-            println!("  - Constants:      {}", result.stats.const_count);
-            println!("  - Statics:        {}", result.stats.static_count);
-            println!();
-            println!("Dead count:         {}", result.stats.dead_count);
-            println!("  - Dead consts:    {}", result.stats.dead_const_count);
-            println!("  - Dead statics:   {}", result.stats.dead_static_count);
-
-            if !result.dead.is_empty() {
-                println!("\nDEAD CONSTANTS/STATICS:");
-                for c in &result.dead {
-                    let kind = if c.is_static { "static" } else { "const" };
-                    let vis = if c.visibility == "pub" {
-                        "[pub]"
-                    } else {
-                        "[priv]"
-                    };
-                    println!("  {} {} {} ({})", vis, kind, c.name, c.file);
-                }
-            } else {
-                println!("\nNo dead constants/statics found.");
-            }
+        for (file, content) in dependency_sources(&cli, &root) {
+            all_usages.push(extract_const_usage(&file, &content));
         }
 
-        std::process::exit(if result.dead.is_empty() { 0 } else { 1 });
+        // Build constant graph and find dead constants
+        let graph = ConstGraph::new(all_constants, &all_usages)
+            .with_macro_mentions(macro_mentions)
+            .with_liveness(liveness)
+            .with_registered(registered);
+        let mut result = graph.analyze();
+        progress.done(detect);
+        drop(extract_span);
+        let attr = Attribution::new(&root);
+        result
+            .dead
+            .retain(|f| !ignored.contains(&attr.constant(f).id));
+        let collapsed_in = collapsed_files(&cli, &root, &mods);
+        let collapsed = drop_in_files(&mut result.dead, &collapsed_in, |f| &f.file);
+        drop_in_files(&mut result.dead, &generated_files(&mods), |f| &f.file);
+        result.retain_confidence(cli.min_confidence);
+        let recent = take_recent(grace.as_ref(), &mut result.dead, &paths, |c| {
+            attr.constant(c)
+        });
+
+        localize_kept(&mut result.kept, &paths);
+        let delta = run_delta(
+            parse_mode,
+            &root,
+            "constants",
+            RunCounts::new().with("dead_constants", result.dead.len()),
+        );
+        let findings = Findings {
+            attr: &attr,
+            collapsed,
+            kept: &result.kept,
+            recent: &recent,
+            due: &due,
+            delta: delta.as_ref(),
+        };
+        render_constants(
+            &mut Stdout,
+            &render_settings(&cli, &root, &paths),
+            &result,
+            &findings,
+        )?;
+
+        std::process::exit(if result.dead.is_empty() && collapsed == 0 {
+            0
+        } else {
+            1
+        });
     }
 
     // Dead enum variant detection mode
     if cli.dead_variants {
         let input_path = Path::new(&cli.path);
         print_workspace_info(input_path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
+        let root = locate_crate_root(input_path)?;
+        let paths = path_formatter(cli.path_style, &root);
+        let grace = grace_days(cli.grace_period, &root).map(GracePeriod::new);
+        let due = suppressions_due(cli.review_suppressions, &root);
 
         // Gather files and parse modules
-        let files = gather_rs_files(&root)?;
-        let cached = cache::load_cache(&root);
-        let mods = cache::incremental_parse(&root, &files, cached)?;
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mods = parse_crate(&root, &files, parse_mode, &progress)?;
 
         // Extract variants and usages from all files
         let mut all_variants = Vec::new();
         let mut all_usages = Vec::new();
+        let mut macro_mentions = HashSet::new();
 
+        let mut ignored = suppressed_ids(&root);
+        let detect = Phase::Detect("enum analysis");
+        let extract_span = info_span!("extract", detector = %detect).entered();
+        progress.start(detect, Some(mods.len()));
         for info in mods.values() {
+            progress.advance(detect, 1);
             if let Ok(content) = fs::read_to_string(&info.path) {
+                ignored.extend(ignored_ids(&content));
                 let variants = extract_variants(&info.path, &content);
                 let usages = extract_variant_usage(&info.path, &content);
 
                 all_variants.extend(variants);
                 all_usages.push(usages);
+                macro_mentions.extend(extract_macro_mentions(&content));
             }
         }
 
+        for (file, content) in dependency_sources(&cli, &root) {
+            all_usages.push(extract_variant_usage(&file, &content));
+        }
+
         // Build enum graph and find dead variants
-        let graph = EnumGraph::new(all_variants, &all_usages);
-        let result = graph.analyze();
-
-        if cli.json {
-            let json_output = serde_json::json!({
-                "total_variants": result.stats.total_variants,
-                "total_enums": result.stats.total_enums,
-                "dead_variant_count": result.stats.dead_variant_count,
-                "dead_enum_count": result.stats.dead_enum_count,
-                "dead": result.dead.iter().map(|v| {
-                    serde_json::json!({
-                        "enum_name": v.enum_name,
-                        "variant_name": v.variant_name,
-                        "full_name": v.full_name,
-                        "visibility": v.visibility,
-                        "file": v.file,
-                    })
-                }).collect::<Vec<_>>(),
-            });
-            println!("{}", serde_json::to_string_pretty(&json_output)?);
-        } else {
-            println!("=== Dead Enum Variant Analysis ===\n");
-            println!("Total enums:        {}", result.stats.total_enums);
-            println!("Total variants:     {}", result.stats.total_variants);
-            println!();
-            println!("Dead variants:      {}", result.stats.dead_variant_count);
-            println!("Fully dead enums:   {}", result.stats.dead_enum_count);
-
-            if !result.dead.is_empty() {
-                println!("\nDEAD ENUM VARIANTS:");
-                for v in &result.dead {
-                    let vis = if v.visibility == "pub" {
-                        "[pub]"
-                    } else {
-                        "[priv]"
-                    };
-                    println!("  {} {} ({})", vis, v.full_name, v.file);
-                }
-            } else {
-                println!("\nNo dead enum variants found.");
-            }
+        let graph = EnumGraph::new(all_variants, &all_usages).with_macro_mentions(macro_mentions);
+        let mut result = graph.analyze();
+        progress.done(detect);
+        drop(extract_span);
+        let attr = Attribution::new(&root);
+        result
+            .dead
+            .retain(|f| !ignored.contains(&attr.variant(f).id));
+        let collapsed_in = collapsed_files(&cli, &root, &mods);
+        let collapsed = drop_in_files(&mut result.dead, &collapsed_in, |f| &f.file);
+        drop_in_files(&mut result.dead, &generated_files(&mods), |f| &f.file);
+        result.retain_confidence(cli.min_confidence);
+        let recent = take_recent(grace.as_ref(), &mut result.dead, &paths, |v| {
+            attr.variant(v)
+        });
+
+        // Remove the variants together with the match arms naming them
+        if cli.fix || cli.fix_dry_run {
+            let options = FixOptions::new().dry_run(cli.fix_dry_run).force(cli.yes);
+            fix_dead_variants(&root, &files, &result.dead, &options)?;
+            return Ok(());
         }
 
-        std::process::exit(if result.dead.is_empty() { 0 } else { 1 });
+        localize_kept(&mut result.kept, &paths);
+        let delta = run_delta(
+            parse_mode,
+            &root,
+            "variants",
+            RunCounts::new().with("dead_variants", result.dead.len()),
+        );
+        let findings = Findings {
+            attr: &attr,
+            collapsed,
+            kept: &result.kept,
+            recent: &recent,
+            due: &due,
+            delta: delta.as_ref(),
+        };
+        render_variants(
+            &mut Stdout,
+            &render_settings(&cli, &root, &paths),
+            &result,
+            &findings,
+        )?;
+
+        std::process::exit(if result.dead.is_empty() && collapsed == 0 {
+            0
+        } else {
+            1
+        });
     }
 
     // Dead match arm detection mode
     if cli.dead_match_arms {
         let input_path = Path::new(&cli.path);
         print_workspace_info(input_path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
+        let root = locate_crate_root(input_path)?;
+        let paths = path_formatter(cli.path_style, &root);
+        let grace = grace_days(cli.grace_period, &root).map(GracePeriod::new);
+        let due = suppressions_due(cli.review_suppressions, &root);
 
         // Gather files and parse modules
-        let files = gather_rs_files(&root)?;
-        let cached = cache::load_cache(&root);
-        let mods = cache::incremental_parse(&root, &files, cached)?;
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mods = parse_crate(&root, &files, parse_mode, &progress)?;
 
         // Extract match arms and usages from all files
         let mut all_arms = Vec::new();
         let mut total_match_count = 0;
         let mut all_usages = Vec::new();
+        let mut declared = Vec::new();
 
+        let mut ignored = suppressed_ids(&root);
+        let detect = Phase::Detect("match arm analysis");
+        let extract_span = info_span!("extract", detector = %detect).entered();
+        progress.start(detect, Some(mods.len()));
         for info in mods.values() {
+            progress.advance(detect, 1);
             if let Ok(content) = fs::read_to_string(&info.path) {
+                ignored.extend(ignored_ids(&content));
                 let extraction = extract_match_arms(&info.path, &content);
                 all_arms.extend(extraction.arms);
                 total_match_count += extraction.match_count;
 
                 let usages = extract_match_usages(&info.path, &content);
                 all_usages.push(usages);
+                declared.extend(extract_variants(&info.path, &content));
             }
         }
 
-        // Build match graph and find dead arms
-        let graph = MatchGraph::new(all_arms, total_match_count, &all_usages);
-        let result = graph.analyze();
-
-        if cli.json {
-            let json_output = serde_json::json!({
-                "total_match_expressions": result.stats.total_match_expressions,
-                "total_arms": result.stats.total_arms,
-                "wildcard_count": result.stats.wildcard_count,
-                "dead_arm_count": result.stats.dead_arm_count,
-                "masked_arm_count": result.stats.masked_arm_count,
-                "dead_arms": result.dead_arms.iter().map(|a| {
-                    serde_json::json!({
-                        "pattern": a.pattern,
-                        "reason": format!("{:?}", a.reason),
-                        "file": a.file,
-                    })
-                }).collect::<Vec<_>>(),
-            });
-            println!("{}", serde_json::to_string_pretty(&json_output)?);
+        // Build match graph, link arms to the crate's enums and find dead arms
+        let graph = MatchGraph::new(all_arms, total_match_count, &all_usages)
+            .correlate_with_enums(&declared);
+        let mut result = graph.analyze();
+        progress.done(detect);
+        drop(extract_span);
+        let attr = Attribution::new(&root);
+        result
+            .dead_arms
+            .retain(|f| !ignored.contains(&attr.match_arm(f).id));
+        let collapsed_in = collapsed_files(&cli, &root, &mods);
+        let collapsed = drop_in_files(&mut result.dead_arms, &collapsed_in, |f| &f.file);
+        drop_in_files(&mut result.dead_arms, &generated_files(&mods), |f| &f.file);
+        result.retain_confidence(cli.min_confidence);
+        let recent = take_recent(grace.as_ref(), &mut result.dead_arms, &paths, |a| {
+            attr.match_arm(a)
+        });
+
+        localize_kept(&mut result.kept, &paths);
+        let delta = run_delta(
+            parse_mode,
+            &root,
+            "match_arms",
+            RunCounts::new().with("dead_match_arms", result.dead_arms.len()),
+        );
+        let findings = Findings {
+            attr: &attr,
+            collapsed,
+            kept: &result.kept,
+            recent: &recent,
+            due: &due,
+            delta: delta.as_ref(),
+        };
+        render_match_arms(
+            &mut Stdout,
+            &render_settings(&cli, &root, &paths),
+            &result,
+            &findings,
+        )?;
+
+        std::process::exit(if result.dead_arms.is_empty() && collapsed == 0 {
+            0
         } else {
-            println!("=== Dead Match Arm Analysis ===\n");
-            println!("Total match expressions: {}", result.stats.total_match_expressions);
-            println!("Total arms:              {}", result.stats.total_arms);
-            println!("Wildcard arms:           {}", result.stats.wildcard_count);
-            println!();
-            println!("Dead/Masked arms:        {}", result.stats.dead_arm_count);
-
-            if !result.dead_arms.is_empty() {
-                println!("\nDEAD/MASKED MATCH ARMS:");
-                for arm in &result.dead_arms {
-                    let reason = match arm.reason {
-                        DeadArmReason::NeverUsed => "[never-used]",
-                        DeadArmReason::MaskedByWildcard => "[masked]",
-                        DeadArmReason::NonFinalWildcard => "[non-final-wildcard]",
-                    };
-                    println!("  {} {} ({})", reason, arm.pattern, arm.file);
-                }
-            } else {
-                println!("\nNo dead match arms found.");
+            1
+        });
+    }
+
+    // Dead parameter detection mode
+    if cli.dead_params {
+        let input_path = Path::new(&cli.path);
+        print_workspace_info(input_path);
+        let root = locate_crate_root(input_path)?;
+        let paths = path_formatter(cli.path_style, &root);
+        let grace = grace_days(cli.grace_period, &root).map(GracePeriod::new);
+        let due = suppressions_due(cli.review_suppressions, &root);
+
+        // Gather files and parse modules
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mods = parse_crate(&root, &files, parse_mode, &progress)?;
+
+        // Extract parameters and body reads from all files
+        let mut all_extractions = Vec::new();
+
+        let mut ignored = suppressed_ids(&root);
+        let detect = Phase::Detect("parameter analysis");
+        let extract_span = info_span!("extract", detector = %detect).entered();
+        progress.start(detect, Some(mods.len()));
+        for info in mods.values() {
+            progress.advance(detect, 1);
+            if let Ok(content) = fs::read_to_string(&info.path) {
+                ignored.extend(ignored_ids(&content));
+                all_extractions.push(extract_params(&info.path, &content));
+            }
+        }
+
+        let mut result = ParamGraph::new(&all_extractions).analyze();
+        progress.done(detect);
+        drop(extract_span);
+        let attr = Attribution::new(&root);
+        result.dead.retain(|f| !ignored.contains(&attr.param(f).id));
+        let collapsed_in = collapsed_files(&cli, &root, &mods);
+        let collapsed = drop_in_files(&mut result.dead, &collapsed_in, |f| &f.file);
+        drop_in_files(&mut result.dead, &generated_files(&mods), |f| &f.file);
+        result.retain_confidence(cli.min_confidence);
+        let recent = take_recent(grace.as_ref(), &mut result.dead, &paths, |p| attr.param(p));
+
+        // Rename unused parameters to `_name`; callers are left untouched
+        if cli.fix || cli.fix_dry_run {
+            let options = FixOptions::new().dry_run(cli.fix_dry_run).force(cli.yes);
+            fix_dead_params(&root, &result.dead, &options)?;
+            return Ok(());
+        }
+
+        localize_kept(&mut result.kept, &paths);
+        let delta = run_delta(
+            parse_mode,
+            &root,
+            "params",
+            RunCounts::new().with("dead_params", result.dead.len()),
+        );
+        let findings = Findings {
+            attr: &attr,
+            collapsed,
+            kept: &result.kept,
+            recent: &recent,
+            due: &due,
+            delta: delta.as_ref(),
+        };
+        render_params(
+            &mut Stdout,
+            &render_settings(&cli, &root, &paths),
+            &result,
+            &findings,
+        )?;
+
+        std::process::exit(if result.dead.is_empty() && collapsed == 0 {
+            0
+        } else {
+            1
+        });
+    }
+
+    // Dead field detection mode
+    if cli.dead_fields {
+        let input_path = Path::new(&cli.path);
+        print_workspace_info(input_path);
+        let root = locate_crate_root(input_path)?;
+        let paths = path_formatter(cli.path_style, &root);
+        let grace = grace_days(cli.grace_period, &root).map(GracePeriod::new);
+        let due = suppressions_due(cli.review_suppressions, &root);
+
+        // Gather files and parse modules
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mods = parse_crate(&root, &files, parse_mode, &progress)?;
+
+        // Extract declared fields and classify field accesses in all files
+        let mut all_extractions = Vec::new();
+        let mut all_usages = Vec::new();
+
+        let mut ignored = suppressed_ids(&root);
+        let detect = Phase::Detect("field analysis");
+        let extract_span = info_span!("extract", detector = %detect).entered();
+        progress.start(detect, Some(mods.len()));
+        for info in mods.values() {
+            progress.advance(detect, 1);
+            if let Ok(content) = fs::read_to_string(&info.path) {
+                ignored.extend(ignored_ids(&content));
+                all_extractions.push(extract_fields(&info.path, &content));
+                all_usages.push(extract_field_usages(&info.path, &content));
             }
         }
+        for (file, content) in dependency_sources(&cli, &root) {
+            all_usages.push(extract_field_usages(&file, &content));
+        }
 
-        std::process::exit(if result.dead_arms.is_empty() { 0 } else { 1 });
+        let mut result = FieldGraph::new(&all_extractions, &all_usages).analyze();
+        progress.done(detect);
+        drop(extract_span);
+        let attr = Attribution::new(&root);
+        result.dead.retain(|f| !ignored.contains(&attr.field(f).id));
+        let collapsed_in = collapsed_files(&cli, &root, &mods);
+        let collapsed = drop_in_files(&mut result.dead, &collapsed_in, |f| &f.file);
+        drop_in_files(&mut result.dead, &generated_files(&mods), |f| &f.file);
+        result.retain_confidence(cli.min_confidence);
+        let recent = take_recent(grace.as_ref(), &mut result.dead, &paths, |f| attr.field(f));
+        result.recount();
+
+        localize_kept(&mut result.kept, &paths);
+        let delta = run_delta(
+            parse_mode,
+            &root,
+            "fields",
+            RunCounts::new().with("dead_fields", result.dead.len()),
+        );
+        let findings = Findings {
+            attr: &attr,
+            collapsed,
+            kept: &result.kept,
+            recent: &recent,
+            due: &due,
+            delta: delta.as_ref(),
+        };
+        render_fields(
+            &mut Stdout,
+            &render_settings(&cli, &root, &paths),
+            &result,
+            &findings,
+        )?;
+
+        std::process::exit(if result.dead.is_empty() && collapsed == 0 {
+            0
+        } else {
+            1
+        });
+    }
+
+    // Unused dependency detection mode
+    if cli.dead_deps {
+        let input_path = Path::new(&cli.path);
+        // A workspace root is checked as a whole, members included
+        let root = if is_workspace_root(input_path) {
+            input_path.canonicalize()?
+        } else {
+            locate_crate_root(input_path)?
+        };
+        let paths = path_formatter(cli.path_style, &root);
+
+        let result = find_dead_dependencies(&root, parse_mode)?;
+        render_dead_deps(&mut Stdout, &render_settings(&cli, &root, &paths), &result)?;
+
+        std::process::exit(if result.dead.is_empty() { 0 } else { 1 });
     }
 
     // Module dependency graph for visualizer
     if cli.modgraph_viz {
         let input_path = Path::new(&cli.path);
         print_workspace_info(input_path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
+        let root = locate_crate_root(input_path)?;
 
         // Gather files and parse modules
-        let files = gather_rs_files(&root)?;
-        let cached = cache::load_cache(&root);
-        let mods = cache::incremental_parse(&root, &files, cached)?;
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mods = parse_crate(&root, &files, parse_mode, &progress)?;
 
         // Build dependency graph and find reachable modules
         let graph = build_graph(&mods);
-        let roots = find_root_modules(&root);
+        let roots = resolve_root_modules(&root, &cli.roots);
         let reachable = reachable_from_roots(&graph, roots.iter().map(String::as_str));
 
         // Output visualizer-compatible JSON
-        let json = module_graph_to_visualizer_json(&mods, &reachable);
-        println!("{}", serde_json::to_string_pretty(&json)?);
+        let json = modgraph_json(&cli, &root, &mods, &roots, &reachable)?;
+        Stdout.line(&serde_json::to_string_pretty(&json)?);
 
         std::process::exit(0);
     }
@@ -889,22 +2945,19 @@ fn main() -> Result<()> {
     // Export module graph to file
     if let Some(ref path) = cli.export_modgraph {
         // Security: Validate output path
-        let safe_path = validate_output_path(path)
-            .with_context(|| format!("Invalid output path: {}", path))?;
+        let safe_path = safe_output_path(path);
 
         let input_path = Path::new(&cli.path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
+        let root = locate_crate_root(input_path)?;
 
-        let files = gather_rs_files(&root)?;
-        let cached = cache::load_cache(&root);
-        let mods = cache::incremental_parse(&root, &files, cached)?;
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mods = parse_crate(&root, &files, parse_mode, &progress)?;
 
         let graph = build_graph(&mods);
-        let roots = find_root_modules(&root);
+        let roots = resolve_root_modules(&root, &cli.roots);
         let reachable = reachable_from_roots(&graph, roots.iter().map(String::as_str));
 
-        let json = module_graph_to_visualizer_json(&mods, &reachable);
+        let json = modgraph_json(&cli, &root, &mods, &roots, &reachable)?;
         let serialized = serde_json::to_string_pretty(&json)
             .context("Failed to serialize module graph to JSON")?;
 
@@ -918,135 +2971,164 @@ fn main() -> Result<()> {
     // Export function callgraph to file
     if let Some(ref path) = cli.export_callgraph {
         // Security: Validate output path
-        let safe_path = validate_output_path(path)
-            .with_context(|| format!("Invalid output path: {}", path))?;
+        let safe_path = safe_output_path(path);
 
         let input_path = Path::new(&cli.path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
-
-        let files = gather_rs_files(&root)?;
-        let cached = cache::load_cache(&root);
-        let mods = cache::incremental_parse(&root, &files, cached)?;
-
-        let mut all_functions = Vec::new();
-        let mut usage_map = std::collections::HashMap::new();
-
-        for info in mods.values() {
-            if let Ok(content) = fs::read_to_string(&info.path) {
-                let functions = extract_callgraph_functions(&info.path, &content);
-                let usages = extract_call_usages(&info.path, &content);
-                all_functions.extend(functions);
-                usage_map.insert(info.path.display().to_string(), usages);
-            }
-        }
+        let root = locate_crate_root(input_path)?;
 
-        let graph = CallGraph::build(&all_functions, &usage_map);
-        let json = graph.to_visualizer_json();
-        let serialized = serde_json::to_string_pretty(&json)
-            .context("Failed to serialize callgraph to JSON")?;
+        let graph = build_call_graph(&root, parse_mode)?;
+        let json = serde_json::to_value(focused_visualizer_graph(&cli, &graph)?)?;
+        let serialized =
+            serde_json::to_string_pretty(&json).context("Failed to serialize callgraph to JSON")?;
 
         fs::write(&safe_path, &serialized)
             .with_context(|| format!("Failed to write callgraph to {}", safe_path.display()))?;
 
-        eprintln!("[deadmod] Function callgraph exported → {}", safe_path.display());
+        eprintln!(
+            "[deadmod] Function callgraph exported → {}",
+            safe_path.display()
+        );
+        std::process::exit(0);
+    }
+
+    // Dead code badge
+    if let Some(ref path) = cli.badge_json {
+        let safe_path = safe_output_path(path);
+
+        let root = locate_crate_root(Path::new(&cli.path))?;
+        let result = Deadmod::new(&root)
+            .include_functions(true)
+            .with_roots(cli.roots.iter().cloned())
+            .ignore_patterns(cli.ignore.iter().cloned())
+            .with_hidden_policy(hidden_policy(cli.doc_hidden, &root))
+            .with_pub_policy(pub_policy(cli.assume_pub_reachable, &root))
+            .with_liveness(liveness_rules(&root))
+            .with_limits(scan_limits(Some(&cli), &root))
+            .with_parse_mode(parse_mode)
+            .min_confidence(cli.min_confidence)
+            .collapse(true)
+            .with_progress(progress.clone())
+            .analyze()?;
+        warn_skipped(&result.skipped);
+
+        let badge = Badge::from_result(&result, cli.badge_red_at);
+        badge.save(&safe_path)?;
+        eprintln!(
+            "[deadmod] Badge \"{}: {}\" written → {}",
+            badge.label,
+            badge.message,
+            safe_path.display()
+        );
         std::process::exit(0);
     }
 
     // Export combined graph (modules + functions) to file
     if let Some(ref path) = cli.export_combined {
+        if cli.focus.is_some() {
+            bail!("--focus does not apply to --export-combined");
+        }
         // Security: Validate output path
-        let safe_path = validate_output_path(path)
-            .with_context(|| format!("Invalid output path: {}", path))?;
+        let safe_path = safe_output_path(path);
 
         let input_path = Path::new(&cli.path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
+        let root = locate_crate_root(input_path)?;
 
-        let files = gather_rs_files(&root)?;
-        let cached = cache::load_cache(&root);
-        let mods = cache::incremental_parse(&root, &files, cached)?;
+        let files = gather_files(&root, &scan_limits(Some(&cli), &root), &progress)?;
+        let mods = parse_crate(&root, &files, parse_mode, &progress)?;
 
         // Build module graph
         let mod_graph = build_graph(&mods);
-        let roots = find_root_modules(&root);
+        let roots = resolve_root_modules(&root, &cli.roots);
         let reachable = reachable_from_roots(&mod_graph, roots.iter().map(String::as_str));
-        let module_graph_json = module_graph_to_visualizer_json(&mods, &reachable);
 
-        // Build function callgraph
-        let mut all_functions = Vec::new();
-        let mut usage_map = std::collections::HashMap::new();
-        for info in mods.values() {
-            if let Ok(content) = fs::read_to_string(&info.path) {
-                let functions = extract_callgraph_functions(&info.path, &content);
-                let usages = extract_call_usages(&info.path, &content);
-                all_functions.extend(functions);
-                usage_map.insert(info.path.display().to_string(), usages);
-            }
-        }
-        let func_graph = CallGraph::build(&all_functions, &usage_map);
-        let function_graph_json = func_graph.to_visualizer_json();
-
-        // Combine both graphs
-        let combined = serde_json::json!({
-            "module_graph": module_graph_json,
-            "function_graph": function_graph_json,
-        });
+        // Build function callgraph and link both levels
+        let func_graph = build_call_graph(&root, parse_mode)?;
+        let combined = combined_graph_json(&mods, &reachable, &func_graph);
 
         let serialized = serde_json::to_string_pretty(&combined)
             .context("Failed to serialize combined graph to JSON")?;
 
-        fs::write(&safe_path, &serialized)
-            .with_context(|| format!("Failed to write combined graph to {}", safe_path.display()))?;
-
-        eprintln!("[deadmod] Combined graph exported → {}", safe_path.display());
-        eprintln!("  • Module graph: {} nodes, {} edges",
-            module_graph_json["stats"]["total_modules"],
-            module_graph_json["stats"]["total_edges"]);
-        eprintln!("  • Function graph: {} nodes, {} edges",
-            function_graph_json["stats"]["total_functions"],
-            function_graph_json["stats"]["total_edges"]);
+        fs::write(&safe_path, &serialized).with_context(|| {
+            format!("Failed to write combined graph to {}", safe_path.display())
+        })?;
+
+        eprintln!(
+            "[deadmod] Combined graph exported → {}",
+            safe_path.display()
+        );
+        eprintln!(
+            "  • Module graph: {} nodes, {} edges",
+            combined["module_graph"]["stats"]["total_modules"],
+            combined["module_graph"]["stats"]["total_edges"]
+        );
+        eprintln!(
+            "  • Function graph: {} nodes, {} edges",
+            combined["function_graph"]["stats"]["total_functions"],
+            combined["function_graph"]["stats"]["total_edges"]
+        );
+        eprintln!(
+            "  • Links: {} contains, {} uses ({} constants/variants)",
+            combined["stats"]["contains"], combined["stats"]["uses"], combined["stats"]["items"]
+        );
         std::process::exit(0);
     }
 
     // Call graph generation mode
-    if cli.callgraph || cli.callgraph_dot || cli.callgraph_viz {
+    if cli.callgraph
+        || cli.callgraph_dot
+        || cli.callgraph_viz
+        || cli.callgraph_folded
+        || cli.html_callgraph
+        || cli.html_pixi_callgraph
+    {
         let input_path = Path::new(&cli.path);
         print_workspace_info(input_path);
-        let root = find_crate_root(input_path)
-            .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
-
-        // Gather files and parse modules
-        let files = gather_rs_files(&root)?;
-        let cached = cache::load_cache(&root);
-        let mods = cache::incremental_parse(&root, &files, cached)?;
-
-        // Extract functions and call usages from all files
-        let mut all_functions = Vec::new();
-        let mut usage_map = std::collections::HashMap::new();
-
-        for info in mods.values() {
-            if let Ok(content) = fs::read_to_string(&info.path) {
-                let functions = extract_callgraph_functions(&info.path, &content);
-                let usages = extract_call_usages(&info.path, &content);
-
-                all_functions.extend(functions);
-                usage_map.insert(info.path.display().to_string(), usages);
-            }
-        }
+        let root = locate_crate_root(input_path)?;
 
         // Build call graph
-        let graph = CallGraph::build(&all_functions, &usage_map);
-
-        if cli.callgraph_dot {
-            // Output DOT format
-            println!("{}", graph.to_dot());
+        let graph = build_call_graph(&root, parse_mode)?;
+        if cli.focus.is_some() && cli.callgraph_folded {
+            bail!(
+                "--focus does not apply to --callgraph-folded, whose stacks start at entry points"
+            );
+        }
+        // Plain exports show only the focused functions; dead flags are
+        // computed on the whole graph (see focused_visualizer_graph)
+        let plain = match &cli.focus {
+            Some(_) => graph.restricted(&focused_functions(&cli, &graph)?),
+            None => graph.clone(),
+        };
+
+        let output = if cli.callgraph_dot {
+            // DOT format
+            plain.to_dot()
+        } else if cli.callgraph_folded {
+            // Folded stacks (one line per function)
+            graph.to_folded_stacks()
+        } else if cli.html_pixi_callgraph {
+            generate_pixi_callgraph_with_layout(
+                &focused_visualizer_graph(&cli, &graph)?,
+                &layout_options(&cli),
+                cli.theme,
+            )
+        } else if cli.html_callgraph {
+            generate_html_callgraph_with_layout(
+                &focused_visualizer_graph(&cli, &graph)?,
+                &layout_options(&cli),
+                cli.theme,
+            )
         } else if cli.callgraph_viz {
-            // Output visualizer-compatible JSON (numeric IDs, dead flags)
-            println!("{}", serde_json::to_string_pretty(&graph.to_visualizer_json())?);
+            // Visualizer-compatible JSON (numeric IDs, dead flags)
+            serde_json::to_string_pretty(&focused_visualizer_graph(&cli, &graph)?)?
         } else {
-            // Output JSON format
-            println!("{}", serde_json::to_string_pretty(&graph.to_json())?);
+            // JSON format
+            serde_json::to_string_pretty(&plain.to_json())?
+        };
+        // Folded stacks end in a newline of their own
+        if cli.callgraph_folded {
+            Stdout.write(&output);
+        } else {
+            Stdout.line(&output);
         }
 
         std::process::exit(0);
@@ -1056,87 +3138,119 @@ fn main() -> Result<()> {
     if cli.workspace {
         let root = Path::new(&cli.path)
             .canonicalize()
-            .with_context(|| format!("Failed to canonicalize path: {}", cli.path))?;
+            .or_else(|_| locate_crate_root(Path::new(&cli.path)))?;
 
-        let results = analyze_workspace(&root)?;
+        let results = analyze_workspace_with_roots(&root, &cli.roots)?;
 
         // Check if any crate has dead modules (for exit code)
         let has_dead = results.iter().any(|r| !r.dead_modules.is_empty());
 
-        if cli.json {
-            let json_output: Vec<serde_json::Value> = results
-                .iter()
-                .map(|r| {
-                    serde_json::json!({
-                        "crate": r.name,
-                        "root": r.root.display().to_string(),
-                        "dead_modules": r.dead_modules,
-                        "reachable_modules": r.reachable_modules,
-                    })
-                })
-                .collect();
-            println!("{}", serde_json::to_string_pretty(&json_output)?);
-        } else {
-            for result in &results {
-                println!("=== Crate: {} ===", result.name);
-                if result.dead_modules.is_empty() {
-                    println!("No dead modules found.\n");
-                } else {
-                    for m in &result.dead_modules {
-                        println!("  - {}", m);
-                    }
-                    println!();
-                }
-            }
-        }
-
-        // DOT output for workspace (combined or per-crate)
-        if cli.dot {
-            for result in &results {
-                println!("// === DOT for crate: {} ===", result.name);
-                println!("{}", result.dot_output);
-            }
-        }
+        let paths = path_formatter(cli.path_style, &root);
+        render_workspace(
+            &mut Stdout,
+            &render_settings(&cli, &root, &paths),
+            &results,
+            cli.dot,
+        )?;
 
         std::process::exit(if has_dead { 1 } else { 0 });
     }
 
     // Smart mode: Auto-detect workspace and scan all crates automatically
+    let specs = validate_output_specs(output_specs(&cli));
     let input_path = Path::new(&cli.path);
-    let canonical_path = input_path.canonicalize()
-        .with_context(|| format!("Failed to canonicalize path: {}", cli.path))?;
+    // A path that does not resolve fails as CrateRootNotFound
+    let canonical_path = input_path
+        .canonicalize()
+        .or_else(|_| locate_crate_root(input_path))?;
+
+    // A rust-project.json describes the crates when Cargo does not
+    let project = match cli.project_json {
+        Some(ref path) => Some(ProjectModel::load(Path::new(path))?),
+        None if !canonical_path.join("Cargo.toml").exists() => ProjectModel::find(&canonical_path)
+            .map(|path| ProjectModel::load(&path))
+            .transpose()?,
+        None => None,
+    };
 
     // Check if this is a workspace root - if so, auto-scan all crates
-    if is_workspace_root(&canonical_path) {
-        eprintln!("INFO: Detected Cargo workspace - scanning all crates automatically...");
-
-        let all_crates = find_all_crates(&canonical_path)?;
-        eprintln!("INFO: Found {} crate(s):", all_crates.len());
-        for cr in &all_crates {
-            let name = cr.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    if project.is_some() || is_workspace_root(&canonical_path) {
+        // (name, directory, root modules) of every crate
+        let mut externs = HashMap::new();
+        let members: Vec<(String, PathBuf, HashSet<String>)> = match project {
+            Some(ref model) => {
+                eprintln!(
+                    "INFO: Using {} - scanning its crates...",
+                    model.path.display()
+                );
+                model
+                    .members()
+                    .map(|krate| {
+                        let roots = if cli.roots.is_empty() {
+                            HashSet::from([krate.root_name()])
+                        } else {
+                            roots_for_crate(&cli.roots, &krate.name)
+                        };
+                        (krate.name.clone(), krate.dir.clone(), roots)
+                    })
+                    .collect()
+            }
+            None => {
+                eprintln!("INFO: Detected Cargo workspace - scanning all crates automatically...");
+                // Exact members, targets and dependencies; directory scan without cargo
+                let meta = CargoMetadata::load(&canonical_path).ok();
+                let dirs: Vec<PathBuf> = match meta {
+                    Some(ref meta) => {
+                        externs = meta.member_externs();
+                        meta.members()
+                            .into_iter()
+                            .map(|package| package.dir())
+                            .collect()
+                    }
+                    None => find_all_crates(&canonical_path)?,
+                };
+                dirs.into_iter()
+                    .map(|dir| {
+                        let name = member_name(&dir);
+                        let roots = if !cli.roots.is_empty() {
+                            roots_for_crate(&cli.roots, &name)
+                        } else {
+                            meta.as_ref()
+                                .and_then(|meta| meta.member_at(&dir))
+                                .map(|package| package.root_modules())
+                                .unwrap_or_else(|| find_root_modules(&dir))
+                        };
+                        (name, dir, roots)
+                    })
+                    .collect()
+            }
+        };
+        eprintln!("INFO: Found {} crate(s):", members.len());
+        for (name, _, _) in &members {
             eprintln!("  - {}", name);
         }
         eprintln!();
 
-        // Build combined module map with crate prefixes
-        let mut combined_mods: std::collections::HashMap<String, deadmod_core::ModuleInfo> = std::collections::HashMap::new();
+        // Parse every crate, then combine them into one graph with crate prefixes
+        let mut crate_mods = Vec::with_capacity(members.len());
         let mut all_roots: Vec<String> = Vec::new();
 
-        for crate_root in &all_crates {
-            let crate_name = crate_root.file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_else(|| "unknown".to_string());
+        for (crate_name, crate_root, crate_roots) in &members {
+            let crate_name = crate_name.clone();
 
-            let files = match gather_rs_files(crate_root) {
-                Ok(f) => f,
-                Err(e) => {
-                    eprintln!("[WARN] Failed to scan {}: {}", crate_name, e);
-                    continue;
-                }
-            };
+            progress.crate_start(&crate_name);
+            let files =
+                match gather_files(crate_root, &scan_limits(Some(&cli), crate_root), &progress) {
+                    Ok(f) => f,
+                    Err(e) => {
+                        eprintln!("[WARN] Failed to scan {}: {}", crate_name, e);
+                        continue;
+                    }
+                };
 
-            let cached = cache::load_cache(crate_root);
-            let mods = match cache::incremental_parse(crate_root, &files, cached) {
+            let mods = parse_crate(crate_root, &files, parse_mode, &progress);
+            progress.crate_done(&crate_name);
+            let mods = match mods {
                 Ok(m) => m,
                 Err(e) => {
                     eprintln!("[WARN] Failed to parse {}: {}", crate_name, e);
@@ -1144,23 +3258,12 @@ fn main() -> Result<()> {
                 }
             };
 
-            // Find root modules for this crate
-            let crate_roots = find_root_modules(crate_root);
             for root_mod in crate_roots {
                 all_roots.push(format!("{}::{}", crate_name, root_mod));
             }
-
-            // Add modules with crate prefix
-            for (name, mut info) in mods {
-                let prefixed_name = format!("{}::{}", crate_name, name);
-                // Update refs to use prefixed names
-                let prefixed_refs: HashSet<String> = info.refs.iter()
-                    .map(|r| format!("{}::{}", crate_name, r))
-                    .collect();
-                info.refs = prefixed_refs;
-                combined_mods.insert(prefixed_name, info);
-            }
+            crate_mods.push((crate_name, mods));
         }
+        let combined_mods = combine_crate_modules_with_externs(crate_mods, &externs);
 
         if combined_mods.is_empty() {
             eprintln!("No modules found in workspace.");
@@ -1168,86 +3271,52 @@ fn main() -> Result<()> {
         }
 
         // Build combined graph
+        let graph_span = info_span!("graph", modules = combined_mods.len()).entered();
+        progress.start(Phase::Graph, None);
         let graph = build_graph(&combined_mods);
-        let valid_roots = all_roots.iter()
-            .filter(|name| combined_mods.contains_key(*name))
-            .map(|s| s.as_str());
-        let reachable = reachable_from_roots(&graph, valid_roots);
+        all_roots.retain(|name| combined_mods.contains_key(name));
+        let reachable = reachable_from_roots(&graph, all_roots.iter().map(String::as_str));
+        progress.done(Phase::Graph);
+        drop(graph_span);
 
         // Find dead modules
-        let mut dead = find_dead(&combined_mods, &reachable);
+        let mut dead = dead_names(&find_dead(&combined_mods, &reachable));
         dead.sort();
 
-        // PixiJS graph for workspace
-        if cli.html_pixi || cli.html_pixi_file.is_some() {
-            let reachable_owned: HashSet<String> = reachable.iter().map(|s| s.to_string()).collect();
-            let html = generate_pixi_graph(&combined_mods, &reachable_owned);
-
-            if let Some(ref file) = cli.html_pixi_file {
-                match validate_output_path(file) {
-                    Ok(safe_path) => {
-                        if let Err(e) = fs::write(&safe_path, &html) {
-                            eprintln!("[WARN] PixiJS graph write failed: {}", e);
-                        } else {
-                            eprintln!("PixiJS WebGL graph saved to: {}", safe_path.display());
-                        }
-                    }
-                    Err(e) => eprintln!("[WARN] Invalid output path: {}", e),
-                }
-            } else {
-                println!("{}", html);
-            }
-            std::process::exit(if dead.is_empty() { 0 } else { 1 });
-        }
-
-        // HTML graph for workspace
-        if cli.html || cli.html_file.is_some() {
-            let reachable_owned: HashSet<String> = reachable.iter().map(|s| s.to_string()).collect();
-            let html = generate_html_graph(&combined_mods, &reachable_owned);
-
-            if let Some(ref file) = cli.html_file {
-                match validate_output_path(file) {
-                    Ok(safe_path) => {
-                        if let Err(e) = fs::write(&safe_path, &html) {
-                            eprintln!("[WARN] HTML write failed: {}", e);
-                        } else {
-                            eprintln!("HTML graph saved to: {}", safe_path.display());
-                        }
-                    }
-                    Err(e) => eprintln!("[WARN] Invalid output path: {}", e),
-                }
-            } else {
-                println!("{}", html);
-            }
-            std::process::exit(if dead.is_empty() { 0 } else { 1 });
-        }
-
-        // Text output for workspace
-        if cli.json {
-            let json_output = serde_json::json!({
-                "workspace": true,
-                "crates": all_crates.len(),
-                "total_modules": combined_mods.len(),
-                "reachable": reachable.len(),
-                "dead_count": dead.len(),
-                "dead_modules": dead,
-            });
-            println!("{}", serde_json::to_string_pretty(&json_output)?);
-        } else {
-            println!("=== Workspace Analysis ===\n");
-            println!("Crates: {}", all_crates.len());
-            println!("Total modules: {}", combined_mods.len());
-            println!("Reachable: {}", reachable.len());
-            println!("Dead: {}\n", dead.len());
-
-            if !dead.is_empty() {
-                println!("DEAD MODULES:");
-                for m in &dead {
-                    println!("  - {}", m);
-                }
-            } else {
-                println!("No dead modules found.");
-            }
+        // Emit all requested outputs from this single analysis run
+        let reachable_owned: HashSet<String> = reachable.iter().map(|s| s.to_string()).collect();
+        let paths = path_formatter(cli.path_style, &canonical_path);
+        let report_mods = paths.localize_modules(&combined_mods);
+        let member_roots: Vec<(String, PathBuf)> = members
+            .iter()
+            .map(|(name, dir, _)| (name.clone(), PathBuf::from(paths.format(dir))))
+            .collect();
+        let crate_analyses = split_by_crate(&member_roots, &report_mods, &reachable_owned, &dead);
+        let counts = RunCounts::new().with("dead_modules", dead.len());
+        let delta = run_delta(parse_mode, &canonical_path, "workspace", counts);
+        let mut report = ModuleReport::new(&report_mods, &reachable_owned, &dead)
+            .with_workspace_crates(members.len())
+            .with_crate_analyses(&crate_analyses)
+            .with_entry_points(&all_roots)
+            .with_item_limit(item_limit(&cli));
+        if let Some(ref delta) = delta {
+            report = report.with_delta(delta);
+        }
+        if let Some(ref focus) = cli.focus {
+            report = report.with_focus(focus, cli.depth);
+        }
+        if let Some(threshold) = cli.pixi_cluster_threshold {
+            report = report.with_pixi_cluster_threshold(threshold);
+        }
+        report = report
+            .with_layout(layout_options(&cli))
+            .with_theme(cli.theme)
+            .with_dot_options(dot_options(&cli));
+        if cli.json_normalized {
+            report = report.with_normalized_json(&canonical_path);
+        }
+        if let Err(e) = write_outputs(&specs, &report) {
+            eprintln!("[WARN] {:#}", e);
         }
 
         std::process::exit(if dead.is_empty() { 0 } else { 1 });
@@ -1256,8 +3325,7 @@ fn main() -> Result<()> {
     // Single crate mode (original behavior)
     // 1. Determine crate root
     print_workspace_info(input_path);
-    let root = find_crate_root(input_path)
-        .with_context(|| format!("Failed to find crate root from: {}", cli.path))?;
+    let root = locate_crate_root(input_path)?;
 
     // 2. Load config from deadmod.toml if present (safe - don't fail on config errors)
     let mut ignore = cli.ignore.clone();
@@ -1269,125 +3337,137 @@ fn main() -> Result<()> {
         }
         Ok(None) => {} // No config file - that's fine
         Err(e) => {
-            eprintln!("[WARN] config load failed: {}", e);
+            warn_config_failed(&e);
         }
     }
 
     // 3. Scan for .rs files
-    let files = gather_rs_files(&root)
+    let (files, skipped) = progress
+        .phase(Phase::Scan, None, || {
+            gather_rs_files_limited(&root, &scan_limits(Some(&cli), &root))
+        })
         .with_context(|| format!("Failed to gather Rust files from: {}", root.display()))?;
 
     // 4. Parse all modules with incremental caching (resilient - never fails)
-    let cached = cache::load_cache(&root);
-    let mut mods = cache::incremental_parse(&root, &files, cached)?;
+    let mut mods = parse_crate(&root, &files, parse_mode, &progress)?;
 
     // 5. Filter ignored modules
     mods.retain(|name, _| !is_ignored(name, &ignore));
 
     // 6. Build dependency graph
+    let graph_span = info_span!("graph", modules = mods.len()).entered();
+    progress.start(Phase::Graph, None);
     let graph = build_graph(&mods);
 
     // 7. Find reachable modules from all entry points (single O(|V|+|E|) traversal)
-    let root_modules = find_root_modules(&root);
-    let valid_roots = root_modules
-        .iter()
-        .filter(|name| mods.contains_key(*name))
-        .map(|s| s.as_str());
-    let reachable = reachable_from_roots(&graph, valid_roots);
-
-    // 8. Detect dead modules
-    let mut dead = find_dead(&mods, &reachable);
+    let root_modules = resolve_root_modules(&root, &cli.roots);
+    for name in cli.roots.iter().filter(|r| !mods.contains_key(*r)) {
+        eprintln!("[WARN] Unknown root module: {}", name);
+    }
+    let entry_points: Vec<String> = root_modules
+        .into_iter()
+        .filter(|name| mods.contains_key(name))
+        .collect();
+    let reachable = reachable_from_roots(&graph, entry_points.iter().map(String::as_str));
+    progress.done(Phase::Graph);
+    drop(graph_span);
+
+    // 8. Detect dead modules, less those recorded as false positives
+    let mut dead = dead_names(&find_dead(&mods, &reachable));
+    let suppressed = suppressed_ids(&root);
+    dead.retain(|name| !suppressed.contains(&module_id(name)));
     dead.sort();
 
-    // 9. Auto-fix mode (if requested)
-    if cli.fix || cli.fix_dry_run {
-        let dry_run = cli.fix_dry_run;
-        fix_dead_modules(&root, &dead, &mods, dry_run)?;
-        std::process::exit(if dead.is_empty() { 0 } else { 1 });
+    // 9. Auto-fix mode (if requested), staged by a removal plan
+    if let Some(file) = &cli.write_fix_plan {
+        let plan = fix::plan(&dead, &mods, cli.plan_batch);
+        plan.save(Path::new(file))?;
+        render_plan_written(&mut Stdout, &plan, file);
+        return Ok(());
     }
-
-    // 10. HTML interactive graph (if requested)
-    if cli.html || cli.html_file.is_some() {
-        let reachable_owned: HashSet<String> = reachable.iter().map(|s| s.to_string()).collect();
-        let html = generate_html_graph(&mods, &reachable_owned);
-
-        if let Some(ref file) = cli.html_file {
-            // Security: Validate output path
-            match validate_output_path(file) {
-                Ok(safe_path) => {
-                    if let Err(e) = fs::write(&safe_path, &html) {
-                        eprintln!("[WARN] HTML write failed to {}: {}", safe_path.display(), e);
-                    } else {
-                        println!("HTML graph saved to: {}", safe_path.display());
-                    }
-                }
-                Err(e) => {
-                    eprintln!("[ERROR] Invalid output path: {}", e);
-                    std::process::exit(2);
-                }
-            }
-        } else {
-            println!("{}", html);
+    if let Some(file) = &cli.fix_plan {
+        let plan = RemovalPlan::load(Path::new(file))?;
+        let step = match cli.plan_step {
+            Some(n) => Some(plan.step(n).with_context(|| {
+                format!(
+                    "removal plan {} has no step {} ({} steps)",
+                    file,
+                    n,
+                    plan.steps.len()
+                )
+            })?),
+            None => plan.next_step(&mods),
+        };
+        let Some(step) = step else {
+            render_plan_step(&mut Stdout, &plan, file, None);
+            return Ok(());
+        };
+        let (targets, live) = step_targets(step, &mods, &dead);
+        for module in live {
+            eprintln!("[WARN] {} is no longer dead; skipped", module);
         }
-        std::process::exit(if dead.is_empty() { 0 } else { 1 });
+        render_plan_step(&mut Stdout, &plan, file, Some((step, targets.len())));
+        dead.retain(|module| targets.contains(module));
+        cli.fix = cli.fix || !(cli.fix_dry_run || cli.fix_verify);
     }
-
-    // 10b. PixiJS WebGL interactive graph (GPU-accelerated)
-    if cli.html_pixi || cli.html_pixi_file.is_some() {
-        let reachable_owned: HashSet<String> = reachable.iter().map(|s| s.to_string()).collect();
-        let html = generate_pixi_graph(&mods, &reachable_owned);
-
-        if let Some(ref file) = cli.html_pixi_file {
-            // Security: Validate output path
-            match validate_output_path(file) {
-                Ok(safe_path) => {
-                    if let Err(e) = fs::write(&safe_path, &html) {
-                        eprintln!("[WARN] PixiJS HTML write failed to {}: {}", safe_path.display(), e);
-                    } else {
-                        println!("PixiJS WebGL graph saved to: {}", safe_path.display());
-                    }
-                }
-                Err(e) => {
-                    eprintln!("[ERROR] Invalid output path: {}", e);
-                    std::process::exit(2);
-                }
-            }
-        } else {
-            println!("{}", html);
-        }
+    if cli.fix_verify && !cli.fix_dry_run {
+        let options = FixOptions::new().force(cli.yes);
+        let verify = VerifyOptions {
+            run_tests: cli.verify_tests,
+        };
+        let result = fix_and_verify(&root, &dead, &mods, &options, &verify)?;
+        std::process::exit(if result.rolled_back.is_empty() { 0 } else { 1 });
+    }
+    if cli.fix || cli.fix_dry_run {
+        let options = FixOptions::new().dry_run(cli.fix_dry_run).force(cli.yes);
+        fix_dead_modules_with(&root, &dead, &mods, &options)?;
         std::process::exit(if dead.is_empty() { 0 } else { 1 });
     }
 
-    // 11. Report results
-    if cli.json {
-        print_json(&dead);
-    } else {
-        print_plain(&dead);
+    // 10. Report results in every requested format (single analysis run)
+    let reachable_owned: HashSet<String> = reachable.iter().map(|s| s.to_string()).collect();
+    let report_mods = path_formatter(cli.path_style, &root).localize_modules(&mods);
+    let delta = run_delta(
+        parse_mode,
+        &root,
+        "modules",
+        RunCounts::new().with("dead_modules", dead.len()),
+    );
+    let mut report = ModuleReport::new(&report_mods, &reachable_owned, &dead)
+        .with_skipped(&skipped)
+        .with_entry_points(&entry_points)
+        .with_item_limit(item_limit(&cli));
+    if let Some(ref delta) = delta {
+        report = report.with_delta(delta);
     }
-
-    // 12. DOT/Graphviz output (safe - don't crash on write errors)
-    if cli.dot {
-        let reachable_owned: HashSet<String> = reachable.iter().map(|s| s.to_string()).collect();
-        let dot = visualize::generate_dot(&mods, &reachable_owned);
-        if let Some(ref file) = cli.dot_file {
-            // Security: Validate output path
-            match validate_output_path(file) {
-                Ok(safe_path) => {
-                    if let Err(e) = fs::write(&safe_path, &dot) {
-                        eprintln!("[WARN] DOT write failed to {}: {}", safe_path.display(), e);
-                    }
-                }
-                Err(e) => {
-                    eprintln!("[ERROR] Invalid output path: {}", e);
-                    std::process::exit(2);
-                }
-            }
-        } else {
-            println!("{}", dot);
-        }
+    if let Some(ref focus) = cli.focus {
+        report = report.with_focus(focus, cli.depth);
+    }
+    if let Some(threshold) = cli.pixi_cluster_threshold {
+        report = report.with_pixi_cluster_threshold(threshold);
+    }
+    report = report
+        .with_layout(layout_options(&cli))
+        .with_theme(cli.theme)
+        .with_dot_options(dot_options(&cli));
+    if cli.json_normalized {
+        report = report.with_normalized_json(&root);
+    }
+    if let Err(e) = write_outputs(&specs, &report) {
+        eprintln!("[ERROR] {:#}", e);
+        std::process::exit(2);
+    }
+    if specs
+        .iter()
+        .any(|spec| spec.format == OutputFormat::Plain && spec.path.is_none())
+    {
+        print_suppressions_due(
+            &mut Stdout,
+            &suppressions_due(cli.review_suppressions, &root),
+        );
     }
 
-    // 13. Exit code (CI-friendly)
+    // 11. Exit code (CI-friendly)
     std::process::exit(if dead.is_empty() { 0 } else { 1 });
 }
 
@@ -1456,8 +3536,14 @@ mod tests {
 
         fs::create_dir_all(ws_root.join("crate_a/src")).unwrap();
         fs::create_dir_all(ws_root.join("crate_b/src")).unwrap();
-        create_file(&ws_root.join("crate_a/Cargo.toml"), "[package]\nname = \"a\"");
-        create_file(&ws_root.join("crate_b/Cargo.toml"), "[package]\nname = \"b\"");
+        create_file(
+            &ws_root.join("crate_a/Cargo.toml"),
+            "[package]\nname = \"a\"",
+        );
+        create_file(
+            &ws_root.join("crate_b/Cargo.toml"),
+            "[package]\nname = \"b\"",
+        );
         create_file(&ws_root.join("crate_a/src/lib.rs"), "");
         create_file(&ws_root.join("crate_b/src/lib.rs"), "");
 
@@ -1490,6 +3576,620 @@ mod tests {
         assert!(is_ignored("my_mock_module", &ignore));
     }
 
+    // --- output_specs TESTS ---
+
+    #[test]
+    fn test_output_specs_default_plain() {
+        let cli = Cli::parse_from(["deadmod", "."]);
+        assert_eq!(
+            output_specs(&cli),
+            vec![OutputSpec::stdout(OutputFormat::Plain)]
+        );
+    }
+
+    #[test]
+    fn test_output_specs_legacy_json_and_dot() {
+        let cli = Cli::parse_from(["deadmod", ".", "--json", "--dot-file", "deps.dot"]);
+        assert_eq!(
+            output_specs(&cli),
+            vec![
+                OutputSpec::stdout(OutputFormat::Json),
+                OutputSpec::file(OutputFormat::Dot, "deps.dot"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_output_specs_repeatable_output() {
+        let cli = Cli::parse_from([
+            "deadmod",
+            ".",
+            "--output",
+            "format=json,path=out.json",
+            "--output",
+            "format=html,path=graph.html",
+        ]);
+        assert_eq!(
+            output_specs(&cli),
+            vec![
+                OutputSpec::file(OutputFormat::Json, "out.json"),
+                OutputSpec::file(OutputFormat::Html, "graph.html"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_output_specs_html_replaces_text_report() {
+        let cli = Cli::parse_from(["deadmod", ".", "--html-file", "g.html"]);
+        assert_eq!(
+            output_specs(&cli),
+            vec![OutputSpec::file(OutputFormat::Html, "g.html")]
+        );
+    }
+
+    // --- fix subcommand TESTS ---
+
+    #[test]
+    fn test_fast_requires_no_cache() {
+        let cli = Cli::parse_from(["deadmod", ".", "--no-cache", "--fast"]);
+        assert!(cli.no_cache && cli.fast);
+        assert!(Cli::try_parse_from(["deadmod", ".", "--fast"]).is_err());
+    }
+
+    #[test]
+    fn test_fix_subcommand_undo_latest() {
+        let cli = Cli::parse_from(["deadmod", "fix", "--undo"]);
+        let Some(Command::Fix(args)) = cli.command else {
+            panic!("expected fix subcommand");
+        };
+        assert_eq!(args.undo.as_deref(), Some(""));
+        assert_eq!(args.path, default_path());
+    }
+
+    #[test]
+    fn test_fix_subcommand_undo_id() {
+        let cli = Cli::parse_from(["deadmod", "fix", "my_crate", "--undo=20260101-120000"]);
+        let Some(Command::Fix(args)) = cli.command else {
+            panic!("expected fix subcommand");
+        };
+        assert_eq!(args.undo.as_deref(), Some("20260101-120000"));
+        assert_eq!(args.path, "my_crate");
+    }
+
+    #[test]
+    fn test_fix_subcommand_undo_then_path() {
+        // A value after a space is the path, not an ID
+        let cli = Cli::parse_from(["deadmod", "fix", "--undo", "."]);
+        let Some(Command::Fix(args)) = cli.command else {
+            panic!("expected fix subcommand");
+        };
+        assert_eq!(args.undo.as_deref(), Some(""));
+        assert_eq!(args.path, ".");
+    }
+
+    #[test]
+    fn test_fix_subcommand_plan() {
+        let cli = Cli::parse_from([
+            "deadmod",
+            "fix",
+            "--write-plan",
+            "plan.json",
+            "--batch",
+            "3",
+        ]);
+        let Some(Command::Fix(args)) = cli.command else {
+            panic!("expected fix subcommand");
+        };
+        assert_eq!(args.write_plan.as_deref(), Some("plan.json"));
+        assert_eq!(args.batch, 3);
+
+        let cli = Cli::parse_from(["deadmod", "fix", "--plan", "plan.json", "--step", "2"]);
+        let Some(Command::Fix(args)) = cli.command else {
+            panic!("expected fix subcommand");
+        };
+        assert_eq!(args.plan.as_deref(), Some("plan.json"));
+        assert_eq!(
+            (args.step, args.batch),
+            (Some(2), DEFAULT_CONFIRM_THRESHOLD)
+        );
+    }
+
+    #[test]
+    fn test_mark_false_positive_subcommand() {
+        let cli = Cli::parse_from([
+            "deadmod",
+            "mark-false-positive",
+            "3fa9c2d1e07b",
+            "--reason",
+            "FFI entry point",
+        ]);
+        let Some(Command::MarkFalsePositive(args)) = cli.command else {
+            panic!("expected mark-false-positive subcommand");
+        };
+        assert_eq!(
+            (args.id.as_str(), args.reason.as_str()),
+            ("3fa9c2d1e07b", "FFI entry point")
+        );
+        assert_eq!((args.path, args.author), (default_path(), None));
+
+        // A reason is required
+        assert!(Cli::try_parse_from(["deadmod", "mark-false-positive", "3fa9c2d1e07b"]).is_err());
+        let cli = Cli::parse_from(["deadmod", ".", "--dead-func", "--review-suppressions", "90"]);
+        assert_eq!(cli.review_suppressions, Some(90));
+    }
+
+    #[test]
+    fn test_dead_params_flag() {
+        let cli = Cli::parse_from(["deadmod", ".", "--dead-params", "--fix-dry-run"]);
+        assert!(cli.dead_params && cli.fix_dry_run);
+        assert!(!Cli::parse_from(["deadmod", "."]).dead_params);
+        assert!(Cli::parse_from(["deadmod", ".", "--dead-fields"]).dead_fields);
+    }
+
+    #[test]
+    fn test_json_normalized_flag() {
+        let cli = Cli::parse_from(["deadmod", ".", "--json-normalized"]);
+        assert!(cli.json_normalized && !cli.json);
+        assert!(!Cli::parse_from(["deadmod", ".", "--json"]).json_normalized);
+    }
+
+    #[test]
+    fn test_item_limit_flags() {
+        let cli = Cli::parse_from(["deadmod", ".", "--dead-func", "--max-items", "20", "--top"]);
+        assert_eq!(item_limit(&cli), ItemLimit::new(Some(20), true));
+        assert!(!item_limit(&Cli::parse_from(["deadmod", "."])).is_active());
+    }
+
+    #[test]
+    fn test_progress_flag() {
+        assert_eq!(
+            Cli::parse_from(["deadmod", "."]).progress,
+            ProgressMode::Auto
+        );
+        let cli = Cli::parse_from(["deadmod", ".", "--dead-func", "--progress", "always"]);
+        assert_eq!(cli.progress, ProgressMode::Always);
+        assert!(Cli::try_parse_from(["deadmod", ".", "--progress", "sometimes"]).is_err());
+    }
+
+    #[test]
+    fn test_path_without_subcommand() {
+        let cli = Cli::parse_from(["deadmod", "some/crate", "--json"]);
+        assert!(cli.command.is_none());
+        assert_eq!(cli.path, "some/crate");
+    }
+
+    #[test]
+    fn test_arch_subcommands() {
+        let cli = Cli::parse_from(["deadmod", "cycles", "some/crate", "--json"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Cycles(CyclesArgs { ref path, json: true })) if path == "some/crate"
+        ));
+
+        let cli = Cli::parse_from(["deadmod", "metrics", "--json"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Metrics(MetricsArgs { ref path, json: true })) if *path == default_path()
+        ));
+
+        let cli = Cli::parse_from(["deadmod", "layers", "--rules", "layers.toml"]);
+        match cli.command {
+            Some(Command::Layers(args)) => {
+                assert_eq!(args.path, default_path());
+                assert_eq!(args.rules, Some(PathBuf::from("layers.toml")));
+            }
+            other => panic!("expected layers subcommand, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_callpath_subcommand() {
+        let cli = Cli::parse_from(["deadmod", "callpath", "main", "utils::helper", "--dot"]);
+        match cli.command {
+            Some(Command::Callpath(args)) => {
+                assert_eq!(args.from, "main");
+                assert_eq!(args.to, "utils::helper");
+                assert_eq!(args.path, default_path());
+                assert_eq!(args.max_paths, 10);
+                assert!(args.dot);
+            }
+            other => panic!("expected callpath subcommand, got {:?}", other),
+        }
+
+        assert!(Cli::try_parse_from(["deadmod", "callpath", "main"]).is_err());
+        assert!(Cli::try_parse_from(["deadmod", "callpath", "a", "b", "--dot", "--json"]).is_err());
+    }
+
+    #[test]
+    fn test_analyze_graph_subcommand() {
+        let cli = Cli::parse_from([
+            "deadmod",
+            "analyze-graph",
+            "graph.json",
+            "--roots",
+            "main,cli",
+            "--focus",
+            "net",
+        ]);
+        match cli.command {
+            Some(Command::AnalyzeGraph(args)) => {
+                assert_eq!(args.graph, PathBuf::from("graph.json"));
+                assert_eq!(args.roots, vec!["main".to_string(), "cli".to_string()]);
+                assert_eq!(args.focus.as_deref(), Some("net"));
+                assert_eq!(args.depth, 1);
+            }
+            other => panic!("expected analyze-graph subcommand, got {:?}", other),
+        }
+
+        assert!(Cli::try_parse_from(["deadmod", "analyze-graph"]).is_err());
+        assert!(Cli::try_parse_from([
+            "deadmod",
+            "analyze-graph",
+            "g.json",
+            "--metrics",
+            "--cycles"
+        ])
+        .is_err());
+        assert!(
+            Cli::try_parse_from(["deadmod", "analyze-graph", "g.json", "--depth", "2"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_badge_flags() {
+        let cli = Cli::parse_from(["deadmod", "--badge-json", "badge.json"]);
+        assert_eq!(cli.badge_json.as_deref(), Some("badge.json"));
+        assert_eq!(cli.badge_red_at, DEFAULT_BADGE_RED_AT);
+
+        let cli = Cli::parse_from([
+            "deadmod",
+            "--badge-json",
+            "badge.json",
+            "--badge-red-at",
+            "25",
+        ]);
+        assert_eq!(cli.badge_red_at, 25);
+        assert!(Cli::try_parse_from(["deadmod", "--badge-red-at", "25"]).is_err());
+    }
+
+    #[test]
+    fn test_archive_subcommand() {
+        let cli = Cli::parse_from([
+            "deadmod",
+            "archive",
+            "demo-0.1.0.crate",
+            "--max-file-size",
+            "1M",
+            "--json",
+        ]);
+        match cli.command {
+            Some(Command::Archive(args)) => {
+                assert_eq!(args.archive, PathBuf::from("demo-0.1.0.crate"));
+                assert_eq!(args.max_file_size, Some(1_000_000));
+                assert_eq!(args.min_confidence, Confidence::Low);
+                assert!(args.json);
+            }
+            other => panic!("expected archive subcommand, got {:?}", other),
+        }
+
+        assert!(Cli::try_parse_from(["deadmod", "archive"]).is_err());
+    }
+
+    #[test]
+    fn test_self_check_subcommand() {
+        let cli = Cli::parse_from(["deadmod", "self-check", "--no-cache"]);
+        match cli.command {
+            Some(Command::SelfCheck(args)) => {
+                assert_eq!(args.path, default_path());
+                assert!(args.no_cache);
+                assert!(!args.update);
+            }
+            other => panic!("expected self-check subcommand, got {:?}", other),
+        }
+
+        assert!(Cli::try_parse_from(["deadmod", "self-check", "--update", "--json"]).is_err());
+    }
+
+    #[test]
+    fn test_min_confidence_flag() {
+        let cli = Cli::parse_from(["deadmod", "--dead-func"]);
+        assert_eq!(cli.min_confidence, Confidence::Low);
+
+        let cli = Cli::parse_from(["deadmod", "--dead-func", "--min-confidence", "high"]);
+        assert_eq!(cli.min_confidence, Confidence::High);
+
+        assert!(Cli::try_parse_from(["deadmod", "--min-confidence", "certain"]).is_err());
+    }
+
+    #[test]
+    fn test_doc_hidden_flag() {
+        let cli = Cli::parse_from(["deadmod", "--dead-func"]);
+        assert_eq!(cli.doc_hidden, None);
+
+        let cli = Cli::parse_from(["deadmod", "--dead-func", "--doc-hidden", "exclude"]);
+        assert_eq!(cli.doc_hidden, Some(HiddenApiPolicy::Exclude));
+
+        assert!(Cli::try_parse_from(["deadmod", "--doc-hidden", "hide"]).is_err());
+    }
+
+    #[test]
+    fn test_assume_pub_reachable_flag() {
+        let cli = Cli::parse_from(["deadmod", "--dead-func"]);
+        assert_eq!(cli.assume_pub_reachable, None);
+
+        let cli = Cli::parse_from(["deadmod", "--dead-func", "--assume-pub-reachable", "never"]);
+        assert_eq!(cli.assume_pub_reachable, Some(PubPolicy::Never));
+
+        assert!(Cli::try_parse_from(["deadmod", "--assume-pub-reachable", "maybe"]).is_err());
+    }
+
+    #[test]
+    fn test_limit_flags() {
+        let cli = Cli::parse_from([
+            "deadmod",
+            "--max-file-size",
+            "10MB",
+            "--max-files",
+            "500",
+            "--timeout",
+            "60",
+        ]);
+        assert_eq!(cli.max_file_size, Some(10_000_000));
+        assert_eq!(cli.max_files, Some(500));
+        assert_eq!(cli.timeout, Some(60));
+
+        assert!(Cli::try_parse_from(["deadmod", "--max-file-size", "huge"]).is_err());
+    }
+
+    #[test]
+    fn test_file_subcommand() {
+        let cli = Cli::parse_from(["deadmod", "file", "src/lib.rs", "--json"]);
+        match cli.command {
+            Some(Command::File(args)) => {
+                assert_eq!(args.file, PathBuf::from("src/lib.rs"));
+                assert_eq!(args.min_confidence, Confidence::Low);
+                assert!(args.json);
+            }
+            other => panic!("expected file subcommand, got {:?}", other),
+        }
+
+        assert!(Cli::try_parse_from(["deadmod", "file"]).is_err());
+    }
+
+    #[test]
+    fn test_daemon_subcommand() {
+        let cli = Cli::parse_from(["deadmod", "daemon", "some/crate", "--socket", "/tmp/d.sock"]);
+        match cli.command {
+            Some(Command::Daemon(args)) => {
+                assert_eq!(args.path, "some/crate");
+                assert_eq!(args.socket, Some(PathBuf::from("/tmp/d.sock")));
+            }
+            other => panic!("expected daemon subcommand, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_explain_subcommand() {
+        let cli = Cli::parse_from(["deadmod", "explain", "3f2a9c1d0b7e", "--json"]);
+        match cli.command {
+            Some(Command::Explain(args)) => {
+                assert_eq!(args.id, "3f2a9c1d0b7e");
+                assert_eq!(args.path, default_path());
+                assert!(args.json);
+            }
+            other => panic!("expected explain subcommand, got {:?}", other),
+        }
+
+        assert!(Cli::try_parse_from(["deadmod", "explain"]).is_err());
+    }
+
+    #[test]
+    fn test_find_subcommand() {
+        let cli = Cli::parse_from([
+            "deadmod",
+            "find",
+            "net::connect",
+            "--path-style",
+            "relative",
+        ]);
+        match cli.command {
+            Some(Command::Find(args)) => {
+                assert_eq!(args.query, "net::connect");
+                assert_eq!(args.path, default_path());
+                assert_eq!(args.path_style, Some(PathStyle::Relative));
+                assert!(!args.json);
+            }
+            other => panic!("expected find subcommand, got {:?}", other),
+        }
+
+        assert!(Cli::try_parse_from(["deadmod", "find"]).is_err());
+    }
+
+    #[test]
+    fn test_check_removal_subcommand() {
+        let cli = Cli::parse_from([
+            "deadmod",
+            "check-removal",
+            "Config::load",
+            "crates/app",
+            "--json",
+        ]);
+        match cli.command {
+            Some(Command::CheckRemoval(args)) => {
+                assert_eq!(args.item, "Config::load");
+                assert_eq!(args.path, "crates/app");
+                assert!(args.json);
+            }
+            other => panic!("expected check-removal subcommand, got {:?}", other),
+        }
+
+        assert!(Cli::try_parse_from(["deadmod", "check-removal"]).is_err());
+    }
+
+    #[test]
+    fn test_warm_subcommand() {
+        let cli = Cli::parse_from(["deadmod", "warm", "some/workspace", "--json"]);
+        match cli.command {
+            Some(Command::Warm(args)) => {
+                assert_eq!(args.path, "some/workspace");
+                assert!(args.json);
+            }
+            other => panic!("expected warm subcommand, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_serve_subcommand() {
+        let cli = Cli::parse_from(["deadmod", "serve", "--port", "8080"]);
+        match cli.command {
+            Some(Command::Serve(args)) => {
+                assert_eq!(args.path, default_path());
+                assert_eq!(args.port, 8080);
+                assert_eq!(args.host, "127.0.0.1");
+            }
+            other => panic!("expected serve subcommand, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_bench_selftest_subcommand() {
+        let cli = Cli::parse_from(["deadmod", "bench-selftest", "--functions", "1000", "--json"]);
+        match cli.command {
+            Some(Command::BenchSelftest(args)) => {
+                assert_eq!(args.functions, 1000);
+                assert!(args.json);
+            }
+            other => panic!("expected bench-selftest subcommand, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_coverage_flag() {
+        let cli = Cli::parse_from(["deadmod", "--dead-func", "--coverage", "cov.json"]);
+        assert_eq!(cli.coverage, Some(PathBuf::from("cov.json")));
+
+        assert!(Cli::try_parse_from(["deadmod", "--coverage", "cov.json"]).is_err());
+    }
+
+    #[test]
+    fn test_roots_flag() {
+        let cli = Cli::parse_from(["deadmod", "."]);
+        assert!(cli.roots.is_empty());
+
+        let cli = Cli::parse_from(["deadmod", ".", "--roots", "api,server::http"]);
+        assert_eq!(
+            cli.roots,
+            vec!["api".to_string(), "server::http".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_per_target_flag() {
+        let cli = Cli::parse_from(["deadmod", ".", "--per-target", "--json"]);
+        assert!(cli.per_target);
+        assert!(cli.json);
+    }
+
+    #[test]
+    fn test_group_by_flag() {
+        let cli = Cli::parse_from(["deadmod", "."]);
+        assert_eq!(cli.group_by, None);
+
+        let cli = Cli::parse_from(["deadmod", ".", "--group-by", "module", "--json"]);
+        assert_eq!(cli.group_by, Some(GroupBy::Module));
+
+        assert!(Cli::try_parse_from(["deadmod", "--group-by", "file"]).is_err());
+    }
+
+    #[test]
+    fn test_no_collapse_flag() {
+        let cli = Cli::parse_from(["deadmod", ".", "--dead-func"]);
+        assert!(!cli.no_collapse);
+
+        let cli = Cli::parse_from(["deadmod", ".", "--dead-func", "--no-collapse"]);
+        assert!(cli.no_collapse);
+    }
+
+    #[test]
+    fn test_jobs_flag() {
+        let cli = Cli::parse_from(["deadmod", "."]);
+        assert_eq!(cli.jobs, None);
+
+        let cli = Cli::parse_from(["deadmod", ".", "--jobs", "2"]);
+        assert_eq!(cli.jobs.map(NonZeroUsize::get), Some(2));
+
+        // Global: accepted after a subcommand too
+        let cli = Cli::parse_from(["deadmod", "cycles", "-j", "1"]);
+        assert_eq!(cli.jobs.map(NonZeroUsize::get), Some(1));
+
+        assert!(Cli::try_parse_from(["deadmod", "--jobs", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_html_callgraph_flags() {
+        let cli = Cli::parse_from(["deadmod", ".", "--html-callgraph"]);
+        assert!(cli.html_callgraph && !cli.html_pixi_callgraph);
+        assert!(!cli.html);
+
+        let cli = Cli::parse_from(["deadmod", ".", "--html-pixi-callgraph"]);
+        assert!(cli.html_pixi_callgraph && !cli.html_pixi);
+    }
+
+    #[test]
+    fn test_focus_flags() {
+        let cli = Cli::parse_from([
+            "deadmod", ".", "--dot", "--focus", "net::tcp", "--depth", "2",
+        ]);
+        assert_eq!((cli.focus.as_deref(), cli.depth), (Some("net::tcp"), 2));
+
+        let cli = Cli::parse_from(["deadmod", ".", "--callgraph-viz", "--focus", "run"]);
+        assert_eq!(cli.depth, 1);
+
+        // --depth means nothing without a focus
+        assert!(Cli::try_parse_from(["deadmod", ".", "--depth", "2"]).is_err());
+    }
+
+    #[test]
+    fn test_dot_flags() {
+        let cli = Cli::parse_from([
+            "deadmod",
+            ".",
+            "--dot",
+            "--dot-clusters",
+            "crate",
+            "--dot-rankdir",
+            "TB",
+        ]);
+        assert_eq!(
+            dot_options(&cli),
+            DotOptions::default()
+                .with_clusters(DotClusters::Crate)
+                .with_rankdir(RankDir::TopBottom)
+        );
+
+        let cli = Cli::parse_from(["deadmod", ".", "--dot", "--dot-max-nodes", "50"]);
+        assert_eq!(dot_options(&cli).max_nodes, Some(50));
+        assert!(Cli::try_parse_from(["deadmod", ".", "--dot-rankdir", "up"]).is_err());
+    }
+
+    #[test]
+    fn test_path_style_flag() {
+        let cli = Cli::parse_from(["deadmod", ".", "--path-style", "crate"]);
+        assert_eq!(cli.path_style, Some(PathStyle::Crate));
+        assert_eq!(Cli::parse_from(["deadmod", "."]).path_style, None);
+
+        assert!(Cli::try_parse_from(["deadmod", ".", "--path-style", "home"]).is_err());
+    }
+
+    #[test]
+    fn test_grace_period_flag() {
+        let cli = Cli::parse_from(["deadmod", ".", "--dead-func", "--grace-period", "14"]);
+        assert_eq!(cli.grace_period, Some(14));
+        assert_eq!(Cli::parse_from(["deadmod", "."]).grace_period, None);
+
+        assert!(Cli::try_parse_from(["deadmod", ".", "--grace-period", "-1"]).is_err());
+    }
+
     // --- is_workspace TESTS ---
 
     #[test]
@@ -1506,10 +4206,7 @@ mod tests {
     #[test]
     fn test_is_workspace_false() {
         let temp_dir = create_temp_dir("ws_false");
-        create_file(
-            &temp_dir.join("Cargo.toml"),
-            "[package]\nname = \"test\"",
-        );
+        create_file(&temp_dir.join("Cargo.toml"), "[package]\nname = \"test\"");
 
         assert!(!is_workspace_root(&temp_dir));
     }
@@ -0,0 +1,2687 @@
+//! Report rendering, kept apart from the analysis.
+//!
+//! Each mode in `main.rs` runs its analysis and hands the typed result to a
+//! `render_*` function here, which writes plain text or JSON to an
+//! [`OutputSink`]. Renderers never touch the filesystem or exit; `main.rs`
+//! picks the sink ([`Stdout`]) and the exit code. The golden tests below
+//! pin every format against `tests/golden/`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use deadmod_core::synthetic::SyntheticProject;
+use deadmod_core::{
+    format_cycles_json, format_cycles_plain, format_grouped_plain, format_metrics_json,
+    format_metrics_plain, format_plan_plain, format_violations_json, format_violations_plain,
+    get_cluster_tree, more_summary, normalize_json, AnalysisResult, Attribution, Confidence,
+    ConstAnalysisResult, CrateAnalysis, DeadArmReason, DeadDepsResult, DeadImpl, DeadItemKind,
+    DeadmodError, DependencyKind, EnumAnalysisResult, ExpectedDiff, ExpectedFinding,
+    FieldAnalysisResult, FileFinding, FileFindings, FindingEvidence, FuncAnalysisResult,
+    FunctionInfo, GenericAnalysisResult, GenericKind, GraphMetrics, ItemLimit, KeptItem,
+    LayerViolation, MacroAnalysisResult, MacroKind, MatchArmAnalysisResult, ModuleCycle,
+    ModuleDiscovery, ModuleNode, ParamAnalysisResult, PathFormatter, PlanStep, RecentItem,
+    RecoveryAction, RecoveryManifest, RemovalCheck, RemovalPlan, RunDelta, Suppression,
+    SymbolSearch, TraitAnalysisResult, Truncated, WarmStats, EXPECTED_FILE, SUPPRESSIONS_FILE,
+};
+
+/// Where rendered reports go.
+pub trait OutputSink {
+    /// Write `text` as is.
+    fn write(&mut self, text: &str);
+
+    /// Write `text` and a newline.
+    fn line(&mut self, text: &str) {
+        self.write(text);
+        self.write("\n");
+    }
+}
+
+/// Standard output.
+pub struct Stdout;
+
+impl OutputSink for Stdout {
+    fn write(&mut self, text: &str) {
+        print!("{}", text);
+    }
+}
+
+/// Standard error, for notes that must not mix with a report.
+pub struct Stderr;
+
+impl OutputSink for Stderr {
+    fn write(&mut self, text: &str) {
+        eprint!("{}", text);
+    }
+}
+
+/// Collects the output, e.g. for tests.
+impl OutputSink for String {
+    fn write(&mut self, text: &str) {
+        self.push_str(text);
+    }
+}
+
+/// `println!` into an [`OutputSink`].
+macro_rules! out {
+    ($sink:expr) => {
+        $sink.line("")
+    };
+    ($sink:expr, $($arg:tt)*) => {
+        $sink.line(&format!($($arg)*))
+    };
+}
+
+/// Settings every renderer shares.
+pub struct Render<'a> {
+    /// JSON instead of plain text
+    pub json: bool,
+    /// Crate root JSON is normalized against (`--json-normalized`)
+    pub normalize_root: Option<&'a Path>,
+    /// Style file paths are written in
+    pub paths: &'a PathFormatter,
+    /// How much of each finding list plain output shows
+    pub limit: ItemLimit,
+}
+
+impl<'a> Render<'a> {
+    /// Plain text or JSON with paths in `paths`' style, every finding
+    /// listed and JSON not normalized.
+    pub fn new(json: bool, paths: &'a PathFormatter) -> Self {
+        Self {
+            json,
+            normalize_root: None,
+            paths,
+            limit: ItemLimit::default(),
+        }
+    }
+
+    /// Write `value` as pretty JSON, normalized under `--json-normalized`.
+    pub fn json_report(&self, out: &mut dyn OutputSink, mut value: Value) -> Result<()> {
+        if let Some(root) = self.normalize_root {
+            normalize_json(&mut value, root);
+        }
+        out.line(&serde_json::to_string_pretty(&value)?);
+        Ok(())
+    }
+}
+
+/// What a single-detector report carries besides the detector's result:
+/// how finding IDs are assigned and the sections every report ends with.
+pub struct Findings<'a> {
+    /// Finding IDs
+    pub attr: &'a Attribution<'a>,
+    /// Findings folded into dead modules
+    pub collapsed: usize,
+    /// Items kept by `#[deadmod::keep]`
+    pub kept: &'a [KeptItem],
+    /// Findings within the grace period
+    pub recent: &'a [RecentItem],
+    /// False positives due for re-review
+    pub due: &'a [Suppression],
+    /// Counts against the last run
+    pub delta: Option<&'a RunDelta>,
+}
+
+impl Findings<'_> {
+    /// Add the shared sections to a detector's JSON report.
+    fn insert_json(&self, value: &mut Value) {
+        value["kept"] = json!(self.kept);
+        value["recent"] = json!(self.recent);
+        value["review_due"] = json!(self.due);
+        value["collapsed"] = json!(self.collapsed);
+        if let Some(delta) = self.delta {
+            value["delta"] = json!(delta);
+        }
+    }
+
+    /// Print the shared sections after a detector's plain report.
+    fn print(&self, out: &mut dyn OutputSink) {
+        print_collapsed(out, self.collapsed);
+        print_kept(out, self.kept);
+        print_recent(out, self.recent);
+        print_suppressions_due(out, self.due);
+        print_delta(out, self.delta);
+    }
+
+    /// Write a detector's JSON report with the shared sections added.
+    fn json_report(
+        &self,
+        out: &mut dyn OutputSink,
+        r: &Render<'_>,
+        mut value: Value,
+    ) -> Result<()> {
+        self.insert_json(&mut value);
+        r.json_report(out, value)
+    }
+}
+
+/// Print items kept by `#[deadmod::keep]`, with their reasons.
+pub fn print_kept(out: &mut dyn OutputSink, kept: &[KeptItem]) {
+    if kept.is_empty() {
+        return;
+    }
+    out!(out, "\nKEPT BY ANNOTATION ({}):", kept.len());
+    for item in kept {
+        match item.reason {
+            Some(ref reason) => out!(out, "  {} ({}): {}", item.name, item.file, reason),
+            None => out!(out, "  {} ({})", item.name, item.file),
+        }
+    }
+}
+
+/// Print findings still within the grace period.
+pub fn print_recent(out: &mut dyn OutputSink, recent: &[RecentItem]) {
+    if recent.is_empty() {
+        return;
+    }
+    out!(
+        out,
+        "\nRECENTLY ADDED ({}, within the grace period, not failing):",
+        recent.len()
+    );
+    for recent in recent {
+        out!(
+            out,
+            "  [{}] {} ({}) added {} [id: {}]",
+            recent.item.kind,
+            recent.item.name,
+            recent.item.file.display(),
+            recent.added,
+            recent.item.id
+        );
+    }
+}
+
+/// Print false positives due for re-review.
+pub fn print_suppressions_due(out: &mut dyn OutputSink, due: &[Suppression]) {
+    if due.is_empty() {
+        return;
+    }
+    out!(
+        out,
+        "\nSUPPRESSIONS DUE FOR REVIEW ({}, marked as false positives before the review period):",
+        due.len()
+    );
+    for s in due {
+        out!(
+            out,
+            "  [{}] {} ({}) marked {} by {}: {} [id: {}]",
+            s.kind,
+            s.name,
+            s.file,
+            s.added,
+            s.author,
+            s.reason,
+            s.id
+        );
+    }
+}
+
+/// Print the change since the last run.
+pub fn print_delta(out: &mut dyn OutputSink, delta: Option<&RunDelta>) {
+    if let Some(delta) = delta {
+        out!(out, "\n{}", delta);
+    }
+}
+
+/// Print the summary of what a truncated list left out.
+fn print_more<T: ?Sized>(out: &mut dyn OutputSink, listed: &Truncated<T>) {
+    if let Some(summary) = listed.summary() {
+        out!(out, "  {}", summary);
+    }
+}
+
+fn print_collapsed(out: &mut dyn OutputSink, count: usize) {
+    if count > 0 {
+        out!(
+            out,
+            "\n{} more inside dead modules (use --no-collapse to list them)",
+            count
+        );
+    }
+}
+
+/// Print `impl` blocks whose methods are all dead.
+fn print_dead_impls(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    attr: &Attribution,
+    dead_impls: &[DeadImpl],
+) {
+    if dead_impls.is_empty() {
+        return;
+    }
+    out!(out, "\nDEAD IMPL BLOCKS ({}):", dead_impls.len());
+    let listed = r.limit.apply(dead_impls, |block| {
+        (
+            block.confidence,
+            block.span.end_line.saturating_sub(block.span.start_line) + 1,
+        )
+    });
+    for block in &listed {
+        out!(
+            out,
+            "  {} ({}:{}-{}) [{} method(s)] [confidence: {}] [id: {}]",
+            block.span.label,
+            r.paths.format(&block.file),
+            block.span.start_line,
+            block.span.end_line,
+            block.methods.len(),
+            block.confidence,
+            attr.dead_impl(block).id
+        );
+    }
+    print_more(out, &listed);
+}
+
+/// JSON for `impl` blocks whose methods are all dead.
+fn dead_impls_json(r: &Render<'_>, attr: &Attribution, dead_impls: &[DeadImpl]) -> Value {
+    dead_impls
+        .iter()
+        .map(|block| {
+            json!({
+                "id": attr.dead_impl(block).id,
+                "label": block.span.label,
+                "file": r.paths.format(&block.file),
+                "start_line": block.span.start_line,
+                "end_line": block.span.end_line,
+                "methods": block.methods,
+                "confidence": block.confidence,
+            })
+        })
+        .collect()
+}
+
+/// Visibility marker of a dead function in plain output.
+fn function_marker(func: &FunctionInfo) -> &'static str {
+    if func.test_only {
+        "[test]"
+    } else if func.doc_hidden {
+        "[pub, hidden]"
+    } else if func.visibility.starts_with("pub") {
+        "[pub]"
+    } else {
+        "[priv]"
+    }
+}
+
+/// Marker for `pub` items in plain output.
+fn pub_marker(visibility: &str) -> &'static str {
+    if visibility == "pub" {
+        "[pub]"
+    } else {
+        "[priv]"
+    }
+}
+
+/// `--dead-func` / `--dead-test-helpers`; `uncovered` lists reachable
+/// functions missing from `--coverage` data.
+pub fn render_functions(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    result: &FuncAnalysisResult,
+    uncovered: Option<&[&FunctionInfo]>,
+    findings: &Findings<'_>,
+) -> Result<()> {
+    let attr = findings.attr;
+    let dead_lines = result.dead.iter().map(|f| f.metrics.lines).sum::<usize>();
+    if r.json {
+        let mut value = json!({
+            "total_functions": result.stats.total_functions,
+            "reachable_functions": result.stats.reachable_count,
+            "dead_functions": result.stats.dead_count,
+            "public_dead": result.stats.public_dead,
+            "private_dead": result.stats.private_dead,
+            "scheduled_for_removal": result.scheduled_for_removal().count(),
+            "dead_lines": dead_lines,
+            "dead_impl_blocks": dead_impls_json(r, attr, &result.dead_impls),
+            "dead": result.dead.iter().map(|f| {
+                json!({
+                    "id": attr.function(f).id,
+                    "name": f.name,
+                    "full_path": f.full_path,
+                    "visibility": f.visibility,
+                    "file": r.paths.format(&f.file),
+                    "is_method": f.is_method,
+                    "test_only": f.test_only,
+                    "doc_hidden": f.doc_hidden,
+                    "deprecated": f.deprecated,
+                    "confidence": f.confidence,
+                    "lines": f.metrics.lines,
+                    "params": f.metrics.params,
+                    "complexity": f.metrics.complexity,
+                })
+            }).collect::<Vec<_>>(),
+        });
+        if let Some(uncovered) = uncovered {
+            value["reachable_uncovered"] = uncovered
+                .iter()
+                .map(|f| {
+                    json!({
+                        "name": f.name,
+                        "full_path": f.full_path,
+                        "visibility": f.visibility,
+                        "file": r.paths.format(&f.file),
+                    })
+                })
+                .collect();
+        }
+        return findings.json_report(out, r, value);
+    }
+
+    out!(out, "=== Dead Function Analysis ===\n");
+    out!(out, "Total functions: {}", result.stats.total_functions);
+    out!(out, "Reachable:       {}", result.stats.reachable_count);
+    out!(out, "Dead:            {}", result.stats.dead_count);
+    out!(out, "  - Public:      {}", result.stats.public_dead);
+    out!(out, "  - Private:     {}", result.stats.private_dead);
+    out!(out, "Dead lines:      {}", dead_lines);
+
+    let active: Vec<_> = result
+        .dead
+        .iter()
+        .filter(|f| f.deprecated.is_none())
+        .collect();
+    if !active.is_empty() {
+        out!(out, "\nDEAD FUNCTIONS:");
+        let listed = r.limit.apply(active, |f| (f.confidence, f.metrics.lines));
+        for func in &listed {
+            out!(
+                out,
+                "  {} {} ({}) [confidence: {}] [id: {}]",
+                function_marker(func),
+                func.full_path,
+                r.paths.format(&func.file),
+                func.confidence,
+                attr.function(func).id
+            );
+        }
+        print_more(out, &listed);
+    }
+
+    let scheduled: Vec<_> = result.scheduled_for_removal().collect();
+    if !scheduled.is_empty() {
+        out!(out, "\nSCHEDULED FOR REMOVAL (deprecated):");
+        let listed = r
+            .limit
+            .apply(scheduled, |f| (f.confidence, f.metrics.lines));
+        for func in &listed {
+            let since = func
+                .deprecated
+                .as_ref()
+                .and_then(|d| d.since.as_deref())
+                .map(|v| format!(" since {}", v))
+                .unwrap_or_default();
+            out!(
+                out,
+                "  {} {}{} ({}) [confidence: {}] [id: {}]",
+                function_marker(func),
+                func.full_path,
+                since,
+                r.paths.format(&func.file),
+                func.confidence,
+                attr.function(func).id
+            );
+        }
+        print_more(out, &listed);
+    }
+
+    if let Some(uncovered) = uncovered {
+        if uncovered.is_empty() {
+            out!(out, "\nAll reachable functions are covered.");
+        } else {
+            out!(out, "\nREACHABLE BUT UNCOVERED ({}):", uncovered.len());
+            let listed = r.limit.apply(uncovered.iter().copied(), |f| {
+                (Confidence::High, f.metrics.lines)
+            });
+            for func in &listed {
+                out!(
+                    out,
+                    "  {} {} ({})",
+                    function_marker(func),
+                    func.full_path,
+                    r.paths.format(&func.file)
+                );
+            }
+            print_more(out, &listed);
+        }
+    }
+
+    print_dead_impls(out, r, attr, &result.dead_impls);
+
+    if result.dead.is_empty() {
+        out!(out, "\nNo dead functions found.");
+    }
+
+    findings.print(out);
+    Ok(())
+}
+
+/// `--dead-traits`.
+pub fn render_traits(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    result: &TraitAnalysisResult,
+    findings: &Findings<'_>,
+) -> Result<()> {
+    let attr = findings.attr;
+    if r.json {
+        let value = json!({
+            "total_trait_methods": result.stats.total_trait_methods,
+            "total_impl_methods": result.stats.total_impl_methods,
+            "dead_trait_methods": result.stats.dead_trait_method_count,
+            "dead_impl_methods": result.stats.dead_impl_method_count,
+            "required_methods": result.stats.required_methods,
+            "provided_methods": result.stats.provided_methods,
+            "dead_traits": result.dead_trait_methods.iter().map(|m| {
+                json!({
+                    "id": attr.trait_method(m).id,
+                    "trait_name": m.trait_name,
+                    "method_name": m.method_name,
+                    "full_path": m.full_path,
+                    "visibility": m.visibility,
+                    "is_required": m.is_required,
+                    "file": r.paths.format(&m.file),
+                    "confidence": m.confidence,
+                })
+            }).collect::<Vec<_>>(),
+            "dead_impls": result.dead_impl_methods.iter().map(|m| {
+                json!({
+                    "id": attr.impl_method(m).id,
+                    "trait_name": m.trait_name,
+                    "type_name": m.type_name,
+                    "method_name": m.method_name,
+                    "full_id": m.full_id,
+                    "file": r.paths.format(&m.file),
+                    "confidence": m.confidence,
+                })
+            }).collect::<Vec<_>>(),
+            "dead_impl_blocks": dead_impls_json(r, attr, &result.dead_impls),
+        });
+        return findings.json_report(out, r, value);
+    }
+
+    out!(out, "=== Dead Trait Method Analysis ===\n");
+    out!(
+        out,
+        "Total trait methods:  {}",
+        result.stats.total_trait_methods
+    );
+    out!(
+        out,
+        "  - Required:         {}",
+        result.stats.required_methods
+    );
+    out!(
+        out,
+        "  - Provided:         {}",
+        result.stats.provided_methods
+    );
+    out!(
+        out,
+        "Total impl methods:   {}",
+        result.stats.total_impl_methods
+    );
+    out!(out);
+    out!(
+        out,
+        "Dead trait methods:   {}",
+        result.stats.dead_trait_method_count
+    );
+    out!(
+        out,
+        "Dead impl methods:    {}",
+        result.stats.dead_impl_method_count
+    );
+    out!(
+        out,
+        "Dead impl blocks:     {}",
+        result.stats.dead_impl_block_count
+    );
+
+    if !result.dead_trait_methods.is_empty() {
+        out!(out, "\nDEAD TRAIT METHODS:");
+        let listed = r
+            .limit
+            .apply(&result.dead_trait_methods, |m| (m.confidence, 0));
+        for method in &listed {
+            let req_marker = if method.is_required {
+                "[required]"
+            } else {
+                "[provided]"
+            };
+            out!(
+                out,
+                "  {} {}::{} ({}) [confidence: {}] [id: {}]",
+                req_marker,
+                method.trait_name,
+                method.method_name,
+                r.paths.format(&method.file),
+                method.confidence,
+                attr.trait_method(method).id
+            );
+        }
+        print_more(out, &listed);
+    }
+
+    if !result.dead_impl_methods.is_empty() {
+        out!(out, "\nDEAD IMPL METHODS:");
+        let listed = r
+            .limit
+            .apply(&result.dead_impl_methods, |m| (m.confidence, 0));
+        for method in &listed {
+            out!(
+                out,
+                "  impl {} for {} :: {} ({}) [confidence: {}] [id: {}]",
+                method.trait_name,
+                method.type_name,
+                method.method_name,
+                r.paths.format(&method.file),
+                method.confidence,
+                attr.impl_method(method).id
+            );
+        }
+        print_more(out, &listed);
+    }
+
+    print_dead_impls(out, r, attr, &result.dead_impls);
+
+    if result.dead_trait_methods.is_empty() && result.dead_impl_methods.is_empty() {
+        out!(out, "\nNo dead trait methods found.");
+    }
+
+    findings.print(out);
+    Ok(())
+}
+
+/// `--dead-generics`.
+pub fn render_generics(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    result: &GenericAnalysisResult,
+    findings: &Findings<'_>,
+) -> Result<()> {
+    let attr = findings.attr;
+    if r.json {
+        let value = json!({
+            "total_declared_types": result.stats.total_declared_types,
+            "total_declared_lifetimes": result.stats.total_declared_lifetimes,
+            "total_declared_consts": result.stats.total_declared_consts,
+            "dead_types": result.stats.dead_types,
+            "dead_lifetimes": result.stats.dead_lifetimes,
+            "dead_consts": result.stats.dead_consts,
+            "dead": result.dead.iter().map(|d| {
+                json!({
+                    "id": attr.generic(d).id,
+                    "name": d.name,
+                    "kind": format!("{:?}", d.kind),
+                    "parent": d.parent,
+                    "parent_kind": format!("{:?}", d.parent_kind),
+                    "file": r.paths.format(&d.file),
+                    "unused_bounds": d.unused_bounds,
+                    "confidence": d.confidence,
+                })
+            }).collect::<Vec<_>>(),
+        });
+        return findings.json_report(out, r, value);
+    }
+
+    out!(out, "=== Dead Generic Parameter Analysis ===\n");
+    out!(
+        out,
+        "Declared type parameters:     {}",
+        result.stats.total_declared_types
+    );
+    out!(
+        out,
+        "Declared lifetimes:           {}",
+        result.stats.total_declared_lifetimes
+    );
+    out!(
+        out,
+        "Declared const parameters:    {}",
+        result.stats.total_declared_consts
+    );
+    out!(out);
+    out!(
+        out,
+        "Dead type parameters:         {}",
+        result.stats.dead_types
+    );
+    out!(
+        out,
+        "Dead lifetimes:               {}",
+        result.stats.dead_lifetimes
+    );
+    out!(
+        out,
+        "Dead const parameters:        {}",
+        result.stats.dead_consts
+    );
+
+    if !result.dead.is_empty() {
+        out!(out, "\nDEAD GENERIC PARAMETERS:");
+        let listed = r.limit.apply(&result.dead, |d| (d.confidence, 0));
+        for d in &listed {
+            let kind_str = match d.kind {
+                GenericKind::Type => "type",
+                GenericKind::Lifetime => "lifetime",
+                GenericKind::Const => "const",
+            };
+            let bounds_str = if !d.unused_bounds.is_empty() {
+                format!(" (bounds: {})", d.unused_bounds.join(", "))
+            } else {
+                String::new()
+            };
+            out!(
+                out,
+                "  [{}] {} in {}{} ({}) [confidence: {}] [id: {}]",
+                kind_str,
+                d.name,
+                d.parent,
+                bounds_str,
+                r.paths.format(&d.file),
+                d.confidence,
+                attr.generic(d).id
+            );
+        }
+        print_more(out, &listed);
+    } else {
+        out!(out, "\nNo dead generic parameters found.");
+    }
+
+    findings.print(out);
+    Ok(())
+}
+
+/// `--dead-macros`.
+pub fn render_macros(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    result: &MacroAnalysisResult,
+    findings: &Findings<'_>,
+) -> Result<()> {
+    let attr = findings.attr;
+    if r.json {
+        let value = json!({
+            "total_declared": result.stats.total_declared,
+            "exported_count": result.stats.exported_count,
+            "dead_count": result.stats.dead_count,
+            "dead_exported_count": result.stats.dead_exported_count,
+            "exported_assumed_used": result.stats.exported_assumed_used,
+            "exported_workspace_used": result.stats.exported_workspace_used,
+            "dead": result.dead.iter().map(|m| {
+                json!({
+                    "id": attr.macro_def(m).id,
+                    "name": m.name,
+                    "exported": m.exported,
+                    "kind": m.kind,
+                    "file": r.paths.format(&m.file),
+                    "module_path": m.module_path,
+                    "confidence": m.confidence,
+                })
+            }).collect::<Vec<_>>(),
+        });
+        return findings.json_report(out, r, value);
+    }
+
+    out!(out, "=== Dead Macro Analysis ===\n");
+    out!(
+        out,
+        "Total macros declared:  {}",
+        result.stats.total_declared
+    );
+    out!(
+        out,
+        "  - Exported:           {}",
+        result.stats.exported_count
+    );
+    if result.stats.exported_workspace_used > 0 {
+        out!(
+            out,
+            "  - Used by members:    {}",
+            result.stats.exported_workspace_used
+        );
+    }
+    if result.stats.exported_assumed_used > 0 {
+        out!(
+            out,
+            "  - Assumed used:       {}",
+            result.stats.exported_assumed_used
+        );
+    }
+    out!(out);
+    out!(out, "Dead macros:            {}", result.stats.dead_count);
+    out!(
+        out,
+        "  - Exported dead:      {}",
+        result.stats.dead_exported_count
+    );
+
+    if !result.dead.is_empty() {
+        out!(out, "\nDEAD MACROS:");
+        let listed = r.limit.apply(&result.dead, |m| (m.confidence, 0));
+        for m in &listed {
+            let export_marker = if m.exported { "[exported]" } else { "[local]" };
+            let kind = if m.kind == MacroKind::MacroRules {
+                String::new()
+            } else {
+                format!(" <{}>", m.kind)
+            };
+            out!(
+                out,
+                "  {} {}{} ({}) [confidence: {}] [id: {}]",
+                export_marker,
+                m.name,
+                kind,
+                r.paths.format(&m.file),
+                m.confidence,
+                attr.macro_def(m).id
+            );
+        }
+        print_more(out, &listed);
+    } else {
+        out!(out, "\nNo dead macros found.");
+    }
+
+    findings.print(out);
+    Ok(())
+}
+
+/// `--dead-constants`.
+pub fn render_constants(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    result: &ConstAnalysisResult,
+    findings: &Findings<'_>,
+) -> Result<()> {
+    let attr = findings.attr;
+    if r.json {
+        let value = json!({
+            "total_declared": result.stats.total_declared,
+            "const_count": result.stats.const_count,
+            "static_count": result.stats.static_count,
+            "dead_count": result.stats.dead_count,
+            "dead_const_count": result.stats.dead_const_count,
+            "dead_static_count": result.stats.dead_static_count,
+            "dead": result.dead.iter().map(|c| {
+                json!({
+                    "id": attr.constant(c).id,
+                    "name": c.name,
+                    "is_static": c.is_static,
+                    "visibility": c.visibility,
+                    "file": r.paths.format(&c.file),
+                    "module_path": c.module_path,
+                    "confidence": c.confidence,
+                })
+            }).collect::<Vec<_>>(),
+        });
+        return findings.json_report(out, r, value);
+    }
+
+    out!(out, "=== Dead Constants/Statics Analysis ===\n");
+    out!(out, "Total declared:     {}", result.stats.total_declared);
+    out!(out, "  - Constants:      {}", result.stats.const_count);
+    out!(out, "  - Statics:        {}", result.stats.static_count);
+    out!(out);
+    out!(out, "Dead count:         {}", result.stats.dead_count);
+    out!(out, "  - Dead consts:    {}", result.stats.dead_const_count);
+    out!(
+        out,
+        "  - Dead statics:   {}",
+        result.stats.dead_static_count
+    );
+
+    if !result.dead.is_empty() {
+        out!(out, "\nDEAD CONSTANTS/STATICS:");
+        let listed = r.limit.apply(&result.dead, |c| (c.confidence, 0));
+        for c in &listed {
+            let kind = if c.is_static { "static" } else { "const" };
+            out!(
+                out,
+                "  {} {} {} ({}) [confidence: {}] [id: {}]",
+                pub_marker(&c.visibility),
+                kind,
+                c.name,
+                r.paths.format(&c.file),
+                c.confidence,
+                attr.constant(c).id
+            );
+        }
+        print_more(out, &listed);
+    } else {
+        out!(out, "\nNo dead constants/statics found.");
+    }
+
+    findings.print(out);
+    Ok(())
+}
+
+/// `--dead-variants`.
+pub fn render_variants(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    result: &EnumAnalysisResult,
+    findings: &Findings<'_>,
+) -> Result<()> {
+    let attr = findings.attr;
+    if r.json {
+        let value = json!({
+            "total_variants": result.stats.total_variants,
+            "total_enums": result.stats.total_enums,
+            "dead_variant_count": result.stats.dead_variant_count,
+            "dead_enum_count": result.stats.dead_enum_count,
+            "dead": result.dead.iter().map(|v| {
+                json!({
+                    "id": attr.variant(v).id,
+                    "enum_name": v.enum_name,
+                    "variant_name": v.variant_name,
+                    "full_name": v.full_name,
+                    "visibility": v.visibility,
+                    "file": r.paths.format(&v.file),
+                    "confidence": v.confidence,
+                })
+            }).collect::<Vec<_>>(),
+        });
+        return findings.json_report(out, r, value);
+    }
+
+    out!(out, "=== Dead Enum Variant Analysis ===\n");
+    out!(out, "Total enums:        {}", result.stats.total_enums);
+    out!(out, "Total variants:     {}", result.stats.total_variants);
+    out!(out);
+    out!(
+        out,
+        "Dead variants:      {}",
+        result.stats.dead_variant_count
+    );
+    out!(out, "Fully dead enums:   {}", result.stats.dead_enum_count);
+
+    if !result.dead.is_empty() {
+        out!(out, "\nDEAD ENUM VARIANTS:");
+        let listed = r.limit.apply(&result.dead, |v| (v.confidence, 0));
+        for v in &listed {
+            out!(
+                out,
+                "  {} {} ({}) [confidence: {}] [id: {}]",
+                pub_marker(&v.visibility),
+                v.full_name,
+                r.paths.format(&v.file),
+                v.confidence,
+                attr.variant(v).id
+            );
+        }
+        print_more(out, &listed);
+    } else {
+        out!(out, "\nNo dead enum variants found.");
+    }
+
+    findings.print(out);
+    Ok(())
+}
+
+/// `--dead-match-arms`.
+pub fn render_match_arms(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    result: &MatchArmAnalysisResult,
+    findings: &Findings<'_>,
+) -> Result<()> {
+    let attr = findings.attr;
+    if r.json {
+        let value = json!({
+            "total_match_expressions": result.stats.total_match_expressions,
+            "total_arms": result.stats.total_arms,
+            "wildcard_count": result.stats.wildcard_count,
+            "dead_arm_count": result.stats.dead_arm_count,
+            "masked_arm_count": result.stats.masked_arm_count,
+            "dead_arms": result.dead_arms.iter().map(|a| {
+                json!({
+                    "id": attr.match_arm(a).id,
+                    "pattern": a.pattern,
+                    "reason": format!("{:?}", a.reason),
+                    "explanation": a.explanation(),
+                    "enum": a.enum_name,
+                    "variant": a.variant_name,
+                    "file": r.paths.format(&a.file),
+                    "line": a.line,
+                    "column": a.column,
+                    "match_line": a.match_line,
+                    "confidence": a.confidence,
+                })
+            }).collect::<Vec<_>>(),
+        });
+        return findings.json_report(out, r, value);
+    }
+
+    out!(out, "=== Dead Match Arm Analysis ===\n");
+    out!(
+        out,
+        "Total match expressions: {}",
+        result.stats.total_match_expressions
+    );
+    out!(out, "Total arms:              {}", result.stats.total_arms);
+    out!(
+        out,
+        "Wildcard arms:           {}",
+        result.stats.wildcard_count
+    );
+    out!(out);
+    out!(
+        out,
+        "Dead/Masked arms:        {}",
+        result.stats.dead_arm_count
+    );
+
+    if !result.dead_arms.is_empty() {
+        out!(out, "\nDEAD/MASKED MATCH ARMS:");
+        let listed = r.limit.apply(&result.dead_arms, |a| (a.confidence, 0));
+        for arm in &listed {
+            let reason = match arm.reason {
+                DeadArmReason::NeverUsed => "[never-used]",
+                DeadArmReason::MaskedByWildcard => "[masked]",
+                DeadArmReason::NonFinalWildcard => "[non-final-wildcard]",
+            };
+            out!(
+                out,
+                "  {} {} ({}:{}) [confidence: {}] [id: {}]",
+                reason,
+                arm.pattern,
+                r.paths.format(&arm.file),
+                arm.line,
+                arm.confidence,
+                attr.match_arm(arm).id
+            );
+            out!(
+                out,
+                "      {} (match at line {})",
+                arm.explanation(),
+                arm.match_line
+            );
+        }
+        print_more(out, &listed);
+    } else {
+        out!(out, "\nNo dead match arms found.");
+    }
+
+    findings.print(out);
+    Ok(())
+}
+
+/// `--dead-params`.
+pub fn render_params(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    result: &ParamAnalysisResult,
+    findings: &Findings<'_>,
+) -> Result<()> {
+    let attr = findings.attr;
+    if r.json {
+        let value = json!({
+            "total_functions": result.stats.total_functions,
+            "total_params": result.stats.total_params,
+            "dead_param_count": result.stats.dead_param_count,
+            "dead": result.dead.iter().map(|p| {
+                json!({
+                    "id": attr.param(p).id,
+                    "name": p.name,
+                    "function": p.function,
+                    "position": p.position,
+                    "visibility": p.visibility,
+                    "file": r.paths.format(&p.file),
+                    "line": p.line,
+                    "column": p.column,
+                    "confidence": p.confidence,
+                })
+            }).collect::<Vec<_>>(),
+        });
+        return findings.json_report(out, r, value);
+    }
+
+    out!(out, "=== Dead Parameter Analysis ===\n");
+    out!(out, "Functions analyzed:  {}", result.stats.total_functions);
+    out!(out, "Parameters:          {}", result.stats.total_params);
+    out!(out);
+    out!(
+        out,
+        "Dead parameters:     {}",
+        result.stats.dead_param_count
+    );
+
+    if !result.dead.is_empty() {
+        out!(out, "\nDEAD PARAMETERS:");
+        let listed = r.limit.apply(&result.dead, |p| (p.confidence, 0));
+        for p in &listed {
+            out!(
+                out,
+                "  {} in {} ({}:{}) [confidence: {}] [id: {}]",
+                p.name,
+                p.function,
+                r.paths.format(&p.file),
+                p.line,
+                p.confidence,
+                attr.param(p).id
+            );
+        }
+        print_more(out, &listed);
+    } else {
+        out!(out, "\nNo dead parameters found.");
+    }
+
+    findings.print(out);
+    Ok(())
+}
+
+/// `--dead-fields`.
+pub fn render_fields(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    result: &FieldAnalysisResult,
+    findings: &Findings<'_>,
+) -> Result<()> {
+    let attr = findings.attr;
+    if r.json {
+        let value = json!({
+            "total_structs": result.stats.total_structs,
+            "total_fields": result.stats.total_fields,
+            "write_only_count": result.stats.write_only_count,
+            "unused_count": result.stats.unused_count,
+            "dead": result.dead.iter().map(|f| {
+                json!({
+                    "id": attr.field(f).id,
+                    "name": f.name,
+                    "struct": f.struct_name,
+                    "kind": f.kind,
+                    "visibility": f.visibility,
+                    "file": r.paths.format(&f.file),
+                    "line": f.line,
+                    "column": f.column,
+                    "confidence": f.confidence,
+                })
+            }).collect::<Vec<_>>(),
+        });
+        return findings.json_report(out, r, value);
+    }
+
+    out!(out, "=== Dead Field Analysis ===\n");
+    out!(out, "Structs analyzed:    {}", result.stats.total_structs);
+    out!(out, "Fields:              {}", result.stats.total_fields);
+    out!(out);
+    out!(
+        out,
+        "Write-only fields:   {}",
+        result.stats.write_only_count
+    );
+    out!(out, "Unused fields:       {}", result.stats.unused_count);
+
+    if !result.dead.is_empty() {
+        out!(out, "\nDEAD FIELDS:");
+        let listed = r.limit.apply(&result.dead, |f| (f.confidence, 0));
+        for f in &listed {
+            out!(
+                out,
+                "  [{}] {} ({}:{}) [confidence: {}] [id: {}]",
+                f.kind,
+                f.full_name(),
+                r.paths.format(&f.file),
+                f.line,
+                f.confidence,
+                attr.field(f).id
+            );
+        }
+        print_more(out, &listed);
+    } else {
+        out!(out, "\nNo dead fields found.");
+    }
+
+    findings.print(out);
+    Ok(())
+}
+
+/// `--dead-deps`.
+pub fn render_dead_deps(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    result: &DeadDepsResult,
+) -> Result<()> {
+    if r.json {
+        let value = json!({
+            "checked": result.checked,
+            "dead_count": result.dead.len(),
+            "dead": result.dead.iter().map(|d| {
+                json!({
+                    "name": d.name,
+                    "kind": d.kind,
+                    "manifest": r.paths.format(&d.manifest),
+                    "target": d.target,
+                    "optional": d.optional,
+                    "dead_users": d.dead_users.iter().map(|p| r.paths.format(p)).collect::<Vec<_>>(),
+                })
+            }).collect::<Vec<_>>(),
+        });
+        return r.json_report(out, value);
+    }
+
+    out!(out, "=== Dead Dependency Analysis ===\n");
+    out!(out, "Dependencies checked:  {}", result.checked);
+    out!(out, "Unused dependencies:   {}", result.dead.len());
+
+    if !result.dead.is_empty() {
+        out!(out, "\nUNUSED DEPENDENCIES:");
+        let listed = r.limit.apply(&result.dead, |_| (Confidence::High, 0));
+        for d in &listed {
+            let mut notes = Vec::new();
+            if let Some(target) = &d.target {
+                notes.push(format!(
+                    "[{}: {}]",
+                    if d.kind == DependencyKind::Patch {
+                        "registry"
+                    } else {
+                        "target"
+                    },
+                    target
+                ));
+            }
+            if d.optional {
+                notes.push("[optional]".to_string());
+            }
+            let notes: String = notes.iter().map(|n| format!(" {}", n)).collect();
+            out!(
+                out,
+                "  [{}] {} ({}){}",
+                d.kind,
+                d.name,
+                r.paths.format(&d.manifest),
+                notes
+            );
+            for user in &d.dead_users {
+                out!(
+                    out,
+                    "      only used by dead module {}",
+                    r.paths.format(user)
+                );
+            }
+        }
+        print_more(out, &listed);
+    } else {
+        out!(out, "\nNo unused dependencies found.");
+    }
+    Ok(())
+}
+
+/// `--discover`: the crate's directories as module clusters.
+pub fn render_discovery(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    root: &Path,
+    discovery: &ModuleDiscovery,
+) -> Result<()> {
+    if r.json {
+        let clusters: Vec<_> = discovery
+            .clusters
+            .values()
+            .map(|c| {
+                json!({
+                    "name": c.name,
+                    "path": r.paths.format(&c.path),
+                    "relative_path": c.relative_path,
+                    "depth": c.depth,
+                    "has_mod_file": c.mod_file.is_some(),
+                    "modules": c.modules.iter().map(|m| &m.name).collect::<Vec<_>>(),
+                    "children": c.children,
+                    "parent": c.parent,
+                })
+            })
+            .collect();
+
+        let value = json!({
+            "file_count": discovery.file_count,
+            "cluster_count": discovery.clusters.len(),
+            "crate_roots": discovery.crate_roots.iter().map(|p| r.paths.format(p)).collect::<Vec<_>>(),
+            "clusters": clusters,
+        });
+        return r.json_report(out, value);
+    }
+
+    out!(out, "=== Filesystem Module Discovery ===\n");
+    out!(out, "Crate root: {}", r.paths.format(root));
+    out!(out, "Total .rs files: {}", discovery.file_count);
+    out!(
+        out,
+        "Clusters (directories): {}\n",
+        discovery.clusters.len()
+    );
+
+    // Cluster hierarchy as a tree, each cluster followed by its modules
+    out!(out, "CLUSTER HIERARCHY:");
+    for (name, children) in &get_cluster_tree(discovery) {
+        let depth = name.matches("::").count();
+        let icon = if children.is_empty() { "📄" } else { "📁" };
+        out!(out, "{}{}  {}", "  ".repeat(depth), icon, name);
+
+        if let Some(cluster) = discovery.clusters.get(name) {
+            for module in &cluster.modules {
+                let status = if module.is_crate_root { "🎯" } else { "  " };
+                out!(out, "{}{}  {}", "  ".repeat(depth + 1), status, module.name);
+            }
+        }
+    }
+
+    if !discovery.crate_roots.is_empty() {
+        out!(out, "\nCRATE ROOTS:");
+        for root_file in &discovery.crate_roots {
+            out!(out, "  🎯 {}", r.paths.format(root_file));
+        }
+    }
+    Ok(())
+}
+
+/// `--per-target`: code no target reaches and code only some targets use.
+pub struct TargetMatrix<'a> {
+    /// Targets, sorted
+    pub targets: Vec<&'a str>,
+    /// Modules no target reaches
+    pub dead_modules: &'a [String],
+    /// Modules some targets reach, with those targets
+    pub partial_modules: &'a BTreeMap<String, Vec<String>>,
+    /// Functions no target reaches
+    pub dead_funcs: &'a [String],
+    /// Functions some targets reach, with those targets
+    pub partial_funcs: &'a BTreeMap<String, Vec<String>>,
+}
+
+/// `--per-target`.
+pub fn render_per_target(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    matrix: &TargetMatrix<'_>,
+) -> Result<()> {
+    if r.json {
+        let value = json!({
+            "targets": matrix.targets,
+            "modules": {
+                "dead_for_all": matrix.dead_modules,
+                "used_by_some": matrix.partial_modules,
+            },
+            "functions": {
+                "dead_for_all": matrix.dead_funcs,
+                "used_by_some": matrix.partial_funcs,
+            },
+        });
+        return r.json_report(out, value);
+    }
+
+    out!(out, "=== Per-Target Analysis ===\n");
+    out!(
+        out,
+        "Targets ({}): {}",
+        matrix.targets.len(),
+        matrix.targets.join(", ")
+    );
+
+    let sections = [
+        ("MODULES", matrix.dead_modules, matrix.partial_modules),
+        ("FUNCTIONS", matrix.dead_funcs, matrix.partial_funcs),
+    ];
+    for (kind, dead, partial) in sections {
+        if !dead.is_empty() {
+            out!(out, "\n{} DEAD FOR ALL TARGETS ({}):", kind, dead.len());
+            let listed = r.limit.apply(dead, |_| (Confidence::High, 0));
+            for item in &listed {
+                out!(out, "  - {}", item);
+            }
+            print_more(out, &listed);
+        }
+        if !partial.is_empty() {
+            out!(out, "\n{} USED BY SOME TARGETS ({}):", kind, partial.len());
+            let partial: Vec<_> = partial.iter().collect();
+            let listed = r.limit.apply(&partial, |_| (Confidence::High, 0));
+            for (item, users) in &listed {
+                out!(out, "  - {} [{}]", item, users.join(", "));
+            }
+            print_more(out, &listed);
+        }
+    }
+
+    if matrix.dead_modules.is_empty() && matrix.dead_funcs.is_empty() {
+        out!(out, "\nNo code is dead for all targets.");
+    }
+    Ok(())
+}
+
+/// `--group-by module`: every detector's findings as a module tree.
+pub fn render_grouped(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    tree: &ModuleNode,
+    result: &AnalysisResult,
+    due: &[Suppression],
+    delta: Option<&RunDelta>,
+) -> Result<()> {
+    if r.json {
+        let mut value = json!({
+            "total": tree.total(),
+            "root": tree,
+        });
+        if !(result.kept.is_empty() && result.recent.is_empty() && due.is_empty()) {
+            value["kept"] = serde_json::to_value(&result.kept)?;
+            value["recent"] = serde_json::to_value(&result.recent)?;
+            value["review_due"] = serde_json::to_value(due)?;
+        }
+        if let Some(delta) = delta {
+            value["delta"] = serde_json::to_value(delta)?;
+        }
+        return r.json_report(out, value);
+    }
+
+    let (listed, hidden) = tree.limited(&r.limit);
+    out.write(&format_grouped_plain(&listed));
+    if let Some(summary) = more_summary(hidden) {
+        out!(out, "{}", summary);
+    }
+    print_kept(out, &result.kept);
+    print_recent(out, &result.recent);
+    print_suppressions_due(out, due);
+    print_delta(out, delta);
+    Ok(())
+}
+
+/// `deadmod file <FILE>`: findings of one file; `file` as displayed.
+pub fn render_file(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    file: &str,
+    findings: &FileFindings,
+) -> Result<()> {
+    if r.json {
+        let value = json!({
+            "file": file,
+            "dead_module": findings.dead_module.is_some(),
+            "dead": findings.items,
+            "collapsed": findings.collapsed,
+            "kept": findings.kept,
+            "recent": findings.recent,
+        });
+        return r.json_report(out, value);
+    }
+
+    if !findings.has_dead_code() {
+        out!(out, "No dead code found in {}.", file);
+    } else {
+        out!(out, "DEAD CODE IN {} ({}):", file, findings.dead_count());
+        if findings.dead_module.is_some() {
+            out!(out, "  [module] unreachable from any crate root");
+        }
+        let print = |out: &mut dyn OutputSink, indent: &str, f: &FileFinding| {
+            let item = &f.item;
+            out!(
+                out,
+                "{}[{}] {} (line {}) [confidence: {}] [id: {}]",
+                indent,
+                item.kind,
+                item.name,
+                f.span.start_line,
+                item.confidence,
+                item.id
+            );
+        };
+        findings
+            .collapsed
+            .iter()
+            .for_each(|f| print(out, "    ", f));
+        findings.items.iter().for_each(|f| print(out, "  ", f));
+    }
+    print_kept(out, &findings.kept);
+    print_recent(out, &findings.recent);
+    Ok(())
+}
+
+/// `deadmod find`: definitions of a name with their liveness, and the
+/// places it is mentioned.
+pub fn render_find(out: &mut dyn OutputSink, r: &Render<'_>, search: &SymbolSearch) -> Result<()> {
+    if r.json {
+        return r.json_report(out, json!(search));
+    }
+
+    if search.definitions.is_empty() && search.references.is_empty() {
+        out!(out, "Nothing named `{}` found.", search.query);
+        return Ok(());
+    }
+    if search.definitions.is_empty() {
+        out!(out, "DEFINITIONS: none in this crate");
+    } else {
+        out!(out, "DEFINITIONS ({}):", search.definitions.len());
+        for def in &search.definitions {
+            let at = match def.line {
+                0 => def.file.display().to_string(),
+                line => format!("{}:{}", def.file.display(), line),
+            };
+            out!(
+                out,
+                "  [{}] {} ({}) [{}] [id: {}]",
+                def.kind,
+                def.path,
+                at,
+                def.liveness,
+                def.id
+            );
+        }
+    }
+    out!(out);
+    if search.references.is_empty() {
+        out!(out, "REFERENCES: the name appears nowhere else.");
+    } else {
+        out!(out, "REFERENCES ({}):", search.references.len());
+        for reference in &search.references {
+            out!(
+                out,
+                "  {}:{}: [{}] {}",
+                reference.file.display(),
+                reference.line,
+                reference.role,
+                reference.text
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `deadmod self-check`: findings not in `deadmod-expected.toml`, and
+/// expected ones no longer reported, out of `findings` reported.
+pub fn render_self_check(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    diff: &ExpectedDiff,
+    findings: usize,
+) -> Result<()> {
+    if r.json {
+        let value = json!({
+            "passed": diff.passed(),
+            "expected_file": EXPECTED_FILE,
+            "findings": findings,
+            "new": diff.new,
+            "fixed": diff.fixed,
+        });
+        return r.json_report(out, value);
+    }
+
+    out!(out, "=== Self-Check ===\n");
+    out!(out, "Findings:            {}", findings);
+    out!(out, "Not expected:        {}", diff.new.len());
+    out!(out, "No longer reported:  {}", diff.fixed.len());
+
+    let print = |out: &mut dyn OutputSink, f: &ExpectedFinding| {
+        out!(
+            out,
+            "  - [{}] {} ({}) [crate: {}] [id: {}]",
+            f.kind,
+            f.name,
+            f.file,
+            f.krate,
+            f.id
+        );
+    };
+    if !diff.new.is_empty() {
+        out!(out, "\nNEW FINDINGS (not in {}):", EXPECTED_FILE);
+        diff.new.iter().for_each(|f| print(out, f));
+    }
+    if !diff.fixed.is_empty() {
+        out!(
+            out,
+            "\nNO LONGER REPORTED (drop them with `deadmod self-check --update`):"
+        );
+        diff.fixed.iter().for_each(|f| print(out, f));
+    }
+
+    if diff.passed() {
+        out!(out, "\nSelf-check passed: every finding is expected.");
+    } else {
+        out!(
+            out,
+            "\nSelf-check failed: {} new finding(s).",
+            diff.new.len()
+        );
+    }
+    Ok(())
+}
+
+/// `--workspace`: dead modules per member crate, plus their DOT graphs
+/// with `--dot`.
+pub fn render_workspace(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    results: &[CrateAnalysis],
+    dot: bool,
+) -> Result<()> {
+    if r.json {
+        let value: Vec<Value> = results
+            .iter()
+            .map(|result| {
+                json!({
+                    "crate": result.name,
+                    "root": result.root.display().to_string(),
+                    "dead_modules": result.dead_modules,
+                    "reachable_modules": result.reachable_modules,
+                })
+            })
+            .collect();
+        r.json_report(out, value.into())?;
+    } else {
+        for result in results {
+            out!(out, "=== Crate: {} ===", result.name);
+            if result.dead_modules.is_empty() {
+                out!(out, "No dead modules found.\n");
+            } else {
+                let listed = r
+                    .limit
+                    .apply(&result.dead_modules, |_| (Confidence::High, 0));
+                for m in &listed {
+                    out!(out, "  - {}", m);
+                }
+                print_more(out, &listed);
+                out!(out);
+            }
+        }
+    }
+
+    if dot {
+        for result in results {
+            out!(out, "// === DOT for crate: {} ===", result.name);
+            out!(out, "{}", result.dot_output);
+        }
+    }
+    Ok(())
+}
+
+/// `deadmod explain`: the evidence behind one finding.
+pub fn render_explain(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    evidence: &FindingEvidence,
+) -> Result<()> {
+    if r.json {
+        return r.json_report(out, json!(evidence));
+    }
+
+    out!(
+        out,
+        "[{}] {} [id: {}]",
+        evidence.kind,
+        evidence.name,
+        evidence.id
+    );
+    out!(out, "  File: {}", evidence.file.display());
+    out!(out, "  Module: {}", evidence.module_path);
+    if let Some(confidence) = evidence.confidence {
+        out!(out, "  Confidence: {}", confidence);
+    }
+
+    out!(out);
+    if evidence.entry_points.is_empty() {
+        out!(
+            out,
+            "ENTRY POINTS: none, any reference anywhere would keep it alive"
+        );
+    } else {
+        let what = if evidence.kind == DeadItemKind::Module {
+            "root modules"
+        } else {
+            "functions"
+        };
+        out!(
+            out,
+            "ENTRY POINTS ({} {}), not reachable from any of them:",
+            evidence.entry_points.len(),
+            what
+        );
+        for entry in &evidence.entry_points {
+            out!(out, "  - {}", entry);
+        }
+    }
+
+    out!(out);
+    out!(
+        out,
+        "SEARCHED {} file(s) for references.",
+        evidence.searched.len()
+    );
+
+    out!(out);
+    if evidence.mentions.is_empty() {
+        out!(out, "MENTIONS: the name appears nowhere else.");
+    } else {
+        out!(
+            out,
+            "MENTIONS ({}), none counted as a reference:",
+            evidence.mentions.len()
+        );
+        for mention in &evidence.mentions {
+            out!(
+                out,
+                "  {}:{}: {}",
+                mention.file.display(),
+                mention.line,
+                mention.text
+            );
+            out!(out, "      -> {}", mention.exclusion);
+        }
+    }
+    Ok(())
+}
+
+/// `deadmod check-removal`: textual references that would break if the
+/// item were removed.
+pub fn render_check_removal(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    check: &RemovalCheck,
+) -> Result<()> {
+    if r.json {
+        return r.json_report(out, json!(check));
+    }
+
+    out!(out, "[{}] {} [id: {}]", check.kind, check.name, check.id);
+    out!(out, "  File: {}", check.file.display());
+    out!(out);
+    if check.safe {
+        out!(
+            out,
+            "SAFE TO REMOVE: no string literal, intra-doc link, macro token or config file names it."
+        );
+    } else {
+        out!(
+            out,
+            "NOT SAFE TO REMOVE: {} textual reference(s) the analysis cannot see:",
+            check.references.len()
+        );
+        for reference in &check.references {
+            out!(
+                out,
+                "  {}:{}: {}",
+                reference.file.display(),
+                reference.line,
+                reference.text
+            );
+            out!(out, "      -> {}", reference.kind);
+        }
+    }
+    Ok(())
+}
+
+/// `deadmod mark-false-positive`: the recorded suppression, `replaced` if
+/// it updated an earlier one.
+pub fn render_marked(out: &mut dyn OutputSink, suppression: &Suppression, replaced: bool) {
+    out!(
+        out,
+        "{} [{}] {} ({}) [id: {}] as a false positive in {}",
+        if replaced { "Updated" } else { "Marked" },
+        suppression.kind,
+        suppression.name,
+        suppression.file,
+        suppression.id,
+        SUPPRESSIONS_FILE
+    );
+}
+
+/// An output destination (`--output`, `--badge-json`, an export file)
+/// rejected by path validation, with its hint.
+pub fn render_output_path_error(out: &mut dyn OutputSink, err: &DeadmodError) {
+    out!(out, "[ERROR] Invalid output path: {}", err);
+    if let Some(hint) = err.hint() {
+        out!(out, "[HINT] {}", hint);
+    }
+}
+
+/// `deadmod self-check --update`: `count` findings written to `path`.
+pub fn render_expected_written(out: &mut dyn OutputSink, count: usize, path: &Path) {
+    out!(
+        out,
+        "Wrote {} expected finding(s) to {}",
+        count,
+        path.display()
+    );
+}
+
+/// `deadmod fix --list`: recorded fix transactions.
+pub fn render_fix_history(out: &mut dyn OutputSink, transactions: &[RecoveryManifest]) {
+    if transactions.is_empty() {
+        out!(out, "No fix transactions recorded.");
+        return;
+    }
+    out!(out, "FIX TRANSACTIONS ({}):", transactions.len());
+    for tx in transactions {
+        let removed = tx
+            .entries
+            .iter()
+            .filter(|e| e.action == RecoveryAction::Removed)
+            .count();
+        out!(
+            out,
+            "- {}  ({} removed, {} modified)",
+            tx.id,
+            removed,
+            tx.entries.len() - removed
+        );
+    }
+}
+
+/// `--write-fix-plan`: the plan, and the file it was saved to.
+pub fn render_plan_written(out: &mut dyn OutputSink, plan: &RemovalPlan, file: &str) {
+    out.write(&format_plan_plain(plan));
+    out!(out, "Plan written to {}", file);
+}
+
+/// `--fix-plan`: the step about to be applied with its number of target
+/// modules, or `None` once every step of the plan in `file` is applied.
+pub fn render_plan_step(
+    out: &mut dyn OutputSink,
+    plan: &RemovalPlan,
+    file: &str,
+    step: Option<(&PlanStep, usize)>,
+) {
+    match step {
+        Some((step, targets)) => out!(
+            out,
+            "Applying step {} of {} ({} module(s)):",
+            step.step,
+            plan.steps.len(),
+            targets
+        ),
+        None => out!(
+            out,
+            "All {} step(s) of {} are applied.",
+            plan.steps.len(),
+            file
+        ),
+    }
+}
+
+/// `deadmod callpath`: call paths from `from` to `to`.
+pub fn render_callpath(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    from: &str,
+    to: &str,
+    paths: &[Vec<String>],
+) -> Result<()> {
+    if r.json {
+        let value = json!({
+            "from": from,
+            "to": to,
+            "paths": paths,
+        });
+        return r.json_report(out, value);
+    }
+
+    if paths.is_empty() {
+        out!(out, "No call path from {} to {}.", from, to);
+    } else {
+        out!(out, "CALL PATHS {} -> {} ({}):", from, to, paths.len());
+        for (i, path) in paths.iter().enumerate() {
+            out!(out, "{}. {}", i + 1, path.join(" -> "));
+        }
+    }
+    Ok(())
+}
+
+/// `deadmod cycles` and `analyze-graph --cycles`.
+pub fn render_cycles(out: &mut dyn OutputSink, r: &Render<'_>, cycles: &[ModuleCycle]) {
+    if r.json {
+        out.line(&format_cycles_json(cycles));
+    } else {
+        out.write(&format_cycles_plain(cycles));
+    }
+}
+
+/// `deadmod metrics` and `analyze-graph --metrics`.
+pub fn render_metrics(out: &mut dyn OutputSink, r: &Render<'_>, metrics: &GraphMetrics) {
+    if r.json {
+        out.line(&format_metrics_json(metrics));
+    } else {
+        out.write(&format_metrics_plain(metrics));
+    }
+}
+
+/// `deadmod layers` and `analyze-graph --layers`.
+pub fn render_layer_violations(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    violations: &[LayerViolation],
+) {
+    if r.json {
+        out.line(&format_violations_json(violations));
+    } else {
+        out.write(&format_violations_plain(violations));
+    }
+}
+
+/// `deadmod warm`: cache state of each crate.
+pub fn render_warm(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    crates: &[(PathBuf, WarmStats)],
+) -> Result<()> {
+    if r.json {
+        let value: Vec<Value> = crates
+            .iter()
+            .map(|(root, stats)| json!({ "crate": root, "stats": stats }))
+            .collect();
+        return r.json_report(out, value.into());
+    }
+
+    for (root, stats) in crates {
+        out!(
+            out,
+            "{}: {} module(s) cached, {} already current",
+            root.display(),
+            stats.cached,
+            stats.reused
+        );
+    }
+    Ok(())
+}
+
+/// What `deadmod bench-selftest` measured on a generated project.
+pub struct SelfTest<'a> {
+    /// What was planted
+    pub project: &'a SyntheticProject,
+    /// Worker threads
+    pub threads: usize,
+    /// Time spent in each phase, in the order phases first started
+    pub phases: Vec<(String, Duration)>,
+    /// Wall time of the whole analysis
+    pub elapsed: Duration,
+    /// Dead items found and planted, by kind
+    pub checks: Vec<(&'static str, usize, usize)>,
+}
+
+impl SelfTest<'_> {
+    /// Whether exactly the planted dead code was found.
+    pub fn ok(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|(_, found, planted)| found == planted)
+    }
+}
+
+/// `deadmod bench-selftest`: timings, throughput and the planted-code check.
+pub fn render_bench_selftest(
+    out: &mut dyn OutputSink,
+    r: &Render<'_>,
+    test: &SelfTest<'_>,
+) -> Result<()> {
+    let project = test.project;
+    let secs = test.elapsed.as_secs_f64().max(f64::EPSILON);
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+
+    if r.json {
+        let value = json!({
+            "project": project,
+            "threads": test.threads,
+            "phases": test.phases.iter().map(|(name, total)| {
+                json!({ "phase": name, "ms": ms(*total) })
+            }).collect::<Vec<_>>(),
+            "total_ms": ms(test.elapsed),
+            "throughput": {
+                "files_per_sec": project.files as f64 / secs,
+                "lines_per_sec": project.lines as f64 / secs,
+                "functions_per_sec": project.functions as f64 / secs,
+            },
+            "check": test.checks.iter().map(|(kind, found, planted)| {
+                (kind.to_string(), json!({ "found": found, "planted": planted }))
+            }).collect::<serde_json::Map<_, _>>(),
+            "ok": test.ok(),
+        });
+        return r.json_report(out, value);
+    }
+
+    out!(out, "=== Self-Test: Synthetic Project ===\n");
+    out!(out, "Files:      {}", project.files);
+    out!(out, "Lines:      {}", project.lines);
+    out!(
+        out,
+        "Functions:  {} ({} planted dead)",
+        project.functions,
+        project.dead_functions
+    );
+    out!(out, "Threads:    {}\n", test.threads);
+
+    for (name, total) in &test.phases {
+        out!(out, "  {:<24} {:>10.1} ms", name, ms(*total));
+    }
+    out!(out, "  {:<24} {:>10.1} ms\n", "total", ms(test.elapsed));
+
+    out!(
+        out,
+        "Throughput: {:.0} functions/s, {:.0} files/s, {:.0} lines/s",
+        project.functions as f64 / secs,
+        project.files as f64 / secs,
+        project.lines as f64 / secs
+    );
+    let found: Vec<String> = test
+        .checks
+        .iter()
+        .map(|(kind, found, planted)| format!("{}/{} {}", found, planted, kind))
+        .collect();
+    out!(
+        out,
+        "Dead code found: {} [{}]",
+        found.join(", "),
+        if test.ok() { "ok" } else { "MISMATCH" }
+    );
+    Ok(())
+}
+
+/// Note on a workspace root: its member crates, each better analyzed on
+/// its own.
+pub fn render_workspace_members(out: &mut dyn OutputSink, members: &[String]) {
+    out!(
+        out,
+        "INFO: Detected Cargo workspace with {} member(s):",
+        members.len()
+    );
+    for member in members {
+        out!(out, "  - {}", member);
+    }
+    out!(
+        out,
+        "TIP: Run on each crate separately for accurate results."
+    );
+    out!(out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    use deadmod_core::fix::RecoveryEntry;
+    use deadmod_core::{
+        check_removal, explain, extract_call_names, extract_const_usage, extract_constants,
+        extract_declared_generics, extract_field_usages, extract_fields, extract_functions,
+        extract_generic_usages, extract_macro_usages, extract_macros, extract_match_arms,
+        extract_match_usages, extract_params, extract_trait_usages, extract_traits,
+        extract_variant_usage, extract_variants, find_module_cycles, find_symbol, fix,
+        group_by_module, module_metrics, ConstGraph, DeadDependency, Deadmod, DiscoveredModule,
+        EnumGraph, FieldGraph, FuncGraph, GenericGraph, Keep, MacroGraph, MatchGraph, MemorySource,
+        ModuleCluster, ModuleInfo, ParamGraph, RunCounts, TraitGraph,
+    };
+
+    /// One file with a finding for every detector.
+    const SRC: &str = r#"
+pub fn api(mode: Mode) -> u32 {
+    let state = State { used: 1, unused: 2 };
+    let _ = make();
+    match mode {
+        Mode::Fast => state.used + Square.area(),
+        Mode::Slow => LIMIT,
+        Mode::Never => 0,
+    }
+}
+
+fn make() -> Mode { Mode::Fast }
+fn helper(x: u32, unused: u32) -> u32 { x }
+fn wrap<T, U>(t: T) -> T { t }
+
+const LIMIT: u32 = 1;
+const UNUSED: u32 = 2;
+
+pub enum Mode { Fast, Slow, Never, Idle }
+struct State { used: u32, unused: u32 }
+
+macro_rules! unused_macro { () => {}; }
+
+trait Shape { fn area(&self) -> u32; fn perimeter(&self) -> u32; fn corners(&self) -> u32 { 4 } }
+struct Square;
+impl Shape for Square { fn area(&self) -> u32 { 1 } fn perimeter(&self) -> u32 { 4 } }
+impl Square { fn orphan(&self) {} }
+"#;
+
+    const FILE: &str = "src/lib.rs";
+
+    /// Compare `actual` with `tests/golden/<name>`; `DEADMOD_UPDATE_GOLDEN=1`
+    /// rewrites the file instead.
+    fn assert_golden(name: &str, actual: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(name);
+        if std::env::var_os("DEADMOD_UPDATE_GOLDEN").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, actual).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+            panic!(
+                "{}: {} (DEADMOD_UPDATE_GOLDEN=1 creates it)",
+                path.display(),
+                e
+            )
+        });
+        assert_eq!(
+            actual, expected,
+            "{} changed (DEADMOD_UPDATE_GOLDEN=1 rewrites it)",
+            name
+        );
+    }
+
+    /// Render `render` as plain text and as JSON against `<name>.txt` and `<name>.json`.
+    fn check(name: &str, render: impl Fn(&mut dyn OutputSink, &Render<'_>) -> Result<()>) {
+        let paths = PathFormatter::default();
+        for (json, ext) in [(false, "txt"), (true, "json")] {
+            let r = Render::new(json, &paths);
+            let mut out = String::new();
+            render(&mut out, &r).unwrap();
+            assert_golden(&format!("{}.{}", name, ext), &out);
+        }
+    }
+
+    fn none<'a>(attr: &'a Attribution<'a>) -> Findings<'a> {
+        Findings {
+            attr,
+            collapsed: 0,
+            kept: &[],
+            recent: &[],
+            due: &[],
+            delta: None,
+        }
+    }
+
+    #[test]
+    fn golden_functions() {
+        let path = Path::new(FILE);
+        let funcs = extract_functions(path, SRC);
+        let calls = HashMap::from([(FILE.to_string(), extract_call_names(path, SRC))]);
+        let result = FuncGraph::build(&funcs, &calls).analyze();
+        assert!(!result.dead.is_empty());
+
+        let attr = Attribution::new(Path::new(""));
+        let kept = [KeptItem::new(
+            "legacy",
+            FILE,
+            &Keep {
+                reason: Some("FFI".to_string()),
+            },
+        )];
+        let recent = [RecentItem {
+            item: attr.function(&result.dead[0]),
+            added: "2026-01-02".to_string(),
+        }];
+        let mut due = Suppression::new("f-0001", "function", "old", FILE, "used by a macro", "dev");
+        due.added = "2025-01-02".to_string();
+        let due = [due];
+        let previous = RunCounts::new().with("dead_functions", result.dead.len() + 2);
+        let delta = RunDelta::between(
+            &previous,
+            &RunCounts::new().with("dead_functions", result.dead.len()),
+        );
+        let findings = Findings {
+            attr: &attr,
+            collapsed: 2,
+            kept: &kept,
+            recent: &recent,
+            due: &due,
+            delta: Some(&delta),
+        };
+        let uncovered: Vec<_> = funcs.iter().filter(|f| f.name == "api").collect();
+        check("functions", |out, r| {
+            render_functions(out, r, &result, Some(&uncovered), &findings)
+        });
+    }
+
+    #[test]
+    fn golden_traits() {
+        let path = Path::new(FILE);
+        let result = TraitGraph::build(
+            &[extract_traits(path, SRC)],
+            &[extract_trait_usages(path, SRC)],
+        )
+        .analyze();
+        let attr = Attribution::new(Path::new(""));
+        check("traits", |out, r| {
+            render_traits(out, r, &result, &none(&attr))
+        });
+    }
+
+    #[test]
+    fn golden_generics() {
+        let path = Path::new(FILE);
+        let result = GenericGraph::new(
+            &[extract_declared_generics(path, SRC)],
+            &[extract_generic_usages(path, SRC)],
+        )
+        .analyze();
+        let attr = Attribution::new(Path::new(""));
+        check("generics", |out, r| {
+            render_generics(out, r, &result, &none(&attr))
+        });
+    }
+
+    #[test]
+    fn golden_macros() {
+        let path = Path::new(FILE);
+        let result = MacroGraph::new(
+            extract_macros(path, SRC),
+            &[extract_macro_usages(path, SRC)],
+        )
+        .analyze();
+        let attr = Attribution::new(Path::new(""));
+        check("macros", |out, r| {
+            render_macros(out, r, &result, &none(&attr))
+        });
+    }
+
+    #[test]
+    fn golden_constants() {
+        let path = Path::new(FILE);
+        let result = ConstGraph::new(
+            extract_constants(path, SRC),
+            &[extract_const_usage(path, SRC)],
+        )
+        .analyze();
+        let attr = Attribution::new(Path::new(""));
+        check("constants", |out, r| {
+            render_constants(out, r, &result, &none(&attr))
+        });
+    }
+
+    #[test]
+    fn golden_variants() {
+        let path = Path::new(FILE);
+        let result = EnumGraph::new(
+            extract_variants(path, SRC),
+            &[extract_variant_usage(path, SRC)],
+        )
+        .analyze();
+        let attr = Attribution::new(Path::new(""));
+        check("variants", |out, r| {
+            render_variants(out, r, &result, &none(&attr))
+        });
+    }
+
+    #[test]
+    fn golden_match_arms() {
+        let path = Path::new(FILE);
+        let arms = extract_match_arms(path, SRC);
+        let result = MatchGraph::new(
+            arms.arms,
+            arms.match_count,
+            &[extract_match_usages(path, SRC)],
+        )
+        .correlate_with_enums(&extract_variants(path, SRC))
+        .analyze();
+        let attr = Attribution::new(Path::new(""));
+        check("match_arms", |out, r| {
+            render_match_arms(out, r, &result, &none(&attr))
+        });
+    }
+
+    #[test]
+    fn golden_params() {
+        let result = ParamGraph::new(&[extract_params(Path::new(FILE), SRC)]).analyze();
+        let attr = Attribution::new(Path::new(""));
+        check("params", |out, r| {
+            render_params(out, r, &result, &none(&attr))
+        });
+    }
+
+    #[test]
+    fn golden_fields() {
+        let path = Path::new(FILE);
+        let result = FieldGraph::new(
+            &[extract_fields(path, SRC)],
+            &[extract_field_usages(path, SRC)],
+        )
+        .analyze();
+        let attr = Attribution::new(Path::new(""));
+        check("fields", |out, r| {
+            render_fields(out, r, &result, &none(&attr))
+        });
+    }
+
+    #[test]
+    fn golden_dead_deps() {
+        let dep = |name: &str, kind, target: Option<&str>, optional, dead_users: &[&str]| {
+            DeadDependency {
+                name: name.to_string(),
+                kind,
+                manifest: PathBuf::from("Cargo.toml"),
+                target: target.map(str::to_string),
+                optional,
+                dead_users: dead_users.iter().map(PathBuf::from).collect(),
+            }
+        };
+        let result = DeadDepsResult {
+            dead: vec![
+                dep("extra", DependencyKind::Normal, None, true, &[]),
+                dep(
+                    "libc",
+                    DependencyKind::Normal,
+                    Some("cfg(unix)"),
+                    false,
+                    &[],
+                ),
+                dep(
+                    "proptest",
+                    DependencyKind::Dev,
+                    None,
+                    false,
+                    &["src/old_tests.rs"],
+                ),
+                dep(
+                    "left-pad",
+                    DependencyKind::Patch,
+                    Some("crates-io"),
+                    false,
+                    &[],
+                ),
+            ],
+            checked: 9,
+        };
+        check("dead_deps", |out, r| render_dead_deps(out, r, &result));
+    }
+
+    #[test]
+    fn golden_discovery() {
+        let module = |name: &str, file: &str, cluster: &str, depth| DiscoveredModule {
+            name: name.to_string(),
+            path: PathBuf::from(file),
+            cluster: cluster.to_string(),
+            is_crate_root: name == "lib",
+            is_mod_file: false,
+            depth,
+        };
+        let lib = module("lib", "src/lib.rs", "root", 0);
+        let tcp = module("tcp", "src/net/tcp.rs", "net", 1);
+        let cluster =
+            |name: &str, dir: &str, modules: Vec<DiscoveredModule>, children: &[&str], depth| {
+                ModuleCluster {
+                    name: name.to_string(),
+                    path: PathBuf::from(dir),
+                    relative_path: dir
+                        .trim_start_matches("src")
+                        .trim_start_matches('/')
+                        .to_string(),
+                    mod_file: None,
+                    modules,
+                    children: children.iter().map(|c| c.to_string()).collect(),
+                    parent: (depth > 0).then(|| "root".to_string()),
+                    depth,
+                }
+            };
+        let discovery = ModuleDiscovery {
+            clusters: HashMap::from([
+                (
+                    "root".to_string(),
+                    cluster("root", "src", vec![lib.clone()], &["net"], 0),
+                ),
+                (
+                    "net".to_string(),
+                    cluster("net", "src/net", vec![tcp.clone()], &[], 1),
+                ),
+            ]),
+            modules: vec![lib, tcp],
+            crate_roots: vec![PathBuf::from("src/lib.rs")],
+            file_count: 2,
+        };
+
+        // Clusters come from a HashMap, so JSON output is checked normalized.
+        let paths = PathFormatter::default();
+        for (json, ext) in [(false, "txt"), (true, "json")] {
+            let r = Render {
+                json,
+                normalize_root: Some(Path::new(".")),
+                paths: &paths,
+                limit: ItemLimit::default(),
+            };
+            let mut out = String::new();
+            render_discovery(&mut out, &r, Path::new("."), &discovery).unwrap();
+            assert_golden(&format!("discovery.{}", ext), &out);
+        }
+    }
+
+    #[test]
+    fn golden_per_target() {
+        let partial_modules = BTreeMap::from([("net".to_string(), vec!["bin:server".to_string()])]);
+        let partial_funcs = BTreeMap::from([(
+            "net::connect".to_string(),
+            vec!["lib".to_string(), "test:net".to_string()],
+        )]);
+        let matrix = TargetMatrix {
+            targets: vec!["bin:server", "lib", "test:net"],
+            dead_modules: &["legacy".to_string()],
+            partial_modules: &partial_modules,
+            dead_funcs: &["net::retry".to_string(), "util::pad".to_string()],
+            partial_funcs: &partial_funcs,
+        };
+        check("per_target", |out, r| render_per_target(out, r, &matrix));
+    }
+
+    fn analyzed() -> AnalysisResult {
+        Deadmod::from_sources([
+            (
+                PathBuf::from("src/lib.rs"),
+                "pub mod net;\npub fn api() { net::used(); }".to_string(),
+            ),
+            (
+                PathBuf::from("src/net/mod.rs"),
+                "pub fn used() {}\nfn unused() {}\nconst LIMIT: u8 = 1;".to_string(),
+            ),
+            (
+                PathBuf::from("src/dead.rs"),
+                "pub fn orphan() {}".to_string(),
+            ),
+        ])
+        .include_functions(true)
+        .include_constants(true)
+        .analyze()
+        .unwrap()
+    }
+
+    #[test]
+    fn golden_grouped() {
+        let result = analyzed();
+        let tree = group_by_module(&result);
+        check("grouped", |out, r| {
+            render_grouped(out, r, &tree, &result, &[], None)
+        });
+    }
+
+    #[test]
+    fn golden_file() {
+        let source = "pub fn used() {}\nfn unused() {}\nconst LIMIT: u8 = 1;";
+        let findings = FileFindings::from_result(&analyzed(), Path::new("src/net/mod.rs"), source);
+        check("file", |out, r| {
+            render_file(out, r, "src/net/mod.rs", &findings)
+        });
+    }
+
+    #[test]
+    fn golden_find() {
+        let sources = [
+            (
+                PathBuf::from("src/lib.rs"),
+                "pub mod net;\n/// See `connect`\npub fn api() { net::connect(); }".to_string(),
+            ),
+            (
+                PathBuf::from("src/net.rs"),
+                "pub fn connect() {}\nfn ping() { log!(connect); }".to_string(),
+            ),
+            (PathBuf::from("src/old.rs"), "fn connect() {}".to_string()),
+        ];
+        let source = MemorySource::new(sources.clone());
+        let result = Deadmod::from_sources(sources).all().analyze().unwrap();
+        let search = find_symbol(&result, "connect", &source);
+        check("find", |out, r| render_find(out, r, &search));
+
+        let missing = find_symbol(&result, "nothing_here", &source);
+        let mut out = String::new();
+        let paths = PathFormatter::default();
+        let r = Render::new(false, &paths);
+        render_find(&mut out, &r, &missing).unwrap();
+        assert_eq!(out, "Nothing named `nothing_here` found.\n");
+    }
+
+    #[test]
+    fn golden_self_check() {
+        let finding = |id: &str, kind: &str, name: &str, file: &str| ExpectedFinding {
+            id: id.to_string(),
+            krate: "core".to_string(),
+            kind: kind.to_string(),
+            name: name.to_string(),
+            file: file.to_string(),
+        };
+        let diff = ExpectedDiff {
+            new: vec![finding(
+                "3fa9c2d1e07b",
+                "function",
+                "retry_later",
+                "core/src/net.rs",
+            )],
+            fixed: vec![finding(
+                "9b1e04c7d2aa",
+                "module",
+                "legacy",
+                "core/src/legacy.rs",
+            )],
+        };
+        check("self_check", |out, r| render_self_check(out, r, &diff, 4));
+    }
+
+    #[test]
+    fn golden_workspace() {
+        let results = [
+            CrateAnalysis {
+                name: "core".to_string(),
+                root: PathBuf::from("core"),
+                total_modules: 2,
+                dead_modules: vec!["legacy".to_string()],
+                reachable_modules: vec!["lib".to_string()],
+                dot_output: "digraph core {}".to_string(),
+            },
+            CrateAnalysis {
+                name: "cli".to_string(),
+                root: PathBuf::from("cli"),
+                total_modules: 1,
+                dead_modules: Vec::new(),
+                reachable_modules: vec!["main".to_string()],
+                dot_output: "digraph cli {}".to_string(),
+            },
+        ];
+        check("workspace", |out, r| {
+            render_workspace(out, r, &results, false)
+        });
+
+        let paths = PathFormatter::default();
+        let r = Render::new(false, &paths);
+        let mut out = String::new();
+        render_workspace(&mut out, &r, &results, true).unwrap();
+        assert_golden("workspace_dot.txt", &out);
+    }
+
+    #[test]
+    fn golden_explain_and_check_removal() {
+        let sources = [
+            (
+                PathBuf::from("src/lib.rs"),
+                "pub mod net;\n/// Replaced [`retry`] in 2.0\npub fn api() { net::send(); }"
+                    .to_string(),
+            ),
+            (
+                PathBuf::from("src/net.rs"),
+                "pub fn send() {}\nfn retry() {}\nfn unused() {}\npub const ROUTE: &str = \"retry\";"
+                    .to_string(),
+            ),
+        ];
+        let source = MemorySource::new(sources.clone());
+        let result = Deadmod::from_sources(sources).all().analyze().unwrap();
+
+        let retry = check_removal(&result, "retry", &source).unwrap();
+        assert!(!retry.safe);
+        let evidence = explain(&result, &retry.id, &source).unwrap();
+        check("explain", |out, r| render_explain(out, r, &evidence));
+        check("check_removal", |out, r| {
+            render_check_removal(out, r, &retry)
+        });
+
+        let unused = check_removal(&result, "unused", &source).unwrap();
+        assert!(unused.safe);
+        check("check_removal_safe", |out, r| {
+            render_check_removal(out, r, &unused)
+        });
+    }
+
+    #[test]
+    fn golden_fix_notes() {
+        let mut out = String::new();
+        let mut suppression =
+            Suppression::new("3fa9c2d1e07b", "function", "retry", FILE, "FFI", "dev");
+        render_marked(&mut out, &suppression, false);
+        suppression.reason = "called through a macro".to_string();
+        render_marked(&mut out, &suppression, true);
+        render_expected_written(&mut out, 4, Path::new("deadmod-expected.json"));
+
+        render_fix_history(&mut out, &[]);
+        let entry = |path: &str, action| RecoveryEntry {
+            path: PathBuf::from(path),
+            action,
+            sha256: String::new(),
+            content: String::new(),
+        };
+        let mut tx = RecoveryManifest::new(Path::new("/work/app"));
+        tx.id = "1767312000-1".to_string();
+        tx.entries = vec![
+            entry("/work/app/src/legacy.rs", RecoveryAction::Removed),
+            entry("/work/app/src/lib.rs", RecoveryAction::Modified),
+        ];
+        render_fix_history(&mut out, &[tx]);
+
+        let mods: HashMap<String, ModuleInfo> =
+            [("lib", &[][..]), ("legacy", &["old"][..]), ("old", &[][..])]
+                .into_iter()
+                .map(|(name, refs)| {
+                    let mut info = ModuleInfo::new(PathBuf::from(format!("src/{}.rs", name)));
+                    info.refs = refs.iter().map(|r| r.to_string()).collect();
+                    (name.to_string(), info)
+                })
+                .collect();
+        let plan = fix::plan(&["legacy", "old"], &mods, 1);
+        render_plan_written(&mut out, &plan, "deadmod-plan.json");
+        render_plan_step(
+            &mut out,
+            &plan,
+            "deadmod-plan.json",
+            Some((&plan.steps[0], 1)),
+        );
+        render_plan_step(&mut out, &plan, "deadmod-plan.json", None);
+
+        render_workspace_members(&mut out, &["core".to_string(), "cli".to_string()]);
+        assert_golden("fix_notes.txt", &out);
+    }
+
+    #[test]
+    fn golden_output_path_error() {
+        // `--output json=/tmp/report.json` and `--badge-json ../badge.json`
+        let mut out = String::new();
+        render_output_path_error(
+            &mut out,
+            &DeadmodError::output_path_rejected(
+                "/tmp/report.json",
+                "must be relative, not absolute",
+            ),
+        );
+        render_output_path_error(
+            &mut out,
+            &DeadmodError::output_path_rejected("../badge.json", "path traversal (..) not allowed"),
+        );
+        assert_golden("output_path_error.txt", &out);
+    }
+
+    #[test]
+    fn golden_callpath() {
+        let paths = [
+            vec!["main".to_string(), "run".to_string(), "send".to_string()],
+            vec!["main".to_string(), "send".to_string()],
+        ];
+        check("callpath", |out, r| {
+            render_callpath(out, r, "main", "send", &paths)
+        });
+        check("callpath_none", |out, r| {
+            render_callpath(out, r, "send", "main", &[])
+        });
+    }
+
+    #[test]
+    fn golden_graph_reports() {
+        let mods: HashMap<String, ModuleInfo> = [
+            ("lib", &["net", "util"][..]),
+            ("net", &["util"][..]),
+            ("util", &["net"][..]),
+        ]
+        .into_iter()
+        .map(|(name, refs)| {
+            let mut info = ModuleInfo::new(PathBuf::from(format!("src/{}.rs", name)));
+            info.refs = refs.iter().map(|r| r.to_string()).collect();
+            (name.to_string(), info)
+        })
+        .collect();
+        let cycles = find_module_cycles(&mods);
+        assert!(!cycles.is_empty());
+        let metrics = module_metrics(&mods);
+        let violations = [LayerViolation {
+            from: "net".to_string(),
+            to: "util".to_string(),
+            denied_by: "util".to_string(),
+            reason: Some("net stays dependency-free".to_string()),
+        }];
+        check("cycles", |out, r| {
+            render_cycles(out, r, &cycles);
+            Ok(())
+        });
+        check("metrics", |out, r| {
+            render_metrics(out, r, &metrics);
+            Ok(())
+        });
+        check("layer_violations", |out, r| {
+            render_layer_violations(out, r, &violations);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn golden_warm() {
+        let crates = [
+            (
+                PathBuf::from("core"),
+                WarmStats {
+                    modules: 4,
+                    cached: 4,
+                    reused: 3,
+                },
+            ),
+            (PathBuf::from("cli"), WarmStats::default()),
+        ];
+        check("warm", |out, r| render_warm(out, r, &crates));
+    }
+
+    #[test]
+    fn golden_bench_selftest() {
+        let project = SyntheticProject {
+            files: 12,
+            lines: 480,
+            functions: 60,
+            dead_functions: 6,
+            dead_constants: 2,
+            dead_variants: 3,
+            dead_modules: 1,
+        };
+        let test = SelfTest {
+            project: &project,
+            threads: 4,
+            phases: vec![
+                ("parse".to_string(), Duration::from_millis(40)),
+                ("analyze".to_string(), Duration::from_millis(10)),
+            ],
+            elapsed: Duration::from_millis(50),
+            checks: vec![("functions", 6, 6), ("modules", 0, 1)],
+        };
+        assert!(!test.ok());
+        check("bench_selftest", |out, r| {
+            render_bench_selftest(out, r, &test)
+        });
+    }
+
+    #[test]
+    fn test_string_sink_collects_lines() {
+        let mut out = String::new();
+        print_kept(&mut out, &[]);
+        assert!(out.is_empty());
+        out!(&mut out, "a {}", 1);
+        out.line("b");
+        assert_eq!(out, "a 1\nb\n");
+    }
+}
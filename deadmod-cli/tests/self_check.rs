@@ -0,0 +1,29 @@
+//! Dogfooding gate: `deadmod self-check` on this workspace, against the
+//! committed `deadmod-expected.toml`. New dead code in deadmod fails here;
+//! accept it (or a fix) with `cargo run -p deadmod-cli -- self-check --update`.
+//!
+//! Copy this file into a project to gate it the same way.
+
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn self_check_own_workspace() {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    // Keep run state out of the source tree even though nothing is cached
+    let cache_dir = std::env::temp_dir().join(format!("deadmod_self_check_{}", std::process::id()));
+    let output = Command::new(env!("CARGO_BIN_EXE_deadmod"))
+        .args(["self-check", "--no-cache"])
+        .arg(workspace)
+        .env("DEADMOD_CACHE_DIR", &cache_dir)
+        .output()
+        .expect("failed to run deadmod");
+    std::fs::remove_dir_all(&cache_dir).ok();
+
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,256 @@
+//! Analysis benchmarks on generated projects.
+//!
+//! ```bash
+//! cargo bench -p deadmod-core --bench analysis
+//! DEADMOD_BENCH_SIZES=1000,10000,100000 cargo bench -p deadmod-core --bench analysis
+//! ```
+//!
+//! Each size is a number of functions (see `deadmod_core::synthetic`); the
+//! default covers 1k and 10k. Projects are written under the system temp
+//! directory and reused between runs of the same size.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use deadmod_core::synthetic::{generate, SyntheticSpec};
+use deadmod_core::{
+    build_graph, extract_call_names, extract_const_usage, extract_constants,
+    extract_declared_generics, extract_functions, extract_generic_usages, extract_macro_usages,
+    extract_macros, extract_match_arms, extract_match_usages, extract_trait_usages, extract_traits,
+    extract_variant_usage, extract_variants, gather_rs_files, load_cache_as, parse_crate,
+    parse_modules, reachable_from_roots, save_cache_as, CacheFormat, FuncGraph, ParseMode,
+    Progress,
+};
+
+/// Function counts benchmarked unless `DEADMOD_BENCH_SIZES` says otherwise.
+const DEFAULT_SIZES: &[usize] = &[1_000, 10_000];
+
+fn sizes() -> Vec<usize> {
+    match std::env::var("DEADMOD_BENCH_SIZES") {
+        Ok(list) => list
+            .split(',')
+            .filter_map(|n| n.trim().parse().ok())
+            .collect(),
+        Err(_) => DEFAULT_SIZES.to_vec(),
+    }
+}
+
+/// A generated project on disk with its sources loaded.
+struct Fixture {
+    functions: usize,
+    root: PathBuf,
+    files: Vec<PathBuf>,
+    sources: Vec<(PathBuf, String)>,
+}
+
+impl Fixture {
+    fn new(functions: usize) -> Self {
+        let root = std::env::temp_dir().join(format!("deadmod_bench_{}", functions));
+        if !root.join("src").join("main.rs").exists() {
+            generate(&root, &SyntheticSpec::new(functions)).expect("generate synthetic project");
+        }
+        let files = gather_rs_files(&root).expect("scan synthetic project");
+        let sources = files
+            .iter()
+            .map(|path| {
+                (
+                    path.clone(),
+                    std::fs::read_to_string(path).expect("read source"),
+                )
+            })
+            .collect();
+        Self {
+            functions,
+            root,
+            files,
+            sources,
+        }
+    }
+
+    /// Run `extract` over every file, as the detectors do.
+    fn each_file<T>(&self, extract: impl Fn(&Path, &str) -> T) -> Vec<T> {
+        self.sources
+            .iter()
+            .map(|(path, content)| extract(path, content))
+            .collect()
+    }
+}
+
+fn fixtures() -> Vec<Fixture> {
+    sizes().into_iter().map(Fixture::new).collect()
+}
+
+fn bench_scan_parse(c: &mut Criterion, fixtures: &[Fixture]) {
+    let mut group = c.benchmark_group("scan_parse");
+    for fx in fixtures {
+        group.throughput(Throughput::Elements(fx.files.len() as u64));
+        group.bench_with_input(BenchmarkId::new("scan", fx.functions), fx, |b, fx| {
+            b.iter(|| gather_rs_files(&fx.root).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("parse", fx.functions), fx, |b, fx| {
+            b.iter(|| parse_modules(&fx.files).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// A run's parse phase in each [`ParseMode`]; `cached_cold` starts without
+/// a cache and writes one, as a first run does.
+fn bench_parse_modes(c: &mut Criterion, fixtures: &[Fixture]) {
+    let mut group = c.benchmark_group("parse_mode");
+    for fx in fixtures {
+        group.throughput(Throughput::Elements(fx.files.len() as u64));
+        let parse = |mode| parse_crate(&fx.root, &fx.files, mode, &Progress::none()).unwrap();
+        group.bench_function(BenchmarkId::new("cached_cold", fx.functions), |b| {
+            b.iter_batched(
+                || std::fs::remove_dir_all(fx.root.join(".deadmod")).ok(),
+                |_| parse(ParseMode::Cached),
+                BatchSize::PerIteration,
+            )
+        });
+        parse(ParseMode::Cached);
+        group.bench_function(BenchmarkId::new("cached_warm", fx.functions), |b| {
+            b.iter(|| parse(ParseMode::Cached))
+        });
+        group.bench_function(BenchmarkId::new("uncached", fx.functions), |b| {
+            b.iter(|| parse(ParseMode::Uncached))
+        });
+        group.bench_function(BenchmarkId::new("fast", fx.functions), |b| {
+            b.iter(|| parse(ParseMode::Fast))
+        });
+    }
+    group.finish();
+}
+
+/// Saving and loading the cache each parse leaves, in both formats.
+fn bench_cache_formats(c: &mut Criterion, fixtures: &[Fixture]) {
+    let mut group = c.benchmark_group("cache");
+    for fx in fixtures {
+        group.throughput(Throughput::Elements(fx.files.len() as u64));
+        parse_crate(&fx.root, &fx.files, ParseMode::Cached, &Progress::none()).unwrap();
+        let cache = load_cache_as(&fx.root, CacheFormat::Binary).expect("binary cache");
+        for (name, format) in [("binary", CacheFormat::Binary), ("json", CacheFormat::Json)] {
+            group.bench_function(
+                BenchmarkId::new(format!("save_{}", name), fx.functions),
+                |b| b.iter(|| save_cache_as(&fx.root, &cache, format).unwrap()),
+            );
+            group.bench_function(
+                BenchmarkId::new(format!("load_{}", name), fx.functions),
+                |b| b.iter(|| load_cache_as(&fx.root, format).unwrap()),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_extractors(c: &mut Criterion, fixtures: &[Fixture]) {
+    let mut group = c.benchmark_group("extract");
+    for fx in fixtures {
+        group.throughput(Throughput::Elements(fx.functions as u64));
+        let id = |name| BenchmarkId::new(name, fx.functions);
+        group.bench_function(id("functions"), |b| {
+            b.iter(|| fx.each_file(extract_functions))
+        });
+        group.bench_function(id("calls"), |b| b.iter(|| fx.each_file(extract_call_names)));
+        group.bench_function(id("traits"), |b| b.iter(|| fx.each_file(extract_traits)));
+        group.bench_function(id("trait_usages"), |b| {
+            b.iter(|| fx.each_file(extract_trait_usages))
+        });
+        group.bench_function(id("constants"), |b| {
+            b.iter(|| fx.each_file(extract_constants))
+        });
+        group.bench_function(id("const_usages"), |b| {
+            b.iter(|| fx.each_file(extract_const_usage))
+        });
+        group.bench_function(id("variants"), |b| {
+            b.iter(|| fx.each_file(extract_variants))
+        });
+        group.bench_function(id("variant_usages"), |b| {
+            b.iter(|| fx.each_file(extract_variant_usage))
+        });
+        group.bench_function(id("macros"), |b| b.iter(|| fx.each_file(extract_macros)));
+        group.bench_function(id("macro_usages"), |b| {
+            b.iter(|| fx.each_file(extract_macro_usages))
+        });
+        group.bench_function(id("generics"), |b| {
+            b.iter(|| fx.each_file(extract_declared_generics))
+        });
+        group.bench_function(id("generic_usages"), |b| {
+            b.iter(|| fx.each_file(extract_generic_usages))
+        });
+        group.bench_function(id("match_arms"), |b| {
+            b.iter(|| fx.each_file(extract_match_arms))
+        });
+        group.bench_function(id("match_usages"), |b| {
+            b.iter(|| fx.each_file(extract_match_usages))
+        });
+    }
+    group.finish();
+}
+
+fn bench_graphs(c: &mut Criterion, fixtures: &[Fixture]) {
+    let mut group = c.benchmark_group("graph");
+    for fx in fixtures {
+        group.throughput(Throughput::Elements(fx.functions as u64));
+        let modules = parse_modules(&fx.files).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("module_graph", fx.functions),
+            &modules,
+            |b, modules| b.iter(|| build_graph(modules)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("reachability", fx.functions),
+            &modules,
+            |b, modules| {
+                let graph = build_graph(modules);
+                b.iter(|| reachable_from_roots(&graph, ["main"]))
+            },
+        );
+
+        let functions: Vec<_> = fx
+            .each_file(extract_functions)
+            .into_iter()
+            .flatten()
+            .collect();
+        let calls: HashMap<String, HashSet<String>> = fx
+            .sources
+            .iter()
+            .map(|(path, content)| {
+                (
+                    path.display().to_string(),
+                    extract_call_names(path, content),
+                )
+            })
+            .collect();
+        group.bench_function(BenchmarkId::new("func_graph", fx.functions), |b| {
+            b.iter(|| FuncGraph::build(&functions, &calls))
+        });
+        group.bench_function(BenchmarkId::new("func_reachability", fx.functions), |b| {
+            let graph = FuncGraph::build(&functions, &calls);
+            b.iter(|| graph.analyze())
+        });
+    }
+    group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+    let fixtures = fixtures();
+    bench_scan_parse(c, &fixtures);
+    bench_parse_modes(c, &fixtures);
+    bench_cache_formats(c, &fixtures);
+    bench_extractors(c, &fixtures);
+    bench_graphs(c, &fixtures);
+}
+
+// Whole-project iterations are slow at 100k functions: few, short samples
+criterion_group! {
+    name = analysis;
+    config = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(5));
+    targets = benches
+}
+criterion_main!(analysis);
@@ -0,0 +1,202 @@
+//! Cross-detector correlation: fold item findings under their dead module.
+//!
+//! When a whole module is unreachable, every function, constant or variant
+//! inside it is also reported by the item-level detectors. Collapsing moves
+//! those findings under the dead module, so each dead module is reported
+//! once with its contents nested instead of flooding the output.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::builder::{AnalysisResult, DeadItem};
+use crate::detect::{dead_names, find_dead};
+use crate::graph::{build_graph, reachable_from_roots};
+use crate::parse::ModuleInfo;
+use crate::report::module_path_of;
+use crate::root::resolve_root_modules;
+
+/// A dead module with the item findings folded into it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollapsedModule {
+    /// Module name (file stem)
+    pub name: String,
+    /// Module path (`crate::net::http`)
+    pub module_path: String,
+    /// Module file
+    pub file: PathBuf,
+    /// Findings inside the module, across all detectors
+    pub items: Vec<DeadItem>,
+}
+
+/// Move every item finding located in a dead module into
+/// `result.collapsed`, one entry per dead module that had findings.
+pub fn collapse_dead_modules(result: &mut AnalysisResult) {
+    let mut by_file: HashMap<PathBuf, CollapsedModule> = HashMap::new();
+    for name in &result.dead_modules {
+        if let Some(info) = result.modules.get(name) {
+            by_file.insert(
+                info.path.clone(),
+                CollapsedModule {
+                    name: name.clone(),
+                    module_path: module_path_of(&result.root, &info.path),
+                    file: info.path.clone(),
+                    items: Vec::new(),
+                },
+            );
+        }
+    }
+    if by_file.is_empty() {
+        return;
+    }
+
+    for items in [
+        &mut result.dead_functions,
+        &mut result.dead_traits,
+        &mut result.dead_constants,
+        &mut result.dead_enums,
+        &mut result.dead_macros,
+        &mut result.dead_generics,
+        &mut result.dead_matcharms,
+    ] {
+        for item in std::mem::take(items) {
+            match by_file.get_mut(&item.file) {
+                Some(module) => module.items.push(item),
+                None => items.push(item),
+            }
+        }
+    }
+
+    let mut collapsed: Vec<CollapsedModule> = by_file
+        .into_values()
+        .filter(|m| !m.items.is_empty())
+        .collect();
+    collapsed.sort_by(|a, b| a.module_path.cmp(&b.module_path));
+    result.collapsed.extend(collapsed);
+}
+
+/// Files of the modules unreachable from `roots` (or the crate's default
+/// roots when empty), as displayed in detector findings.
+pub fn dead_module_files(
+    crate_root: &Path,
+    modules: &HashMap<String, ModuleInfo>,
+    roots: &[String],
+) -> HashSet<String> {
+    let graph = build_graph(modules);
+    let root_modules = resolve_root_modules(crate_root, roots);
+    let valid_roots = root_modules
+        .iter()
+        .filter(|name| modules.contains_key(*name))
+        .map(|s| s.as_str());
+    let reachable = reachable_from_roots(&graph, valid_roots);
+    dead_names(&find_dead(modules, &reachable))
+        .into_iter()
+        .filter_map(|name| modules.get(name))
+        .map(|info| info.path.display().to_string())
+        .collect()
+}
+
+/// Files carrying a generated-code header, as displayed in detector
+/// findings. Findings inside them are not reported.
+pub fn generated_files(modules: &HashMap<String, ModuleInfo>) -> HashSet<String> {
+    modules
+        .values()
+        .filter(|info| info.generated)
+        .map(|info| info.path.display().to_string())
+        .collect()
+}
+
+/// Drop findings located in `files`, returning how many were dropped.
+///
+/// Used by single-detector reports, which only know each finding's file.
+pub fn drop_in_files<T>(
+    items: &mut Vec<T>,
+    files: &HashSet<String>,
+    file_of: impl Fn(&T) -> &str,
+) -> usize {
+    let before = items.len();
+    items.retain(|item| !files.contains(file_of(item)));
+    before - items.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Deadmod;
+
+    fn result() -> AnalysisResult {
+        Deadmod::from_sources([
+            (
+                PathBuf::from("src/lib.rs"),
+                "mod a;\npub fn api() { a::used(); }\nfn unused_root() {}".to_string(),
+            ),
+            (PathBuf::from("src/a.rs"), "pub fn used() {}".to_string()),
+            (
+                PathBuf::from("src/b.rs"),
+                "fn unused_b() {}\nconst LIMIT: u8 = 1;\nenum E { A }".to_string(),
+            ),
+        ])
+        .all()
+        .analyze()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_collapse_dead_modules() {
+        let mut result = result();
+        let before = result.dead_count();
+        collapse_dead_modules(&mut result);
+
+        let remaining: Vec<_> = result.dead_items().map(|i| i.name.as_str()).collect();
+        assert_eq!(remaining, vec!["unused_root"]);
+        assert_eq!(result.collapsed.len(), 1);
+
+        let b = &result.collapsed[0];
+        assert_eq!(b.name, "b");
+        assert_eq!(b.module_path, "crate::b");
+        assert!(b.items.iter().any(|i| i.name == "unused_b"));
+        assert!(b.items.iter().any(|i| i.name == "LIMIT"));
+        assert_eq!(result.dead_count() + b.items.len(), before);
+    }
+
+    #[test]
+    fn test_collapse_without_dead_modules() {
+        let mut result = result();
+        result.dead_modules.clear();
+        let before = result.dead_count();
+        collapse_dead_modules(&mut result);
+        assert!(result.collapsed.is_empty());
+        assert_eq!(result.dead_count(), before);
+    }
+
+    #[test]
+    fn test_drop_in_files() {
+        let files: HashSet<String> = ["src/b.rs".to_string()].into_iter().collect();
+        let mut items = vec![("f", "src/a.rs"), ("g", "src/b.rs"), ("h", "src/b.rs")];
+        assert_eq!(drop_in_files(&mut items, &files, |i| i.1), 2);
+        assert_eq!(items, vec![("f", "src/a.rs")]);
+    }
+
+    #[test]
+    fn test_dead_module_files() {
+        let dir = std::env::temp_dir().join(format!("deadmod_aggregate_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "mod a;").unwrap();
+        std::fs::write(dir.join("src/a.rs"), "").unwrap();
+        std::fs::write(dir.join("src/b.rs"), "").unwrap();
+
+        let files = vec![
+            dir.join("src/lib.rs"),
+            dir.join("src/a.rs"),
+            dir.join("src/b.rs"),
+        ];
+        let modules = crate::parse::parse_modules(&files).unwrap();
+        let dead = dead_module_files(&dir, &modules, &[]);
+        assert_eq!(dead.len(), 1);
+        assert!(dead.contains(&dir.join("src/b.rs").display().to_string()));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
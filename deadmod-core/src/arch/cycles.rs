@@ -0,0 +1,91 @@
+//! Module dependency cycle detection.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::graph::{build_graph, find_cycles};
+use crate::parse::ModuleInfo;
+
+/// A set of modules that depend on each other (one strongly connected component).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModuleCycle {
+    /// Member modules, sorted by name
+    pub modules: Vec<String>,
+}
+
+/// Find all dependency cycles between modules.
+pub fn find_module_cycles(mods: &HashMap<String, ModuleInfo>) -> Vec<ModuleCycle> {
+    let graph = build_graph(mods);
+    find_cycles(&graph)
+        .into_iter()
+        .map(|scc| ModuleCycle {
+            modules: scc.into_iter().map(String::from).collect(),
+        })
+        .collect()
+}
+
+/// Formats cycles in plain text format.
+pub fn format_cycles_plain(cycles: &[ModuleCycle]) -> String {
+    if cycles.is_empty() {
+        return "No module dependency cycles found.\n".to_string();
+    }
+
+    let mut out = format!("MODULE CYCLES ({}):\n", cycles.len());
+    for cycle in cycles {
+        out.push_str(&format!(
+            "- {} ({} modules)\n",
+            cycle.modules.join(" <-> "),
+            cycle.modules.len()
+        ));
+    }
+    out
+}
+
+/// Formats cycles in JSON format.
+pub fn format_cycles_json(cycles: &[ModuleCycle]) -> String {
+    serde_json::to_string_pretty(&serde_json::json!({ "cycles": cycles }))
+        .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn module(name: &str, refs: &[&str]) -> (String, ModuleInfo) {
+        let mut info = ModuleInfo::new(PathBuf::from(format!("src/{}.rs", name)));
+        info.refs.extend(refs.iter().map(|r| r.to_string()));
+        (name.to_string(), info)
+    }
+
+    #[test]
+    fn test_find_module_cycles_and_format() {
+        let mods: HashMap<String, ModuleInfo> = [
+            module("main", &["api"]),
+            module("api", &["db"]),
+            module("db", &["api"]),
+        ]
+        .into_iter()
+        .collect();
+
+        let cycles = find_module_cycles(&mods);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].modules, vec!["api", "db"]);
+
+        let plain = format_cycles_plain(&cycles);
+        assert!(plain.contains("MODULE CYCLES (1)"));
+        assert!(plain.contains("api <-> db"));
+
+        let json: serde_json::Value = serde_json::from_str(&format_cycles_json(&cycles)).unwrap();
+        assert_eq!(json["cycles"][0]["modules"][1], "db");
+    }
+
+    #[test]
+    fn test_format_cycles_empty() {
+        assert_eq!(
+            format_cycles_plain(&[]),
+            "No module dependency cycles found.\n"
+        );
+    }
+}
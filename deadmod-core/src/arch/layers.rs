@@ -0,0 +1,238 @@
+//! Forbidden-dependency (layering) rules.
+//!
+//! Rules are read from a TOML file passed with `--rules`, or from the
+//! `[layers]` section of `deadmod.toml`:
+//!
+//! ```toml
+//! [[rule]]                # `[[layers.rule]]` inside deadmod.toml
+//! from = "domain*"
+//! deny = ["infra*", "api"]
+//! reason = "domain must not depend on infrastructure"
+//! ```
+//!
+//! Patterns match module names and support `*` as a wildcard. Only direct
+//! dependencies are checked; combine with cycle detection for transitive
+//! problems.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::parse::ModuleInfo;
+
+/// A set of layering rules.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LayerRules {
+    /// Rules, checked independently of each other
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<LayerRule>,
+}
+
+/// Modules matching `from` must not depend on modules matching any `deny` pattern.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LayerRule {
+    /// Pattern for the depending module
+    pub from: String,
+    /// Patterns for forbidden dependencies
+    pub deny: Vec<String>,
+    /// Explanation shown with violations
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// A dependency that breaks a layering rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LayerViolation {
+    /// Depending module
+    pub from: String,
+    /// Forbidden dependency
+    pub to: String,
+    /// The `deny` pattern that matched
+    pub denied_by: String,
+    /// Reason from the rule, if any
+    pub reason: Option<String>,
+}
+
+impl LayerRules {
+    /// Load rules from a standalone TOML file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read layer rules: {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Invalid layer rules: {}", path.display()))
+    }
+}
+
+/// Match a module name against a pattern where `*` matches any sequence.
+fn pattern_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.as_bytes();
+    let name = name.as_bytes();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == name[n] {
+            p += 1;
+            n += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            // Let the last `*` swallow one more character
+            p = star_p + 1;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Check every module dependency against the layering rules.
+///
+/// Violations are sorted by `(from, to)` for deterministic output.
+pub fn check_layers(mods: &HashMap<String, ModuleInfo>, rules: &LayerRules) -> Vec<LayerViolation> {
+    let mut violations = Vec::new();
+
+    for (name, info) in mods {
+        for rule in rules
+            .rules
+            .iter()
+            .filter(|r| pattern_matches(&r.from, name))
+        {
+            for dep in info
+                .refs
+                .iter()
+                .filter(|d| mods.contains_key(*d) && *d != name)
+            {
+                if let Some(denied) = rule.deny.iter().find(|p| pattern_matches(p, dep)) {
+                    violations.push(LayerViolation {
+                        from: name.clone(),
+                        to: dep.clone(),
+                        denied_by: denied.clone(),
+                        reason: rule.reason.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    violations.sort_by(|a, b| a.from.cmp(&b.from).then_with(|| a.to.cmp(&b.to)));
+    violations.dedup_by(|a, b| a.from == b.from && a.to == b.to);
+    violations
+}
+
+/// Formats layer violations in plain text format.
+pub fn format_violations_plain(violations: &[LayerViolation]) -> String {
+    if violations.is_empty() {
+        return "No layer violations found.\n".to_string();
+    }
+
+    let mut out = format!("LAYER VIOLATIONS ({}):\n", violations.len());
+    for v in violations {
+        out.push_str(&format!(
+            "- {} -> {} (denied by '{}')",
+            v.from, v.to, v.denied_by
+        ));
+        if let Some(ref reason) = v.reason {
+            out.push_str(&format!(": {}", reason));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Formats layer violations in JSON format.
+pub fn format_violations_json(violations: &[LayerViolation]) -> String {
+    serde_json::to_string_pretty(&serde_json::json!({ "violations": violations }))
+        .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn module(name: &str, refs: &[&str]) -> (String, ModuleInfo) {
+        let mut info = ModuleInfo::new(PathBuf::from(format!("src/{}.rs", name)));
+        info.refs.extend(refs.iter().map(|r| r.to_string()));
+        (name.to_string(), info)
+    }
+
+    fn rules(toml_src: &str) -> LayerRules {
+        toml::from_str(toml_src).unwrap()
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches("api", "api"));
+        assert!(!pattern_matches("api", "api_v2"));
+        assert!(pattern_matches("api*", "api_v2"));
+        assert!(pattern_matches("*_test", "db_test"));
+        assert!(pattern_matches("*db*", "mydb_pool"));
+        assert!(pattern_matches("a*b*c", "aXXbYYc"));
+        assert!(!pattern_matches("a*b*c", "aXXbYY"));
+        assert!(pattern_matches("*", "anything"));
+    }
+
+    #[test]
+    fn test_check_layers_reports_denied_edges() {
+        let mods: HashMap<String, ModuleInfo> = [
+            module("domain", &["infra_db", "model"]),
+            module("infra_db", &["domain"]),
+            module("model", &[]),
+        ]
+        .into_iter()
+        .collect();
+
+        let rules = rules(
+            r#"
+            [[rule]]
+            from = "domain"
+            deny = ["infra*"]
+            reason = "domain stays pure"
+            "#,
+        );
+
+        let violations = check_layers(&mods, &rules);
+        assert_eq!(
+            violations,
+            vec![LayerViolation {
+                from: "domain".to_string(),
+                to: "infra_db".to_string(),
+                denied_by: "infra*".to_string(),
+                reason: Some("domain stays pure".to_string()),
+            }]
+        );
+
+        let plain = format_violations_plain(&violations);
+        assert!(plain.contains("domain -> infra_db (denied by 'infra*'): domain stays pure"));
+    }
+
+    #[test]
+    fn test_check_layers_no_rules() {
+        let mods: HashMap<String, ModuleInfo> = [module("a", &["b"]), module("b", &[])]
+            .into_iter()
+            .collect();
+        assert!(check_layers(&mods, &LayerRules::default()).is_empty());
+    }
+
+    #[test]
+    fn test_load_rules_file() {
+        let path = std::env::temp_dir().join(format!("deadmod_layers_{}.toml", std::process::id()));
+        fs::write(&path, "[[rule]]\nfrom = \"*\"\ndeny = [\"legacy\"]\n").unwrap();
+
+        let loaded = LayerRules::load(&path).unwrap();
+        assert_eq!(loaded.rules.len(), 1);
+        assert!(loaded.rules[0].reason.is_none());
+
+        fs::write(&path, "[[rule]]\nfrom = 1\n").unwrap();
+        assert!(LayerRules::load(&path).is_err());
+
+        fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,28 @@
+//! Architecture linting on the module dependency graph.
+//!
+//! Reuses the graph built for dead module detection to check structural
+//! rules instead of reachability:
+//! - **Cycles**: modules that (transitively) depend on each other
+//! - **Layers**: forbidden dependencies declared as `from` → `deny` rules
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deadmod_core::arch::{check_layers, find_module_cycles, LayerRules};
+//!
+//! for cycle in find_module_cycles(&mods) {
+//!     println!("cycle: {}", cycle.modules.join(" -> "));
+//! }
+//!
+//! let rules = LayerRules::load(Path::new("layers.toml"))?;
+//! let violations = check_layers(&mods, &rules);
+//! ```
+
+pub mod cycles;
+pub mod layers;
+
+pub use cycles::{find_module_cycles, format_cycles_json, format_cycles_plain, ModuleCycle};
+pub use layers::{
+    check_layers, format_violations_json, format_violations_plain, LayerRule, LayerRules,
+    LayerViolation,
+};
@@ -0,0 +1,509 @@
+//! Source snapshots in `.tar`, `.tar.gz` and `.zip` archives.
+//!
+//! [`ArchiveSource`] reads an archive into memory and serves its Rust files
+//! through a [`MemorySource`], so a published crate, a CI artifact or an
+//! uploaded snapshot can be analyzed without extracting it to disk:
+//!
+//! ```rust,ignore
+//! use deadmod_core::prelude::*;
+//! use deadmod_core::archive::ArchiveSource;
+//!
+//! let source = ArchiveSource::open("serde-1.0.200.crate".as_ref(), &Limits::default())?;
+//! let result = Deadmod::new("").with_source(source).all().analyze()?;
+//! ```
+//!
+//! The crate is the shallowest directory holding a `Cargo.toml` (the
+//! top-level directory of a `cargo package` or GitHub snapshot), else the
+//! archive root; file paths are relative to it. Entries with absolute
+//! paths or `..` components are never read.
+//!
+//! [`ArchiveLimits`] caps the entry count and the decompressed size of each
+//! Rust file and of all of them together, so a tar or zip bomb is rejected
+//! while reading instead of exhausting memory.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+
+use crate::error::DeadmodError;
+use crate::limits::{Limits, SkipReason, Skipped};
+use crate::scan::is_scanned_path;
+use crate::source::{MemorySource, SourceProvider};
+
+/// Supported archive formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// Uncompressed tarball (`.tar`)
+    Tar,
+    /// Gzip-compressed tarball (`.tar.gz`, `.tgz`, `.crate`)
+    TarGz,
+    /// Zip archive (`.zip`)
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Recognize an archive by its leading bytes, falling back to the
+    /// extension of `path`.
+    pub fn detect(path: &Path, bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            return Some(Self::TarGz);
+        }
+        if bytes.starts_with(b"PK\x03\x04") || bytes.starts_with(b"PK\x05\x06") {
+            return Some(Self::Zip);
+        }
+        if bytes.get(257..262) == Some(b"ustar".as_slice()) {
+            return Some(Self::Tar);
+        }
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") || name.ends_with(".crate") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Caps on reading an archive. Exceeding any of them fails the read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveLimits {
+    /// Largest decompressed size of a single Rust file, in bytes
+    pub max_entry_size: u64,
+    /// Largest decompressed size of all Rust files together, in bytes
+    pub max_total_size: u64,
+    /// Most entries (files, directories and links) in the archive
+    pub max_entries: usize,
+}
+
+impl Default for ArchiveLimits {
+    /// 16 MiB per file, 256 MiB in total and 100 000 entries: far beyond
+    /// any published crate.
+    fn default() -> Self {
+        Self {
+            max_entry_size: 16 << 20,
+            max_total_size: 256 << 20,
+            max_entries: 100_000,
+        }
+    }
+}
+
+impl ArchiveLimits {
+    /// Fail on a Rust file larger than `bytes` once decompressed.
+    pub fn with_max_entry_size(mut self, bytes: u64) -> Self {
+        self.max_entry_size = bytes;
+        self
+    }
+
+    /// Fail once the Rust files read total more than `bytes`.
+    pub fn with_max_total_size(mut self, bytes: u64) -> Self {
+        self.max_total_size = bytes;
+        self
+    }
+
+    /// Fail on an archive with more than `count` entries.
+    pub fn with_max_entries(mut self, count: usize) -> Self {
+        self.max_entries = count;
+        self
+    }
+}
+
+/// Rust files of a crate inside an archive, held in memory.
+#[derive(Debug, Clone)]
+pub struct ArchiveSource {
+    files: MemorySource,
+    crate_dir: PathBuf,
+    skipped: Vec<Skipped>,
+}
+
+/// Entries read from an archive, before the crate directory is known.
+#[derive(Default)]
+struct Entries {
+    /// Every file path, to locate `Cargo.toml`
+    paths: Vec<PathBuf>,
+    sources: BTreeMap<PathBuf, String>,
+    too_large: Vec<(PathBuf, u64)>,
+    /// Entries seen so far
+    count: usize,
+    /// Decompressed bytes read into `sources`
+    total_size: u64,
+}
+
+impl ArchiveSource {
+    /// Read the archive at `path`, leaving out files beyond `limits`, with
+    /// the default [`ArchiveLimits`].
+    pub fn open(path: &Path, limits: &Limits) -> Result<Self> {
+        Self::open_with(path, limits, &ArchiveLimits::default())
+    }
+
+    /// [`ArchiveSource::open`] with explicit archive caps.
+    pub fn open_with(path: &Path, limits: &Limits, caps: &ArchiveLimits) -> Result<Self> {
+        let bytes = fs::read(path).map_err(|e| DeadmodError::io(path, e))?;
+        let Some(format) = ArchiveFormat::detect(path, &bytes) else {
+            bail!("{} is not a .tar, .tar.gz or .zip archive", path.display());
+        };
+        Self::from_bytes_with(&bytes, format, limits, caps)
+            .with_context(|| format!("Failed to read archive {}", path.display()))
+    }
+
+    /// Read an archive held in memory, with the default [`ArchiveLimits`].
+    pub fn from_bytes(bytes: &[u8], format: ArchiveFormat, limits: &Limits) -> Result<Self> {
+        Self::from_bytes_with(bytes, format, limits, &ArchiveLimits::default())
+    }
+
+    /// [`ArchiveSource::from_bytes`] with explicit archive caps.
+    pub fn from_bytes_with(
+        bytes: &[u8],
+        format: ArchiveFormat,
+        limits: &Limits,
+        caps: &ArchiveLimits,
+    ) -> Result<Self> {
+        let entries = match format {
+            ArchiveFormat::Tar => read_tar(bytes, limits, caps)?,
+            ArchiveFormat::TarGz => read_tar(GzDecoder::new(bytes), limits, caps)?,
+            ArchiveFormat::Zip => read_zip(bytes, limits, caps)?,
+        };
+        Ok(Self::from_entries(entries, limits))
+    }
+
+    fn from_entries(entries: Entries, limits: &Limits) -> Self {
+        let crate_dir = crate_dir(&entries.paths);
+        let in_crate = |path: &Path| {
+            path.strip_prefix(&crate_dir)
+                .ok()
+                .filter(|rel| is_scanned_path(Path::new(""), rel, &[]))
+                .map(Path::to_path_buf)
+        };
+
+        let mut skipped: Vec<Skipped> = entries
+            .too_large
+            .iter()
+            .filter_map(|(path, size)| {
+                let rel = in_crate(path)?;
+                Some(Skipped {
+                    input: rel.display().to_string(),
+                    reason: SkipReason::TooLarge {
+                        size: *size,
+                        limit: limits.max_file_size.unwrap_or_default(),
+                    },
+                })
+            })
+            .collect();
+
+        let mut sources: Vec<(PathBuf, String)> = entries
+            .sources
+            .into_iter()
+            .filter_map(|(path, content)| Some((in_crate(&path)?, content)))
+            .collect();
+        if let Some(limit) = limits.max_files.filter(|&limit| sources.len() > limit) {
+            skipped.extend(sources.drain(limit..).map(|(path, _)| Skipped {
+                input: path.display().to_string(),
+                reason: SkipReason::TooManyFiles { limit },
+            }));
+        }
+
+        Self {
+            files: MemorySource::new(sources),
+            crate_dir,
+            skipped,
+        }
+    }
+
+    /// Directory of the crate inside the archive (empty at the top level).
+    pub fn crate_dir(&self) -> &Path {
+        &self.crate_dir
+    }
+}
+
+impl SourceProvider for ArchiveSource {
+    fn root(&self) -> &Path {
+        self.files.root()
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        self.files.files()
+    }
+
+    fn read(&self, path: &Path) -> Result<String> {
+        self.files.read(path)
+    }
+
+    fn skipped(&self) -> Vec<Skipped> {
+        self.skipped.clone()
+    }
+}
+
+fn read_tar(reader: impl Read, limits: &Limits, caps: &ArchiveLimits) -> Result<Entries> {
+    let mut entries = Entries::default();
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries().context("Invalid tar archive")? {
+        entries.count(caps)?;
+        let mut entry = entry.context("Invalid tar entry")?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path().context("Invalid tar entry path")?.into_owned();
+        let size = entry.size();
+        entries.add(&name, size, &mut entry, limits, caps)?;
+    }
+    Ok(entries)
+}
+
+fn read_zip(bytes: &[u8], limits: &Limits, caps: &ArchiveLimits) -> Result<Entries> {
+    let mut entries = Entries::default();
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).context("Invalid zip archive")?;
+    for i in 0..archive.len() {
+        entries.count(caps)?;
+        let mut file = archive.by_index(i).context("Invalid zip entry")?;
+        if file.is_dir() {
+            continue;
+        }
+        let name = PathBuf::from(file.name());
+        let size = file.size();
+        entries.add(&name, size, &mut file, limits, caps)?;
+    }
+    Ok(entries)
+}
+
+impl Entries {
+    /// Count one more entry, failing past [`ArchiveLimits::max_entries`].
+    fn count(&mut self, caps: &ArchiveLimits) -> Result<()> {
+        self.count += 1;
+        if self.count > caps.max_entries {
+            bail!("archive has more than {} entries", caps.max_entries);
+        }
+        Ok(())
+    }
+
+    /// Record one file entry, reading it if it is an analyzable `.rs` file.
+    fn add(
+        &mut self,
+        name: &Path,
+        size: u64,
+        reader: &mut dyn Read,
+        limits: &Limits,
+        caps: &ArchiveLimits,
+    ) -> Result<()> {
+        let Some(path) = safe_path(name) else {
+            eprintln!("[WARN] Skipping unsafe archive entry {}", name.display());
+            return Ok(());
+        };
+        self.paths.push(path.clone());
+        if path.extension().is_none_or(|ext| ext != "rs") {
+            return Ok(());
+        }
+        if limits.max_file_size.is_some_and(|limit| size > limit) {
+            self.too_large.push((path, size));
+            return Ok(());
+        }
+        if size > caps.max_entry_size {
+            bail!(
+                "archive entry {} is {} bytes decompressed, over the {} byte cap",
+                path.display(),
+                size,
+                caps.max_entry_size
+            );
+        }
+        self.total_size += size;
+        if self.total_size > caps.max_total_size {
+            bail!(
+                "archive sources exceed {} bytes decompressed",
+                caps.max_total_size
+            );
+        }
+
+        // The declared size bounds the read, whatever the entry really holds
+        let mut content = Vec::new();
+        reader
+            .take(size)
+            .read_to_end(&mut content)
+            .with_context(|| format!("Failed to read archive entry {}", path.display()))?;
+        match String::from_utf8(content) {
+            Ok(content) => {
+                self.sources.insert(path, content);
+            }
+            Err(_) => eprintln!("[WARN] Skipping {}: not valid UTF-8", path.display()),
+        }
+        Ok(())
+    }
+}
+
+/// `name` as a relative path, or `None` if it is absolute or escapes the
+/// archive through `..`.
+fn safe_path(name: &Path) -> Option<PathBuf> {
+    let mut path = PathBuf::new();
+    for component in name.components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    (!path.as_os_str().is_empty()).then_some(path)
+}
+
+/// Directory of the shallowest `Cargo.toml`, else the archive root.
+fn crate_dir(paths: &[PathBuf]) -> PathBuf {
+    paths
+        .iter()
+        .filter(|path| path.file_name().is_some_and(|name| name == "Cargo.toml"))
+        .min_by_key(|path| (path.components().count(), path.as_path()))
+        .and_then(|manifest| manifest.parent())
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Deadmod;
+    use std::io::Write;
+
+    const FILES: &[(&str, &str)] = &[
+        ("demo-0.1.0/Cargo.toml", "[package]\nname = \"demo\"\n"),
+        (
+            "demo-0.1.0/src/main.rs",
+            "mod used;\nfn main() { used::run(); }\n",
+        ),
+        ("demo-0.1.0/src/used.rs", "pub fn run() {}\n"),
+        ("demo-0.1.0/src/dead.rs", "pub fn old() {}\n"),
+        (
+            "demo-0.1.0/target/debug/build/out.rs",
+            "pub fn generated() {}\n",
+        ),
+    ];
+
+    fn tar_gz(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::fast(),
+        ));
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        for (path, content) in files {
+            writer.start_file(*path, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_archive_source_analyzes_snapshot() {
+        for bytes in [tar_gz(FILES), zip(FILES)] {
+            let format = ArchiveFormat::detect(Path::new("snapshot"), &bytes).unwrap();
+            let source = ArchiveSource::from_bytes(&bytes, format, &Limits::default()).unwrap();
+            assert_eq!(source.crate_dir(), Path::new("demo-0.1.0"));
+            assert_eq!(
+                source.files().unwrap(),
+                vec![
+                    PathBuf::from("src/dead.rs"),
+                    PathBuf::from("src/main.rs"),
+                    PathBuf::from("src/used.rs")
+                ]
+            );
+
+            let result = Deadmod::new("").with_source(source).analyze().unwrap();
+            assert_eq!(result.dead_modules, vec!["dead"], "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn test_archive_source_limits_and_unsafe_paths() {
+        let big = "// padding\n".repeat(100);
+        let files = [
+            ("src/main.rs", "fn main() {}\n"),
+            ("src/big.rs", big.as_str()),
+            ("src/a.rs", ""),
+            ("src/b.rs", ""),
+            ("../escape.rs", "fn escape() {}\n"),
+        ];
+        let limits = Limits::default().with_max_file_size(500).with_max_files(2);
+        let source = ArchiveSource::from_bytes(&zip(&files), ArchiveFormat::Zip, &limits).unwrap();
+
+        assert_eq!(source.crate_dir(), Path::new(""));
+        assert_eq!(
+            source.files().unwrap(),
+            vec![PathBuf::from("src/a.rs"), PathBuf::from("src/b.rs")]
+        );
+        let skipped: Vec<String> = source.skipped().iter().map(|s| s.input.clone()).collect();
+        assert_eq!(skipped, vec!["src/big.rs", "src/main.rs"]);
+        assert!(matches!(
+            source.skipped()[0].reason,
+            SkipReason::TooLarge {
+                size: 1100,
+                limit: 500
+            }
+        ));
+    }
+
+    #[test]
+    fn test_archive_limits_reject_bombs() {
+        let big = "// padding\n".repeat(200);
+        let oversized = [
+            ("src/main.rs", "fn main() {}\n"),
+            ("src/big.rs", big.as_str()),
+        ];
+        let many: Vec<(String, &str)> = (0..10).map(|i| (format!("src/m{}.rs", i), "")).collect();
+        let many: Vec<(&str, &str)> = many.iter().map(|(p, c)| (p.as_str(), *c)).collect();
+
+        let per_entry = ArchiveLimits::default().with_max_entry_size(1000);
+        let total = ArchiveLimits::default().with_max_total_size(2000);
+        let count = ArchiveLimits::default().with_max_entries(5);
+        for (bytes, format) in [
+            (tar_gz(&oversized), ArchiveFormat::TarGz),
+            (zip(&oversized), ArchiveFormat::Zip),
+        ] {
+            let err =
+                ArchiveSource::from_bytes_with(&bytes, format, &Limits::default(), &per_entry)
+                    .unwrap_err();
+            assert!(err.to_string().contains("src/big.rs"), "{}", err);
+
+            let doubled = [oversized[1], ("src/big2.rs", big.as_str())];
+            let bytes = match format {
+                ArchiveFormat::Zip => zip(&doubled),
+                _ => tar_gz(&doubled),
+            };
+            let err = ArchiveSource::from_bytes_with(&bytes, format, &Limits::default(), &total)
+                .unwrap_err();
+            assert!(err.to_string().contains("2000 bytes"), "{}", err);
+        }
+        for (bytes, format) in [
+            (tar_gz(&many), ArchiveFormat::TarGz),
+            (zip(&many), ArchiveFormat::Zip),
+        ] {
+            let err = ArchiveSource::from_bytes_with(&bytes, format, &Limits::default(), &count)
+                .unwrap_err();
+            assert!(err.to_string().contains("more than 5 entries"), "{}", err);
+        }
+
+        // A file skipped by `max_file_size` is never read, so it passes the cap
+        let limits = Limits::default().with_max_file_size(500);
+        let source = ArchiveSource::from_bytes_with(
+            &zip(&oversized),
+            ArchiveFormat::Zip,
+            &limits,
+            &per_entry,
+        )
+        .unwrap();
+        assert_eq!(source.files().unwrap(), vec![PathBuf::from("src/main.rs")]);
+    }
+}
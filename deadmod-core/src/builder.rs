@@ -9,23 +9,76 @@
 //!     .with_cache(true)
 //!     .include_functions(true)
 //!     .include_traits(true)
+//!     .with_threads(4)
 //!     .dry_run(true)
 //!     .analyze()?;
 //!
 //! println!("Dead modules: {:?}", result.dead_modules);
 //! ```
+//!
+//! In-memory sources skip scanning and caching entirely; see [`crate::source`]:
+//!
+//! ```rust,ignore
+//! let result = Deadmod::from_sources([
+//!     ("src/lib.rs".into(), "mod a;".to_string()),
+//!     ("src/a.rs".into(), String::new()),
+//! ])
+//! .analyze()?;
+//! ```
 
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
-
-use crate::cache;
-use crate::detect::find_dead;
-use crate::graph::{build_graph, reachable_from_roots};
-use crate::parse::ModuleInfo;
-use crate::root::find_root_modules;
-use crate::scan::gather_rs_files;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info_span};
+
+use crate::aggregate::{collapse_dead_modules, CollapsedModule};
+use crate::cache::ParseMode;
+use crate::collision::{find_collisions, ModuleCollision};
+use crate::common::{
+    extract_macro_mentions, finding_id, ignored_ids, module_id, Confidence, DeadImpl,
+    HiddenApiPolicy, KeptItem, LivenessRules, PubPolicy,
+};
+use crate::config::DetectorToggles;
+use crate::constants::{extract_const_usage, extract_constants, ConstGraph, DeadConst};
+use crate::detect::{dead_names, find_dead};
+use crate::dirty::{Detector, DetectorRun, IncrementalState};
+use crate::edition::Edition;
+use crate::enums::{extract_variant_usage, extract_variants, DeadVariant, EnumGraph};
+use crate::error::DeadmodError;
+use crate::evidence::AnalysisEvidence;
+use crate::fields::DeadField;
+use crate::func::{extract_call_names, extract_functions, FuncGraph, FunctionInfo};
+use crate::generics::{
+    extract_declared_generics, extract_generic_usages, DeadGeneric, GenericGraph, GenericKind,
+};
+use crate::grace::{GracePeriod, RecentItem};
+use crate::graph::{build_graph, module_crate, reachable_from_roots};
+use crate::limits::{Deadline, Limits, Skipped};
+use crate::macros::{
+    extract_macro_usages, extract_macros, DeadMacro, ExportedMacroPolicy, MacroGraph,
+};
+use crate::matcharms::{extract_match_arms, extract_match_usages, DeadMatchArm, MatchGraph};
+#[cfg(feature = "cargo-metadata")]
+use crate::metadata::CargoMetadata;
+use crate::parallel::{resolve_jobs, run_with_threads};
+use crate::params::DeadParam;
+use crate::parse::{select_edges, EdgeKinds, ModuleInfo};
+use crate::progress::{Phase, Progress};
+use crate::project_model::ProjectModel;
+use crate::report::{join_module_path, module_path_of, PathFormatter};
+use crate::root::roots_for_crate;
+use crate::source::{FsSource, MemorySource, SourceProvider};
+use crate::suppressions::Suppressions;
+use crate::traits::{
+    extract_trait_usages, extract_traits, TraitGraph, TraitImplMethod, TraitMethodDef,
+};
+use crate::workspace::{
+    combine_crate_modules_with_externs, find_all_crates, find_workspace_root, is_workspace_root,
+    member_name,
+};
 
 /// Builder for configuring dead code analysis.
 ///
@@ -42,8 +95,8 @@ pub struct Deadmod {
     /// Root path of the crate to analyze
     root: PathBuf,
 
-    /// Whether to use incremental caching
-    use_cache: bool,
+    /// How the incremental cache is used
+    parse_mode: ParseMode,
 
     /// Whether to include function-level analysis
     include_functions: bool,
@@ -66,9 +119,15 @@ pub struct Deadmod {
     /// Whether to include match arm analysis
     include_matcharms: bool,
 
+    /// Detectors deadmod.toml's `[detectors]` table leaves enabled
+    toggles: DetectorToggles,
+
     /// Whether to analyze tests as entry points
     include_tests: bool,
 
+    /// Whether to report test helpers no test uses
+    include_test_helpers: bool,
+
     /// Custom excluded directories
     excluded_dirs: Vec<String>,
 
@@ -78,8 +137,49 @@ pub struct Deadmod {
     /// Dry-run mode (don't modify files)
     dry_run: bool,
 
+    /// Confirm large fixes without prompting
+    force: bool,
+
     /// Verbose output
     verbose: bool,
+
+    /// Custom source provider (defaults to scanning `root`)
+    source: Option<Arc<dyn SourceProvider>>,
+
+    /// Worker threads for a dedicated pool (`None`: `DEADMOD_JOBS` or the global pool)
+    threads: Option<usize>,
+
+    /// How `#[doc(hidden)]` public functions are treated
+    hidden_policy: HiddenApiPolicy,
+    pub_policy: PubPolicy,
+    exported_macro_policy: ExportedMacroPolicy,
+
+    /// Attributes and registry macros that keep functions and constants alive
+    liveness: LivenessRules,
+
+    /// Findings below this confidence are dropped
+    min_confidence: Confidence,
+
+    /// Custom root modules replacing Cargo's entry points (empty: detect)
+    roots: Vec<String>,
+
+    /// Fold findings inside dead modules into [`AnalysisResult::collapsed`]
+    collapse: bool,
+
+    /// Days newly added findings go to [`AnalysisResult::recent`] (`None`: off)
+    grace_period: Option<u32>,
+
+    /// Caps on scanned files and analysis time
+    limits: Limits,
+
+    /// Reference kinds that create module edges (`None`: the crate's config)
+    edge_kinds: Option<EdgeKinds>,
+
+    /// Edition to resolve paths with (`None`: the crate's `Cargo.toml`)
+    edition: Option<Edition>,
+
+    /// Where scanning, parsing and detector progress is reported
+    progress: Progress,
 }
 
 impl Deadmod {
@@ -87,7 +187,7 @@ impl Deadmod {
     pub fn new(root: impl Into<PathBuf>) -> Self {
         Self {
             root: root.into(),
-            use_cache: true,
+            parse_mode: ParseMode::Cached,
             include_functions: false,
             include_traits: false,
             include_constants: false,
@@ -95,17 +195,62 @@ impl Deadmod {
             include_macros: false,
             include_generics: false,
             include_matcharms: false,
+            toggles: DetectorToggles::default(),
             include_tests: true,
+            include_test_helpers: false,
             excluded_dirs: Vec::new(),
             ignored_patterns: Vec::new(),
             dry_run: false,
+            force: false,
             verbose: false,
+            source: None,
+            threads: None,
+            hidden_policy: HiddenApiPolicy::default(),
+            pub_policy: PubPolicy::default(),
+            exported_macro_policy: ExportedMacroPolicy::default(),
+            liveness: LivenessRules::default(),
+            min_confidence: Confidence::Low,
+            roots: Vec::new(),
+            collapse: false,
+            grace_period: None,
+            limits: Limits::default(),
+            edge_kinds: None,
+            edition: None,
+            progress: Progress::none(),
         }
     }
 
+    /// Create an analysis builder over in-memory `(path, content)` pairs.
+    ///
+    /// Nothing is scanned, cached or read from disk.
+    pub fn from_sources(sources: impl IntoIterator<Item = (PathBuf, String)>) -> Self {
+        Self::new(PathBuf::new()).with_source(MemorySource::new(sources))
+    }
+
+    /// Read files from a custom [`SourceProvider`] instead of scanning `root`.
+    ///
+    /// The provider takes over caching, so [`Deadmod::with_cache`] no longer
+    /// applies.
+    pub fn with_source(mut self, source: impl SourceProvider + 'static) -> Self {
+        self.root = source.root().to_path_buf();
+        self.source = Some(Arc::new(source));
+        self
+    }
+
     /// Enable or disable incremental caching.
     pub fn with_cache(mut self, enabled: bool) -> Self {
-        self.use_cache = enabled;
+        self.parse_mode = if enabled {
+            ParseMode::Cached
+        } else {
+            ParseMode::Uncached
+        };
+        self
+    }
+
+    /// Choose how the cache is used and files are read, e.g.
+    /// [`ParseMode::Fast`] for cold CI runs.
+    pub fn with_parse_mode(mut self, mode: ParseMode) -> Self {
+        self.parse_mode = mode;
         self
     }
 
@@ -157,6 +302,15 @@ impl Deadmod {
         self
     }
 
+    /// Report test helpers no test uses: dead functions compiled only for
+    /// tests, which function analysis otherwise never reports. Runs function
+    /// analysis; without [`Deadmod::include_functions`] only test helpers
+    /// are listed. Not enabled by [`Deadmod::all`].
+    pub fn include_test_helpers(mut self, enabled: bool) -> Self {
+        self.include_test_helpers = enabled;
+        self
+    }
+
     /// Enable all detection modes.
     pub fn all(mut self) -> Self {
         self.include_functions = true;
@@ -169,6 +323,14 @@ impl Deadmod {
         self
     }
 
+    /// Turn detectors off, as in deadmod.toml's `[detectors]` table. Applies
+    /// on top of the `include_*` options and [`Deadmod::all`]; a disabled
+    /// `modules` reports no dead modules.
+    pub fn with_detector_toggles(mut self, toggles: DetectorToggles) -> Self {
+        self.toggles = toggles;
+        self
+    }
+
     /// Add directories to exclude from scanning.
     pub fn exclude_dirs(mut self, dirs: impl IntoIterator<Item = impl Into<String>>) -> Self {
         self.excluded_dirs.extend(dirs.into_iter().map(Into::into));
@@ -187,53 +349,407 @@ impl Deadmod {
         self
     }
 
+    /// Confirm fixes that remove more files than the safety threshold.
+    pub fn force(mut self, enabled: bool) -> Self {
+        self.force = enabled;
+        self
+    }
+
     /// Enable verbose output.
     pub fn verbose(mut self, enabled: bool) -> Self {
         self.verbose = enabled;
         self
     }
 
+    /// Run parallel work on a dedicated pool of `threads` workers instead of
+    /// rayon's global pool. Overrides `DEADMOD_JOBS`.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// How `#[doc(hidden)]` public functions are treated by function analysis.
+    pub fn with_hidden_policy(mut self, policy: HiddenApiPolicy) -> Self {
+        self.hidden_policy = policy;
+        self
+    }
+
+    /// Whether `pub` functions are entry points for function analysis
+    /// (default [`PubPolicy::Always`]).
+    pub fn with_pub_policy(mut self, policy: PubPolicy) -> Self {
+        self.pub_policy = policy;
+        self
+    }
+
+    /// How macro analysis treats exported macros unused in the crate
+    /// (default [`ExportedMacroPolicy::WorkspaceCheck`]).
+    pub fn with_exported_macro_policy(mut self, policy: ExportedMacroPolicy) -> Self {
+        self.exported_macro_policy = policy;
+        self
+    }
+
+    /// Attributes (`#[used]`, `#[ctor]`, ...) and registry macros
+    /// (`inventory::submit!`) that keep functions and constants alive.
+    pub fn with_liveness(mut self, rules: LivenessRules) -> Self {
+        self.liveness = rules;
+        self
+    }
+
+    /// Drop item-level findings below `min` confidence.
+    pub fn min_confidence(mut self, min: Confidence) -> Self {
+        self.min_confidence = min;
+        self
+    }
+
+    /// Analyze reachability from these modules only, instead of the crate's
+    /// entry points (`main`, `lib`, binaries). Function entry points are
+    /// limited to the files of these modules.
+    pub fn with_roots(mut self, roots: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.roots = roots.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Fold item findings inside dead modules under their module
+    /// (see [`crate::aggregate`]) instead of listing them separately.
+    pub fn collapse(mut self, enabled: bool) -> Self {
+        self.collapse = enabled;
+        self
+    }
+
+    /// Report findings on lines added in the last `days` days (by `git
+    /// blame`) as [`AnalysisResult::recent`] instead of as dead code (see
+    /// [`crate::grace`]).
+    pub fn with_grace_period(mut self, days: u32) -> Self {
+        self.grace_period = Some(days);
+        self
+    }
+
+    /// Skip files and analysis steps beyond `limits`, listing them in
+    /// [`AnalysisResult::skipped`]. File size and count limits apply to
+    /// filesystem scans; the timeout applies to every source.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Reference kinds that create module graph edges, overriding the
+    /// crate's `edges` setting (see [`EdgeKinds`]).
+    pub fn with_edge_kinds(mut self, kinds: EdgeKinds) -> Self {
+        self.edge_kinds = Some(kinds);
+        self
+    }
+
+    /// Edition to resolve paths with, overriding the crate's `Cargo.toml`
+    /// (see [`Edition`]). In-memory sources are 2021 unless set.
+    pub fn with_edition(mut self, edition: Edition) -> Self {
+        self.edition = Some(edition);
+        self
+    }
+
+    /// Report progress to `progress` (see [`crate::progress`]). Workspace
+    /// analyses report each member as a crate.
+    pub fn with_progress(mut self, progress: Progress) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Content of `path`, from the [`SourceProvider`] if one is set.
+    pub(crate) fn read_file(&self, path: &Path) -> Result<String> {
+        match &self.source {
+            Some(source) => source.read(path),
+            None => std::fs::read_to_string(path).map_err(|e| DeadmodError::io(path, e).into()),
+        }
+    }
+
     /// Run the analysis and return results.
     pub fn analyze(&self) -> Result<AnalysisResult> {
-        // 1. Gather files
-        let files = gather_rs_files(&self.root)
-            .context("Failed to gather .rs files")?;
+        run_with_threads(resolve_jobs(self.threads), || self.run_analysis(None))?
+    }
 
-        // 2. Load cache if enabled
-        let cached = if self.use_cache {
-            cache::load_cache(&self.root)
-        } else {
-            None
+    /// Run the analysis, re-running only the item-level detectors whose
+    /// inputs changed since the last call with `state` (see
+    /// [`crate::dirty`]). The first call runs them all.
+    ///
+    /// Macro usages in other workspace members are read whenever macro
+    /// analysis runs, but changes there alone do not re-run it.
+    pub fn analyze_incremental(&self, state: &mut IncrementalState) -> Result<AnalysisResult> {
+        run_with_threads(resolve_jobs(self.threads), || {
+            self.run_analysis(Some(state))
+        })?
+    }
+
+    /// Analyze every crate of the workspace at `root` with default settings.
+    ///
+    /// Shorthand for `Deadmod::new(root).analyze_workspace()`.
+    pub fn workspace(root: impl Into<PathBuf>) -> Result<WorkspaceAnalysisResult> {
+        Self::new(root).analyze_workspace()
+    }
+
+    /// Analyze every member of the workspace at the builder's root, the way
+    /// `deadmod <workspace>` does.
+    ///
+    /// Each member gets its own [`AnalysisResult`] under these settings.
+    /// The members are then combined into one module graph keyed
+    /// `crate::module`, where `use other_crate::...` links to that crate's
+    /// `lib` module, and reachability over that graph gives the workspace's
+    /// dead modules. With the `cargo-metadata` feature, members, their
+    /// targets and the dependencies between them come from `cargo metadata`,
+    /// so each member's entry points are the targets Cargo builds; without
+    /// it, or without cargo, directories are scanned instead. Custom
+    /// roots apply as in [`roots_for_crate`]; a member no custom root applies
+    /// to has no entry points in the combined graph, so it is only alive
+    /// through other crates. A root that is not a workspace is analyzed as a
+    /// workspace of one crate, and a root without a `Cargo.toml` but with a
+    /// `rust-project.json` as that project (see [`Deadmod::analyze_project`]).
+    /// Members are always scanned from disk, ignoring [`Deadmod::with_source`].
+    pub fn analyze_workspace(&self) -> Result<WorkspaceAnalysisResult> {
+        let root = self
+            .root
+            .canonicalize()
+            .with_context(|| format!("Failed to canonicalize path: {}", self.root.display()))?;
+        if !root.join("Cargo.toml").exists() {
+            if let Some(path) = ProjectModel::find(&root) {
+                return self.analyze_project(&ProjectModel::load(&path)?);
+            }
+        }
+        if !is_workspace_root(&root) {
+            let member = WorkspaceMember {
+                name: member_name(&root),
+                dir: root.clone(),
+                roots: None,
+                edition: None,
+            };
+            return self.analyze_members(root, vec![member], &HashMap::new());
+        }
+
+        #[cfg(feature = "cargo-metadata")]
+        if let Ok(meta) = CargoMetadata::load(&root) {
+            let members = meta
+                .members()
+                .into_iter()
+                .map(|package| WorkspaceMember {
+                    name: member_name(&package.dir()),
+                    dir: package.dir(),
+                    roots: Some(package.root_modules()),
+                    edition: package.edition,
+                })
+                .collect();
+            return self.analyze_members(root, members, &meta.member_externs());
+        }
+
+        let members = find_all_crates(&root)?
+            .into_iter()
+            .map(|dir| WorkspaceMember {
+                name: member_name(&dir),
+                dir,
+                roots: None,
+                edition: None,
+            })
+            .collect();
+        self.analyze_members(root, members, &HashMap::new())
+    }
+
+    /// Analyze the member crates of a `rust-project.json`, for build systems
+    /// other than Cargo.
+    ///
+    /// Works like [`Deadmod::analyze_workspace`], except that each crate's
+    /// directory and root module come from the project description: the
+    /// root module is the crate's only entry point unless custom roots
+    /// apply. The result's root is the directory holding the file.
+    pub fn analyze_project(&self, model: &ProjectModel) -> Result<WorkspaceAnalysisResult> {
+        let root = model
+            .path
+            .canonicalize()
+            .with_context(|| format!("Failed to canonicalize path: {}", model.path.display()))?
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let members = model
+            .members()
+            .map(|krate| WorkspaceMember {
+                name: krate.name.clone(),
+                dir: krate.dir.clone(),
+                roots: Some(HashSet::from([krate.root_name()])),
+                edition: Some(krate.edition),
+            })
+            .collect();
+        self.analyze_members(root, members, &HashMap::new())
+    }
+
+    fn analyze_members(
+        &self,
+        root: PathBuf,
+        members: Vec<WorkspaceMember>,
+        externs: &HashMap<String, HashMap<String, String>>,
+    ) -> Result<WorkspaceAnalysisResult> {
+        let mut crates = Vec::with_capacity(members.len());
+        let mut crate_mods = Vec::with_capacity(members.len());
+        let mut entry_points: Vec<String> = Vec::new();
+        for WorkspaceMember {
+            name,
+            dir: member,
+            roots,
+            edition,
+        } in members
+        {
+            let custom = match roots {
+                Some(roots) if self.roots.is_empty() => roots,
+                _ => roots_for_crate(&self.roots, &name),
+            };
+            let mut builder = self.clone();
+            builder.edition = edition.or(self.edition);
+            builder.root = member.clone();
+            builder.source = None;
+            builder.roots = custom.iter().cloned().collect();
+            builder.roots.sort();
+
+            self.progress.crate_start(&name);
+            let result = builder.analyze();
+            self.progress.crate_done(&name);
+            let result = match result {
+                Ok(result) => result,
+                Err(e) => {
+                    eprintln!("[WARN] crate {} failed: {:#}", member.display(), e);
+                    continue;
+                }
+            };
+            let roots = if builder.roots.is_empty() && self.roots.is_empty() {
+                result.evidence.root_modules.clone()
+            } else {
+                builder.roots
+            };
+            entry_points.extend(roots.iter().map(|r| format!("{}::{}", name, r)));
+            crate_mods.push((name.clone(), result.modules.clone()));
+            let edition = builder
+                .edition
+                .unwrap_or_else(|| Edition::for_crate(&member));
+            crates.push(CrateResult {
+                name,
+                edition,
+                result,
+            });
+        }
+
+        let modules = combine_crate_modules_with_externs(crate_mods, externs);
+        let graph = build_graph(&modules);
+        let reachable = reachable_from_roots(
+            &graph,
+            entry_points
+                .iter()
+                .filter(|name| modules.contains_key(*name))
+                .map(String::as_str),
+        );
+        let mut dead_modules: Vec<String> = dead_names(&find_dead(&modules, &reachable))
+            .into_iter()
+            .filter(|key| {
+                let local = key.split_once("::").map_or(*key, |(_, m)| m);
+                self.toggles.modules && !self.is_ignored(key) && !self.is_ignored(local)
+            })
+            .map(String::from)
+            .collect();
+        dead_modules.sort();
+
+        let mut cross_crate_edges: Vec<CrossCrateEdge> = modules
+            .iter()
+            .flat_map(|(from, info)| {
+                info.refs
+                    .iter()
+                    .filter(|to| {
+                        modules
+                            .get(*to)
+                            .is_some_and(|dst| module_crate(info) != module_crate(dst))
+                    })
+                    .map(move |to| CrossCrateEdge {
+                        from: from.clone(),
+                        to: to.clone(),
+                    })
+            })
+            .collect();
+        cross_crate_edges.sort_by(|a, b| (&a.from, &a.to).cmp(&(&b.from, &b.to)));
+
+        Ok(WorkspaceAnalysisResult {
+            root,
+            total_modules: modules.len(),
+            crates,
+            dead_modules,
+            cross_crate_edges,
+        })
+    }
+
+    fn run_analysis(&self, state: Option<&mut IncrementalState>) -> Result<AnalysisResult> {
+        let deadline = self.limits.deadline();
+
+        // 1. Pick the source (filesystem scan unless one was provided)
+        let source: Arc<dyn SourceProvider> = match &self.source {
+            Some(source) => Arc::clone(source),
+            None => Arc::new(
+                FsSource::new(&self.root)
+                    .with_parse_mode(self.parse_mode)
+                    .with_limits(self.limits)
+                    .with_progress(self.progress.clone()),
+            ),
         };
 
-        // 3. Parse modules (incremental if cache available)
-        let modules = cache::incremental_parse(&self.root, &files, cached)
-            .context("Failed to parse modules")?;
+        // 2-3. Gather and parse modules (incremental for filesystem sources)
+        let mut modules = source.parse_modules()?;
+        if let Some(edition) = self.edition {
+            for info in modules.values_mut() {
+                info.edition = edition;
+            }
+            let kinds = self
+                .edge_kinds
+                .clone()
+                .unwrap_or_else(|| EdgeKinds::for_crate(source.root()));
+            select_edges(&mut modules, &kinds);
+        } else if let Some(kinds) = &self.edge_kinds {
+            select_edges(&mut modules, kinds);
+        }
 
-        // 4. Find root modules
-        let root_mods = find_root_modules(&self.root);
+        // 4. Find root modules (unless overridden)
+        let root_mods: HashSet<String> = if self.roots.is_empty() {
+            source.root_modules()
+        } else {
+            for name in self.roots.iter().filter(|r| !modules.contains_key(*r)) {
+                eprintln!("[WARN] Unknown root module: {}", name);
+            }
+            self.roots.iter().cloned().collect()
+        };
 
         // 5. Build graph and find reachable
+        let graph_span = info_span!("graph", modules = modules.len()).entered();
+        self.progress.start(Phase::Graph, None);
         let graph = build_graph(&modules);
         let valid_roots = root_mods
             .iter()
             .filter(|name| modules.contains_key(*name))
             .map(|s| s.as_str());
         let reachable: HashSet<&str> = reachable_from_roots(&graph, valid_roots);
+        self.progress.done(Phase::Graph);
+        debug!(reachable = reachable.len(), "module graph built");
+        drop(graph_span);
 
         // 6. Find dead modules
-        let dead_modules: Vec<String> = find_dead(&modules, &reachable)
+        let dead_modules: Vec<String> = dead_names(&find_dead(&modules, &reachable))
             .into_iter()
-            .filter(|m| !self.is_ignored(m))
+            .filter(|m| self.toggles.modules && !self.is_ignored(m))
             .map(String::from)
             .collect();
+        let collisions = find_collisions(&modules);
+        let mut root_modules: Vec<String> = root_mods
+            .iter()
+            .filter(|name| modules.contains_key(*name))
+            .cloned()
+            .collect();
+        root_modules.sort();
 
         // 7. Build result
-        let result = AnalysisResult {
-            root: self.root.clone(),
+        let mut result = AnalysisResult {
+            root: source.root().to_path_buf(),
             total_modules: modules.len(),
             reachable_modules: reachable.iter().map(|s| s.to_string()).collect(),
             dead_modules,
+            collisions,
+            skipped: source.skipped(),
             dead_functions: Vec::new(),
             dead_traits: Vec::new(),
             dead_constants: Vec::new(),
@@ -241,23 +757,404 @@ impl Deadmod {
             dead_macros: Vec::new(),
             dead_generics: Vec::new(),
             dead_matcharms: Vec::new(),
+            dead_impls: Vec::new(),
+            kept: Vec::new(),
+            recent: Vec::new(),
+            collapsed: Vec::new(),
             modules,
+            evidence: AnalysisEvidence {
+                root_modules,
+                ..AnalysisEvidence::default()
+            },
         };
 
-        // Note: Additional detection modes (functions, traits, etc.) can be
-        // enabled in future versions. The flags are stored in the builder
-        // for forward compatibility.
-        let _ = &self.include_functions;
-        let _ = &self.include_traits;
-        let _ = &self.include_constants;
-        let _ = &self.include_enums;
-        let _ = &self.include_macros;
-        let _ = &self.include_generics;
-        let _ = &self.include_matcharms;
+        // 8. Item-level detectors over the same sources
+        self.detect_items(source.as_ref(), &mut result, &deadline, state);
+
+        // 9. Findings recorded as false positives (sources without a root
+        // directory have no suppressions file; never read the cwd's)
+        if !result.root.as_os_str().is_empty() {
+            match Suppressions::load(&result.root) {
+                Ok(suppressions) if !suppressions.is_empty() => {
+                    result.apply_suppressions(&suppressions.ids())
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("[WARN] {:#}", e),
+            }
+        }
+
+        // 10. Findings still in their grace period are information, not failures
+        if let Some(days) = self.grace_period {
+            result.apply_grace_period(&GracePeriod::new(days));
+        }
+
+        // 11. Fold findings under their dead module
+        if self.collapse {
+            collapse_dead_modules(&mut result);
+        }
 
         Ok(result)
     }
 
+    /// Run every enabled item-level detector and record its findings.
+    ///
+    /// With `state`, detectors whose inputs did not change since the last
+    /// run reuse its findings (see [`crate::dirty`]).
+    fn detect_items(
+        &self,
+        source: &dyn SourceProvider,
+        result: &mut AnalysisResult,
+        deadline: &Deadline,
+        mut state: Option<&mut IncrementalState>,
+    ) {
+        let enabled = self.enabled_detectors();
+        if enabled.is_empty() {
+            return;
+        }
+
+        let mut files: Vec<(PathBuf, String)> = Vec::with_capacity(result.modules.len());
+        for info in result.modules.values() {
+            match source.read(&info.path) {
+                Ok(content) => files.push((info.path.clone(), content)),
+                Err(e) => eprintln!("[WARN] Skipping {}: {:#}", info.path.display(), e),
+            }
+        }
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        result.evidence.searched = files.iter().map(|(path, _)| path.clone()).collect();
+
+        let dirty = match state.as_deref_mut() {
+            Some(state) => {
+                let generated: BTreeSet<PathBuf> = result
+                    .modules
+                    .values()
+                    .filter(|info| info.generated)
+                    .map(|info| info.path.clone())
+                    .collect();
+                let mut dirty = state.tracker.update(&files, &enabled, &self.liveness);
+                if generated != state.generated {
+                    state.generated = generated;
+                    dirty.extend(&enabled);
+                }
+                state.last_run.clear();
+                dirty
+            }
+            None => enabled.clone(),
+        };
+
+        let inputs = DetectorInputs {
+            mentions: files
+                .iter()
+                .flat_map(|(_, content)| extract_macro_mentions(content))
+                .collect(),
+            registered: if self.include_functions || self.include_constants {
+                files
+                    .iter()
+                    .flat_map(|(_, content)| self.liveness.registered_names(content))
+                    .collect()
+            } else {
+                HashSet::new()
+            },
+            attr: Attribution::new(&result.root),
+            files: &files,
+            modules: &result.modules,
+        };
+
+        let mut runs = Vec::with_capacity(enabled.len());
+        for detector in enabled {
+            let reused = match state.as_deref() {
+                Some(state) if !dirty.contains(&detector) => state.runs.get(&detector).cloned(),
+                _ => None,
+            };
+            let run = match reused {
+                Some(run) => {
+                    debug!(
+                        detector = step_name(detector),
+                        "inputs unchanged, reusing findings"
+                    );
+                    run
+                }
+                None => {
+                    if !deadline.check(step_name(detector), &mut result.skipped) {
+                        if let Some(state) = state.as_deref_mut() {
+                            state.runs.remove(&detector);
+                        }
+                        continue;
+                    }
+                    let phase = Phase::Detect(step_name(detector));
+                    let run =
+                        info_span!("extract", detector = step_name(detector)).in_scope(|| {
+                            let run = self
+                                .progress
+                                .phase(phase, None, || self.run_detector(detector, &inputs));
+                            debug!(findings = run.dead.len(), "detector finished");
+                            run
+                        });
+                    if let Some(state) = state.as_deref_mut() {
+                        state.runs.insert(detector, run.clone());
+                        state.last_run.insert(detector);
+                    }
+                    run
+                }
+            };
+            runs.push((detector, run));
+        }
+
+        for (detector, mut run) in runs {
+            result.kept.append(&mut run.kept);
+            result.dead_impls.append(&mut run.dead_impls);
+            if detector == Detector::Functions {
+                result.evidence.entry_functions = run.entry_functions;
+            }
+            let findings = match detector {
+                Detector::Functions => &mut result.dead_functions,
+                Detector::Traits => &mut result.dead_traits,
+                Detector::Constants => &mut result.dead_constants,
+                Detector::Enums => &mut result.dead_enums,
+                Detector::Macros => &mut result.dead_macros,
+                Detector::Generics => &mut result.dead_generics,
+                Detector::MatchArms => &mut result.dead_matcharms,
+            };
+            *findings = run.dead;
+        }
+
+        // Function and trait analysis both see inherent impls
+        result
+            .dead_impls
+            .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        result
+            .dead_impls
+            .dedup_by(|a, b| a.file == b.file && a.line == b.line);
+
+        // Generated files keep other code alive but get no findings themselves
+        let generated: HashSet<&Path> = result
+            .modules
+            .values()
+            .filter(|info| info.generated)
+            .map(|info| info.path.as_path())
+            .collect();
+        if !generated.is_empty() {
+            for items in [
+                &mut result.dead_functions,
+                &mut result.dead_traits,
+                &mut result.dead_constants,
+                &mut result.dead_enums,
+                &mut result.dead_macros,
+                &mut result.dead_generics,
+                &mut result.dead_matcharms,
+                &mut result.dead_impls,
+            ] {
+                items.retain(|item| !generated.contains(item.file.as_path()));
+            }
+        }
+
+        // `// deadmod:ignore <id>` suppressions anywhere in the crate
+        let ignored: HashSet<String> = files
+            .iter()
+            .flat_map(|(_, content)| ignored_ids(content))
+            .collect();
+        if !ignored.is_empty() {
+            for items in [
+                &mut result.dead_functions,
+                &mut result.dead_traits,
+                &mut result.dead_constants,
+                &mut result.dead_enums,
+                &mut result.dead_macros,
+                &mut result.dead_generics,
+                &mut result.dead_matcharms,
+                &mut result.dead_impls,
+            ] {
+                items.retain(|item| !ignored.contains(&item.id));
+            }
+        }
+    }
+
+    /// Item-level detectors this builder runs, in run order.
+    fn enabled_detectors(&self) -> BTreeSet<Detector> {
+        Detector::ALL
+            .into_iter()
+            .filter(|detector| self.toggles.enables(*detector))
+            .filter(|detector| match detector {
+                Detector::Functions => self.include_functions || self.include_test_helpers,
+                Detector::Traits => self.include_traits,
+                Detector::Constants => self.include_constants,
+                Detector::Enums => self.include_enums,
+                Detector::Macros => self.include_macros,
+                Detector::Generics => self.include_generics,
+                Detector::MatchArms => self.include_matcharms,
+            })
+            .collect()
+    }
+
+    /// Run one item-level detector over every file.
+    fn run_detector(&self, detector: Detector, inputs: &DetectorInputs) -> DetectorRun {
+        let files = inputs.files;
+        let attr = &inputs.attr;
+        let min = self.min_confidence;
+        let mut run = DetectorRun::default();
+
+        match detector {
+            Detector::Functions => {
+                let mut funcs = Vec::new();
+                let mut file_calls = HashMap::new();
+                for (path, content) in files {
+                    funcs.extend(extract_functions(path, content));
+                    file_calls.insert(
+                        path.display().to_string(),
+                        extract_call_names(path, content),
+                    );
+                }
+                let mut graph = FuncGraph::build(&funcs, &file_calls)
+                    .with_macro_mentions(inputs.mentions.clone())
+                    .with_hidden_policy(self.hidden_policy)
+                    .with_pub_entry_points(self.pub_policy.pub_is_reachable(attr.root))
+                    .with_liveness(self.liveness.clone())
+                    .with_registered(inputs.registered.clone())
+                    .with_test_helpers(self.include_test_helpers);
+                if !self.roots.is_empty() {
+                    let root_files = self
+                        .roots
+                        .iter()
+                        .filter_map(|name| inputs.modules.get(name))
+                        .map(|info| info.path.display().to_string())
+                        .collect();
+                    graph = graph.with_entry_files(root_files);
+                }
+                let mut analysis = graph.analyze();
+                if !self.include_functions {
+                    analysis.dead.retain(|f| f.test_only);
+                    analysis.dead_impls.clear();
+                }
+                analysis.retain_confidence(min);
+                run.kept = analysis.kept;
+                run.entry_functions = analysis.entry_points;
+                run.dead = analysis.dead.iter().map(|f| attr.function(f)).collect();
+                run.dead_impls = analysis
+                    .dead_impls
+                    .iter()
+                    .map(|i| attr.dead_impl(i))
+                    .collect();
+            }
+            Detector::Traits => {
+                let mut extractions = Vec::new();
+                let mut usages = Vec::new();
+                for (path, content) in files {
+                    extractions.push(extract_traits(path, content));
+                    usages.push(extract_trait_usages(path, content));
+                }
+                let mut analysis = TraitGraph::build(&extractions, &usages)
+                    .with_macro_mentions(inputs.mentions.clone())
+                    .analyze();
+                analysis.retain_confidence(min);
+                run.kept = analysis.kept;
+                let trait_methods = analysis
+                    .dead_trait_methods
+                    .iter()
+                    .map(|m| attr.trait_method(m));
+                let impl_methods = analysis
+                    .dead_impl_methods
+                    .iter()
+                    .map(|m| attr.impl_method(m));
+                run.dead = trait_methods.chain(impl_methods).collect();
+                run.dead_impls = analysis
+                    .dead_impls
+                    .iter()
+                    .map(|i| attr.dead_impl(i))
+                    .collect();
+            }
+            Detector::Constants => {
+                let mut declared = Vec::new();
+                let mut usages = Vec::new();
+                for (path, content) in files {
+                    declared.extend(extract_constants(path, content));
+                    usages.push(extract_const_usage(path, content));
+                }
+                let mut analysis = ConstGraph::new(declared, &usages)
+                    .with_macro_mentions(inputs.mentions.clone())
+                    .with_liveness(self.liveness.clone())
+                    .with_registered(inputs.registered.clone())
+                    .analyze();
+                analysis.retain_confidence(min);
+                run.kept = analysis.kept;
+                run.dead = analysis.dead.iter().map(|c| attr.constant(c)).collect();
+            }
+            Detector::Enums => {
+                let mut declared = Vec::new();
+                let mut usages = Vec::new();
+                for (path, content) in files {
+                    declared.extend(extract_variants(path, content));
+                    usages.push(extract_variant_usage(path, content));
+                }
+                let mut analysis = EnumGraph::new(declared, &usages)
+                    .with_macro_mentions(inputs.mentions.clone())
+                    .analyze();
+                analysis.retain_confidence(min);
+                run.kept = analysis.kept;
+                run.dead = analysis.dead.iter().map(|v| attr.variant(v)).collect();
+            }
+            Detector::Macros => {
+                let mut declared = Vec::new();
+                let mut usages = Vec::new();
+                for (path, content) in files {
+                    declared.extend(extract_macros(path, content));
+                    usages.push(extract_macro_usages(path, content));
+                }
+                // Exported macros are used from other crates (`#[macro_use] extern
+                // crate` in 2015 code): scan the rest of the workspace
+                let mut members = Vec::new();
+                if self.source.is_none()
+                    && self.exported_macro_policy == ExportedMacroPolicy::WorkspaceCheck
+                    && declared.iter().any(|m| m.exported)
+                {
+                    members = workspace_macro_usages(&self.root);
+                }
+                let mut analysis = MacroGraph::new(declared, &usages)
+                    .with_macro_mentions(inputs.mentions.clone())
+                    .with_exported_policy(self.exported_macro_policy)
+                    .with_workspace_usages(&members)
+                    .analyze();
+                analysis.retain_confidence(min);
+                run.kept = analysis.kept;
+                run.dead = analysis.dead.iter().map(|m| attr.macro_def(m)).collect();
+            }
+            Detector::Generics => {
+                let mut extractions = Vec::new();
+                let mut usages = Vec::new();
+                for (path, content) in files {
+                    extractions.push(extract_declared_generics(path, content));
+                    usages.push(extract_generic_usages(path, content));
+                }
+                let mut analysis = GenericGraph::new(&extractions, &usages).analyze();
+                analysis.retain_confidence(min);
+                run.kept = analysis.kept;
+                run.dead = analysis.dead.iter().map(|g| attr.generic(g)).collect();
+            }
+            Detector::MatchArms => {
+                let mut arms = Vec::new();
+                let mut match_count = 0;
+                let mut usages = Vec::new();
+                let mut declared = Vec::new();
+                for (path, content) in files {
+                    let extraction = extract_match_arms(path, content);
+                    arms.extend(extraction.arms);
+                    match_count += extraction.match_count;
+                    usages.push(extract_match_usages(path, content));
+                    declared.extend(extract_variants(path, content));
+                }
+                let mut analysis = MatchGraph::new(arms, match_count, &usages)
+                    .correlate_with_enums(&declared)
+                    .analyze();
+                analysis.retain_confidence(min);
+                run.kept = analysis.kept;
+                run.dead = analysis
+                    .dead_arms
+                    .iter()
+                    .map(|a| attr.match_arm(a))
+                    .collect();
+            }
+        }
+        run
+    }
+
     /// Check if a module name matches any ignored pattern.
     fn is_ignored(&self, name: &str) -> bool {
         for pattern in &self.ignored_patterns {
@@ -281,12 +1178,249 @@ impl Deadmod {
     #[cfg(feature = "fix")]
     pub fn fix(&self, result: &AnalysisResult) -> Result<crate::fix::FixResult> {
         let dead_refs: Vec<&str> = result.dead_modules.iter().map(|s| s.as_str()).collect();
-        crate::fix::fix_dead_modules(&self.root, &dead_refs, &result.modules, self.dry_run)
+        let options = crate::fix::FixOptions::new()
+            .dry_run(self.dry_run)
+            .force(self.force);
+        crate::fix::fix_dead_modules_with(&self.root, &dead_refs, &result.modules, &options)
+    }
+}
+
+/// Turns detector findings into [`DeadItem`]s: owning module path and
+/// stable ID. Shared by [`Deadmod`] and single-detector reports so both
+/// assign the same IDs.
+pub struct Attribution<'a> {
+    root: &'a Path,
+}
+
+impl<'a> Attribution<'a> {
+    /// Attribute findings of the crate at `root`.
+    pub fn new(root: &'a Path) -> Self {
+        Self { root }
+    }
+
+    /// A dead function or method.
+    pub fn function(&self, f: &FunctionInfo) -> DeadItem {
+        let mut tail = vec![f.name.as_str()];
+        if let Some(ty) = &f.impl_type {
+            tail.insert(0, ty);
+        }
+        let kind = if f.is_method {
+            DeadItemKind::Method
+        } else {
+            DeadItemKind::Function
+        };
+        let inline = inline_module(&f.full_path, &tail);
+        self.item(kind, &f.full_path, &f.file, f.line, &inline, f.confidence)
+    }
+
+    /// A dead trait method declaration.
+    pub fn trait_method(&self, m: &TraitMethodDef) -> DeadItem {
+        let inline = inline_module(&m.full_path, &[&m.trait_name, &m.method_name]);
+        let name = format!("{}::{}", m.trait_name, m.method_name);
+        self.item(
+            DeadItemKind::TraitMethod,
+            &name,
+            &m.file,
+            m.line,
+            &inline,
+            m.confidence,
+        )
+    }
+
+    /// A dead trait impl method.
+    pub fn impl_method(&self, m: &TraitImplMethod) -> DeadItem {
+        let name = format!("<{} as {}>::{}", m.type_name, m.trait_name, m.method_name);
+        self.item(
+            DeadItemKind::Method,
+            &name,
+            &m.file,
+            m.line,
+            "",
+            m.confidence,
+        )
+    }
+
+    /// A dead `const` or `static`.
+    pub fn constant(&self, c: &DeadConst) -> DeadItem {
+        let kind = if c.is_static {
+            DeadItemKind::Static
+        } else {
+            DeadItemKind::Constant
+        };
+        self.item(kind, &c.name, &c.file, c.line, &c.module_path, c.confidence)
+    }
+
+    /// A dead enum variant.
+    pub fn variant(&self, v: &DeadVariant) -> DeadItem {
+        self.item(
+            DeadItemKind::EnumVariant,
+            &v.full_name,
+            &v.file,
+            v.line,
+            &v.module_path,
+            v.confidence,
+        )
+    }
+
+    /// A dead macro.
+    pub fn macro_def(&self, m: &DeadMacro) -> DeadItem {
+        self.item(
+            DeadItemKind::Macro,
+            &m.name,
+            &m.file,
+            m.line,
+            &m.module_path,
+            m.confidence,
+        )
+    }
+
+    /// A dead generic parameter.
+    pub fn generic(&self, g: &DeadGeneric) -> DeadItem {
+        let kind = match g.kind {
+            GenericKind::Type => DeadItemKind::TypeParam,
+            GenericKind::Lifetime => DeadItemKind::Lifetime,
+            GenericKind::Const => DeadItemKind::ConstParam,
+        };
+        let name = format!("{} in {}", g.name, g.parent);
+        self.item(kind, &name, &g.file, g.line, "", g.confidence)
+    }
+
+    /// A dead match arm.
+    pub fn match_arm(&self, a: &DeadMatchArm) -> DeadItem {
+        self.item(
+            DeadItemKind::MatchArm,
+            &a.pattern,
+            &a.file,
+            a.line,
+            "",
+            a.confidence,
+        )
+    }
+
+    /// An unused function parameter.
+    pub fn param(&self, p: &DeadParam) -> DeadItem {
+        let name = format!("{} in {}", p.name, p.function);
+        self.item(
+            DeadItemKind::Parameter,
+            &name,
+            &p.file,
+            p.line,
+            "",
+            p.confidence,
+        )
+    }
+
+    /// A write-only or unused struct field.
+    pub fn field(&self, f: &DeadField) -> DeadItem {
+        self.item(
+            DeadItemKind::Field,
+            &f.full_name(),
+            &f.file,
+            f.line,
+            "",
+            f.confidence,
+        )
     }
+
+    /// An `impl` block whose methods are all dead.
+    pub fn dead_impl(&self, i: &DeadImpl) -> DeadItem {
+        self.item(
+            DeadItemKind::Impl,
+            &i.span.label,
+            &i.file,
+            i.span.start_line,
+            "",
+            i.confidence,
+        )
+    }
+
+    fn item(
+        &self,
+        kind: DeadItemKind,
+        name: &str,
+        file: &str,
+        line: usize,
+        inline: &str,
+        confidence: Confidence,
+    ) -> DeadItem {
+        let file = PathBuf::from(file);
+        let module_path = join_module_path(&module_path_of(self.root, &file), inline);
+        DeadItem {
+            id: finding_id(&kind.to_string(), &module_path, name),
+            name: name.to_string(),
+            module_path,
+            file,
+            line,
+            kind,
+            confidence,
+        }
+    }
+}
+
+/// Inline `mod` path of an item: `full_path` minus its trailing `tail` segments.
+fn inline_module(full_path: &str, tail: &[&str]) -> String {
+    full_path
+        .strip_suffix(tail.join("::").as_str())
+        .map(|prefix| prefix.trim_end_matches("::").to_string())
+        .unwrap_or_default()
 }
 
+/// Inputs shared by the item-level detectors.
+struct DetectorInputs<'a> {
+    /// Names mentioned inside macro invocations
+    mentions: HashSet<String>,
+    /// Names passed to registry macros
+    registered: HashSet<String>,
+    attr: Attribution<'a>,
+    files: &'a [(PathBuf, String)],
+    modules: &'a HashMap<String, ModuleInfo>,
+}
+
+/// Step name a detector is listed under in [`AnalysisResult::skipped`].
+fn step_name(detector: Detector) -> &'static str {
+    match detector {
+        Detector::Functions => "function analysis",
+        Detector::Traits => "trait analysis",
+        Detector::Constants => "constant analysis",
+        Detector::Enums => "enum analysis",
+        Detector::Macros => "macro analysis",
+        Detector::Generics => "generic analysis",
+        Detector::MatchArms => "match arm analysis",
+    }
+}
+
+/// Macro usages from every other member of the workspace containing `root`.
+fn workspace_macro_usages(root: &Path) -> Vec<crate::macros::MacroUsageResult> {
+    let mut usages = Vec::new();
+    let Some(ws_root) = find_workspace_root(root) else {
+        return usages;
+    };
+    let own = root.canonicalize().ok();
+    for member in find_all_crates(&ws_root).unwrap_or_default() {
+        if member.canonicalize().ok() == own {
+            continue;
+        }
+        for file in crate::scan::gather_rs_files(&member).unwrap_or_default() {
+            if let Ok(content) = std::fs::read_to_string(&file) {
+                usages.push(extract_macro_usages(&file, &content));
+            }
+        }
+    }
+    usages
+}
+
+/// Whether a path names `file`, comparing canonical paths when they exist.
+pub(crate) fn file_matcher(file: &Path) -> impl Fn(&Path) -> bool {
+    let canonical = |p: &Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+    let target = canonical(file);
+    move |p| canonical(p) == target
+}
+
+/// Version of the [`AnalysisResult::save`] file format.
+pub const ANALYSIS_FORMAT_VERSION: u32 = 1;
+
 /// Result of running dead code analysis.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisResult {
     /// Root path that was analyzed
     pub root: PathBuf,
@@ -300,6 +1434,13 @@ pub struct AnalysisResult {
     /// Dead (unreachable) modules
     pub dead_modules: Vec<String>,
 
+    /// Files sharing a module path; they get no dead/alive verdict
+    pub collisions: Vec<ModuleCollision>,
+
+    /// Files and analysis steps left out by [`Limits`]
+    #[serde(default)]
+    pub skipped: Vec<Skipped>,
+
     /// Dead functions (if function analysis enabled)
     pub dead_functions: Vec<DeadItem>,
 
@@ -321,8 +1462,29 @@ pub struct AnalysisResult {
     /// Dead match arms (if matcharm analysis enabled)
     pub dead_matcharms: Vec<DeadItem>,
 
+    /// `impl` blocks whose methods are all dead (function or trait analysis).
+    /// Their methods are also listed individually, so these are not counted
+    /// by [`AnalysisResult::dead_count`].
+    #[serde(default)]
+    pub dead_impls: Vec<DeadItem>,
+
+    /// Items kept by `#[deadmod::keep]`, across the enabled detectors
+    pub kept: Vec<KeptItem>,
+
+    /// Findings on recently added lines, within the grace period; they are
+    /// not counted as dead code
+    #[serde(default)]
+    pub recent: Vec<RecentItem>,
+
+    /// Dead modules with the item findings folded into them (when collapsing)
+    pub collapsed: Vec<CollapsedModule>,
+
     /// Parsed module information (for fix operations)
     pub modules: HashMap<String, ModuleInfo>,
+
+    /// Entry points and searched files, for explaining findings
+    #[serde(default)]
+    pub evidence: AnalysisEvidence,
 }
 
 impl AnalysisResult {
@@ -358,23 +1520,309 @@ impl AnalysisResult {
             (self.dead_modules.len() as f64 / self.total_modules as f64) * 100.0
         }
     }
+
+    /// All item-level findings, across every detector.
+    pub fn dead_items(&self) -> impl Iterator<Item = &DeadItem> {
+        self.dead_functions
+            .iter()
+            .chain(&self.dead_traits)
+            .chain(&self.dead_constants)
+            .chain(&self.dead_enums)
+            .chain(&self.dead_macros)
+            .chain(&self.dead_generics)
+            .chain(&self.dead_matcharms)
+    }
+
+    /// Drop findings, dead modules included, whose ID is in `ids`.
+    pub fn apply_suppressions(&mut self, ids: &HashSet<String>) {
+        self.dead_modules
+            .retain(|name| !ids.contains(&module_id(name)));
+        for items in [
+            &mut self.dead_functions,
+            &mut self.dead_traits,
+            &mut self.dead_constants,
+            &mut self.dead_enums,
+            &mut self.dead_macros,
+            &mut self.dead_generics,
+            &mut self.dead_matcharms,
+            &mut self.dead_impls,
+        ] {
+            items.retain(|item| !ids.contains(&item.id));
+        }
+    }
+
+    /// Move findings added within `grace` into [`AnalysisResult::recent`].
+    /// Dead `impl` blocks on recent lines are dropped.
+    pub fn apply_grace_period(&mut self, grace: &GracePeriod) {
+        for items in [
+            &mut self.dead_functions,
+            &mut self.dead_traits,
+            &mut self.dead_constants,
+            &mut self.dead_enums,
+            &mut self.dead_macros,
+            &mut self.dead_generics,
+            &mut self.dead_matcharms,
+        ] {
+            self.recent.append(&mut grace.take_recent(items));
+        }
+        grace.take_recent(&mut self.dead_impls);
+    }
+
+    /// Keep only findings located in `file`.
+    ///
+    /// The analysis itself still covers the whole crate, so usages from other
+    /// files count; this only narrows what is reported (per-file diagnostics).
+    pub fn retain_file(&mut self, file: &Path) {
+        let in_file = file_matcher(file);
+
+        let modules = &self.modules;
+        self.dead_modules
+            .retain(|name| modules.get(name).is_some_and(|info| in_file(&info.path)));
+        for items in [
+            &mut self.dead_functions,
+            &mut self.dead_traits,
+            &mut self.dead_constants,
+            &mut self.dead_enums,
+            &mut self.dead_macros,
+            &mut self.dead_generics,
+            &mut self.dead_matcharms,
+            &mut self.dead_impls,
+        ] {
+            items.retain(|item| in_file(&item.file));
+        }
+        self.kept.retain(|item| in_file(Path::new(&item.file)));
+        self.recent.retain(|recent| in_file(&recent.item.file));
+        self.collapsed.retain(|module| in_file(&module.file));
+    }
+
+    /// The dead modules, dead items and kept items, borrowed rather than
+    /// cloned.
+    pub fn findings(&self) -> FindingsRef<'_> {
+        FindingsRef {
+            dead_modules: self.dead_modules.iter().map(String::as_str).collect(),
+            dead: self.dead_items().collect(),
+            kept: self.kept.iter().collect(),
+        }
+    }
+
+    /// [`AnalysisResult::findings`] in `file` only, like
+    /// [`AnalysisResult::retain_file`] without cloning the result first.
+    pub fn findings_in(&self, file: &Path) -> FindingsRef<'_> {
+        let in_file = file_matcher(file);
+        FindingsRef {
+            dead_modules: self
+                .dead_modules
+                .iter()
+                .filter(|name| {
+                    self.modules
+                        .get(*name)
+                        .is_some_and(|info| in_file(&info.path))
+                })
+                .map(String::as_str)
+                .collect(),
+            dead: self
+                .dead_items()
+                .filter(|item| in_file(&item.file))
+                .collect(),
+            kept: self
+                .kept
+                .iter()
+                .filter(|item| in_file(Path::new(&item.file)))
+                .collect(),
+        }
+    }
+
+    /// Rewrite the file paths of findings in `paths`' style, for reporting.
+    /// Call it last: [`AnalysisResult::retain_file`] needs the paths as
+    /// scanned. [`AnalysisResult::modules`] keeps them too (module paths and
+    /// fixes derive from them); graph outputs use
+    /// [`PathFormatter::localize_modules`].
+    pub fn localize_paths(&mut self, paths: &PathFormatter) {
+        if paths.style().is_none() {
+            return;
+        }
+        let localize = |path: &mut PathBuf| *path = PathBuf::from(paths.format(&*path));
+        for items in [
+            &mut self.dead_functions,
+            &mut self.dead_traits,
+            &mut self.dead_constants,
+            &mut self.dead_enums,
+            &mut self.dead_macros,
+            &mut self.dead_generics,
+            &mut self.dead_matcharms,
+            &mut self.dead_impls,
+        ] {
+            items.iter_mut().for_each(|item| localize(&mut item.file));
+        }
+        for module in &mut self.collapsed {
+            localize(&mut module.file);
+            module
+                .items
+                .iter_mut()
+                .for_each(|item| localize(&mut item.file));
+        }
+        for item in &mut self.kept {
+            item.file = paths.format(&item.file);
+        }
+        self.recent
+            .iter_mut()
+            .for_each(|recent| localize(&mut recent.item.file));
+        for collision in &mut self.collisions {
+            collision.files.iter_mut().for_each(localize);
+        }
+        self.evidence.searched.iter_mut().for_each(localize);
+    }
+
+    /// Write the result to `path` as JSON, so reporting, diffing or queries
+    /// can run later without re-analyzing.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let saved = SavedAnalysis {
+            format_version: ANALYSIS_FORMAT_VERSION,
+            deadmod_version: env!("CARGO_PKG_VERSION").to_string(),
+            result: std::borrow::Cow::Borrowed(self),
+        };
+        let json = serde_json::to_string(&saved).context("Failed to serialize analysis")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write analysis to {}", path.display()))
+    }
+
+    /// Read a result written by [`AnalysisResult::save`].
+    ///
+    /// Fails if the file was written in a different format version.
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read analysis from {}", path.display()))?;
+        let saved: SavedAnalysis<'static> = serde_json::from_str(&json)
+            .with_context(|| format!("Invalid analysis file {}", path.display()))?;
+        anyhow::ensure!(
+            saved.format_version == ANALYSIS_FORMAT_VERSION,
+            "analysis file {} has format version {} (expected {}); re-run the analysis",
+            path.display(),
+            saved.format_version,
+            ANALYSIS_FORMAT_VERSION
+        );
+        Ok(saved.result.into_owned())
+    }
+}
+
+/// Findings of an [`AnalysisResult`], borrowed from it
+/// ([`AnalysisResult::findings`], [`AnalysisResult::findings_in`]).
+#[derive(Debug, Clone, Serialize)]
+pub struct FindingsRef<'a> {
+    /// Dead modules
+    pub dead_modules: Vec<&'a str>,
+    /// Dead items of every detector
+    pub dead: Vec<&'a DeadItem>,
+    /// Items kept by `#[deadmod::keep]`
+    pub kept: Vec<&'a KeptItem>,
+}
+
+/// Result of [`Deadmod::analyze_workspace`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceAnalysisResult {
+    /// Workspace root that was analyzed
+    pub root: PathBuf,
+
+    /// Modules across all members
+    pub total_modules: usize,
+
+    /// Per-crate results, in member order
+    pub crates: Vec<CrateResult>,
+
+    /// Modules unreachable in the combined workspace graph (`crate::module`), sorted
+    pub dead_modules: Vec<String>,
+
+    /// Module references from one member to another, sorted
+    pub cross_crate_edges: Vec<CrossCrateEdge>,
+}
+
+impl WorkspaceAnalysisResult {
+    /// Result of the member named `name`.
+    pub fn crate_result(&self, name: &str) -> Option<&AnalysisResult> {
+        self.crates
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| &c.result)
+    }
+
+    /// Check if any dead code was found, in the workspace graph or any member.
+    pub fn has_dead_code(&self) -> bool {
+        !self.dead_modules.is_empty() || self.crates.iter().any(|c| c.result.has_dead_code())
+    }
+
+    /// Dead modules of the workspace graph plus every member's item findings.
+    pub fn dead_count(&self) -> usize {
+        self.dead_modules.len()
+            + self
+                .crates
+                .iter()
+                .map(|c| c.result.dead_items().count())
+                .sum::<usize>()
+    }
+}
+
+/// A crate to analyze as part of a workspace or project.
+struct WorkspaceMember {
+    name: String,
+    dir: PathBuf,
+    /// Entry points given by the project description or `cargo metadata`
+    roots: Option<HashSet<String>>,
+    /// Edition given by the project description
+    edition: Option<Edition>,
+}
+
+/// Analysis of one workspace member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrateResult {
+    /// Member name (its directory name, or `display_name` from
+    /// `rust-project.json`, as in `crate::module` keys)
+    pub name: String,
+    /// Edition paths were resolved with
+    #[serde(default)]
+    pub edition: Edition,
+    /// The member analyzed on its own
+    pub result: AnalysisResult,
+}
+
+/// A module reference crossing crate boundaries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossCrateEdge {
+    /// Referencing module (`crate::module`)
+    pub from: String,
+    /// Referenced module (`crate::module`)
+    pub to: String,
+}
+
+/// On-disk envelope of a saved [`AnalysisResult`].
+#[derive(Serialize, Deserialize)]
+struct SavedAnalysis<'a> {
+    format_version: u32,
+    deadmod_version: String,
+    result: std::borrow::Cow<'a, AnalysisResult>,
 }
 
 /// A dead code item with location information.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeadItem {
+    /// Stable ID (kind, module path and name; see [`finding_id`])
+    pub id: String,
     /// Name or path of the dead item
     pub name: String,
     /// File containing the dead item
     pub file: PathBuf,
-    /// Line number (1-indexed)
+    /// Line number (1-indexed, 0 if unknown)
     pub line: usize,
     /// Item kind (function, method, constant, etc.)
     pub kind: DeadItemKind,
+    /// Confidence that the item is really dead
+    pub confidence: Confidence,
+    /// Owning module path (`crate::net::http`)
+    pub module_path: String,
 }
 
 /// Kind of dead code item.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DeadItemKind {
     Module,
     Function,
@@ -386,7 +1834,11 @@ pub enum DeadItemKind {
     Macro,
     TypeParam,
     Lifetime,
+    ConstParam,
     MatchArm,
+    Impl,
+    Parameter,
+    Field,
 }
 
 impl std::fmt::Display for DeadItemKind {
@@ -402,7 +1854,11 @@ impl std::fmt::Display for DeadItemKind {
             Self::Macro => write!(f, "macro"),
             Self::TypeParam => write!(f, "type parameter"),
             Self::Lifetime => write!(f, "lifetime"),
+            Self::ConstParam => write!(f, "const parameter"),
             Self::MatchArm => write!(f, "match arm"),
+            Self::Impl => write!(f, "impl block"),
+            Self::Parameter => write!(f, "parameter"),
+            Self::Field => write!(f, "field"),
         }
     }
 }
@@ -410,6 +1866,7 @@ impl std::fmt::Display for DeadItemKind {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse::EdgeKind;
     use std::fs;
 
     fn create_test_crate() -> PathBuf {
@@ -479,6 +1936,739 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_builder_from_sources() {
+        let result = Deadmod::from_sources([
+            (
+                PathBuf::from("src/main.rs"),
+                "mod used;\nfn main() {}".to_string(),
+            ),
+            (
+                PathBuf::from("src/used.rs"),
+                "pub fn helper() {}".to_string(),
+            ),
+            (
+                PathBuf::from("src/dead.rs"),
+                "pub fn unused() {}".to_string(),
+            ),
+        ])
+        .analyze()
+        .unwrap();
+
+        assert_eq!(result.total_modules, 3);
+        assert_eq!(result.dead_modules, vec!["dead".to_string()]);
+        assert!(result.root.as_os_str().is_empty());
+    }
+
+    #[test]
+    fn test_builder_detector_toggles() {
+        let sources = || {
+            [
+                (
+                    PathBuf::from("src/main.rs"),
+                    "fn main() {}
+fn unused() {}
+const LIMIT: u8 = 1;"
+                        .to_string(),
+                ),
+                (PathBuf::from("src/dead.rs"), String::new()),
+            ]
+        };
+        let toggles = DetectorToggles {
+            modules: false,
+            functions: false,
+            ..DetectorToggles::default()
+        };
+        let result = Deadmod::from_sources(sources())
+            .all()
+            .with_detector_toggles(toggles)
+            .analyze()
+            .unwrap();
+
+        assert!(result.dead_modules.is_empty());
+        assert!(result.dead_functions.is_empty());
+        assert_eq!(result.dead_constants.len(), 1);
+
+        let result = Deadmod::from_sources(sources()).all().analyze().unwrap();
+        assert_eq!(result.dead_modules, vec!["dead".to_string()]);
+        assert_eq!(result.dead_functions.len(), 1);
+    }
+
+    #[test]
+    fn test_builder_with_threads() {
+        let result = Deadmod::from_sources([
+            (PathBuf::from("src/lib.rs"), "mod a;".to_string()),
+            (PathBuf::from("src/a.rs"), String::new()),
+            (PathBuf::from("src/b.rs"), String::new()),
+        ])
+        .with_threads(2)
+        .analyze()
+        .unwrap();
+
+        assert_eq!(result.dead_modules, vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn test_builder_item_detectors_attribute_modules() {
+        let result = Deadmod::from_sources([
+            (
+                PathBuf::from("src/lib.rs"),
+                "pub mod net;\npub fn api() { net::used(); }".to_string(),
+            ),
+            (
+                PathBuf::from("src/net/mod.rs"),
+                "pub fn used() {}\nfn unused() {}\nmod inner { const LIMIT: u8 = 1; }".to_string(),
+            ),
+        ])
+        .all()
+        .analyze()
+        .unwrap();
+
+        let unused = result
+            .dead_functions
+            .iter()
+            .find(|f| f.name == "unused")
+            .unwrap();
+        assert_eq!(unused.kind, DeadItemKind::Function);
+        assert_eq!(unused.module_path, "crate::net");
+        assert!(!result.dead_functions.iter().any(|f| f.name == "used"));
+
+        let limit = result
+            .dead_constants
+            .iter()
+            .find(|c| c.name == "LIMIT")
+            .unwrap();
+        assert_eq!(limit.module_path, "crate::net::inner");
+
+        let filtered =
+            Deadmod::from_sources([(PathBuf::from("src/lib.rs"), "fn unused() {}".to_string())])
+                .include_functions(true)
+                .min_confidence(Confidence::High)
+                .analyze()
+                .unwrap();
+        assert!(filtered
+            .dead_functions
+            .iter()
+            .all(|f| f.confidence == Confidence::High));
+    }
+
+    #[test]
+    fn test_dead_impl_blocks_reported_once() {
+        let result = Deadmod::from_sources([(
+            PathBuf::from("src/main.rs"),
+            "struct Foo;\n\nimpl Foo {\n    fn a(&self) {}\n}\n\nfn main() {}".to_string(),
+        )])
+        .all()
+        .analyze()
+        .unwrap();
+
+        assert_eq!(result.dead_impls.len(), 1);
+        let block = &result.dead_impls[0];
+        assert_eq!(
+            (block.name.as_str(), block.line, block.kind),
+            ("impl Foo", 3, DeadItemKind::Impl)
+        );
+        assert!(result.dead_functions.iter().any(|f| f.name == "Foo::a"));
+    }
+
+    #[test]
+    fn test_builder_honors_keep_annotation() {
+        let result = Deadmod::from_sources([(
+            PathBuf::from("src/lib.rs"),
+            "#[deadmod::keep(reason = \"loaded by name\")]\nfn hook() {}\n\
+             #[deadmod::keep]\nconst MAGIC: u32 = 7;\nfn unused() {}"
+                .to_string(),
+        )])
+        .all()
+        .analyze()
+        .unwrap();
+
+        let dead: Vec<_> = result.dead_items().map(|i| i.name.as_str()).collect();
+        assert_eq!(dead, vec!["unused"]);
+        let kept: Vec<_> = result
+            .kept
+            .iter()
+            .map(|k| (k.name.as_str(), k.reason.as_deref()))
+            .collect();
+        assert_eq!(
+            kept,
+            vec![("hook", Some("loaded by name")), ("MAGIC", None)]
+        );
+    }
+
+    #[test]
+    fn test_finding_ids_survive_line_moves() {
+        let analyze = |src: &str| {
+            Deadmod::from_sources([(PathBuf::from("src/lib.rs"), src.to_string())])
+                .include_functions(true)
+                .analyze()
+                .unwrap()
+        };
+        let before = analyze("fn unused() {}");
+        let after = analyze("\n\n// moved down\nfn unused() {}");
+        assert_eq!(before.dead_functions[0].id, after.dead_functions[0].id);
+        assert_eq!(
+            before.dead_functions[0].id,
+            finding_id("function", "crate", "unused")
+        );
+    }
+
+    #[test]
+    fn test_deadmod_ignore_suppresses_by_id() {
+        let id = finding_id("function", "crate", "ffi_only");
+        let result = Deadmod::from_sources([(
+            PathBuf::from("src/lib.rs"),
+            format!(
+                "// deadmod:ignore {}\nfn ffi_only() {{}}\nfn unused() {{}}",
+                id
+            ),
+        )])
+        .include_functions(true)
+        .analyze()
+        .unwrap();
+        let dead: Vec<_> = result.dead_items().map(|i| i.name.as_str()).collect();
+        assert_eq!(dead, vec!["unused"]);
+    }
+
+    #[test]
+    fn test_linker_and_registry_items_are_not_dead() {
+        let src = r#"
+#[used]
+static KEEP: u8 = 0;
+#[unsafe(link_section = ".init_array")]
+static INIT: [u8; 4] = [0; 4];
+#[ctor::ctor]
+fn setup() {}
+fn plugin_main() {}
+inventory::submit! { Plugin::new(plugin_main) }
+#[my_registry::register]
+fn custom() {}
+fn unused() {}
+"#;
+        let analyze = |rules: LivenessRules| {
+            let result = Deadmod::from_sources([(PathBuf::from("src/lib.rs"), src.to_string())])
+                .include_functions(true)
+                .include_constants(true)
+                .with_liveness(rules)
+                .analyze()
+                .unwrap();
+            result
+                .dead_items()
+                .map(|i| i.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(analyze(LivenessRules::default()), vec!["custom", "unused"]);
+        let rules = LivenessRules::default().with_extra(LivenessRules {
+            attributes: vec!["my_registry::register".to_string()],
+            registry_macros: Vec::new(),
+        });
+        assert_eq!(analyze(rules), vec!["unused"]);
+    }
+
+    #[test]
+    fn test_included_file_is_not_dead() {
+        let result = Deadmod::from_sources([
+            (
+                PathBuf::from("src/lib.rs"),
+                "include!(\"generated.rs\");".to_string(),
+            ),
+            (
+                PathBuf::from("src/generated.rs"),
+                "pub fn generated() {}".to_string(),
+            ),
+            (PathBuf::from("src/orphan.rs"), String::new()),
+        ])
+        .analyze()
+        .unwrap();
+        assert_eq!(result.dead_modules, vec!["orphan".to_string()]);
+    }
+
+    #[test]
+    fn test_generated_files_are_not_reported() {
+        let result = Deadmod::from_sources([
+            (PathBuf::from("src/lib.rs"), "pub fn api() {}".to_string()),
+            (
+                PathBuf::from("src/proto.rs"),
+                "// @generated by prost-build\nfn decode() { crate::helper(); }\nconst UNUSED: u8 = 0;".to_string(),
+            ),
+            (PathBuf::from("src/helper.rs"), "pub fn helper() {}".to_string()),
+        ])
+        .all()
+        .analyze()
+        .unwrap();
+
+        assert!(result.modules["proto"].generated);
+        assert_eq!(result.dead_modules, vec!["helper".to_string()]);
+        let dead: Vec<_> = result.dead_items().map(|i| i.name.as_str()).collect();
+        assert!(!dead.contains(&"decode"));
+        assert!(!dead.contains(&"UNUSED"));
+        assert!(!dead.contains(&"helper"));
+    }
+
+    #[test]
+    fn test_external_targets_are_roots_and_usage_sources() {
+        let result = Deadmod::from_sources([
+            (
+                PathBuf::from("src/lib.rs"),
+                "pub const LIMIT: u8 = 1;\npub enum Mode { Fast, Slow }\npub fn tested() {}\npub fn unused() {}".to_string(),
+            ),
+            (
+                PathBuf::from("tests/it.rs"),
+                "mod common;\n#[test]\nfn t() { app::tested(); common::setup(app::LIMIT, app::Mode::Fast); }".to_string(),
+            ),
+            (PathBuf::from("tests/common/mod.rs"), "pub fn setup(_: u8, _: app::Mode) {}".to_string()),
+            (PathBuf::from("examples/demo/main.rs"), "fn main() {}".to_string()),
+        ])
+        .all()
+        .with_pub_policy(PubPolicy::Never)
+        .analyze()
+        .unwrap();
+
+        assert!(result.modules.contains_key("tests::it"));
+        assert!(result.modules.contains_key("examples::demo"));
+        assert!(result.dead_modules.is_empty());
+
+        let dead: Vec<_> = result.dead_items().map(|i| i.name.as_str()).collect();
+        assert!(dead.contains(&"unused"));
+        assert!(dead.contains(&"Mode::Slow"));
+        for live in ["tested", "setup", "LIMIT", "Mode::Fast"] {
+            assert!(!dead.contains(&live), "{} reported dead", live);
+        }
+    }
+
+    #[test]
+    fn test_test_helpers_are_opt_in() {
+        let sources = || {
+            [
+                (
+                    PathBuf::from("src/lib.rs"),
+                    "mod net;\nfn unused() {}\n#[cfg(test)]\nmod tests {\n    fn setup() {}\n    #[test]\n    fn t() { setup(); }\n}".to_string(),
+                ),
+                (
+                    PathBuf::from("src/net.rs"),
+                    "#[cfg(test)]\nmod tests {\n    fn setup() {}\n    fn stale_fixture() {}\n    #[test]\n    fn t() { setup(); }\n}".to_string(),
+                ),
+            ]
+        };
+        let dead = |builder: Deadmod| -> Vec<(String, String)> {
+            let result = builder.with_pub_policy(PubPolicy::Never).analyze().unwrap();
+            assert!(result.dead_modules.is_empty());
+            let mut dead: Vec<_> = result
+                .dead_functions
+                .iter()
+                .map(|f| (f.name.clone(), f.file.display().to_string()))
+                .collect();
+            dead.sort();
+            dead
+        };
+
+        let default = dead(Deadmod::from_sources(sources()).include_functions(true));
+        assert_eq!(default, [("unused".to_string(), "src/lib.rs".to_string())]);
+
+        let helpers = dead(Deadmod::from_sources(sources()).include_test_helpers(true));
+        assert_eq!(
+            helpers,
+            [("tests::stale_fixture".to_string(), "src/net.rs".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_edge_kinds_change_reachability() {
+        let analyze = |kinds: Option<EdgeKinds>| {
+            let mut builder = Deadmod::from_sources([
+                (
+                    PathBuf::from("src/lib.rs"),
+                    "mod net;\nmod metrics;\nuse net::Conn;".to_string(),
+                ),
+                (
+                    PathBuf::from("src/net.rs"),
+                    "pub struct Conn;\nfn send() { crate::metrics::record(); }".to_string(),
+                ),
+                (
+                    PathBuf::from("src/metrics.rs"),
+                    "pub fn record() {}".to_string(),
+                ),
+                (PathBuf::from("src/orphan.rs"), String::new()),
+                (
+                    PathBuf::from("src/stats.rs"),
+                    "fn f() { orphan::x(); }".to_string(),
+                ),
+            ]);
+            if let Some(kinds) = kinds {
+                builder = builder.with_edge_kinds(kinds);
+            }
+            let mut dead = builder.analyze().unwrap().dead_modules;
+            dead.sort();
+            dead
+        };
+
+        assert_eq!(analyze(None), ["orphan", "stats"]);
+        // Only `use` imports: declared but never imported modules are dead
+        assert_eq!(
+            analyze(Some(EdgeKinds::new([EdgeKind::Use]))),
+            ["metrics", "orphan", "stats"]
+        );
+        assert_eq!(
+            analyze(Some(EdgeKinds::new([EdgeKind::Use, EdgeKind::Path]))),
+            ["orphan", "stats"]
+        );
+    }
+
+    #[test]
+    fn test_2015_absolute_paths_reach_crate_modules() {
+        let analyze = |edition: Edition| {
+            let mut dead = Deadmod::from_sources([
+                (
+                    PathBuf::from("src/lib.rs"),
+                    "mod net;\nmod codec;\nuse ::net::Conn;".to_string(),
+                ),
+                (PathBuf::from("src/net.rs"), "pub struct Conn;".to_string()),
+                (PathBuf::from("src/codec.rs"), String::new()),
+            ])
+            .with_edge_kinds(EdgeKinds::new([EdgeKind::Use]))
+            .with_edition(edition)
+            .analyze()
+            .unwrap()
+            .dead_modules;
+            dead.sort();
+            dead
+        };
+
+        assert_eq!(analyze(Edition::E2015), ["codec"]);
+        assert_eq!(analyze(Edition::E2021), ["codec", "net"]);
+    }
+
+    #[test]
+    fn test_same_named_modules_are_told_apart() {
+        let mut result = Deadmod::from_sources([
+            (
+                PathBuf::from("src/lib.rs"),
+                "mod net;\nmod db;\nmod cache;".to_string(),
+            ),
+            (PathBuf::from("src/net/mod.rs"), "mod utils;".to_string()),
+            (PathBuf::from("src/net/utils.rs"), String::new()),
+            (PathBuf::from("src/db/mod.rs"), String::new()),
+            (PathBuf::from("src/db/utils.rs"), String::new()),
+            (PathBuf::from("src/cache.rs"), String::new()),
+            (PathBuf::from("src/cache/mod.rs"), String::new()),
+        ])
+        .analyze()
+        .unwrap();
+
+        result.dead_modules.sort();
+        assert_eq!(result.total_modules, 7);
+        assert_eq!(result.dead_modules, vec!["db::utils".to_string()]);
+        assert_eq!(result.collisions.len(), 1);
+        assert_eq!(result.collisions[0].module_path, "cache");
+    }
+
+    #[test]
+    fn test_limits_skip_large_files_and_late_detectors() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_builder_limits_{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "mod small;").unwrap();
+        fs::write(dir.join("src/small.rs"), "").unwrap();
+        fs::write(dir.join("src/generated.rs"), "x".repeat(4_096)).unwrap();
+
+        let result = Deadmod::new(&dir)
+            .with_cache(false)
+            .with_limits(Limits::default().with_max_file_size(1_024))
+            .analyze()
+            .unwrap();
+        assert!(result.dead_modules.is_empty());
+        assert_eq!(result.skipped.len(), 1);
+        assert!(result.skipped[0].input.ends_with("generated.rs"));
+
+        let result = Deadmod::new(&dir)
+            .with_cache(false)
+            .include_functions(true)
+            .include_constants(true)
+            .with_limits(Limits::default().with_timeout(0))
+            .analyze()
+            .unwrap();
+        let inputs: Vec<&str> = result.skipped.iter().map(|s| s.input.as_str()).collect();
+        assert_eq!(inputs, ["function analysis", "constant analysis"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_incremental_analysis_reruns_affected_detectors() {
+        let builder = |helper: &str| {
+            Deadmod::from_sources([
+                (
+                    PathBuf::from("src/lib.rs"),
+                    "mod a;\nmacro_rules! unused { () => {} }\npub fn api() { a::used(); }"
+                        .to_string(),
+                ),
+                (
+                    PathBuf::from("src/a.rs"),
+                    format!("pub fn used() {{}}\n{}", helper),
+                ),
+            ])
+            .all()
+        };
+        let mut state = IncrementalState::new();
+
+        let first = builder("fn helper() {}")
+            .analyze_incremental(&mut state)
+            .unwrap();
+        assert_eq!(state.last_run().len(), Detector::ALL.len());
+        assert_eq!(first.dead_macros.len(), 1);
+
+        let second = builder("fn helper() {}\nfn other() {}")
+            .analyze_incremental(&mut state)
+            .unwrap();
+        assert!(state.last_run().contains(&Detector::Functions));
+        assert!(!state.last_run().contains(&Detector::Macros));
+        assert_eq!(second.dead_macros[0].name, "unused");
+        let names: Vec<&str> = second
+            .dead_functions
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert!(names.contains(&"other"), "{:?}", names);
+
+        // Matches a full analysis
+        let full = builder("fn helper() {}\nfn other() {}").analyze().unwrap();
+        assert_eq!(second.dead_count(), full.dead_count());
+    }
+
+    #[test]
+    fn test_workspace_analysis() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_builder_workspace_{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join("alpha/src")).unwrap();
+        fs::create_dir_all(dir.join("beta/src")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"alpha\", \"beta\"]\n",
+        )
+        .unwrap();
+        // Cross-crate edges follow declared dependencies
+        for (name, deps) in [
+            ("alpha", ""),
+            (
+                "beta",
+                "\n[dependencies]\nalpha = { path = \"../alpha\" }\n",
+            ),
+        ] {
+            fs::write(
+                dir.join(name).join("Cargo.toml"),
+                format!(
+                    "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n{}",
+                    name, deps
+                ),
+            )
+            .unwrap();
+        }
+        fs::write(dir.join("alpha/src/lib.rs"), "pub mod used;").unwrap();
+        fs::write(dir.join("alpha/src/used.rs"), "pub fn used() {}").unwrap();
+        fs::write(dir.join("alpha/src/orphan.rs"), "pub fn orphan() {}").unwrap();
+        fs::write(
+            dir.join("beta/src/main.rs"),
+            "use alpha::used;\nfn main() { used::used(); }",
+        )
+        .unwrap();
+
+        let result = Deadmod::new(&dir)
+            .with_cache(false)
+            .analyze_workspace()
+            .unwrap();
+        assert_eq!(result.crates.len(), 2);
+        assert_eq!(result.total_modules, 4);
+        assert_eq!(
+            result.crate_result("alpha").unwrap().dead_modules,
+            ["orphan"]
+        );
+        assert!(result.crate_result("beta").unwrap().dead_modules.is_empty());
+        assert_eq!(result.dead_modules, ["alpha::orphan"]);
+        assert_eq!(
+            result.cross_crate_edges,
+            [CrossCrateEdge {
+                from: "beta::main".to_string(),
+                to: "alpha::lib".to_string(),
+            }]
+        );
+
+        // Only beta is an entry point: alpha's lib is alive through beta alone
+        let result = Deadmod::new(&dir)
+            .with_cache(false)
+            .with_roots(["beta::main"])
+            .analyze_workspace()
+            .unwrap();
+        assert_eq!(result.dead_modules, ["alpha::orphan"]);
+        let result = Deadmod::new(&dir)
+            .with_cache(false)
+            .with_roots(["alpha::used"])
+            .analyze_workspace()
+            .unwrap();
+        assert_eq!(
+            result.dead_modules,
+            ["alpha::lib", "alpha::orphan", "beta::main"]
+        );
+        assert!(result.has_dead_code());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_project_json_analysis() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_builder_project_{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join("utils/src")).unwrap();
+        fs::create_dir_all(dir.join("tools/gen")).unwrap();
+        fs::write(
+            dir.join(crate::PROJECT_JSON),
+            r#"{"crates": [
+                {"root_module": "utils/src/lib.rs", "edition": "2021", "deps": []},
+                {"root_module": "tools/gen/gen.rs", "edition": "2018", "deps": [{"crate": 0, "name": "utils"}]},
+                {"root_module": "/nonexistent/std/lib.rs", "is_workspace_member": false}
+            ]}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("utils/src/lib.rs"), "pub mod used;").unwrap();
+        fs::write(dir.join("utils/src/used.rs"), "pub fn used() {}").unwrap();
+        fs::write(dir.join("utils/src/orphan.rs"), "pub fn orphan() {}").unwrap();
+        fs::write(
+            dir.join("tools/gen/gen.rs"),
+            "mod helper;\nuse utils::used;\nfn main() {}",
+        )
+        .unwrap();
+        fs::write(dir.join("tools/gen/helper.rs"), "").unwrap();
+
+        let result = Deadmod::new(&dir)
+            .with_cache(false)
+            .analyze_workspace()
+            .unwrap();
+        let names: Vec<&str> = result.crates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["utils", "gen"]);
+        assert_eq!(result.crates[1].edition, Edition::E2018);
+        assert_eq!(result.dead_modules, ["utils::orphan"]);
+        assert!(result.crate_result("gen").unwrap().dead_modules.is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_saved_analysis_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("analysis.json");
+
+        let result = Deadmod::from_sources([
+            (
+                PathBuf::from("src/lib.rs"),
+                "mod a;\nfn unused() {}".to_string(),
+            ),
+            (PathBuf::from("src/a.rs"), String::new()),
+            (PathBuf::from("src/orphan.rs"), String::new()),
+        ])
+        .include_functions(true)
+        .analyze()
+        .unwrap();
+        result.save(&file).unwrap();
+
+        let loaded = AnalysisResult::load(&file).unwrap();
+        assert_eq!(loaded.dead_modules, result.dead_modules);
+        assert_eq!(loaded.dead_functions.len(), 1);
+        assert_eq!(loaded.dead_functions[0].id, result.dead_functions[0].id);
+        assert_eq!(loaded.modules["a"].module_path, "a");
+        assert_eq!(loaded.modules.len(), 3);
+
+        let stale = fs::read_to_string(&file).unwrap().replacen(
+            "\"format_version\":1",
+            "\"format_version\":0",
+            1,
+        );
+        fs::write(&file, stale).unwrap();
+        assert!(AnalysisResult::load(&file).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_retain_file() {
+        let mut result = Deadmod::from_sources([
+            (
+                PathBuf::from("src/lib.rs"),
+                "mod a;\nfn unused_root() {}".to_string(),
+            ),
+            (
+                PathBuf::from("src/a.rs"),
+                "pub fn used() {}\nfn unused_a() {}".to_string(),
+            ),
+            (PathBuf::from("src/b.rs"), "fn unused_b() {}".to_string()),
+        ])
+        .include_functions(true)
+        .analyze()
+        .unwrap();
+        assert_eq!(result.dead_items().count(), 3);
+
+        // The borrowed view agrees with narrowing in place
+        let findings = result.findings_in(Path::new("src/b.rs"));
+        assert_eq!(findings.dead_modules, vec!["b"]);
+        assert_eq!(
+            findings
+                .dead
+                .iter()
+                .map(|i| i.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["unused_b"]
+        );
+        assert_eq!(result.findings().dead.len(), 3);
+
+        result.retain_file(Path::new("src/a.rs"));
+        let names: Vec<_> = result.dead_items().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["unused_a"]);
+        assert!(result.dead_modules.is_empty());
+
+        let mut result = Deadmod::from_sources([
+            (PathBuf::from("src/lib.rs"), String::new()),
+            (PathBuf::from("src/b.rs"), String::new()),
+        ])
+        .analyze()
+        .unwrap();
+        result.retain_file(Path::new("src/b.rs"));
+        assert_eq!(result.dead_modules, vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn test_builder_with_roots() {
+        let result = Deadmod::from_sources([
+            (
+                PathBuf::from("src/main.rs"),
+                "mod api;\nmod cli;\nfn main() { cli::run(); }".to_string(),
+            ),
+            (
+                PathBuf::from("src/api.rs"),
+                "pub fn serve() { helper(); }\nfn helper() {}".to_string(),
+            ),
+            (PathBuf::from("src/cli.rs"), "pub fn run() {}".to_string()),
+        ])
+        .with_roots(["api"])
+        .include_functions(true)
+        .analyze()
+        .unwrap();
+
+        let mut dead = result.dead_modules.clone();
+        dead.sort();
+        assert_eq!(dead, vec!["cli".to_string(), "main".to_string()]);
+
+        let mut dead_funcs: Vec<_> = result
+            .dead_functions
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        dead_funcs.sort();
+        assert_eq!(dead_funcs, vec!["main", "run"]);
+    }
+
     #[test]
     fn test_dead_item_kind_display() {
         assert_eq!(DeadItemKind::Function.to_string(), "function");
@@ -492,6 +2682,8 @@ mod tests {
             total_modules: 10,
             reachable_modules: vec!["a".into(), "b".into()],
             dead_modules: vec!["c".into(), "d".into()],
+            collisions: Vec::new(),
+            skipped: Vec::new(),
             dead_functions: Vec::new(),
             dead_traits: Vec::new(),
             dead_constants: Vec::new(),
@@ -499,7 +2691,12 @@ mod tests {
             dead_macros: Vec::new(),
             dead_generics: Vec::new(),
             dead_matcharms: Vec::new(),
+            dead_impls: Vec::new(),
+            kept: Vec::new(),
+            recent: Vec::new(),
+            collapsed: Vec::new(),
             modules: HashMap::new(),
+            evidence: Default::default(),
         };
 
         assert!(result.has_dead_code());
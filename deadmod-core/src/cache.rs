@@ -8,32 +8,146 @@
 //! Caches parsed module information based on file content hashes,
 //! avoiding re-parsing unchanged files.
 //!
+//! # Cache Format
+//!
+//! The cache is stored in `.deadmod/cache.bin`: a magic header, the
+//! bincode-encoded [`CacheMetadata`], then the modules as zstd-compressed
+//! bincode. Loading maps the file (see `common::mapped`) and checks the
+//! metadata before decompressing anything, so an outdated cache is dropped
+//! cheaply. `DEADMOD_CACHE_FORMAT=json` switches to pretty JSON in
+//! `.deadmod/cache.json` for debugging (see [`CacheFormat`]).
+//!
+//! # Concurrent Runs
+//!
+//! CI matrix jobs, or the LSP server next to a CLI run, may share a cache.
+//! Saves write a temp file and rename it over the cache while holding an
+//! exclusive advisory lock on `cache.lock`, so writers never interleave;
+//! loads hold a shared lock. A lock still held after [`LOCK_TIMEOUT`] makes
+//! a save give up with a warning (the cache is best effort) and a load read
+//! without it, which the atomic rename keeps safe. `DEADMOD_CACHE_DIR`
+//! moves caches out of the crate entirely, e.g. to a per-job directory
+//! (see [`cache_dir`]).
+//!
 //! # Cache Versioning
 //!
 //! The cache includes version metadata to ensure cache invalidation when:
 //! - Deadmod version changes (may have different parsing logic)
 //! - Rust toolchain version changes (affects syntax support)
 //! - Cache format changes
-
-use crate::parse::{extract_uses_and_decls, ModuleInfo, Visibility};
+//!
+//! # Uncached Runs
+//!
+//! A CI job starting from a clean checkout never reuses the cache, yet pays
+//! for hashing every file and (de)serializing the cache. [`parse_crate`]
+//! takes a [`ParseMode`]: [`ParseMode::Uncached`] skips the cache load and
+//! save, and [`ParseMode::Fast`] additionally drops hashing and reads files
+//! through memory maps (see `common::mapped`).
+
+use crate::collision::key_modules;
+use crate::common::with_file_bytes;
+use crate::edition::Edition;
+use crate::error::{DeadmodError, DeadmodResult, IoResultExt};
+use crate::parse::{
+    extract_dependencies, select_edges, CfgModDecl, EdgeKind, EdgeKinds, IncludeSite, ModuleInfo,
+    Visibility,
+};
+use crate::progress::{Phase, Progress};
+use crate::scan::GeneratedMarkers;
 use anyhow::{Context, Result};
+use bincode::Options;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info_span};
 
 /// Maximum cache file size (50MB) - prevents unbounded cache growth
 const MAX_CACHE_SIZE_BYTES: usize = 50_000_000;
 
 /// Current cache format version. Increment when cache format changes.
-const CACHE_VERSION: u32 = 2;
+///
+/// The binary format is positional: adding, removing or reordering a
+/// [`CachedModule`] field requires a new version (`#[serde(default)]` only
+/// helps JSON caches).
+const CACHE_VERSION: u32 = 8;
+
+/// Leading bytes of a binary cache file.
+const CACHE_MAGIC: &[u8] = b"DEADMOD\0";
+
+/// zstd level for binary caches: fast to write, still several times smaller
+/// than the bincode it wraps.
+const ZSTD_LEVEL: i32 = 3;
+
+/// Upper bound on the decompressed size of a binary cache, so a corrupted
+/// file cannot make decoding allocate without limit.
+const MAX_DECODED_CACHE_BYTES: u64 = 1 << 30;
+
+/// Environment variable moving caches out of `<crate>/.deadmod/`.
+pub const CACHE_DIR_ENV_VAR: &str = "DEADMOD_CACHE_DIR";
+
+/// How long a load or save waits for another process's cache lock.
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Environment variable selecting the cache format (`binary` or `json`).
+pub const CACHE_FORMAT_ENV_VAR: &str = "DEADMOD_CACHE_FORMAT";
 
 /// Deadmod version for cache compatibility checking.
 const DEADMOD_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// How a parse uses the on-disk cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Load the `.deadmod/` cache, re-parse changed files, save it back
+    #[default]
+    Cached,
+    /// Parse every file; the cache is neither loaded nor saved
+    Uncached,
+    /// [`ParseMode::Uncached`] without content hashing, reading files
+    /// through memory maps (cold CI runs)
+    Fast,
+}
+
+/// On-disk encoding of the cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheFormat {
+    /// zstd-compressed bincode in `.deadmod/cache.bin`
+    #[default]
+    Binary,
+    /// Pretty JSON in `.deadmod/cache.json`, for debugging
+    Json,
+}
+
+impl CacheFormat {
+    /// Format from `DEADMOD_CACHE_FORMAT`, binary unless set to `json`.
+    ///
+    /// Invalid values are ignored with a warning.
+    pub fn from_env() -> Self {
+        match std::env::var(CACHE_FORMAT_ENV_VAR).as_deref() {
+            Err(_) | Ok("binary") => Self::Binary,
+            Ok("json") => Self::Json,
+            Ok(value) => {
+                eprintln!(
+                    "[WARN] Ignoring {}={:?} (expected binary or json)",
+                    CACHE_FORMAT_ENV_VAR, value
+                );
+                Self::Binary
+            }
+        }
+    }
+
+    /// Name of the cache file inside the cache directory.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Binary => "cache.bin",
+            Self::Json => "cache.json",
+        }
+    }
+}
+
 /// Cached representation of a module.
 /// Stores the hash of the file and the module references found during parsing.
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -46,6 +160,22 @@ pub struct CachedModule {
     /// Whether module is doc(hidden)
     #[serde(default)]
     pub doc_hidden: bool,
+    /// `include!` sites (added in cache v3)
+    #[serde(default)]
+    pub includes: Vec<IncludeSite>,
+    /// Visibility of `mod` declarations (added in cache v4)
+    #[serde(default)]
+    pub mod_decls: HashMap<String, CachedVisibility>,
+    /// Every referenced name with its reference kinds (added in cache v5;
+    /// `::name` references kept apart since v7)
+    #[serde(default)]
+    pub ref_kinds: HashMap<String, BTreeSet<EdgeKind>>,
+    /// Inline `#[cfg(test)]` modules (added in cache v6)
+    #[serde(default)]
+    pub test_mods: BTreeSet<String>,
+    /// cfg-gated `mod` declarations (added in cache v8)
+    #[serde(default)]
+    pub cfg_mods: Vec<CfgModDecl>,
 }
 
 /// Serializable visibility for cache storage.
@@ -125,7 +255,7 @@ impl CacheMetadata {
     }
 }
 
-/// The full cache model, stored as a file in `.deadmod/cache.json`.
+/// The full cache model, stored in `.deadmod/` (see [`CacheFormat`]).
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct DeadmodCache {
     /// Cache metadata for version checking
@@ -151,20 +281,114 @@ pub fn file_hash(path: &Path) -> Result<String> {
     Ok(hash_bytes(&bytes))
 }
 
-/// Load the cache from `.deadmod/cache.json`.
+/// Directory holding the cache of the crate at `crate_root`.
+///
+/// `<crate_root>/.deadmod` unless `DEADMOD_CACHE_DIR` is set; then a
+/// subdirectory of it named after the crate directory plus a hash of its
+/// canonical path, so crates sharing the variable never share a cache.
+pub fn cache_dir(crate_root: &Path) -> PathBuf {
+    cache_dir_in(std::env::var_os(CACHE_DIR_ENV_VAR).as_deref(), crate_root)
+}
+
+/// [`cache_dir`] with `DEADMOD_CACHE_DIR` set to `base`.
+fn cache_dir_in(base: Option<&std::ffi::OsStr>, crate_root: &Path) -> PathBuf {
+    match base.filter(|dir| !dir.is_empty()) {
+        Some(base) => {
+            let root = crate_root
+                .canonicalize()
+                .unwrap_or_else(|_| crate_root.to_path_buf());
+            let name = root
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let hash = hash_bytes(root.to_string_lossy().as_bytes());
+            PathBuf::from(base).join(format!("{}-{}", name, &hash[..12]))
+        }
+        None => crate_root.join(".deadmod"),
+    }
+}
+
+/// Advisory lock on `cache.lock` in a cache directory, released on drop.
+struct CacheLock {
+    /// `None` where the lock file cannot be opened or locked (read-only
+    /// directories, some network filesystems): runs go on unlocked
+    _file: Option<fs::File>,
+}
+
+impl CacheLock {
+    /// Lock `dir` (shared for readers, exclusive for writers), waiting up
+    /// to [`LOCK_TIMEOUT`]; `None` if another process held it throughout.
+    fn acquire(dir: &Path, exclusive: bool) -> Option<Self> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(dir.join("cache.lock"));
+        let Ok(file) = file else {
+            return Some(Self { _file: None });
+        };
+        let started = Instant::now();
+        loop {
+            let attempt = if exclusive {
+                file.try_lock()
+            } else {
+                file.try_lock_shared()
+            };
+            match attempt {
+                Ok(()) => return Some(Self { _file: Some(file) }),
+                Err(fs::TryLockError::WouldBlock) if started.elapsed() < LOCK_TIMEOUT => {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                Err(fs::TryLockError::WouldBlock) => return None,
+                Err(fs::TryLockError::Error(_)) => return Some(Self { _file: None }),
+            }
+        }
+    }
+}
+
+/// Load the cache in the format chosen by `DEADMOD_CACHE_FORMAT`.
 ///
 /// Returns `None` if:
 /// - File doesn't exist
-/// - File is corrupted
+/// - File is corrupted (warned about)
 /// - Cache version is incompatible with current deadmod version
 pub fn load_cache(crate_root: &Path) -> Option<DeadmodCache> {
-    let path = crate_root.join(".deadmod/cache.json");
+    load_cache_as(crate_root, CacheFormat::from_env())
+}
+
+/// Load the cache stored in `format` (see [`load_cache`]).
+pub fn load_cache_as(crate_root: &Path, format: CacheFormat) -> Option<DeadmodCache> {
+    read_cache_as(crate_root, format).unwrap_or_else(|e| {
+        eprintln!("[WARN] {}, rebuilding...", e);
+        None
+    })
+}
+
+/// Read the cache stored in `format`: `None` when there is none or it was
+/// written by an incompatible version (and is removed), and
+/// [`DeadmodError::CacheCorrupted`] when it cannot be read back.
+pub fn read_cache_as(
+    crate_root: &Path,
+    format: CacheFormat,
+) -> DeadmodResult<Option<DeadmodCache>> {
+    let dir = cache_dir(crate_root);
+    let path = dir.join(format.file_name());
     if !path.exists() {
-        return None;
+        return Ok(None);
     }
 
-    let text = fs::read_to_string(&path).ok()?;
-    let cache: DeadmodCache = serde_json::from_str(&text).ok()?;
+    // Without the lock, the rename in `save_cache_as` still keeps reads whole
+    let _lock = CacheLock::acquire(&dir, false);
+    let corrupted = |message: String| DeadmodError::cache_corrupted(&path, message);
+    let cache = match format {
+        CacheFormat::Binary => with_file_bytes(&path, decode_binary)
+            .with_path(&path)?
+            .ok_or_else(|| corrupted("not a deadmod binary cache".to_string()))?,
+        CacheFormat::Json => {
+            let text = fs::read_to_string(&path).with_path(&path)?;
+            serde_json::from_str(&text).map_err(|e| corrupted(e.to_string()))?
+        }
+    };
 
     // Check version compatibility
     if !cache.metadata.is_compatible() {
@@ -177,33 +401,90 @@ pub fn load_cache(crate_root: &Path) -> Option<DeadmodCache> {
         );
         // Remove incompatible cache
         let _ = fs::remove_file(&path);
-        return None;
+        return Ok(None);
     }
 
-    Some(cache)
+    Ok(Some(cache))
+}
+
+/// Bincode settings shared by encoding and decoding (varint integers).
+fn bincode_options() -> impl bincode::Options {
+    bincode::DefaultOptions::new().with_limit(MAX_DECODED_CACHE_BYTES)
 }
 
-/// Save the current cache state to disk.
+/// Encode `cache` as magic, metadata and compressed modules.
+fn encode_binary(cache: &DeadmodCache) -> Result<Vec<u8>> {
+    let mut out = CACHE_MAGIC.to_vec();
+    bincode_options().serialize_into(&mut out, &cache.metadata)?;
+    let modules = bincode_options().serialize(&cache.modules)?;
+    out.extend(zstd::bulk::compress(&modules, ZSTD_LEVEL)?);
+    Ok(out)
+}
+
+/// Decode a binary cache, or `None` if it is not one or is corrupted.
+///
+/// An incompatible cache comes back with its metadata and no modules,
+/// which may not even decode under the current layout.
+fn decode_binary(bytes: &[u8]) -> Option<DeadmodCache> {
+    let mut rest = bytes.strip_prefix(CACHE_MAGIC)?;
+    let metadata: CacheMetadata = bincode_options().deserialize_from(&mut rest).ok()?;
+    if !metadata.is_compatible() {
+        return Some(DeadmodCache {
+            metadata,
+            modules: HashMap::new(),
+        });
+    }
+    // Decompress in one go: decoding from a slice beats a streaming reader
+    let mut modules = Vec::new();
+    zstd::Decoder::with_buffer(rest)
+        .ok()?
+        .take(MAX_DECODED_CACHE_BYTES)
+        .read_to_end(&mut modules)
+        .ok()?;
+    let modules = bincode_options().deserialize(&modules).ok()?;
+    Some(DeadmodCache { metadata, modules })
+}
+
+/// Save the cache in the format chosen by `DEADMOD_CACHE_FORMAT`.
+pub fn save_cache(crate_root: &Path, cache: &DeadmodCache) -> Result<()> {
+    save_cache_as(crate_root, cache, CacheFormat::from_env())
+}
+
+/// Save the current cache state to disk in `format`.
 ///
 /// Uses atomic write pattern (temp file + rename) to prevent:
 /// - Partial writes if process is interrupted
 /// - Race conditions with concurrent readers
 /// - Corrupted cache files
 ///
+/// Concurrent writers are serialized by an exclusive lock; if another
+/// process holds it past [`LOCK_TIMEOUT`], nothing is saved.
+///
 /// Security features:
 /// - Random suffix in temp filename prevents collision attacks
 /// - Size limit prevents unbounded cache growth (DoS)
-pub fn save_cache(crate_root: &Path, cache: &DeadmodCache) -> Result<()> {
-    let dir = crate_root.join(".deadmod");
+pub fn save_cache_as(crate_root: &Path, cache: &DeadmodCache, format: CacheFormat) -> Result<()> {
+    let dir = cache_dir(crate_root);
     if !dir.exists() {
         fs::create_dir_all(&dir)?;
     }
 
-    let path = dir.join("cache.json");
-    let json = serde_json::to_string_pretty(cache)?;
+    let path = dir.join(format.file_name());
+    let bytes = match format {
+        CacheFormat::Binary => encode_binary(cache)?,
+        CacheFormat::Json => serde_json::to_string_pretty(cache)?.into_bytes(),
+    };
+
+    let Some(_lock) = CacheLock::acquire(&dir, true) else {
+        eprintln!(
+            "[WARN] cache {} is locked by another process, not saved",
+            dir.display()
+        );
+        return Ok(());
+    };
 
     // Security: Check cache size to prevent unbounded growth
-    if json.len() > MAX_CACHE_SIZE_BYTES {
+    if bytes.len() > MAX_CACHE_SIZE_BYTES {
         eprintln!(
             "[WARN] Cache exceeds {}MB limit, clearing old cache",
             MAX_CACHE_SIZE_BYTES / 1_000_000
@@ -219,12 +500,16 @@ pub fn save_cache(crate_root: &Path, cache: &DeadmodCache) -> Result<()> {
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
         .as_nanos();
-    let temp_path = dir.join(format!("cache.json.{}.{}.tmp", std::process::id(), nanos));
+    let temp_path = dir.join(format!(
+        "{}.{}.{}.tmp",
+        format.file_name(),
+        std::process::id(),
+        nanos
+    ));
 
     // Write to temp file
-    fs::write(&temp_path, &json).with_context(|| {
-        format!("Failed to write temp cache file: {}", temp_path.display())
-    })?;
+    fs::write(&temp_path, &bytes)
+        .with_context(|| format!("Failed to write temp cache file: {}", temp_path.display()))?;
 
     // Atomic rename (on most filesystems)
     fs::rename(&temp_path, &path).with_context(|| {
@@ -239,8 +524,8 @@ pub fn save_cache(crate_root: &Path, cache: &DeadmodCache) -> Result<()> {
 /// Result of processing a single file for incremental parsing.
 enum FileProcessResult {
     /// Successfully processed (name, info, cache_entry)
-    /// ModuleInfo is boxed to reduce enum size (clippy::large_enum_variant)
-    Ok(String, Box<ModuleInfo>, CachedModule),
+    /// Both are boxed to reduce enum size (clippy::large_enum_variant)
+    Ok(String, Box<ModuleInfo>, Box<CachedModule>),
     /// Skipped due to error
     Skipped,
 }
@@ -254,6 +539,7 @@ enum FileProcessResult {
 fn process_file(
     file: &PathBuf,
     old_cache: Option<&DeadmodCache>,
+    markers: &GeneratedMarkers,
 ) -> FileProcessResult {
     // Extract module name from file stem
     let name = match file.file_stem() {
@@ -282,27 +568,49 @@ fn process_file(
             if cached.hash == hash {
                 // Cache hit: reuse parsed refs without re-parsing
                 let mut info = ModuleInfo::new(file.clone());
+                info.generated = markers.matches(&content);
                 info.refs = cached.refs.clone();
-                return FileProcessResult::Ok(name, Box::new(info), cached.clone());
+                info.ref_kinds = cached.ref_kinds.clone();
+                info.test_mods = cached.test_mods.clone();
+                info.cfg_mods = cached.cfg_mods.clone();
+                info.includes = cached.includes.clone();
+                info.mod_decls = cached
+                    .mod_decls
+                    .iter()
+                    .map(|(name, &vis)| (name.clone(), vis.into()))
+                    .collect();
+                debug!(file = %file.display(), "cache hit");
+                return FileProcessResult::Ok(name, Box::new(info), Box::new(cached.clone()));
             }
         }
     }
 
     // Cache miss: parse the content we already have in memory
     let mut info = ModuleInfo::new(file.clone());
-    if let Err(e) = extract_uses_and_decls(&content, &mut info.refs) {
+    info.generated = markers.matches(&content);
+    if let Err(e) = extract_dependencies(&content, &mut info) {
         eprintln!("[WARN] AST parse failed {}: {}", file.display(), e);
         // Continue with empty refs - module still exists in graph
     }
+    debug!(file = %file.display(), refs = info.refs.len(), "parsed");
 
     let cache_entry = CachedModule {
         hash,
         refs: info.refs.clone(),
         visibility: CachedVisibility::from(info.visibility),
         doc_hidden: info.doc_hidden,
+        includes: info.includes.clone(),
+        mod_decls: info
+            .mod_decls
+            .iter()
+            .map(|(name, &vis)| (name.clone(), vis.into()))
+            .collect(),
+        ref_kinds: info.ref_kinds.clone(),
+        test_mods: info.test_mods.clone(),
+        cfg_mods: info.cfg_mods.clone(),
     };
 
-    FileProcessResult::Ok(name, Box::new(info), cache_entry)
+    FileProcessResult::Ok(name, Box::new(info), Box::new(cache_entry))
 }
 
 /// Incremental parsing with NASA-grade resilience and parallel execution.
@@ -322,14 +630,67 @@ pub fn incremental_parse(
     files: &[PathBuf],
     old_cache: Option<DeadmodCache>,
 ) -> Result<HashMap<String, ModuleInfo>> {
+    incremental_parse_with_progress(crate_root, files, old_cache, &Progress::none())
+}
+
+/// [`incremental_parse`], reporting each file to `progress` as
+/// [`Phase::Parse`].
+pub fn incremental_parse_with_progress(
+    crate_root: &Path,
+    files: &[PathBuf],
+    old_cache: Option<DeadmodCache>,
+    progress: &Progress,
+) -> Result<HashMap<String, ModuleInfo>> {
+    parse_files(crate_root, files, old_cache, true, progress)
+}
+
+/// Parse `files` as `mode` says, reporting each file to `progress`.
+///
+/// [`ParseMode::Cached`] is [`incremental_parse_with_progress`] over the
+/// loaded cache. Module information is the same in every mode.
+pub fn parse_crate(
+    crate_root: &Path,
+    files: &[PathBuf],
+    mode: ParseMode,
+    progress: &Progress,
+) -> Result<HashMap<String, ModuleInfo>> {
+    match mode {
+        ParseMode::Cached => parse_files(crate_root, files, load_cache(crate_root), true, progress),
+        ParseMode::Uncached => parse_files(crate_root, files, None, false, progress),
+        ParseMode::Fast => Ok(fast_parse(crate_root, files, progress)),
+    }
+}
+
+/// Parse through `old_cache`, saving the new cache when `save` is set.
+fn parse_files(
+    crate_root: &Path,
+    files: &[PathBuf],
+    old_cache: Option<DeadmodCache>,
+    save: bool,
+    progress: &Progress,
+) -> Result<HashMap<String, ModuleInfo>> {
+    let span = info_span!("parse", files = files.len(), cached = old_cache.is_some());
+    let _entered = span.enter();
+
+    // Header markers are cheap to check, so they are not cached
+    let markers = GeneratedMarkers::for_crate(crate_root);
+
     // Process all files in parallel using Rayon
+    progress.start(Phase::Parse, Some(files.len()));
     let results: Vec<FileProcessResult> = files
         .par_iter()
-        .map(|file| process_file(file, old_cache.as_ref()))
+        .map(|file| {
+            // Workers do not inherit the span; file events belong to it
+            let _entered = span.enter();
+            let result = process_file(file, old_cache.as_ref(), &markers);
+            progress.advance(Phase::Parse, 1);
+            result
+        })
         .collect();
+    progress.done(Phase::Parse);
 
     // Aggregate results (sequential, but O(n) simple insertions)
-    let mut mods = HashMap::with_capacity(results.len());
+    let mut mods = Vec::with_capacity(results.len());
     let mut new_cache = DeadmodCache {
         metadata: CacheMetadata::current(),
         modules: HashMap::with_capacity(results.len()),
@@ -337,17 +698,124 @@ pub fn incremental_parse(
 
     for result in results {
         if let FileProcessResult::Ok(name, info, cache_entry) = result {
-            mods.insert(name.clone(), *info);
-            new_cache.modules.insert(name, cache_entry);
+            mods.push(*info);
+            new_cache.modules.insert(name, *cache_entry);
         }
     }
 
     // Best-effort cache save (don't fail if write fails)
-    if let Err(e) = save_cache(crate_root, &new_cache) {
-        eprintln!("[WARN] cache save failed: {}", e);
+    if save {
+        if let Err(e) = save_cache(crate_root, &new_cache) {
+            eprintln!("[WARN] cache save failed: {}", e);
+        }
     }
 
-    Ok(mods)
+    Ok(finish_modules(crate_root, mods))
+}
+
+/// Key parsed modules and apply the crate's edition and edge kinds.
+fn finish_modules(crate_root: &Path, mods: Vec<ModuleInfo>) -> HashMap<String, ModuleInfo> {
+    let mut mods = key_modules(mods);
+    let edition = Edition::for_crate(crate_root);
+    for info in mods.values_mut() {
+        info.edition = edition;
+    }
+    select_edges(&mut mods, &EdgeKinds::for_crate(crate_root));
+    mods
+}
+
+/// [`ParseMode::Fast`]: parse straight from mapped files, no hashing and
+/// no cache entries.
+fn fast_parse(
+    crate_root: &Path,
+    files: &[PathBuf],
+    progress: &Progress,
+) -> HashMap<String, ModuleInfo> {
+    let span = info_span!("parse", files = files.len(), fast = true);
+    let _entered = span.enter();
+    let markers = GeneratedMarkers::for_crate(crate_root);
+
+    progress.start(Phase::Parse, Some(files.len()));
+    let mods: Vec<ModuleInfo> = files
+        .par_iter()
+        .filter_map(|file| {
+            let _entered = span.enter();
+            let info = fast_parse_file(file, &markers);
+            progress.advance(Phase::Parse, 1);
+            info
+        })
+        .collect();
+    progress.done(Phase::Parse);
+
+    finish_modules(crate_root, mods)
+}
+
+/// Parse one file for [`fast_parse`], skipping it with a warning on error
+/// as [`process_file`] does.
+fn fast_parse_file(file: &Path, markers: &GeneratedMarkers) -> Option<ModuleInfo> {
+    if file.file_stem().is_none() {
+        eprintln!("[WARN] skipping file with no stem: {}", file.display());
+        return None;
+    }
+
+    let parsed = with_file_bytes(file, |bytes| {
+        let content = std::str::from_utf8(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let mut info = ModuleInfo::new(file.to_path_buf());
+        info.generated = markers.matches(content);
+        if let Err(e) = extract_dependencies(content, &mut info) {
+            eprintln!("[WARN] AST parse failed {}: {}", file.display(), e);
+        }
+        debug!(file = %file.display(), refs = info.refs.len(), "parsed");
+        Ok(info)
+    });
+
+    match parsed.and_then(|info| info) {
+        Ok(info) => Some(info),
+        Err(e) => {
+            eprintln!("[WARN] read error {}: {}", file.display(), e);
+            None
+        }
+    }
+}
+
+/// Cache state of one crate after [`warm_cache`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WarmStats {
+    /// Modules parsed from the crate's files
+    pub modules: usize,
+    /// Entries in the saved cache (0 if it could not be written)
+    pub cached: usize,
+    /// Entries that were already current before warming
+    pub reused: usize,
+}
+
+/// Parse `files` into the crate's cache without analyzing anything.
+///
+/// Later runs, the daemon and LSP startup then only re-parse files changed
+/// since. Returns how much of the cache was already current.
+pub fn warm_cache(crate_root: &Path, files: &[PathBuf]) -> Result<WarmStats> {
+    let old_cache = load_cache(crate_root);
+    let previous: HashMap<String, String> = old_cache
+        .iter()
+        .flat_map(|cache| &cache.modules)
+        .map(|(name, module)| (name.clone(), module.hash.clone()))
+        .collect();
+
+    let modules = incremental_parse(crate_root, files, old_cache)?.len();
+
+    let saved = load_cache(crate_root)
+        .map(|cache| cache.modules)
+        .unwrap_or_default();
+    let reused = saved
+        .iter()
+        .filter(|(name, module)| previous.get(*name) == Some(&module.hash))
+        .count();
+    Ok(WarmStats {
+        modules,
+        cached: saved.len(),
+        reused,
+    })
 }
 
 #[cfg(test)]
@@ -424,6 +892,11 @@ mod tests {
                 refs,
                 visibility: CachedVisibility::default(),
                 doc_hidden: false,
+                includes: Vec::new(),
+                mod_decls: HashMap::new(),
+                ref_kinds: HashMap::new(),
+                test_mods: BTreeSet::new(),
+                cfg_mods: Vec::new(),
             },
         );
 
@@ -437,6 +910,90 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_binary_cache_version_mismatch() {
+        let dir = create_temp_dir("binary_version");
+        let mut cache = DeadmodCache {
+            metadata: CacheMetadata::current(),
+            modules: HashMap::new(),
+        };
+        cache.metadata.cache_version = CACHE_VERSION - 1;
+        save_cache(&dir, &cache).unwrap();
+
+        // Dropped (and deleted) without decoding the modules
+        assert!(load_cache(&dir).is_none());
+        assert!(!dir.join(".deadmod/cache.bin").exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_cache_dir_override() {
+        let root = create_temp_dir("cache_dir");
+        assert_eq!(cache_dir_in(None, &root), root.join(".deadmod"));
+
+        let base = std::ffi::OsStr::new("/tmp/ci-job-7");
+        let dir = cache_dir_in(Some(base), &root);
+        assert_eq!(dir.parent(), Some(Path::new("/tmp/ci-job-7")));
+        let name = dir.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with(&format!("{}-", root.file_name().unwrap().to_string_lossy())));
+        assert_ne!(dir, cache_dir_in(Some(base), &root.join("src")));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_concurrent_saves_leave_whole_cache() {
+        let dir = create_temp_dir("concurrent_saves");
+        let cache_with = |n: usize| DeadmodCache {
+            metadata: CacheMetadata::current(),
+            modules: (0..200)
+                .map(|i| {
+                    let module = CachedModule {
+                        hash: format!("{}-{}", n, i),
+                        refs: HashSet::from([format!("ref_{}", i)]),
+                        visibility: CachedVisibility::default(),
+                        doc_hidden: false,
+                        includes: Vec::new(),
+                        mod_decls: HashMap::new(),
+                        ref_kinds: HashMap::new(),
+                        test_mods: BTreeSet::new(),
+                        cfg_mods: Vec::new(),
+                    };
+                    (format!("mod_{}", i), module)
+                })
+                .collect(),
+        };
+
+        std::thread::scope(|scope| {
+            for n in 0..8 {
+                let (dir, cache) = (&dir, cache_with(n));
+                scope.spawn(move || {
+                    for _ in 0..5 {
+                        save_cache(dir, &cache).unwrap();
+                        assert_eq!(load_cache(dir).unwrap().modules.len(), 200);
+                    }
+                });
+            }
+        });
+
+        // One writer's cache, never a mix
+        let loaded = load_cache(&dir).unwrap();
+        let writer = loaded.modules["mod_0"]
+            .hash
+            .split('-')
+            .next()
+            .unwrap()
+            .to_string();
+        assert!(loaded
+            .modules
+            .values()
+            .all(|m| m.hash.starts_with(&format!("{}-", writer))));
+        assert!(dir.join(".deadmod/cache.lock").exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_load_cache_not_found() {
         let dir = create_temp_dir("not_found");
@@ -468,6 +1025,41 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_parse_modes_skip_cache() {
+        let dir = create_temp_dir("parse_modes");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        let main_rs = dir.join("src/main.rs");
+        let utils_rs = dir.join("src/utils.rs");
+        fs::write(&main_rs, "pub mod utils; fn main() {}").unwrap();
+        fs::write(&utils_rs, "").unwrap();
+        let files = vec![main_rs, utils_rs];
+
+        let modules = |mode| {
+            let mods = parse_crate(&dir, &files, mode, &Progress::none()).unwrap();
+            let mut mods: Vec<_> = mods
+                .into_iter()
+                .map(|(name, info)| {
+                    (
+                        name,
+                        info.is_potentially_external(),
+                        info.refs.into_iter().collect::<BTreeSet<_>>(),
+                    )
+                })
+                .collect();
+            mods.sort();
+            mods
+        };
+
+        let fast = modules(ParseMode::Fast);
+        assert_eq!(fast, modules(ParseMode::Uncached));
+        assert!(load_cache(&dir).is_none());
+        assert_eq!(fast, modules(ParseMode::Cached));
+        assert!(load_cache(&dir).is_some());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_incremental_parse_cache_hit() {
         let dir = create_temp_dir("cache_hit");
@@ -491,6 +1083,40 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_warm_cache_reports_reuse() {
+        let dir = create_temp_dir("warm");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        let main_rs = dir.join("src/main.rs");
+        let utils_rs = dir.join("src/utils.rs");
+        fs::write(&main_rs, "mod utils; fn main() {}").unwrap();
+        fs::write(&utils_rs, "pub fn helper() {}").unwrap();
+        let files = vec![main_rs, utils_rs.clone()];
+
+        let cold = warm_cache(&dir, &files).unwrap();
+        assert_eq!(
+            cold,
+            WarmStats {
+                modules: 2,
+                cached: 2,
+                reused: 0
+            }
+        );
+
+        fs::write(&utils_rs, "pub fn helper() { todo!() }").unwrap();
+        let warm = warm_cache(&dir, &files).unwrap();
+        assert_eq!(
+            warm,
+            WarmStats {
+                modules: 2,
+                cached: 2,
+                reused: 1
+            }
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_incremental_parse_cache_invalidation() {
         let dir = create_temp_dir("cache_invalidation");
@@ -555,7 +1181,7 @@ mod tests {
 
         save_cache(&dir, &cache).unwrap();
 
-        let cache_path = dir.join(".deadmod/cache.json");
+        let cache_path = dir.join(".deadmod/cache.bin");
         assert!(cache_path.exists());
 
         fs::remove_dir_all(&dir).ok();
@@ -595,6 +1221,11 @@ mod tests {
                 refs: HashSet::new(),
                 visibility: CachedVisibility::default(),
                 doc_hidden: false,
+                includes: Vec::new(),
+                mod_decls: HashMap::new(),
+                ref_kinds: HashMap::new(),
+                test_mods: BTreeSet::new(),
+                cfg_mods: Vec::new(),
             },
         );
         save_cache(&dir, &cache1).unwrap();
@@ -611,6 +1242,11 @@ mod tests {
                 refs: HashSet::new(),
                 visibility: CachedVisibility::default(),
                 doc_hidden: false,
+                includes: Vec::new(),
+                mod_decls: HashMap::new(),
+                ref_kinds: HashMap::new(),
+                test_mods: BTreeSet::new(),
+                cfg_mods: Vec::new(),
             },
         );
         save_cache(&dir, &cache2).unwrap();
@@ -641,15 +1277,34 @@ mod tests {
                 refs,
                 visibility: CachedVisibility::default(),
                 doc_hidden: false,
+                includes: Vec::new(),
+                mod_decls: HashMap::new(),
+                ref_kinds: HashMap::new(),
+                test_mods: BTreeSet::new(),
+                cfg_mods: Vec::new(),
             },
         );
-        save_cache(&dir, &cache).unwrap();
+        save_cache_as(&dir, &cache, CacheFormat::Json).unwrap();
 
         // Read raw JSON and verify it's parseable
         let cache_path = dir.join(".deadmod/cache.json");
         let content = fs::read_to_string(&cache_path).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&content).unwrap();
         assert!(parsed.is_object());
+        assert_eq!(
+            load_cache_as(&dir, CacheFormat::Json)
+                .unwrap()
+                .modules
+                .len(),
+            1
+        );
+
+        // The binary cache is a separate file
+        assert!(load_cache(&dir).is_none());
+        save_cache(&dir, &cache).unwrap();
+        let binary = fs::metadata(dir.join(".deadmod/cache.bin")).unwrap().len();
+        assert!(binary < content.len() as u64);
+        assert_eq!(load_cache(&dir).unwrap().modules["test"].refs.len(), 2);
 
         fs::remove_dir_all(&dir).ok();
     }
@@ -664,9 +1319,36 @@ mod tests {
         fs::write(deadmod_dir.join("cache.json"), "{ not valid json ").unwrap();
 
         // Should return None, not panic
-        let result = load_cache(&dir);
+        let result = load_cache_as(&dir, CacheFormat::Json);
         assert!(result.is_none());
 
+        // Same for a truncated binary cache
+        let mut bytes = encode_binary(&DeadmodCache {
+            metadata: CacheMetadata::current(),
+            modules: HashMap::from([(
+                "main".to_string(),
+                CachedModule {
+                    hash: "abc".to_string(),
+                    refs: HashSet::from(["utils".to_string()]),
+                    visibility: CachedVisibility::default(),
+                    doc_hidden: false,
+                    includes: Vec::new(),
+                    mod_decls: HashMap::new(),
+                    ref_kinds: HashMap::new(),
+                    test_mods: BTreeSet::new(),
+                    cfg_mods: Vec::new(),
+                },
+            )]),
+        })
+        .unwrap();
+        bytes.truncate(bytes.len() - 4);
+        fs::write(deadmod_dir.join("cache.bin"), &bytes).unwrap();
+        assert!(load_cache(&dir).is_none());
+        let err = read_cache_as(&dir, CacheFormat::Binary).unwrap_err();
+        assert!(
+            matches!(err, DeadmodError::CacheCorrupted { ref path, .. } if path.ends_with(".deadmod/cache.bin"))
+        );
+
         fs::remove_dir_all(&dir).ok();
     }
 
@@ -676,12 +1358,13 @@ mod tests {
         let deadmod_dir = dir.join(".deadmod");
         fs::create_dir_all(&deadmod_dir).unwrap();
 
-        // Write empty file
+        // Write empty files
         fs::write(deadmod_dir.join("cache.json"), "").unwrap();
+        fs::write(deadmod_dir.join("cache.bin"), "").unwrap();
 
         // Should return None, not panic
-        let result = load_cache(&dir);
-        assert!(result.is_none());
+        assert!(load_cache_as(&dir, CacheFormat::Json).is_none());
+        assert!(load_cache(&dir).is_none());
 
         fs::remove_dir_all(&dir).ok();
     }
@@ -704,6 +1387,11 @@ mod tests {
                     refs: HashSet::new(),
                     visibility: CachedVisibility::default(),
                     doc_hidden: false,
+                    includes: Vec::new(),
+                    mod_decls: HashMap::new(),
+                    ref_kinds: HashMap::new(),
+                    test_mods: BTreeSet::new(),
+                    cfg_mods: Vec::new(),
                 },
             );
             save_cache(&dir, &cache).unwrap();
@@ -737,6 +1425,11 @@ mod tests {
                     refs,
                     visibility: CachedVisibility::default(),
                     doc_hidden: false,
+                    includes: Vec::new(),
+                    mod_decls: HashMap::new(),
+                    ref_kinds: HashMap::new(),
+                    test_mods: BTreeSet::new(),
+                    cfg_mods: Vec::new(),
                 },
             );
         }
@@ -768,6 +1461,11 @@ mod tests {
                 refs,
                 visibility: CachedVisibility::default(),
                 doc_hidden: false,
+                includes: Vec::new(),
+                mod_decls: HashMap::new(),
+                ref_kinds: HashMap::new(),
+                test_mods: BTreeSet::new(),
+                cfg_mods: Vec::new(),
             },
         );
 
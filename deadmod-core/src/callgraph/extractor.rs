@@ -9,13 +9,14 @@
 //! NASA-grade resilience: handles malformed AST gracefully.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::path::Path;
 use syn::{
-    visit::Visit, File, ImplItem, Item, ItemFn, ItemImpl, ItemMod, ItemTrait, TraitItem,
-    Visibility,
+    visit::Visit, Attribute, Block, Expr, File, ImplItem, Item, ItemFn, ItemImpl, ItemMod,
+    ItemTrait, Pat, Signature, TraitItem, Visibility,
 };
 
-use crate::common::visibility_str;
+use crate::common::{has_cfg_attr, keep_annotation, visibility_str, Confidence, FnMetrics, Keep};
 
 /// Information about a function definition.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +33,22 @@ pub struct FunctionDef {
     pub parent_type: Option<String>,
     /// Visibility
     pub visibility: String,
+    /// Whether the function has #[cfg] or #[cfg_attr] attributes
+    #[serde(default)]
+    pub has_cfg: bool,
+    /// `#[deadmod::keep]` details, if the function is kept on purpose
+    #[serde(default)]
+    pub keep: Option<Keep>,
+    /// Confidence that the function is dead (set for unreachable functions)
+    #[serde(default)]
+    pub confidence: Confidence,
+    /// Body size, parameter count and estimated complexity
+    #[serde(default)]
+    pub metrics: FnMetrics,
+    /// Capitalized paths named in the body (`MAX_LEN`, `Light::Red`,
+    /// `Self::Empty`), candidates for constants and enum variants it uses
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub item_refs: Vec<String>,
 }
 
 /// AST visitor that extracts all function definitions.
@@ -58,25 +75,94 @@ impl FunctionExtractor {
         }
     }
 
-    fn push_fn(&mut self, name: &str, vis: &Visibility, is_method: bool, parent_type: Option<String>) {
-        let full_path = self.build_full_path(name);
+    fn push_fn(
+        &mut self,
+        sig: &Signature,
+        body: Option<&Block>,
+        vis: &Visibility,
+        is_method: bool,
+        parent_type: Option<String>,
+        attrs: &[Attribute],
+    ) {
+        let name = sig.ident.to_string();
+        let full_path = self.build_full_path(&name);
         self.results.push(FunctionDef {
-            name: name.to_string(),
+            name,
             full_path,
             file: self.file_path.clone(),
             is_method,
             parent_type,
             visibility: visibility_str(vis).to_string(),
+            has_cfg: has_cfg_attr(attrs),
+            keep: keep_annotation(attrs),
+            confidence: Confidence::default(),
+            metrics: FnMetrics::of(sig, body),
+            item_refs: body.map(item_refs).unwrap_or_default(),
         });
     }
 }
 
+/// Sorted capitalized paths in `body`, skipping nested fns.
+fn item_refs(body: &Block) -> Vec<String> {
+    let mut collector = ItemRefCollector {
+        refs: BTreeSet::new(),
+    };
+    collector.visit_block(body);
+    collector.refs.into_iter().collect()
+}
+
+struct ItemRefCollector {
+    refs: BTreeSet<String>,
+}
+
+impl ItemRefCollector {
+    fn add(&mut self, path: &syn::Path) {
+        let capitalized = path.segments.last().is_some_and(|s| {
+            s.ident
+                .to_string()
+                .starts_with(|c: char| c.is_ascii_uppercase())
+        });
+        if capitalized {
+            let segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+            self.refs.insert(segments.join("::"));
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ItemRefCollector {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Path(p) => self.add(&p.path),
+            Expr::Struct(s) => self.add(&s.path),
+            _ => {}
+        }
+        syn::visit::visit_expr(self, expr);
+    }
+
+    fn visit_pat(&mut self, pat: &'ast Pat) {
+        match pat {
+            Pat::Path(p) => self.add(&p.path),
+            Pat::TupleStruct(p) => self.add(&p.path),
+            Pat::Struct(p) => self.add(&p.path),
+            _ => {}
+        }
+        syn::visit::visit_pat(self, pat);
+    }
+
+    fn visit_item_fn(&mut self, _: &'ast ItemFn) {}
+}
+
 impl<'ast> Visit<'ast> for FunctionExtractor {
     fn visit_item(&mut self, item: &'ast Item) {
         match item {
             // Free functions
-            Item::Fn(ItemFn { sig, vis, .. }) => {
-                self.push_fn(&sig.ident.to_string(), vis, false, None);
+            Item::Fn(ItemFn {
+                sig,
+                vis,
+                attrs,
+                block,
+            }) => {
+                self.push_fn(sig, Some(block), vis, false, None, attrs);
             }
 
             // Impl blocks
@@ -105,10 +191,12 @@ impl<'ast> Visit<'ast> for FunctionExtractor {
                             matches!(arg, syn::FnArg::Receiver(_))
                         });
                         self.push_fn(
-                            &method.sig.ident.to_string(),
+                            &method.sig,
+                            Some(&method.block),
                             &method.vis,
                             is_method,
                             Some(type_name.clone()),
+                            &method.attrs,
                         );
                     }
                 }
@@ -122,7 +210,14 @@ impl<'ast> Visit<'ast> for FunctionExtractor {
 
                 for trait_item in items {
                     if let TraitItem::Fn(method) = trait_item {
-                        self.push_fn(&method.sig.ident.to_string(), vis, true, None);
+                        self.push_fn(
+                            &method.sig,
+                            method.default.as_ref(),
+                            vis,
+                            true,
+                            None,
+                            &method.attrs,
+                        );
                     }
                 }
 
@@ -246,6 +341,20 @@ mod inner {
         assert_eq!(result[0].full_path, "inner::nested_fn");
     }
 
+    #[test]
+    fn test_extract_item_refs() {
+        let content = r#"
+fn f(l: Light) -> usize {
+    let n = match l { Light::Red => MAX, Self::Off(_) => 0, _ => local };
+    fn nested() -> usize { HIDDEN }
+    n
+}
+"#;
+        let result = extract_callgraph_functions(&PathBuf::from("test.rs"), content);
+        assert_eq!(result[0].item_refs, vec!["Light::Red", "MAX", "Self::Off"]);
+        assert_eq!(result[1].item_refs, vec!["HIDDEN"]);
+    }
+
     #[test]
     fn test_malformed_resilient() {
         let content = "fn broken(";
@@ -8,6 +8,11 @@
 //! - DOT format export for Graphviz visualization
 //! - JSON export for programmatic analysis
 //! - Dead function detection (unreachable from entry points)
+//! - Call chain queries between two functions ([`CallGraph::paths_between`])
+//! - Folded-stacks export for flamegraph tools ([`CallGraph::to_folded_stacks`])
+//! - Recursion groups, topological order and dominators
+//!   ([`CallGraph::recursive_groups`], [`CallGraph::topological_order`],
+//!   [`CallGraph::dominators`])
 //!
 //! # Performance Characteristics
 //!
@@ -15,14 +20,35 @@
 //! - Reachability: O(|F| + |E|) BFS traversal via `GraphTraversal` trait
 //! - Entry points: O(|F|) single pass
 //! - Analysis caching: O(1) after first call via `OnceCell`
-
+//!
+//! [`CallGraph::analyze`] returns owned results, cloning every unreachable
+//! [`FunctionDef`]; [`CallGraph::analyze_ref`] borrows them from the graph
+//! instead, for embedders that keep many graphs alive.
+//!
+//! Calls are stored in a petgraph [`StableDiGraph`] whose node indices are
+//! the interned [`Symbol`]s of full paths (see [`Interner`]), so indices
+//! never shift and graph algorithms come from petgraph instead of being
+//! reimplemented here. [`CallGraph::edges`], [`CallGraph::callees`] and
+//! [`CallGraph::callers`] resolve symbols back to paths.
+
+use petgraph::algo::{dominators::simple_fast, tarjan_scc, toposort};
+use petgraph::stable_graph::{NodeIndex, StableDiGraph};
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+use petgraph::Direction;
 use serde::Serialize;
 use std::cell::OnceCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
 
 use super::extractor::FunctionDef;
+use super::path_resolver::{ModulePathContext, SymbolTable};
 use super::usage::CallUsageResult;
-use crate::common::GraphTraversal;
+use crate::common::{
+    Confidence, ConfidenceSignals, FnMetrics, GraphTraversal, Interner, Resolution, Symbol,
+};
+
+/// Frame under which unreachable functions are stacked in folded output.
+pub const FOLDED_DEAD_ROOT: &str = "[dead]";
 
 // ============================================================================
 // Typed JSON Structures (compile-time validation, easier refactoring)
@@ -37,8 +63,13 @@ pub struct VisualizerNode {
     pub file: String,
     pub module: String,
     pub dead: bool,
+    /// Confidence for dead nodes (omitted for live ones)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Confidence>,
     pub visibility: String,
     pub is_method: bool,
+    /// Body size, parameter count and estimated complexity
+    pub metrics: FnMetrics,
 }
 
 /// An edge in the visualizer JSON output.
@@ -66,21 +97,62 @@ pub struct VisualizerGraph {
     pub stats: VisualizerStats,
 }
 
+impl VisualizerGraph {
+    /// Keep only the functions whose full path is in `keep`, and the calls
+    /// between them.
+    ///
+    /// Dead flags stay as computed on the whole graph; ids are renumbered
+    /// and stats recounted.
+    pub fn retain(&mut self, keep: &HashSet<String>) {
+        let mut new_ids: HashMap<usize, usize> = HashMap::new();
+        self.nodes.retain(|node| keep.contains(&node.full_path));
+        for (id, node) in self.nodes.iter_mut().enumerate() {
+            new_ids.insert(node.id, id);
+            node.id = id;
+        }
+        self.edges.retain_mut(
+            |edge| match (new_ids.get(&edge.from), new_ids.get(&edge.to)) {
+                (Some(&from), Some(&to)) => {
+                    (edge.from, edge.to) = (from, to);
+                    true
+                }
+                _ => false,
+            },
+        );
+
+        self.modules = self.nodes.iter().map(|n| n.module.clone()).collect();
+        self.modules.sort();
+        self.modules.dedup();
+        self.stats = VisualizerStats {
+            total_functions: self.nodes.len(),
+            total_edges: self.edges.len(),
+            dead_functions: self.nodes.iter().filter(|n| n.dead).count(),
+            total_modules: self.modules.len(),
+        };
+    }
+}
+
 // ============================================================================
 // Core Call Graph
 // ============================================================================
 
+/// Upper bound on partial paths expanded by [`CallGraph::paths_between`],
+/// so dense graphs cannot make a query run away.
+const MAX_PATH_EXPANSIONS: usize = 100_000;
+
 /// A call graph representing function relationships.
 #[derive(Debug)]
 pub struct CallGraph {
     /// Map from full_path to FunctionDef
     pub nodes: HashMap<String, FunctionDef>,
-    /// Edges: (caller, callee) pairs
-    pub edges: HashSet<(String, String)>,
-    /// Forward adjacency list: caller -> [callees] for O(1) neighbor lookup
-    pub adjacency: HashMap<String, Vec<String>>,
-    /// Reverse edges for finding callers
-    pub reverse_edges: HashMap<String, HashSet<String>>,
+    /// Full paths of the functions edges refer to
+    symbols: Interner,
+    /// Calls (caller -> callee); node `i` holds the symbol of index `i`
+    calls: StableDiGraph<Symbol, ()>,
+    /// How call edges were matched (confidence signal)
+    resolution: Resolution,
+    /// Identifiers mentioned inside macro invocations (confidence signal)
+    macro_mentions: HashSet<String>,
     /// Cached analysis result (computed once, reused)
     cached_analysis: OnceCell<CallGraphAnalysis>,
 }
@@ -106,19 +178,71 @@ pub struct CallGraphAnalysis {
     pub stats: CallGraphStats,
 }
 
+/// An unreachable function, borrowed from its [`CallGraph`].
+#[derive(Debug, Clone, Copy)]
+pub struct DeadFunctionRef<'a> {
+    /// The function as extracted (its own `confidence` is unset)
+    pub def: &'a FunctionDef,
+    /// Confidence that the function is really dead
+    pub confidence: Confidence,
+}
+
+impl DeadFunctionRef<'_> {
+    /// The function with its confidence set, as in [`CallGraphAnalysis`].
+    pub fn to_owned_def(&self) -> FunctionDef {
+        FunctionDef {
+            confidence: self.confidence,
+            ..self.def.clone()
+        }
+    }
+}
+
+/// Result of call graph analysis, borrowed from the graph.
+#[derive(Debug, Clone)]
+pub struct CallGraphAnalysisRef<'a> {
+    /// Functions unreachable from any entry point
+    pub unreachable: Vec<DeadFunctionRef<'a>>,
+    /// Entry points (main, test functions, pub functions)
+    pub entry_points: Vec<&'a str>,
+    /// Statistics
+    pub stats: CallGraphStats,
+}
+
+impl CallGraphAnalysisRef<'_> {
+    /// Clone the borrowed functions into an owned [`CallGraphAnalysis`].
+    pub fn to_owned_analysis(&self) -> CallGraphAnalysis {
+        CallGraphAnalysis {
+            unreachable: self
+                .unreachable
+                .iter()
+                .map(DeadFunctionRef::to_owned_def)
+                .collect(),
+            entry_points: self
+                .entry_points
+                .iter()
+                .map(|path| path.to_string())
+                .collect(),
+            stats: self.stats.clone(),
+        }
+    }
+}
+
 // ============================================================================
 // GraphTraversal Implementation (shared BFS logic)
 // ============================================================================
 
 impl GraphTraversal for CallGraph {
-    type Node = String;
+    type Node = Symbol;
 
-    fn neighbors(&self, node: &String) -> Vec<String> {
-        self.adjacency.get(node).cloned().unwrap_or_default()
+    fn neighbors(&self, node: &Symbol) -> Vec<Symbol> {
+        self.calls
+            .neighbors(node_index(*node))
+            .map(|n| self.calls[n])
+            .collect()
     }
 
-    fn contains_node(&self, node: &String) -> bool {
-        self.nodes.contains_key(node)
+    fn contains_node(&self, node: &Symbol) -> bool {
+        node.index() < self.symbols.len() && self.nodes.contains_key(self.symbols.resolve(*node))
     }
 }
 
@@ -127,81 +251,112 @@ impl CallGraph {
     pub fn new() -> Self {
         Self {
             nodes: HashMap::new(),
-            edges: HashSet::new(),
-            adjacency: HashMap::new(),
-            reverse_edges: HashMap::new(),
+            symbols: Interner::new(),
+            calls: StableDiGraph::default(),
+            resolution: Resolution::Name,
+            macro_mentions: HashSet::new(),
             cached_analysis: OnceCell::new(),
         }
     }
 
+    /// Provide identifiers found inside macro invocations.
+    ///
+    /// Unreachable functions whose name is mentioned in a macro are reported
+    /// with lower confidence. Call before [`CallGraph::analyze`], whose result
+    /// is cached.
+    pub fn with_macro_mentions(mut self, mentions: HashSet<String>) -> Self {
+        self.macro_mentions = mentions;
+        self
+    }
+
     /// Build a call graph from function definitions and call usages.
     ///
     /// If `resolved_calls` are present in the usage result (from `extract_call_usages_resolved`),
-    /// uses semantic path resolution for accurate edge matching. Otherwise falls back to
-    /// name-based heuristic matching.
-    pub fn build(
+    /// they are looked up in a [`SymbolTable`] of `functions`. Otherwise falls back to
+    /// name-based heuristic matching. Use [`CallGraph::build_with_symbols`] to
+    /// also follow `pub use` re-exports.
+    pub fn build(functions: &[FunctionDef], usages: &HashMap<String, CallUsageResult>) -> Self {
+        Self::build_with_symbols(functions, usages, &SymbolTable::build(functions))
+    }
+
+    /// Build a call graph, resolving `resolved_calls` through `symbols`.
+    ///
+    /// A resolved call that names a crate definition gets edges to exactly
+    /// that definition. Calls the table cannot resolve (method calls,
+    /// glob imports) fall back to matching the function name.
+    pub fn build_with_symbols(
         functions: &[FunctionDef],
         usages: &HashMap<String, CallUsageResult>,
+        symbols: &SymbolTable,
     ) -> Self {
         let mut graph = Self::new();
+        graph.symbols = Interner::with_capacity(functions.len());
+        graph.calls = StableDiGraph::with_capacity(functions.len(), 0);
 
         // Register all function nodes
-        for func in functions {
-            graph.nodes.insert(func.full_path.clone(), func.clone());
-        }
+        let ids: Vec<Symbol> = functions
+            .iter()
+            .map(|func| {
+                graph.nodes.insert(func.full_path.clone(), func.clone());
+                graph.intern(&func.full_path)
+            })
+            .collect();
 
         // Build name -> full_path index for efficient lookup
-        let mut name_index: HashMap<String, Vec<String>> = HashMap::new();
-        for func in functions {
-            name_index
-                .entry(func.name.clone())
-                .or_default()
-                .push(func.full_path.clone());
+        let mut name_index: HashMap<&str, Vec<Symbol>> = HashMap::new();
+        for (func, &id) in functions.iter().zip(&ids) {
+            name_index.entry(func.name.as_str()).or_default().push(id);
         }
 
         // Build path suffix index for resolved path matching
         // Maps path suffixes to full paths for efficient lookup
-        let mut suffix_index: HashMap<String, Vec<String>> = HashMap::new();
-        for func in functions {
+        let mut suffix_index: HashMap<String, Vec<Symbol>> = HashMap::new();
+        for (func, &id) in functions.iter().zip(&ids) {
             // Index by full path
             suffix_index
                 .entry(func.full_path.clone())
                 .or_default()
-                .push(func.full_path.clone());
+                .push(id);
 
             // Index by path without leading module (e.g., "handler::process" from "api::v1::handler::process")
             let parts: Vec<&str> = func.full_path.split("::").collect();
             for i in 1..parts.len() {
                 let suffix = parts[i..].join("::");
-                suffix_index
-                    .entry(suffix)
-                    .or_default()
-                    .push(func.full_path.clone());
+                suffix_index.entry(suffix).or_default().push(id);
             }
         }
 
         // Collect all node full_paths for fallback matching
-        let all_paths: Vec<String> = graph.nodes.keys().cloned().collect();
+        let mut all_paths: Vec<(Symbol, &str)> = functions
+            .iter()
+            .zip(&ids)
+            .map(|(func, &id)| (id, func.full_path.as_str()))
+            .collect();
+        all_paths.sort_unstable_by_key(|&(id, _)| id);
+        all_paths.dedup_by_key(|&mut (id, _)| id);
 
         // Add edges based on calls
-        for func in functions {
+        for (func, &caller) in functions.iter().zip(&ids) {
             if let Some(usage) = usages.get(&func.file) {
                 // Check if we have resolved paths (semantic resolution)
                 if !usage.resolved_calls.is_empty() {
-                    // Use resolved paths for accurate matching
+                    graph.resolution = Resolution::Path;
+                    let ctx = ModulePathContext::from_file_path(Path::new(&func.file));
+
                     for resolved in &usage.resolved_calls {
-                        // Try exact match first
-                        if let Some(targets) = suffix_index.get(resolved) {
-                            for target in targets {
-                                if target != &func.full_path {
-                                    graph.add_edge(&func.full_path, target);
+                        // Exact definition via the crate symbol table
+                        match symbols.resolve(resolved, &ctx) {
+                            Some(targets) => {
+                                for target in targets {
+                                    let callee = graph.intern(target);
+                                    graph.add_call(caller, callee);
                                 }
                             }
-                        } else {
-                            // Try suffix match for partial resolution
-                            for full_path in &all_paths {
-                                if full_path.ends_with(resolved) && full_path != &func.full_path {
-                                    graph.add_edge(&func.full_path, full_path);
+                            None => {
+                                // Unresolvable (method call, glob import): match by name
+                                let name = resolved.rsplit("::").next().unwrap_or(resolved);
+                                for &callee in name_index.get(name).into_iter().flatten() {
+                                    graph.add_call(caller, callee);
                                 }
                             }
                         }
@@ -210,12 +365,8 @@ impl CallGraph {
                     // Fallback: name-based heuristic matching (original behavior)
                     // Match simple name calls
                     for call_name in &usage.calls {
-                        if let Some(targets) = name_index.get(call_name) {
-                            for target in targets {
-                                if target != &func.full_path {
-                                    graph.add_edge(&func.full_path, target);
-                                }
-                            }
+                        for &callee in name_index.get(call_name.as_str()).into_iter().flatten() {
+                            graph.add_call(caller, callee);
                         }
                     }
 
@@ -223,19 +374,16 @@ impl CallGraph {
                     for qualified in &usage.qualified_calls {
                         // Try exact suffix match first (O(1))
                         if let Some(targets) = suffix_index.get(qualified) {
-                            for target in targets {
-                                if target != &func.full_path {
-                                    graph.add_edge(&func.full_path, target);
-                                }
+                            for &callee in targets {
+                                graph.add_call(caller, callee);
                             }
                         } else {
                             // Fallback: substring matching for partial matches
                             // This is O(n) but should be rare after suffix index lookup
-                            for full_path in &all_paths {
-                                if (full_path.ends_with(qualified) || qualified.ends_with(full_path))
-                                    && full_path != &func.full_path
+                            for &(callee, full_path) in &all_paths {
+                                if full_path.ends_with(qualified) || qualified.ends_with(full_path)
                                 {
-                                    graph.add_edge(&func.full_path, full_path);
+                                    graph.add_call(caller, callee);
                                 }
                             }
                         }
@@ -247,35 +395,128 @@ impl CallGraph {
         graph
     }
 
-    /// Add an edge from caller to callee.
-    ///
-    /// Optimized to minimize string allocations:
-    /// - Reuses cloned strings across edge, adjacency, and reverse_edges
-    /// - Early exits if edge already exists (no allocations on duplicate)
+    /// Symbol of a full path, adding its node on first sight.
+    fn intern(&mut self, path: &str) -> Symbol {
+        let sym = self.symbols.intern(path);
+        // Nodes are never removed, so the next node index is the count
+        if sym.index() == self.calls.node_count() {
+            self.calls.add_node(sym);
+        }
+        sym
+    }
+
+    /// Add an edge from caller to callee (full paths).
     fn add_edge(&mut self, caller: &str, callee: &str) {
-        // Clone once, reuse for all data structures
-        let caller_owned = caller.to_string();
-        let callee_owned = callee.to_string();
-
-        // Early exit if edge already exists (avoid further allocations)
-        if !self
-            .edges
-            .insert((caller_owned.clone(), callee_owned.clone()))
-        {
-            return;
+        let caller = self.intern(caller);
+        let callee = self.intern(callee);
+        self.insert_edge(caller, callee);
+    }
+
+    /// Add an edge for a call, unless the function calls itself.
+    fn add_call(&mut self, caller: Symbol, callee: Symbol) {
+        if caller != callee {
+            self.insert_edge(caller, callee);
+        }
+    }
+
+    /// Record an edge once; repeated calls add nothing.
+    fn insert_edge(&mut self, caller: Symbol, callee: Symbol) {
+        let (caller, callee) = (node_index(caller), node_index(callee));
+        if self.calls.find_edge(caller, callee).is_none() {
+            self.calls.add_edge(caller, callee, ());
+        }
+    }
+
+    /// Full path of a node.
+    fn path_of(&self, node: NodeIndex) -> &str {
+        self.symbols.resolve(self.calls[node])
+    }
+
+    /// Calls as `(caller, callee)` full paths, in no particular order.
+    pub fn edges(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.calls
+            .edge_references()
+            .map(|e| (self.path_of(e.source()), self.path_of(e.target())))
+    }
+
+    /// Whether `from` calls `to` (full paths).
+    pub fn has_edge(&self, from: &str, to: &str) -> bool {
+        match (self.symbols.get(from), self.symbols.get(to)) {
+            (Some(from), Some(to)) => self.calls.contains_edge(node_index(from), node_index(to)),
+            _ => false,
         }
+    }
+
+    /// Functions `path` calls, in no particular order.
+    pub fn callees(&self, path: &str) -> impl Iterator<Item = &str> + '_ {
+        self.adjacent(path, Direction::Outgoing)
+    }
+
+    /// Functions that call `path`, in no particular order.
+    pub fn callers(&self, path: &str) -> impl Iterator<Item = &str> + '_ {
+        self.adjacent(path, Direction::Incoming)
+    }
 
-        // Update adjacency list for forward traversal (reuse cloned strings)
-        self.adjacency
-            .entry(caller_owned.clone())
-            .or_default()
-            .push(callee_owned.clone());
+    fn adjacent(&self, path: &str, dir: Direction) -> impl Iterator<Item = &str> + '_ {
+        self.symbols
+            .get(path)
+            .into_iter()
+            .flat_map(move |sym| self.calls.neighbors_directed(node_index(sym), dir))
+            .map(|n| self.path_of(n))
+    }
+
+    /// Groups of mutually recursive functions (strongly connected components
+    /// with more than one member), each sorted, groups sorted by first path.
+    ///
+    /// Direct self-recursion is not recorded as an edge, so it never forms
+    /// a group.
+    pub fn recursive_groups(&self) -> Vec<Vec<String>> {
+        let mut groups: Vec<Vec<String>> = tarjan_scc(&self.calls)
+            .into_iter()
+            .filter(|scc| scc.len() > 1)
+            .map(|scc| {
+                let mut group: Vec<String> = scc
+                    .into_iter()
+                    .map(|n| self.path_of(n).to_string())
+                    .collect();
+                group.sort();
+                group
+            })
+            .collect();
+        groups.sort();
+        groups
+    }
+
+    /// Every function ordered so that callers come before their callees, or
+    /// `None` if the graph has recursion (see [`Self::recursive_groups`]).
+    pub fn topological_order(&self) -> Option<Vec<String>> {
+        let order = toposort(&self.calls, None).ok()?;
+        Some(
+            order
+                .into_iter()
+                .map(|n| self.path_of(n).to_string())
+                .collect(),
+        )
+    }
 
-        // Update reverse edges for finding callers (reuse cloned strings)
-        self.reverse_edges
-            .entry(callee_owned)
-            .or_default()
-            .insert(caller_owned);
+    /// Immediate dominator of every function reachable from `entry` (a full
+    /// path): the last function all call chains from `entry` to it pass
+    /// through. Removing a dominator's caller edges makes everything it
+    /// dominates unreachable from `entry`.
+    ///
+    /// `entry` itself has no entry; empty if `entry` is not in the graph.
+    pub fn dominators(&self, entry: &str) -> HashMap<String, String> {
+        let Some(root) = self.symbols.get(entry) else {
+            return HashMap::new();
+        };
+        let doms = simple_fast(&self.calls, node_index(root));
+        self.calls
+            .node_indices()
+            .filter_map(|n| {
+                let idom = doms.immediate_dominator(n)?;
+                Some((self.path_of(n).to_string(), self.path_of(idom).to_string()))
+            })
+            .collect()
     }
 
     /// Find all entry points in the graph.
@@ -284,15 +525,24 @@ impl CallGraph {
     /// - `main` function
     /// - `#[test]` functions
     /// - Public functions (could be called externally)
+    /// - `#[deadmod::keep]` functions
     ///
     /// Aliased as `entry_points()` for API consistency.
     pub fn find_entry_points(&self) -> Vec<String> {
+        self.entry_paths().into_iter().map(String::from).collect()
+    }
+
+    /// Full paths of the entry points, borrowed from the graph.
+    fn entry_paths(&self) -> Vec<&str> {
         self.nodes
             .iter()
             .filter(|(path, func)| {
-                func.name == "main" || path.contains("test") || func.visibility == "pub"
+                func.name == "main"
+                    || path.contains("test")
+                    || func.visibility == "pub"
+                    || func.keep.is_some()
             })
-            .map(|(path, _)| path.clone())
+            .map(|(path, _)| path.as_str())
             .collect()
     }
 
@@ -306,17 +556,31 @@ impl CallGraph {
     ///
     /// Uses the shared `GraphTraversal` trait for O(|V| + |E|) BFS.
     pub fn find_reachable(&self, entry_points: &[String]) -> HashSet<String> {
-        self.reachable_from(entry_points.iter().cloned())
+        self.reachable_paths(entry_points)
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    /// Full paths reachable from `entry_points`, borrowed from the graph.
+    fn reachable_paths(&self, entry_points: &[impl AsRef<str>]) -> HashSet<&str> {
+        let roots = entry_points
+            .iter()
+            .filter_map(|path| self.symbols.get(path.as_ref()));
+        self.reachable_from(roots)
+            .into_iter()
+            .map(|sym| self.symbols.resolve(sym))
+            .collect()
     }
 
     /// Find all unreachable functions.
     pub fn find_unreachable(&self) -> Vec<&FunctionDef> {
-        let entry_points = self.find_entry_points();
-        let reachable = self.find_reachable(&entry_points);
+        let entry_points = self.entry_paths();
+        let reachable = self.reachable_paths(&entry_points);
 
         self.nodes
             .values()
-            .filter(|func| !reachable.contains(&func.full_path))
+            .filter(|func| !reachable.contains(func.full_path.as_str()))
             .collect()
     }
 
@@ -328,16 +592,27 @@ impl CallGraph {
         self.cached_analysis.get_or_init(|| self.compute_analysis())
     }
 
-    /// Compute the analysis (internal, not cached).
-    fn compute_analysis(&self) -> CallGraphAnalysis {
-        let entry_points = self.find_entry_points();
-        let reachable = self.find_reachable(&entry_points);
+    /// Analyze the call graph without cloning its functions.
+    ///
+    /// Not cached: each call traverses the graph again.
+    pub fn analyze_ref(&self) -> CallGraphAnalysisRef<'_> {
+        let entry_points = self.entry_paths();
+        let reachable = self.reachable_paths(&entry_points);
 
-        let unreachable: Vec<FunctionDef> = self
+        let unreachable: Vec<DeadFunctionRef<'_>> = self
             .nodes
             .values()
-            .filter(|func| !reachable.contains(&func.full_path))
-            .cloned()
+            .filter(|func| !reachable.contains(func.full_path.as_str()))
+            .map(|func| DeadFunctionRef {
+                def: func,
+                confidence: ConfidenceSignals {
+                    visibility: &func.visibility,
+                    has_cfg: func.has_cfg,
+                    in_macro: self.macro_mentions.contains(&func.name),
+                    resolution: self.resolution,
+                }
+                .score(),
+            })
             .collect();
 
         // Compute maximum call depth from any entry point
@@ -345,27 +620,32 @@ impl CallGraph {
 
         let stats = CallGraphStats {
             total_functions: self.nodes.len(),
-            total_edges: self.edges.len(),
+            total_edges: self.edge_count(),
             entry_points: entry_points.len(),
             unreachable_functions: unreachable.len(),
             max_call_depth,
         };
 
-        CallGraphAnalysis {
+        CallGraphAnalysisRef {
             unreachable,
             entry_points,
             stats,
         }
     }
 
+    /// Compute the analysis (internal, not cached).
+    fn compute_analysis(&self) -> CallGraphAnalysis {
+        self.analyze_ref().to_owned_analysis()
+    }
+
     /// Compute the maximum call depth from entry points using BFS.
     ///
     /// Returns the longest path from any entry point to any reachable function.
     /// Uses BFS level tracking to find the maximum depth efficiently.
     ///
     /// Complexity: O(|V| + |E|) - single BFS traversal
-    fn compute_max_call_depth(&self, entry_points: &[String]) -> usize {
-        if entry_points.is_empty() || self.adjacency.is_empty() {
+    fn compute_max_call_depth(&self, entry_points: &[&str]) -> usize {
+        if entry_points.is_empty() || self.calls.edge_count() == 0 {
             return 0;
         }
 
@@ -373,12 +653,16 @@ impl CallGraph {
 
         // For each entry point, compute max depth via BFS with level tracking
         for start in entry_points {
-            if !self.nodes.contains_key(start) {
+            let Some(start) = self
+                .symbols
+                .get(start)
+                .filter(|sym| self.contains_node(sym))
+            else {
                 continue;
-            }
+            };
 
-            let mut visited: HashSet<&str> = HashSet::new();
-            let mut queue: std::collections::VecDeque<(&str, usize)> = std::collections::VecDeque::new();
+            let mut visited: HashSet<Symbol> = HashSet::new();
+            let mut queue: VecDeque<(Symbol, usize)> = VecDeque::new();
 
             visited.insert(start);
             queue.push_back((start, 0));
@@ -386,12 +670,9 @@ impl CallGraph {
             while let Some((node, depth)) = queue.pop_front() {
                 max_depth = max_depth.max(depth);
 
-                if let Some(callees) = self.adjacency.get(node) {
-                    for callee in callees {
-                        if !visited.contains(callee.as_str()) {
-                            visited.insert(callee);
-                            queue.push_back((callee, depth + 1));
-                        }
+                for callee in self.neighbors(&node) {
+                    if visited.insert(callee) {
+                        queue.push_back((callee, depth + 1));
                     }
                 }
             }
@@ -414,7 +695,7 @@ impl CallGraph {
                 })
             }).collect::<Vec<_>>(),
 
-            "edges": self.edges.iter().map(|(from, to)| {
+            "edges": self.edges().map(|(from, to)| {
                 serde_json::json!({
                     "from": from,
                     "to": to,
@@ -423,7 +704,7 @@ impl CallGraph {
 
             "stats": {
                 "total_functions": self.nodes.len(),
-                "total_edges": self.edges.len(),
+                "total_edges": self.edge_count(),
             }
         })
     }
@@ -431,7 +712,6 @@ impl CallGraph {
     /// Export the graph to visualizer-compatible JSON format.
     ///
     /// Uses typed `VisualizerGraph` struct for compile-time validation.
-    /// Leverages cached analysis to avoid redundant BFS traversals.
     ///
     /// Output format for PixiJS visualizer:
     /// ```json
@@ -447,22 +727,26 @@ impl CallGraph {
     /// Build a typed `VisualizerGraph` for export.
     ///
     /// Returns a strongly-typed struct that can be serialized to JSON.
-    /// Uses cached analysis to avoid redundant computations.
+    /// Uses [`CallGraph::analyze_ref`], so no function is cloned for it.
     pub fn to_visualizer_graph(&self) -> VisualizerGraph {
-        // Use cached analysis to get reachable set
-        let analysis = self.analyze();
-        let reachable_set = self.find_reachable(&analysis.entry_points);
-        let reachable: HashSet<&str> = analysis
-            .entry_points
+        let analysis = self.analyze_ref();
+        let mut reachable = self.reachable_paths(&analysis.entry_points);
+        reachable.extend(analysis.entry_points.iter().copied());
+        let confidence: HashMap<&str, Confidence> = analysis
+            .unreachable
             .iter()
-            .chain(reachable_set.iter())
-            .map(|s| s.as_str())
+            .map(|f| (f.def.full_path.as_str(), f.confidence))
             .collect();
 
-        // Build path -> numeric ID mapping
-        let paths: Vec<&String> = self.nodes.keys().collect();
-        let path_to_id: HashMap<&String, usize> =
-            paths.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+        // Build symbol -> numeric ID mapping, in path order so IDs are stable
+        let mut paths: Vec<&String> = self.nodes.keys().collect();
+        paths.sort_unstable();
+        let mut symbol_to_id: Vec<Option<usize>> = vec![None; self.symbols.len()];
+        for (i, path) in paths.iter().enumerate() {
+            if let Some(sym) = self.symbols.get(path) {
+                symbol_to_id[sym.index()] = Some(i);
+            }
+        }
 
         // Build typed nodes
         let nodes: Vec<VisualizerNode> = paths
@@ -485,22 +769,29 @@ impl CallGraph {
                     file: func.file.clone(),
                     module,
                     dead: is_dead,
+                    confidence: if is_dead {
+                        confidence.get(path.as_str()).copied()
+                    } else {
+                        None
+                    },
                     visibility: func.visibility.clone(),
                     is_method: func.is_method,
+                    metrics: func.metrics,
                 }
             })
             .collect();
 
         // Build typed edges
-        let edges: Vec<VisualizerEdge> = self
-            .edges
-            .iter()
-            .filter_map(|(from, to)| {
-                let from_id = *path_to_id.get(from)?;
-                let to_id = *path_to_id.get(to)?;
+        let mut edges: Vec<VisualizerEdge> = self
+            .calls
+            .edge_references()
+            .filter_map(|e| {
+                let from_id = symbol_to_id[e.source().index()]?;
+                let to_id = symbol_to_id[e.target().index()]?;
                 Some(VisualizerEdge { from: from_id, to: to_id })
             })
             .collect();
+        edges.sort_unstable_by_key(|e| (e.from, e.to));
 
         // Collect unique modules for clustering color palette
         let mut modules: Vec<String> = nodes.iter().map(|n| n.module.clone()).collect();
@@ -512,7 +803,7 @@ impl CallGraph {
         VisualizerGraph {
             stats: VisualizerStats {
                 total_functions: self.nodes.len(),
-                total_edges: self.edges.len(),
+                total_edges: self.edge_count(),
                 dead_functions: dead_count,
                 total_modules: modules.len(),
             },
@@ -522,6 +813,111 @@ impl CallGraph {
         }
     }
 
+    /// Resolve a user-supplied function name to full paths in the graph.
+    ///
+    /// An exact full path wins; otherwise every function whose simple name
+    /// equals `query`, or whose full path ends in `::query`, matches.
+    /// Results are sorted.
+    pub fn resolve_function(&self, query: &str) -> Vec<String> {
+        if self.nodes.contains_key(query) {
+            return vec![query.to_string()];
+        }
+
+        let suffix = format!("::{}", query);
+        let mut matches: Vec<String> = self
+            .nodes
+            .iter()
+            .filter(|(path, func)| func.name == query || path.ends_with(&suffix))
+            .map(|(path, _)| path.clone())
+            .collect();
+        matches.sort();
+        matches
+    }
+
+    /// Functions within `depth` calls of `focus` (a full path), callers and
+    /// callees alike (see [`crate::graph::subgraph`]).
+    pub fn neighborhood(&self, focus: &str, depth: usize) -> HashSet<String> {
+        crate::graph::subgraph(self.edges(), focus, depth)
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    /// A graph of just the functions in `keep` and the calls between them.
+    ///
+    /// Entry points and reachability are recomputed on the smaller graph,
+    /// so exports with dead flags should filter the whole graph's
+    /// [`VisualizerGraph`] instead ([`VisualizerGraph::retain`]).
+    pub fn restricted(&self, keep: &HashSet<String>) -> CallGraph {
+        let mut graph = CallGraph {
+            nodes: self
+                .nodes
+                .iter()
+                .filter(|(path, _)| keep.contains(*path))
+                .map(|(path, func)| (path.clone(), func.clone()))
+                .collect(),
+            resolution: self.resolution,
+            macro_mentions: self.macro_mentions.clone(),
+            ..CallGraph::new()
+        };
+        for (from, to) in self.edges() {
+            if keep.contains(from) && keep.contains(to) {
+                graph.add_edge(from, to);
+            }
+        }
+        graph
+    }
+
+    /// Find call chains from `from` to `to` (both full paths).
+    ///
+    /// Returns up to `max_paths` simple paths (no function repeated), shortest
+    /// first, each starting with `from` and ending with `to`. Breadth-first
+    /// search over partial paths keeps the shortest chains even when the
+    /// limit cuts the result short.
+    pub fn paths_between(&self, from: &str, to: &str, max_paths: usize) -> Vec<Vec<String>> {
+        let mut paths = Vec::new();
+        if max_paths == 0 || !self.nodes.contains_key(from) || !self.nodes.contains_key(to) {
+            return paths;
+        }
+        if from == to {
+            paths.push(vec![from.to_string()]);
+            return paths;
+        }
+
+        let mut queue: VecDeque<Vec<&str>> = VecDeque::from([vec![from]]);
+        let mut expansions = 0;
+
+        while let Some(path) = queue.pop_front() {
+            expansions += 1;
+            if expansions > MAX_PATH_EXPANSIONS {
+                break;
+            }
+
+            let last = path[path.len() - 1];
+            let mut callees: Vec<&str> = self.callees(last).collect();
+            callees.sort_unstable();
+            callees.dedup();
+
+            for callee in callees {
+                if path.contains(&callee) {
+                    continue;
+                }
+                let mut next = path.clone();
+                next.push(callee);
+                if callee == to {
+                    paths.push(next.into_iter().map(String::from).collect());
+                    if paths.len() >= max_paths {
+                        return paths;
+                    }
+                } else {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        paths
+    }
+
     /// Export the graph to DOT format for Graphviz.
     pub fn to_dot(&self) -> String {
         let mut dot = String::from("digraph CallGraph {\n");
@@ -530,38 +926,179 @@ impl CallGraph {
 
         // Add nodes
         for (path, func) in &self.nodes {
-            let color = if func.visibility == "pub" {
-                "lightblue"
-            } else {
-                "white"
-            };
-            let escaped_path = path.replace("::", "_").replace("<", "_").replace(">", "_");
-            // Safe truncation that respects UTF-8 character boundaries
-            let label = if func.name.chars().count() > 20 {
-                let truncated: String = func.name.chars().take(17).collect();
-                format!("{}...", truncated)
-            } else {
-                func.name.clone()
-            };
+            dot.push_str(&Self::dot_node(path, func));
+        }
+
+        dot.push('\n');
+
+        // Add edges
+        for (from, to) in self.edges() {
             dot.push_str(&format!(
-                "    {} [label=\"{}\" style=filled fillcolor={}];\n",
-                escaped_path, label, color
+                "    {} -> {};\n",
+                Self::dot_id(from),
+                Self::dot_id(to)
             ));
         }
 
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Export only the functions and calls on the given paths to DOT.
+    ///
+    /// Used with [`CallGraph::paths_between`] to render a call chain.
+    pub fn paths_to_dot(&self, paths: &[Vec<String>]) -> String {
+        let mut nodes: Vec<&str> = paths.iter().flatten().map(String::as_str).collect();
+        nodes.sort_unstable();
+        nodes.dedup();
+
+        let mut edges: Vec<(&str, &str)> = paths
+            .iter()
+            .flat_map(|p| p.windows(2).map(|w| (w[0].as_str(), w[1].as_str())))
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+
+        let mut dot = String::from("digraph CallPath {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=box, fontname=\"monospace\"];\n\n");
+
+        for path in nodes {
+            if let Some(func) = self.nodes.get(path) {
+                dot.push_str(&Self::dot_node(path, func));
+            }
+        }
+
         dot.push('\n');
 
-        // Add edges
-        for (from, to) in &self.edges {
-            let from_escaped = from.replace("::", "_").replace("<", "_").replace(">", "_");
-            let to_escaped = to.replace("::", "_").replace("<", "_").replace(">", "_");
-            dot.push_str(&format!("    {} -> {};\n", from_escaped, to_escaped));
+        for (from, to) in edges {
+            dot.push_str(&format!(
+                "    {} -> {};\n",
+                Self::dot_id(from),
+                Self::dot_id(to)
+            ));
         }
 
         dot.push_str("}\n");
         dot
     }
 
+    /// Export the graph as folded stacks for inferno or flamegraph.pl.
+    ///
+    /// Each function is one line: its shortest call chain from an entry point
+    /// (BFS order), frames separated by `;`, with a count of 1. A frame's
+    /// width is thus the number of functions first reached through it, and
+    /// the stack depth is the function's call depth. Unreachable functions
+    /// are stacked under a [`FOLDED_DEAD_ROOT`] frame, starting from the dead
+    /// functions no other dead function calls, so each dead subtree shows up
+    /// as one block beside the live graph.
+    pub fn to_folded_stacks(&self) -> String {
+        let mut entry_points = self.find_entry_points();
+        entry_points.sort();
+        let mut parent: HashMap<&str, Option<&str>> = HashMap::new();
+        self.bfs_parents(entry_points.iter().map(String::as_str), &mut parent);
+        let live: HashSet<&str> = parent.keys().copied().collect();
+
+        let mut dead: Vec<&str> = self
+            .nodes
+            .keys()
+            .map(String::as_str)
+            .filter(|path| !live.contains(path))
+            .collect();
+        dead.sort_unstable();
+        let called: HashSet<&str> = self
+            .edges()
+            .filter(|(from, _)| !live.contains(from))
+            .map(|(_, to)| to)
+            .collect();
+        self.bfs_parents(
+            dead.iter().copied().filter(|path| !called.contains(path)),
+            &mut parent,
+        );
+        // Dead cycles have no root; start from their first member
+        for path in &dead {
+            if !parent.contains_key(path) {
+                self.bfs_parents([*path], &mut parent);
+            }
+        }
+
+        let mut lines: Vec<String> = self
+            .nodes
+            .keys()
+            .map(|path| {
+                let mut frames = Vec::new();
+                let mut current = Some(path.as_str());
+                while let Some(frame) = current {
+                    frames.push(frame);
+                    current = parent.get(frame).copied().flatten();
+                }
+                if !live.contains(path.as_str()) {
+                    frames.push(FOLDED_DEAD_ROOT);
+                }
+                frames.reverse();
+                format!("{} 1\n", frames.join(";"))
+            })
+            .collect();
+        lines.sort();
+        lines.concat()
+    }
+
+    /// Breadth-first search from `roots` (in order), recording each newly
+    /// reached function's parent. Functions already in `parent` are skipped.
+    fn bfs_parents<'a>(
+        &'a self,
+        roots: impl IntoIterator<Item = &'a str>,
+        parent: &mut HashMap<&'a str, Option<&'a str>>,
+    ) {
+        let mut queue = VecDeque::new();
+        for root in roots {
+            if self.nodes.contains_key(root) && !parent.contains_key(root) {
+                parent.insert(root, None);
+                queue.push_back(root);
+            }
+        }
+
+        while let Some(node) = queue.pop_front() {
+            let mut callees: Vec<&str> = self.callees(node).collect();
+            callees.sort_unstable();
+            callees.dedup();
+
+            for callee in callees {
+                if self.nodes.contains_key(callee) && !parent.contains_key(callee) {
+                    parent.insert(callee, Some(node));
+                    queue.push_back(callee);
+                }
+            }
+        }
+    }
+
+    /// DOT identifier for a function path.
+    fn dot_id(path: &str) -> String {
+        path.replace("::", "_").replace(['<', '>'], "_")
+    }
+
+    /// DOT node statement for a function.
+    fn dot_node(path: &str, func: &FunctionDef) -> String {
+        let color = if func.visibility == "pub" {
+            "lightblue"
+        } else {
+            "white"
+        };
+        // Safe truncation that respects UTF-8 character boundaries
+        let label = if func.name.chars().count() > 20 {
+            let truncated: String = func.name.chars().take(17).collect();
+            format!("{}...", truncated)
+        } else {
+            func.name.clone()
+        };
+        format!(
+            "    {} [label=\"{}\" style=filled fillcolor={}];\n",
+            Self::dot_id(path),
+            label,
+            color
+        )
+    }
+
     /// Get the number of functions in the graph.
     pub fn function_count(&self) -> usize {
         self.nodes.len()
@@ -569,10 +1106,16 @@ impl CallGraph {
 
     /// Get the number of edges in the graph.
     pub fn edge_count(&self) -> usize {
-        self.edges.len()
+        self.calls.edge_count()
     }
 }
 
+/// Node of a symbol in [`CallGraph::calls`].
+#[inline]
+fn node_index(sym: Symbol) -> NodeIndex {
+    NodeIndex::new(sym.index())
+}
+
 impl Default for CallGraph {
     fn default() -> Self {
         Self::new()
@@ -583,9 +1126,10 @@ impl Clone for CallGraph {
     fn clone(&self) -> Self {
         Self {
             nodes: self.nodes.clone(),
-            edges: self.edges.clone(),
-            adjacency: self.adjacency.clone(),
-            reverse_edges: self.reverse_edges.clone(),
+            symbols: self.symbols.clone(),
+            calls: self.calls.clone(),
+            resolution: self.resolution,
+            macro_mentions: self.macro_mentions.clone(),
             cached_analysis: OnceCell::new(), // Don't clone cache, will be recomputed if needed
         }
     }
@@ -603,6 +1147,11 @@ mod tests {
             is_method: false,
             parent_type: None,
             visibility: vis.to_string(),
+            has_cfg: false,
+            keep: None,
+            confidence: Confidence::default(),
+            metrics: FnMetrics::default(),
+            item_refs: Vec::new(),
         }
     }
 
@@ -627,7 +1176,7 @@ mod tests {
 
         assert_eq!(graph.function_count(), 2);
         assert_eq!(graph.edge_count(), 1);
-        assert!(graph.edges.contains(&("main".to_string(), "helper".to_string())));
+        assert!(graph.has_edge("main", "helper"));
     }
 
     #[test]
@@ -675,6 +1224,33 @@ mod tests {
         assert!(!reachable.contains("unused"));
     }
 
+    #[test]
+    fn test_analyze_ref_borrows_functions() {
+        let functions = vec![
+            make_func("main", "main", "main.rs", "private"),
+            make_func("dead_code", "dead_code", "lib.rs", "private"),
+        ];
+
+        let graph = CallGraph::build(&functions, &HashMap::new());
+        let view = graph.analyze_ref();
+        assert_eq!(view.entry_points, vec!["main"]);
+        assert_eq!(view.unreachable.len(), 1);
+        assert!(std::ptr::eq(
+            view.unreachable[0].def,
+            &graph.nodes["dead_code"]
+        ));
+
+        let owned = graph.analyze();
+        assert_eq!(
+            owned.unreachable[0].confidence,
+            view.unreachable[0].confidence
+        );
+        assert_eq!(
+            owned.stats.unreachable_functions,
+            view.stats.unreachable_functions
+        );
+    }
+
     #[test]
     fn test_find_unreachable() {
         let functions = vec![
@@ -689,6 +1265,30 @@ mod tests {
         assert_eq!(unreachable[0].name, "dead_code");
     }
 
+    #[test]
+    fn test_unreachable_confidence() {
+        let functions = vec![
+            make_func("main", "main", "main.rs", "private"),
+            make_func("dead_code", "dead_code", "lib.rs", "private"),
+            make_func("quoted", "quoted", "lib.rs", "private"),
+        ];
+
+        let graph = CallGraph::build(&functions, &HashMap::new())
+            .with_macro_mentions(HashSet::from(["quoted".to_string()]));
+        let analysis = graph.analyze();
+
+        let confidence = |name: &str| {
+            analysis
+                .unreachable
+                .iter()
+                .find(|f| f.name == name)
+                .unwrap()
+                .confidence
+        };
+        assert_eq!(confidence("dead_code"), Confidence::High);
+        assert_eq!(confidence("quoted"), Confidence::Low);
+    }
+
     #[test]
     fn test_to_json() {
         let functions = vec![make_func("foo", "foo", "test.rs", "pub")];
@@ -700,6 +1300,36 @@ mod tests {
         assert!(json["stats"]["total_functions"].as_u64() == Some(1));
     }
 
+    #[test]
+    fn test_build_with_resolved_calls() {
+        use super::super::usage::extract_call_usages_resolved;
+        use std::path::PathBuf;
+
+        let files = [
+            ("src/main.rs", "use crate::db::query as q;\nfn main() { q(); let c = Cache; c.get(); }"),
+            ("src/db/mod.rs", "pub fn query() {}"),
+            ("src/cache.rs", "pub struct Cache;\nimpl Cache {\n    pub fn query(&self) {}\n    pub fn get(&self) {}\n}"),
+        ];
+        let mut functions = Vec::new();
+        let mut usages = HashMap::new();
+        for (path, content) in files {
+            let path = PathBuf::from(path);
+            functions.extend(super::super::extract_callgraph_functions(&path, content));
+            usages.insert(
+                path.display().to_string(),
+                extract_call_usages_resolved(&path, content),
+            );
+        }
+
+        let graph = CallGraph::build(&functions, &usages);
+        let callees: HashSet<&str> = graph.callees("main").collect();
+        // The aliased import resolves to db::query only, not Cache::query
+        assert!(callees.contains("query"));
+        assert!(!callees.contains("Cache::query"));
+        // Method calls fall back to name matching
+        assert!(callees.contains("Cache::get"));
+    }
+
     #[test]
     fn test_to_dot() {
         let functions = vec![
@@ -794,6 +1424,166 @@ mod tests {
         assert_eq!(graph.edge_count(), 0);
     }
 
+    /// Graph with exactly the given call edges (bypasses file-level matching).
+    fn graph_with_edges(edges: &[(&str, &str)]) -> CallGraph {
+        let mut graph = CallGraph::new();
+        for (from, to) in edges {
+            for name in [from, to] {
+                graph.nodes.insert(
+                    name.to_string(),
+                    make_func(name, name, "test.rs", "private"),
+                );
+            }
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_paths_between_shortest_first() {
+        let graph = graph_with_edges(&[
+            ("main", "run"),
+            ("run", "helper"),
+            ("main", "helper"),
+            ("helper", "run"), // cycle must not loop
+            ("run", "log"),
+        ]);
+
+        let paths = graph.paths_between("main", "helper", 10);
+        assert_eq!(
+            paths,
+            vec![
+                vec!["main".to_string(), "helper".to_string()],
+                vec!["main".to_string(), "run".to_string(), "helper".to_string()],
+            ]
+        );
+
+        assert_eq!(graph.paths_between("main", "helper", 1).len(), 1);
+        assert!(graph.paths_between("log", "main", 10).is_empty());
+        assert!(graph.paths_between("main", "missing", 10).is_empty());
+        assert_eq!(
+            graph.paths_between("run", "run", 10),
+            vec![vec!["run".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_graph_algorithms() {
+        let graph = graph_with_edges(&[
+            ("main", "parse"),
+            ("main", "run"),
+            ("parse", "expr"),
+            ("expr", "term"),
+            ("term", "expr"), // mutual recursion
+            ("run", "log"),
+            ("expr", "log"),
+        ]);
+
+        assert_eq!(
+            graph.recursive_groups(),
+            vec![vec!["expr".to_string(), "term".to_string()]]
+        );
+        assert_eq!(graph.topological_order(), None);
+
+        let doms = graph.dominators("main");
+        assert_eq!(doms["expr"], "parse");
+        assert_eq!(doms["term"], "expr");
+        assert_eq!(doms["log"], "main"); // reached via run and via parse
+        assert!(!doms.contains_key("main"));
+        assert!(graph.dominators("missing").is_empty());
+
+        let dag = graph_with_edges(&[("main", "a"), ("a", "b"), ("main", "b")]);
+        assert_eq!(dag.topological_order().unwrap(), ["main", "a", "b"]);
+        assert!(dag.recursive_groups().is_empty());
+    }
+
+    #[test]
+    fn test_to_folded_stacks() {
+        let graph = graph_with_edges(&[
+            ("main", "run"),
+            ("run", "helper"),
+            ("main", "helper"),
+            ("old_api", "old_helper"),
+            ("old_helper", "helper"), // dead code calling live code
+            ("loop_a", "loop_b"),
+            ("loop_b", "loop_a"),
+        ]);
+
+        let folded = graph.to_folded_stacks();
+        let lines: Vec<&str> = folded.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "[dead];loop_a 1",
+                "[dead];loop_a;loop_b 1",
+                "[dead];old_api 1",
+                "[dead];old_api;old_helper 1",
+                "main 1",
+                "main;helper 1",
+                "main;run 1",
+            ]
+        );
+        assert!(CallGraph::new().to_folded_stacks().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_function_and_paths_to_dot() {
+        let mut graph = graph_with_edges(&[("main", "utils::helper"), ("main", "other")]);
+        graph.nodes.get_mut("utils::helper").unwrap().name = "helper".to_string();
+
+        assert_eq!(graph.resolve_function("main"), vec!["main".to_string()]);
+        assert_eq!(
+            graph.resolve_function("helper"),
+            vec!["utils::helper".to_string()]
+        );
+        assert!(graph.resolve_function("nope").is_empty());
+
+        let paths = graph.paths_between("main", "utils::helper", 5);
+        let dot = graph.paths_to_dot(&paths);
+        assert!(dot.starts_with("digraph CallPath {"));
+        assert!(dot.contains("main -> utils_helper;"));
+        assert!(!dot.contains("other"));
+    }
+
+    #[test]
+    fn test_focused_exports() {
+        let graph = graph_with_edges(&[
+            ("main", "run"),
+            ("run", "helper"),
+            ("old_api", "old_helper"),
+        ]);
+
+        let keep = graph.neighborhood("run", 1);
+        let mut focused: Vec<&str> = keep.iter().map(String::as_str).collect();
+        focused.sort_unstable();
+        assert_eq!(focused, ["helper", "main", "run"]);
+
+        let restricted = graph.restricted(&graph.neighborhood("helper", 1));
+        assert_eq!(
+            (restricted.function_count(), restricted.edge_count()),
+            (2, 1)
+        );
+        assert!(!restricted.to_dot().contains("main"));
+
+        // Dead flags come from the whole graph
+        let mut viz = graph.to_visualizer_graph();
+        viz.retain(&graph.neighborhood("old_helper", 1));
+        assert_eq!(viz.nodes.len(), 2);
+        assert!(viz.nodes.iter().all(|n| n.dead && n.id < 2));
+        let edge = &viz.edges[0];
+        assert_eq!(
+            (
+                viz.nodes[edge.from].name.as_str(),
+                viz.nodes[edge.to].name.as_str()
+            ),
+            ("old_api", "old_helper")
+        );
+        assert_eq!(
+            (viz.stats.total_functions, viz.stats.dead_functions),
+            (2, 2)
+        );
+    }
+
     #[test]
     fn test_callgraph_cyclic_calls() {
         let functions = vec![
@@ -882,7 +1672,7 @@ mod tests {
         let graph = CallGraph::build(&functions, &usages);
 
         // Should resolve qualified call
-        assert!(graph.edges.contains(&("caller".to_string(), "module::target".to_string())));
+        assert!(graph.has_edge("caller", "module::target"));
     }
 
     #[test]
@@ -906,9 +1696,9 @@ mod tests {
         let graph = CallGraph::build(&functions, &usages);
 
         // Verify adjacency list is populated correctly
-        assert!(graph.adjacency.contains_key("a"));
-        let neighbors = graph.adjacency.get("a").unwrap();
-        assert!(neighbors.contains(&"b".to_string()) || neighbors.contains(&"c".to_string()));
+        let neighbors: Vec<&str> = graph.callees("a").collect();
+        assert!(!neighbors.is_empty());
+        assert!(neighbors.contains(&"b") || neighbors.contains(&"c"));
     }
 
     #[test]
@@ -931,8 +1721,8 @@ mod tests {
         let graph = CallGraph::build(&functions, &usages);
 
         // Check reverse edge exists
-        assert!(graph.reverse_edges.contains_key("callee"));
-        assert!(graph.reverse_edges["callee"].contains("caller"));
+        let callers: Vec<&str> = graph.callers("callee").collect();
+        assert_eq!(callers, ["caller"]);
     }
 
     #[test]
@@ -5,7 +5,7 @@
 //! - Track all function calls and method invocations
 //! - Build a directed graph of caller -> callee relationships
 //! - Find unreachable (dead) functions
-//! - Export to DOT (Graphviz) and JSON formats
+//! - Export to DOT (Graphviz), JSON and folded-stacks (flamegraph) formats
 //!
 //! # Architecture
 //!
@@ -71,12 +71,12 @@ pub mod usage;
 // Re-exports for convenience
 pub use extractor::{extract_callgraph_functions, FunctionDef};
 pub use graph::{
-    CallGraph, CallGraphAnalysis, CallGraphStats,
-    VisualizerEdge, VisualizerGraph, VisualizerNode, VisualizerStats,
+    CallGraph, CallGraphAnalysis, CallGraphAnalysisRef, CallGraphStats, DeadFunctionRef,
+    VisualizerEdge, VisualizerGraph, VisualizerNode, VisualizerStats, FOLDED_DEAD_ROOT,
 };
 pub use path_resolver::{
     collect_use_statements, resolve_call_full, resolve_call_path, segments_to_path,
-    ModulePathContext, ResolvedCall, UseMap,
+    ModulePathContext, ResolvedCall, SymbolTable, UseMap,
 };
 pub use usage::{extract_call_usages, extract_call_usages_resolved, CallUsageResult};
 
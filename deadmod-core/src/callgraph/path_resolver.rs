@@ -6,10 +6,17 @@
 //! - `self::router::Route::new()` → `crate::api::v1::router::Route::new`
 //!
 //! This enables accurate call graph edges instead of name-based heuristics.
+//! A crate-wide [`SymbolTable`] then maps resolved paths to the functions that
+//! define them, following `pub use` re-exports.
 
 use std::collections::HashMap;
 use std::path::Path;
-use syn::{File, Item, UseTree};
+use syn::{File, Item, UseTree, Visibility};
+
+use super::extractor::FunctionDef;
+
+/// Maximum re-export hops followed when resolving a path.
+const MAX_REEXPORT_HOPS: usize = 8;
 
 /// Module's position in the crate hierarchy.
 ///
@@ -269,6 +276,124 @@ pub fn segments_to_path(segments: &[String]) -> String {
     }
 }
 
+/// Crate-wide table of function definitions by module path.
+///
+/// Keys are crate-relative paths without `crate::` (`db::query`,
+/// `api::Client::new`), built from each function's file position and its
+/// in-file path. Values are the [`FunctionDef::full_path`]s defining them.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    /// Qualified path -> defining functions
+    defs: HashMap<String, Vec<String>>,
+    /// `pub use` re-export path -> target path segments
+    reexports: HashMap<String, Vec<String>>,
+}
+
+impl SymbolTable {
+    /// Index `functions` by their crate-relative module path.
+    pub fn build(functions: &[FunctionDef]) -> Self {
+        let mut table = Self::default();
+        for func in functions {
+            let ctx = ModulePathContext::from_file_path(Path::new(&func.file));
+            let mut segments = ctx.segments;
+            segments.extend(func.full_path.split("::").map(String::from));
+            let targets = table.defs.entry(segments_to_path(&segments)).or_default();
+            if !targets.contains(&func.full_path) {
+                targets.push(func.full_path.clone());
+            }
+        }
+        table
+    }
+
+    /// Record the `pub use` re-exports of a file, so `use crate::net::connect`
+    /// finds `net::tcp::connect` re-exported by `pub use tcp::connect`.
+    /// Glob re-exports are not recorded.
+    pub fn add_reexports(&mut self, ast: &File, ctx: &ModulePathContext) {
+        let mut exported = UseMap::new();
+        for item in &ast.items {
+            if let Item::Use(u) = item {
+                if !matches!(u.vis, Visibility::Inherited) {
+                    handle_use_tree(&u.tree, ctx, &mut exported, Vec::new());
+                }
+            }
+        }
+        for (name, target) in exported.map {
+            if name.ends_with("::*") {
+                continue;
+            }
+            let mut alias = ctx.segments.clone();
+            alias.push(name);
+            // A relative `pub use tcp::connect` names a child module
+            let target = match target.first() {
+                Some(first) if self.is_crate_module(first) || ctx.segments.is_empty() => target,
+                _ => ctx.segments.iter().cloned().chain(target).collect(),
+            };
+            self.reexports.insert(segments_to_path(&alias), target);
+        }
+    }
+
+    /// Parse `content` and record its `pub use` re-exports; unparsable files
+    /// are skipped.
+    pub fn add_file_reexports(&mut self, path: &Path, content: &str) {
+        if let Ok(ast) = syn::parse_file(content) {
+            self.add_reexports(&ast, &ModulePathContext::from_file_path(path));
+        }
+    }
+
+    /// Whether `name` is a top-level module or item of the crate.
+    fn is_crate_module(&self, name: &str) -> bool {
+        self.defs
+            .keys()
+            .any(|path| path.split("::").next() == Some(name))
+    }
+
+    /// Functions defined at `path` (as produced by [`resolve_call_path`])
+    /// called from the module `ctx`.
+    ///
+    /// Tries the path as given, then relative to `ctx` (an unimported
+    /// `Type::method`), following re-exports at each step. Returns `None` when
+    /// the path is not defined in the crate.
+    pub fn resolve(&self, path: &str, ctx: &ModulePathContext) -> Option<&[String]> {
+        let relative = if ctx.segments.is_empty() {
+            None
+        } else {
+            Some(format!("{}::{}", ctx.segments.join("::"), path))
+        };
+        std::iter::once(path.to_string())
+            .chain(relative)
+            .find_map(|candidate| self.lookup(candidate))
+    }
+
+    fn lookup(&self, mut path: String) -> Option<&[String]> {
+        for _ in 0..=MAX_REEXPORT_HOPS {
+            if let Some(defs) = self.defs.get(&path) {
+                return Some(defs);
+            }
+            // Replace the longest re-exported prefix and try again
+            let segments: Vec<&str> = path.split("::").collect();
+            let (target, rest) = (1..=segments.len()).rev().find_map(|len| {
+                self.reexports
+                    .get(&segments[..len].join("::"))
+                    .map(|target| (target, &segments[len..]))
+            })?;
+            let mut next = target.clone();
+            next.extend(rest.iter().map(|s| s.to_string()));
+            path = segments_to_path(&next);
+        }
+        None
+    }
+
+    /// Number of indexed definitions.
+    pub fn len(&self) -> usize {
+        self.defs.len()
+    }
+
+    /// Whether the table has no definitions.
+    pub fn is_empty(&self) -> bool {
+        self.defs.is_empty()
+    }
+}
+
 /// Full resolution result for a call.
 #[derive(Debug, Clone)]
 pub struct ResolvedCall {
@@ -460,6 +585,78 @@ mod tests {
         assert_eq!(usemap.resolve("C"), Some(&vec!["db".to_string(), "client".to_string()]));
     }
 
+    fn def(name: &str, full_path: &str, file: &str) -> FunctionDef {
+        FunctionDef {
+            name: name.to_string(),
+            full_path: full_path.to_string(),
+            file: file.to_string(),
+            is_method: false,
+            parent_type: None,
+            visibility: "pub".to_string(),
+            has_cfg: false,
+            keep: None,
+            confidence: Default::default(),
+            metrics: Default::default(),
+            item_refs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_symbol_table_resolves_definitions() {
+        let table = SymbolTable::build(&[
+            def("query", "query", "src/db/mod.rs"),
+            def("query", "query", "src/cache.rs"),
+            def("new", "Client::new", "src/api/client.rs"),
+        ]);
+        assert_eq!(table.len(), 3);
+
+        let root = ModulePathContext::default();
+        assert_eq!(
+            table.resolve("db::query", &root),
+            Some(&["query".to_string()][..])
+        );
+        assert!(table.resolve("api::client::Client::new", &root).is_some());
+        assert!(table.resolve("std::mem::drop", &root).is_none());
+
+        // Unimported `Client::new()` inside its own module
+        let ctx = ModulePathContext::from_file_path(Path::new("src/api/client.rs"));
+        assert!(table.resolve("Client::new", &ctx).is_some());
+    }
+
+    #[test]
+    fn test_symbol_table_follows_reexports() {
+        let mut table = SymbolTable::build(&[
+            def("connect", "connect", "src/net/tcp.rs"),
+            def("new", "Client::new", "src/net/client.rs"),
+        ]);
+        let ast =
+            syn::parse_file("pub use tcp::connect;\npub use self::client::Client as NetClient;")
+                .unwrap();
+        table.add_reexports(
+            &ast,
+            &ModulePathContext::from_file_path(Path::new("src/net/mod.rs")),
+        );
+        let ast =
+            syn::parse_file("pub use crate::net::connect as open;\nuse crate::net::tcp;").unwrap();
+        table.add_reexports(
+            &ast,
+            &ModulePathContext::from_file_path(Path::new("src/lib.rs")),
+        );
+
+        let root = ModulePathContext::default();
+        assert_eq!(
+            table.resolve("net::connect", &root),
+            Some(&["connect".to_string()][..])
+        );
+        assert_eq!(
+            table.resolve("open", &root),
+            Some(&["connect".to_string()][..])
+        );
+        assert!(table.resolve("net::NetClient::new", &root).is_some());
+        // Private `use` is not a re-export
+        assert!(table.resolve("tcp::connect", &root).is_none());
+    }
+
     #[test]
     fn test_resolve_full_metadata() {
         let ctx = ModulePathContext::from_file_path(Path::new("src/api/handler.rs"));
@@ -4,7 +4,10 @@
 //! - Direct calls: `foo()`
 //! - Method calls: `x.method()`
 //! - Qualified calls: `Type::method()`
-//! - Path references: `module::function`
+//! - Path references: `module::function`, including functions handed to
+//!   executors and combinators (`tokio::spawn(worker)`, `.then(process)`)
+//! - Async code: calls inside `async` blocks, awaited or `?`-chained
+//!   calls, and `select!` arms (see [`crate::common::function_args`])
 //!
 //! With path resolution enabled, calls are resolved to fully qualified paths
 //! based on `use` imports and module context.
@@ -13,9 +16,13 @@
 
 use std::collections::HashSet;
 use std::path::Path;
-use syn::{visit::Visit, Expr, File};
+use syn::{visit::Visit, Expr, File, Macro};
 
-use super::path_resolver::{collect_use_statements, resolve_call_path, segments_to_path, ModulePathContext};
+use crate::common::visit_macro_args;
+
+use super::path_resolver::{
+    collect_use_statements, resolve_call_path, segments_to_path, ModulePathContext,
+};
 
 /// Result of call extraction from a file.
 #[derive(Debug, Clone, Default)]
@@ -94,6 +101,18 @@ impl<'ast> Visit<'ast> for CallUsageExtractor {
 
         syn::visit::visit_expr(self, expr);
     }
+
+    // Calls and function references inside macro arguments
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac, |v, found| {
+            if found.is_method {
+                v.calls.insert(found.name());
+            } else if found.is_call || found.name().starts_with(char::is_lowercase) {
+                v.record_path(&found.path);
+            }
+        });
+        syn::visit::visit_macro(self, mac);
+    }
 }
 
 /// Extract all function calls from file content.
@@ -231,6 +250,51 @@ fn main() {
         assert!(result.calls.contains("collect"));
     }
 
+    #[test]
+    fn test_extract_async_calls() {
+        let content = r#"
+use crate::jobs::worker;
+
+async fn serve(rx: Receiver) -> Result<()> {
+    tokio::spawn(async move { handle_conn().await });
+    tokio::spawn(worker);
+    let mut futs = FuturesUnordered::new();
+    futs.push(fetch(1));
+    stream.then(process_item).for_each(Self::record).await;
+    load_config().await?.validate()?;
+    tokio::select! {
+        Some(msg) = rx.recv() => on_msg(msg).await,
+        _ = shutdown_signal() => {}
+    }
+    tokio::join!(task_a(), task_b());
+    Ok(())
+}
+"#;
+        let path = PathBuf::from("src/server.rs");
+        let result = extract_call_usages(&path, content);
+        for name in [
+            "handle_conn",
+            "worker",
+            "fetch",
+            "process_item",
+            "record",
+            "load_config",
+            "validate",
+            "recv",
+            "on_msg",
+            "shutdown_signal",
+            "task_a",
+            "task_b",
+        ] {
+            assert!(result.calls.contains(name), "{} not found", name);
+        }
+        assert!(result.qualified_calls.contains("Self::record"));
+        assert!(!result.calls.contains("await"));
+
+        let resolved = extract_call_usages_resolved(&path, content);
+        assert!(resolved.resolved_calls.contains("jobs::worker"));
+    }
+
     #[test]
     fn test_malformed_resilient() {
         let content = "fn main() { broken(";
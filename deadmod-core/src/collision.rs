@@ -0,0 +1,354 @@
+//! Module-name collision handling.
+//!
+//! Module references are recorded by name, so `src/net/utils.rs` and
+//! `src/db/utils.rs` would overwrite each other in a name-keyed map and
+//! silently corrupt the graph. [`key_modules`] keeps the name for unique
+//! modules and rekeys colliding files by module path (`net::utils`,
+//! `db::utils`), pointing each reference at the candidate its referrer can
+//! see: a child module first, then a sibling, then a top-level module.
+//!
+//! Files that still share a module path (`foo.rs` next to `foo/mod.rs`, or
+//! two crates scanned as one) cannot be told apart. They are keyed by file
+//! path, flagged with [`ModuleInfo::collision`] and listed by
+//! [`find_collisions`] instead of getting a dead/alive verdict.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::graph::qualified_module_path;
+use crate::parse::{
+    is_target_root_file, normalize_lexically, path_to_normalized_string, EdgeKind, ModuleInfo,
+    CRATE_ROOT_PATH,
+};
+
+/// Several files that map to the same module path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleCollision {
+    /// The shared module path (`net::utils`)
+    pub module_path: String,
+    /// The colliding files, sorted
+    pub files: Vec<PathBuf>,
+}
+
+/// Build the module map from parsed modules, disambiguating shared names.
+///
+/// `mod.rs` files are named after their directory, like the `mod`
+/// declarations that reference them. Test, bench and example targets are
+/// never referenced by name and are keyed by module path (`tests::it`), the
+/// name [`find_root_modules`](crate::root::find_root_modules) registers them
+/// under.
+pub fn key_modules(modules: impl IntoIterator<Item = ModuleInfo>) -> HashMap<String, ModuleInfo> {
+    let mut modules: Vec<ModuleInfo> = modules.into_iter().collect();
+    let mut keys: Vec<String> = modules
+        .iter()
+        .map(|info| {
+            if is_target_root_file(&info.path) {
+                info.module_path.clone()
+            } else {
+                ref_name(info).to_string()
+            }
+        })
+        .collect();
+
+    let mut by_name: HashMap<String, Vec<usize>> = HashMap::with_capacity(modules.len());
+    for (i, key) in keys.iter().enumerate() {
+        by_name.entry(key.clone()).or_default().push(i);
+    }
+    by_name.retain(|_, indices| indices.len() > 1);
+
+    // Unique names keep their key; shared ones switch to their module path
+    for indices in by_name.values() {
+        for &i in indices {
+            if !keeps_name(&modules[i]) {
+                keys[i] = modules[i].module_path.clone();
+            }
+        }
+    }
+
+    // Whatever still collides is keyed by file path and gets no verdict
+    let mut counts: HashMap<&str, usize> = HashMap::with_capacity(keys.len());
+    for key in &keys {
+        *counts.entry(key.as_str()).or_insert(0) += 1;
+    }
+    let residual: Vec<usize> = (0..keys.len())
+        .filter(|&i| counts[keys[i].as_str()] > 1)
+        .collect();
+    for i in residual {
+        keys[i] = path_to_normalized_string(&modules[i].path);
+        modules[i].collision = true;
+    }
+
+    // Point references to shared names at the module the referrer sees
+    let shared: HashMap<String, Vec<(String, String)>> = by_name
+        .into_iter()
+        .map(|(name, indices)| {
+            let candidates = indices
+                .into_iter()
+                .map(|i| (keys[i].clone(), modules[i].module_path.clone()))
+                .collect();
+            (name, candidates)
+        })
+        .collect();
+    for info in &mut modules {
+        let names: HashSet<String> = info
+            .refs
+            .iter()
+            .chain(info.ref_kinds.keys())
+            .filter(|r| shared.contains_key(*r))
+            .cloned()
+            .collect();
+        for name in names {
+            let was_edge = info.refs.remove(&name);
+            let kinds = info.ref_kinds.remove(&name);
+            let candidates = &shared[&name];
+            let paths: Vec<&str> = candidates.iter().map(|(_, path)| path.as_str()).collect();
+            let targets = resolve_ref(&info.module_path, &name, &paths);
+            for (key, path) in candidates {
+                if targets.contains(&path.as_str()) {
+                    if was_edge {
+                        info.refs.insert(key.clone());
+                    }
+                    if let Some(kinds) = &kinds {
+                        info.ref_kinds.entry(key.clone()).or_default().extend(kinds);
+                    }
+                }
+            }
+        }
+    }
+
+    // cfg-gated `#[path]` declarations name a file, not a module: each
+    // alternative is a `mod` edge to the file it loads
+    let by_file: HashMap<PathBuf, String> = modules
+        .iter()
+        .zip(&keys)
+        .map(|(info, key)| (normalize_lexically(&info.path), key.clone()))
+        .collect();
+    for info in &mut modules {
+        let targets: Vec<String> = info
+            .cfg_mods
+            .iter()
+            .filter_map(|decl| by_file.get(&decl.target_file(&info.path)?))
+            .cloned()
+            .collect();
+        for key in targets {
+            info.ref_kinds
+                .entry(key.clone())
+                .or_default()
+                .insert(EdgeKind::Mod);
+            info.refs.insert(key);
+        }
+    }
+
+    keys.into_iter().zip(modules).collect()
+}
+
+/// Modules flagged by [`key_modules`] as indistinguishable, grouped by
+/// module path (crate-qualified in workspace mode).
+pub fn find_collisions(mods: &HashMap<String, ModuleInfo>) -> Vec<ModuleCollision> {
+    let mut groups: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for (name, info) in mods.iter().filter(|(_, info)| info.collision) {
+        groups
+            .entry(qualified_module_path(name, info))
+            .or_default()
+            .push(info.path.clone());
+    }
+
+    groups
+        .into_iter()
+        .map(|(module_path, mut files)| {
+            files.sort();
+            ModuleCollision { module_path, files }
+        })
+        .collect()
+}
+
+/// Name other modules use to reference `info`: its stem, or the directory
+/// name for `mod.rs`.
+fn ref_name(info: &ModuleInfo) -> &str {
+    if info.name == "mod" && info.module_path != CRATE_ROOT_PATH {
+        info.module_path
+            .rsplit("::")
+            .next()
+            .unwrap_or(&info.module_path)
+    } else {
+        &info.name
+    }
+}
+
+/// Crate and binary roots keep their name: entry points are looked up by it.
+fn keeps_name(info: &ModuleInfo) -> bool {
+    info.module_path == CRATE_ROOT_PATH || info.module_path == format!("bin::{}", info.name)
+}
+
+/// Candidate module paths a reference to `name` from `from` can mean:
+/// a child, then a sibling, then a top-level module; all of them if none fits.
+fn resolve_ref<'a>(from: &str, name: &str, paths: &[&'a str]) -> Vec<&'a str> {
+    let parent = from.rsplit_once("::").map(|(parent, _)| parent);
+    let scoped = |scope: Option<&str>| match scope {
+        Some(scope) if scope != CRATE_ROOT_PATH => format!("{}::{}", scope, name),
+        _ => name.to_string(),
+    };
+
+    for want in [scoped(Some(from)), scoped(parent), name.to_string()] {
+        let hits: Vec<&str> = paths.iter().copied().filter(|p| *p == want).collect();
+        if !hits.is_empty() {
+            return hits;
+        }
+    }
+    paths.to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module(path: &str, refs: &[&str]) -> ModuleInfo {
+        let mut info = ModuleInfo::new(PathBuf::from(path));
+        info.refs = refs.iter().map(|r| r.to_string()).collect();
+        info
+    }
+
+    #[test]
+    fn test_key_modules_unique_names_unchanged() {
+        let mods = key_modules(vec![
+            module("src/lib.rs", &["net", "db"]),
+            module("src/net.rs", &[]),
+            module("src/db/mod.rs", &[]),
+        ]);
+        assert!(mods.contains_key("lib"));
+        assert!(mods.contains_key("net"));
+        assert!(mods.contains_key("db"));
+        assert!(find_collisions(&mods).is_empty());
+    }
+
+    #[test]
+    fn test_key_modules_disambiguates_shared_names() {
+        let mods = key_modules(vec![
+            module("src/lib.rs", &["net", "db"]),
+            module("src/net/mod.rs", &["utils"]),
+            module("src/net/utils.rs", &[]),
+            module("src/db/mod.rs", &[]),
+            module("src/db/utils.rs", &[]),
+        ]);
+
+        assert!(mods.contains_key("net::utils"));
+        assert!(mods.contains_key("db::utils"));
+        assert!(!mods.contains_key("utils"));
+        assert_eq!(mods["net"].refs, ["net::utils".to_string()].into());
+        assert!(find_collisions(&mods).is_empty());
+    }
+
+    #[test]
+    fn test_key_modules_prefers_sibling_then_top_level() {
+        let mods = key_modules(vec![
+            module("src/lib.rs", &["config"]),
+            module("src/config.rs", &[]),
+            module("src/app/config.rs", &[]),
+            module("src/app/run.rs", &["config"]),
+            module("src/db/pool.rs", &["config"]),
+        ]);
+
+        assert!(mods["lib"].refs.contains("config"));
+        assert!(mods["run"].refs.contains("app::config"));
+        assert!(mods["pool"].refs.contains("config"));
+        assert!(!mods["pool"].refs.contains("app::config"));
+    }
+
+    #[test]
+    fn test_key_modules_keeps_root_names() {
+        let mods = key_modules(vec![
+            module("src/main.rs", &[]),
+            module("src/cli/main.rs", &[]),
+            module("src/bin/tool.rs", &[]),
+            module("src/tools/tool.rs", &[]),
+        ]);
+        assert!(mods.contains_key("main"));
+        assert!(mods.contains_key("cli::main"));
+        assert!(mods.contains_key("tool"));
+        assert!(mods.contains_key("tools::tool"));
+    }
+
+    #[test]
+    fn test_key_modules_keys_targets_by_module_path() {
+        let mods = key_modules(vec![
+            module("src/lib.rs", &["net"]),
+            module("src/net.rs", &[]),
+            module("tests/net.rs", &["common"]),
+            module("tests/common/mod.rs", &[]),
+            module("examples/demo/main.rs", &[]),
+        ]);
+        assert!(mods.contains_key("net"));
+        assert!(mods.contains_key("tests::net"));
+        assert!(mods.contains_key("examples::demo"));
+        assert_eq!(mods["tests::net"].refs, ["common".to_string()].into());
+        assert!(find_collisions(&mods).is_empty());
+    }
+
+    #[test]
+    fn test_key_modules_reports_residual_collisions() {
+        let mods = key_modules(vec![
+            module("src/lib.rs", &["net"]),
+            module("src/net.rs", &[]),
+            module("src/net/mod.rs", &[]),
+        ]);
+
+        assert!(mods.contains_key("src/net.rs"));
+        assert!(mods.contains_key("src/net/mod.rs"));
+        assert_eq!(mods["lib"].refs.len(), 2);
+
+        let collisions = find_collisions(&mods);
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].module_path, "net");
+        assert_eq!(
+            collisions[0].files,
+            vec![PathBuf::from("src/net/mod.rs"), PathBuf::from("src/net.rs")]
+        );
+    }
+
+    #[test]
+    fn test_key_modules_links_cfg_path_alternatives() {
+        let mut lib = ModuleInfo::new(PathBuf::from("src/lib.rs"));
+        crate::parse::extract_dependencies(
+            r#"
+#[cfg(windows)]
+#[path = "sys/windows.rs"]
+mod sys;
+#[cfg(not(windows))]
+#[cfg(feature = "posix")]
+#[path = "./sys/../sys/posix.rs"]
+mod sys;
+#[cfg(unix)]
+mod unix;
+mod always;
+"#,
+            &mut lib,
+        )
+        .unwrap();
+        let cfgs: Vec<(&str, &str)> = lib
+            .cfg_mods
+            .iter()
+            .map(|d| (d.name.as_str(), d.cfg.as_str()))
+            .collect();
+        assert_eq!(
+            cfgs,
+            [
+                ("sys", "windows"),
+                ("sys", "all(not (windows), feature = \"posix\")"),
+                ("unix", "unix")
+            ]
+        );
+
+        let mods = key_modules(vec![
+            lib,
+            module("src/sys/windows.rs", &[]),
+            module("src/sys/posix.rs", &[]),
+            module("src/unix.rs", &[]),
+        ]);
+        let mut refs: Vec<&str> = mods["lib"].refs.iter().map(String::as_str).collect();
+        refs.sort_unstable();
+        assert_eq!(refs, ["always", "posix", "sys", "unix", "windows"]);
+        assert!(mods["lib"].ref_kinds["posix"].contains(&EdgeKind::Mod));
+    }
+}
@@ -0,0 +1,252 @@
+//! Combined export: module and function graphs linked to each other.
+//!
+//! `--export-combined` writes both graphs side by side. The links added here
+//! let visualizers and downstream tools move between levels:
+//!
+//! - `contains`: module node → function node defined in its file
+//! - `uses`: function node → constant or enum variant named in its body
+//!
+//! Constants and variants are listed once under `items`, with their dead
+//! status, so a dead constant can be traced back to the functions (and
+//! modules) still naming it.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use serde_json::{json, Value};
+
+use crate::callgraph::{CallGraph, FunctionDef};
+use crate::common::extract_macro_mentions;
+use crate::constants::{extract_const_usage, extract_constants, ConstDef, ConstGraph};
+use crate::enums::{extract_variant_usage, extract_variants, EnumGraph, EnumVariantDef};
+use crate::graph::module_graph_to_visualizer_json;
+use crate::parse::ModuleInfo;
+
+/// Build the combined export for `--export-combined`.
+///
+/// ```json
+/// {
+///   "module_graph": { ... },
+///   "function_graph": { ... },
+///   "items": [{ "id": 0, "kind": "const", "name": "MAX", "file": "src/lib.rs", "dead": false }],
+///   "links": [
+///     { "kind": "contains", "from": 0, "to": 3 },
+///     { "kind": "uses", "from": 3, "to": 0 }
+///   ],
+///   "stats": { "items": 1, "contains": 1, "uses": 1 }
+/// }
+/// ```
+///
+/// `contains` links go from a module graph id to a function graph id,
+/// `uses` links from a function graph id to an `items` id. Uses are matched
+/// by name (`MAX`, `Light::Red`, `Self::Red`), preferring items declared in
+/// the function's own file.
+pub fn combined_graph_json(
+    mods: &HashMap<String, ModuleInfo>,
+    reachable: &HashSet<&str>,
+    call_graph: &CallGraph,
+) -> Value {
+    let module_graph = module_graph_to_visualizer_json(mods, reachable);
+    let function_graph = call_graph.to_visualizer_graph();
+
+    let mut constants = Vec::new();
+    let mut const_usages = Vec::new();
+    let mut variants = Vec::new();
+    let mut variant_usages = Vec::new();
+    let mut macro_mentions = HashSet::new();
+    for info in mods.values() {
+        if let Ok(content) = fs::read_to_string(&info.path) {
+            constants.extend(extract_constants(&info.path, &content));
+            const_usages.push(extract_const_usage(&info.path, &content));
+            variants.extend(extract_variants(&info.path, &content));
+            variant_usages.push(extract_variant_usage(&info.path, &content));
+            macro_mentions.extend(extract_macro_mentions(&content));
+        }
+    }
+    constants.sort_by(|a, b| (&a.file, &a.name).cmp(&(&b.file, &b.name)));
+    variants.sort_by(|a, b| (&a.file, &a.full_name).cmp(&(&b.file, &b.full_name)));
+
+    let dead_consts: HashSet<(String, String)> = ConstGraph::new(constants.clone(), &const_usages)
+        .with_macro_mentions(macro_mentions.clone())
+        .find_dead()
+        .into_iter()
+        .map(|d| (d.file, d.name))
+        .collect();
+    let dead_variants: HashSet<(String, String)> =
+        EnumGraph::new(variants.clone(), &variant_usages)
+            .with_macro_mentions(macro_mentions)
+            .find_dead()
+            .into_iter()
+            .map(|d| (d.file, d.full_name))
+            .collect();
+
+    let mut items: Vec<Value> = Vec::with_capacity(constants.len() + variants.len());
+    for c in &constants {
+        items.push(json!({
+            "id": items.len(),
+            "kind": if c.is_static { "static" } else { "const" },
+            "name": match &c.impl_type {
+                Some(ty) => format!("{}::{}", ty, c.name),
+                None => c.name.clone(),
+            },
+            "file": c.file,
+            "dead": dead_consts.contains(&(c.file.clone(), c.name.clone())),
+        }));
+    }
+    for v in &variants {
+        items.push(json!({
+            "id": items.len(),
+            "kind": "variant",
+            "name": v.full_name,
+            "file": v.file,
+            "dead": dead_variants.contains(&(v.file.clone(), v.full_name.clone())),
+        }));
+    }
+
+    let module_by_file: HashMap<&str, u64> = module_graph["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|node| Some((node["file"].as_str()?, node["id"].as_u64()?)))
+        .collect();
+
+    let mut links = Vec::new();
+    let mut contains = 0;
+    let mut uses = 0;
+    for node in &function_graph.nodes {
+        if let Some(&module_id) = module_by_file.get(node.file.as_str()) {
+            links.push(json!({ "kind": "contains", "from": module_id, "to": node.id }));
+            contains += 1;
+        }
+
+        let Some(func) = call_graph.nodes.get(&node.full_path) else {
+            continue;
+        };
+        let mut targets: Vec<usize> = func
+            .item_refs
+            .iter()
+            .flat_map(|path| used_items(func, path, &constants, &variants))
+            .collect();
+        targets.sort_unstable();
+        targets.dedup();
+        for item_id in targets {
+            links.push(json!({ "kind": "uses", "from": node.id, "to": item_id }));
+            uses += 1;
+        }
+    }
+
+    json!({
+        "stats": {
+            "items": items.len(),
+            "contains": contains,
+            "uses": uses,
+        },
+        "module_graph": module_graph,
+        "function_graph": function_graph,
+        "items": items,
+        "links": links,
+    })
+}
+
+/// Item ids (constants first, then variants) that `path` in `func` names.
+fn used_items(
+    func: &FunctionDef,
+    path: &str,
+    constants: &[ConstDef],
+    variants: &[EnumVariantDef],
+) -> Vec<usize> {
+    let segments: Vec<&str> = path.split("::").collect();
+    let Some((&name, prefix)) = segments.split_last() else {
+        return Vec::new();
+    };
+    // `Self::X` names an item of the impl's own type
+    let owner = prefix.last().map(|&p| match (p, &func.parent_type) {
+        ("Self", Some(ty)) => ty.as_str(),
+        _ => p,
+    });
+    let owner_is_type = owner.is_some_and(|o| o.starts_with(|c: char| c.is_ascii_uppercase()));
+
+    let const_hits = constants.iter().enumerate().filter(|(_, c)| {
+        c.name == name
+            && match owner {
+                Some(ty) if owner_is_type => c.impl_type.as_deref() == Some(ty),
+                _ => c.impl_type.is_none(),
+            }
+    });
+    let variant_hits = variants
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| v.variant_name == name && owner == Some(v.enum_name.as_str()))
+        .map(|(i, v)| (constants.len() + i, &v.file));
+
+    let hits: Vec<(usize, &String)> = const_hits
+        .map(|(i, c)| (i, &c.file))
+        .chain(variant_hits)
+        .collect();
+    let local: Vec<usize> = hits
+        .iter()
+        .filter(|(_, file)| **file == func.file)
+        .map(|(i, _)| *i)
+        .collect();
+    if local.is_empty() {
+        hits.into_iter().map(|(i, _)| i).collect()
+    } else {
+        local
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::callgraph::{extract_call_usages, extract_callgraph_functions};
+    use crate::graph::{build_graph, reachable_from_roots};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_combined_graph_links() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_combined_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let lib = dir.join("lib.rs");
+        let content = "\
+const LIMIT: usize = 3;
+const UNUSED: usize = 4;
+enum Light { Red, Green }
+
+pub fn limit(l: Light) -> usize {
+    match l { Light::Red => LIMIT, _ => 0 }
+}
+";
+        fs::write(&lib, content).unwrap();
+
+        let mut mods = HashMap::new();
+        mods.insert("lib".to_string(), ModuleInfo::new(PathBuf::from(&lib)));
+        let reachable = reachable_from_roots(&build_graph(&mods), ["lib"]);
+
+        let functions = extract_callgraph_functions(&lib, content);
+        let usage_map = HashMap::from([(
+            lib.display().to_string(),
+            extract_call_usages(&lib, content),
+        )]);
+        let call_graph = CallGraph::build(&functions, &usage_map);
+
+        let json = combined_graph_json(&mods, &reachable, &call_graph);
+        let names: Vec<&str> = json["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["LIMIT", "UNUSED", "Light::Green", "Light::Red"]);
+        assert_eq!(json["items"][1]["dead"], true);
+        assert_eq!(json["items"][0]["dead"], false);
+
+        let links = json["links"].as_array().unwrap();
+        assert!(links.contains(&json!({ "kind": "contains", "from": 0, "to": 0 })));
+        assert!(links.contains(&json!({ "kind": "uses", "from": 0, "to": 0 })));
+        assert!(links.contains(&json!({ "kind": "uses", "from": 0, "to": 3 })));
+        assert_eq!(json["stats"]["uses"], 2);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
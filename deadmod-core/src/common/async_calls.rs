@@ -0,0 +1,168 @@
+//! Calls made through async plumbing.
+//!
+//! Async code often hands a function to an executor or combinator instead
+//! of calling it, and the runtime calls it later:
+//! - `tokio::spawn(worker)`, `task::spawn_blocking(compute)`
+//! - `stream.then(process_item)`, `.for_each(Self::handle)`,
+//!   `.map_err(log_error)`
+//!
+//! A visitor that only records call expressions never sees these uses.
+//! [`function_args`] picks out the arguments that name a function, so
+//! extractors can count them as calls. Iterator adapters (`.map(parse)`)
+//! pass functions the same way and are covered too.
+//!
+//! `select!` bodies (`v = rx.recv() => handle(v).await,`) are not
+//! comma-separated expressions; [`parse_select_arms`] parses them for
+//! [`visit_macro_args`](super::visit_macro_args), so their futures, guards
+//! and handlers are visited like ordinary code.
+//!
+//! The rest of async syntax (`.await`, `async move` blocks, `?` chains,
+//! futures pushed into a `FuturesUnordered`) is ordinary expression syntax
+//! that `syn::visit` already walks.
+
+use syn::parse::ParseStream;
+use syn::{Expr, Ident, Pat, Token};
+
+/// Arguments of a call or method call that name a function: a path whose
+/// last segment starts lowercase (`worker`, `Self::handle`,
+/// `handlers::index`).
+///
+/// Local variables look the same and are included, so a function sharing
+/// a name with a variable passed around stays live (conservative).
+pub fn function_args<'a>(
+    args: impl IntoIterator<Item = &'a Expr>,
+) -> impl Iterator<Item = &'a syn::Path> {
+    args.into_iter().filter_map(|arg| match arg {
+        Expr::Path(p) if p.qself.is_none() && names_function(&p.path) => Some(&p.path),
+        _ => None,
+    })
+}
+
+fn names_function(path: &syn::Path) -> bool {
+    path.segments.last().is_some_and(|seg| {
+        seg.ident != "self"
+            && seg
+                .ident
+                .to_string()
+                .starts_with(|c: char| c.is_lowercase() || c == '_')
+    })
+}
+
+/// Whether `path` names a `select!` macro (tokio, futures).
+pub(crate) fn is_select_macro(path: &syn::Path) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|seg| seg.ident == "select" || seg.ident == "select_biased")
+}
+
+/// Parse a `select!` body into the expressions it runs: each arm's future,
+/// guard and handler.
+///
+/// ```text
+/// biased;
+/// v = rx.recv(), if open => handle(v),
+/// _ = sleep(delay) => { tick().await }
+/// else => break,        // tokio
+/// complete => break,    // futures
+/// default => idle(),    // futures
+/// ```
+pub(crate) fn parse_select_arms(input: ParseStream) -> syn::Result<Vec<Expr>> {
+    let mut exprs = Vec::new();
+    if input.peek(Ident) && input.peek2(Token![;]) {
+        input.parse::<Ident>()?;
+        input.parse::<Token![;]>()?;
+    }
+
+    while !input.is_empty() {
+        if input.peek(Token![else]) && input.peek2(Token![=>]) {
+            input.parse::<Token![else]>()?;
+        } else if input.peek(Ident) && input.peek2(Token![=>]) {
+            input.parse::<Ident>()?;
+        } else {
+            Pat::parse_multi_with_leading_vert(input)?;
+            input.parse::<Token![=]>()?;
+            exprs.push(input.parse()?);
+            if input.peek(Token![,]) && input.peek2(Token![if]) {
+                input.parse::<Token![,]>()?;
+                input.parse::<Token![if]>()?;
+                exprs.push(input.parse()?);
+            }
+        }
+        input.parse::<Token![=>]>()?;
+        exprs.push(input.parse()?);
+        input.parse::<Option<Token![,]>>()?;
+    }
+    Ok(exprs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse::Parser;
+
+    fn paths(call: &str) -> Vec<String> {
+        let args: Vec<Expr> = match syn::parse_str::<Expr>(call).unwrap() {
+            Expr::Call(call) => call.args.into_iter().collect(),
+            Expr::MethodCall(call) => call.args.into_iter().collect(),
+            _ => unreachable!(),
+        };
+        function_args(&args)
+            .map(|path| {
+                path.segments
+                    .iter()
+                    .map(|s| s.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_function_args() {
+        assert_eq!(paths("tokio::spawn(worker)"), ["worker"]);
+        assert_eq!(paths("s.for_each(Self::handle)"), ["Self::handle"]);
+        assert_eq!(
+            paths("s.map_err(errors::log, Kind::Io, self, 1, |x| x)"),
+            ["errors::log"]
+        );
+    }
+
+    /// What an expression runs: the called function or method, awaited or not.
+    fn runs(expr: &Expr) -> String {
+        match expr {
+            Expr::Call(call) => runs(&call.func),
+            Expr::MethodCall(call) => call.method.to_string(),
+            Expr::Await(awaited) => runs(&awaited.base),
+            Expr::Path(p) => p.path.segments.last().unwrap().ident.to_string(),
+            Expr::Block(_) => "{}".to_string(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_select_arms() {
+        let body = "
+            biased;
+            v = rx.recv(), if open => handle(v).await,
+            Some(x) = next_item() => { process(x).await }
+            _ = sleep(delay) => tick(),
+            else => shutdown(),
+        ";
+        let exprs = parse_select_arms.parse_str(body).unwrap();
+        let runs: Vec<String> = exprs.iter().map(runs).collect();
+        assert_eq!(
+            runs,
+            [
+                "recv",
+                "open",
+                "handle",
+                "next_item",
+                "{}",
+                "sleep",
+                "tick",
+                "shutdown"
+            ]
+        );
+        assert!(is_select_macro(&syn::parse_str("tokio::select").unwrap()));
+    }
+}
@@ -0,0 +1,286 @@
+//! Confidence scoring for dead code findings.
+//!
+//! Detection is heuristic: calls are matched by name, `#[cfg]` branches are
+//! not evaluated, and macro bodies are opaque token streams. Every finding
+//! therefore carries a [`Confidence`] derived from [`ConfidenceSignals`]:
+//!
+//! | Signal                                   | Penalty |
+//! |------------------------------------------|---------|
+//! | `pub` visibility (external users unseen) | 2       |
+//! | Name mentioned inside a macro invocation | 2       |
+//! | `#[cfg]` / `#[cfg_attr]` on the item     | 1       |
+//! | Path-resolved or structural detection    | 1       |
+//!
+//! A total of 0 is [`Confidence::High`], 1 is [`Confidence::Medium`], and
+//! anything above is [`Confidence::Low`].
+
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use proc_macro2::{TokenStream, TokenTree};
+use serde::{Deserialize, Serialize};
+use syn::punctuated::Punctuated;
+use syn::{visit::Visit, Attribute, Macro, Meta, Token};
+
+/// How likely a finding is to be genuinely dead.
+///
+/// Ordered `Low < Medium < High`, so `finding.confidence >= min` filters.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    Low,
+    Medium,
+    #[default]
+    High,
+}
+
+impl Confidence {
+    /// All levels, lowest first.
+    pub const ALL: [Confidence; 3] = [Confidence::Low, Confidence::Medium, Confidence::High];
+
+    /// Lowercase name used in CLI flags and reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+impl fmt::Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Confidence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown confidence '{}' (expected low, medium or high)", s))
+    }
+}
+
+/// How a detector decided an item is unused.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Resolution {
+    /// No usage of the item's name anywhere in the crate
+    #[default]
+    Name,
+    /// Call paths were resolved through `use` statements, which can miss
+    /// re-exports and glob imports
+    Path,
+    /// Structural heuristic (generic bounds, match patterns)
+    Heuristic,
+}
+
+/// Evidence gathered for a single finding.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfidenceSignals<'a> {
+    /// Visibility string as produced by [`super::visibility_str`]
+    pub visibility: &'a str,
+    /// Item carries `#[cfg]` or `#[cfg_attr]`
+    pub has_cfg: bool,
+    /// Item name appears inside a macro invocation somewhere in the crate
+    pub in_macro: bool,
+    /// Detection method
+    pub resolution: Resolution,
+}
+
+impl ConfidenceSignals<'_> {
+    /// Combine the signals into a confidence level.
+    pub fn score(&self) -> Confidence {
+        let mut penalty = 0;
+        if self.visibility == "pub" {
+            penalty += 2;
+        }
+        if self.in_macro {
+            penalty += 2;
+        }
+        if self.has_cfg {
+            penalty += 1;
+        }
+        if self.resolution != Resolution::Name {
+            penalty += 1;
+        }
+
+        match penalty {
+            0 => Confidence::High,
+            1 => Confidence::Medium,
+            _ => Confidence::Low,
+        }
+    }
+}
+
+/// Check whether an item is conditionally compiled.
+pub fn has_cfg_attr(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|a| a.path().is_ident("cfg") || a.path().is_ident("cfg_attr"))
+}
+
+/// Check whether an item is compiled only for tests: `#[cfg(test)]` or
+/// `#[cfg(all(test, ...))]`.
+pub fn is_cfg_test(attrs: &[Attribute]) -> bool {
+    attrs.iter().filter(|a| a.path().is_ident("cfg")).any(|a| {
+        a.parse_args::<Meta>()
+            .is_ok_and(|meta| requires_test(&meta))
+    })
+}
+
+fn requires_test(meta: &Meta) -> bool {
+    match meta {
+        Meta::Path(path) => path.is_ident("test"),
+        Meta::List(list) if list.path.is_ident("all") => list
+            .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            .is_ok_and(|metas| metas.iter().any(requires_test)),
+        _ => false,
+    }
+}
+
+/// Visitor collecting every identifier inside macro invocation tokens.
+#[derive(Default)]
+struct MacroMentionCollector {
+    idents: HashSet<String>,
+}
+
+impl MacroMentionCollector {
+    fn collect_tokens(&mut self, tokens: TokenStream) {
+        for tt in tokens {
+            match tt {
+                TokenTree::Ident(ident) => {
+                    self.idents.insert(ident.to_string());
+                }
+                TokenTree::Group(group) => self.collect_tokens(group.stream()),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for MacroMentionCollector {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        self.collect_tokens(mac.tokens.clone());
+    }
+}
+
+/// Extract identifiers mentioned inside macro invocations in a file.
+///
+/// The usage extractors read macro arguments only on a best-effort basis
+/// (identifiers pasted together by a macro stay hidden), so a dead item
+/// whose name shows up here gets a lower confidence. Returns an empty set on parse errors.
+pub fn extract_macro_mentions(content: &str) -> HashSet<String> {
+    match syn::parse_file(content) {
+        Ok(ast) => {
+            let mut collector = MacroMentionCollector::default();
+            collector.visit_file(&ast);
+            collector.idents
+        }
+        Err(_) => HashSet::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confidence_ordering_and_parse() {
+        assert!(Confidence::Low < Confidence::Medium);
+        assert!(Confidence::Medium < Confidence::High);
+        assert_eq!("MEDIUM".parse::<Confidence>().unwrap(), Confidence::Medium);
+        assert!("certain".parse::<Confidence>().is_err());
+        assert_eq!(Confidence::High.to_string(), "high");
+    }
+
+    #[test]
+    fn test_score_signals() {
+        let private = ConfidenceSignals {
+            visibility: "private",
+            ..Default::default()
+        };
+        assert_eq!(private.score(), Confidence::High);
+
+        let cfg = ConfidenceSignals {
+            has_cfg: true,
+            ..private
+        };
+        assert_eq!(cfg.score(), Confidence::Medium);
+
+        let public = ConfidenceSignals {
+            visibility: "pub",
+            ..private
+        };
+        assert_eq!(public.score(), Confidence::Low);
+
+        let macro_mentioned = ConfidenceSignals {
+            in_macro: true,
+            ..private
+        };
+        assert_eq!(macro_mentioned.score(), Confidence::Low);
+
+        let heuristic = ConfidenceSignals {
+            resolution: Resolution::Heuristic,
+            ..private
+        };
+        assert_eq!(heuristic.score(), Confidence::Medium);
+    }
+
+    #[test]
+    fn test_has_cfg_attr() {
+        let item: syn::ItemFn = syn::parse_quote! {
+            #[cfg(feature = "x")]
+            fn gated() {}
+        };
+        assert!(has_cfg_attr(&item.attrs));
+
+        let item: syn::ItemFn = syn::parse_quote! {
+            #[inline]
+            fn plain() {}
+        };
+        assert!(!has_cfg_attr(&item.attrs));
+    }
+
+    #[test]
+    fn test_is_cfg_test() {
+        let attrs = |item: syn::ItemFn| item.attrs;
+        assert!(is_cfg_test(&attrs(
+            syn::parse_quote! { #[cfg(test)] fn f() {} }
+        )));
+        assert!(is_cfg_test(&attrs(
+            syn::parse_quote! { #[cfg(all(test, unix))] fn f() {} }
+        )));
+        assert!(!is_cfg_test(&attrs(
+            syn::parse_quote! { #[cfg(not(test))] fn f() {} }
+        )));
+        assert!(!is_cfg_test(&attrs(
+            syn::parse_quote! { #[cfg(any(test, feature = "x"))] fn f() {} }
+        )));
+        assert!(!is_cfg_test(&attrs(
+            syn::parse_quote! { #[test] fn f() {} }
+        )));
+    }
+
+    #[test]
+    fn test_extract_macro_mentions() {
+        let mentions = extract_macro_mentions(
+            r#"
+            fn main() {
+                my_macro!(helper(1), { nested_call() });
+                println!("{}", value);
+            }
+            "#,
+        );
+        assert!(mentions.contains("helper"));
+        assert!(mentions.contains("nested_call"));
+        assert!(mentions.contains("value"));
+        assert!(!mentions.contains("main"));
+    }
+}
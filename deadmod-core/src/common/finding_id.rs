@@ -0,0 +1,83 @@
+//! Stable finding identifiers and `deadmod:ignore` suppressions.
+//!
+//! A finding's ID hashes its kind, module path and name, never its line, so it
+//! survives edits around the item and lets baselines and suppression comments
+//! follow an item across runs:
+//!
+//! ```text
+//! // deadmod:ignore 3fa9c2d1e07b
+//! fn only_called_via_ffi() {}
+//! ```
+//!
+//! Findings with the same kind, module and name (two `_` arms in one module,
+//! say) share an ID.
+
+use std::collections::HashSet;
+
+use sha2::{Digest, Sha256};
+
+/// Marker introducing suppressed IDs in a comment.
+pub const IGNORE_MARKER: &str = "deadmod:ignore";
+
+/// Length of a finding ID in hex digits.
+const ID_LEN: usize = 12;
+
+/// Stable ID of the finding `name` of `kind` in module `module_path`.
+pub fn finding_id(kind: &str, module_path: &str, name: &str) -> String {
+    let mut sha = Sha256::new();
+    for part in [kind, module_path, name] {
+        sha.update(part.as_bytes());
+        sha.update([0]);
+    }
+    let mut id = format!("{:x}", sha.finalize());
+    id.truncate(ID_LEN);
+    id
+}
+
+/// Stable ID of the dead module `name` (its graph name, e.g. `net` or
+/// `crate_a::net` in workspaces).
+pub fn module_id(name: &str) -> String {
+    finding_id("module", name, "")
+}
+
+/// IDs listed in `// deadmod:ignore <id> [<id>...]` comments in `content`.
+pub fn ignored_ids(content: &str) -> HashSet<String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once("//").map(|(_, comment)| comment))
+        .filter_map(|comment| comment.split_once(IGNORE_MARKER).map(|(_, ids)| ids))
+        .flat_map(|ids| ids.split(|c: char| c.is_whitespace() || c == ','))
+        .filter(|id| id.len() == ID_LEN && id.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finding_id_is_stable() {
+        let id = finding_id("function", "crate::net", "connect");
+        assert_eq!(id.len(), ID_LEN);
+        assert_eq!(id, finding_id("function", "crate::net", "connect"));
+        assert_ne!(id, finding_id("method", "crate::net", "connect"));
+        assert_ne!(id, finding_id("function", "crate", "connect"));
+        // Separator prevents ambiguous concatenations
+        assert_ne!(finding_id("a", "bc", "d"), finding_id("ab", "c", "d"));
+        assert_ne!(module_id("net"), module_id("io"));
+    }
+
+    #[test]
+    fn test_ignored_ids() {
+        let src = "// deadmod:ignore 3fa9c2d1e07b\n\
+                   fn a() {} // deadmod:ignore 0123456789AB, aaaaaaaaaaaa\n\
+                   /// deadmod:ignore not-an-id 123\n\
+                   let s = \"deadmod:ignore ffffffffffff\";";
+        let ids = ignored_ids(src);
+        assert_eq!(ids.len(), 3);
+        assert!(ids.contains("3fa9c2d1e07b"));
+        assert!(ids.contains("0123456789ab"));
+        assert!(ids.contains("aaaaaaaaaaaa"));
+    }
+}
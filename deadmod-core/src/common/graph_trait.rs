@@ -17,14 +17,14 @@ use std::hash::Hash;
 /// # Example
 /// ```ignore
 /// impl GraphTraversal for CallGraph {
-///     type Node = String;
+///     type Node = Symbol;
 ///
-///     fn neighbors(&self, node: &String) -> Vec<String> {
-///         self.adjacency.get(node).cloned().unwrap_or_default()
+///     fn neighbors(&self, node: &Symbol) -> Vec<Symbol> {
+///         self.calls.neighbors(node_index(*node)).map(|n| self.calls[n]).collect()
 ///     }
 ///
-///     fn contains_node(&self, node: &String) -> bool {
-///         self.nodes.contains_key(node)
+///     fn contains_node(&self, node: &Symbol) -> bool {
+///         node.index() < self.symbols.len()
 ///     }
 /// }
 ///
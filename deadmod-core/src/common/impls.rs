@@ -0,0 +1,146 @@
+//! Whole `impl` blocks whose methods are all dead.
+//!
+//! Method-level detectors report each uncalled method on its own. When every
+//! method of an `impl` block is dead, the block itself can go: a larger, more
+//! useful finding. Extractors tag each method with the [`ImplSpan`] of its
+//! block, and [`dead_impls`] groups the dead methods back into blocks.
+
+use std::collections::{BTreeMap, HashMap};
+
+use proc_macro2::Span;
+use serde::{Deserialize, Serialize};
+use syn::spanned::Spanned;
+use syn::{ImplItem, ItemImpl};
+
+use super::Confidence;
+
+/// Location of the `impl` block a method is defined in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ImplSpan {
+    /// First line of the block, attributes included (1-based)
+    pub start_line: usize,
+    /// Column of the first character on `start_line` (0-based)
+    pub start_column: usize,
+    /// Last line of the block (1-based)
+    pub end_line: usize,
+    /// Column just past the closing brace on `end_line` (0-based)
+    pub end_column: usize,
+    /// `impl Type` or `impl Trait for Type`
+    pub label: String,
+    /// Whether the block holds nothing but methods (no consts, types or macros)
+    pub methods_only: bool,
+}
+
+impl ImplSpan {
+    /// Span of `item`, labeled with the already-rendered `trait_name` and
+    /// `type_name`.
+    pub fn of(item: &ItemImpl, trait_name: Option<&str>, type_name: &str) -> Self {
+        let span: Span = item.span();
+        let label = match trait_name {
+            Some(trait_name) => format!("impl {} for {}", trait_name, type_name),
+            None => format!("impl {}", type_name),
+        };
+        Self {
+            start_line: span.start().line,
+            start_column: span.start().column,
+            end_line: span.end().line,
+            end_column: span.end().column,
+            label,
+            methods_only: item.items.iter().all(|i| matches!(i, ImplItem::Fn(_))),
+        }
+    }
+}
+
+/// An `impl` block in which every method is dead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadImpl {
+    /// Source file path
+    pub file: String,
+    /// Where the block is, for removing it whole
+    pub span: ImplSpan,
+    /// Names of its methods, sorted
+    pub methods: Vec<String>,
+    /// Lowest confidence among its methods
+    pub confidence: Confidence,
+}
+
+/// Blocks all of whose methods are dead.
+///
+/// `all` lists `(file, block)` for every method the detector saw, `dead`
+/// lists `(file, block, method name, confidence)` for the dead ones. Blocks
+/// with anything besides methods are never reported, since removing them
+/// would take live items along. Sorted by file and line.
+pub fn dead_impls<'a>(
+    all: impl IntoIterator<Item = (&'a str, &'a ImplSpan)>,
+    dead: impl IntoIterator<Item = (&'a str, &'a ImplSpan, &'a str, Confidence)>,
+) -> Vec<DeadImpl> {
+    let mut totals: HashMap<(&str, &ImplSpan), usize> = HashMap::new();
+    for key in all {
+        *totals.entry(key).or_default() += 1;
+    }
+
+    let mut blocks: BTreeMap<(&str, &ImplSpan), (Vec<String>, Confidence)> = BTreeMap::new();
+    for (file, span, name, confidence) in dead {
+        let block = blocks
+            .entry((file, span))
+            .or_insert((Vec::new(), confidence));
+        block.0.push(name.to_string());
+        block.1 = block.1.min(confidence);
+    }
+
+    blocks
+        .into_iter()
+        .filter(|(key, (methods, _))| key.1.methods_only && totals.get(key) == Some(&methods.len()))
+        .map(|((file, span), (mut methods, confidence))| {
+            methods.sort();
+            DeadImpl {
+                file: file.to_string(),
+                span: span.clone(),
+                methods,
+                confidence,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(source: &str) -> ImplSpan {
+        let item: ItemImpl = syn::parse_str(source).unwrap();
+        ImplSpan::of(&item, None, "Foo")
+    }
+
+    #[test]
+    fn test_impl_span_covers_attributes() {
+        let s = span("#[cfg(unix)]\nimpl Foo {\n    fn a() {}\n}");
+        assert_eq!((s.start_line, s.end_line, s.end_column), (1, 4, 1));
+        assert_eq!(s.label, "impl Foo");
+        assert!(s.methods_only);
+        assert!(!span("impl Foo { const N: u8 = 1; fn a() {} }").methods_only);
+    }
+
+    #[test]
+    fn test_dead_impls_need_every_method_dead() {
+        let whole = span("impl Foo { fn a() {} fn b() {} }");
+        let mut partial = span("impl Foo { fn c() {} fn d() {} }");
+        partial.start_line = 5;
+
+        let all = [
+            ("f.rs", &whole),
+            ("f.rs", &whole),
+            ("f.rs", &partial),
+            ("f.rs", &partial),
+        ];
+        let dead = [
+            ("f.rs", &whole, "b", Confidence::High),
+            ("f.rs", &whole, "a", Confidence::Medium),
+            ("f.rs", &partial, "c", Confidence::High),
+        ];
+        let found = dead_impls(all, dead);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].methods, ["a", "b"]);
+        assert_eq!(found[0].confidence, Confidence::Medium);
+    }
+}
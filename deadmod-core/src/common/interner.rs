@@ -0,0 +1,111 @@
+//! String interning for graph keys.
+//!
+//! Function graphs refer to the same full paths over and over: as nodes,
+//! in every edge, in forward and reverse adjacency and in name indexes.
+//! An [`Interner`] stores each distinct string once and hands out a
+//! [`Symbol`], a `u32` that is copied, hashed and compared instead of the
+//! string. Symbols are dense (0, 1, 2, ... in first-interned order), so
+//! graphs can also index plain vectors with them.
+//!
+//! Symbols are only meaningful to the interner that produced them.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Handle to a string in an [`Interner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Position of the string in interning order.
+    #[inline]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Set of distinct strings, each stored once.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    ids: HashMap<Arc<str>, Symbol>,
+    strings: Vec<Arc<str>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            ids: HashMap::with_capacity(capacity),
+            strings: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Symbol for `s`, storing it on first sight.
+    pub fn intern(&mut self, s: &str) -> Symbol {
+        if let Some(&sym) = self.ids.get(s) {
+            return sym;
+        }
+        let sym =
+            Symbol(u32::try_from(self.strings.len()).expect("more than u32::MAX interned strings"));
+        let s: Arc<str> = Arc::from(s);
+        self.strings.push(Arc::clone(&s));
+        self.ids.insert(s, sym);
+        sym
+    }
+
+    /// Symbol for `s` if it was interned.
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        self.ids.get(s).copied()
+    }
+
+    /// The string behind `sym`.
+    ///
+    /// Panics if `sym` came from another interner with more strings.
+    #[inline]
+    pub fn resolve(&self, sym: Symbol) -> &str {
+        &self.strings[sym.index()]
+    }
+
+    /// All symbols with their strings, in interning order.
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> {
+        self.strings
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (Symbol(i as u32), &**s))
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_and_resolve() {
+        let mut interner = Interner::new();
+        let a = interner.intern("crate::a");
+        let b = interner.intern("crate::b");
+        assert_eq!(interner.intern("crate::a"), a);
+        assert_ne!(a, b);
+        assert_eq!((a.index(), b.index()), (0, 1));
+
+        assert_eq!(interner.resolve(b), "crate::b");
+        assert_eq!(interner.get("crate::b"), Some(b));
+        assert_eq!(interner.get("crate::c"), None);
+        assert_eq!(
+            interner.iter().map(|(_, s)| s).collect::<Vec<_>>(),
+            ["crate::a", "crate::b"]
+        );
+        assert_eq!(interner.len(), 2);
+    }
+}
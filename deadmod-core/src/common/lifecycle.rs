@@ -0,0 +1,295 @@
+//! API lifecycle attributes: `#[doc(hidden)]`, `#[deprecated]` and
+//! `#[deadmod::keep]`.
+//!
+//! Deprecated dead items are already on their way out, so reports list them
+//! separately as "scheduled for removal". `#[doc(hidden)] pub` items are
+//! public in name only; [`HiddenApiPolicy`] decides whether they still count
+//! as public API (and therefore as live entry points). Items annotated with
+//! `#[deadmod::keep]` (from the `deadmod-annotations` crate) are never
+//! reported; reports list them with their reason instead.
+//!
+//! The deprecation and hidden-API classification applies to functions and
+//! methods only; other dead items are reported the same with or without
+//! these attributes.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use syn::{Attribute, Expr, ExprLit, Lit, Meta, Token};
+
+/// Details from a `#[deprecated]` attribute.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deprecation {
+    /// Version from `since = "..."`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    /// Message from `note = "..."` or `#[deprecated = "..."]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Details from a `#[deadmod::keep]` attribute.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keep {
+    /// Message from `reason = "..."`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// An item that would be reported but is kept by `#[deadmod::keep]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeptItem {
+    /// Item name, qualified the way its detector reports it
+    pub name: String,
+    /// Source file
+    pub file: String,
+    /// Why the item is kept, if given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl KeptItem {
+    /// Record `name` in `file` as kept by `keep`.
+    pub fn new(name: impl Into<String>, file: impl Into<String>, keep: &Keep) -> Self {
+        Self {
+            name: name.into(),
+            file: file.into(),
+            reason: keep.reason.clone(),
+        }
+    }
+}
+
+/// How `#[doc(hidden)] pub` items are treated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HiddenApiPolicy {
+    /// Same as any `pub` item: assumed used externally
+    #[default]
+    Public,
+    /// Not assumed used; reported when unreachable, at `pub` confidence
+    Downgrade,
+    /// Not assumed used; scored as crate-internal
+    Exclude,
+}
+
+impl HiddenApiPolicy {
+    /// All policies, in documentation order.
+    pub const ALL: [HiddenApiPolicy; 3] = [Self::Public, Self::Downgrade, Self::Exclude];
+
+    /// Lowercase name used in CLI flags and deadmod.toml.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Downgrade => "downgrade",
+            Self::Exclude => "exclude",
+        }
+    }
+
+    /// Whether a `pub` item with the given `doc(hidden)` flag is public API.
+    pub fn is_public_api(&self, doc_hidden: bool) -> bool {
+        !doc_hidden || *self == Self::Public
+    }
+}
+
+impl fmt::Display for HiddenApiPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HiddenApiPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|p| p.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "unknown doc(hidden) policy '{}' (expected public, downgrade or exclude)",
+                    s
+                )
+            })
+    }
+}
+
+/// Check whether an item carries `#[doc(hidden)]`.
+pub fn is_doc_hidden(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        if !attr.path().is_ident("doc") {
+            return false;
+        }
+        let mut hidden = false;
+        // `#[doc = "..."]` is not a list and simply fails to parse here
+        let _ = attr.parse_nested_meta(|meta| {
+            hidden |= meta.path.is_ident("hidden");
+            // Skip the value of siblings like `alias = "x"` so parsing continues
+            if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|_| Ok(()))?;
+            }
+            Ok(())
+        });
+        hidden
+    })
+}
+
+/// Read the `#[deprecated]` attribute, if any.
+///
+/// Accepts all three forms: `#[deprecated]`, `#[deprecated = "note"]` and
+/// `#[deprecated(since = "1.2.0", note = "...")]`.
+pub fn deprecation(attrs: &[Attribute]) -> Option<Deprecation> {
+    let attr = attrs.iter().find(|a| a.path().is_ident("deprecated"))?;
+    let mut info = Deprecation::default();
+
+    match &attr.meta {
+        Meta::Path(_) => {}
+        Meta::NameValue(nv) => info.note = string_lit(&nv.value),
+        Meta::List(_) => {
+            let _ = attr.parse_nested_meta(|meta| {
+                let value: Expr = meta.value()?.parse()?;
+                if meta.path.is_ident("since") {
+                    info.since = string_lit(&value);
+                } else if meta.path.is_ident("note") {
+                    info.note = string_lit(&value);
+                }
+                Ok(())
+            });
+        }
+    }
+
+    Some(info)
+}
+
+/// Read the `#[deadmod::keep]` attribute, if any.
+///
+/// Accepts `#[deadmod::keep]` and `#[deadmod::keep(reason = "...")]`, also
+/// spelled `deadmod_annotations::keep` when the crate is not renamed.
+pub fn keep_annotation(attrs: &[Attribute]) -> Option<Keep> {
+    let attr = attrs.iter().find(|a| is_keep_path(a.path()))?;
+    let mut keep = Keep::default();
+
+    if let Meta::List(_) = &attr.meta {
+        let _ = attr.parse_nested_meta(|meta| {
+            let value: Expr = meta.value()?.parse()?;
+            if meta.path.is_ident("reason") {
+                keep.reason = string_lit(&value);
+            }
+            Ok(())
+        });
+    }
+
+    Some(keep)
+}
+
+fn is_keep_path(path: &syn::Path) -> bool {
+    let mut segments = path.segments.iter().map(|s| s.ident.to_string());
+    matches!(
+        (
+            segments.next().as_deref(),
+            segments.next().as_deref(),
+            segments.next()
+        ),
+        (Some("deadmod" | "deadmod_annotations"), Some("keep"), None)
+    )
+}
+
+fn string_lit(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(s), ..
+        }) => Some(s.value()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attrs_of(item: syn::ItemFn) -> Vec<Attribute> {
+        item.attrs
+    }
+
+    #[test]
+    fn test_is_doc_hidden() {
+        assert!(is_doc_hidden(&attrs_of(syn::parse_quote! {
+            #[doc(hidden)]
+            pub fn internal() {}
+        })));
+        assert!(is_doc_hidden(&attrs_of(syn::parse_quote! {
+            #[doc(alias = "x", hidden)]
+            pub fn internal() {}
+        })));
+        assert!(!is_doc_hidden(&attrs_of(syn::parse_quote! {
+            /// Documented normally
+            pub fn visible() {}
+        })));
+    }
+
+    #[test]
+    fn test_deprecation_forms() {
+        assert_eq!(
+            deprecation(&attrs_of(syn::parse_quote! {
+                #[deprecated]
+                fn a() {}
+            })),
+            Some(Deprecation::default())
+        );
+
+        let note = deprecation(&attrs_of(syn::parse_quote! {
+            #[deprecated = "use b"]
+            fn a() {}
+        }))
+        .unwrap();
+        assert_eq!(note.note.as_deref(), Some("use b"));
+
+        let full = deprecation(&attrs_of(syn::parse_quote! {
+            #[deprecated(since = "0.4.0", note = "use c")]
+            fn a() {}
+        }))
+        .unwrap();
+        assert_eq!(full.since.as_deref(), Some("0.4.0"));
+        assert_eq!(full.note.as_deref(), Some("use c"));
+
+        assert!(deprecation(&attrs_of(syn::parse_quote! { fn a() {} })).is_none());
+    }
+
+    #[test]
+    fn test_keep_annotation() {
+        let keep = keep_annotation(&attrs_of(syn::parse_quote! {
+            #[deadmod::keep(reason = "called from C")]
+            fn a() {}
+        }))
+        .unwrap();
+        assert_eq!(keep.reason.as_deref(), Some("called from C"));
+
+        assert_eq!(
+            keep_annotation(&attrs_of(syn::parse_quote! {
+                #[deadmod_annotations::keep]
+                fn a() {}
+            })),
+            Some(Keep::default())
+        );
+        assert!(keep_annotation(&attrs_of(syn::parse_quote! {
+            #[keep]
+            fn a() {}
+        }))
+        .is_none());
+        assert!(keep_annotation(&attrs_of(syn::parse_quote! { fn a() {} })).is_none());
+    }
+
+    #[test]
+    fn test_hidden_api_policy() {
+        assert_eq!(
+            "Downgrade".parse::<HiddenApiPolicy>().unwrap(),
+            HiddenApiPolicy::Downgrade
+        );
+        assert!("hide".parse::<HiddenApiPolicy>().is_err());
+        assert!(HiddenApiPolicy::Public.is_public_api(true));
+        assert!(HiddenApiPolicy::Exclude.is_public_api(false));
+        assert!(!HiddenApiPolicy::Exclude.is_public_api(true));
+    }
+}
@@ -0,0 +1,230 @@
+//! Items kept alive by the linker or a registry instead of by references.
+//!
+//! `#[used]` and `#[link_section]` statics, `#[ctor]`/`#[dtor]` functions and
+//! `#[linkme::distributed_slice]` elements are never referenced by name, and
+//! values passed to `inventory::submit!` are collected at link time. Removing
+//! them compiles fine and silently breaks the program, so the function and
+//! constant analyzers treat them as live. Both lists are configurable in
+//! `deadmod.toml`:
+//!
+//! ```toml
+//! [liveness]
+//! attributes = ["my_framework::register"]
+//! registry_macros = ["plugin_registry::submit"]
+//! ```
+//!
+//! Configured names are added to the defaults. A name without `::` matches
+//! the last path segment (`distributed_slice` matches
+//! `linkme::distributed_slice`); a qualified name must match exactly.
+
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use syn::visit::Visit;
+use syn::{Attribute, Macro, Meta};
+
+/// Attributes that keep an item alive by default.
+pub const DEFAULT_ALIVE_ATTRIBUTES: &[&str] = &[
+    "used",
+    "link_section",
+    "export_name",
+    "ctor",
+    "dtor",
+    "distributed_slice",
+];
+
+/// Macros whose arguments are registered at link time by default.
+pub const DEFAULT_REGISTRY_MACROS: &[&str] = &["inventory::submit"];
+
+/// Attributes and registry macros that make an item live.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct LivenessRules {
+    /// Attribute paths (`used`, `linkme::distributed_slice`)
+    pub attributes: Vec<String>,
+    /// Registry macro paths (`inventory::submit`); identifiers passed to
+    /// them count as used
+    pub registry_macros: Vec<String>,
+}
+
+impl Default for LivenessRules {
+    fn default() -> Self {
+        Self {
+            attributes: DEFAULT_ALIVE_ATTRIBUTES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            registry_macros: DEFAULT_REGISTRY_MACROS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl LivenessRules {
+    /// The defaults plus the attributes and macros from `extra`.
+    pub fn with_extra(mut self, extra: LivenessRules) -> Self {
+        for attr in extra.attributes {
+            if !self.attributes.contains(&attr) {
+                self.attributes.push(attr);
+            }
+        }
+        for mac in extra.registry_macros {
+            if !self.registry_macros.contains(&mac) {
+                self.registry_macros.push(mac);
+            }
+        }
+        self
+    }
+
+    /// First of `attributes` (as recorded by [`attribute_paths`]) that keeps
+    /// an item alive.
+    pub fn alive_attribute<'a>(&self, attributes: &'a [String]) -> Option<&'a str> {
+        attributes
+            .iter()
+            .find(|path| self.attributes.iter().any(|rule| path_matches(rule, path)))
+            .map(String::as_str)
+    }
+
+    /// Identifiers passed to registry macros anywhere in `content`.
+    pub fn registered_names(&self, content: &str) -> HashSet<String> {
+        let Ok(ast) = syn::parse_file(content) else {
+            return HashSet::new();
+        };
+        let mut visitor = RegistryVisitor {
+            rules: self,
+            names: HashSet::new(),
+        };
+        visitor.visit_file(&ast);
+        visitor.names
+    }
+}
+
+/// Paths of `attrs`, e.g. `used` or `linkme::distributed_slice`.
+///
+/// `#[unsafe(...)]` (edition 2024) is unwrapped to the attribute inside it;
+/// doc comments are skipped.
+pub fn attribute_paths(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| !attr.path().is_ident("doc"))
+        .filter_map(|attr| {
+            if attr.path().is_ident("unsafe") {
+                let Meta::List(list) = &attr.meta else {
+                    return None;
+                };
+                let inner: Meta = syn::parse2(list.tokens.clone()).ok()?;
+                Some(path_string(inner.path()))
+            } else {
+                Some(path_string(attr.path()))
+            }
+        })
+        .collect()
+}
+
+fn path_string(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Whether `path` matches `rule` (unqualified rules match the last segment).
+fn path_matches(rule: &str, path: &str) -> bool {
+    if rule.contains("::") {
+        rule == path
+    } else {
+        path.rsplit("::").next() == Some(rule)
+    }
+}
+
+struct RegistryVisitor<'a> {
+    rules: &'a LivenessRules,
+    names: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for RegistryVisitor<'_> {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let path = path_string(&mac.path);
+        if self
+            .rules
+            .registry_macros
+            .iter()
+            .any(|rule| path_matches(rule, &path))
+        {
+            collect_idents(mac.tokens.clone(), &mut self.names);
+        }
+        syn::visit::visit_macro(self, mac);
+    }
+}
+
+fn collect_idents(tokens: proc_macro2::TokenStream, names: &mut HashSet<String>) {
+    for token in tokens {
+        match token {
+            proc_macro2::TokenTree::Ident(ident) => {
+                names.insert(ident.to_string());
+            }
+            proc_macro2::TokenTree::Group(group) => collect_idents(group.stream(), names),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attrs(src: &str) -> Vec<String> {
+        let item: syn::ItemStatic = syn::parse_str(src).unwrap();
+        attribute_paths(&item.attrs)
+    }
+
+    #[test]
+    fn test_attribute_paths() {
+        assert_eq!(
+            attrs("/// docs\n#[used]\n#[linkme::distributed_slice(HOOKS)]\nstatic X: u8 = 0;"),
+            vec!["used", "linkme::distributed_slice"]
+        );
+        assert_eq!(
+            attrs("#[unsafe(link_section = \".init_array\")]\nstatic X: u8 = 0;"),
+            vec!["link_section"]
+        );
+    }
+
+    #[test]
+    fn test_alive_attribute() {
+        let rules = LivenessRules::default();
+        let found = vec!["allow".to_string(), "linkme::distributed_slice".to_string()];
+        assert_eq!(
+            rules.alive_attribute(&found),
+            Some("linkme::distributed_slice")
+        );
+        assert_eq!(rules.alive_attribute(&["inline".to_string()]), None);
+
+        let rules = LivenessRules::default().with_extra(LivenessRules {
+            attributes: vec!["my::register".to_string()],
+            registry_macros: Vec::new(),
+        });
+        assert!(rules
+            .alive_attribute(&["my::register".to_string()])
+            .is_some());
+        assert!(rules
+            .alive_attribute(&["other::register".to_string()])
+            .is_none());
+        assert!(rules.alive_attribute(&["used".to_string()]).is_some());
+    }
+
+    #[test]
+    fn test_registered_names() {
+        let src = r#"
+            inventory::submit! { Plugin { name: "a", init: init_a } }
+            fn f() { other!(init_b); }
+        "#;
+        let names = LivenessRules::default().registered_names(src);
+        assert!(names.contains("init_a"));
+        assert!(names.contains("Plugin"));
+        assert!(!names.contains("init_b"));
+    }
+}
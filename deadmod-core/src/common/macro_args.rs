@@ -0,0 +1,226 @@
+//! Usages hidden inside macro invocation arguments.
+//!
+//! syn keeps macro arguments as raw tokens, so the call in
+//! `info!("{}", foo())` or `vec![make_thing()]` is invisible to AST
+//! visitors. [`visit_macro_args`] parses the common argument shapes
+//! (comma-separated expressions, `vec![x; n]`, `name = value`) and walks
+//! them like ordinary expressions, and `select!` arms with
+//! [`parse_select_arms`]. When the arguments are not expressions
+//! (custom DSLs, `macro_rules!` bodies) it falls back to scanning the token
+//! tree for paths with [`macro_paths`].
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use syn::parse::ParseStream;
+use syn::{visit::Visit, Expr, Macro, Token};
+
+use super::{is_select_macro, parse_select_arms};
+
+/// A path found by scanning macro tokens.
+#[derive(Clone)]
+pub struct MacroPath {
+    /// The path as written (`foo`, `module::helper`)
+    pub path: syn::Path,
+    /// Followed by a parenthesized group: `foo(..)`
+    pub is_call: bool,
+    /// Preceded by a dot: `.method(..)`
+    pub is_method: bool,
+}
+
+impl MacroPath {
+    /// Last segment of the path.
+    pub fn name(&self) -> String {
+        self.path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Walk the arguments of `mac` with `visitor`.
+///
+/// Parsed arguments go through the visitor's `visit_expr`; otherwise every
+/// path in the tokens is handed to `on_path`.
+pub fn visit_macro_args<V>(
+    visitor: &mut V,
+    mac: &Macro,
+    mut on_path: impl FnMut(&mut V, &MacroPath),
+) where
+    V: for<'a> Visit<'a>,
+{
+    let parsed = mac.parse_body_with(parse_args).or_else(|e| {
+        if is_select_macro(&mac.path) {
+            mac.parse_body_with(parse_select_arms)
+        } else {
+            Err(e)
+        }
+    });
+    match parsed {
+        Ok(exprs) => {
+            for expr in &exprs {
+                visitor.visit_expr(expr);
+            }
+        }
+        Err(_) => {
+            for path in macro_paths(&mac.tokens) {
+                on_path(visitor, &path);
+            }
+        }
+    }
+}
+
+/// Expressions separated by `,` or `;`, with an optional trailing separator.
+fn parse_args(input: ParseStream) -> syn::Result<Vec<Expr>> {
+    let mut exprs = Vec::new();
+    while !input.is_empty() {
+        exprs.push(input.parse()?);
+        if input.is_empty() {
+            break;
+        }
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        } else {
+            input.parse::<Token![;]>()?;
+        }
+    }
+    Ok(exprs)
+}
+
+/// Scan a token tree for paths (`a`, `a::b::c`), including nested groups.
+///
+/// `macro_rules!` metavariables (`$x`) and `.await` are skipped.
+pub fn macro_paths(tokens: &TokenStream) -> Vec<MacroPath> {
+    let mut paths = Vec::new();
+    collect_paths(tokens.clone(), &mut paths);
+    paths
+}
+
+fn collect_paths(tokens: TokenStream, paths: &mut Vec<MacroPath>) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            TokenTree::Group(group) => {
+                collect_paths(group.stream(), paths);
+                i += 1;
+            }
+            TokenTree::Ident(ident) => {
+                let prev = i.checked_sub(1).map(|p| &tokens[p]);
+                let is_meta_var = matches!(prev, Some(TokenTree::Punct(p)) if p.as_char() == '$');
+                let is_method = matches!(prev, Some(TokenTree::Punct(p)) if p.as_char() == '.');
+
+                let mut segments = vec![ident.clone()];
+                i += 1;
+                while let (
+                    Some(TokenTree::Punct(a)),
+                    Some(TokenTree::Punct(b)),
+                    Some(TokenTree::Ident(next)),
+                ) = (tokens.get(i), tokens.get(i + 1), tokens.get(i + 2))
+                {
+                    if a.as_char() != ':' || b.as_char() != ':' {
+                        break;
+                    }
+                    segments.push(next.clone());
+                    i += 3;
+                }
+
+                if is_meta_var || (is_method && ident == "await") {
+                    continue;
+                }
+                let is_call = matches!(tokens.get(i), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis);
+                paths.push(MacroPath {
+                    path: syn::Path {
+                        leading_colon: None,
+                        segments: segments.into_iter().map(syn::PathSegment::from).collect(),
+                    },
+                    is_call,
+                    is_method,
+                });
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(src: &str) -> Vec<(String, bool, bool)> {
+        let tokens: TokenStream = src.parse().unwrap();
+        macro_paths(&tokens)
+            .into_iter()
+            .map(|p| {
+                let path = p
+                    .path
+                    .segments
+                    .iter()
+                    .map(|s| s.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::");
+                (path, p.is_call, p.is_method)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_macro_paths() {
+        assert_eq!(
+            paths("route GET => handlers::index(req).finish().await { $x }"),
+            vec![
+                ("route".to_string(), false, false),
+                ("GET".to_string(), false, false),
+                ("handlers::index".to_string(), true, false),
+                ("req".to_string(), false, false),
+                ("finish".to_string(), true, true),
+            ]
+        );
+    }
+
+    #[derive(Default)]
+    struct Calls(Vec<String>);
+
+    impl<'ast> Visit<'ast> for Calls {
+        fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+            if let Expr::Path(p) = &*call.func {
+                self.0
+                    .push(p.path.segments.last().unwrap().ident.to_string());
+            }
+            syn::visit::visit_expr_call(self, call);
+        }
+
+        fn visit_macro(&mut self, mac: &'ast Macro) {
+            visit_macro_args(self, mac, |v, path| {
+                if path.is_call {
+                    v.0.push(path.name());
+                }
+            });
+        }
+    }
+
+    fn calls(src: &str) -> Vec<String> {
+        let file = syn::parse_file(src).unwrap();
+        let mut visitor = Calls::default();
+        visitor.visit_file(&file);
+        visitor.0
+    }
+
+    #[test]
+    fn test_visit_macro_args() {
+        assert_eq!(
+            calls(r#"fn f() { info!("{} {n}", foo(), n = bar()); }"#),
+            ["foo", "bar"]
+        );
+        assert_eq!(
+            calls("fn f() { let v = vec![make(); count()]; }"),
+            ["make", "count"]
+        );
+        assert_eq!(calls("fn f() { outer!(inner!(deep())); }"), ["deep"]);
+        assert_eq!(calls("fn f() { dsl!(when ready => fire()); }"), ["fire"]);
+        // Parsed as arms, so `rx.recv()` is a method call, not a path
+        assert_eq!(
+            calls("fn f() { select! { v = rx.recv() => on_msg(v), else => stop() } }"),
+            ["on_msg", "stop"]
+        );
+    }
+}
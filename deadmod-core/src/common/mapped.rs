@@ -0,0 +1,121 @@
+//! Memory-mapped file reading for uncached fast parses.
+//!
+//! A cold run reads every file exactly once and never keeps the bytes, so
+//! copying them into a fresh `String` per file is pure overhead. On Unix,
+//! [`with_file_bytes`] maps the file read-only and hands out the mapping;
+//! elsewhere, and for empty files or failed mappings, it reads into a
+//! per-thread buffer that grows to the largest file the thread has seen
+//! and is reused for the next one.
+//!
+//! Mappings are private, and a file whose size changed between `open` and
+//! the mapping (an editor saving mid-run) is read into the buffer instead.
+//! A file truncated by another process later, while it is being parsed,
+//! can still fault, so only the explicit fast mode reads this way; the
+//! default path keeps using `fs::read_to_string`.
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+thread_local! {
+    /// Fallback read buffer, reused by every file this thread reads.
+    static READ_BUF: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Run `f` over the bytes of the file at `path`.
+pub(crate) fn with_file_bytes<R>(path: &Path, f: impl FnOnce(&[u8]) -> R) -> io::Result<R> {
+    let mut file = File::open(path)?;
+    let len = usize::try_from(file.metadata()?.len()).unwrap_or(usize::MAX);
+
+    #[cfg(unix)]
+    if let Some(map) = Mapping::new(&file, len) {
+        // A file being rewritten may not fill the mapping; read it instead
+        if usize::try_from(file.metadata()?.len()).ok() == Some(len) {
+            return Ok(f(map.bytes()));
+        }
+    }
+
+    READ_BUF.with(|buf| {
+        let mut buf = buf.borrow_mut();
+        buf.clear();
+        buf.reserve(len);
+        file.read_to_end(&mut buf)?;
+        Ok(f(&buf))
+    })
+}
+
+/// A private read-only mapping of a whole file, unmapped on drop.
+#[cfg(unix)]
+struct Mapping {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+#[cfg(unix)]
+impl Mapping {
+    /// Map `len` bytes of `file`; `None` for empty files or when mapping fails.
+    fn new(file: &File, len: usize) -> Option<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        if len == 0 || len == usize::MAX {
+            return None;
+        }
+        // SAFETY: a fresh PROT_READ/MAP_PRIVATE mapping of an open file
+        // descriptor; the kernel picks the address and failure is checked.
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        (ptr != libc::MAP_FAILED).then_some(Self { ptr, len })
+    }
+
+    fn bytes(&self) -> &[u8] {
+        // SAFETY: `ptr` is a live private mapping of `len` readable bytes
+        // until drop, and the returned slice borrows `self`. The file had
+        // `len` bytes after mapping (checked by the caller); truncating it
+        // while the slice is alive is the one remaining fault, accepted for
+        // the opt-in fast mode only.
+        unsafe { std::slice::from_raw_parts(self.ptr.cast::<u8>(), self.len) }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        // SAFETY: `ptr`/`len` are exactly what mmap returned, unmapped once.
+        unsafe {
+            libc::munmap(self.ptr, self.len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_file_bytes() {
+        let dir = std::env::temp_dir().join(format!("deadmod_mapped_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("a.rs");
+        let empty = dir.join("empty.rs");
+        std::fs::write(&file, "mod net;\n").unwrap();
+        std::fs::write(&empty, "").unwrap();
+
+        assert_eq!(
+            with_file_bytes(&file, |b| b.to_vec()).unwrap(),
+            b"mod net;\n"
+        );
+        assert_eq!(with_file_bytes(&empty, |b| b.len()).unwrap(), 0);
+        assert!(with_file_bytes(&dir.join("missing.rs"), |b| b.len()).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
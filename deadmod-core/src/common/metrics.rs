@@ -0,0 +1,122 @@
+//! Size and complexity of function bodies.
+//!
+//! Dead functions differ a lot in how much code they represent; these
+//! numbers let reports rank them. Complexity is a McCabe-style estimate from
+//! the syntax tree: 1, plus one per branch point (`if`, loop, extra match
+//! arm, `&&`/`||`, `?`). Nested fns are separate items and not counted;
+//! closures are.
+
+use serde::{Deserialize, Serialize};
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{BinOp, Block, Expr, FnArg, ItemFn, Signature};
+
+/// Size and complexity of one function.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FnMetrics {
+    /// Lines spanned by the body, braces included (0 without a body)
+    pub lines: usize,
+    /// Parameters, not counting a `self` receiver
+    pub params: usize,
+    /// Estimated cyclomatic complexity (at least 1)
+    pub complexity: usize,
+}
+
+impl FnMetrics {
+    /// Measure a function from its signature and optional body.
+    pub fn of(sig: &Signature, body: Option<&Block>) -> Self {
+        let params = sig
+            .inputs
+            .iter()
+            .filter(|arg| matches!(arg, FnArg::Typed(_)))
+            .count();
+        let Some(body) = body else {
+            return Self {
+                lines: 0,
+                params,
+                complexity: 1,
+            };
+        };
+
+        let span = body.span();
+        let mut counter = BranchCounter { branches: 0 };
+        counter.visit_block(body);
+        Self {
+            lines: span.end().line.saturating_sub(span.start().line) + 1,
+            params,
+            complexity: 1 + counter.branches,
+        }
+    }
+}
+
+struct BranchCounter {
+    branches: usize,
+}
+
+impl<'ast> Visit<'ast> for BranchCounter {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        self.branches += match expr {
+            Expr::If(_) | Expr::While(_) | Expr::ForLoop(_) | Expr::Loop(_) | Expr::Try(_) => 1,
+            Expr::Match(m) => m.arms.len().saturating_sub(1),
+            Expr::Binary(b) if matches!(b.op, BinOp::And(_) | BinOp::Or(_)) => 1,
+            _ => 0,
+        };
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_item_fn(&mut self, _: &'ast ItemFn) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(source: &str) -> FnMetrics {
+        let item: ItemFn = syn::parse_str(source).unwrap();
+        FnMetrics::of(&item.sig, Some(&item.block))
+    }
+
+    #[test]
+    fn test_straight_line_function() {
+        assert_eq!(
+            metrics("fn f(a: u8, b: u8) -> u8 { a + b }"),
+            FnMetrics {
+                lines: 1,
+                params: 2,
+                complexity: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_branches_counted() {
+        let m = metrics(
+            "fn f(x: Option<u8>) -> Result<u8, E> {
+                if x.is_some() && x != Some(3) {
+                    return Ok(1);
+                }
+                for _ in 0..3 {}
+                let y = match x { Some(0) => 0, Some(_) => 1, None => g()? };
+                fn nested() { if true {} }
+                Ok(y)
+            }",
+        );
+        // if, &&, for, 2 extra arms, ?
+        assert_eq!(m.complexity, 7);
+        assert_eq!(m.lines, 9);
+        assert_eq!(m.params, 1);
+    }
+
+    #[test]
+    fn test_receiver_and_missing_body() {
+        let sig: Signature = syn::parse_str("fn f(&self, n: usize)").unwrap();
+        assert_eq!(
+            FnMetrics::of(&sig, None),
+            FnMetrics {
+                lines: 0,
+                params: 1,
+                complexity: 1
+            }
+        );
+    }
+}
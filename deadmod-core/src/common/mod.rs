@@ -3,10 +3,41 @@
 //! This module provides shared functionality to reduce code duplication
 //! across the various extractor and analyzer modules.
 
-mod visibility;
-mod path_builder;
+mod async_calls;
+mod confidence;
+mod finding_id;
 mod graph_trait;
+mod impls;
+mod interner;
+mod lifecycle;
+mod liveness;
+mod macro_args;
+mod mapped;
+mod metrics;
+mod path_builder;
+mod pub_policy;
+mod span_edit;
+mod visibility;
 
-pub use visibility::visibility_str;
-pub use path_builder::ModulePathBuilder;
+pub use async_calls::function_args;
+pub(crate) use async_calls::{is_select_macro, parse_select_arms};
+pub use confidence::{
+    extract_macro_mentions, has_cfg_attr, is_cfg_test, Confidence, ConfidenceSignals, Resolution,
+};
+pub use finding_id::{finding_id, ignored_ids, module_id, IGNORE_MARKER};
 pub use graph_trait::GraphTraversal;
+pub use impls::{dead_impls, DeadImpl, ImplSpan};
+pub use interner::{Interner, Symbol};
+pub use lifecycle::{
+    deprecation, is_doc_hidden, keep_annotation, Deprecation, HiddenApiPolicy, Keep, KeptItem,
+};
+pub use liveness::{
+    attribute_paths, LivenessRules, DEFAULT_ALIVE_ATTRIBUTES, DEFAULT_REGISTRY_MACROS,
+};
+pub use macro_args::{macro_paths, visit_macro_args, MacroPath};
+pub(crate) use mapped::with_file_bytes;
+pub use metrics::FnMetrics;
+pub use path_builder::ModulePathBuilder;
+pub use pub_policy::PubPolicy;
+pub use span_edit::SourceEdits;
+pub use visibility::visibility_str;
@@ -0,0 +1,128 @@
+//! Whether `pub` functions are assumed to be used from outside the crate.
+//!
+//! Treating every `pub` function as an entry point is right for a library
+//! with dependents, but hides dead `pub` functions in binaries and internal
+//! crates. [`PubPolicy::Auto`] only exempts them for a library that some
+//! other workspace member depends on (or that lives outside a workspace, where
+//! its dependents cannot be seen).
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::workspace::{is_library_crate, workspace_dependents};
+
+/// Whether `pub` functions count as entry points.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PubPolicy {
+    /// Only for libraries with dependents (or unknown dependents)
+    Auto,
+    /// Always: every `pub` function may be used externally
+    #[default]
+    Always,
+    /// Never: `pub` functions must be reached from another entry point
+    Never,
+}
+
+impl PubPolicy {
+    /// All policies, in documentation order.
+    pub const ALL: [PubPolicy; 3] = [Self::Auto, Self::Always, Self::Never];
+
+    /// Lowercase name used in CLI flags and deadmod.toml.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Always => "always",
+            Self::Never => "never",
+        }
+    }
+
+    /// Whether `pub` functions of the crate at `crate_root` are entry points.
+    ///
+    /// `Auto` answers yes when the crate cannot be inspected (no
+    /// `Cargo.toml`), so in-memory sources keep the conservative behavior.
+    pub fn pub_is_reachable(&self, crate_root: &Path) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => {
+                if !crate_root.join("Cargo.toml").is_file() {
+                    return true;
+                }
+                is_library_crate(crate_root)
+                    && workspace_dependents(crate_root).is_none_or(|deps| !deps.is_empty())
+            }
+        }
+    }
+}
+
+impl fmt::Display for PubPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PubPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|p| p.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "unknown pub policy '{}' (expected auto, always or never)",
+                    s
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_pub_policy_from_str() {
+        assert_eq!("auto".parse::<PubPolicy>(), Ok(PubPolicy::Auto));
+        assert_eq!("NEVER".parse::<PubPolicy>(), Ok(PubPolicy::Never));
+        assert!("sometimes".parse::<PubPolicy>().is_err());
+        assert_eq!(PubPolicy::default(), PubPolicy::Always);
+    }
+
+    #[test]
+    fn test_pub_policy_auto() {
+        let ws = std::env::temp_dir().join(format!("deadmod_pub_policy_{}", std::process::id()));
+        let write = |rel: &str, content: &str| {
+            let path = ws.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write(
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"core\", \"app\", \"tool\"]\n",
+        );
+        write("core/Cargo.toml", "[package]\nname = \"my-core\"\n");
+        write("core/src/lib.rs", "");
+        write("tool/Cargo.toml", "[package]\nname = \"tool\"\n");
+        write("tool/src/lib.rs", "");
+        write(
+            "app/Cargo.toml",
+            "[package]\nname = \"app\"\n\n[dependencies]\nmy-core = { path = \"../core\" }\n",
+        );
+        write("app/src/main.rs", "");
+
+        assert!(PubPolicy::Auto.pub_is_reachable(&ws.join("core")));
+        assert!(!PubPolicy::Auto.pub_is_reachable(&ws.join("tool")));
+        assert!(!PubPolicy::Auto.pub_is_reachable(&ws.join("app")));
+        assert!(PubPolicy::Always.pub_is_reachable(&ws.join("app")));
+        assert!(!PubPolicy::Never.pub_is_reachable(&ws.join("core")));
+        assert!(PubPolicy::Auto.pub_is_reachable(Path::new("")));
+
+        fs::remove_dir_all(&ws).ok();
+    }
+}
@@ -0,0 +1,113 @@
+//! Removing syntax nodes from source text by their spans.
+//!
+//! Rewrites that must keep the rest of a file byte-for-byte intact (comments,
+//! formatting) cannot round-trip through syn. [`SourceEdits`] maps spans back
+//! to byte ranges and deletes them, taking whole lines when a node is alone
+//! on its lines so no blank gaps are left behind.
+
+use std::ops::Range;
+
+use proc_macro2::{LineColumn, Span};
+
+/// Pending removals from one source text.
+#[derive(Debug)]
+pub struct SourceEdits<'a> {
+    content: &'a str,
+    line_starts: Vec<usize>,
+    removals: Vec<Range<usize>>,
+}
+
+impl<'a> SourceEdits<'a> {
+    /// Start editing `content` (the text the spans were parsed from).
+    pub fn new(content: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            content,
+            line_starts,
+            removals: Vec::new(),
+        }
+    }
+
+    /// Byte offset of a span position.
+    pub fn offset(&self, pos: LineColumn) -> usize {
+        let Some(&start) = self.line_starts.get(pos.line.saturating_sub(1)) else {
+            return self.content.len();
+        };
+        self.content[start..]
+            .char_indices()
+            .nth(pos.column)
+            .map_or(self.content.len(), |(i, _)| start + i)
+    }
+
+    /// Byte range covered by `span`.
+    pub fn range(&self, span: Span) -> Range<usize> {
+        self.offset(span.start())..self.offset(span.end())
+    }
+
+    /// Delete `range`, widened to whole lines when nothing else shares them.
+    pub fn remove(&mut self, range: Range<usize>) {
+        let line_start = self.content[..range.start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.content[range.end..]
+            .find('\n')
+            .map_or(self.content.len(), |i| range.end + i + 1);
+        let alone = self.content[line_start..range.start].trim().is_empty()
+            && self.content[range.end..line_end].trim().is_empty();
+        self.removals
+            .push(if alone { line_start..line_end } else { range });
+    }
+
+    /// Whether nothing has been removed.
+    pub fn is_empty(&self) -> bool {
+        self.removals.is_empty()
+    }
+
+    /// The edited text. Overlapping removals are merged.
+    pub fn apply(mut self) -> String {
+        self.removals.sort_by_key(|r| r.start);
+        let mut out = String::with_capacity(self.content.len());
+        let mut pos = 0;
+        for range in &self.removals {
+            if range.start > pos {
+                out.push_str(&self.content[pos..range.start]);
+            }
+            pos = pos.max(range.end);
+        }
+        out.push_str(&self.content[pos..]);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::spanned::Spanned;
+
+    #[test]
+    fn test_remove_whole_lines_and_inline_ranges() {
+        let content = "enum E {\n    /// doc\n    A,\n    B, C,\n}\n";
+        let item: syn::ItemEnum = syn::parse_str(content).unwrap();
+        let mut edits = SourceEdits::new(content);
+
+        let pairs: Vec<_> = item.variants.pairs().collect();
+        let a = pairs[0].value().span();
+        let a_comma = pairs[0].punct().unwrap().span();
+        edits.remove(edits.offset(a.start())..edits.offset(a_comma.end()));
+        let c = edits.range(pairs[2].value().span());
+        edits.remove(c);
+
+        assert_eq!(edits.apply(), "enum E {\n    B, ,\n}\n");
+    }
+
+    #[test]
+    fn test_offset_counts_chars() {
+        let content = "// é\nfn ü() {}\n";
+        let edits = SourceEdits::new(content);
+        assert_eq!(edits.offset(LineColumn { line: 2, column: 3 }), 9);
+        assert_eq!(
+            &content[edits.offset(LineColumn { line: 2, column: 4 })..],
+            "() {}\n"
+        );
+    }
+}
@@ -1,9 +1,19 @@
 //! Configuration loading from deadmod.toml.
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use serde::Deserialize;
 use std::{fs, path::Path};
 
+use crate::arch::LayerRules;
+use crate::common::{HiddenApiPolicy, LivenessRules, PubPolicy};
+use crate::dirty::Detector;
+use crate::error::DeadmodError;
+use crate::limits::Limits;
+use crate::macros::ExportedMacroPolicy;
+use crate::parse::EdgeKinds;
+use crate::report::PathStyle;
+use crate::scan::GeneratedMarkers;
+
 /// Main configuration structure for deadmod.toml.
 #[derive(Debug, Deserialize, Default)]
 pub struct DeadmodConfig {
@@ -11,6 +21,83 @@ pub struct DeadmodConfig {
     pub ignore: Option<Vec<String>>,
     /// Output configuration.
     pub output: Option<OutputConfig>,
+    /// Layering rules for `deadmod layers`.
+    pub layers: Option<LayerRules>,
+    /// Treatment of `#[doc(hidden)] pub` functions: "public", "downgrade" or
+    /// "exclude". Other item kinds ignore it.
+    pub doc_hidden: Option<HiddenApiPolicy>,
+    /// Extra attributes and registry macros that keep items alive.
+    pub liveness: Option<LivenessRules>,
+    /// Whether `pub` functions are entry points: "auto", "always" or "never".
+    pub assume_pub_reachable: Option<PubPolicy>,
+    /// Exported macros unused in their crate: "assume-used", "report" or "workspace-check".
+    pub exported_macros: Option<ExportedMacroPolicy>,
+    /// Caps on file size, file count and analysis time.
+    pub limits: Option<Limits>,
+    /// Extra header markers of generated files.
+    pub generated: Option<GeneratedMarkers>,
+    /// Reference kinds that create module edges: "mod", "use", "pub_use", "path".
+    pub edges: Option<EdgeKinds>,
+    /// Days findings on newly added lines are reported as information.
+    pub grace_period_days: Option<u32>,
+    /// Days after which recorded false positives are listed for re-review.
+    pub suppression_review_days: Option<u32>,
+    /// Detectors run by all-detector analyses.
+    pub detectors: Option<DetectorToggles>,
+}
+
+/// The `[detectors]` table: detectors to run when analyzing with all of
+/// them (`--group-by module`, `self-check`, the LSP, ...). Missing keys stay
+/// enabled; unknown keys are errors, so a typo does not go unnoticed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DetectorToggles {
+    /// Dead modules
+    pub modules: bool,
+    /// Functions and methods
+    pub functions: bool,
+    /// Trait and impl methods
+    pub traits: bool,
+    /// Constants and statics
+    pub constants: bool,
+    /// Enum variants
+    pub enums: bool,
+    /// Macros
+    pub macros: bool,
+    /// Generic parameters
+    pub generics: bool,
+    /// Match arms
+    pub match_arms: bool,
+}
+
+impl Default for DetectorToggles {
+    fn default() -> Self {
+        Self {
+            modules: true,
+            functions: true,
+            traits: true,
+            constants: true,
+            enums: true,
+            macros: true,
+            generics: true,
+            match_arms: true,
+        }
+    }
+}
+
+impl DetectorToggles {
+    /// Whether the item-level `detector` is enabled.
+    pub fn enables(&self, detector: Detector) -> bool {
+        match detector {
+            Detector::Functions => self.functions,
+            Detector::Traits => self.traits,
+            Detector::Constants => self.constants,
+            Detector::Enums => self.enums,
+            Detector::Macros => self.macros,
+            Detector::Generics => self.generics,
+            Detector::MatchArms => self.match_arms,
+        }
+    }
 }
 
 /// Output format configuration.
@@ -18,6 +105,8 @@ pub struct DeadmodConfig {
 pub struct OutputConfig {
     /// Output format: "plain" or "json".
     pub format: Option<String>,
+    /// File paths in reports: "absolute", "relative" or "crate".
+    pub paths: Option<PathStyle>,
 }
 
 /// Loads configuration from deadmod.toml if it exists.
@@ -28,13 +117,19 @@ pub fn load_config(root: &Path) -> Result<Option<DeadmodConfig>> {
     }
 
     let content = fs::read_to_string(&path)?;
-    let cfg = toml::from_str(&content).context("Invalid deadmod.toml")?;
+    let cfg = toml::from_str(&content).map_err(|e| {
+        let line = e
+            .span()
+            .map(|span| content[..span.start].matches('\n').count() + 1);
+        DeadmodError::config_invalid(&path, line, e.message())
+    })?;
     Ok(Some(cfg))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse::EdgeKind;
     use std::fs;
 
     #[test]
@@ -133,6 +228,183 @@ format = "plain"
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_load_config_with_doc_hidden() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_config_hidden_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("deadmod.toml"), "doc_hidden = \"exclude\"\n").unwrap();
+
+        let cfg = load_config(&dir).unwrap().unwrap();
+        assert_eq!(cfg.doc_hidden, Some(HiddenApiPolicy::Exclude));
+
+        fs::write(dir.join("deadmod.toml"), "doc_hidden = \"maybe\"\n").unwrap();
+        assert!(load_config(&dir).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_load_config_with_pub_policy() {
+        let dir = std::env::temp_dir().join(format!("deadmod_config_pub_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("deadmod.toml"),
+            "assume_pub_reachable = \"auto\"\n",
+        )
+        .unwrap();
+
+        let cfg = load_config(&dir).unwrap().unwrap();
+        assert_eq!(cfg.assume_pub_reachable, Some(PubPolicy::Auto));
+        assert_eq!(cfg.exported_macros, None);
+
+        fs::write(
+            dir.join("deadmod.toml"),
+            "exported_macros = \"assume-used\"\n",
+        )
+        .unwrap();
+        let cfg = load_config(&dir).unwrap().unwrap();
+        assert_eq!(cfg.exported_macros, Some(ExportedMacroPolicy::AssumeUsed));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_load_config_with_detectors() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_config_detectors_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("deadmod.toml"),
+            "[detectors]\nmatch_arms = false\ngenerics = false\n",
+        )
+        .unwrap();
+
+        let toggles = load_config(&dir).unwrap().unwrap().detectors.unwrap();
+        assert!(toggles.modules && toggles.functions);
+        assert!(!toggles.enables(Detector::MatchArms));
+        assert!(!toggles.enables(Detector::Generics));
+        assert!(toggles.enables(Detector::Macros));
+
+        fs::write(dir.join("deadmod.toml"), "[detectors]\nmatch_arm = false\n").unwrap();
+        assert!(load_config(&dir).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_load_config_with_limits() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_config_limits_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("deadmod.toml"),
+            "[limits]\nmax_file_size = 1000000\ntimeout = 60\n",
+        )
+        .unwrap();
+
+        let cfg = load_config(&dir).unwrap().unwrap();
+        assert_eq!(
+            cfg.limits,
+            Some(
+                Limits::default()
+                    .with_max_file_size(1_000_000)
+                    .with_timeout(60)
+            )
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_load_config_with_liveness() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_config_liveness_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("deadmod.toml"),
+            r#"
+[liveness]
+attributes = ["my_framework::register"]
+"#,
+        )
+        .unwrap();
+
+        let cfg = load_config(&dir).unwrap().unwrap();
+        let rules = LivenessRules::default().with_extra(cfg.liveness.unwrap());
+        assert!(rules.attributes.contains(&"used".to_string()));
+        assert!(rules
+            .attributes
+            .contains(&"my_framework::register".to_string()));
+        assert_eq!(rules.registry_macros, vec!["inventory::submit".to_string()]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_load_config_with_layers() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_config_layers_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("deadmod.toml"),
+            r#"
+[[layers.rule]]
+from = "domain*"
+deny = ["infra*", "api"]
+"#,
+        )
+        .unwrap();
+
+        let cfg = load_config(&dir).unwrap().unwrap();
+        let layers = cfg.layers.unwrap();
+        assert_eq!(layers.rules.len(), 1);
+        assert_eq!(layers.rules[0].deny, vec!["infra*", "api"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_load_config_with_generated_markers() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_config_generated_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("deadmod.toml"),
+            r#"
+[generated]
+markers = ["Code generated by sqlc"]
+"#,
+        )
+        .unwrap();
+
+        let cfg = load_config(&dir).unwrap().unwrap();
+        assert_eq!(
+            cfg.generated.unwrap().markers,
+            vec!["Code generated by sqlc"]
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_load_config_with_edges() {
+        let dir = std::env::temp_dir().join(format!("deadmod_config_edges_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("deadmod.toml"), r#"edges = ["mod", "path"]"#).unwrap();
+
+        let cfg = load_config(&dir).unwrap().unwrap();
+        assert_eq!(
+            cfg.edges.unwrap(),
+            EdgeKinds::new([EdgeKind::Mod, EdgeKind::Path])
+        );
+
+        fs::write(dir.join("deadmod.toml"), r#"edges = ["imports"]"#).unwrap();
+        assert!(load_config(&dir).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_load_config_invalid_toml() {
         let dir = std::env::temp_dir().join(format!("deadmod_config_invalid_{}", std::process::id()));
@@ -142,6 +414,17 @@ format = "plain"
         let result = load_config(&dir);
         assert!(result.is_err());
 
+        fs::write(
+            dir.join("deadmod.toml"),
+            "ignore = [\"a\"]\ngrace_period_days = \"soon\"\n",
+        )
+        .unwrap();
+        let err = load_config(&dir).unwrap_err();
+        match err.downcast_ref::<DeadmodError>() {
+            Some(DeadmodError::ConfigInvalid { line, .. }) => assert_eq!(*line, Some(2)),
+            other => panic!("expected ConfigInvalid, got {:?}", other),
+        }
+
         fs::remove_dir_all(&dir).ok();
     }
 }
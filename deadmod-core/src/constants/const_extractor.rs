@@ -10,9 +10,12 @@
 
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use syn::{visit::Visit, File, ImplItem, Item, ItemConst, ItemImpl, ItemMod, ItemStatic, Visibility};
+use syn::{
+    visit::Visit, Attribute, File, Ident, ImplItem, Item, ItemConst, ItemImpl, ItemMod, ItemStatic,
+    Visibility,
+};
 
-use crate::common::visibility_str;
+use crate::common::{attribute_paths, has_cfg_attr, keep_annotation, visibility_str, Keep};
 
 /// Information about a constant or static definition.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +24,9 @@ pub struct ConstDef {
     pub name: String,
     /// Source file path
     pub file: String,
+    /// 1-based line of the name
+    #[serde(default)]
+    pub line: usize,
     /// Whether this is a static (vs const)
     pub is_static: bool,
     /// Whether this is mutable (static mut)
@@ -31,6 +37,15 @@ pub struct ConstDef {
     pub module_path: String,
     /// If inside an impl block, the type name
     pub impl_type: Option<String>,
+    /// Whether the item has #[cfg] or #[cfg_attr] attributes
+    #[serde(default)]
+    pub has_cfg: bool,
+    /// `#[deadmod::keep]` details, if the item is kept on purpose
+    #[serde(default)]
+    pub keep: Option<Keep>,
+    /// Attribute paths, for [`LivenessRules`](crate::common::LivenessRules)
+    #[serde(default)]
+    pub attributes: Vec<String>,
 }
 
 /// AST visitor that extracts all constant definitions.
@@ -39,6 +54,8 @@ struct ConstExtractor {
     results: Vec<ConstDef>,
     current_mod: Vec<String>,
     current_impl: Option<String>,
+    /// `#[deadmod::keep]` on the enclosing impl block
+    impl_keep: Option<Keep>,
 }
 
 impl ConstExtractor {
@@ -48,6 +65,7 @@ impl ConstExtractor {
             results: Vec::with_capacity(16),
             current_mod: Vec::new(),
             current_impl: None,
+            impl_keep: None,
         }
     }
 
@@ -55,27 +73,41 @@ impl ConstExtractor {
         self.current_mod.join("::")
     }
 
-    fn record_const(&mut self, name: &str, vis: &Visibility) {
+    fn record_const(&mut self, ident: &Ident, vis: &Visibility, attrs: &[Attribute]) {
         self.results.push(ConstDef {
-            name: name.to_string(),
+            name: ident.to_string(),
             file: self.file_path.clone(),
+            line: ident.span().start().line,
             is_static: false,
             is_mutable: false,
             visibility: visibility_str(vis).to_string(),
             module_path: self.build_module_path(),
             impl_type: self.current_impl.clone(),
+            has_cfg: has_cfg_attr(attrs),
+            keep: keep_annotation(attrs).or_else(|| self.impl_keep.clone()),
+            attributes: attribute_paths(attrs),
         });
     }
 
-    fn record_static(&mut self, name: &str, vis: &Visibility, is_mut: bool) {
+    fn record_static(
+        &mut self,
+        ident: &Ident,
+        vis: &Visibility,
+        is_mut: bool,
+        attrs: &[Attribute],
+    ) {
         self.results.push(ConstDef {
-            name: name.to_string(),
+            name: ident.to_string(),
             file: self.file_path.clone(),
+            line: ident.span().start().line,
             is_static: true,
             is_mutable: is_mut,
             visibility: visibility_str(vis).to_string(),
             module_path: self.build_module_path(),
             impl_type: self.current_impl.clone(),
+            has_cfg: has_cfg_attr(attrs),
+            keep: keep_annotation(attrs).or_else(|| self.impl_keep.clone()),
+            attributes: attribute_paths(attrs),
         });
     }
 }
@@ -83,35 +115,43 @@ impl ConstExtractor {
 impl<'ast> Visit<'ast> for ConstExtractor {
     fn visit_item(&mut self, item: &'ast Item) {
         match item {
-            Item::Const(ItemConst { ident, vis, .. }) => {
-                self.record_const(&ident.to_string(), vis);
+            Item::Const(ItemConst {
+                ident, vis, attrs, ..
+            }) => {
+                self.record_const(ident, vis, attrs);
             }
 
             Item::Static(ItemStatic {
                 ident,
                 vis,
                 mutability,
+                attrs,
                 ..
             }) => {
                 // In syn 2.x, mutability is StaticMutability enum, not Option
                 let is_mut = matches!(mutability, syn::StaticMutability::Mut(_));
-                self.record_static(&ident.to_string(), vis, is_mut);
+                self.record_static(ident, vis, is_mut, attrs);
             }
 
             Item::Impl(ItemImpl {
-                self_ty, items, ..
+                self_ty,
+                items,
+                attrs,
+                ..
             }) => {
                 // Extract type name for impl block
                 let type_name = extract_type_name(self_ty);
                 self.current_impl = Some(type_name);
+                self.impl_keep = keep_annotation(attrs);
 
                 for impl_item in items {
                     if let ImplItem::Const(c) = impl_item {
-                        self.record_const(&c.ident.to_string(), &c.vis);
+                        self.record_const(&c.ident, &c.vis, &c.attrs);
                     }
                 }
 
                 self.current_impl = None;
+                self.impl_keep = None;
             }
 
             Item::Mod(ItemMod {
@@ -177,6 +217,29 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_extract_keep_annotation() {
+        let content = r#"
+#[deadmod::keep(reason = "read by the linker script")]
+static BOOT_MAGIC: u32 = 0;
+
+#[deadmod::keep]
+impl Limits {
+    const MAX: usize = 8;
+}
+
+const PLAIN: i32 = 1;
+"#;
+        let result = extract_constants(&PathBuf::from("test.rs"), content);
+        let keep = |name: &str| result.iter().find(|c| c.name == name).unwrap().keep.clone();
+        assert_eq!(
+            keep("BOOT_MAGIC").unwrap().reason.as_deref(),
+            Some("read by the linker script")
+        );
+        assert!(keep("MAX").is_some());
+        assert!(keep("PLAIN").is_none());
+    }
+
     #[test]
     fn test_extract_const() {
         let content = r#"
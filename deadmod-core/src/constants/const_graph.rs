@@ -4,6 +4,9 @@
 //!
 //! Entry points (always considered reachable):
 //! - `pub` constants (could be used by external crates)
+//! - `#[deadmod::keep]` constants (listed in [`ConstAnalysisResult::kept`])
+//! - Statics kept alive by the linker or a registry (`#[used]`,
+//!   `#[link_section]`, `#[linkme::distributed_slice]`), see [`LivenessRules`]
 //!
 //! Performance characteristics:
 //! - Graph build: O(|C| + |U|) where C = constants, U = usages
@@ -13,6 +16,7 @@ use std::collections::HashSet;
 
 use super::const_extractor::ConstDef;
 use super::const_usage::ConstUsageResult;
+use crate::common::{Confidence, ConfidenceSignals, KeptItem, LivenessRules};
 
 /// A dead constant that was declared but never used.
 #[derive(Debug, Clone)]
@@ -25,10 +29,14 @@ pub struct DeadConst {
     pub visibility: String,
     /// Source file
     pub file: String,
+    /// 1-based line of the name
+    pub line: usize,
     /// Module path
     pub module_path: String,
     /// Impl type if applicable
     pub impl_type: Option<String>,
+    /// Confidence that the constant is really unused
+    pub confidence: Confidence,
 }
 
 /// Statistics about constant analysis.
@@ -47,10 +55,22 @@ pub struct ConstStats {
 pub struct ConstAnalysisResult {
     /// All dead constants found
     pub dead: Vec<DeadConst>,
+    /// Constants kept alive by `#[deadmod::keep]`
+    pub kept: Vec<KeptItem>,
     /// Statistics
     pub stats: ConstStats,
 }
 
+impl ConstAnalysisResult {
+    /// Drop dead constants below `min` confidence and update the dead counts.
+    pub fn retain_confidence(&mut self, min: Confidence) {
+        self.dead.retain(|c| c.confidence >= min);
+        self.stats.dead_count = self.dead.len();
+        self.stats.dead_static_count = self.dead.iter().filter(|c| c.is_static).count();
+        self.stats.dead_const_count = self.stats.dead_count - self.stats.dead_static_count;
+    }
+}
+
 /// Graph for analyzing constant usage.
 #[derive(Default)]
 pub struct ConstGraph {
@@ -58,6 +78,12 @@ pub struct ConstGraph {
     declared: Vec<ConstDef>,
     /// Set of used constant names
     used: HashSet<String>,
+    /// Identifiers mentioned inside macro invocations (confidence signal)
+    macro_mentions: HashSet<String>,
+    /// Attributes that make a constant live
+    liveness: LivenessRules,
+    /// Identifiers passed to registry macros
+    registered: HashSet<String>,
 }
 
 impl ConstGraph {
@@ -69,12 +95,42 @@ impl ConstGraph {
             used.extend(usage.used_constants.clone());
         }
 
-        Self { declared, used }
+        Self {
+            declared,
+            used,
+            macro_mentions: HashSet::new(),
+            liveness: LivenessRules::default(),
+            registered: HashSet::new(),
+        }
+    }
+
+    /// Provide identifiers found inside macro invocations.
+    ///
+    /// Dead constants whose name is mentioned in a macro are reported with
+    /// lower confidence.
+    pub fn with_macro_mentions(mut self, mentions: HashSet<String>) -> Self {
+        self.macro_mentions = mentions;
+        self
     }
 
-    /// Check if a constant is used.
+    /// Treat constants carrying one of `rules.attributes` as used.
+    pub fn with_liveness(mut self, rules: LivenessRules) -> Self {
+        self.liveness = rules;
+        self
+    }
+
+    /// Treat constants named in registry macros as used, see
+    /// [`LivenessRules::registered_names`].
+    pub fn with_registered(mut self, names: HashSet<String>) -> Self {
+        self.registered = names;
+        self
+    }
+
+    /// Check if a constant is used, referenced by the linker or registered.
     fn is_const_used(&self, c: &ConstDef) -> bool {
         self.used.contains(&c.name)
+            || self.registered.contains(&c.name)
+            || self.liveness.alive_attribute(&c.attributes).is_some()
     }
 
     /// Find all dead constants.
@@ -85,14 +141,22 @@ impl ConstGraph {
         let mut dead = Vec::new();
 
         for c in &self.declared {
-            if !self.is_const_used(c) {
+            if c.keep.is_none() && !self.is_const_used(c) {
                 dead.push(DeadConst {
                     name: c.name.clone(),
                     is_static: c.is_static,
                     visibility: c.visibility.clone(),
                     file: c.file.clone(),
+                    line: c.line,
                     module_path: c.module_path.clone(),
                     impl_type: c.impl_type.clone(),
+                    confidence: ConfidenceSignals {
+                        visibility: &c.visibility,
+                        has_cfg: c.has_cfg,
+                        in_macro: self.macro_mentions.contains(&c.name),
+                        ..Default::default()
+                    }
+                    .score(),
                 });
             }
         }
@@ -116,7 +180,17 @@ impl ConstGraph {
             dead_static_count: dead.iter().filter(|c| c.is_static).count(),
         };
 
-        ConstAnalysisResult { dead, stats }
+        let kept = self
+            .declared
+            .iter()
+            .filter_map(|c| {
+                c.keep
+                    .as_ref()
+                    .map(|keep| KeptItem::new(&c.name, &c.file, keep))
+            })
+            .collect();
+
+        ConstAnalysisResult { dead, kept, stats }
     }
 
     /// Get the total number of declared constants.
@@ -133,11 +207,15 @@ mod tests {
         ConstDef {
             name: name.to_string(),
             file: file.to_string(),
+            line: 0,
             is_static,
             is_mutable: false,
             visibility: "private".to_string(),
             module_path: String::new(),
             impl_type: None,
+            has_cfg: false,
+            keep: None,
+            attributes: Vec::new(),
         }
     }
 
@@ -219,4 +297,61 @@ mod tests {
         assert_eq!(result.stats.dead_const_count, 1);
         assert_eq!(result.stats.dead_static_count, 1);
     }
+
+    #[test]
+    fn test_kept_const_is_not_dead() {
+        let mut kept = make_const("KEPT", false, "a.rs");
+        kept.keep = Some(crate::common::Keep {
+            reason: Some("used by build.rs".to_string()),
+        });
+        let declared = vec![kept, make_const("UNUSED", false, "a.rs")];
+
+        let result = ConstGraph::new(declared, &[]).analyze();
+        assert_eq!(result.stats.dead_count, 1);
+        assert_eq!(result.dead[0].name, "UNUSED");
+        assert_eq!(result.kept.len(), 1);
+        assert_eq!(result.kept[0].reason.as_deref(), Some("used by build.rs"));
+    }
+
+    #[test]
+    fn test_linker_kept_statics_are_not_dead() {
+        let mut used = make_const("KEEP_SYMBOL", true, "a.rs");
+        used.attributes = vec!["used".to_string()];
+        let mut section = make_const("INIT", true, "a.rs");
+        section.attributes = vec!["link_section".to_string()];
+        let declared = vec![
+            used,
+            section,
+            make_const("REGISTERED", true, "a.rs"),
+            make_const("UNUSED", true, "a.rs"),
+        ];
+
+        let result = ConstGraph::new(declared, &[])
+            .with_registered(HashSet::from(["REGISTERED".to_string()]))
+            .analyze();
+        assert_eq!(result.stats.dead_count, 1);
+        assert_eq!(result.dead[0].name, "UNUSED");
+    }
+
+    #[test]
+    fn test_dead_const_confidence() {
+        let mut gated = make_const("GATED", false, "a.rs");
+        gated.has_cfg = true;
+        let mut public = make_const("PUBLIC", false, "a.rs");
+        public.visibility = "pub".to_string();
+        let declared = vec![make_const("PLAIN", false, "a.rs"), gated, public];
+
+        let graph = ConstGraph::new(declared, &[]);
+        let dead = graph.find_dead();
+
+        let confidence = |name: &str| dead.iter().find(|d| d.name == name).unwrap().confidence;
+        assert_eq!(confidence("PLAIN"), Confidence::High);
+        assert_eq!(confidence("GATED"), Confidence::Medium);
+        assert_eq!(confidence("PUBLIC"), Confidence::Low);
+
+        let mut result = graph.analyze();
+        result.retain_confidence(Confidence::Medium);
+        assert_eq!(result.stats.dead_count, 2);
+        assert!(result.dead.iter().all(|d| d.name != "PUBLIC"));
+    }
 }
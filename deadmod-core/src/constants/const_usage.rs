@@ -10,7 +10,9 @@
 
 use std::collections::HashSet;
 use std::path::Path;
-use syn::{visit::Visit, Expr, File, Pat, Type};
+use syn::{visit::Visit, Expr, File, Macro, Pat, Type};
+
+use crate::common::visit_macro_args;
 
 /// Information about constant usages in a file.
 #[derive(Debug, Clone, Default)]
@@ -106,6 +108,12 @@ impl<'ast> Visit<'ast> for ConstUsageExtractor {
 
         syn::visit::visit_type(self, ty);
     }
+
+    // Constants inside macro arguments: assert!(len < MAX_LEN)
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac, |v, found| v.record_path(&found.path));
+        syn::visit::visit_macro(self, mac);
+    }
 }
 
 /// Extract all constant usages from file content.
@@ -200,6 +208,19 @@ fn main() {
         assert!(result.used_constants.contains("PATTERN"));
     }
 
+    #[test]
+    fn test_extract_const_in_macro() {
+        let content = r#"
+fn main() {
+    assert!(len <= MAX_LEN, "too long");
+    table! { limit => LIMITS::DEFAULT }
+}
+"#;
+        let result = extract_const_usage(&PathBuf::from("test.rs"), content);
+        assert!(result.used_constants.contains("MAX_LEN"));
+        assert!(result.used_constants.contains("DEFAULT"));
+    }
+
     #[test]
     fn test_malformed_resilient() {
         let content = "fn main() { let x = BROKEN";
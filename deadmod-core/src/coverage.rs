@@ -0,0 +1,343 @@
+//! Test coverage import: separate "dead" from "reachable but untested".
+//!
+//! Reads function coverage from `llvm-cov export -format=json` (also what
+//! `cargo llvm-cov --json` writes) or grcov's coveralls JSON, and reports
+//! functions that are statically reachable yet never executed by the tests.
+//!
+//! Function names are matched by path without the crate name, so
+//! `mycrate::net::Client::connect` in the coverage data matches
+//! `Client::connect` in `src/net.rs`. Legacy-mangled symbols (`_ZN...E`) are
+//! demangled here; v0 symbols (`_R...`) need a demangled export
+//! (`llvm-cov export -Xdemangler=rustfilt`). Functions absent from the data
+//! are treated as unknown, never as uncovered.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::error::{DeadmodError, IoResultExt};
+use crate::func::{FuncAnalysisResult, FunctionInfo};
+use crate::report::module_path_of;
+
+/// Per-function execution data, keyed by crate-relative path.
+#[derive(Debug, Clone, Default)]
+pub struct CoverageData {
+    executed: HashMap<String, bool>,
+}
+
+impl CoverageData {
+    /// Parse llvm-cov export JSON or grcov coveralls JSON.
+    pub fn from_json(content: &str) -> Result<Self> {
+        let json: Value = serde_json::from_str(content).map_err(|e| {
+            DeadmodError::parse("coverage", format!("Invalid coverage JSON: {}", e))
+        })?;
+
+        let mut data = Self::default();
+        if let Some(exports) = json.get("data").and_then(Value::as_array) {
+            // llvm-cov: data[].functions[] = {name, count, ...}
+            for function in exports
+                .iter()
+                .filter_map(|export| export.get("functions").and_then(Value::as_array))
+                .flatten()
+            {
+                let name = function.get("name").and_then(Value::as_str);
+                let count = function.get("count").and_then(Value::as_u64).unwrap_or(0);
+                if let Some(name) = name {
+                    data.record(name, count > 0);
+                }
+            }
+        } else if let Some(files) = json.get("source_files").and_then(Value::as_array) {
+            // grcov coveralls: source_files[].functions[] = {name, start, exec}
+            for function in files
+                .iter()
+                .filter_map(|file| file.get("functions").and_then(Value::as_array))
+                .flatten()
+            {
+                let name = function.get("name").and_then(Value::as_str);
+                let exec = function
+                    .get("exec")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                if let Some(name) = name {
+                    data.record(name, exec);
+                }
+            }
+        } else {
+            return Err(DeadmodError::parse(
+                "coverage",
+                "Unrecognized coverage format (expected llvm-cov export or grcov coveralls JSON)",
+            )
+            .into());
+        }
+        Ok(data)
+    }
+
+    /// Number of distinct functions with coverage data.
+    pub fn len(&self) -> usize {
+        self.executed.len()
+    }
+
+    /// Whether no function coverage was found.
+    pub fn is_empty(&self) -> bool {
+        self.executed.is_empty()
+    }
+
+    /// Whether the function at crate-relative `path` (`net::Client::connect`)
+    /// ran; `None` if the coverage data does not mention it.
+    pub fn is_executed(&self, path: &str) -> Option<bool> {
+        self.executed.get(path).copied()
+    }
+
+    /// Monomorphized copies share a key: one executed copy covers the function.
+    fn record(&mut self, symbol: &str, executed: bool) {
+        let Some(path) = normalize_symbol(symbol) else {
+            return;
+        };
+        *self.executed.entry(path).or_insert(false) |= executed;
+    }
+}
+
+/// Load coverage data from a JSON file.
+pub fn load_coverage(path: &Path) -> Result<CoverageData> {
+    let content = std::fs::read_to_string(path).with_path(path)?;
+    CoverageData::from_json(&content)
+}
+
+/// Reachable functions that the coverage data shows were never executed.
+///
+/// `root` is the crate root, used to turn each function's file into its
+/// module path.
+pub fn reachable_but_uncovered<'a>(
+    analysis: &'a FuncAnalysisResult,
+    coverage: &CoverageData,
+    root: &Path,
+) -> Vec<&'a FunctionInfo> {
+    analysis
+        .all_functions
+        .iter()
+        .filter(|f| analysis.reachable.contains(&f.full_path) && !f.is_test)
+        .filter(|f| coverage.is_executed(&crate_relative_path(f, root)) == Some(false))
+        .collect()
+}
+
+/// Crate-relative path of a function: its file's module path plus `full_path`.
+fn crate_relative_path(func: &FunctionInfo, root: &Path) -> String {
+    let module = module_path_of(root, Path::new(&func.file));
+    let mut segments: Vec<&str> = module.split("::").skip(1).collect();
+    let full_path = strip_generics(&func.full_path);
+    segments.extend(full_path.split("::"));
+    segments.join("::")
+}
+
+/// Demangle (if needed) and reduce a symbol to its crate-relative path.
+///
+/// Returns `None` for closures and other compiler-generated items.
+fn normalize_symbol(symbol: &str) -> Option<String> {
+    let demangled = if symbol.starts_with("_ZN") || symbol.starts_with("__ZN") {
+        demangle_legacy(symbol)?
+    } else {
+        symbol.to_string()
+    };
+    if demangled.contains("{{") || demangled.starts_with("_R") {
+        return None;
+    }
+
+    let path = strip_generics(&qualify_self(&demangled));
+    let (_crate_name, rest) = path.split_once("::")?;
+    Some(rest.to_string())
+}
+
+/// `<mycrate::Foo as core::fmt::Debug>::fmt` → `mycrate::Foo::fmt`.
+fn qualify_self(path: &str) -> String {
+    if let Some(inner) = path.strip_prefix('<') {
+        if let Some((self_ty, rest)) = inner.split_once(" as ") {
+            if let Some(idx) = rest.find(">::") {
+                return format!("{}::{}", self_ty, &rest[idx + 3..]);
+            }
+        }
+    }
+    path.to_string()
+}
+
+/// Remove `<...>` generic arguments.
+fn strip_generics(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    let mut depth = 0usize;
+    for c in path.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Demangle a legacy Rust symbol (`_ZN7mycrate3foo17h0123456789abcdefE`).
+fn demangle_legacy(symbol: &str) -> Option<String> {
+    let mut rest = symbol.trim_start_matches('_').strip_prefix("ZN")?;
+    let mut segments = Vec::new();
+    while !rest.starts_with('E') {
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        let len: usize = rest[..digits].parse().ok()?;
+        let segment = rest.get(digits..digits + len)?;
+        segments.push(segment);
+        rest = &rest[digits + len..];
+    }
+
+    // Trailing hash segment: h + 16 hex digits
+    if let Some(last) = segments.last() {
+        if last.len() == 17
+            && last.starts_with('h')
+            && last[1..].chars().all(|c| c.is_ascii_hexdigit())
+        {
+            segments.pop();
+        }
+    }
+
+    let decoded: Vec<String> = segments.iter().map(|s| unescape_segment(s)).collect();
+    Some(decoded.join("::"))
+}
+
+fn unescape_segment(segment: &str) -> String {
+    // A leading `_` is added to segments that would otherwise start with `$`
+    let segment = if segment.starts_with("_$") {
+        &segment[1..]
+    } else {
+        segment
+    };
+    let mut out = String::with_capacity(segment.len());
+    let mut rest = segment;
+    while !rest.is_empty() {
+        if let Some(escaped) = rest.strip_prefix('$') {
+            if let Some(end) = escaped.find('$') {
+                let code = &escaped[..end];
+                let decoded = match code {
+                    "SP" => Some("@".to_string()),
+                    "BP" => Some("*".to_string()),
+                    "RF" => Some("&".to_string()),
+                    "LT" => Some("<".to_string()),
+                    "GT" => Some(">".to_string()),
+                    "LP" => Some("(".to_string()),
+                    "RP" => Some(")".to_string()),
+                    "C" => Some(",".to_string()),
+                    _ => code
+                        .strip_prefix('u')
+                        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                        .and_then(char::from_u32)
+                        .map(String::from),
+                };
+                if let Some(decoded) = decoded {
+                    out.push_str(&decoded);
+                    rest = &escaped[end + 1..];
+                    continue;
+                }
+            }
+        }
+        if let Some(after) = rest.strip_prefix("..") {
+            out.push_str("::");
+            rest = after;
+            continue;
+        }
+        let c = rest.chars().next().unwrap_or_default();
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::func::{extract_call_names, extract_functions, FuncGraph};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_demangle_legacy() {
+        assert_eq!(
+            demangle_legacy("_ZN7mycrate3net6Client7connect17h0123456789abcdefE").as_deref(),
+            Some("mycrate::net::Client::connect")
+        );
+        assert_eq!(
+            demangle_legacy(
+                "_ZN49_$LT$mycrate..Foo$u20$as$u20$core..fmt..Debug$GT$3fmt17h0123456789abcdefE"
+            )
+            .as_deref(),
+            Some("<mycrate::Foo as core::fmt::Debug>::fmt")
+        );
+        assert_eq!(demangle_legacy("not_mangled"), None);
+    }
+
+    #[test]
+    fn test_normalize_symbol() {
+        assert_eq!(
+            normalize_symbol("mycrate::net::connect").as_deref(),
+            Some("net::connect")
+        );
+        assert_eq!(
+            normalize_symbol("<mycrate::Foo<T> as core::fmt::Debug>::fmt").as_deref(),
+            Some("Foo::fmt")
+        );
+        assert_eq!(normalize_symbol("mycrate::main::{{closure}}"), None);
+        assert_eq!(normalize_symbol("main"), None);
+    }
+
+    #[test]
+    fn test_from_json_formats() {
+        let llvm = r#"{"data": [{"functions": [
+            {"name": "_ZN7mycrate4used17h0123456789abcdefE", "count": 3},
+            {"name": "_ZN7mycrate6unused17h0123456789abcdefE", "count": 0},
+            {"name": "_ZN7mycrate6unused17hfedcba9876543210E", "count": 0}
+        ]}]}"#;
+        let data = CoverageData::from_json(llvm).unwrap();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data.is_executed("used"), Some(true));
+        assert_eq!(data.is_executed("unused"), Some(false));
+        assert_eq!(data.is_executed("missing"), None);
+
+        let grcov = r#"{"source_files": [{"name": "src/lib.rs", "functions": [
+            {"name": "mycrate::a::f", "start": 1, "exec": true}
+        ]}]}"#;
+        assert_eq!(
+            CoverageData::from_json(grcov).unwrap().is_executed("a::f"),
+            Some(true)
+        );
+
+        assert!(CoverageData::from_json(r#"{"files": []}"#).is_err());
+        assert!(CoverageData::from_json("nope").is_err());
+    }
+
+    #[test]
+    fn test_reachable_but_uncovered() {
+        let root = Path::new("/crate");
+        let lib = root.join("src/lib.rs");
+        let net = root.join("src/net.rs");
+        let lib_src = "pub fn api() { net::tested(); net::untested(); }\nfn dead() {}";
+        let net_src = "pub fn tested() {}\npub fn untested() {}";
+
+        let mut funcs = extract_functions(&lib, lib_src);
+        funcs.extend(extract_functions(&net, net_src));
+        let mut calls = HashMap::new();
+        calls.insert(lib.display().to_string(), extract_call_names(&lib, lib_src));
+        let analysis = FuncGraph::build(&funcs, &calls).analyze();
+
+        let coverage = CoverageData::from_json(
+            r#"{"data": [{"functions": [
+                {"name": "mycrate::api", "count": 1},
+                {"name": "mycrate::net::tested", "count": 1},
+                {"name": "mycrate::net::untested", "count": 0},
+                {"name": "mycrate::dead", "count": 0}
+            ]}]}"#,
+        )
+        .unwrap();
+
+        let uncovered: Vec<_> = reachable_but_uncovered(&analysis, &coverage, root)
+            .into_iter()
+            .map(|f| f.full_path.as_str())
+            .collect();
+        assert_eq!(uncovered, vec!["untested"]);
+    }
+}
@@ -0,0 +1,559 @@
+//! Persistent analysis daemon speaking line-delimited JSON-RPC 2.0.
+//!
+//! A daemon keeps one warm [`AnalysisResult`] in memory and serves it to any
+//! number of clients (CLI, LSP, CI wrappers) over a local Unix socket, so only
+//! the first request pays for a full analysis. Each request and response is a
+//! single JSON line.
+//!
+//! | Method       | Params              | Result                                          |
+//! |--------------|---------------------|-------------------------------------------------|
+//! | `analyze`    | `{"file"?: path}`   | Summary and findings (only those in `file`)     |
+//! | `query`      | `{"name": string}`  | Findings named `name` or owned by module `name` |
+//! | `modules`    | none                | Every module with its path, file and verdict    |
+//! | `dead`       | none                | Dead modules and dead items only                |
+//! | `graph`      | none                | Module graph in visualizer JSON format          |
+//! | `invalidate` | `{"file"?: path}`   | Drops the warm result; the next call re-analyzes |
+//! | `shutdown`   | none                | Stops the server                                |
+//!
+//! After `invalidate` with a `file`, the next call re-runs only the item
+//! detectors the changed files affect (see [`crate::dirty`]); without one,
+//! everything is analyzed afresh. `analyze` lists the detectors that ran in
+//! `detectors_run`.
+//!
+//! The same methods are served over HTTP by [`crate::http`].
+//! ```text
+//! → {"jsonrpc":"2.0","id":1,"method":"query","params":{"name":"helper"}}
+//! ← {"jsonrpc":"2.0","id":1,"result":{"dead_modules":[],"dead":[...]}}
+//! ```
+
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::builder::{AnalysisResult, Deadmod, FindingsRef};
+use crate::dirty::IncrementalState;
+use crate::graph::{module_graph_to_visualizer_json, qualified_module_path};
+
+/// Default socket location, relative to the crate root.
+pub const SOCKET_FILE: &str = ".deadmod/daemon.sock";
+
+pub(crate) const PARSE_ERROR: i64 = -32700;
+pub(crate) const METHOD_NOT_FOUND: i64 = -32601;
+pub(crate) const INVALID_PARAMS: i64 = -32602;
+pub(crate) const ANALYSIS_FAILED: i64 = -32000;
+
+/// Default socket path for the crate at `root`.
+pub fn default_socket_path(root: &Path) -> PathBuf {
+    root.join(SOCKET_FILE)
+}
+
+/// Analysis state shared by all daemon clients.
+pub struct Daemon {
+    builder: Deadmod,
+    result: Option<AnalysisResult>,
+    invalidated: BTreeSet<PathBuf>,
+    state: IncrementalState,
+}
+
+/// Response to one request line.
+#[derive(Debug, Clone)]
+pub struct Reply {
+    /// JSON-RPC response (single line)
+    pub body: String,
+    /// Whether the client asked the daemon to stop
+    pub shutdown: bool,
+}
+
+impl Daemon {
+    /// Create a daemon that runs `builder` whenever it needs a fresh result.
+    pub fn new(builder: Deadmod) -> Self {
+        Self {
+            builder,
+            result: None,
+            invalidated: BTreeSet::new(),
+            state: IncrementalState::new(),
+        }
+    }
+
+    /// Whether a warm result is currently held.
+    pub fn is_warm(&self) -> bool {
+        self.result.is_some()
+    }
+
+    /// Handle one JSON-RPC request line.
+    pub fn handle(&mut self, line: &str) -> Reply {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => {
+                return reply(
+                    Value::Null,
+                    Err((PARSE_ERROR, format!("Parse error: {}", e))),
+                )
+            }
+        };
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+        let outcome = match request.get("method").and_then(Value::as_str) {
+            Some("shutdown") => {
+                let mut reply = reply(id, Ok(Value::Null));
+                reply.shutdown = true;
+                return reply;
+            }
+            Some(method) => self.call(method, &params),
+            None => Err((METHOD_NOT_FOUND, "Missing method".to_string())),
+        };
+        reply(id, outcome)
+    }
+
+    /// Run one method (anything but `shutdown`) and return its result or a
+    /// JSON-RPC `(code, message)` error.
+    pub fn call(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        match method {
+            "analyze" => self.analyze(params),
+            "query" => self.query(params),
+            "modules" => self.modules(),
+            "dead" => self.dead(),
+            "graph" => self.graph(),
+            "invalidate" => Ok(self.invalidate(params)),
+            other => Err((METHOD_NOT_FOUND, format!("Unknown method: {}", other))),
+        }
+    }
+
+    fn result(&mut self) -> Result<&AnalysisResult, (i64, String)> {
+        if self.result.is_none() {
+            let result = self
+                .builder
+                .analyze_incremental(&mut self.state)
+                .map_err(|e| (ANALYSIS_FAILED, format!("Analysis failed: {:#}", e)))?;
+            self.result = Some(result);
+            self.invalidated.clear();
+        }
+        Ok(self.result.as_ref().expect("result was just computed"))
+    }
+
+    fn analyze(&mut self, params: &Value) -> Result<Value, (i64, String)> {
+        let file = params
+            .get("file")
+            .and_then(Value::as_str)
+            .map(PathBuf::from);
+        let result = self.result()?;
+        let mut summary = match file {
+            Some(file) => {
+                let mut summary = summarize(result.total_modules, &result.findings_in(&file));
+                summary["file"] = json!(file);
+                summary
+            }
+            None => summarize(result.total_modules, &result.findings()),
+        };
+        summary["detectors_run"] = json!(self.state.last_run());
+        Ok(summary)
+    }
+
+    fn query(&mut self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                (
+                    INVALID_PARAMS,
+                    "query requires a \"name\" string".to_string(),
+                )
+            })?
+            .to_string();
+        let result = self.result()?;
+
+        let suffix = format!("::{}", name);
+        let dead: Vec<_> = result
+            .dead_items()
+            .filter(|i| i.name == name || i.name.ends_with(&suffix) || i.module_path == name)
+            .collect();
+        let dead_modules: Vec<_> = result.dead_modules.iter().filter(|m| **m == name).collect();
+        Ok(json!({ "dead_modules": dead_modules, "dead": dead }))
+    }
+
+    fn modules(&mut self) -> Result<Value, (i64, String)> {
+        let result = self.result()?;
+        let dead: BTreeSet<&str> = result.dead_modules.iter().map(String::as_str).collect();
+        let mut names: Vec<&String> = result.modules.keys().collect();
+        names.sort();
+
+        let modules: Vec<Value> = names
+            .into_iter()
+            .map(|name| {
+                let info = &result.modules[name];
+                json!({
+                    "name": name,
+                    "module_path": qualified_module_path(name, info),
+                    "file": info.path,
+                    "dead": dead.contains(name.as_str()),
+                })
+            })
+            .collect();
+        Ok(json!({ "modules": modules }))
+    }
+
+    fn dead(&mut self) -> Result<Value, (i64, String)> {
+        let result = self.result()?;
+        Ok(json!({
+            "dead_modules": result.dead_modules,
+            "dead": result.dead_items().collect::<Vec<_>>(),
+        }))
+    }
+
+    fn graph(&mut self) -> Result<Value, (i64, String)> {
+        let result = self.result()?;
+        let reachable: HashSet<&str> = result
+            .reachable_modules
+            .iter()
+            .map(String::as_str)
+            .collect();
+        Ok(module_graph_to_visualizer_json(&result.modules, &reachable))
+    }
+
+    fn invalidate(&mut self, params: &Value) -> Value {
+        match params.get("file").and_then(Value::as_str) {
+            Some(file) => {
+                self.invalidated.insert(PathBuf::from(file));
+            }
+            // Nothing to narrow the change down to: start over
+            None => self.state = IncrementalState::new(),
+        }
+        let was_warm = self.result.take().is_some();
+        json!({ "was_warm": was_warm, "invalidated": self.invalidated })
+    }
+}
+
+fn summarize(total_modules: usize, findings: &FindingsRef<'_>) -> Value {
+    json!({
+        "total_modules": total_modules,
+        "dead_modules": findings.dead_modules,
+        "dead": findings.dead,
+        "kept": findings.kept,
+    })
+}
+
+fn reply(id: Value, outcome: Result<Value, (i64, String)>) -> Reply {
+    let body = match outcome {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        }),
+    };
+    Reply {
+        body: body.to_string(),
+        shutdown: false,
+    }
+}
+
+/// Serve `daemon` on the Unix socket at `socket` until a `shutdown` request.
+///
+/// Each client gets its own thread and may send any number of requests on
+/// its connection; requests from all clients take turns on the shared
+/// daemon. A stale socket file is replaced, a live one is an error.
+#[cfg(unix)]
+pub fn serve(daemon: Daemon, socket: &Path) -> Result<()> {
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use crate::error::{DeadmodError, IoResultExt};
+
+    if socket.exists() {
+        if UnixStream::connect(socket).is_ok() {
+            return Err(DeadmodError::internal(format!(
+                "A daemon is already listening on {}",
+                socket.display()
+            ))
+            .into());
+        }
+        std::fs::remove_file(socket).with_path(socket)?;
+    }
+    if let Some(parent) = socket.parent() {
+        std::fs::create_dir_all(parent).with_path(parent)?;
+    }
+    let listener = UnixListener::bind(socket).with_path(socket)?;
+
+    let daemon = Arc::new(Mutex::new(daemon));
+    let stopping = Arc::new(AtomicBool::new(false));
+    for stream in listener.incoming() {
+        if stopping.load(Ordering::SeqCst) {
+            break;
+        }
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("[WARN] daemon: accept failed: {}", e);
+                continue;
+            }
+        };
+        let daemon = Arc::clone(&daemon);
+        let stopping = Arc::clone(&stopping);
+        let socket = socket.to_path_buf();
+        std::thread::spawn(move || {
+            if serve_connection(stream, &daemon) {
+                stopping.store(true, Ordering::SeqCst);
+                // Wake the accept loop so it sees the flag
+                let _ = UnixStream::connect(&socket);
+            }
+        });
+    }
+
+    let _ = std::fs::remove_file(socket);
+    Ok(())
+}
+
+/// Answer the requests of one client until it disconnects. Returns whether
+/// it asked the daemon to stop.
+#[cfg(unix)]
+fn serve_connection(
+    stream: std::os::unix::net::UnixStream,
+    daemon: &std::sync::Mutex<Daemon>,
+) -> bool {
+    use std::io::{BufRead, BufReader, Write};
+
+    let mut writer = match stream.try_clone() {
+        Ok(writer) => writer,
+        Err(e) => {
+            eprintln!("[WARN] daemon: connection setup failed: {}", e);
+            return false;
+        }
+    };
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply = daemon
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .handle(&line);
+        if writeln!(writer, "{}", reply.body).is_err() {
+            break;
+        }
+        if reply.shutdown {
+            return true;
+        }
+    }
+    false
+}
+
+/// Unix sockets are required; other platforms are not supported yet.
+#[cfg(not(unix))]
+pub fn serve(_daemon: Daemon, socket: &Path) -> Result<()> {
+    Err(crate::error::DeadmodError::internal(format!(
+        "deadmod daemon needs Unix domain sockets; cannot listen on {}",
+        socket.display()
+    ))
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn daemon() -> Daemon {
+        Daemon::new(
+            Deadmod::from_sources([
+                (
+                    PathBuf::from("src/lib.rs"),
+                    "mod a;\npub fn api() { a::used(); }".to_string(),
+                ),
+                (
+                    PathBuf::from("src/a.rs"),
+                    "pub fn used() {}\nfn helper() {}".to_string(),
+                ),
+                (PathBuf::from("src/b.rs"), String::new()),
+            ])
+            .include_functions(true),
+        )
+    }
+
+    fn call(daemon: &mut Daemon, request: Value) -> Value {
+        serde_json::from_str(&daemon.handle(&request.to_string()).body).unwrap()
+    }
+
+    #[test]
+    fn test_analyze_and_query() {
+        let mut daemon = daemon();
+        assert!(!daemon.is_warm());
+
+        let response = call(
+            &mut daemon,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "analyze"}),
+        );
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["dead_modules"], json!(["b"]));
+        assert!(daemon.is_warm());
+
+        let response = call(
+            &mut daemon,
+            json!({"id": 2, "method": "analyze", "params": {"file": "src/lib.rs"}}),
+        );
+        assert_eq!(response["result"]["dead"], json!([]));
+
+        let response = call(
+            &mut daemon,
+            json!({"id": 3, "method": "query", "params": {"name": "helper"}}),
+        );
+        assert_eq!(response["result"]["dead"][0]["module_path"], "crate::a");
+    }
+
+    #[test]
+    fn test_invalidate_and_shutdown() {
+        let mut daemon = daemon();
+        call(&mut daemon, json!({"id": 1, "method": "analyze"}));
+
+        let response = call(
+            &mut daemon,
+            json!({"id": 2, "method": "invalidate", "params": {"file": "src/a.rs"}}),
+        );
+        assert_eq!(response["result"]["was_warm"], true);
+        assert_eq!(response["result"]["invalidated"], json!(["src/a.rs"]));
+        assert!(!daemon.is_warm());
+
+        // The sources did not change, so no detector re-runs
+        let response = call(&mut daemon, json!({"id": 3, "method": "analyze"}));
+        assert_eq!(response["result"]["detectors_run"], json!([]));
+        call(&mut daemon, json!({"id": 4, "method": "invalidate"}));
+        let response = call(&mut daemon, json!({"id": 5, "method": "analyze"}));
+        assert_eq!(response["result"]["detectors_run"], json!(["functions"]));
+
+        let reply = daemon.handle(r#"{"id": 6, "method": "shutdown"}"#);
+        assert!(reply.shutdown);
+    }
+
+    #[test]
+    fn test_modules_dead_and_graph() {
+        let mut daemon = daemon();
+
+        let modules = daemon.call("modules", &Value::Null).unwrap();
+        let names: Vec<&Value> = modules["modules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| &m["name"])
+            .collect();
+        assert_eq!(names, [&json!("a"), &json!("b"), &json!("lib")]);
+        assert_eq!(modules["modules"][1]["dead"], true);
+        assert_eq!(modules["modules"][2]["module_path"], "crate");
+
+        let dead = daemon.call("dead", &Value::Null).unwrap();
+        assert_eq!(dead["dead_modules"], json!(["b"]));
+        assert_eq!(dead["dead"][0]["name"], "helper");
+
+        let graph = daemon.call("graph", &Value::Null).unwrap();
+        assert_eq!(graph["stats"]["total_modules"], 3);
+        assert_eq!(graph["stats"]["dead_modules"], 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut daemon = daemon();
+        assert_eq!(
+            call(&mut daemon, json!({"id": 1, "method": "nope"}))["error"]["code"],
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            call(&mut daemon, json!({"id": 2, "method": "query"}))["error"]["code"],
+            INVALID_PARAMS
+        );
+
+        let reply: Value = serde_json::from_str(&daemon.handle("{not json").body).unwrap();
+        assert_eq!(reply["error"]["code"], PARSE_ERROR);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_serve_over_socket() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::os::unix::net::UnixStream;
+
+        let dir = std::env::temp_dir().join(format!("deadmod_daemon_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let socket = dir.join("daemon.sock");
+
+        let server_socket = socket.clone();
+        let server = std::thread::spawn(move || serve(daemon(), &server_socket));
+
+        let mut stream = None;
+        for _ in 0..100 {
+            if let Ok(s) = UnixStream::connect(&socket) {
+                stream = Some(s);
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        let mut stream = stream.expect("daemon did not start");
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        writeln!(stream, r#"{{"id": 1, "method": "analyze"}}"#).unwrap();
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let response: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(response["result"]["total_modules"], 3);
+
+        writeln!(stream, r#"{{"id": 2, "method": "shutdown"}}"#).unwrap();
+        server.join().unwrap().unwrap();
+        assert!(!socket.exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_serve_clients_concurrently() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::os::unix::net::UnixStream;
+        use std::time::Duration;
+
+        let dir = std::env::temp_dir().join(format!("deadmod_daemon_mc_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let socket = dir.join("daemon.sock");
+
+        let server_socket = socket.clone();
+        let server = std::thread::spawn(move || serve(daemon(), &server_socket));
+
+        let connect = || {
+            for _ in 0..100 {
+                if let Ok(stream) = UnixStream::connect(&socket) {
+                    stream
+                        .set_read_timeout(Some(Duration::from_secs(10)))
+                        .unwrap();
+                    return stream;
+                }
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            panic!("daemon did not start");
+        };
+        let request = |stream: &mut UnixStream, line: &str| -> Value {
+            writeln!(stream, "{}", line).unwrap();
+            let mut response = String::new();
+            BufReader::new(stream.try_clone().unwrap())
+                .read_line(&mut response)
+                .unwrap();
+            serde_json::from_str(&response).unwrap()
+        };
+
+        // The first client keeps its connection open, like the LSP does
+        let mut first = connect();
+        let response = request(&mut first, r#"{"id": 1, "method": "analyze"}"#);
+        assert_eq!(response["result"]["total_modules"], 3);
+
+        let mut second = connect();
+        let response = request(&mut second, r#"{"id": 1, "method": "dead"}"#);
+        assert_eq!(response["result"]["dead_modules"], json!(["b"]));
+        let response = request(&mut first, r#"{"id": 2, "method": "modules"}"#);
+        assert_eq!(response["id"], 2);
+
+        request(&mut second, r#"{"id": 2, "method": "shutdown"}"#);
+        server.join().unwrap().unwrap();
+        assert!(!socket.exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,534 @@
+//! Dependencies declared in `Cargo.toml` that no code uses (`--dead-deps`).
+//!
+//! A dependency is used when a live file of the package names it: a path
+//! starting with it (`serde::Serialize`, `#[tokio::main]`), a `use` or an
+//! `extern crate`. Files of dead modules do not count, so a dev-dependency
+//! only the tests deadmod reports dead still use is reported, with those
+//! files listed. Build dependencies count uses in the build script too.
+//! The scan is static, like the rest of deadmod: nothing is compiled, and
+//! a dependency only linked for its side effects (`openssl-sys` with
+//! `vendored`) is listed in `[package.metadata.deadmod] ignore-deps`.
+//!
+//! At a workspace root, every member is checked, along with the
+//! `[workspace.dependencies]` no member inherits and the `[patch]` entries
+//! `Cargo.lock` never resolved (patches are only checked with a lock file,
+//! since they may replace transitive dependencies).
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use proc_macro2::{Spacing, TokenStream, TokenTree};
+use serde::{Deserialize, Serialize};
+
+use crate::builder::Deadmod;
+use crate::cache::ParseMode;
+use crate::workspace::{find_all_crates, is_workspace_root, read_manifest};
+
+/// Which manifest table a dependency is declared in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    /// `[dependencies]`
+    Normal,
+    /// `[dev-dependencies]`
+    Dev,
+    /// `[build-dependencies]`
+    Build,
+    /// `[workspace.dependencies]`
+    Workspace,
+    /// `[patch.<registry>]`
+    Patch,
+}
+
+impl DependencyKind {
+    /// Manifest table name, used in reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "dependencies",
+            Self::Dev => "dev-dependencies",
+            Self::Build => "build-dependencies",
+            Self::Workspace => "workspace.dependencies",
+            Self::Patch => "patch",
+        }
+    }
+}
+
+impl fmt::Display for DependencyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A declared dependency nothing uses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadDependency {
+    /// Key in the manifest (the name code would use, hyphens aside)
+    pub name: String,
+    /// Table it is declared in
+    pub kind: DependencyKind,
+    /// `Cargo.toml` declaring it
+    pub manifest: PathBuf,
+    /// `cfg(..)` or target triple of a `[target.'..'.dependencies]` table
+    pub target: Option<String>,
+    /// `optional = true`: only compiled with a feature enabled
+    pub optional: bool,
+    /// Files of dead modules that use it, sorted
+    pub dead_users: Vec<PathBuf>,
+}
+
+/// Result of [`find_dead_dependencies`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeadDepsResult {
+    /// Unused dependencies, by manifest, kind and name
+    pub dead: Vec<DeadDependency>,
+    /// Dependency declarations checked
+    pub checked: usize,
+}
+
+/// A dependency declaration read from a manifest.
+struct Declared {
+    name: String,
+    kind: DependencyKind,
+    target: Option<String>,
+    optional: bool,
+}
+
+/// Find the unused dependencies of the package at `root`, or of every
+/// member when `root` is a workspace root. Packages are parsed with
+/// `parse_mode`, like any other analysis.
+pub fn find_dead_dependencies(root: &Path, parse_mode: ParseMode) -> Result<DeadDepsResult> {
+    let mut result = DeadDepsResult::default();
+    if is_workspace_root(root) {
+        let members = find_all_crates(root)?;
+        for member in &members {
+            check_package(member, parse_mode, &mut result)?;
+        }
+        check_workspace(root, &members, &mut result);
+    } else {
+        check_package(root, parse_mode, &mut result)?;
+        check_patches(root, &mut result);
+    }
+    result
+        .dead
+        .sort_by(|a, b| (&a.manifest, a.kind, &a.name).cmp(&(&b.manifest, b.kind, &b.name)));
+    Ok(result)
+}
+
+/// Check the dependencies a package declares against its sources.
+fn check_package(dir: &Path, parse_mode: ParseMode, result: &mut DeadDepsResult) -> Result<()> {
+    let Some(manifest) = read_manifest(dir) else {
+        return Ok(());
+    };
+    let Some(package) = manifest.get("package") else {
+        return Ok(());
+    };
+    let declared = declared_dependencies(&manifest);
+    if declared.is_empty() {
+        return Ok(());
+    }
+    let ignored = ignored_dependencies(package);
+
+    let analysis = Deadmod::new(dir)
+        .with_parse_mode(parse_mode)
+        .analyze()
+        .with_context(|| format!("Failed to analyze {}", dir.display()))?;
+    let dead_modules: HashSet<&str> = analysis.dead_modules.iter().map(String::as_str).collect();
+    let build_script = build_script(dir, package);
+
+    let mut live = HashSet::new();
+    let mut dead_users: Vec<(PathBuf, HashSet<String>)> = Vec::new();
+    for (name, info) in &analysis.modules {
+        let Ok(content) = fs::read_to_string(&info.path) else {
+            continue;
+        };
+        let names = crate_references(&content);
+        let is_build_script =
+            build_script.is_some() && info.path.canonicalize().ok() == build_script;
+        if dead_modules.contains(name.as_str()) && !is_build_script {
+            dead_users.push((info.path.clone(), names));
+        } else {
+            live.extend(names);
+        }
+    }
+    // A build script outside the scanned tree (`build = "../build.rs"`)
+    if let Some(script) = build_script.filter(|script| {
+        !analysis
+            .modules
+            .values()
+            .any(|info| info.path.canonicalize().ok().as_ref() == Some(script))
+    }) {
+        if let Ok(content) = fs::read_to_string(&script) {
+            live.extend(crate_references(&content));
+        }
+    }
+
+    let manifest_path = dir.join("Cargo.toml");
+    for dep in declared {
+        result.checked += 1;
+        let code_name = dep.name.replace('-', "_");
+        if live.contains(&code_name) || ignored.contains(&dep.name) {
+            continue;
+        }
+        let mut users: Vec<PathBuf> = dead_users
+            .iter()
+            .filter(|(_, names)| names.contains(&code_name))
+            .map(|(path, _)| path.clone())
+            .collect();
+        users.sort();
+        result.dead.push(DeadDependency {
+            name: dep.name,
+            kind: dep.kind,
+            manifest: manifest_path.clone(),
+            target: dep.target,
+            optional: dep.optional,
+            dead_users: users,
+        });
+    }
+    Ok(())
+}
+
+/// Check `[workspace.dependencies]` and `[patch]` of a workspace root.
+fn check_workspace(root: &Path, members: &[PathBuf], result: &mut DeadDepsResult) {
+    let Some(manifest) = read_manifest(root) else {
+        return;
+    };
+    if let Some(shared) = manifest
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(|d| d.as_table())
+    {
+        let inherited: HashSet<String> = members
+            .iter()
+            .filter_map(|member| read_manifest(member))
+            .flat_map(|member| inherited_dependencies(&member))
+            .collect();
+        for (name, spec) in shared {
+            result.checked += 1;
+            if !inherited.contains(name) {
+                result.dead.push(DeadDependency {
+                    name: name.clone(),
+                    kind: DependencyKind::Workspace,
+                    manifest: root.join("Cargo.toml"),
+                    target: None,
+                    optional: is_optional(spec),
+                    dead_users: Vec::new(),
+                });
+            }
+        }
+    }
+    check_patches(root, result);
+}
+
+/// Report `[patch]` entries the `Cargo.lock` next to `root` never resolved.
+fn check_patches(root: &Path, result: &mut DeadDepsResult) {
+    let Some(patches) = read_manifest(root)
+        .and_then(|manifest| manifest.get("patch").and_then(|p| p.as_table()).cloned())
+    else {
+        return;
+    };
+    let Some(lock) = fs::read_to_string(root.join("Cargo.lock"))
+        .ok()
+        .and_then(|text| text.parse::<toml::Value>().ok())
+    else {
+        return;
+    };
+    let locked: HashSet<&str> = lock
+        .get("package")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|package| package.get("name")?.as_str())
+        .collect();
+    // Cargo records patches it could not apply under `[[patch.unused]]`
+    let unused: HashSet<&str> = lock
+        .get("patch")
+        .and_then(|p| p.get("unused"))
+        .and_then(|u| u.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|package| package.get("name")?.as_str())
+        .collect();
+
+    for (registry, entries) in &patches {
+        for (key, spec) in entries.as_table().into_iter().flatten() {
+            result.checked += 1;
+            let package = spec.get("package").and_then(|p| p.as_str()).unwrap_or(key);
+            if unused.contains(package) || !locked.contains(package) {
+                result.dead.push(DeadDependency {
+                    name: key.clone(),
+                    kind: DependencyKind::Patch,
+                    manifest: root.join("Cargo.toml"),
+                    target: Some(registry.clone()),
+                    optional: false,
+                    dead_users: Vec::new(),
+                });
+            }
+        }
+    }
+}
+
+/// Every dependency declaration of a package manifest.
+fn declared_dependencies(manifest: &toml::Value) -> Vec<Declared> {
+    const TABLES: [(&str, DependencyKind); 3] = [
+        ("dependencies", DependencyKind::Normal),
+        ("dev-dependencies", DependencyKind::Dev),
+        ("build-dependencies", DependencyKind::Build),
+    ];
+
+    let mut tables = vec![(None, manifest)];
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        tables.extend(
+            targets
+                .iter()
+                .map(|(target, table)| (Some(target.clone()), table)),
+        );
+    }
+    let mut declared = Vec::new();
+    for (target, table) in tables {
+        for (table_name, kind) in TABLES {
+            for (name, spec) in table
+                .get(table_name)
+                .and_then(|d| d.as_table())
+                .into_iter()
+                .flatten()
+            {
+                declared.push(Declared {
+                    name: name.clone(),
+                    kind,
+                    target: target.clone(),
+                    optional: is_optional(spec),
+                });
+            }
+        }
+    }
+    declared
+}
+
+/// Keys of the dependencies a member manifest inherits with `workspace = true`.
+fn inherited_dependencies(manifest: &toml::Value) -> Vec<String> {
+    let mut tables = vec![manifest];
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        tables.extend(targets.values());
+    }
+    tables
+        .into_iter()
+        .flat_map(|table| {
+            ["dependencies", "dev-dependencies", "build-dependencies"]
+                .into_iter()
+                .filter_map(move |name| table.get(name)?.as_table())
+        })
+        .flatten()
+        .filter(|(_, spec)| spec.get("workspace").and_then(|w| w.as_bool()) == Some(true))
+        // A renamed inheritance (`json = { workspace = true, package = .. }`)
+        // still names the workspace entry by its key
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+fn is_optional(spec: &toml::Value) -> bool {
+    spec.get("optional").and_then(|o| o.as_bool()) == Some(true)
+}
+
+/// `[package.metadata.deadmod] ignore-deps`.
+fn ignored_dependencies(package: &toml::Value) -> HashSet<String> {
+    package
+        .get("metadata")
+        .and_then(|m| m.get("deadmod"))
+        .and_then(|d| d.get("ignore-deps"))
+        .and_then(|i| i.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|name| name.as_str().map(str::to_string))
+        .collect()
+}
+
+/// The package's build script: `[package] build`, or `build.rs` when present.
+fn build_script(dir: &Path, package: &toml::Value) -> Option<PathBuf> {
+    let script = match package.get("build") {
+        Some(toml::Value::String(path)) => dir.join(path),
+        Some(toml::Value::Boolean(false)) => return None,
+        _ => dir.join("build.rs"),
+    };
+    script.canonicalize().ok()
+}
+
+/// Names a source file could refer to a crate by: heads of `::` paths,
+/// and every name in `use` and `extern crate` items.
+///
+/// Over-approximates (a local module `log::` also counts), which only ever
+/// keeps a dependency alive.
+pub(crate) fn crate_references(content: &str) -> HashSet<String> {
+    let mut names = HashSet::new();
+    if let Ok(tokens) = content.parse::<TokenStream>() {
+        collect_references(tokens, &mut names);
+    }
+    names
+}
+
+fn collect_references(tokens: TokenStream, names: &mut HashSet<String>) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut in_use = false;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string();
+                let path_head = matches!(
+                    (tokens.get(i + 1), tokens.get(i + 2)),
+                    (Some(TokenTree::Punct(a)), Some(TokenTree::Punct(b)))
+                        if a.as_char() == ':' && a.spacing() == Spacing::Joint && b.as_char() == ':'
+                );
+                let extern_crate = i >= 2
+                    && matches!(&tokens[i - 1], TokenTree::Ident(c) if c == "crate")
+                    && matches!(&tokens[i - 2], TokenTree::Ident(e) if e == "extern");
+                if in_use || path_head || extern_crate {
+                    names.insert(name.strip_prefix("r#").unwrap_or(&name).to_string());
+                }
+                if name == "use" {
+                    in_use = true;
+                }
+            }
+            TokenTree::Punct(p) if p.as_char() == ';' => in_use = false,
+            TokenTree::Group(group) if in_use => {
+                // `use {serde, log::info};`
+                let mut inner = BTreeSet::new();
+                collect_use_group(group.stream(), &mut inner);
+                names.extend(inner);
+            }
+            TokenTree::Group(group) => collect_references(group.stream(), names),
+            _ => {}
+        }
+    }
+}
+
+/// Every name inside a `use` tree group.
+fn collect_use_group(tokens: TokenStream, names: &mut BTreeSet<String>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                names.insert(ident.to_string());
+            }
+            TokenTree::Group(group) => collect_use_group(group.stream(), names),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn names(dead: &[DeadDependency]) -> Vec<(&str, DependencyKind)> {
+        dead.iter().map(|d| (d.name.as_str(), d.kind)).collect()
+    }
+
+    #[test]
+    fn test_crate_references() {
+        let refs = crate_references(
+            "extern crate libc;\nuse {serde_json, log::info};\nuse regex;\n\
+             #[tokio::main]\nasync fn main() { let x = anyhow::anyhow!(\"\"); let rand = 1; }",
+        );
+        for name in [
+            "libc",
+            "serde_json",
+            "log",
+            "info",
+            "regex",
+            "tokio",
+            "anyhow",
+        ] {
+            assert!(refs.contains(name), "{} missing from {:?}", name, refs);
+        }
+        assert!(!refs.contains("rand"));
+        assert!(!refs.contains("main"));
+    }
+
+    #[test]
+    fn test_find_dead_dependencies() {
+        let dir = std::env::temp_dir().join(format!("deadmod_dead_deps_{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        write(
+            &dir.join("Cargo.toml"),
+            "[package]\nname = \"app\"\nbuild = \"build.rs\"\n\n\
+             [package.metadata.deadmod]\nignore-deps = [\"openssl-sys\"]\n\n\
+             [dependencies]\nserde-json = \"1\"\nregex = \"1\"\nopenssl-sys = \"0.9\"\n\
+             extra = { version = \"1\", optional = true }\n\n\
+             [target.'cfg(unix)'.dependencies]\nlibc = \"0.2\"\n\n\
+             [dev-dependencies]\nproptest = \"1\"\ntempfile = \"3\"\n\n\
+             [build-dependencies]\ncc = \"1\"\n",
+        );
+        write(
+            &dir.join("src/lib.rs"),
+            "pub fn parse() { serde_json::from_str::<u8>(\"1\").ok(); }",
+        );
+        // Never declared with `mod`: dead, and so is its use of proptest
+        write(&dir.join("src/old_tests.rs"), "use proptest::prelude::*;");
+        write(
+            &dir.join("tests/it.rs"),
+            "#[test]\nfn it() { tempfile::tempdir().unwrap(); }",
+        );
+        write(&dir.join("build.rs"), "fn main() { cc::Build::new(); }");
+
+        let result = find_dead_dependencies(&dir, ParseMode::Uncached).unwrap();
+        assert_eq!(result.checked, 8);
+        assert_eq!(
+            names(&result.dead),
+            vec![
+                ("extra", DependencyKind::Normal),
+                ("libc", DependencyKind::Normal),
+                ("regex", DependencyKind::Normal),
+                ("proptest", DependencyKind::Dev),
+            ]
+        );
+        assert!(result.dead[0].optional);
+        assert_eq!(result.dead[1].target.as_deref(), Some("cfg(unix)"));
+        let proptest = &result.dead[3];
+        assert_eq!(proptest.dead_users.len(), 1);
+        assert!(proptest.dead_users[0].ends_with("src/old_tests.rs"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_dead_dependencies_workspace() {
+        let dir = std::env::temp_dir().join(format!("deadmod_dead_deps_ws_{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        write(
+            &dir.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"core\"]\n\n\
+             [workspace.dependencies]\nanyhow = \"1\"\nitertools = \"0.12\"\n\n\
+             [patch.crates-io]\nanyhow = { path = \"vendor/anyhow\" }\nleft-pad = { path = \"vendor/left-pad\" }\n",
+        );
+        write(
+            &dir.join("Cargo.lock"),
+            "[[package]]\nname = \"anyhow\"\nversion = \"1.0.0\"\n\n[[package]]\nname = \"core\"\nversion = \"0.1.0\"\n",
+        );
+        write(
+            &dir.join("core/Cargo.toml"),
+            "[package]\nname = \"core\"\n\n[dependencies]\nanyhow.workspace = true\n",
+        );
+        write(
+            &dir.join("core/src/lib.rs"),
+            "pub fn run() -> anyhow::Result<()> { Ok(()) }",
+        );
+
+        let result = find_dead_dependencies(&dir, ParseMode::Uncached).unwrap();
+        assert_eq!(
+            names(&result.dead),
+            vec![
+                ("itertools", DependencyKind::Workspace),
+                ("left-pad", DependencyKind::Patch)
+            ]
+        );
+        assert_eq!(result.dead[1].target.as_deref(), Some("crates-io"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,332 @@
+//! Sources outside the crate that count as usage (`--include-deps-usages`).
+//!
+//! A `pub` item nothing in its crate uses may still be used by a crate
+//! depending on it, and a trait impl may only ever be called from a
+//! dependency's generic code (`impl Serialize`, a framework's handler
+//! trait). With this opt-in, detectors also read those crates' sources,
+//! but only for usages: nothing declared there is ever reported.
+//!
+//! Sources read:
+//! - Direct dependencies given by `path`, `workspace = true` entries
+//!   included
+//! - Registry dependencies at the versions `Cargo.lock` pins, from a
+//!   `vendor/` directory next to the lock file or the unpacked sources in
+//!   `$CARGO_HOME/registry/src`
+//! - Crates depending on this one by `path`: workspace members, and crates
+//!   in the directories next to the workspace (next to the crate outside
+//!   a workspace)
+//!
+//! Nothing is downloaded; dependencies cargo has not unpacked are skipped.
+
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::scan::gather_rs_files;
+use crate::workspace::{find_all_crates, find_workspace_root, read_manifest};
+
+/// Dependency tables of a manifest (and of each `[target.'cfg(..)']`).
+const DEP_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Root directories of the crates whose code may use the crate at
+/// `crate_root`, sorted. The crate itself is never included.
+pub fn dependency_roots(crate_root: &Path) -> Vec<PathBuf> {
+    dependency_roots_in(crate_root, cargo_home().as_deref())
+}
+
+/// `.rs` files of every [`dependency_roots`] crate, sorted.
+pub fn dependency_files(crate_root: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = dependency_roots(crate_root)
+        .iter()
+        .filter_map(|root| gather_rs_files(root).ok())
+        .flatten()
+        .collect();
+    files.sort();
+    files
+}
+
+fn dependency_roots_in(crate_root: &Path, cargo_home: Option<&Path>) -> Vec<PathBuf> {
+    let Ok(own) = crate_root.canonicalize() else {
+        return Vec::new();
+    };
+    let workspace = find_workspace_root(&own);
+
+    let mut roots = path_dependencies(&own, workspace.as_deref());
+    roots.extend(registry_dependencies(
+        &own,
+        workspace.as_deref().unwrap_or(&own),
+        cargo_home,
+    ));
+    roots.extend(path_dependents(&own, workspace.as_deref()));
+    roots.retain(|root| *root != own);
+    roots.sort();
+    roots.dedup();
+    roots
+}
+
+/// `$CARGO_HOME`, or `~/.cargo`.
+fn cargo_home() -> Option<PathBuf> {
+    if let Some(home) = std::env::var_os("CARGO_HOME") {
+        return Some(PathBuf::from(home));
+    }
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".cargo"))
+}
+
+/// Every `(key, spec)` in the dependency tables of `manifest`.
+fn dependency_specs(manifest: &toml::Value) -> Vec<(&str, &toml::Value)> {
+    let mut tables = vec![manifest];
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        tables.extend(targets.values());
+    }
+    tables
+        .into_iter()
+        .flat_map(|table| {
+            DEP_TABLES
+                .iter()
+                .filter_map(move |name| table.get(name)?.as_table())
+        })
+        .flat_map(|deps| deps.iter().map(|(key, spec)| (key.as_str(), spec)))
+        .collect()
+}
+
+/// The `[workspace.dependencies]` entry a `dep = { workspace = true }`
+/// inherits, if `spec` is one.
+fn inherited<'a>(
+    spec: &toml::Value,
+    key: &str,
+    workspace: Option<&'a toml::Value>,
+) -> Option<&'a toml::Value> {
+    if spec.get("workspace").and_then(|w| w.as_bool()) != Some(true) {
+        return None;
+    }
+    workspace?.get("workspace")?.get("dependencies")?.get(key)
+}
+
+/// Canonical directories of the `path` dependencies of the crate at `root`.
+fn path_dependencies(root: &Path, workspace: Option<&Path>) -> Vec<PathBuf> {
+    let Some(manifest) = read_manifest(root) else {
+        return Vec::new();
+    };
+    let workspace_manifest = workspace.and_then(read_manifest);
+    dependency_specs(&manifest)
+        .into_iter()
+        .filter_map(
+            |(key, spec)| match inherited(spec, key, workspace_manifest.as_ref()) {
+                Some(spec) => workspace?
+                    .join(spec.get("path")?.as_str()?)
+                    .canonicalize()
+                    .ok(),
+                None => root.join(spec.get("path")?.as_str()?).canonicalize().ok(),
+            },
+        )
+        .collect()
+}
+
+/// Unpacked sources of the registry dependencies of the crate at `root`,
+/// at the versions pinned by the `Cargo.lock` in `lock_dir`.
+fn registry_dependencies(root: &Path, lock_dir: &Path, cargo_home: Option<&Path>) -> Vec<PathBuf> {
+    let Some(manifest) = read_manifest(root) else {
+        return Vec::new();
+    };
+    let workspace_manifest = read_manifest(lock_dir);
+    let names: HashSet<&str> = dependency_specs(&manifest)
+        .into_iter()
+        .filter_map(|(key, spec)| {
+            let spec = inherited(spec, key, workspace_manifest.as_ref()).unwrap_or(spec);
+            if spec.get("path").is_some() || spec.get("git").is_some() {
+                return None;
+            }
+            Some(spec.get("package").and_then(|p| p.as_str()).unwrap_or(key))
+        })
+        .collect();
+    let pinned = locked_versions(lock_dir, &names);
+
+    let registries: Vec<PathBuf> = cargo_home
+        .and_then(|home| fs::read_dir(home.join("registry/src")).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    pinned
+        .into_iter()
+        .filter_map(|(name, version)| {
+            let versioned = format!("{}-{}", name, version);
+            let vendor = lock_dir.join("vendor");
+            [vendor.join(&versioned), vendor.join(&name)]
+                .into_iter()
+                .chain(registries.iter().map(|registry| registry.join(&versioned)))
+                .find(|dir| dir.join("Cargo.toml").is_file())
+        })
+        .collect()
+}
+
+/// `(name, version)` of the registry packages in `<lock_dir>/Cargo.lock`
+/// named in `names`.
+fn locked_versions(lock_dir: &Path, names: &HashSet<&str>) -> Vec<(String, String)> {
+    let Some(lock) = fs::read_to_string(lock_dir.join("Cargo.lock"))
+        .ok()
+        .and_then(|text| text.parse::<toml::Value>().ok())
+    else {
+        return Vec::new();
+    };
+    let Some(packages) = lock.get("package").and_then(|p| p.as_array()) else {
+        return Vec::new();
+    };
+    packages
+        .iter()
+        .filter(|package| {
+            package
+                .get("source")
+                .and_then(|s| s.as_str())
+                .is_some_and(|source| {
+                    source.starts_with("registry+") || source.starts_with("sparse+")
+                })
+        })
+        .filter_map(|package| {
+            let name = package.get("name")?.as_str()?;
+            let version = package.get("version")?.as_str()?;
+            names
+                .contains(name)
+                .then(|| (name.to_string(), version.to_string()))
+        })
+        .collect()
+}
+
+/// Crates with a `path` dependency on the crate at `own`: workspace members
+/// and crates next to the workspace (or next to `own` outside one).
+fn path_dependents(own: &Path, workspace: Option<&Path>) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = workspace
+        .and_then(|ws| find_all_crates(ws).ok())
+        .unwrap_or_default();
+    let base = workspace.unwrap_or(own);
+    if let Some(parent) = base.parent() {
+        for entry in fs::read_dir(parent).into_iter().flatten().flatten() {
+            let dir = entry.path();
+            if dir != base && !is_hidden(&dir) {
+                candidates.extend(crates_at(&dir));
+            }
+        }
+    }
+    candidates
+        .into_iter()
+        .filter_map(|dir| dir.canonicalize().ok())
+        .filter(|dir| dir != own)
+        .filter(|dir| {
+            path_dependencies(dir, find_workspace_root(dir).as_deref())
+                .iter()
+                .any(|dep| dep == own)
+        })
+        .collect()
+}
+
+/// `dir` and its direct subdirectories that hold a `Cargo.toml`.
+fn crates_at(dir: &Path) -> Vec<PathBuf> {
+    if !dir.join("Cargo.toml").is_file() {
+        return Vec::new();
+    }
+    let mut crates = vec![dir.to_path_buf()];
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let sub = entry.path();
+        if !is_hidden(&sub)
+            && sub.file_name() != Some(OsStr::new("target"))
+            && sub.join("Cargo.toml").is_file()
+        {
+            crates.push(sub);
+        }
+    }
+    crates
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn package(dir: &Path, name: &str, deps: &str) {
+        write(
+            &dir.join("Cargo.toml"),
+            &format!("[package]\nname = \"{}\"\n\n[dependencies]\n{}", name, deps),
+        );
+        write(&dir.join("src/lib.rs"), "");
+    }
+
+    #[test]
+    fn test_dependency_roots() {
+        let base = std::env::temp_dir().join(format!("deadmod_deps_{}", std::process::id()));
+        fs::remove_dir_all(&base).ok();
+        let own = base.join("core");
+        let home = base.join(".cargo");
+        package(
+            &own,
+            "core",
+            "util = { path = \"../util\" }\nserde = \"1\"\nlog = \"0.4\"\n",
+        );
+        write(
+            &own.join("Cargo.lock"),
+            "[[package]]\nname = \"serde\"\nversion = \"1.0.1\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n\
+             [[package]]\nname = \"log\"\nversion = \"0.4.2\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+        );
+        package(&base.join("util"), "util", "");
+        package(&base.join("app"), "app", "core = { path = \"../core\" }\n");
+        package(&base.join("other"), "other", "");
+        package(
+            &home.join("registry/src/index.crates.io-6f17d22bba15001f/serde-1.0.1"),
+            "serde",
+            "",
+        );
+
+        let roots = dependency_roots_in(&own, Some(&home));
+        let canonical = |p: PathBuf| p.canonicalize().unwrap();
+        assert_eq!(
+            roots,
+            vec![
+                canonical(home.join("registry/src/index.crates.io-6f17d22bba15001f/serde-1.0.1")),
+                canonical(base.join("app")),
+                canonical(base.join("util")),
+            ]
+        );
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_dependency_roots_vendored_and_workspace_inherited() {
+        let base = std::env::temp_dir().join(format!("deadmod_deps_ws_{}", std::process::id()));
+        fs::remove_dir_all(&base).ok();
+        write(
+            &base.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"core\", \"cli\"]\n\n[workspace.dependencies]\ncore = { path = \"core\" }\n",
+        );
+        write(
+            &base.join("Cargo.lock"),
+            "[[package]]\nname = \"serde\"\nversion = \"1.0.1\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+        );
+        package(&base.join("core"), "core", "serde = \"1\"\n");
+        package(&base.join("cli"), "cli", "core = { workspace = true }\n");
+        package(&base.join("vendor/serde"), "serde", "");
+
+        let roots = dependency_roots_in(&base.join("core"), None);
+        assert_eq!(
+            roots,
+            vec![
+                base.join("cli").canonicalize().unwrap(),
+                base.join("vendor/serde").canonicalize().unwrap()
+            ]
+        );
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+}
@@ -1,19 +1,65 @@
 //! Dead module detection logic.
 
+use crate::graph::ModuleVisibility;
 use crate::parse::ModuleInfo;
 use std::collections::{HashMap, HashSet};
 
+/// A dead module and why nothing reaches it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadModuleReport<'a> {
+    /// Module key
+    pub name: &'a str,
+    /// Modules referencing this one, sorted. All of them are dead too:
+    /// a reference from a live module would have made this one live.
+    pub referenced_by: Vec<&'a str>,
+}
+
 /// Finds modules present in the system but not present in the reachable set.
+///
+/// Each report lists the modules that still reference the dead one, using
+/// the same privacy rules as [`crate::graph::build_graph`]. Modules flagged
+/// as [`ModuleInfo::collision`] or [`ModuleInfo::generated`] get no verdict.
 pub fn find_dead<'a>(
     mods: &'a HashMap<String, ModuleInfo>,
     reachable: &HashSet<&str>,
-) -> Vec<&'a str> {
-    mods.keys()
-        .map(|s| s.as_str())
+) -> Vec<DeadModuleReport<'a>> {
+    let mut referenced_by: HashMap<&'a str, Vec<&'a str>> = mods
+        .iter()
+        .filter(|(_, info)| !info.collision && !info.generated)
+        .map(|(name, _)| name.as_str())
         .filter(|m| !reachable.contains(m))
+        .map(|m| (m, Vec::new()))
+        .collect();
+
+    // Only edges into dead modules matter, so visibility is checked for those alone
+    let visibility = ModuleVisibility::new(mods);
+    for (name, info) in mods {
+        for dep in &info.refs {
+            if let Some(referrers) = referenced_by.get_mut(dep.as_str()) {
+                if dep != name && visibility.permits(name, dep) {
+                    referrers.push(name.as_str());
+                }
+            }
+        }
+    }
+
+    referenced_by
+        .into_iter()
+        .map(|(name, mut referrers)| {
+            referrers.sort_unstable();
+            DeadModuleReport {
+                name,
+                referenced_by: referrers,
+            }
+        })
         .collect()
 }
 
+/// Names of the dead modules in `reports`.
+pub fn dead_names<'a>(reports: &[DeadModuleReport<'a>]) -> Vec<&'a str> {
+    reports.iter().map(|report| report.name).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -22,12 +68,21 @@ mod tests {
     fn make_module(name: &str) -> ModuleInfo {
         ModuleInfo {
             name: name.to_string(),
+            module_path: name.to_string(),
+            collision: false,
+            krate: None,
+            generated: false,
             path: PathBuf::from(format!("src/{}.rs", name)),
             refs: HashSet::new(),
+            ref_kinds: HashMap::new(),
+            edition: Default::default(),
             visibility: crate::parse::Visibility::Public,
             doc_hidden: false,
             mod_decls: HashMap::new(),
             reexports: HashSet::new(),
+            includes: Vec::new(),
+            test_mods: Default::default(),
+            cfg_mods: Vec::new(),
         }
     }
 
@@ -35,7 +90,7 @@ mod tests {
     fn test_find_dead_empty_inputs() {
         let mods: HashMap<String, ModuleInfo> = HashMap::new();
         let reachable: HashSet<&str> = HashSet::new();
-        let dead = find_dead(&mods, &reachable);
+        let dead = dead_names(&find_dead(&mods, &reachable));
         assert!(dead.is_empty());
     }
 
@@ -47,7 +102,7 @@ mod tests {
         mods.insert("utils".to_string(), make_module("utils"));
 
         let reachable: HashSet<&str> = ["lib", "api", "utils"].into_iter().collect();
-        let dead = find_dead(&mods, &reachable);
+        let dead = dead_names(&find_dead(&mods, &reachable));
         assert!(dead.is_empty());
     }
 
@@ -60,7 +115,7 @@ mod tests {
         mods.insert("unused".to_string(), make_module("unused"));
 
         let reachable: HashSet<&str> = ["lib", "api"].into_iter().collect();
-        let mut dead = find_dead(&mods, &reachable);
+        let mut dead = dead_names(&find_dead(&mods, &reachable));
         dead.sort();
         assert_eq!(dead, vec!["dead_module", "unused"]);
     }
@@ -72,11 +127,48 @@ mod tests {
         mods.insert("orphan2".to_string(), make_module("orphan2"));
 
         let reachable: HashSet<&str> = HashSet::new();
-        let mut dead = find_dead(&mods, &reachable);
+        let mut dead = dead_names(&find_dead(&mods, &reachable));
         dead.sort();
         assert_eq!(dead, vec!["orphan1", "orphan2"]);
     }
 
+    #[test]
+    fn test_find_dead_referenced_by() {
+        let mut mods = HashMap::new();
+        mods.insert("lib".to_string(), make_module("lib"));
+        for (name, refs) in [
+            ("old", &["legacy", "old"][..]),
+            ("tools", &["legacy"]),
+            ("legacy", &[]),
+        ] {
+            let mut module = make_module(name);
+            module.refs.extend(refs.iter().map(|r| r.to_string()));
+            mods.insert(name.to_string(), module);
+        }
+
+        let reachable: HashSet<&str> = ["lib"].into_iter().collect();
+        let mut dead = find_dead(&mods, &reachable);
+        dead.sort_by_key(|report| report.name);
+        assert_eq!(
+            dead,
+            vec![
+                DeadModuleReport {
+                    name: "legacy",
+                    referenced_by: vec!["old", "tools"]
+                },
+                // Self references explain nothing
+                DeadModuleReport {
+                    name: "old",
+                    referenced_by: vec![]
+                },
+                DeadModuleReport {
+                    name: "tools",
+                    referenced_by: vec![]
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_find_dead_reachable_not_in_mods() {
         // Edge case: reachable set contains module not in mods
@@ -84,7 +176,7 @@ mod tests {
         mods.insert("lib".to_string(), make_module("lib"));
 
         let reachable: HashSet<&str> = ["lib", "nonexistent"].into_iter().collect();
-        let dead = find_dead(&mods, &reachable);
+        let dead = dead_names(&find_dead(&mods, &reachable));
         assert!(dead.is_empty());
     }
 }
@@ -0,0 +1,390 @@
+//! Which item detectors a file change can affect.
+//!
+//! A long-running analysis (the daemon, the HTTP server) sees one file change
+//! at a time, and most changes matter to few detectors: editing a file that
+//! neither defines nor invokes macros cannot change the macro findings.
+//! [`DirtyTracker`] keeps, per file and per detector, a fingerprint of what
+//! the detector extracts from it (its definitions and usages). After a
+//! change, only detectors whose fingerprints moved are dirty;
+//! [`Deadmod::analyze_incremental`] re-runs those and reuses the previous
+//! findings of the rest.
+//!
+//! Inputs every detector reads (names mentioned inside macro invocations,
+//! registry macro arguments, `// deadmod:ignore` comments) dirty them all.
+//! Module reachability is always recomputed; parsing is cached anyway.
+//!
+//! [`Deadmod::analyze_incremental`]: crate::builder::Deadmod::analyze_incremental
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::builder::DeadItem;
+use crate::common::{extract_macro_mentions, ignored_ids, KeptItem, LivenessRules};
+use crate::constants::{extract_const_usage, extract_constants};
+use crate::enums::{extract_variant_usage, extract_variants};
+use crate::func::{extract_call_names, extract_functions};
+use crate::generics::{extract_declared_generics, extract_generic_usages};
+use crate::macros::{extract_macro_usages, extract_macros};
+use crate::matcharms::{extract_match_arms, extract_match_usages};
+use crate::traits::{extract_trait_usages, extract_traits};
+
+/// An item-level detector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Detector {
+    /// Functions and methods (also test helpers)
+    Functions,
+    /// Trait and impl methods
+    Traits,
+    /// Constants and statics
+    Constants,
+    /// Enum variants
+    Enums,
+    /// Macros
+    Macros,
+    /// Generic parameters
+    Generics,
+    /// Match arms
+    MatchArms,
+}
+
+impl Detector {
+    /// Every detector, in the order analysis runs them.
+    pub const ALL: [Detector; 7] = [
+        Self::Functions,
+        Self::Traits,
+        Self::Constants,
+        Self::Enums,
+        Self::Macros,
+        Self::Generics,
+        Self::MatchArms,
+    ];
+
+    /// Name used in reports and JSON.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Functions => "functions",
+            Self::Traits => "traits",
+            Self::Constants => "constants",
+            Self::Enums => "enums",
+            Self::Macros => "macros",
+            Self::Generics => "generics",
+            Self::MatchArms => "match_arms",
+        }
+    }
+
+    /// Fingerprint of what this detector extracts from one file.
+    fn facts(self, path: &Path, content: &str) -> u64 {
+        let fp = Fingerprint::default();
+        let fp = match self {
+            Self::Functions => fp
+                .ordered(extract_functions(path, content))
+                .unordered(extract_call_names(path, content)),
+            Self::Traits => {
+                let traits = extract_traits(path, content);
+                fp.ordered(traits.trait_methods)
+                    .ordered(traits.impl_methods)
+                    .ordered(traits.inherent_methods)
+                    .unordered(extract_trait_usages(path, content))
+            }
+            Self::Constants => fp
+                .ordered(extract_constants(path, content))
+                .unordered(extract_const_usage(path, content).used_constants),
+            Self::Enums => {
+                let usage = extract_variant_usage(path, content);
+                fp.ordered(extract_variants(path, content))
+                    .unordered(usage.used_variants)
+                    .unordered(usage.used_full_paths)
+            }
+            Self::Macros => {
+                let usage = extract_macro_usages(path, content);
+                fp.ordered(extract_macros(path, content))
+                    .unordered(usage.used_macros)
+                    .unordered(usage.used_attributes)
+                    .unordered(usage.used_derives)
+            }
+            Self::Generics => {
+                let usage = extract_generic_usages(path, content);
+                let parents = usage.usages_by_parent.into_iter().map(|(parent, used)| {
+                    let mut types: Vec<String> = used.used_types.into_iter().collect();
+                    let mut lifetimes: Vec<String> = used.used_lifetimes.into_iter().collect();
+                    types.sort();
+                    lifetimes.sort();
+                    (parent, types, lifetimes)
+                });
+                fp.ordered(extract_declared_generics(path, content).declared)
+                    .unordered(parents)
+                    .unordered(usage.global_types)
+                    .unordered(usage.global_lifetimes)
+            }
+            Self::MatchArms => {
+                let arms = extract_match_arms(path, content);
+                let usage = extract_match_usages(path, content);
+                fp.ordered(arms.arms)
+                    .ordered([arms.match_count])
+                    .unordered(usage.used_variants)
+                    .unordered(usage.used_full_paths)
+            }
+        };
+        fp.finish()
+    }
+}
+
+impl fmt::Display for Detector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Order-aware hash of extracted values, via their `Debug` output.
+#[derive(Default)]
+struct Fingerprint(DefaultHasher);
+
+impl Fingerprint {
+    /// Values in source order (definitions).
+    fn ordered<T: Debug>(mut self, items: impl IntoIterator<Item = T>) -> Self {
+        for item in items {
+            format!("{:?}", item).hash(&mut self.0);
+        }
+        // Separates groups, so items cannot move between them unnoticed
+        u8::MAX.hash(&mut self.0);
+        self
+    }
+
+    /// Values from hash sets, whose iteration order means nothing.
+    fn unordered<T: Debug>(self, items: impl IntoIterator<Item = T>) -> Self {
+        let mut items: Vec<String> = items
+            .into_iter()
+            .map(|item| format!("{:?}", item))
+            .collect();
+        items.sort();
+        self.ordered(items)
+    }
+
+    fn finish(self) -> u64 {
+        self.0.finish()
+    }
+}
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Fingerprints of one file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct FileFacts {
+    content: u64,
+    /// Inputs every detector reads
+    shared: u64,
+    detectors: BTreeMap<Detector, u64>,
+}
+
+impl FileFacts {
+    fn of(
+        path: &Path,
+        content: &str,
+        detectors: &BTreeSet<Detector>,
+        liveness: &LivenessRules,
+    ) -> Self {
+        let shared = Fingerprint::default()
+            .unordered(extract_macro_mentions(content))
+            .unordered(liveness.registered_names(content))
+            .unordered(ignored_ids(content))
+            .finish();
+        Self {
+            content: content_hash(content),
+            shared,
+            detectors: detectors
+                .iter()
+                .map(|d| (*d, d.facts(path, content)))
+                .collect(),
+        }
+    }
+}
+
+/// Per-file fingerprints of what each detector extracts.
+#[derive(Debug, Clone, Default)]
+pub struct DirtyTracker {
+    files: HashMap<PathBuf, FileFacts>,
+}
+
+impl DirtyTracker {
+    /// A tracker that has seen nothing yet: every detector starts dirty.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether any file has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Record the current `(path, content)` of every file and return which
+    /// of `detectors` their changes since the last call affect.
+    ///
+    /// The first call returns every detector. After that, only files whose
+    /// content changed are re-extracted. Added and removed
+    /// files count as changed from an empty file, so adding a file without
+    /// constants leaves constant findings alone.
+    pub fn update(
+        &mut self,
+        files: &[(PathBuf, String)],
+        detectors: &BTreeSet<Detector>,
+        liveness: &LivenessRules,
+    ) -> BTreeSet<Detector> {
+        let mut dirty = if self.files.is_empty() {
+            detectors.clone()
+        } else {
+            BTreeSet::new()
+        };
+        let mut empty: Option<FileFacts> = None;
+        let mut empty_facts = |path: &Path| {
+            empty
+                .get_or_insert_with(|| FileFacts::of(path, "", detectors, liveness))
+                .clone()
+        };
+
+        let mut seen = HashMap::with_capacity(files.len());
+        for (path, content) in files {
+            let previous = self.files.remove(path);
+            let current = match previous {
+                Some(ref facts)
+                    if facts.content == content_hash(content)
+                        && detectors.iter().all(|d| facts.detectors.contains_key(d)) =>
+                {
+                    facts.clone()
+                }
+                _ => FileFacts::of(path, content, detectors, liveness),
+            };
+            let previous = previous.unwrap_or_else(|| empty_facts(path));
+            changed(&previous, &current, detectors, &mut dirty);
+            seen.insert(path.clone(), current);
+        }
+        for (path, previous) in std::mem::replace(&mut self.files, seen) {
+            let current = empty_facts(&path);
+            changed(&previous, &current, detectors, &mut dirty);
+        }
+        dirty
+    }
+}
+
+/// Add the detectors whose inputs differ between `previous` and `current`.
+fn changed(
+    previous: &FileFacts,
+    current: &FileFacts,
+    detectors: &BTreeSet<Detector>,
+    dirty: &mut BTreeSet<Detector>,
+) {
+    if previous.shared != current.shared {
+        dirty.extend(detectors);
+        return;
+    }
+    for detector in detectors {
+        if previous.detectors.get(detector) != current.detectors.get(detector) {
+            dirty.insert(*detector);
+        }
+    }
+}
+
+/// What one detector contributed to an [`AnalysisResult`].
+///
+/// [`AnalysisResult`]: crate::builder::AnalysisResult
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DetectorRun {
+    pub dead: Vec<DeadItem>,
+    pub dead_impls: Vec<DeadItem>,
+    pub kept: Vec<KeptItem>,
+    pub entry_functions: Vec<String>,
+}
+
+/// State carried between [`Deadmod::analyze_incremental`] calls.
+///
+/// [`Deadmod::analyze_incremental`]: crate::builder::Deadmod::analyze_incremental
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalState {
+    pub(crate) tracker: DirtyTracker,
+    pub(crate) runs: BTreeMap<Detector, DetectorRun>,
+    pub(crate) generated: BTreeSet<PathBuf>,
+    pub(crate) last_run: BTreeSet<Detector>,
+}
+
+impl IncrementalState {
+    /// Fresh state: the first analysis runs every enabled detector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Detectors the last analysis actually ran (the others were reused).
+    pub fn last_run(&self) -> &BTreeSet<Detector> {
+        &self.last_run
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sources(files: &[(&str, &str)]) -> Vec<(PathBuf, String)> {
+        files
+            .iter()
+            .map(|(p, c)| (PathBuf::from(p), c.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_only_affected_detectors_are_dirty() {
+        let all: BTreeSet<Detector> = Detector::ALL.into_iter().collect();
+        let rules = LivenessRules::default();
+        let mut tracker = DirtyTracker::new();
+
+        let base = [
+            (
+                "src/lib.rs",
+                "macro_rules! m { () => {} }\nfn main() { m!(); }",
+            ),
+            ("src/a.rs", "fn f() {}"),
+        ];
+        assert_eq!(tracker.update(&sources(&base), &all, &rules), all);
+        assert!(tracker.update(&sources(&base), &all, &rules).is_empty());
+
+        // A new function in a file without macros leaves macro analysis alone
+        let edited = [base[0], ("src/a.rs", "fn f() {}\nfn g() {}")];
+        let dirty = tracker.update(&sources(&edited), &all, &rules);
+        assert!(dirty.contains(&Detector::Functions));
+        assert!(!dirty.contains(&Detector::Macros));
+        assert!(!dirty.contains(&Detector::Constants));
+
+        // A trailing comment changes nothing any detector extracts
+        let commented = [base[0], ("src/a.rs", "fn f() {}\nfn g() {} // note")];
+        assert!(tracker
+            .update(&sources(&commented), &all, &rules)
+            .is_empty());
+
+        // Removing the macro file dirties macros
+        let removed = [commented[1]];
+        assert!(tracker
+            .update(&sources(&removed), &all, &rules)
+            .contains(&Detector::Macros));
+    }
+
+    #[test]
+    fn test_ignore_comments_dirty_every_detector() {
+        let all: BTreeSet<Detector> = Detector::ALL.into_iter().collect();
+        let rules = LivenessRules::default();
+        let mut tracker = DirtyTracker::new();
+        tracker.update(&sources(&[("src/lib.rs", "fn f() {}")]), &all, &rules);
+        let dirty = tracker.update(
+            &sources(&[("src/lib.rs", "// deadmod:ignore 0123456789ab\nfn f() {}")]),
+            &all,
+            &rules,
+        );
+        assert_eq!(dirty, all);
+    }
+}
@@ -0,0 +1,152 @@
+//! Rust editions, as far as module resolution is concerned.
+//!
+//! The 2015 edition resolves paths differently: `::net::connect()` and
+//! `use ::net::Conn` name the crate's own top-level `net` module, where later
+//! editions read them as the external crate `net`. Parsing records such
+//! references apart (see [`ModuleInfo::edition`]), and the crate's edition
+//! decides whether they become module edges.
+//!
+//! [`ModuleInfo::edition`]: crate::parse::ModuleInfo::edition
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+use crate::workspace::find_workspace_root;
+
+/// A Rust edition.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum Edition {
+    #[serde(rename = "2015")]
+    E2015,
+    #[serde(rename = "2018")]
+    E2018,
+    /// Assumed when nothing says otherwise (no manifest, in-memory sources)
+    #[default]
+    #[serde(rename = "2021")]
+    E2021,
+    #[serde(rename = "2024")]
+    E2024,
+}
+
+impl Edition {
+    /// Edition of the crate at `crate_root`, from its `Cargo.toml`.
+    ///
+    /// Follows `edition.workspace = true` to `[workspace.package]`. A
+    /// manifest without an edition is 2015, as for Cargo; no (readable)
+    /// manifest gives the default.
+    pub fn for_crate(crate_root: &Path) -> Self {
+        let Some(manifest) = read_manifest(crate_root) else {
+            return Self::default();
+        };
+        let Some(edition) = manifest.get("package").and_then(|p| p.get("edition")) else {
+            return Self::E2015;
+        };
+        let inherited = edition.get("workspace").and_then(|w| w.as_bool()) == Some(true);
+        let value = if inherited {
+            find_workspace_root(crate_root)
+                .and_then(|ws| read_manifest(&ws))
+                .and_then(|ws| {
+                    ws.get("workspace")?
+                        .get("package")?
+                        .get("edition")?
+                        .as_str()
+                        .map(str::to_string)
+                })
+        } else {
+            edition.as_str().map(str::to_string)
+        };
+        match value.map(|v| v.parse()) {
+            Some(Ok(edition)) => edition,
+            Some(Err(e)) => {
+                eprintln!(
+                    "[WARN] {} in {}",
+                    e,
+                    crate_root.join("Cargo.toml").display()
+                );
+                Self::default()
+            }
+            None => Self::E2015,
+        }
+    }
+
+    /// Whether `::name` paths start at the crate root (2015) rather than
+    /// naming another crate.
+    pub fn crate_relative_absolute_paths(self) -> bool {
+        self == Self::E2015
+    }
+
+    /// The edition as written in `Cargo.toml`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::E2015 => "2015",
+            Self::E2018 => "2018",
+            Self::E2021 => "2021",
+            Self::E2024 => "2024",
+        }
+    }
+}
+
+impl fmt::Display for Edition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Edition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Ok(match s.trim() {
+            "2015" => Self::E2015,
+            "2018" => Self::E2018,
+            "2021" => Self::E2021,
+            "2024" => Self::E2024,
+            other => bail!("Unknown edition: {}", other),
+        })
+    }
+}
+
+fn read_manifest(crate_root: &Path) -> Option<toml::Value> {
+    let text = fs::read_to_string(crate_root.join("Cargo.toml")).ok()?;
+    toml::from_str(&text).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edition_for_crate() {
+        let dir = std::env::temp_dir().join(format!("deadmod_edition_test_{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join("old")).unwrap();
+        fs::create_dir_all(dir.join("new")).unwrap();
+        fs::create_dir_all(dir.join("none")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"old\", \"new\"]\n\n[workspace.package]\nedition = \"2018\"\n",
+        )
+        .unwrap();
+        fs::write(dir.join("old/Cargo.toml"), "[package]\nname = \"old\"\n").unwrap();
+        fs::write(
+            dir.join("new/Cargo.toml"),
+            "[package]\nname = \"new\"\nedition.workspace = true\n",
+        )
+        .unwrap();
+
+        assert_eq!(Edition::for_crate(&dir.join("old")), Edition::E2015);
+        assert_eq!(Edition::for_crate(&dir.join("new")), Edition::E2018);
+        assert_eq!(Edition::for_crate(&dir.join("none")), Edition::E2021);
+        assert_eq!("2024".parse::<Edition>().unwrap(), Edition::E2024);
+        assert!("2019".parse::<Edition>().is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
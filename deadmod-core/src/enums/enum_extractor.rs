@@ -11,7 +11,7 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 use syn::{visit::Visit, File, Item, ItemEnum, ItemMod};
 
-use crate::common::visibility_str;
+use crate::common::{has_cfg_attr, keep_annotation, visibility_str, Keep};
 
 /// Information about an enum variant definition.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,10 +24,19 @@ pub struct EnumVariantDef {
     pub full_name: String,
     /// Source file path
     pub file: String,
+    /// 1-based line of the variant's name
+    #[serde(default)]
+    pub line: usize,
     /// Module path
     pub module_path: String,
     /// Visibility of the parent enum
     pub visibility: String,
+    /// Whether the variant or its enum has #[cfg] or #[cfg_attr] attributes
+    #[serde(default)]
+    pub has_cfg: bool,
+    /// `#[deadmod::keep]` details from the parent enum, if it is kept on purpose
+    #[serde(default)]
+    pub keep: Option<Keep>,
 }
 
 /// AST visitor that extracts all enum variant definitions.
@@ -58,10 +67,14 @@ impl<'ast> Visit<'ast> for EnumVariantExtractor {
                 ident,
                 variants,
                 vis,
+                attrs,
                 ..
             }) => {
                 let enum_name = ident.to_string();
                 let visibility = visibility_str(vis);
+                let enum_cfg = has_cfg_attr(attrs);
+                // Attribute macros are not allowed on variants: keep applies per enum
+                let keep = keep_annotation(attrs);
 
                 for variant in variants {
                     let variant_name = variant.ident.to_string();
@@ -70,8 +83,11 @@ impl<'ast> Visit<'ast> for EnumVariantExtractor {
                         variant_name: variant_name.clone(),
                         full_name: format!("{}::{}", enum_name, variant_name),
                         file: self.file_path.clone(),
+                        line: variant.ident.span().start().line,
                         module_path: self.build_module_path(),
                         visibility: visibility.to_string(),
+                        has_cfg: enum_cfg || has_cfg_attr(&variant.attrs),
+                        keep: keep.clone(),
                     });
                 }
             }
@@ -210,6 +226,34 @@ enum Second { X, Y, Z }
         assert_eq!(result.len(), 5);
     }
 
+    #[test]
+    fn test_extract_keep_annotation() {
+        let content = r#"
+#[deadmod::keep(reason = "wire format")]
+enum Opcode { Nop, Halt }
+
+enum Plain { A }
+"#;
+        let result = extract_variants(&PathBuf::from("test.rs"), content);
+        let nop = result.iter().find(|v| v.variant_name == "Nop").unwrap();
+        assert_eq!(
+            nop.keep.as_ref().unwrap().reason.as_deref(),
+            Some("wire format")
+        );
+        assert!(result
+            .iter()
+            .find(|v| v.variant_name == "Halt")
+            .unwrap()
+            .keep
+            .is_some());
+        assert!(result
+            .iter()
+            .find(|v| v.variant_name == "A")
+            .unwrap()
+            .keep
+            .is_none());
+    }
+
     #[test]
     fn test_malformed_resilient() {
         let content = "enum { broken }";
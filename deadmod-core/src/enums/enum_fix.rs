@@ -0,0 +1,105 @@
+//! Removal of dead variants from enum declarations.
+//!
+//! Only the declaration is touched here; match arms naming a removed variant
+//! are handled by [`crate::matcharms::strip_variant_arms`], and the two are
+//! applied together by [`crate::fix::fix_dead_variants`].
+
+use syn::spanned::Spanned;
+use syn::{visit::Visit, File, ItemEnum};
+
+use crate::common::SourceEdits;
+
+/// Remove the `(enum, variant)` pairs declared in `content`, including their
+/// attributes, doc comments and trailing comma.
+///
+/// Returns the new content and the number of variants removed, or `None`
+/// when nothing matched or the file does not parse.
+pub fn strip_variant_decls(content: &str, targets: &[(&str, &str)]) -> Option<(String, usize)> {
+    let ast: File = syn::parse_file(content).ok()?;
+    let mut finder = VariantFinder {
+        edits: SourceEdits::new(content),
+        targets,
+        removed: 0,
+    };
+    finder.visit_file(&ast);
+
+    if finder.removed == 0 {
+        return None;
+    }
+    let removed = finder.removed;
+    Some((finder.edits.apply(), removed))
+}
+
+struct VariantFinder<'a, 't> {
+    edits: SourceEdits<'a>,
+    targets: &'t [(&'t str, &'t str)],
+    removed: usize,
+}
+
+impl<'ast> Visit<'ast> for VariantFinder<'_, '_> {
+    fn visit_item_enum(&mut self, item: &'ast ItemEnum) {
+        let enum_name = item.ident.to_string();
+        for pair in item.variants.pairs() {
+            let variant = pair.value().ident.to_string();
+            if !self
+                .targets
+                .contains(&(enum_name.as_str(), variant.as_str()))
+            {
+                continue;
+            }
+            let start = self.edits.offset(pair.value().span().start());
+            let end = match pair.punct() {
+                Some(comma) => self.edits.offset(comma.span().end()),
+                None => self.edits.offset(pair.value().span().end()),
+            };
+            self.edits.remove(start..end);
+            self.removed += 1;
+        }
+        syn::visit::visit_item_enum(self, item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_variant_decls() {
+        let content = "\
+/// Traffic light
+enum Light {
+    Red,
+    /// Never used
+    #[allow(dead_code)]
+    Amber(u8),
+    Green { wait: u32 },
+}
+
+mod other {
+    enum Light { Amber }
+}
+";
+        let (stripped, removed) = strip_variant_decls(content, &[("Light", "Amber")]).unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(
+            stripped,
+            "\
+/// Traffic light
+enum Light {
+    Red,
+    Green { wait: u32 },
+}
+
+mod other {
+    enum Light {  }
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_strip_variant_decls_no_match() {
+        assert!(strip_variant_decls("enum A { X }", &[("A", "Y")]).is_none());
+        assert!(strip_variant_decls("enum A {", &[("A", "X")]).is_none());
+    }
+}
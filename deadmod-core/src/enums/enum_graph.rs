@@ -4,6 +4,7 @@
 //!
 //! Entry points (always considered reachable):
 //! - Variants of public enums (could be used by external crates)
+//! - Variants of `#[deadmod::keep]` enums (listed in [`EnumAnalysisResult::kept`])
 //!
 //! Performance characteristics:
 //! - Graph build: O(|V| + |U|) where V = variants, U = usages
@@ -13,6 +14,7 @@ use std::collections::HashSet;
 
 use super::enum_extractor::EnumVariantDef;
 use super::enum_usage::EnumUsageResult;
+use crate::common::{Confidence, ConfidenceSignals, KeptItem};
 
 /// A dead enum variant that was declared but never used.
 #[derive(Debug, Clone)]
@@ -25,10 +27,14 @@ pub struct DeadVariant {
     pub full_name: String,
     /// Source file
     pub file: String,
+    /// 1-based line of the variant's name
+    pub line: usize,
     /// Module path
     pub module_path: String,
     /// Visibility of parent enum
     pub visibility: String,
+    /// Confidence that the variant is really unused
+    pub confidence: Confidence,
 }
 
 /// Statistics about enum variant analysis.
@@ -45,10 +51,23 @@ pub struct EnumStats {
 pub struct EnumAnalysisResult {
     /// All dead variants found
     pub dead: Vec<DeadVariant>,
+    /// Enums kept alive by `#[deadmod::keep]`
+    pub kept: Vec<KeptItem>,
     /// Statistics
     pub stats: EnumStats,
 }
 
+impl EnumAnalysisResult {
+    /// Drop dead variants below `min` confidence and update the dead count.
+    ///
+    /// `dead_enum_count` still reflects every variant, since an enum is only
+    /// fully dead if all of its variants are.
+    pub fn retain_confidence(&mut self, min: Confidence) {
+        self.dead.retain(|v| v.confidence >= min);
+        self.stats.dead_variant_count = self.dead.len();
+    }
+}
+
 /// Graph for analyzing enum variant usage.
 #[derive(Default)]
 pub struct EnumGraph {
@@ -58,6 +77,8 @@ pub struct EnumGraph {
     used_variants: HashSet<String>,
     /// Set of used full paths like "Enum::Variant"
     used_full_paths: HashSet<String>,
+    /// Identifiers mentioned inside macro invocations (confidence signal)
+    macro_mentions: HashSet<String>,
 }
 
 impl EnumGraph {
@@ -75,9 +96,19 @@ impl EnumGraph {
             declared,
             used_variants,
             used_full_paths,
+            macro_mentions: HashSet::new(),
         }
     }
 
+    /// Provide identifiers found inside macro invocations.
+    ///
+    /// Dead variants whose name is mentioned in a macro are reported with
+    /// lower confidence.
+    pub fn with_macro_mentions(mut self, mentions: HashSet<String>) -> Self {
+        self.macro_mentions = mentions;
+        self
+    }
+
     /// Check if a variant is used.
     fn is_variant_used(&self, variant: &EnumVariantDef) -> bool {
         // Check by variant name (simple match)
@@ -101,14 +132,22 @@ impl EnumGraph {
         let mut dead = Vec::new();
 
         for variant in &self.declared {
-            if !self.is_variant_used(variant) {
+            if variant.keep.is_none() && !self.is_variant_used(variant) {
                 dead.push(DeadVariant {
                     enum_name: variant.enum_name.clone(),
                     variant_name: variant.variant_name.clone(),
                     full_name: variant.full_name.clone(),
                     file: variant.file.clone(),
+                    line: variant.line,
                     module_path: variant.module_path.clone(),
                     visibility: variant.visibility.clone(),
+                    confidence: ConfidenceSignals {
+                        visibility: &variant.visibility,
+                        has_cfg: variant.has_cfg,
+                        in_macro: self.macro_mentions.contains(&variant.variant_name),
+                        ..Default::default()
+                    }
+                    .score(),
                 });
             }
         }
@@ -148,7 +187,20 @@ impl EnumGraph {
             dead_enum_count: fully_dead_enum_count,
         };
 
-        EnumAnalysisResult { dead, stats }
+        // One entry per kept enum, not per variant
+        let mut seen = HashSet::new();
+        let kept = self
+            .declared
+            .iter()
+            .filter(|v| seen.insert((&v.file, &v.enum_name)))
+            .filter_map(|v| {
+                v.keep
+                    .as_ref()
+                    .map(|keep| KeptItem::new(&v.enum_name, &v.file, keep))
+            })
+            .collect();
+
+        EnumAnalysisResult { dead, kept, stats }
     }
 
     /// Get the total number of declared variants.
@@ -167,8 +219,11 @@ mod tests {
             variant_name: variant_name.to_string(),
             full_name: format!("{}::{}", enum_name, variant_name),
             file: file.to_string(),
+            line: 0,
             module_path: String::new(),
             visibility: "private".to_string(),
+            has_cfg: false,
+            keep: None,
         }
     }
 
@@ -271,4 +326,49 @@ mod tests {
         assert_eq!(result.stats.dead_variant_count, 3); // B, Y, Z
         assert_eq!(result.stats.dead_enum_count, 0); // Both have at least one used
     }
+
+    #[test]
+    fn test_kept_enum_variants_are_not_dead() {
+        let keep = crate::common::Keep {
+            reason: Some("matched by the FFI layer".to_string()),
+        };
+        let mut declared = vec![
+            make_variant("Code", "Ok", "test.rs"),
+            make_variant("Code", "Err", "test.rs"),
+            make_variant("Other", "Unused", "test.rs"),
+        ];
+        declared[0].keep = Some(keep.clone());
+        declared[1].keep = Some(keep);
+
+        let result = EnumGraph::new(declared, &[]).analyze();
+        assert_eq!(result.dead.len(), 1);
+        assert_eq!(result.dead[0].full_name, "Other::Unused");
+        assert_eq!(result.kept.len(), 1);
+        assert_eq!(result.kept[0].name, "Code");
+    }
+
+    #[test]
+    fn test_dead_variant_confidence() {
+        let mut gated = make_variant("Status", "Gated", "test.rs");
+        gated.has_cfg = true;
+        let declared = vec![
+            make_variant("Status", "Idle", "test.rs"),
+            make_variant("Status", "Quoted", "test.rs"),
+            gated,
+        ];
+
+        let graph = EnumGraph::new(declared, &[])
+            .with_macro_mentions(HashSet::from(["Quoted".to_string()]));
+        let dead = graph.find_dead();
+
+        let confidence = |name: &str| {
+            dead.iter()
+                .find(|d| d.variant_name == name)
+                .unwrap()
+                .confidence
+        };
+        assert_eq!(confidence("Idle"), Confidence::High);
+        assert_eq!(confidence("Gated"), Confidence::Medium);
+        assert_eq!(confidence("Quoted"), Confidence::Low);
+    }
 }
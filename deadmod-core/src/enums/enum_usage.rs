@@ -10,7 +10,9 @@
 
 use std::collections::HashSet;
 use std::path::Path;
-use syn::{visit::Visit, Arm, Expr, File, Pat};
+use syn::{visit::Visit, Arm, Expr, File, Macro, Pat};
+
+use crate::common::visit_macro_args;
 
 /// Information about enum variant usages in a file.
 #[derive(Debug, Clone, Default)]
@@ -132,6 +134,12 @@ impl<'ast> Visit<'ast> for EnumUsageExtractor {
         // Visit body expression
         self.visit_expr(&arm.body);
     }
+
+    // Variants inside macro arguments: assert_eq!(state, State::Ready)
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac, |v, found| v.record_path(&found.path));
+        syn::visit::visit_macro(self, mac);
+    }
 }
 
 /// Extract all enum variant usages from file content.
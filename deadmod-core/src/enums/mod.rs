@@ -46,10 +46,12 @@
 //! ```
 
 pub mod enum_extractor;
+pub mod enum_fix;
 pub mod enum_graph;
 pub mod enum_usage;
 
 // Re-exports for convenience
 pub use enum_extractor::{extract_variants, EnumExtractionResult, EnumVariantDef};
+pub use enum_fix::strip_variant_decls;
 pub use enum_graph::{DeadVariant, EnumAnalysisResult, EnumGraph, EnumStats};
 pub use enum_usage::{extract_variant_usage, EnumUsageResult};
@@ -59,6 +59,35 @@ pub enum DeadmodError {
     /// Generic internal error
     #[error("Internal error: {message}")]
     Internal { message: String },
+
+    /// No crate root at or above the given path
+    #[error("No crate root found (searched: {})", join_paths(.searched_paths))]
+    CrateRootNotFound { searched_paths: Vec<PathBuf> },
+
+    /// A cache file that exists but cannot be decoded
+    #[error("Corrupted cache at {}: {message}", .path.display())]
+    CacheCorrupted { path: PathBuf, message: String },
+
+    /// A `deadmod.toml` that is not valid TOML or has a bad value
+    #[error("Invalid config {}{}: {message}", .path.display(), .line.map(|l| format!(":{}", l)).unwrap_or_default())]
+    ConfigInvalid {
+        path: PathBuf,
+        /// Line number (1-indexed) if available
+        line: Option<usize>,
+        message: String,
+    },
+
+    /// An output destination refused for safety
+    #[error("Output path rejected: {reason}: {}", .path.display())]
+    OutputPathRejected { path: PathBuf, reason: String },
+}
+
+fn join_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 impl DeadmodError {
@@ -133,14 +162,78 @@ impl DeadmodError {
         }
     }
 
+    /// Create an internal error.
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::Internal {
+            message: message.into(),
+        }
+    }
+
+    /// Create an error for a path with no crate root.
+    pub fn crate_root_not_found(searched_paths: Vec<PathBuf>) -> Self {
+        Self::CrateRootNotFound { searched_paths }
+    }
+
+    /// Create a corrupted cache error.
+    pub fn cache_corrupted(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Self::CacheCorrupted {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Create an invalid config error, with the line if known.
+    pub fn config_invalid(
+        path: impl Into<PathBuf>,
+        line: Option<usize>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::ConfigInvalid {
+            path: path.into(),
+            line,
+            message: message.into(),
+        }
+    }
+
+    /// Create a rejected output path error.
+    pub fn output_path_rejected(path: impl Into<PathBuf>, reason: impl Into<String>) -> Self {
+        Self::OutputPathRejected {
+            path: path.into(),
+            reason: reason.into(),
+        }
+    }
+
     /// Check if this is a recoverable error (can continue analysis).
     pub fn is_recoverable(&self) -> bool {
         matches!(
             self,
-            Self::Parse { .. } | Self::Cache { .. } | Self::Config { .. }
+            Self::Parse { .. }
+                | Self::Cache { .. }
+                | Self::Config { .. }
+                | Self::CacheCorrupted { .. }
+                | Self::ConfigInvalid { .. }
         )
     }
 
+    /// What the user can do about this error, for display under it.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::CrateRootNotFound { .. } => {
+                Some("pass an existing directory holding Cargo.toml or src/, e.g. `deadmod path/to/crate`")
+            }
+            Self::CacheCorrupted { .. } => {
+                Some("delete the .deadmod directory or run with --no-cache; the cache is rebuilt on the next run")
+            }
+            Self::ConfigInvalid { .. } => {
+                Some("fix or remove the entry in deadmod.toml; CLI_REFERENCE.md lists the keys it accepts")
+            }
+            Self::OutputPathRejected { .. } => {
+                Some("write outputs to a relative path inside the current directory, without `..`")
+            }
+            _ => None,
+        }
+    }
+
     /// Get the path associated with this error, if any.
     pub fn path(&self) -> Option<&PathBuf> {
         match self {
@@ -148,6 +241,10 @@ impl DeadmodError {
             Self::Parse { path, .. } => Some(path),
             Self::Config { path, .. } => Some(path),
             Self::Workspace { path, .. } => Some(path),
+            Self::CrateRootNotFound { searched_paths } => searched_paths.first(),
+            Self::CacheCorrupted { path, .. } => Some(path),
+            Self::ConfigInvalid { path, .. } => Some(path),
+            Self::OutputPathRejected { path, .. } => Some(path),
             _ => None,
         }
     }
@@ -201,6 +298,40 @@ mod tests {
         assert!(!DeadmodError::security("path traversal").is_recoverable());
     }
 
+    #[test]
+    fn test_hints() {
+        let err = DeadmodError::config_invalid("deadmod.toml", Some(3), "unknown field `ignored`");
+        assert_eq!(
+            err.to_string(),
+            "Invalid config deadmod.toml:3: unknown field `ignored`"
+        );
+        assert!(err.hint().is_some());
+        assert!(err.is_recoverable());
+
+        let err = DeadmodError::crate_root_not_found(vec![
+            PathBuf::from("missing"),
+            PathBuf::from("/work"),
+        ]);
+        assert_eq!(
+            err.to_string(),
+            "No crate root found (searched: missing, /work)"
+        );
+        assert_eq!(err.path(), Some(&PathBuf::from("missing")));
+        assert!(err.hint().unwrap().contains("Cargo.toml"));
+
+        assert!(
+            DeadmodError::output_path_rejected("/tmp/x", "absolute path")
+                .hint()
+                .is_some()
+        );
+        assert!(
+            DeadmodError::cache_corrupted(".deadmod/cache.bin", "truncated")
+                .hint()
+                .is_some()
+        );
+        assert!(DeadmodError::security("path traversal").hint().is_none());
+    }
+
     #[test]
     fn test_io_result_ext() {
         let result: std::io::Result<()> =
@@ -0,0 +1,591 @@
+//! Why a finding was reported: the evidence behind one verdict.
+//!
+//! [`Deadmod::analyze`](crate::builder::Deadmod::analyze) records what it
+//! started from and what it read in [`AnalysisEvidence`]. [`explain`] turns
+//! that into a [`FindingEvidence`] for one finding ID: the entry points
+//! reachability started from, the files searched for references, and every
+//! place the name still appears together with the reason it did not keep
+//! the item alive. This is what users need when they suspect a false
+//! positive (`deadmod explain <id>`).
+//!
+//! Mentions are found textually and classified with the token stream: a
+//! name inside a comment, string or macro body is never a reference, and a
+//! code mention that still did not count was either unreachable itself or
+//! resolved to something else (a local, a field, an item of the same name).
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Delimiter, LineColumn, Spacing, TokenStream, TokenTree};
+use serde::{Deserialize, Serialize};
+
+use crate::builder::{AnalysisResult, DeadItem, DeadItemKind};
+use crate::common::{module_id, Confidence};
+use crate::report::{module_path_of, PathFormatter};
+use crate::source::SourceProvider;
+
+/// Inputs of one analysis run, kept so findings can be explained later.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnalysisEvidence {
+    /// Root modules module reachability started from (sorted)
+    pub root_modules: Vec<String>,
+    /// Functions function reachability started from (sorted full paths)
+    pub entry_functions: Vec<String>,
+    /// Files read by the item-level detectors (sorted)
+    pub searched: Vec<PathBuf>,
+}
+
+/// Why a mention of the name did not keep the finding alive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum Exclusion {
+    /// The item's own definition
+    Declaration,
+    /// Inside a comment or doc comment
+    Comment,
+    /// Inside a string literal
+    StringLiteral,
+    /// Inside a macro invocation, whose tokens are not resolved
+    Macro,
+    /// In a module that is itself unreachable
+    DeadModule { module: String },
+    /// In the body of a function that is itself unreachable
+    DeadCaller { function: String },
+    /// Code that did not resolve to this item
+    Unresolved,
+}
+
+impl fmt::Display for Exclusion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Declaration => write!(f, "the definition itself"),
+            Self::Comment => write!(f, "inside a comment"),
+            Self::StringLiteral => write!(f, "inside a string literal"),
+            Self::Macro => write!(f, "inside a macro invocation (lowers confidence, not a reference)"),
+            Self::DeadModule { module } => {
+                write!(f, "in dead module `{}`, unreachable code keeps nothing alive", module)
+            }
+            Self::DeadCaller { function } => {
+                write!(f, "in dead function `{}`, unreachable callers keep nothing alive", function)
+            }
+            Self::Unresolved => write!(
+                f,
+                "code that did not resolve to this item (a local, a field or another item of the same name)"
+            ),
+        }
+    }
+}
+
+/// One place the finding's name appears.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mention {
+    /// File containing the mention
+    pub file: PathBuf,
+    /// Line number (1-indexed)
+    pub line: usize,
+    /// The source line, trimmed
+    pub text: String,
+    /// Why it was not counted as a reference
+    #[serde(flatten)]
+    pub exclusion: Exclusion,
+}
+
+/// The evidence behind one finding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindingEvidence {
+    /// Stable finding ID
+    pub id: String,
+    /// Item kind
+    pub kind: DeadItemKind,
+    /// Name or path of the dead item
+    pub name: String,
+    /// File containing the item
+    pub file: PathBuf,
+    /// Line number (1-indexed, 0 if unknown)
+    pub line: usize,
+    /// Owning module path (`crate::net::http`)
+    pub module_path: String,
+    /// Confidence of the finding (`None` for modules)
+    pub confidence: Option<Confidence>,
+    /// Entry points reachability started from; empty for kinds that are
+    /// alive as soon as anything names them
+    pub entry_points: Vec<String>,
+    /// Files searched for references
+    pub searched: Vec<PathBuf>,
+    /// Every remaining mention of the name, with why it did not count
+    pub mentions: Vec<Mention>,
+}
+
+impl FindingEvidence {
+    /// Rewrite every file path in `paths`' style, for reporting.
+    pub fn localize_paths(&mut self, paths: &PathFormatter) {
+        if paths.style().is_none() {
+            return;
+        }
+        let localize = |path: &mut PathBuf| *path = PathBuf::from(paths.format(&*path));
+        localize(&mut self.file);
+        self.searched.iter_mut().for_each(localize);
+        self.mentions
+            .iter_mut()
+            .for_each(|mention| localize(&mut mention.file));
+    }
+}
+
+/// Explain the finding with stable ID `id` from `result`, reading sources
+/// from `source`. Returns `None` when no finding has that ID.
+pub fn explain(
+    result: &AnalysisResult,
+    id: &str,
+    source: &dyn SourceProvider,
+) -> Option<FindingEvidence> {
+    let id = id.trim().to_ascii_lowercase();
+    let evidence = &result.evidence;
+    let dead_modules: HashMap<PathBuf, &str> = result
+        .dead_modules
+        .iter()
+        .filter_map(|name| {
+            result
+                .modules
+                .get(name)
+                .map(|info| (info.path.clone(), name.as_str()))
+        })
+        .collect();
+
+    let mut finding = if let Some(name) = result
+        .dead_modules
+        .iter()
+        .find(|name| module_id(name) == id)
+    {
+        let info = result.modules.get(name)?;
+        FindingEvidence {
+            id,
+            kind: DeadItemKind::Module,
+            name: name.clone(),
+            file: info.path.clone(),
+            line: 0,
+            module_path: module_path_of(&result.root, &info.path),
+            confidence: None,
+            entry_points: evidence.root_modules.clone(),
+            searched: Vec::new(),
+            mentions: Vec::new(),
+        }
+    } else {
+        let item = find_item(result, &id)?;
+        let entry_points = match item.kind {
+            DeadItemKind::Function | DeadItemKind::Method => evidence.entry_functions.clone(),
+            _ => Vec::new(),
+        };
+        FindingEvidence {
+            id,
+            kind: item.kind,
+            name: item.name.clone(),
+            file: item.file.clone(),
+            line: item.line,
+            module_path: item.module_path.clone(),
+            confidence: Some(item.confidence),
+            entry_points,
+            searched: Vec::new(),
+            mentions: Vec::new(),
+        }
+    };
+
+    finding.searched = if evidence.searched.is_empty() {
+        let mut files: Vec<PathBuf> = result
+            .modules
+            .values()
+            .map(|info| info.path.clone())
+            .collect();
+        files.sort();
+        files
+    } else {
+        evidence.searched.clone()
+    };
+
+    // Reachability-based kinds ignore mentions in unreachable code
+    let reachability = matches!(
+        finding.kind,
+        DeadItemKind::Module | DeadItemKind::Function | DeadItemKind::Method
+    );
+    let mut mentions = Vec::new();
+    if let Some(term) = search_term(&finding) {
+        for file in &finding.searched {
+            let Ok(content) = source.read(file) else {
+                continue;
+            };
+            let unreachable = if reachability {
+                Unreachable {
+                    module: dead_modules.get(file).copied(),
+                    functions: result
+                        .dead_functions
+                        .iter()
+                        .filter(|f| f.file == *file)
+                        .filter_map(|f| f.name.rsplit("::").next())
+                        .collect(),
+                }
+            } else {
+                Unreachable::default()
+            };
+            let own_file = *file == finding.file;
+            mentions.extend(find_mentions(
+                file,
+                &content,
+                term,
+                own_file,
+                finding.kind,
+                &unreachable,
+            ));
+        }
+    }
+    finding.mentions = mentions;
+    Some(finding)
+}
+
+/// Unreachable code in one file, whose mentions keep nothing alive.
+#[derive(Default)]
+struct Unreachable<'a> {
+    /// The file's module, if it is dead
+    module: Option<&'a str>,
+    /// Names of dead functions defined in the file
+    functions: HashSet<&'a str>,
+}
+
+fn find_item<'a>(result: &'a AnalysisResult, id: &str) -> Option<&'a DeadItem> {
+    result
+        .dead_items()
+        .chain(result.collapsed.iter().flat_map(|m| &m.items))
+        .find(|item| item.id == id)
+}
+
+/// The identifier to search for: the last path segment of the name.
+pub(crate) fn search_term(finding: &FindingEvidence) -> Option<&str> {
+    let name = match finding.kind {
+        // Patterns are not names
+        DeadItemKind::MatchArm => return None,
+        DeadItemKind::TypeParam
+        | DeadItemKind::Lifetime
+        | DeadItemKind::ConstParam
+        | DeadItemKind::Parameter => finding.name.split(" in ").next()?,
+        _ => finding.name.as_str(),
+    };
+    let term = name.rsplit("::").next()?.trim_start_matches('\'');
+    (!term.is_empty()).then_some(term)
+}
+
+/// Every mention of `term` in `content`, one per line, with definitions of
+/// any item of that name classified as [`Exclusion::Declaration`] (`deadmod
+/// find`).
+pub(crate) fn mentions_in(file: &Path, content: &str, term: &str) -> Vec<Mention> {
+    find_mentions(
+        file,
+        content,
+        term,
+        true,
+        DeadItemKind::Function,
+        &Unreachable::default(),
+    )
+}
+
+/// Mentions of `term` in `file`; `own_file` when it defines the finding,
+/// of kind `kind`.
+fn find_mentions(
+    file: &Path,
+    content: &str,
+    term: &str,
+    own_file: bool,
+    kind: DeadItemKind,
+    unreachable: &Unreachable,
+) -> Vec<Mention> {
+    let tokens = content.parse::<TokenStream>().ok().map(|stream| {
+        let mut map = TokenMap::default();
+        map.walk(stream, false, None);
+        map
+    });
+    // Generic parameters have no keyword: their first code mention declares them
+    let mut undeclared = own_file
+        && matches!(
+            kind,
+            DeadItemKind::TypeParam | DeadItemKind::Lifetime | DeadItemKind::ConstParam
+        );
+
+    let mut mentions = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let number = index + 1;
+        for (start, _) in line.match_indices(term) {
+            let before = line[..start].chars().next_back();
+            let after = line[start + term.len()..].chars().next();
+            if before.is_some_and(is_ident_char) || after.is_some_and(is_ident_char) {
+                continue;
+            }
+            let column = line[..start].chars().count();
+            let mut exclusion = classify(
+                tokens.as_ref(),
+                &line[..start],
+                number,
+                column,
+                own_file,
+                unreachable,
+            );
+            if undeclared
+                && !matches!(
+                    exclusion,
+                    Exclusion::Comment | Exclusion::StringLiteral | Exclusion::Macro
+                )
+            {
+                exclusion = Exclusion::Declaration;
+                undeclared = false;
+            }
+            mentions.push(Mention {
+                file: file.to_path_buf(),
+                line: number,
+                text: line.trim().to_string(),
+                exclusion,
+            });
+            // One mention per line is enough evidence
+            break;
+        }
+    }
+    mentions
+}
+
+fn classify(
+    tokens: Option<&TokenMap>,
+    prefix: &str,
+    line: usize,
+    column: usize,
+    own_file: bool,
+    unreachable: &Unreachable,
+) -> Exclusion {
+    let Some(tokens) = tokens else {
+        return Exclusion::Unresolved;
+    };
+    let at = (line, column);
+    match tokens.idents.get(&at) {
+        Some(IdentRole::Macro) => Exclusion::Macro,
+        Some(IdentRole::Definition) if own_file => Exclusion::Declaration,
+        Some(_) => {
+            if let Some(module) = unreachable.module {
+                return Exclusion::DeadModule {
+                    module: module.to_string(),
+                };
+            }
+            match tokens.enclosing_fn.get(&at) {
+                Some(function) if unreachable.functions.contains(function.as_str()) => {
+                    Exclusion::DeadCaller {
+                        function: function.clone(),
+                    }
+                }
+                _ => Exclusion::Unresolved,
+            }
+        }
+        None if prefix.contains("//") => Exclusion::Comment,
+        None if tokens.in_literal(line, column) => Exclusion::StringLiteral,
+        None => Exclusion::Comment,
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Keywords whose next identifier is the name of a new item.
+const DEFINING_KEYWORDS: &[&str] = &[
+    "fn", "const", "static", "mod", "struct", "enum", "trait", "type", "union",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IdentRole {
+    /// Names an item where it is defined (`fn name`, a variant in its enum)
+    Definition,
+    /// Inside a macro invocation
+    Macro,
+    /// Any other code
+    Code,
+}
+
+/// Identifier positions with their role (and the fn whose body they are
+/// in), and literal ranges, of one file.
+#[derive(Default)]
+struct TokenMap {
+    idents: HashMap<(usize, usize), IdentRole>,
+    enclosing_fn: HashMap<(usize, usize), String>,
+    literals: Vec<(LineColumn, LineColumn)>,
+}
+
+impl TokenMap {
+    fn walk(&mut self, stream: TokenStream, in_macro: bool, enclosing: Option<&str>) {
+        // Set after `name!`, consumed by the delimited group that follows
+        let mut bang = false;
+        let mut prev_ident: Option<String> = None;
+        // Set after `enum Name`, consumed by its body
+        let mut enum_body = false;
+        // Set after `fn name`, consumed by its body (or `;` without one)
+        let mut fn_body: Option<String> = None;
+        for tree in stream {
+            match tree {
+                TokenTree::Ident(ident) => {
+                    let start = ident.span().start();
+                    let text = ident.to_string();
+                    let role = if in_macro {
+                        IdentRole::Macro
+                    } else if prev_ident
+                        .as_deref()
+                        .is_some_and(|p| DEFINING_KEYWORDS.contains(&p))
+                    {
+                        enum_body = prev_ident.as_deref() == Some("enum");
+                        if prev_ident.as_deref() == Some("fn") {
+                            fn_body = Some(text.clone());
+                        }
+                        IdentRole::Definition
+                    } else {
+                        IdentRole::Code
+                    };
+                    self.idents.insert((start.line, start.column), role);
+                    if let Some(function) = enclosing {
+                        self.enclosing_fn
+                            .insert((start.line, start.column), function.to_string());
+                    }
+                    prev_ident = Some(text);
+                    continue;
+                }
+                TokenTree::Punct(punct) => {
+                    bang = punct.as_char() == '!'
+                        && punct.spacing() == Spacing::Alone
+                        && prev_ident.is_some();
+                    if punct.as_char() == ';' {
+                        fn_body = None;
+                    }
+                }
+                TokenTree::Literal(literal) => {
+                    let span = literal.span();
+                    self.literals.push((span.start(), span.end()));
+                    bang = false;
+                }
+                TokenTree::Group(group) => {
+                    let in_macro = in_macro || bang;
+                    if group.delimiter() != Delimiter::Brace {
+                        self.walk(group.stream(), in_macro, enclosing);
+                    } else if enum_body {
+                        self.walk_enum_body(group.stream(), in_macro, enclosing);
+                    } else if let Some(function) = fn_body.take() {
+                        self.walk(group.stream(), in_macro, Some(&function));
+                    } else {
+                        self.walk(group.stream(), in_macro, enclosing);
+                    }
+                    enum_body = false;
+                    bang = false;
+                }
+            }
+            prev_ident = None;
+        }
+    }
+
+    /// Walk an enum body: the first identifier of each variant defines it.
+    fn walk_enum_body(&mut self, stream: TokenStream, in_macro: bool, enclosing: Option<&str>) {
+        let mut expect_variant = true;
+        let mut rest = TokenStream::new();
+        for tree in stream {
+            match &tree {
+                TokenTree::Ident(ident) if expect_variant && !in_macro => {
+                    let start = ident.span().start();
+                    self.idents
+                        .insert((start.line, start.column), IdentRole::Definition);
+                    expect_variant = false;
+                    continue;
+                }
+                TokenTree::Punct(punct) if punct.as_char() == ',' => expect_variant = true,
+                _ => {}
+            }
+            rest.extend([tree]);
+        }
+        self.walk(rest, in_macro, enclosing);
+    }
+
+    fn in_literal(&self, line: usize, column: usize) -> bool {
+        let at = (line, column);
+        self.literals
+            .iter()
+            .any(|(start, end)| (start.line, start.column) <= at && at < (end.line, end.column))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Deadmod;
+    use crate::source::MemorySource;
+
+    fn sources() -> Vec<(PathBuf, String)> {
+        vec![
+            (
+                PathBuf::from("src/main.rs"),
+                "mod util; mod jobs;\n\nfn main() {\n    // helper() used to run here\n    println!(\"{}\", \"helper\");\n    log!(helper);\n}\n".to_string(),
+            ),
+            (
+                PathBuf::from("src/util.rs"),
+                "fn helper() {}\n\npub fn other() {\n    let helper = 1;\n}\n".to_string(),
+            ),
+            (PathBuf::from("src/jobs.rs"), "fn retry() { helper(); }\n".to_string()),
+            (PathBuf::from("src/old.rs"), "fn legacy() { helper(); }\n".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_explain_function_mentions() {
+        let source = MemorySource::new(sources());
+        let result = Deadmod::from_sources(sources()).all().analyze().unwrap();
+        let item = result
+            .dead_functions
+            .iter()
+            .find(|f| f.name == "helper")
+            .unwrap();
+
+        let evidence = explain(&result, &item.id, &source).unwrap();
+        assert_eq!(evidence.kind, DeadItemKind::Function);
+        assert!(evidence.entry_points.contains(&"main".to_string()));
+        assert_eq!(evidence.searched.len(), 4);
+
+        let reasons: Vec<(&str, usize, &Exclusion)> = evidence
+            .mentions
+            .iter()
+            .map(|m| (m.file.to_str().unwrap(), m.line, &m.exclusion))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                (
+                    "src/jobs.rs",
+                    1,
+                    &Exclusion::DeadCaller {
+                        function: "retry".to_string()
+                    }
+                ),
+                ("src/main.rs", 4, &Exclusion::Comment),
+                ("src/main.rs", 5, &Exclusion::StringLiteral),
+                ("src/main.rs", 6, &Exclusion::Macro),
+                (
+                    "src/old.rs",
+                    1,
+                    &Exclusion::DeadModule {
+                        module: "old".to_string()
+                    }
+                ),
+                ("src/util.rs", 1, &Exclusion::Declaration),
+                ("src/util.rs", 4, &Exclusion::Unresolved),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explain_module_and_unknown_id() {
+        let source = MemorySource::new(sources());
+        let result = Deadmod::from_sources(sources()).analyze().unwrap();
+
+        let evidence = explain(&result, &module_id("old"), &source).unwrap();
+        assert_eq!(evidence.kind, DeadItemKind::Module);
+        assert_eq!(evidence.entry_points, ["main"]);
+        assert!(evidence.confidence.is_none());
+
+        assert!(explain(&result, "000000000000", &source).is_none());
+    }
+}
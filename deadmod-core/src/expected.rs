@@ -0,0 +1,239 @@
+//! Expected findings, for regression gates (`deadmod self-check`).
+//!
+//! A project commits the findings it accepts today to
+//! `deadmod-expected.toml` at its workspace root (crate root outside a
+//! workspace):
+//!
+//! ```toml
+//! [[finding]]
+//! id = "2a37857936cc"
+//! crate = "deadmod-core"
+//! kind = "match arm"
+//! name = "_"
+//! file = "deadmod-core/src/progress.rs"
+//! ```
+//!
+//! A later analysis is compared against it by crate and finding ID: a
+//! finding missing from the file is new dead code, and an expected finding
+//! no longer reported was fixed (or its item renamed or moved to another
+//! module, which changes the ID). Lines are not recorded, so edits around
+//! an expected finding do not disturb the gate.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::builder::AnalysisResult;
+use crate::common::module_id;
+use crate::report::{PathFormatter, PathStyle};
+
+/// Expected findings file, relative to the workspace (or crate) root.
+pub const EXPECTED_FILE: &str = "deadmod-expected.toml";
+
+/// A finding the project accepts.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ExpectedFinding {
+    /// Finding ID
+    pub id: String,
+    /// Crate the finding is in (its directory name)
+    #[serde(rename = "crate")]
+    pub krate: String,
+    /// Finding kind (`function`, `module`, ...)
+    pub kind: String,
+    /// Item name
+    pub name: String,
+    /// File of the item, relative to the workspace root
+    pub file: String,
+}
+
+/// The expected findings of a workspace, sorted by crate, file and ID.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpectedFindings {
+    #[serde(default, rename = "finding")]
+    pub entries: Vec<ExpectedFinding>,
+}
+
+/// An analysis compared with the expected findings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpectedDiff {
+    /// Findings reported but not expected: new dead code
+    pub new: Vec<ExpectedFinding>,
+    /// Expected findings no longer reported
+    pub fixed: Vec<ExpectedFinding>,
+}
+
+impl ExpectedDiff {
+    /// Whether no new finding appeared.
+    pub fn passed(&self) -> bool {
+        self.new.is_empty()
+    }
+}
+
+impl ExpectedFindings {
+    /// Path of the expected findings file of the workspace at `root`.
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(EXPECTED_FILE)
+    }
+
+    /// Every finding of `results`, one analysis per crate with the crate's
+    /// name, with files relative to `root`.
+    pub fn from_results<'a>(
+        root: &Path,
+        results: impl IntoIterator<Item = (&'a str, &'a AnalysisResult)>,
+    ) -> Self {
+        let paths = PathFormatter::new(Some(PathStyle::Crate), root);
+        let mut entries = Vec::new();
+        for (krate, result) in results {
+            for name in &result.dead_modules {
+                let file = result
+                    .modules
+                    .get(name)
+                    .map(|info| paths.format(&info.path))
+                    .unwrap_or_default();
+                entries.push(ExpectedFinding {
+                    id: module_id(name),
+                    krate: krate.to_string(),
+                    kind: "module".to_string(),
+                    name: name.clone(),
+                    file,
+                });
+            }
+            entries.extend(result.dead_items().map(|item| ExpectedFinding {
+                id: item.id.clone(),
+                krate: krate.to_string(),
+                kind: item.kind.to_string(),
+                name: item.name.clone(),
+                file: paths.format(&item.file),
+            }));
+        }
+        entries.sort_by(|a, b| (&a.krate, &a.file, &a.id).cmp(&(&b.krate, &b.file, &b.id)));
+        entries.dedup_by(|a, b| a.krate == b.krate && a.id == b.id);
+        Self { entries }
+    }
+
+    /// Expected findings of the workspace at `root`; none if the file is
+    /// missing.
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = Self::path(root);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read expected findings from {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Invalid expected findings file {}", path.display()))
+    }
+
+    /// Write the expected findings of the workspace at `root`.
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let path = Self::path(root);
+        let content = toml::to_string(self).context("Failed to serialize expected findings")?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write expected findings to {}", path.display()))
+    }
+
+    /// Compare `actual` findings with these.
+    pub fn compare(&self, actual: &ExpectedFindings) -> ExpectedDiff {
+        let key = |f: &ExpectedFinding| (f.krate.clone(), f.id.clone());
+        let expected: HashSet<_> = self.entries.iter().map(key).collect();
+        let reported: HashSet<_> = actual.entries.iter().map(key).collect();
+        ExpectedDiff {
+            new: actual
+                .entries
+                .iter()
+                .filter(|f| !expected.contains(&key(f)))
+                .cloned()
+                .collect(),
+            fixed: self
+                .entries
+                .iter()
+                .filter(|f| !reported.contains(&key(f)))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Deadmod;
+
+    fn analyze(sources: &[(&str, &str)]) -> AnalysisResult {
+        Deadmod::from_sources(
+            sources
+                .iter()
+                .map(|(file, src)| (PathBuf::from(file), src.to_string())),
+        )
+        .all()
+        .analyze()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_expected_findings_round_trip_and_compare() {
+        let before = analyze(&[
+            (
+                "src/main.rs",
+                "mod net;\nfn main() { net::connect(); }\nfn unused() {}",
+            ),
+            ("src/net.rs", "pub fn connect() {}"),
+            ("src/legacy.rs", "pub fn old() {}"),
+        ]);
+        let expected = ExpectedFindings::from_results(Path::new(""), [("app", &before)]);
+        assert!(expected
+            .entries
+            .iter()
+            .any(|f| f.kind == "module" && f.name == "legacy"));
+        assert!(expected
+            .entries
+            .iter()
+            .any(|f| f.kind == "function" && f.name == "unused"));
+        assert!(expected.entries.iter().all(|f| f.krate == "app"));
+
+        let toml = toml::to_string(&expected).unwrap();
+        assert!(
+            toml.contains("[[finding]]") && toml.contains("crate = \"app\""),
+            "{}",
+            toml
+        );
+        assert_eq!(toml::from_str::<ExpectedFindings>(&toml).unwrap(), expected);
+
+        // `unused` is fixed and `stale` appears
+        let after = analyze(&[
+            (
+                "src/main.rs",
+                "mod net;\nfn main() { net::connect(); }\nfn stale() {}",
+            ),
+            ("src/net.rs", "pub fn connect() {}"),
+            ("src/legacy.rs", "pub fn old() {}"),
+        ]);
+        let diff = expected.compare(&ExpectedFindings::from_results(
+            Path::new(""),
+            [("app", &after)],
+        ));
+        assert_eq!(
+            diff.new.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(),
+            vec!["stale"]
+        );
+        assert_eq!(
+            diff.fixed
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["unused"]
+        );
+        assert!(!diff.passed());
+        assert!(expected.compare(&expected).passed());
+    }
+}
@@ -0,0 +1,191 @@
+//! Struct field extraction from Rust AST.
+//!
+//! Extracts the named fields of every struct. Skipped:
+//! - Tuple and unit structs, and `_`-prefixed fields
+//! - `#[repr(C)]` and `#[repr(transparent)]` structs, whose layout is the
+//!   point (FFI, transmutes) and whose fields may never be read by name
+//! - Structs deriving a trait that reads every field (`Serialize`,
+//!   `PartialEq`, `Hash`, ...). Like rustc, `Debug` and `Clone` do not count
+//!
+//! NASA-grade resilience: handles malformed AST gracefully.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use syn::{punctuated::Punctuated, visit::Visit, Attribute, Fields, File, ItemStruct, Meta, Token};
+
+use crate::common::{has_cfg_attr, keep_annotation, visibility_str, Keep};
+
+/// Derives whose generated code reads every field.
+const READING_DERIVES: &[&str] = &["Serialize", "PartialEq", "PartialOrd", "Ord", "Hash"];
+
+/// A named struct field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeclaredField {
+    /// Field name
+    pub name: String,
+    /// Struct declaring it
+    pub struct_name: String,
+    /// Source file path
+    pub file: String,
+    /// Line of the field name (1-indexed)
+    pub line: usize,
+    /// Column of the field name (0-indexed)
+    pub column: usize,
+    /// Visibility of the field
+    pub visibility: String,
+    /// Whether the struct or the field has #[cfg] or #[cfg_attr]
+    #[serde(default)]
+    pub has_cfg: bool,
+    /// `#[deadmod::keep]` details from the struct or the field
+    #[serde(default)]
+    pub keep: Option<Keep>,
+}
+
+impl DeclaredField {
+    /// `Struct::field`
+    pub fn full_name(&self) -> String {
+        format!("{}::{}", self.struct_name, self.name)
+    }
+}
+
+/// Result of field extraction from a file.
+#[derive(Debug, Clone, Default)]
+pub struct FieldExtractionResult {
+    /// Fields of every analyzed struct
+    pub fields: Vec<DeclaredField>,
+    /// Number of structs analyzed
+    pub struct_count: usize,
+}
+
+/// AST visitor that extracts struct fields.
+struct FieldExtractor {
+    file_path: String,
+    result: FieldExtractionResult,
+}
+
+impl<'ast> Visit<'ast> for FieldExtractor {
+    fn visit_item_struct(&mut self, item: &'ast ItemStruct) {
+        let Fields::Named(named) = &item.fields else {
+            return;
+        };
+        if has_fixed_layout(&item.attrs) || derives_reader(&item.attrs) {
+            return;
+        }
+        self.result.struct_count += 1;
+
+        let struct_name = item.ident.to_string();
+        let struct_keep = keep_annotation(&item.attrs);
+        for field in &named.named {
+            let Some(ident) = &field.ident else {
+                continue;
+            };
+            let name = ident.to_string().trim_start_matches("r#").to_string();
+            if name.starts_with('_') {
+                continue;
+            }
+            let start = ident.span().start();
+            self.result.fields.push(DeclaredField {
+                name,
+                struct_name: struct_name.clone(),
+                file: self.file_path.clone(),
+                line: start.line,
+                column: start.column,
+                visibility: visibility_str(&field.vis).to_string(),
+                has_cfg: has_cfg_attr(&item.attrs) || has_cfg_attr(&field.attrs),
+                keep: keep_annotation(&field.attrs).or_else(|| struct_keep.clone()),
+            });
+        }
+    }
+}
+
+/// Nested metas of every `#[name(...)]` attribute.
+fn attr_metas<'a>(attrs: &'a [Attribute], name: &'a str) -> impl Iterator<Item = Meta> + 'a {
+    attrs
+        .iter()
+        .filter(move |a| a.path().is_ident(name))
+        .filter_map(|a| {
+            a.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+}
+
+fn has_fixed_layout(attrs: &[Attribute]) -> bool {
+    attr_metas(attrs, "repr").any(|m| m.path().is_ident("C") || m.path().is_ident("transparent"))
+}
+
+fn derives_reader(attrs: &[Attribute]) -> bool {
+    attr_metas(attrs, "derive").any(|m| {
+        m.path()
+            .segments
+            .last()
+            .is_some_and(|s| READING_DERIVES.iter().any(|d| s.ident == d))
+    })
+}
+
+/// Extract the named fields of every analyzable struct in file content.
+///
+/// On parse error, returns an empty result (resilient behavior).
+pub fn extract_fields(path: &Path, content: &str) -> FieldExtractionResult {
+    let ast: File = match syn::parse_file(content) {
+        Ok(ast) => ast,
+        Err(e) => {
+            eprintln!("[WARN] AST parse failed for {}: {}", path.display(), e);
+            return FieldExtractionResult::default();
+        }
+    };
+
+    let mut extractor = FieldExtractor {
+        file_path: path.display().to_string(),
+        result: FieldExtractionResult::default(),
+    };
+    extractor.visit_file(&ast);
+    extractor.result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_fields() {
+        let content = r#"
+#[derive(Debug, Clone, Default)]
+struct Config {
+    retries: u32,
+    pub name: String,
+    _marker: (),
+}
+
+struct Point(i32, i32);
+
+#[repr(C)]
+struct Header { len: u32 }
+
+#[derive(serde::Serialize, PartialEq)]
+struct Wire { id: u64 }
+
+mod inner {
+    struct Nested {
+        #[cfg(unix)]
+        fd: i32,
+    }
+}
+"#;
+        let result = extract_fields(&PathBuf::from("test.rs"), content);
+        let names: Vec<String> = result.fields.iter().map(DeclaredField::full_name).collect();
+        assert_eq!(names, ["Config::retries", "Config::name", "Nested::fd"]);
+        assert_eq!(result.struct_count, 2);
+        assert_eq!((result.fields[0].line, result.fields[0].column), (4, 4));
+        assert_eq!(result.fields[1].visibility, "pub");
+        assert!(result.fields[2].has_cfg);
+    }
+
+    #[test]
+    fn test_malformed_resilient() {
+        let result = extract_fields(&PathBuf::from("broken.rs"), "struct S { a: u8");
+        assert!(result.fields.is_empty());
+    }
+}
@@ -0,0 +1,302 @@
+//! Field graph for detecting write-only and unused struct fields.
+//!
+//! A field is dead when no read of its name exists anywhere in the crate:
+//! - Write-only: assigned or initialized, but never read
+//! - Unused: neither read nor written
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::Serialize;
+
+use super::field_extractor::{DeclaredField, FieldExtractionResult};
+use super::field_usage::FieldUsageResult;
+use crate::common::{Confidence, ConfidenceSignals, KeptItem};
+
+/// Why a field is dead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeadFieldKind {
+    /// Written but never read
+    WriteOnly,
+    /// Never read nor written
+    Unused,
+}
+
+impl fmt::Display for DeadFieldKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WriteOnly => write!(f, "write-only"),
+            Self::Unused => write!(f, "unused"),
+        }
+    }
+}
+
+/// A struct field that is never read.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadField {
+    /// Field name
+    pub name: String,
+    /// Struct declaring it
+    pub struct_name: String,
+    /// Whether the field is written
+    pub kind: DeadFieldKind,
+    /// Source file
+    pub file: String,
+    /// Line of the field name (1-indexed)
+    pub line: usize,
+    /// Column of the field name (0-indexed)
+    pub column: usize,
+    /// Visibility of the field
+    pub visibility: String,
+    /// Confidence that the field is really dead
+    pub confidence: Confidence,
+}
+
+impl DeadField {
+    /// `Struct::field`
+    pub fn full_name(&self) -> String {
+        format!("{}::{}", self.struct_name, self.name)
+    }
+}
+
+/// Statistics about field analysis.
+#[derive(Debug, Clone, Default)]
+pub struct FieldStats {
+    pub total_structs: usize,
+    pub total_fields: usize,
+    pub write_only_count: usize,
+    pub unused_count: usize,
+}
+
+/// Result of field analysis.
+#[derive(Debug, Clone)]
+pub struct FieldAnalysisResult {
+    /// All dead fields found
+    pub dead: Vec<DeadField>,
+    /// Dead fields kept by `#[deadmod::keep]`
+    pub kept: Vec<KeptItem>,
+    /// Statistics
+    pub stats: FieldStats,
+}
+
+impl FieldAnalysisResult {
+    /// Drop dead fields below `min` confidence and update the counts.
+    pub fn retain_confidence(&mut self, min: Confidence) {
+        self.dead.retain(|f| f.confidence >= min);
+        self.recount();
+    }
+
+    /// Recompute the dead counts from `dead`.
+    pub fn recount(&mut self) {
+        self.stats.write_only_count = self
+            .dead
+            .iter()
+            .filter(|f| f.kind == DeadFieldKind::WriteOnly)
+            .count();
+        self.stats.unused_count = self.dead.len() - self.stats.write_only_count;
+    }
+}
+
+/// Graph for analyzing field accesses.
+#[derive(Default)]
+pub struct FieldGraph {
+    /// All declared fields
+    declared: Vec<DeclaredField>,
+    /// Number of structs analyzed
+    struct_count: usize,
+    reads: HashSet<String>,
+    writes: HashSet<String>,
+    updated_structs: HashSet<String>,
+    macro_mentions: HashSet<String>,
+}
+
+impl FieldGraph {
+    /// Create a new field graph from extraction and usage results.
+    pub fn new(extractions: &[FieldExtractionResult], usages: &[FieldUsageResult]) -> Self {
+        let mut graph = Self {
+            declared: extractions
+                .iter()
+                .flat_map(|e| e.fields.iter().cloned())
+                .collect(),
+            struct_count: extractions.iter().map(|e| e.struct_count).sum(),
+            ..Default::default()
+        };
+        for usage in usages {
+            graph.reads.extend(usage.reads.iter().cloned());
+            graph.writes.extend(usage.writes.iter().cloned());
+            graph
+                .updated_structs
+                .extend(usage.updated_structs.iter().cloned());
+            graph
+                .macro_mentions
+                .extend(usage.macro_mentions.iter().cloned());
+        }
+        graph
+    }
+
+    /// Whether any code reads the field.
+    fn is_read(&self, field: &DeclaredField) -> bool {
+        self.reads.contains(&field.name) || self.updated_structs.contains(&field.struct_name)
+    }
+
+    /// Find all dead fields, sorted by file and position.
+    pub fn find_dead(&self) -> Vec<DeadField> {
+        let mut dead: Vec<DeadField> = self
+            .declared
+            .iter()
+            .filter(|f| !self.is_read(f) && f.keep.is_none())
+            .map(|f| DeadField {
+                name: f.name.clone(),
+                struct_name: f.struct_name.clone(),
+                kind: if self.writes.contains(&f.name) {
+                    DeadFieldKind::WriteOnly
+                } else {
+                    DeadFieldKind::Unused
+                },
+                file: f.file.clone(),
+                line: f.line,
+                column: f.column,
+                visibility: f.visibility.clone(),
+                confidence: ConfidenceSignals {
+                    visibility: &f.visibility,
+                    has_cfg: f.has_cfg,
+                    in_macro: self.macro_mentions.contains(&f.name),
+                    ..Default::default()
+                }
+                .score(),
+            })
+            .collect();
+
+        dead.sort_by(|a, b| {
+            a.file
+                .cmp(&b.file)
+                .then_with(|| a.line.cmp(&b.line))
+                .then_with(|| a.column.cmp(&b.column))
+        });
+        dead
+    }
+
+    /// Perform complete analysis and return structured result.
+    pub fn analyze(&self) -> FieldAnalysisResult {
+        let dead = self.find_dead();
+
+        let kept = self
+            .declared
+            .iter()
+            .filter(|f| !self.is_read(f))
+            .filter_map(|f| {
+                f.keep
+                    .as_ref()
+                    .map(|keep| KeptItem::new(f.full_name(), &f.file, keep))
+            })
+            .collect();
+
+        let mut result = FieldAnalysisResult {
+            dead,
+            kept,
+            stats: FieldStats {
+                total_structs: self.struct_count,
+                total_fields: self.declared.len(),
+                ..Default::default()
+            },
+        };
+        result.recount();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::{extract_field_usages, extract_fields};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_field_graph_classification() {
+        let content = r#"
+#[derive(Default)]
+struct Session {
+    id: u64,
+    retries: u32,
+    pub cached: Vec<u8>,
+    never: bool,
+    #[deadmod::keep(reason = "read over FFI")]
+    handle: usize,
+    logged: String,
+}
+
+struct Patch { a: u8, b: u8 }
+
+impl Session {
+    fn new(id: u64) -> Self {
+        Session { id, retries: 0, ..Default::default() }
+    }
+
+    fn retry(&mut self) -> u64 {
+        self.retries += 1;
+        self.cached = Vec::new();
+        self.handle = 0;
+        self.logged = String::new();
+        println!("{}", logged);
+        self.id
+    }
+}
+
+fn patch(base: Patch) -> Patch {
+    Patch { a: 1, ..base }
+}
+"#;
+        let path = PathBuf::from("test.rs");
+        let extraction = extract_fields(&path, content);
+        let usage = extract_field_usages(&path, content);
+        let mut result = FieldGraph::new(&[extraction], &[usage]).analyze();
+
+        let dead: Vec<(String, DeadFieldKind, Confidence)> = result
+            .dead
+            .iter()
+            .map(|f| (f.full_name(), f.kind, f.confidence))
+            .collect();
+        assert_eq!(
+            dead,
+            [
+                (
+                    "Session::retries".to_string(),
+                    DeadFieldKind::WriteOnly,
+                    Confidence::High
+                ),
+                (
+                    "Session::cached".to_string(),
+                    DeadFieldKind::WriteOnly,
+                    Confidence::Low
+                ),
+                (
+                    "Session::never".to_string(),
+                    DeadFieldKind::Unused,
+                    Confidence::High
+                ),
+                (
+                    "Session::logged".to_string(),
+                    DeadFieldKind::WriteOnly,
+                    Confidence::Low
+                ),
+            ]
+        );
+        assert_eq!(result.kept.len(), 1);
+        assert_eq!(result.kept[0].name, "Session::handle");
+        assert_eq!(
+            (result.stats.total_structs, result.stats.total_fields),
+            (2, 8)
+        );
+        assert_eq!(
+            (result.stats.write_only_count, result.stats.unused_count),
+            (3, 1)
+        );
+
+        result.retain_confidence(Confidence::High);
+        assert_eq!(
+            (result.stats.write_only_count, result.stats.unused_count),
+            (1, 1)
+        );
+    }
+}
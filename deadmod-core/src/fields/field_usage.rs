@@ -0,0 +1,256 @@
+//! Struct field access classification from Rust AST.
+//!
+//! Classifies every field access as a read or a write, flow-insensitively
+//! and by field name (there is no type information):
+//! - Writes: assignment targets (`x.f = v`, `x.f += v`, `x.f[i] = v`) and
+//!   struct literal fields (`S { f: v }`, `S { f }`)
+//! - Reads: every other `x.f`, struct pattern fields (`let S { f, .. } = x`),
+//!   `.f` inside macro arguments, and every field of `S` in a struct update
+//!   (`S { a, ..base }` reads the rest of `base`, but `..Default::default()`
+//!   reads nothing)
+//!
+//! Reads are conservative: a method call on a field (`x.f.push(v)`) or a
+//! borrow (`&mut x.f`) counts as a read, and a read of `f` on any struct
+//! keeps every field named `f` alive. Name matching can therefore hide a
+//! write-only field, but never invent one.
+//!
+//! NASA-grade resilience: handles malformed AST gracefully.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use proc_macro2::{TokenStream, TokenTree};
+use syn::{visit::Visit, BinOp, Expr, ExprStruct, FieldPat, File, ItemImpl, Macro, Member, Type};
+
+/// Field accesses in a file.
+#[derive(Debug, Clone, Default)]
+pub struct FieldUsageResult {
+    /// Field names read somewhere
+    pub reads: HashSet<String>,
+    /// Field names written somewhere
+    pub writes: HashSet<String>,
+    /// Structs whose remaining fields are read by a struct update (`..base`)
+    pub updated_structs: HashSet<String>,
+    /// Identifiers inside macro invocations, not known to be reads
+    pub macro_mentions: HashSet<String>,
+}
+
+/// AST visitor that classifies field accesses.
+#[derive(Default)]
+struct FieldUsageExtractor {
+    result: FieldUsageResult,
+    /// Self type of the enclosing impl, for `Self { .. }`
+    impl_type: Option<String>,
+}
+
+impl FieldUsageExtractor {
+    fn record(set: &mut HashSet<String>, member: &Member) {
+        if let Member::Named(ident) = member {
+            set.insert(ident.to_string().trim_start_matches("r#").to_string());
+        }
+    }
+
+    /// Visit an assignment target: its outermost field is written, fields
+    /// it is reached through are read.
+    fn visit_place(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Field(f) => {
+                Self::record(&mut self.result.writes, &f.member);
+                self.visit_expr(&f.base);
+            }
+            Expr::Index(i) => {
+                self.visit_place(&i.expr);
+                self.visit_expr(&i.index);
+            }
+            Expr::Paren(p) => self.visit_place(&p.expr),
+            other => self.visit_expr(other),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for FieldUsageExtractor {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Assign(a) => {
+                self.visit_place(&a.left);
+                self.visit_expr(&a.right);
+            }
+            Expr::Binary(b) if is_compound_assign(&b.op) => {
+                self.visit_place(&b.left);
+                self.visit_expr(&b.right);
+            }
+            Expr::Field(f) => {
+                Self::record(&mut self.result.reads, &f.member);
+                self.visit_expr(&f.base);
+            }
+            _ => syn::visit::visit_expr(self, expr),
+        }
+    }
+
+    fn visit_expr_struct(&mut self, expr: &'ast ExprStruct) {
+        for field in &expr.fields {
+            Self::record(&mut self.result.writes, &field.member);
+        }
+        if expr
+            .rest
+            .as_deref()
+            .is_some_and(|rest| !is_default_call(rest))
+        {
+            if let Some(seg) = expr.path.segments.last() {
+                let name = match (&self.impl_type, seg.ident == "Self") {
+                    (Some(ty), true) => ty.clone(),
+                    _ => seg.ident.to_string(),
+                };
+                self.result.updated_structs.insert(name);
+            }
+        }
+        syn::visit::visit_expr_struct(self, expr);
+    }
+
+    fn visit_field_pat(&mut self, pat: &'ast FieldPat) {
+        Self::record(&mut self.result.reads, &pat.member);
+        syn::visit::visit_field_pat(self, pat);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        let outer = self.impl_type.replace(type_name(&item.self_ty));
+        syn::visit::visit_item_impl(self, item);
+        self.impl_type = outer;
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        scan_tokens(mac.tokens.clone(), &mut self.result);
+        syn::visit::visit_macro(self, mac);
+    }
+}
+
+fn is_compound_assign(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::AddAssign(_)
+            | BinOp::SubAssign(_)
+            | BinOp::MulAssign(_)
+            | BinOp::DivAssign(_)
+            | BinOp::RemAssign(_)
+            | BinOp::BitXorAssign(_)
+            | BinOp::BitAndAssign(_)
+            | BinOp::BitOrAssign(_)
+            | BinOp::ShlAssign(_)
+            | BinOp::ShrAssign(_)
+    )
+}
+
+/// `Default::default()`, `T::default()` or `Self::default()`.
+fn is_default_call(expr: &Expr) -> bool {
+    match expr {
+        Expr::Call(call) => matches!(
+            &*call.func,
+            Expr::Path(p) if call.args.is_empty() && p.path.segments.last().is_some_and(|s| s.ident == "default")
+        ),
+        _ => false,
+    }
+}
+
+/// Last path segment of an impl's self type.
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(p) => p
+            .path
+            .segments
+            .last()
+            .map_or_else(String::new, |s| s.ident.to_string()),
+        Type::Reference(r) => type_name(&r.elem),
+        _ => String::new(),
+    }
+}
+
+/// Macro arguments: `.f` is a read, any other identifier a mention.
+fn scan_tokens(tokens: TokenStream, result: &mut FieldUsageResult) {
+    let mut after_dot = false;
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string().trim_start_matches("r#").to_string();
+                if after_dot {
+                    result.reads.insert(name);
+                } else {
+                    result.macro_mentions.insert(name);
+                }
+                after_dot = false;
+            }
+            TokenTree::Punct(p) => after_dot = p.as_char() == '.',
+            TokenTree::Group(group) => {
+                scan_tokens(group.stream(), result);
+                after_dot = false;
+            }
+            TokenTree::Literal(_) => after_dot = false,
+        }
+    }
+}
+
+/// Classify the field accesses in file content.
+///
+/// On parse error, returns an empty result (resilient behavior).
+pub fn extract_field_usages(path: &Path, content: &str) -> FieldUsageResult {
+    let ast: File = match syn::parse_file(content) {
+        Ok(ast) => ast,
+        Err(e) => {
+            eprintln!("[WARN] AST parse failed for {}: {}", path.display(), e);
+            return FieldUsageResult::default();
+        }
+    };
+
+    let mut extractor = FieldUsageExtractor::default();
+    extractor.visit_file(&ast);
+    extractor.result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn sorted(set: &HashSet<String>) -> Vec<&str> {
+        let mut names: Vec<&str> = set.iter().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_classify_field_accesses() {
+        let content = r#"
+impl Counter {
+    fn new() -> Self {
+        Self { hits: 0, label, ..Default::default() }
+    }
+
+    fn reset(&self) -> Self {
+        Self { hits: 0, ..*self }
+    }
+
+    fn record(&mut self, n: u32) {
+        self.hits += n;
+        self.stats.total = n;
+        (self.buf)[0] = 1;
+        self.log.push(n);
+        let Point { x, .. } = self.origin;
+        println!("{}", self.shown);
+    }
+}
+"#;
+        let result = extract_field_usages(&PathBuf::from("test.rs"), content);
+        assert_eq!(sorted(&result.writes), ["buf", "hits", "label", "total"]);
+        assert_eq!(
+            sorted(&result.reads),
+            ["log", "origin", "shown", "stats", "x"]
+        );
+        assert_eq!(sorted(&result.updated_structs), ["Counter"]);
+        assert!(result.macro_mentions.contains("self"));
+    }
+
+    #[test]
+    fn test_malformed_resilient() {
+        let result = extract_field_usages(&PathBuf::from("broken.rs"), "fn f() { x.a = ");
+        assert!(result.reads.is_empty() && result.writes.is_empty());
+    }
+}
@@ -0,0 +1,49 @@
+//! Struct field analysis for dead code detection.
+//!
+//! This module provides functionality to detect dead state: struct fields
+//! that nothing reads. Field accesses are classified as reads or writes,
+//! so fields that are only ever assigned (setters, builders, counters no
+//! one looks at) are reported as write-only, and fields never touched at
+//! all as unused.
+//!
+//! # Architecture
+//!
+//! ```text
+//! ┌─────────────────────┐     ┌─────────────────────┐
+//! │ field_extractor.rs  │     │   field_usage.rs    │
+//! │  ─────────────────  │     │  ─────────────────  │
+//! │  Extract declared   │     │  Classify accesses  │
+//! │  struct fields      │     │  as reads / writes  │
+//! └──────────┬──────────┘     └──────────┬──────────┘
+//!            │                           │
+//!            └─────────────┬─────────────┘
+//!                          ▼
+//!              ┌─────────────────────┐
+//!              │   field_graph.rs    │
+//!              │  ─────────────────  │
+//!              │  Find unread fields │
+//!              └─────────────────────┘
+//! ```
+//!
+//! # Example
+//!
+//! ```ignore
+//! use deadmod_core::fields::{extract_field_usages, extract_fields, FieldGraph};
+//!
+//! let extraction = extract_fields(&path, &content);
+//! let usage = extract_field_usages(&path, &content);
+//! let result = FieldGraph::new(&[extraction], &[usage]).analyze();
+//!
+//! for dead in &result.dead {
+//!     println!("[{}] {}", dead.kind, dead.full_name());
+//! }
+//! ```
+
+pub mod field_extractor;
+pub mod field_graph;
+pub mod field_usage;
+
+// Re-exports for convenience
+pub use field_extractor::{extract_fields, DeclaredField, FieldExtractionResult};
+pub use field_graph::{DeadField, DeadFieldKind, FieldAnalysisResult, FieldGraph, FieldStats};
+pub use field_usage::{extract_field_usages, FieldUsageResult};
@@ -0,0 +1,287 @@
+//! Every finding located in one file, with the span of each item.
+//!
+//! [`analyze_file`] runs the enabled detectors once over the file's crate and
+//! keeps what lies in the file, so editors and `deadmod file` get module,
+//! function, constant, enum and macro findings together instead of calling
+//! each extractor themselves.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::builder::{file_matcher, AnalysisResult, DeadItem, Deadmod};
+use crate::common::KeptItem;
+use crate::grace::{declaration_line, RecentItem};
+use crate::limits::Skipped;
+use crate::report::PathFormatter;
+
+/// Where an item's name sits in its file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemSpan {
+    /// 1-based line
+    pub start_line: usize,
+    /// 0-based column, in characters
+    pub start_column: usize,
+    /// 1-based line
+    pub end_line: usize,
+    /// 0-based column, in characters
+    pub end_column: usize,
+}
+
+impl ItemSpan {
+    /// Span of `item`'s name on its definition line in `source`, or of the
+    /// whole line if the name is not found there. Extraction records that
+    /// line; items without one are looked up by declaration and, as a last
+    /// resort, by first mention, then put on line 1.
+    pub fn locate(source: &str, item: &DeadItem) -> Self {
+        let name = item
+            .name
+            .rsplit("::")
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('!');
+        let line = match item.line {
+            0 => declaration_line(source, item)
+                .or_else(|| {
+                    source
+                        .lines()
+                        .position(|text| find_word(text, name).is_some())
+                        .map(|i| i + 1)
+                })
+                .unwrap_or(1),
+            line => line,
+        };
+        let text = source.lines().nth(line - 1).unwrap_or_default();
+        let chars = |s: &str| s.chars().count();
+        let (start_column, end_column) = match find_word(text, name) {
+            Some(at) => (chars(&text[..at]), chars(&text[..at + name.len()])),
+            None => (0, chars(text)),
+        };
+        Self {
+            start_line: line,
+            start_column,
+            end_line: line,
+            end_column,
+        }
+    }
+}
+
+/// Byte offset of `name` as a whole word in `text`.
+fn find_word(text: &str, name: &str) -> Option<usize> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(name)
+        .map(|(at, _)| at)
+        .find(|&at| {
+            !text[..at].ends_with(is_ident) && !text[at + name.len()..].starts_with(is_ident)
+        })
+        .filter(|_| !name.is_empty())
+}
+
+/// A dead item and where it is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileFinding {
+    #[serde(flatten)]
+    pub item: DeadItem,
+    pub span: ItemSpan,
+}
+
+/// Findings of one file, across every enabled detector.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileFindings {
+    /// The file
+    pub file: PathBuf,
+    /// The file's module, if unreachable from every crate root
+    pub dead_module: Option<String>,
+    /// Dead items, in source order
+    pub items: Vec<FileFinding>,
+    /// Dead items folded under [`FileFindings::dead_module`] (when
+    /// collapsing), in source order
+    pub collapsed: Vec<FileFinding>,
+    /// Items kept by `#[deadmod::keep]`
+    pub kept: Vec<KeptItem>,
+    /// Findings within the grace period
+    pub recent: Vec<RecentItem>,
+    /// Files and analysis steps of the crate left out by limits
+    pub skipped: Vec<Skipped>,
+}
+
+impl FileFindings {
+    /// The findings of `result` located in `file`, whose content is
+    /// `source`.
+    pub fn from_result(result: &AnalysisResult, file: &Path, source: &str) -> Self {
+        let in_file = file_matcher(file);
+        let located = |items: &mut dyn Iterator<Item = &DeadItem>| {
+            let mut found: Vec<FileFinding> = items
+                .filter(|item| in_file(&item.file))
+                .map(|item| FileFinding {
+                    item: item.clone(),
+                    span: ItemSpan::locate(source, item),
+                })
+                .collect();
+            found.sort_by_key(|f| (f.span.start_line, f.span.start_column));
+            found
+        };
+        Self {
+            file: file.to_path_buf(),
+            dead_module: result
+                .dead_modules
+                .iter()
+                .find(|name| {
+                    result
+                        .modules
+                        .get(*name)
+                        .is_some_and(|info| in_file(&info.path))
+                })
+                .cloned(),
+            items: located(&mut result.dead_items()),
+            collapsed: located(&mut result.collapsed.iter().flat_map(|m| &m.items)),
+            kept: result
+                .kept
+                .iter()
+                .filter(|k| in_file(Path::new(&k.file)))
+                .cloned()
+                .collect(),
+            recent: result
+                .recent
+                .iter()
+                .filter(|r| in_file(&r.item.file))
+                .cloned()
+                .collect(),
+            skipped: result.skipped.clone(),
+        }
+    }
+
+    /// Whether the file holds dead code (collapsed items count through
+    /// their module).
+    pub fn has_dead_code(&self) -> bool {
+        self.dead_module.is_some() || !self.items.is_empty()
+    }
+
+    /// The dead module plus the dead items outside it.
+    pub fn dead_count(&self) -> usize {
+        usize::from(self.dead_module.is_some()) + self.items.len()
+    }
+
+    /// Write file paths in `paths`' style.
+    pub fn localize_paths(&mut self, paths: &PathFormatter) {
+        if paths.style().is_none() {
+            return;
+        }
+        let localize = |path: &mut PathBuf| *path = PathBuf::from(paths.format(&*path));
+        localize(&mut self.file);
+        for finding in self.items.iter_mut().chain(&mut self.collapsed) {
+            localize(&mut finding.item.file);
+        }
+        for item in &mut self.kept {
+            item.file = paths.format(&item.file);
+        }
+        self.recent
+            .iter_mut()
+            .for_each(|recent| localize(&mut recent.item.file));
+    }
+}
+
+/// Analyze the crate of `context` and return the findings in `path`.
+///
+/// `context` is configured like any analysis (detectors, policies,
+/// collapsing); its source provider, if any, also supplies `path`'s content.
+pub fn analyze_file(path: &Path, context: &Deadmod) -> Result<FileFindings> {
+    let source = context.read_file(path)?;
+    let result = context.analyze()?;
+    Ok(FileFindings::from_result(&result, path, &source))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::DeadItemKind;
+
+    #[test]
+    fn test_analyze_file() {
+        let context = Deadmod::from_sources([
+            (PathBuf::from("src/lib.rs"), "pub mod net;\npub fn api() { net::used(); }".to_string()),
+            (
+                PathBuf::from("src/net.rs"),
+                "pub fn used() {}\nconst LIMIT: u8 = 1;\n/* é */ fn unused() {}\nmacro_rules! gone { () => {} }".to_string(),
+            ),
+            (PathBuf::from("src/dead.rs"), "fn orphan() {}".to_string()),
+        ])
+        .all();
+
+        let findings = analyze_file(Path::new("src/net.rs"), &context).unwrap();
+        assert_eq!(findings.dead_module, None);
+        let found: Vec<_> = findings
+            .items
+            .iter()
+            .map(|f| {
+                (
+                    f.item.kind,
+                    f.item.name.as_str(),
+                    f.span.start_line,
+                    f.span.start_column,
+                    f.span.end_column,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (DeadItemKind::Constant, "LIMIT", 2, 6, 11),
+                (DeadItemKind::Function, "unused", 3, 11, 17),
+                (DeadItemKind::Macro, "gone", 4, 13, 17),
+            ]
+        );
+        assert_eq!(findings.dead_count(), 3);
+
+        let dead = analyze_file(Path::new("src/dead.rs"), &context.clone().collapse(true)).unwrap();
+        assert_eq!(dead.dead_module.as_deref(), Some("dead"));
+        assert!(dead.items.is_empty());
+        assert_eq!(dead.collapsed.len(), 1);
+        assert_eq!(dead.dead_count(), 1);
+
+        assert!(analyze_file(Path::new("src/missing.rs"), &context).is_err());
+    }
+
+    #[test]
+    fn test_line_is_definition_not_comment() {
+        let context = Deadmod::from_sources([(
+            PathBuf::from("src/lib.rs"),
+            "pub fn api() {}\n// stale() is kept for now\n/// Calls stale() once.\nfn stale() {}\n"
+                .to_string(),
+        )])
+        .all();
+
+        let findings = analyze_file(Path::new("src/lib.rs"), &context).unwrap();
+        let found: Vec<_> = findings
+            .items
+            .iter()
+            .map(|f| (f.item.name.as_str(), f.item.line, f.span.start_line))
+            .collect();
+        assert_eq!(found, vec![("stale", 4, 4)]);
+    }
+
+    #[test]
+    fn test_locate_without_line() {
+        let source = "fn renew() {}\n";
+        let item = DeadItem {
+            id: "x".to_string(),
+            name: "gone".to_string(),
+            file: PathBuf::from("src/lib.rs"),
+            line: 1,
+            kind: DeadItemKind::Function,
+            confidence: crate::Confidence::High,
+            module_path: "crate".to_string(),
+        };
+        // Name not on the line: the whole line
+        assert_eq!(
+            ItemSpan::locate(source, &item),
+            ItemSpan {
+                start_line: 1,
+                start_column: 0,
+                end_line: 1,
+                end_column: 13
+            }
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Terminal previews of dry-run fixes.
+//!
+//! `--fix-dry-run` shows what a fix would do: the leading lines of each file
+//! it would delete, and a unified diff of each file it would rewrite. Output
+//! is colored (removed lines red, added lines green) when stdout is a
+//! terminal and `NO_COLOR` is unset.
+
+use std::io::IsTerminal;
+
+/// Leading lines shown of a file that would be deleted.
+pub const PREVIEW_LINES: usize = 10;
+
+/// Unchanged lines shown around each change.
+const CONTEXT: usize = 2;
+
+/// Line pairs above which the diff falls back to replacing the whole
+/// changed region instead of aligning it.
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const CYAN: &str = "\x1b[36m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// One line of a line diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    /// Present before and after
+    Same(&'a str),
+    /// Only present before
+    Removed(&'a str),
+    /// Only present after
+    Added(&'a str),
+}
+
+/// Whether previews should be colored: stdout is a terminal and `NO_COLOR`
+/// is unset.
+pub fn color_enabled() -> bool {
+    std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal()
+}
+
+fn paint(text: &str, style: &str, color: bool) -> String {
+    if color {
+        format!("{}{}{}", style, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Line diff of `old` and `new` (longest common subsequence).
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (a, b) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    let mut out: Vec<DiffLine> = old[..prefix].iter().map(|&l| DiffLine::Same(l)).collect();
+    if a.len().saturating_mul(b.len()) > MAX_DIFF_CELLS {
+        out.extend(a.iter().map(|&l| DiffLine::Removed(l)));
+        out.extend(b.iter().map(|&l| DiffLine::Added(l)));
+    } else {
+        // lcs[i][j]: common subsequence length of a[i..] and b[j..]
+        let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i][j] = if a[i] == b[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                out.push(DiffLine::Same(a[i]));
+                i += 1;
+                j += 1;
+            } else if j == b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+                out.push(DiffLine::Removed(a[i]));
+                i += 1;
+            } else {
+                out.push(DiffLine::Added(b[j]));
+                j += 1;
+            }
+        }
+    }
+    out.extend(old[old.len() - suffix..].iter().map(|&l| DiffLine::Same(l)));
+    out
+}
+
+/// Unified diff of a rewrite of `label`, indented for dry-run output.
+/// Empty when nothing changes.
+pub fn render_diff(label: &str, old: &str, new: &str, color: bool) -> String {
+    let lines = diff_lines(old, new);
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(i, _)| i)
+        .collect();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    // 1-based (old, new) line numbers at each diff line
+    let mut positions = Vec::with_capacity(lines.len());
+    let (mut old_line, mut new_line) = (1, 1);
+    for line in &lines {
+        positions.push((old_line, new_line));
+        match line {
+            DiffLine::Same(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            DiffLine::Removed(_) => old_line += 1,
+            DiffLine::Added(_) => new_line += 1,
+        }
+    }
+
+    let mut out = paint(
+        &format!("    --- {}\n    +++ {}\n", label, label),
+        BOLD,
+        color,
+    );
+    let mut next = 0;
+    while next < changed.len() {
+        // Changes closer than two contexts apart share a hunk
+        let start = changed[next].saturating_sub(CONTEXT);
+        let mut last = changed[next];
+        while next + 1 < changed.len() && changed[next + 1] <= last + 2 * CONTEXT + 1 {
+            next += 1;
+            last = changed[next];
+        }
+        let end = (last + CONTEXT + 1).min(lines.len());
+        let hunk = &lines[start..end];
+
+        let old_len = hunk
+            .iter()
+            .filter(|l| !matches!(l, DiffLine::Added(_)))
+            .count();
+        let new_len = hunk
+            .iter()
+            .filter(|l| !matches!(l, DiffLine::Removed(_)))
+            .count();
+        let (old_start, new_start) = positions[start];
+        // An empty side starts at the line before, as in `diff -u`
+        let old_start = if old_len == 0 {
+            old_start - 1
+        } else {
+            old_start
+        };
+        let new_start = if new_len == 0 {
+            new_start - 1
+        } else {
+            new_start
+        };
+        let header = format!(
+            "@@ -{},{} +{},{} @@",
+            old_start, old_len, new_start, new_len
+        );
+        out.push_str(&format!("    {}\n", paint(&header, CYAN, color)));
+
+        for line in hunk {
+            let text = match line {
+                DiffLine::Same(l) => format!(" {}", l),
+                DiffLine::Removed(l) => paint(&format!("-{}", l), RED, color),
+                DiffLine::Added(l) => paint(&format!("+{}", l), GREEN, color),
+            };
+            out.push_str(&format!("    {}\n", text));
+        }
+        next += 1;
+    }
+    out
+}
+
+/// The first `max_lines` lines of a file that would be deleted, indented for
+/// dry-run output.
+pub fn render_removed_file(content: &str, max_lines: usize, color: bool) -> String {
+    let mut out = String::new();
+    for line in content.lines().take(max_lines) {
+        out.push_str(&format!(
+            "    {}\n",
+            paint(&format!("-{}", line), RED, color)
+        ));
+    }
+    let rest = content.lines().count().saturating_sub(max_lines);
+    if rest > 0 {
+        out.push_str(&format!("    ... {} more line(s)\n", rest));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines_aligns_changes() {
+        let diff = diff_lines(
+            "mod a;\nmod b;\nfn main() {}\n",
+            "mod a;\nfn main() {}\nfn x() {}\n",
+        );
+        assert_eq!(
+            diff,
+            [
+                DiffLine::Same("mod a;"),
+                DiffLine::Removed("mod b;"),
+                DiffLine::Same("fn main() {}"),
+                DiffLine::Added("fn x() {}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_diff_hunks() {
+        let old = "mod used;\nmod dead;\n\nfn main() {}\n";
+        let new = "mod used;\n\nfn main() {}\n";
+        assert_eq!(
+            render_diff("src/main.rs", old, new, false),
+            "    --- src/main.rs\n    +++ src/main.rs\n    @@ -1,4 +1,3 @@\n     mod used;\n    -mod dead;\n     \n     fn main() {}\n"
+        );
+        assert!(render_diff("src/main.rs", old, old, false).is_empty());
+
+        let colored = render_diff("src/main.rs", old, new, true);
+        assert!(colored.contains("\x1b[31m-mod dead;\x1b[0m"));
+    }
+
+    #[test]
+    fn test_render_diff_splits_distant_changes() {
+        let old: String = (0..20).map(|i| format!("line {}\n", i)).collect();
+        let new = old
+            .replace("line 2\n", "")
+            .replace("line 17\n", "line 17 changed\n");
+        let diff = render_diff("f.rs", &old, &new, false);
+        assert_eq!(diff.matches("@@ -").count(), 2);
+        assert!(diff.contains("@@ -1,5 +1,4 @@"));
+        assert!(diff.contains("@@ -16,5 +15,5 @@"));
+    }
+
+    #[test]
+    fn test_render_removed_file_truncates() {
+        let preview = render_removed_file("a\nb\nc\n", 2, false);
+        assert_eq!(preview, "    -a\n    -b\n    ... 1 more line(s)\n");
+    }
+}
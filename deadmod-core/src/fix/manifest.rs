@@ -0,0 +1,157 @@
+//! Recovery manifest written before any fix is applied.
+//!
+//! The manifest records the original content of every file the fixer is
+//! about to delete or rewrite, so a fix can always be reverted. Each fix run
+//! is one transaction stored at `.deadmod/undo/<id>/manifest.json`; see
+//! [`super::undo`] for listing and restoring transactions.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::undo::{allocate_transaction_dir, MANIFEST_FILE};
+
+/// What the fixer did to a recorded file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryAction {
+    /// File was deleted
+    Removed,
+    /// File was rewritten (e.g. `mod xyz;` removed)
+    Modified,
+}
+
+/// Original state of a single file touched by a fix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryEntry {
+    /// Canonical path of the file
+    pub path: PathBuf,
+    /// What the fixer did to it
+    pub action: RecoveryAction,
+    /// SHA-256 of the original content
+    pub sha256: String,
+    /// Original file content
+    pub content: String,
+}
+
+/// Everything needed to revert one fix run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryManifest {
+    /// Transaction ID (directory name under `.deadmod/undo`), assigned on save
+    #[serde(default)]
+    pub id: String,
+    /// Deadmod version that applied the fix
+    pub deadmod_version: String,
+    /// Unix timestamp (seconds) when the manifest was created
+    pub created_at: u64,
+    /// Canonical crate root the fix was applied to
+    pub crate_root: PathBuf,
+    /// Original state of every touched file
+    pub entries: Vec<RecoveryEntry>,
+}
+
+impl RecoveryManifest {
+    /// Create an empty manifest for the given crate root.
+    pub fn new(crate_root: &Path) -> Self {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Self {
+            id: String::new(),
+            deadmod_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at,
+            crate_root: crate_root.to_path_buf(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Snapshot a file's current content before it is touched.
+    ///
+    /// Recording the same path twice keeps the first snapshot, which is the
+    /// pre-fix state.
+    pub fn record(&mut self, path: &Path, action: RecoveryAction) -> Result<()> {
+        if self.entries.iter().any(|e| e.path == path) {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to snapshot {} for recovery", path.display()))?;
+        let sha256 = format!("{:x}", Sha256::digest(content.as_bytes()));
+
+        self.entries.push(RecoveryEntry {
+            path: path.to_path_buf(),
+            action,
+            sha256,
+            content,
+        });
+        Ok(())
+    }
+
+    /// Save the manifest as a new transaction under `.deadmod/undo/<id>`.
+    ///
+    /// Assigns [`RecoveryManifest::id`] and returns the manifest path. Uses the
+    /// same temp file + rename pattern as the parse cache so an interrupted
+    /// write never leaves a truncated manifest behind.
+    pub fn save(&mut self, crate_root: &Path) -> Result<PathBuf> {
+        let (id, dir) = allocate_transaction_dir(crate_root)?;
+        self.id = id;
+
+        let path = dir.join(MANIFEST_FILE);
+        let json =
+            serde_json::to_string_pretty(self).context("Failed to serialize recovery manifest")?;
+        let temp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+
+        fs::write(&temp_path, &json)
+            .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+        fs::rename(&temp_path, &path).with_context(|| {
+            let _ = fs::remove_file(&temp_path);
+            format!("Failed to write recovery manifest: {}", path.display())
+        })?;
+
+        Ok(path)
+    }
+
+    /// Load a manifest from its file path.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("Corrupted recovery manifest: {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_record_save_load() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_manifest_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("dead.rs");
+        fs::write(&file, "pub fn unused() {}\n").unwrap();
+
+        let mut manifest = RecoveryManifest::new(&dir);
+        manifest.record(&file, RecoveryAction::Removed).unwrap();
+        // Duplicate records keep the first snapshot
+        fs::write(&file, "changed").unwrap();
+        manifest.record(&file, RecoveryAction::Modified).unwrap();
+        let path = manifest.save(&dir).unwrap();
+        assert!(!manifest.id.is_empty());
+
+        let loaded = RecoveryManifest::load(&path).unwrap();
+        assert_eq!(loaded.id, manifest.id);
+        assert_eq!(loaded.entries.len(), 1);
+        assert_eq!(loaded.entries[0].action, RecoveryAction::Removed);
+        assert_eq!(loaded.entries[0].content, "pub fn unused() {}\n");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
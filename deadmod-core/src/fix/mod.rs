@@ -14,7 +14,11 @@
 //! # Safety Guarantees
 //!
 //! - Never follows symlinks (prevents accidental deletion outside project)
-//! - Validates all paths are within the project root
+//! - Validates all paths are within the project root ([`sandbox`])
+//! - Requires explicit confirmation ([`FixOptions::force`]) for large removals
+//! - Writes a recovery manifest before touching anything ([`manifest`])
+//! - Every fix can be reverted from the undo log ([`undo`])
+//! - Optional `cargo check` verification with bisecting rollback ([`verify`])
 //! - Dry-run mode for previewing changes
 //! - Atomic operations where possible
 //!
@@ -45,37 +49,138 @@
 //! - O(n) file operations where n = dead modules
 //! - Parallel-safe (stateless operations)
 
+pub mod diff;
+pub mod manifest;
+pub mod params;
+pub mod plan;
+pub mod sandbox;
+pub mod undo;
+pub mod variants;
+pub mod verify;
+
+pub use diff::{diff_lines, render_diff, render_removed_file, DiffLine, PREVIEW_LINES};
+pub use manifest::{RecoveryAction, RecoveryEntry, RecoveryManifest};
+pub use params::fix_dead_params;
+pub use plan::{format_plan_plain, plan, PlanStep, PlannedRemoval, RemovalPlan};
+pub use sandbox::Sandbox;
+pub use undo::{list_transactions, undo_fix, UndoResult, UNDO_DIR};
+pub use variants::fix_dead_variants;
+pub use verify::{fix_and_verify, fix_and_verify_with, VerifyOptions, VerifyResult};
+
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 use anyhow::{Context, Result};
 use regex::Regex;
 
+use crate::error::DeadmodError;
 use crate::parse::ModuleInfo;
 use serde::{Deserialize, Serialize};
 
+/// Number of file removals allowed without explicit confirmation.
+pub const DEFAULT_CONFIRM_THRESHOLD: usize = 10;
+
 /// Result of a fix operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FixResult {
     pub files_removed: Vec<String>,
     pub declarations_removed: Vec<String>,
     pub dirs_removed: Vec<String>,
+    /// Files rewritten in place (e.g. enum variants and match arms removed)
+    #[serde(default)]
+    pub files_modified: Vec<String>,
     pub errors: Vec<String>,
+    /// Targets rejected by the sandbox (symlinks, outside crate root)
+    #[serde(default)]
+    pub refused: Vec<String>,
+    /// Path of the recovery manifest written before applying changes
+    #[serde(default)]
+    pub manifest: Option<String>,
+    /// Undo transaction ID (`deadmod fix --undo=<id>`)
+    #[serde(default)]
+    pub transaction: Option<String>,
 }
 
 impl FixResult {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             files_removed: Vec::new(),
             declarations_removed: Vec::new(),
             dirs_removed: Vec::new(),
+            files_modified: Vec::new(),
             errors: Vec::new(),
+            refused: Vec::new(),
+            manifest: None,
+            transaction: None,
+        }
+    }
+}
+
+/// Options controlling how fixes are applied.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let options = FixOptions::new().dry_run(false).force(true);
+/// fix_dead_modules_with(root, &dead, &mods, &options)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixOptions {
+    /// Only report what would change
+    dry_run: bool,
+    /// Skip the confirmation threshold
+    force: bool,
+    /// Maximum file removals allowed without `force`
+    confirm_threshold: usize,
+    /// Color dry-run previews
+    color: bool,
+}
+
+impl Default for FixOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            force: false,
+            confirm_threshold: DEFAULT_CONFIRM_THRESHOLD,
+            color: diff::color_enabled(),
         }
     }
 }
 
+impl FixOptions {
+    /// Create options with safe defaults (no force, default threshold).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable dry-run mode (no file modifications).
+    pub fn dry_run(mut self, enabled: bool) -> Self {
+        self.dry_run = enabled;
+        self
+    }
+
+    /// Confirm removals above the threshold (CLI: `--yes`).
+    pub fn force(mut self, enabled: bool) -> Self {
+        self.force = enabled;
+        self
+    }
+
+    /// Set how many files may be removed without `force`.
+    pub fn confirm_threshold(mut self, max_files: usize) -> Self {
+        self.confirm_threshold = max_files;
+        self
+    }
+
+    /// Color dry-run previews (default: when stdout is a terminal and
+    /// `NO_COLOR` is unset).
+    pub fn color(mut self, enabled: bool) -> Self {
+        self.color = enabled;
+        self
+    }
+}
+
 /// Pre-compiled regex patterns for mod declaration removal.
 /// Uses OnceLock for thread-safe lazy initialization.
 struct ModPatterns {
@@ -94,9 +199,21 @@ impl ModPatterns {
             simple_mod: Regex::new(&format!(r"(?m)^\s*mod\s+{}\s*;.*$", escaped)).ok()?,
             pub_mod: Regex::new(&format!(r"(?m)^\s*pub\s+mod\s+{}\s*;.*$", escaped)).ok()?,
             // Security: Use non-greedy matching to prevent ReDoS attacks
-            pub_vis_mod: Regex::new(&format!(r"(?m)^\s*pub\s*\([^)]*?\)\s*mod\s+{}\s*;.*$", escaped)).ok()?,
-            attr_mod: Regex::new(&format!(r"(?m)^\s*#\[[^\]]*?\]\s*\n\s*mod\s+{}\s*;.*$", escaped)).ok()?,
-            attr_pub_mod: Regex::new(&format!(r"(?m)^\s*#\[[^\]]*?\]\s*\n\s*pub\s+mod\s+{}\s*;.*$", escaped)).ok()?,
+            pub_vis_mod: Regex::new(&format!(
+                r"(?m)^\s*pub\s*\([^)]*?\)\s*mod\s+{}\s*;.*$",
+                escaped
+            ))
+            .ok()?,
+            attr_mod: Regex::new(&format!(
+                r"(?m)^\s*#\[[^\]]*?\]\s*\n\s*mod\s+{}\s*;.*$",
+                escaped
+            ))
+            .ok()?,
+            attr_pub_mod: Regex::new(&format!(
+                r"(?m)^\s*#\[[^\]]*?\]\s*\n\s*pub\s+mod\s+{}\s*;.*$",
+                escaped
+            ))
+            .ok()?,
         })
     }
 
@@ -105,14 +222,24 @@ impl ModPatterns {
         let mut result = content.to_string();
         let mut found = false;
 
-        for pattern in [&self.simple_mod, &self.pub_mod, &self.pub_vis_mod, &self.attr_mod, &self.attr_pub_mod] {
+        for pattern in [
+            &self.simple_mod,
+            &self.pub_mod,
+            &self.pub_vis_mod,
+            &self.attr_mod,
+            &self.attr_pub_mod,
+        ] {
             if pattern.is_match(&result) {
                 found = true;
                 result = pattern.replace_all(&result, "").to_string();
             }
         }
 
-        if found { Some(result) } else { None }
+        if found {
+            Some(result)
+        } else {
+            None
+        }
     }
 }
 
@@ -120,9 +247,7 @@ impl ModPatterns {
 fn blank_line_regex() -> &'static Regex {
     static REGEX: OnceLock<Regex> = OnceLock::new();
     // SAFETY: This regex pattern is hardcoded and validated at compile-test time.
-    REGEX.get_or_init(|| {
-        Regex::new(r"\n\s*\n\s*\n").expect("Hardcoded regex pattern is valid")
-    })
+    REGEX.get_or_init(|| Regex::new(r"\n\s*\n\s*\n").expect("Hardcoded regex pattern is valid"))
 }
 
 /// Safely remove a file.
@@ -159,34 +284,20 @@ pub fn remove_file(path: &Path, dry_run: bool) -> Result<bool> {
         return Ok(true);
     }
 
-    fs::remove_file(path)
-        .with_context(|| format!("Failed to remove file: {}", path.display()))?;
+    fs::remove_file(path).with_context(|| format!("Failed to remove file: {}", path.display()))?;
 
     println!("[FIX] Removed: {}", path.display());
     Ok(true)
 }
 
-/// Remove a `mod xyz;` declaration from a parent module file.
+/// Remove a `mod xyz;` declaration from module source text.
 ///
-/// Handles various declaration styles:
-/// - `mod xyz;`
-/// - `pub mod xyz;`
-/// - `pub(crate) mod xyz;`
-/// - With or without attributes
-///
-/// Performance: Uses pre-compiled regex patterns per module name.
-/// NASA-grade: never panics, returns error on failure.
-pub fn remove_mod_declaration(parent_path: &Path, child_name: &str, dry_run: bool) -> Result<bool> {
-    if !parent_path.exists() {
-        return Ok(false);
-    }
-
-    let content = fs::read_to_string(parent_path)
-        .with_context(|| format!("Failed to read: {}", parent_path.display()))?;
-
+/// Pure transformation behind [`remove_mod_declaration`]: returns the new
+/// content, or `None` if no declaration was found.
+pub fn strip_mod_declaration(content: &str, child_name: &str) -> Option<String> {
     // Try regex-based removal first (handles complex cases)
     let new_content = if let Some(patterns) = ModPatterns::for_module(child_name) {
-        patterns.apply(&content)
+        patterns.apply(content)
     } else {
         None
     };
@@ -225,9 +336,7 @@ pub fn remove_mod_declaration(parent_path: &Path, child_name: &str, dry_run: boo
         }
     });
 
-    let Some(mut new_content) = new_content else {
-        return Ok(false);
-    };
+    let mut new_content = new_content?;
 
     // Clean up multiple consecutive blank lines using pre-compiled regex
     let blank_regex = blank_line_regex();
@@ -240,6 +349,31 @@ pub fn remove_mod_declaration(parent_path: &Path, child_name: &str, dry_run: boo
         new_content.push('\n');
     }
 
+    Some(new_content)
+}
+
+/// Remove a `mod xyz;` declaration from a parent module file.
+///
+/// Handles various declaration styles:
+/// - `mod xyz;`
+/// - `pub mod xyz;`
+/// - `pub(crate) mod xyz;`
+/// - With or without attributes
+///
+/// Performance: Uses pre-compiled regex patterns per module name.
+/// NASA-grade: never panics, returns error on failure.
+pub fn remove_mod_declaration(parent_path: &Path, child_name: &str, dry_run: bool) -> Result<bool> {
+    if !parent_path.exists() {
+        return Ok(false);
+    }
+
+    let content = fs::read_to_string(parent_path)
+        .with_context(|| format!("Failed to read: {}", parent_path.display()))?;
+
+    let Some(new_content) = strip_mod_declaration(&content, child_name) else {
+        return Ok(false);
+    };
+
     if dry_run {
         println!(
             "[DRY-RUN] Would remove `mod {};` from: {}",
@@ -260,6 +394,17 @@ pub fn remove_mod_declaration(parent_path: &Path, child_name: &str, dry_run: boo
     Ok(true)
 }
 
+/// Print the diff of removing `mod child_name;` from `parent_path`.
+fn print_declaration_diff(parent_path: &Path, child_name: &str, color: bool) {
+    let Ok(content) = fs::read_to_string(parent_path) else {
+        return;
+    };
+    if let Some(new_content) = strip_mod_declaration(&content, child_name) {
+        let label = parent_path.display().to_string();
+        print!("{}", render_diff(&label, &content, &new_content, color));
+    }
+}
+
 /// Maximum recursion depth to prevent stack overflow on deeply nested directories.
 const MAX_RECURSION_DEPTH: usize = 128;
 
@@ -348,58 +493,180 @@ fn find_parent_module(
     None
 }
 
+/// Sandbox-validated changes for one fix run.
+pub(crate) struct FixPlan<'a> {
+    /// (module name, canonical file path) of every file to delete
+    pub removals: Vec<(&'a str, PathBuf)>,
+    /// (module name, canonical parent path) of every `mod` declaration to remove
+    pub decl_edits: Vec<(&'a str, PathBuf)>,
+    /// Targets rejected by the sandbox
+    pub refused: Vec<String>,
+}
+
+/// Resolve the files a fix would touch, without touching them.
+pub(crate) fn plan_fix<'a>(
+    sandbox: &Sandbox,
+    crate_root: &Path,
+    dead: &[&'a str],
+    mods: &HashMap<String, ModuleInfo>,
+) -> FixPlan<'a> {
+    let mut plan = FixPlan {
+        removals: Vec::new(),
+        decl_edits: Vec::new(),
+        refused: Vec::new(),
+    };
+
+    for &module_name in dead {
+        if let Some(info) = mods.get(module_name) {
+            match sandbox.resolve(&info.path) {
+                Ok(Some(path)) => plan.removals.push((module_name, path)),
+                Ok(None) => {}
+                Err(e) => plan.refused.push(e.to_string()),
+            }
+        }
+
+        if let Some(parent_path) = find_parent_module(crate_root, module_name, mods) {
+            match sandbox.resolve(&parent_path) {
+                Ok(Some(path)) => plan.decl_edits.push((module_name, path)),
+                Ok(None) => {}
+                Err(e) => plan.refused.push(e.to_string()),
+            }
+        }
+    }
+
+    // Parents that are removed themselves need no declaration edit
+    let removed: Vec<&PathBuf> = plan.removals.iter().map(|(_, p)| p).collect();
+    plan.decl_edits
+        .retain(|(_, parent)| !removed.contains(&parent));
+
+    plan
+}
+
 /// Main fix orchestration function.
 ///
-/// Removes dead modules and cleans up their declarations.
+/// Removes dead modules and cleans up their declarations using default
+/// [`FixOptions`] (confirmation required above [`DEFAULT_CONFIRM_THRESHOLD`]).
+pub fn fix_dead_modules(
+    crate_root: &Path,
+    dead: &[&str],
+    mods: &HashMap<String, ModuleInfo>,
+    dry_run: bool,
+) -> Result<FixResult> {
+    fix_dead_modules_with(crate_root, dead, mods, &FixOptions::new().dry_run(dry_run))
+}
+
+/// Main fix orchestration function with explicit options.
+///
+/// Every target is resolved through a [`Sandbox`] before anything is
+/// modified; rejected targets are reported in [`FixResult::refused`].
+/// Outside dry-run mode, a [`RecoveryManifest`] with the original content of
+/// every touched file is written before the first change.
 ///
 /// NASA-grade resilience:
 /// - Continues on individual file errors
 /// - Reports all errors at the end
 /// - Never panics
-pub fn fix_dead_modules(
+pub fn fix_dead_modules_with(
     crate_root: &Path,
     dead: &[&str],
     mods: &HashMap<String, ModuleInfo>,
-    dry_run: bool,
+    options: &FixOptions,
 ) -> Result<FixResult> {
     let mut result = FixResult::new();
+    let dry_run = options.dry_run;
 
     if dead.is_empty() {
         println!("No dead modules to fix.");
         return Ok(result);
     }
 
+    let sandbox = Sandbox::new(crate_root)?;
+
+    // 1. Plan: resolve every target through the sandbox before touching anything
+    let FixPlan {
+        removals,
+        decl_edits,
+        refused,
+    } = plan_fix(&sandbox, crate_root, dead, mods);
+    result.refused = refused;
+
+    // 2. Require confirmation for large removals
+    if !dry_run && !options.force && removals.len() > options.confirm_threshold {
+        return Err(DeadmodError::fix(format!(
+            "{} files would be removed (limit {} without confirmation); \
+             re-run with --yes (or FixOptions::force(true)) to proceed",
+            removals.len(),
+            options.confirm_threshold
+        ))
+        .into());
+    }
+
     let mode = if dry_run { "DRY-RUN" } else { "FIX" };
     println!("\n[{}] Processing {} dead module(s)...\n", mode, dead.len());
 
-    for module_name in dead {
-        // 1. Find and remove the module file
-        if let Some(info) = mods.get(*module_name) {
-            match remove_file(&info.path, dry_run) {
-                Ok(true) => result.files_removed.push(info.path.display().to_string()),
-                Ok(false) => {}
-                Err(e) => result.errors.push(format!("remove {}: {}", info.path.display(), e)),
+    // 3. Snapshot originals so the fix can be reverted
+    if !dry_run {
+        let mut manifest = RecoveryManifest::new(sandbox.root());
+        for (_, path) in &removals {
+            manifest.record(path, RecoveryAction::Removed)?;
+        }
+        for (_, path) in &decl_edits {
+            manifest.record(path, RecoveryAction::Modified)?;
+        }
+        let manifest_path = manifest.save(crate_root)?;
+        println!(
+            "[FIX] Undo transaction {} recorded (revert with `deadmod fix --undo={}`)",
+            manifest.id, manifest.id
+        );
+        result.manifest = Some(manifest_path.display().to_string());
+        result.transaction = Some(manifest.id);
+    }
+
+    // 4. Remove the module files
+    for (_, path) in &removals {
+        match remove_file(path, dry_run) {
+            Ok(true) => {
+                if dry_run {
+                    if let Ok(content) = fs::read_to_string(path) {
+                        print!(
+                            "{}",
+                            render_removed_file(&content, PREVIEW_LINES, options.color)
+                        );
+                    }
+                }
+                result.files_removed.push(path.display().to_string())
             }
+            Ok(false) => {}
+            Err(e) => result
+                .errors
+                .push(format!("remove {}: {}", path.display(), e)),
         }
+    }
 
-        // 2. Find and update parent module to remove declaration
-        if let Some(parent_path) = find_parent_module(crate_root, module_name, mods) {
-            match remove_mod_declaration(&parent_path, module_name, dry_run) {
-                Ok(true) => result
-                    .declarations_removed
-                    .push(format!("{} from {}", module_name, parent_path.display())),
-                Ok(false) => {}
-                Err(e) => result.errors.push(format!(
-                    "remove decl {} from {}: {}",
+    // 5. Remove declarations from parent modules
+    for (module_name, parent_path) in &decl_edits {
+        match remove_mod_declaration(parent_path, module_name, dry_run) {
+            Ok(true) => {
+                if dry_run {
+                    print_declaration_diff(parent_path, module_name, options.color);
+                }
+                result.declarations_removed.push(format!(
+                    "{} from {}",
                     module_name,
-                    parent_path.display(),
-                    e
-                )),
+                    parent_path.display()
+                ))
             }
+            Ok(false) => {}
+            Err(e) => result.errors.push(format!(
+                "remove decl {} from {}: {}",
+                module_name,
+                parent_path.display(),
+                e
+            )),
         }
     }
 
-    // 3. Clean up empty directories
+    // 6. Clean up empty directories
     let src = crate_root.join("src");
     match clean_empty_dirs(&src, dry_run) {
         Ok(dirs) => result.dirs_removed = dirs,
@@ -410,9 +677,19 @@ pub fn fix_dead_modules(
     println!();
     println!("=== {} Summary ===", mode);
     println!("Files removed: {}", result.files_removed.len());
-    println!("Declarations removed: {}", result.declarations_removed.len());
+    println!(
+        "Declarations removed: {}",
+        result.declarations_removed.len()
+    );
     println!("Empty dirs removed: {}", result.dirs_removed.len());
 
+    if !result.refused.is_empty() {
+        println!("Refused (sandbox): {}", result.refused.len());
+        for refused in &result.refused {
+            eprintln!("  - {}", refused);
+        }
+    }
+
     if !result.errors.is_empty() {
         println!("Errors: {}", result.errors.len());
         for err in &result.errors {
@@ -575,7 +852,10 @@ mod tests {
         let src = dir.join("src");
         fs::create_dir_all(&src).unwrap();
 
-        create_file(&src.join("main.rs"), "mod utils;\nmod dead;\n\nfn main() {}\n");
+        create_file(
+            &src.join("main.rs"),
+            "mod utils;\nmod dead;\n\nfn main() {}\n",
+        );
         create_file(&src.join("utils.rs"), "pub fn helper() {}\n");
         create_file(&src.join("dead.rs"), "pub fn unused() {}\n");
 
@@ -754,7 +1034,7 @@ mod tests {
             // Create symlink
             if symlink(&real_file, &link_file).is_ok() {
                 // Should refuse to delete symlink
-                let result = remove_file(&link_file, false);
+                let _result = remove_file(&link_file, false);
                 // Even if it succeeds, the real file should not be affected
                 assert!(real_file.exists());
             }
@@ -803,7 +1083,10 @@ mod tests {
         let src = dir.join("src");
         fs::create_dir_all(&src).unwrap();
 
-        create_file(&src.join("main.rs"), "mod a;\nmod b;\nmod c;\nfn main() {}\n");
+        create_file(
+            &src.join("main.rs"),
+            "mod a;\nmod b;\nmod c;\nfn main() {}\n",
+        );
         create_file(&src.join("a.rs"), "// dead");
         create_file(&src.join("b.rs"), "// dead");
         create_file(&src.join("c.rs"), "// alive");
@@ -852,4 +1135,104 @@ mod tests {
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    // --- SANDBOX / CONFIRMATION / MANIFEST TESTS ---
+
+    fn dead_crate(name: &str, count: usize) -> (std::path::PathBuf, HashMap<String, ModuleInfo>) {
+        let dir = create_temp_dir(name);
+        let src = dir.join("src");
+        let mut main_info = ModuleInfo::new(src.join("main.rs"));
+        let mut mods = HashMap::new();
+        let mut decls = String::new();
+
+        for i in 0..count {
+            let name = format!("dead{}", i);
+            create_file(&src.join(format!("{}.rs", name)), "// dead\n");
+            decls.push_str(&format!("mod {};\n", name));
+            main_info.refs.insert(name.clone());
+            mods.insert(
+                name.clone(),
+                ModuleInfo::new(src.join(format!("{}.rs", name))),
+            );
+        }
+        create_file(&src.join("main.rs"), &format!("{}fn main() {{}}\n", decls));
+        mods.insert("main".to_string(), main_info);
+        (dir, mods)
+    }
+
+    #[test]
+    fn test_fix_requires_confirmation_above_threshold() {
+        let (dir, mods) = dead_crate("fix_confirm", 3);
+        let dead = vec!["dead0", "dead1", "dead2"];
+
+        let options = FixOptions::new().confirm_threshold(2);
+        let err = fix_dead_modules_with(&dir, &dead, &mods, &options).unwrap_err();
+        assert!(err.to_string().contains("--yes"));
+        // Nothing touched
+        assert!(dir.join("src/dead0.rs").exists());
+
+        let result = fix_dead_modules_with(&dir, &dead, &mods, &options.force(true)).unwrap();
+        assert_eq!(result.files_removed.len(), 3);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_fix_dry_run_ignores_threshold() {
+        let (dir, mods) = dead_crate("fix_confirm_dry", 3);
+        let dead = vec!["dead0", "dead1", "dead2"];
+
+        let options = FixOptions::new().confirm_threshold(1).dry_run(true);
+        let result = fix_dead_modules_with(&dir, &dead, &mods, &options).unwrap();
+        assert_eq!(result.files_removed.len(), 3);
+        assert!(result.manifest.is_none());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_fix_writes_recovery_manifest() {
+        let (dir, mods) = dead_crate("fix_manifest", 1);
+        let result = fix_dead_modules(&dir, &["dead0"], &mods, false).unwrap();
+        assert!(result.manifest.is_some());
+
+        let manifest =
+            RecoveryManifest::load(Path::new(result.manifest.as_ref().unwrap())).unwrap();
+        assert_eq!(Some(&manifest.id), result.transaction.as_ref());
+        let removed = manifest
+            .entries
+            .iter()
+            .find(|e| e.action == RecoveryAction::Removed)
+            .unwrap();
+        assert_eq!(removed.content, "// dead\n");
+        let modified = manifest
+            .entries
+            .iter()
+            .find(|e| e.action == RecoveryAction::Modified)
+            .unwrap();
+        assert!(modified.content.contains("mod dead0;"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_fix_refuses_target_outside_root() {
+        let outer = create_temp_dir("fix_outside");
+        let crate_root = outer.join("crate");
+        create_file(&crate_root.join("src/main.rs"), "fn main() {}\n");
+        create_file(&outer.join("victim.rs"), "important\n");
+
+        let mut mods = HashMap::new();
+        mods.insert(
+            "victim".to_string(),
+            ModuleInfo::new(crate_root.join("src/../../victim.rs")),
+        );
+
+        let result = fix_dead_modules(&crate_root, &["victim"], &mods, false).unwrap();
+        assert!(result.files_removed.is_empty());
+        assert_eq!(result.refused.len(), 1);
+        assert!(outer.join("victim.rs").exists());
+
+        fs::remove_dir_all(&outer).ok();
+    }
 }
@@ -0,0 +1,176 @@
+//! Renaming of unused function parameters to `_name`.
+//!
+//! Parameters are renamed in place ([`crate::params::rename_unused_params`])
+//! rather than removed, so callers keep compiling. All files are rewritten
+//! as one transaction with a single [`RecoveryManifest`]; `deadmod fix
+//! --undo` reverts them together.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::{render_diff, FixOptions, FixResult, RecoveryAction, RecoveryManifest, Sandbox};
+use crate::params::{rename_unused_params, DeadParam};
+
+/// Prefix every dead parameter with `_` in the file it was reported in.
+///
+/// Files rejected by the sandbox are reported in [`FixResult::refused`] and
+/// left untouched.
+pub fn fix_dead_params(
+    crate_root: &Path,
+    dead: &[DeadParam],
+    options: &FixOptions,
+) -> Result<FixResult> {
+    let mut result = FixResult::new();
+    let dry_run = options.dry_run;
+
+    if dead.is_empty() {
+        println!("No dead parameters to fix.");
+        return Ok(result);
+    }
+
+    let sandbox = Sandbox::new(crate_root)?;
+    let mut by_file: BTreeMap<&str, Vec<(&str, &str)>> = BTreeMap::new();
+    for param in dead {
+        by_file
+            .entry(param.file.as_str())
+            .or_default()
+            .push((param.function.as_str(), param.name.as_str()));
+    }
+
+    let mode = if dry_run { "DRY-RUN" } else { "FIX" };
+    println!(
+        "\n[{}] Renaming {} dead parameter(s)...\n",
+        mode,
+        dead.len()
+    );
+
+    // 1. Plan: compute every rewrite before touching anything
+    let mut renamed = 0;
+    let mut edits: Vec<(PathBuf, String)> = Vec::new();
+    for (file, targets) in by_file {
+        let path = match sandbox.resolve(Path::new(file)) {
+            Ok(Some(path)) => path,
+            Ok(None) => continue,
+            Err(e) => {
+                result.refused.push(e.to_string());
+                continue;
+            }
+        };
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                result
+                    .errors
+                    .push(format!("read {}: {}", path.display(), e));
+                continue;
+            }
+        };
+        if let Some((new_content, count)) = rename_unused_params(&content, &targets) {
+            renamed += count;
+            edits.push((path, new_content));
+        }
+    }
+
+    // 2. Snapshot originals so the fix can be reverted as one transaction
+    if !dry_run && !edits.is_empty() {
+        let mut manifest = RecoveryManifest::new(sandbox.root());
+        for (path, _) in &edits {
+            manifest.record(path, RecoveryAction::Modified)?;
+        }
+        let manifest_path = manifest.save(crate_root)?;
+        println!(
+            "[FIX] Undo transaction {} recorded (revert with `deadmod fix --undo={}`)",
+            manifest.id, manifest.id
+        );
+        result.manifest = Some(manifest_path.display().to_string());
+        result.transaction = Some(manifest.id);
+    }
+
+    // 3. Apply the rewrites
+    for (path, new_content) in &edits {
+        if dry_run {
+            println!("[DRY-RUN] Would rewrite: {}", path.display());
+            if let Ok(content) = fs::read_to_string(path) {
+                let label = path.display().to_string();
+                print!(
+                    "{}",
+                    render_diff(&label, &content, new_content, options.color)
+                );
+            }
+        } else {
+            let written = fs::write(path, new_content)
+                .with_context(|| format!("Failed to write: {}", path.display()));
+            if let Err(e) = written {
+                result.errors.push(e.to_string());
+                continue;
+            }
+            println!("[FIX] Rewrote: {}", path.display());
+        }
+        result.files_modified.push(path.display().to_string());
+    }
+
+    // Summary
+    println!();
+    println!("=== {} Summary ===", mode);
+    println!("Parameters renamed: {}", renamed);
+    println!("Files rewritten: {}", result.files_modified.len());
+
+    if !result.refused.is_empty() {
+        println!("Refused (sandbox): {}", result.refused.len());
+        for refused in &result.refused {
+            eprintln!("  - {}", refused);
+        }
+    }
+
+    if !result.errors.is_empty() {
+        println!("Errors: {}", result.errors.len());
+        for err in &result.errors {
+            eprintln!("  - {}", err);
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Confidence;
+    use crate::fix::undo_fix;
+
+    #[test]
+    fn test_fix_dead_params_and_undo() {
+        let dir =
+            std::env::temp_dir().join(format!("deadmod_param_fix_test_{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join("src")).unwrap();
+        let lib = dir.join("src/lib.rs");
+        let original = "fn scale(x: u32, factor: u32) -> u32 {\n    x * 2\n}\n";
+        fs::write(&lib, original).unwrap();
+
+        let dead = vec![DeadParam {
+            name: "factor".to_string(),
+            function: "scale".to_string(),
+            position: 1,
+            file: lib.display().to_string(),
+            line: 1,
+            column: 17,
+            visibility: "private".to_string(),
+            confidence: Confidence::High,
+        }];
+
+        let result = fix_dead_params(&dir, &dead, &FixOptions::new().dry_run(true)).unwrap();
+        assert_eq!(result.files_modified.len(), 1);
+        assert_eq!(fs::read_to_string(&lib).unwrap(), original);
+
+        let result = fix_dead_params(&dir, &dead, &FixOptions::new()).unwrap();
+        assert!(fs::read_to_string(&lib).unwrap().contains("_factor: u32"));
+
+        undo_fix(&dir, result.transaction.as_deref(), false).unwrap();
+        assert_eq!(fs::read_to_string(&lib).unwrap(), original);
+        fs::remove_dir_all(&dir).ok();
+    }
+}
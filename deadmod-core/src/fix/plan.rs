@@ -0,0 +1,356 @@
+//! Staged removal plans for large cleanups.
+//!
+//! Removing every dead module in one fix is hard to review and, when
+//! something goes wrong, hard to bisect. [`plan`] orders the removals
+//! instead: a module is removed before the modules it depends on, so each
+//! step leaves the crate as consistent as the last one, and steps are capped
+//! in size so each can be reviewed, verified and committed on its own.
+//!
+//! # Ordering
+//!
+//! Among the dead modules, an edge `a -> b` (a references b, including the
+//! `mod b;` declaration in a parent) means `a` goes in an earlier step than
+//! `b`. Modules that reference each other (a dependency cycle) can only go
+//! together and are never split across steps. Modules without dead
+//! dependents go first.
+//!
+//! A plan is written as JSON ([`RemovalPlan::save`]) and applied one step at
+//! a time (`deadmod fix --plan plan.json`): each run takes the first step
+//! whose modules still exist, and skips modules that are no longer dead.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use petgraph::algo::tarjan_scc;
+use petgraph::graphmap::DiGraphMap;
+use serde::{Deserialize, Serialize};
+
+use crate::common::module_id;
+use crate::graph::build_graph;
+use crate::parse::ModuleInfo;
+
+/// Version of the plan file format written by [`RemovalPlan::save`].
+pub const PLAN_FORMAT_VERSION: u32 = 1;
+
+/// One module removal in a plan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedRemoval {
+    /// Module name (graph key)
+    pub module: String,
+    /// Stable ID of the dead module finding
+    pub id: String,
+    /// The module's file, as scanned
+    pub file: String,
+    /// Dead modules removed in earlier steps that referenced this one
+    #[serde(default)]
+    pub after: Vec<String>,
+}
+
+/// Removals applied together as one fix.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanStep {
+    /// 1-based position in the plan
+    pub step: usize,
+    pub removals: Vec<PlannedRemoval>,
+}
+
+impl PlanStep {
+    /// Module names of the step's removals.
+    pub fn modules(&self) -> Vec<&str> {
+        self.removals.iter().map(|r| r.module.as_str()).collect()
+    }
+}
+
+/// Dead module removals in dependency order, batched into steps.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemovalPlan {
+    pub format_version: u32,
+    /// Deadmod version that wrote the plan
+    pub deadmod_version: String,
+    /// Largest step size the plan was made with (0: unlimited)
+    pub max_batch: usize,
+    pub steps: Vec<PlanStep>,
+}
+
+impl RemovalPlan {
+    /// Number of planned removals across all steps.
+    pub fn len(&self) -> usize {
+        self.steps.iter().map(|s| s.removals.len()).sum()
+    }
+
+    /// Whether the plan removes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Step number `step` (1-based).
+    pub fn step(&self, step: usize) -> Option<&PlanStep> {
+        self.steps.iter().find(|s| s.step == step)
+    }
+
+    /// First step with a module still in `mods`: the next one to apply.
+    pub fn next_step(&self, mods: &HashMap<String, ModuleInfo>) -> Option<&PlanStep> {
+        self.steps
+            .iter()
+            .find(|s| s.removals.iter().any(|r| mods.contains_key(&r.module)))
+    }
+
+    /// Write the plan as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json =
+            serde_json::to_string_pretty(self).context("Failed to serialize removal plan")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write removal plan to {}", path.display()))
+    }
+
+    /// Read a plan written by [`RemovalPlan::save`].
+    ///
+    /// Fails if the file was written in a different format version.
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read removal plan from {}", path.display()))?;
+        let plan: Self = serde_json::from_str(&json)
+            .with_context(|| format!("Invalid removal plan {}", path.display()))?;
+        anyhow::ensure!(
+            plan.format_version == PLAN_FORMAT_VERSION,
+            "removal plan {} has format version {} (expected {}); re-create the plan",
+            path.display(),
+            plan.format_version,
+            PLAN_FORMAT_VERSION
+        );
+        Ok(plan)
+    }
+}
+
+/// Order the removal of `dead` modules so dependents go before their
+/// dependencies, in steps of at most `max_batch` modules (0: no limit).
+///
+/// A dependency cycle larger than `max_batch` gets a step of its own.
+/// Modules missing from `mods` are left out.
+pub fn plan(dead: &[&str], mods: &HashMap<String, ModuleInfo>, max_batch: usize) -> RemovalPlan {
+    let dead: BTreeSet<&str> = dead
+        .iter()
+        .copied()
+        .filter(|m| mods.contains_key(*m))
+        .collect();
+    let full = build_graph(mods);
+    let mut graph: DiGraphMap<&str, ()> = DiGraphMap::new();
+    for &module in &dead {
+        graph.add_node(module);
+        for dep in full
+            .neighbors(module)
+            .filter(|d| *d != module && dead.contains(d))
+        {
+            graph.add_edge(module, dep, ());
+        }
+    }
+
+    // Cycles are removed as a unit
+    let mut groups: Vec<Vec<&str>> = tarjan_scc(&graph);
+    for group in &mut groups {
+        group.sort_unstable();
+    }
+    let group_of: HashMap<&str, usize> = groups
+        .iter()
+        .enumerate()
+        .flat_map(|(i, group)| group.iter().map(move |m| (*m, i)))
+        .collect();
+
+    // Level of a group: one past the deepest group depending on it.
+    // tarjan_scc yields dependencies before dependents, so walk it backwards.
+    let mut levels = vec![0usize; groups.len()];
+    for i in (0..groups.len()).rev() {
+        for &module in &groups[i] {
+            for dep in graph.neighbors(module) {
+                let j = group_of[dep];
+                if j != i {
+                    levels[j] = levels[j].max(levels[i] + 1);
+                }
+            }
+        }
+    }
+    let mut by_level: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (i, level) in levels.iter().enumerate() {
+        by_level.entry(*level).or_default().push(i);
+    }
+
+    let removal = |module: &str| {
+        let mut after: Vec<String> = graph
+            .neighbors_directed(module, petgraph::Direction::Incoming)
+            .filter(|m| group_of[m] != group_of[module])
+            .map(String::from)
+            .collect();
+        after.sort();
+        PlannedRemoval {
+            module: module.to_string(),
+            id: module_id(module),
+            file: mods[module].path.display().to_string(),
+            after,
+        }
+    };
+
+    let mut batches: Vec<Vec<PlannedRemoval>> = Vec::new();
+    for mut level in by_level.into_values() {
+        level.sort_by_key(|&i| groups[i][0]);
+        let mut batch: Vec<PlannedRemoval> = Vec::new();
+        for i in level {
+            let group = &groups[i];
+            if !batch.is_empty() && max_batch > 0 && batch.len() + group.len() > max_batch {
+                batches.push(std::mem::take(&mut batch));
+            }
+            batch.extend(group.iter().map(|m| removal(m)));
+        }
+        if !batch.is_empty() {
+            batches.push(batch);
+        }
+    }
+
+    RemovalPlan {
+        format_version: PLAN_FORMAT_VERSION,
+        deadmod_version: env!("CARGO_PKG_VERSION").to_string(),
+        max_batch,
+        steps: batches
+            .into_iter()
+            .enumerate()
+            .map(|(i, removals)| PlanStep {
+                step: i + 1,
+                removals,
+            })
+            .collect(),
+    }
+}
+
+/// Formats a plan as one line per step.
+pub fn format_plan_plain(plan: &RemovalPlan) -> String {
+    if plan.is_empty() {
+        return "No dead modules to plan.\n".to_string();
+    }
+    let mut out = format!(
+        "REMOVAL PLAN ({} module(s) in {} step(s)):\n",
+        plan.len(),
+        plan.steps.len()
+    );
+    for step in &plan.steps {
+        out.push_str(&format!(
+            "  step {}: {}\n",
+            step.step,
+            step.modules().join(", ")
+        ));
+    }
+    out
+}
+
+/// Modules of `step` still to remove: those in `dead`. The others are
+/// returned second, as still present but no longer dead.
+pub fn step_targets<'a>(
+    step: &'a PlanStep,
+    mods: &HashMap<String, ModuleInfo>,
+    dead: &[&str],
+) -> (Vec<&'a str>, Vec<&'a str>) {
+    let dead: HashSet<&str> = dead.iter().copied().collect();
+    step.modules()
+        .into_iter()
+        .filter(|m| mods.contains_key(*m))
+        .partition(|m| dead.contains(m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn mods(edges: &[(&str, &[&str])]) -> HashMap<String, ModuleInfo> {
+        edges
+            .iter()
+            .map(|(name, refs)| {
+                let mut info = ModuleInfo::new(PathBuf::from(format!("src/{}.rs", name)));
+                info.refs = refs.iter().map(|r| r.to_string()).collect();
+                (name.to_string(), info)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_orders_dependents_first() {
+        // old -> legacy -> {shim, util}; shim <-> compat; orphan stands alone
+        let mods = mods(&[
+            ("lib", &["live"]),
+            ("live", &[]),
+            ("old", &["legacy", "live"]),
+            ("legacy", &["shim", "util"]),
+            ("shim", &["compat"]),
+            ("compat", &["shim"]),
+            ("util", &[]),
+            ("orphan", &[]),
+        ]);
+        let dead = ["compat", "legacy", "old", "orphan", "shim", "util"];
+
+        let plan = plan(&dead, &mods, 0);
+        let steps: Vec<Vec<&str>> = plan.steps.iter().map(PlanStep::modules).collect();
+        assert_eq!(
+            steps,
+            [
+                vec!["old", "orphan"],
+                vec!["legacy"],
+                vec!["compat", "shim", "util"]
+            ]
+        );
+        assert_eq!(plan.steps[1].removals[0].after, ["old"]);
+        assert_eq!(plan.steps[2].removals[0].after, Vec::<String>::new());
+        assert_eq!(plan.len(), 6);
+
+        // Batches split levels, never cycles
+        let plan = super::plan(&dead, &mods, 1);
+        let steps: Vec<Vec<&str>> = plan.steps.iter().map(PlanStep::modules).collect();
+        assert_eq!(
+            steps,
+            [
+                vec!["old"],
+                vec!["orphan"],
+                vec!["legacy"],
+                vec!["compat", "shim"],
+                vec!["util"]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_progress() {
+        let mut mods = mods(&[("lib", &[]), ("a", &["b"]), ("b", &[])]);
+        let plan = plan(&["a", "b"], &mods, 0);
+        assert_eq!(plan.next_step(&mods).map(|s| s.step), Some(1));
+
+        // Step 1 applied: `a` is gone
+        mods.remove("a");
+        let next = plan.next_step(&mods).unwrap();
+        assert_eq!(next.step, 2);
+        assert_eq!(step_targets(next, &mods, &["b"]), (vec!["b"], vec![]));
+        // `b` gained a user since the plan was made
+        assert_eq!(step_targets(next, &mods, &[]), (vec![], vec!["b"]));
+
+        mods.remove("b");
+        assert!(plan.next_step(&mods).is_none());
+    }
+
+    #[test]
+    fn test_plan_save_load() {
+        let dir = std::env::temp_dir().join(format!("deadmod_plan_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("plan.json");
+
+        let mods = mods(&[("a", &["b"]), ("b", &[])]);
+        let plan = plan(&["a", "b"], &mods, 5);
+        plan.save(&path).unwrap();
+        assert_eq!(RemovalPlan::load(&path).unwrap(), plan);
+
+        let stale = serde_json::to_string(&RemovalPlan {
+            format_version: PLAN_FORMAT_VERSION + 1,
+            ..plan
+        })
+        .unwrap();
+        std::fs::write(&path, stale).unwrap();
+        assert!(RemovalPlan::load(&path).is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,156 @@
+//! Path sandboxing for fix operations.
+//!
+//! Every path the fixer is about to delete or rewrite is resolved through a
+//! [`Sandbox`] first. Module paths come from parsing, so a crafted `#[path]`
+//! attribute or a symlink inside `src/` could otherwise point the fixer at
+//! files outside the crate.
+//!
+//! # Guarantees
+//!
+//! - Targets are canonicalized before comparison (no `..` tricks)
+//! - Targets must live inside the canonical crate root
+//! - Symlinks are refused outright, never followed
+//! - Only regular files are accepted
+
+use std::path::{Path, PathBuf};
+
+use crate::error::{DeadmodError, DeadmodResult};
+
+/// Canonical crate root that all fix targets must live under.
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    root: PathBuf,
+}
+
+impl Sandbox {
+    /// Create a sandbox rooted at the canonicalized crate root.
+    pub fn new(crate_root: &Path) -> DeadmodResult<Self> {
+        let root = crate_root
+            .canonicalize()
+            .map_err(|e| DeadmodError::io(crate_root, e))?;
+        Ok(Self { root })
+    }
+
+    /// The canonical crate root.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolve a fix target to its canonical path.
+    ///
+    /// Returns `Ok(None)` if the file does not exist (nothing to do), and a
+    /// [`DeadmodError::Security`] if the target is a symlink, not a regular
+    /// file, or resolves outside the crate root.
+    pub fn resolve(&self, path: &Path) -> DeadmodResult<Option<PathBuf>> {
+        // Security: Inspect the link itself, never its target
+        let metadata = match path.symlink_metadata() {
+            Ok(m) => m,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(DeadmodError::io(path, e)),
+        };
+
+        if metadata.file_type().is_symlink() {
+            return Err(DeadmodError::security(format!(
+                "refusing to touch symlink: {}",
+                path.display()
+            )));
+        }
+
+        if !metadata.is_file() {
+            return Err(DeadmodError::security(format!(
+                "refusing to touch non-regular file: {}",
+                path.display()
+            )));
+        }
+
+        let canonical = path.canonicalize().map_err(|e| DeadmodError::io(path, e))?;
+
+        // Security: Symlinked parent directories are caught here, since the
+        // canonical path then leaves the crate root
+        if !canonical.starts_with(&self.root) {
+            return Err(DeadmodError::security(format!(
+                "refusing to touch file outside crate root {}: {}",
+                self.root.display(),
+                canonical.display()
+            )));
+        }
+
+        Ok(Some(canonical))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join("deadmod_sandbox_test")
+            .join(format!("{}_{}", name, std::process::id()));
+        if dir.exists() {
+            fs::remove_dir_all(&dir).ok();
+        }
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_resolve_inside_root() {
+        let dir = temp_dir("inside");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/a.rs"), "").unwrap();
+
+        let sandbox = Sandbox::new(&dir).unwrap();
+        let resolved = sandbox
+            .resolve(&dir.join("src/../src/a.rs"))
+            .unwrap()
+            .unwrap();
+        assert!(resolved.starts_with(sandbox.root()));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_resolve_missing_file() {
+        let dir = temp_dir("missing");
+        let sandbox = Sandbox::new(&dir).unwrap();
+        assert!(sandbox.resolve(&dir.join("nope.rs")).unwrap().is_none());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_resolve_outside_root() {
+        let dir = temp_dir("outside");
+        let crate_root = dir.join("crate");
+        fs::create_dir_all(&crate_root).unwrap();
+        fs::write(dir.join("victim.rs"), "").unwrap();
+
+        let sandbox = Sandbox::new(&crate_root).unwrap();
+        let err = sandbox
+            .resolve(&crate_root.join("../victim.rs"))
+            .unwrap_err();
+        assert!(matches!(err, DeadmodError::Security { .. }));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_resolve_refuses_symlink() {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::symlink;
+
+            let dir = temp_dir("symlink");
+            fs::write(dir.join("real.rs"), "").unwrap();
+            symlink(dir.join("real.rs"), dir.join("link.rs")).unwrap();
+
+            let sandbox = Sandbox::new(&dir).unwrap();
+            let err = sandbox.resolve(&dir.join("link.rs")).unwrap_err();
+            assert!(matches!(err, DeadmodError::Security { .. }));
+
+            fs::remove_dir_all(&dir).ok();
+        }
+    }
+}
@@ -0,0 +1,375 @@
+//! Transaction log and rollback for fixes.
+//!
+//! Every non-dry-run fix writes a [`RecoveryManifest`] to
+//! `.deadmod/undo/<id>/manifest.json` before modifying anything. IDs are UTC
+//! timestamps (`YYYYMMDD-HHMMSS`, with a `-N` suffix on collision), so they
+//! sort chronologically.
+//!
+//! [`undo_fix`] restores every file in a transaction to its recorded state
+//! and then drops the transaction, so repeated undos walk back through the
+//! history like a stack.
+//!
+//! ```rust,ignore
+//! use deadmod_core::fix::undo::{list_transactions, undo_fix};
+//!
+//! for tx in list_transactions(root)? {
+//!     println!("{} ({} files)", tx.id, tx.entries.len());
+//! }
+//! undo_fix(root, None, false)?; // revert the latest fix
+//! ```
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::manifest::{RecoveryAction, RecoveryManifest};
+use super::sandbox::Sandbox;
+use crate::error::DeadmodError;
+
+/// Undo log directory relative to the crate root.
+pub const UNDO_DIR: &str = ".deadmod/undo";
+
+/// Manifest file name inside each transaction directory.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Maximum collision suffix tried when allocating a transaction ID.
+const MAX_ID_SUFFIX: usize = 1000;
+
+/// Result of reverting a fix transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoResult {
+    /// Transaction that was reverted
+    pub transaction: String,
+    /// Files restored to their recorded content
+    pub restored: Vec<String>,
+    /// Per-file failures (the transaction is kept if any occur)
+    pub errors: Vec<String>,
+}
+
+/// Create a fresh transaction directory and return `(id, dir)`.
+///
+/// `create_dir` (not `create_dir_all`) on the leaf makes allocation atomic:
+/// two concurrent fixes can never share a transaction.
+pub(crate) fn allocate_transaction_dir(crate_root: &Path) -> Result<(String, PathBuf)> {
+    let undo_dir = crate_root.join(UNDO_DIR);
+    fs::create_dir_all(&undo_dir)
+        .with_context(|| format!("Failed to create {}", undo_dir.display()))?;
+
+    let base = chrono::Utc::now().format("%Y%m%d-%H%M%S").to_string();
+    for n in 0..MAX_ID_SUFFIX {
+        let id = if n == 0 {
+            base.clone()
+        } else {
+            format!("{}-{}", base, n)
+        };
+        let dir = undo_dir.join(&id);
+        match fs::create_dir(&dir) {
+            Ok(()) => return Ok((id, dir)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e).with_context(|| format!("Failed to create {}", dir.display())),
+        }
+    }
+
+    Err(DeadmodError::fix(format!(
+        "could not allocate an undo transaction ID for {}",
+        base
+    ))
+    .into())
+}
+
+/// List recorded fix transactions, oldest first.
+///
+/// Corrupted manifests are skipped with a warning.
+pub fn list_transactions(crate_root: &Path) -> Result<Vec<RecoveryManifest>> {
+    let undo_dir = crate_root.join(UNDO_DIR);
+    if !undo_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut ids: Vec<String> = fs::read_dir(&undo_dir)
+        .with_context(|| format!("Failed to read {}", undo_dir.display()))?
+        .flatten()
+        .filter(|e| e.path().join(MANIFEST_FILE).is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    ids.sort();
+
+    let mut transactions = Vec::with_capacity(ids.len());
+    for id in ids {
+        match RecoveryManifest::load(&undo_dir.join(&id).join(MANIFEST_FILE)) {
+            Ok(mut manifest) => {
+                manifest.id = id;
+                transactions.push(manifest);
+            }
+            Err(e) => eprintln!("[WARN] Skipping undo transaction {}: {:#}", id, e),
+        }
+    }
+
+    Ok(transactions)
+}
+
+/// Revert a fix transaction (the latest one if `id` is `None`).
+///
+/// Removed files are recreated and modified files are overwritten with their
+/// recorded content. Every path is checked against the crate root first, so
+/// a tampered manifest cannot write outside the crate. On full success the
+/// transaction directory is deleted.
+pub fn undo_fix(crate_root: &Path, id: Option<&str>, dry_run: bool) -> Result<UndoResult> {
+    let transactions = list_transactions(crate_root)?;
+    let manifest = match id {
+        Some(id) => transactions
+            .into_iter()
+            .find(|t| t.id == id)
+            .ok_or_else(|| DeadmodError::fix(format!("no undo transaction with ID '{}'", id)))?,
+        None => transactions
+            .into_iter()
+            .next_back()
+            .ok_or_else(|| DeadmodError::fix("no fix transactions to undo"))?,
+    };
+
+    let sandbox = Sandbox::new(crate_root)?;
+    let mut result = UndoResult {
+        transaction: manifest.id.clone(),
+        restored: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let mode = if dry_run { "DRY-RUN" } else { "UNDO" };
+    println!(
+        "\n[{}] Reverting transaction {} ({} file(s))...\n",
+        mode,
+        manifest.id,
+        manifest.entries.len()
+    );
+
+    for entry in &manifest.entries {
+        if let Err(e) = check_restore_target(&sandbox, &entry.path) {
+            result.errors.push(e.to_string());
+            continue;
+        }
+
+        if dry_run {
+            let verb = match entry.action {
+                RecoveryAction::Removed => "recreate",
+                RecoveryAction::Modified => "restore",
+            };
+            println!("[DRY-RUN] Would {}: {}", verb, entry.path.display());
+            result.restored.push(entry.path.display().to_string());
+            continue;
+        }
+
+        let written = entry
+            .path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&entry.path, &entry.content));
+
+        match written {
+            Ok(()) => {
+                println!("[UNDO] Restored: {}", entry.path.display());
+                result.restored.push(entry.path.display().to_string());
+            }
+            Err(e) => result
+                .errors
+                .push(format!("restore {}: {}", entry.path.display(), e)),
+        }
+    }
+
+    if !dry_run && result.errors.is_empty() {
+        let dir = crate_root.join(UNDO_DIR).join(&manifest.id);
+        if let Err(e) = fs::remove_dir_all(&dir) {
+            eprintln!(
+                "[WARN] Failed to drop undo transaction {}: {}",
+                dir.display(),
+                e
+            );
+        }
+    }
+
+    println!();
+    println!("=== {} Summary ===", mode);
+    println!("Files restored: {}", result.restored.len());
+    if !result.errors.is_empty() {
+        println!("Errors: {}", result.errors.len());
+        for err in &result.errors {
+            eprintln!("  - {}", err);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Security: Ensure a manifest path is safe to write.
+///
+/// Recorded paths are canonical, so they must be absolute, free of `..`, and
+/// inside the canonical crate root. The nearest existing ancestor must still
+/// resolve inside the crate root, so a parent directory swapped for a symlink
+/// cannot redirect a recreated file. An existing file at the path must pass
+/// the regular sandbox check (no symlinks).
+fn check_restore_target(sandbox: &Sandbox, path: &Path) -> Result<(), DeadmodError> {
+    let lexically_safe = path.is_absolute()
+        && !path.components().any(|c| matches!(c, Component::ParentDir))
+        && path.starts_with(sandbox.root());
+
+    if !lexically_safe {
+        return Err(DeadmodError::security(format!(
+            "refusing to restore file outside crate root: {}",
+            path.display()
+        )));
+    }
+
+    // Security: Directories are created below this ancestor, so it must not
+    // lead out of the crate through a symlink
+    if let Some(ancestor) = path
+        .ancestors()
+        .skip(1)
+        .find(|a| a.symlink_metadata().is_ok())
+    {
+        let canonical = ancestor
+            .canonicalize()
+            .map_err(|e| DeadmodError::io(ancestor, e))?;
+        if !canonical.starts_with(sandbox.root()) {
+            return Err(DeadmodError::security(format!(
+                "refusing to restore through symlinked directory: {}",
+                ancestor.display()
+            )));
+        }
+    }
+
+    sandbox.resolve(path).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::fix_dead_modules;
+    use crate::parse::ModuleInfo;
+    use std::collections::HashMap;
+
+    fn temp_crate(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("deadmod_undo_test").join(format!(
+            "{}_{}",
+            name,
+            std::process::id()
+        ));
+        if dir.exists() {
+            fs::remove_dir_all(&dir).ok();
+        }
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/main.rs"), "mod dead;\nfn main() {}\n").unwrap();
+        fs::write(dir.join("src/dead.rs"), "pub fn unused() {}\n").unwrap();
+        dir
+    }
+
+    fn mods_for(dir: &Path) -> HashMap<String, ModuleInfo> {
+        let mut mods = HashMap::new();
+        let mut main_info = ModuleInfo::new(dir.join("src/main.rs"));
+        main_info.refs.insert("dead".to_string());
+        mods.insert("main".to_string(), main_info);
+        mods.insert("dead".to_string(), ModuleInfo::new(dir.join("src/dead.rs")));
+        mods
+    }
+
+    #[test]
+    fn test_undo_restores_latest_fix() {
+        let dir = temp_crate("restore");
+        fix_dead_modules(&dir, &["dead"], &mods_for(&dir), false).unwrap();
+        assert!(!dir.join("src/dead.rs").exists());
+
+        let result = undo_fix(&dir, None, false).unwrap();
+        assert!(result.errors.is_empty());
+        assert_eq!(result.restored.len(), 2);
+        assert_eq!(
+            fs::read_to_string(dir.join("src/dead.rs")).unwrap(),
+            "pub fn unused() {}\n"
+        );
+        assert!(fs::read_to_string(dir.join("src/main.rs"))
+            .unwrap()
+            .contains("mod dead;"));
+
+        // Transaction is consumed
+        assert!(list_transactions(&dir).unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_undo_dry_run_keeps_state() {
+        let dir = temp_crate("dry");
+        fix_dead_modules(&dir, &["dead"], &mods_for(&dir), false).unwrap();
+
+        let result = undo_fix(&dir, None, true).unwrap();
+        assert_eq!(result.restored.len(), 2);
+        assert!(!dir.join("src/dead.rs").exists());
+        assert_eq!(list_transactions(&dir).unwrap().len(), 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_undo_unknown_id() {
+        let dir = temp_crate("unknown");
+        assert!(undo_fix(&dir, Some("19700101-000000"), false).is_err());
+        assert!(undo_fix(&dir, None, false).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_allocate_transaction_ids_unique() {
+        let dir = temp_crate("alloc");
+        let (a, _) = allocate_transaction_dir(&dir).unwrap();
+        let (b, _) = allocate_transaction_dir(&dir).unwrap();
+        assert_ne!(a, b);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_undo_refuses_tampered_path() {
+        let dir = temp_crate("tampered");
+        let outside = dir.parent().unwrap().join("outside_tampered.rs");
+
+        let mut manifest = RecoveryManifest::new(&dir);
+        manifest.entries.push(crate::fix::RecoveryEntry {
+            path: outside.clone(),
+            action: RecoveryAction::Removed,
+            sha256: String::new(),
+            content: "pwned".to_string(),
+        });
+        manifest.save(&dir).unwrap();
+
+        let result = undo_fix(&dir, None, false).unwrap();
+        assert_eq!(result.errors.len(), 1);
+        assert!(!outside.exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_undo_refuses_symlinked_parent() {
+        let dir = temp_crate("symlinked_parent").canonicalize().unwrap();
+        let outside = dir.parent().unwrap().join("outside_symlinked_parent");
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, dir.join("src/gone")).unwrap();
+
+        let mut manifest = RecoveryManifest::new(&dir);
+        manifest.entries.push(crate::fix::RecoveryEntry {
+            path: dir.join("src/gone/nested/dead.rs"),
+            action: RecoveryAction::Removed,
+            sha256: String::new(),
+            content: "pwned".to_string(),
+        });
+        manifest.save(&dir).unwrap();
+
+        let result = undo_fix(&dir, None, false).unwrap();
+        assert_eq!(result.errors.len(), 1);
+        assert!(!outside.join("nested").exists());
+
+        fs::remove_dir_all(&dir).ok();
+        fs::remove_dir_all(&outside).ok();
+    }
+}
@@ -0,0 +1,232 @@
+//! Coordinated removal of dead enum variants and the match arms naming them.
+//!
+//! Removing a variant from its enum breaks every `match` that still lists
+//! it, so the declaration edits ([`crate::enums::strip_variant_decls`]) and
+//! the arm edits ([`crate::matcharms::strip_variant_arms`]) are computed for
+//! all files first and applied as one transaction with a single
+//! [`RecoveryManifest`]. `deadmod fix --undo` reverts both together.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::{render_diff, FixOptions, FixResult, RecoveryAction, RecoveryManifest, Sandbox};
+use crate::enums::{strip_variant_decls, DeadVariant};
+use crate::matcharms::strip_variant_arms;
+
+/// Remove dead variants from their enums and every match arm naming them.
+///
+/// `files` are all source files of the crate; declarations are only edited
+/// in the file each variant was reported in, match arms in every file.
+/// Files rejected by the sandbox are reported in [`FixResult::refused`] and
+/// left untouched.
+pub fn fix_dead_variants(
+    crate_root: &Path,
+    files: &[PathBuf],
+    dead: &[DeadVariant],
+    options: &FixOptions,
+) -> Result<FixResult> {
+    let mut result = FixResult::new();
+    let dry_run = options.dry_run;
+
+    if dead.is_empty() {
+        println!("No dead variants to fix.");
+        return Ok(result);
+    }
+
+    let sandbox = Sandbox::new(crate_root)?;
+    let targets: Vec<(&str, &str)> = dead
+        .iter()
+        .map(|v| (v.enum_name.as_str(), v.variant_name.as_str()))
+        .collect();
+
+    let mode = if dry_run { "DRY-RUN" } else { "FIX" };
+    println!(
+        "\n[{}] Processing {} dead variant(s)...\n",
+        mode,
+        dead.len()
+    );
+
+    // 1. Plan: compute every rewrite before touching anything
+    let mut edits: Vec<(PathBuf, String)> = Vec::new();
+    for file in files {
+        let path = match sandbox.resolve(file) {
+            Ok(Some(path)) => path,
+            Ok(None) => continue,
+            Err(e) => {
+                result.refused.push(e.to_string());
+                continue;
+            }
+        };
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                result
+                    .errors
+                    .push(format!("read {}: {}", path.display(), e));
+                continue;
+            }
+        };
+
+        let declared: Vec<(&str, &str)> = dead
+            .iter()
+            .filter(|v| Path::new(&v.file) == file.as_path())
+            .map(|v| (v.enum_name.as_str(), v.variant_name.as_str()))
+            .collect();
+
+        let mut new_content = content.clone();
+        if let Some((stripped, removed)) = strip_variant_decls(&new_content, &declared) {
+            new_content = stripped;
+            result
+                .declarations_removed
+                .extend((0..removed).map(|_| path.display().to_string()));
+        }
+        if let Some((stripped, _)) = strip_variant_arms(&new_content, &targets) {
+            new_content = stripped;
+        }
+
+        if new_content != content {
+            edits.push((path, new_content));
+        }
+    }
+
+    // 2. Snapshot originals so the fix can be reverted as one transaction
+    if !dry_run && !edits.is_empty() {
+        let mut manifest = RecoveryManifest::new(sandbox.root());
+        for (path, _) in &edits {
+            manifest.record(path, RecoveryAction::Modified)?;
+        }
+        let manifest_path = manifest.save(crate_root)?;
+        println!(
+            "[FIX] Undo transaction {} recorded (revert with `deadmod fix --undo={}`)",
+            manifest.id, manifest.id
+        );
+        result.manifest = Some(manifest_path.display().to_string());
+        result.transaction = Some(manifest.id);
+    }
+
+    // 3. Apply the rewrites
+    for (path, new_content) in &edits {
+        if dry_run {
+            println!("[DRY-RUN] Would rewrite: {}", path.display());
+            if let Ok(content) = fs::read_to_string(path) {
+                let label = path.display().to_string();
+                print!(
+                    "{}",
+                    render_diff(&label, &content, new_content, options.color)
+                );
+            }
+        } else {
+            let written = fs::write(path, new_content)
+                .with_context(|| format!("Failed to write: {}", path.display()));
+            if let Err(e) = written {
+                result.errors.push(e.to_string());
+                continue;
+            }
+            println!("[FIX] Rewrote: {}", path.display());
+        }
+        result.files_modified.push(path.display().to_string());
+    }
+
+    // Summary
+    println!();
+    println!("=== {} Summary ===", mode);
+    println!("Variants removed: {}", result.declarations_removed.len());
+    println!("Files rewritten: {}", result.files_modified.len());
+
+    if !result.refused.is_empty() {
+        println!("Refused (sandbox): {}", result.refused.len());
+        for refused in &result.refused {
+            eprintln!("  - {}", refused);
+        }
+    }
+
+    if !result.errors.is_empty() {
+        println!("Errors: {}", result.errors.len());
+        for err in &result.errors {
+            eprintln!("  - {}", err);
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Confidence;
+    use crate::fix::undo_fix;
+
+    fn dead_variant(file: &Path, enum_name: &str, variant: &str) -> DeadVariant {
+        DeadVariant {
+            enum_name: enum_name.to_string(),
+            variant_name: variant.to_string(),
+            full_name: format!("{}::{}", enum_name, variant),
+            file: file.display().to_string(),
+            line: 0,
+            module_path: "crate".to_string(),
+            visibility: "private".to_string(),
+            confidence: Confidence::High,
+        }
+    }
+
+    fn variant_crate(name: &str) -> (PathBuf, Vec<PathBuf>) {
+        let dir = std::env::temp_dir()
+            .join("deadmod_variant_fix_test")
+            .join(format!("{}_{}", name, std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join("src")).unwrap();
+
+        let lib = dir.join("src/lib.rs");
+        let user = dir.join("src/user.rs");
+        fs::write(&lib, "mod user;\n\nenum Light {\n    Red,\n    Amber,\n}\n").unwrap();
+        fs::write(
+            &user,
+            "fn f(l: crate::Light) -> u8 {\n    match l {\n        Light::Red => 0,\n        Light::Amber => 1,\n    }\n}\n",
+        )
+        .unwrap();
+        (dir, vec![lib, user])
+    }
+
+    #[test]
+    fn test_fix_dead_variants_rewrites_enum_and_matches() {
+        let (dir, files) = variant_crate("apply");
+        let dead = vec![dead_variant(&files[0], "Light", "Amber")];
+
+        let result = fix_dead_variants(&dir, &files, &dead, &FixOptions::new()).unwrap();
+        assert_eq!(result.declarations_removed.len(), 1);
+        assert_eq!(result.files_modified.len(), 2);
+        assert!(result.transaction.is_some());
+
+        let lib = fs::read_to_string(&files[0]).unwrap();
+        assert_eq!(lib, "mod user;\n\nenum Light {\n    Red,\n}\n");
+        let user = fs::read_to_string(&files[1]).unwrap();
+        assert!(!user.contains("Amber"));
+        assert!(user.contains("Light::Red => 0,"));
+
+        // Both files come back from one undo
+        undo_fix(&dir, result.transaction.as_deref(), false).unwrap();
+        assert!(fs::read_to_string(&files[0]).unwrap().contains("Amber"));
+        assert!(fs::read_to_string(&files[1])
+            .unwrap()
+            .contains("Light::Amber => 1"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_fix_dead_variants_dry_run() {
+        let (dir, files) = variant_crate("dry");
+        let original = fs::read_to_string(&files[1]).unwrap();
+        let dead = vec![dead_variant(&files[0], "Light", "Amber")];
+
+        let options = FixOptions::new().dry_run(true);
+        let result = fix_dead_variants(&dir, &files, &dead, &options).unwrap();
+        assert_eq!(result.files_modified.len(), 2);
+        assert!(result.manifest.is_none());
+        assert_eq!(fs::read_to_string(&files[1]).unwrap(), original);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
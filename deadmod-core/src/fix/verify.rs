@@ -0,0 +1,392 @@
+//! Compiler-verified fixes.
+//!
+//! Dead module detection is heuristic, so a removal can break the build
+//! (e.g. a module only reached through a macro or `#[path]`). Verification
+//! applies the fix, runs `cargo check --all-targets` (optionally
+//! `cargo test`), and if the crate no longer builds, bisects the removals to
+//! find and roll back the ones responsible while keeping the rest. Checking
+//! every target catches removals only tests, examples or benches needed.
+//!
+//! # Bisection
+//!
+//! Each module removal (file deletion + `mod` declaration edit) is one unit.
+//! Starting from the original sources, a group of units is applied on top of
+//! the already-accepted units and checked:
+//!
+//! - passes: the whole group is accepted
+//! - fails with one unit: that unit is rolled back
+//! - fails with several units: the group is split in half and each half is
+//!   tried in turn
+//!
+//! With `k` culprits among `n` removals this needs `O(k log n)` checks.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::manifest::RecoveryManifest;
+use super::{
+    fix_dead_modules_with, plan_fix, strip_mod_declaration, FixOptions, FixPlan, FixResult, Sandbox,
+};
+use crate::error::DeadmodError;
+use crate::parse::ModuleInfo;
+
+/// Which cargo commands must pass for a fix to be kept.
+#[derive(Debug, Clone, Default)]
+pub struct VerifyOptions {
+    /// Also run `cargo test` after `cargo check` succeeds
+    pub run_tests: bool,
+}
+
+/// Outcome of a verified fix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyResult {
+    /// The underlying fix (files/declarations as originally applied)
+    pub fix: FixResult,
+    /// Modules whose removal was kept
+    pub kept: Vec<String>,
+    /// Modules whose removal broke the build and was rolled back
+    pub rolled_back: Vec<String>,
+    /// Number of build checks executed
+    pub checks_run: usize,
+}
+
+/// Apply a fix and verify it with `cargo check` (and optionally `cargo test`).
+pub fn fix_and_verify(
+    crate_root: &Path,
+    dead: &[&str],
+    mods: &HashMap<String, ModuleInfo>,
+    options: &FixOptions,
+    verify: &VerifyOptions,
+) -> Result<VerifyResult> {
+    let run_tests = verify.run_tests;
+    fix_and_verify_with(crate_root, dead, mods, options, |root| {
+        cargo_passes(root, run_tests)
+    })
+}
+
+/// Apply a fix and verify it with a custom build check.
+///
+/// `check` returns `Ok(true)` if the crate builds in its current state.
+/// This is the testable core of [`fix_and_verify`].
+pub fn fix_and_verify_with<F>(
+    crate_root: &Path,
+    dead: &[&str],
+    mods: &HashMap<String, ModuleInfo>,
+    options: &FixOptions,
+    mut check: F,
+) -> Result<VerifyResult>
+where
+    F: FnMut(&Path) -> Result<bool>,
+{
+    if options.dry_run {
+        return Err(DeadmodError::fix(
+            "fix verification requires changes; it cannot run in dry-run mode",
+        )
+        .into());
+    }
+
+    // Plan before applying: afterwards the removed files no longer resolve
+    let sandbox = Sandbox::new(crate_root)?;
+    let plan = plan_fix(&sandbox, crate_root, dead, mods);
+    let units = units_from_plan(&plan);
+
+    let fix = fix_dead_modules_with(crate_root, dead, mods, options)?;
+    let mut result = VerifyResult {
+        kept: Vec::new(),
+        rolled_back: Vec::new(),
+        checks_run: 0,
+        fix,
+    };
+
+    let Some(ref manifest_path) = result.fix.manifest else {
+        // Nothing was changed
+        return Ok(result);
+    };
+    let manifest = RecoveryManifest::load(Path::new(manifest_path))?;
+
+    println!(
+        "\n[VERIFY] Checking build with all {} removal(s) applied...",
+        units.len()
+    );
+    result.checks_run += 1;
+    if check(crate_root)? {
+        println!("[VERIFY] Build passes.");
+        result.kept = units.iter().map(|u| u.module.to_string()).collect();
+        return Ok(result);
+    }
+
+    // Make sure the failure is ours before blaming any removal
+    restore_state(&manifest, &units, &[])?;
+    result.checks_run += 1;
+    if !check(crate_root)? {
+        return Err(DeadmodError::fix(
+            "crate does not build even without the fix; all removals were rolled back",
+        )
+        .into());
+    }
+
+    println!(
+        "[VERIFY] Build fails; bisecting {} removal(s)...",
+        units.len()
+    );
+    let mut accepted: Vec<usize> = Vec::new();
+    let mut culprits: Vec<usize> = Vec::new();
+    let mut pending: Vec<Vec<usize>> = vec![(0..units.len()).collect()];
+    let mut state_matches_accepted = true;
+
+    while let Some(group) = pending.pop() {
+        let candidate: Vec<usize> = accepted.iter().chain(&group).copied().collect();
+        restore_state(&manifest, &units, &candidate)?;
+        result.checks_run += 1;
+
+        if check(crate_root)? {
+            accepted = candidate;
+            state_matches_accepted = true;
+            continue;
+        }
+
+        state_matches_accepted = false;
+        if group.len() == 1 {
+            println!(
+                "[VERIFY] Removal of `{}` breaks the build; rolling back",
+                units[group[0]].module
+            );
+            culprits.push(group[0]);
+        } else {
+            // Try the first half next (stack order)
+            let (left, right) = group.split_at(group.len() / 2);
+            pending.push(right.to_vec());
+            pending.push(left.to_vec());
+        }
+    }
+
+    if !state_matches_accepted {
+        restore_state(&manifest, &units, &accepted)?;
+    }
+
+    accepted.sort_unstable();
+    culprits.sort_unstable();
+    result.kept = accepted
+        .iter()
+        .map(|&i| units[i].module.to_string())
+        .collect();
+    result.rolled_back = culprits
+        .iter()
+        .map(|&i| units[i].module.to_string())
+        .collect();
+
+    println!();
+    println!("=== VERIFY Summary ===");
+    println!("Removals kept: {}", result.kept.len());
+    println!("Removals rolled back: {}", result.rolled_back.len());
+    for module in &result.rolled_back {
+        println!("  - {}", module);
+    }
+    println!("Build checks run: {}", result.checks_run);
+
+    Ok(result)
+}
+
+/// One module removal: its file and the parent holding its `mod` declaration.
+struct RemovalUnit<'a> {
+    module: &'a str,
+    file: Option<&'a Path>,
+    parent: Option<&'a Path>,
+}
+
+fn units_from_plan<'a>(plan: &'a FixPlan<'a>) -> Vec<RemovalUnit<'a>> {
+    let mut units: Vec<RemovalUnit<'a>> = Vec::new();
+
+    for (module, path) in &plan.removals {
+        units.push(RemovalUnit {
+            module,
+            file: Some(path),
+            parent: None,
+        });
+    }
+    for (module, parent) in &plan.decl_edits {
+        match units.iter_mut().find(|u| u.module == *module) {
+            Some(unit) => unit.parent = Some(parent),
+            None => units.push(RemovalUnit {
+                module,
+                file: None,
+                parent: Some(parent),
+            }),
+        }
+    }
+
+    units
+}
+
+/// Reset every recorded file to its original content, then apply `applied` units.
+///
+/// Works from the manifest rather than undoing individual units, so edits to
+/// a parent shared by several units always compose correctly.
+fn restore_state(
+    manifest: &RecoveryManifest,
+    units: &[RemovalUnit<'_>],
+    applied: &[usize],
+) -> Result<()> {
+    for entry in &manifest.entries {
+        if let Some(parent) = entry.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to recreate {}", parent.display()))?;
+        }
+        fs::write(&entry.path, &entry.content)
+            .with_context(|| format!("Failed to restore {}", entry.path.display()))?;
+    }
+
+    for &i in applied {
+        let unit = &units[i];
+        if let Some(file) = unit.file {
+            fs::remove_file(file)
+                .with_context(|| format!("Failed to remove {}", file.display()))?;
+        }
+        if let Some(parent) = unit.parent {
+            let content = fs::read_to_string(parent)
+                .with_context(|| format!("Failed to read {}", parent.display()))?;
+            if let Some(new_content) = strip_mod_declaration(&content, unit.module) {
+                fs::write(parent, new_content)
+                    .with_context(|| format!("Failed to write {}", parent.display()))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Run `cargo check --all-targets` (and `cargo test` if requested) in the
+/// crate root, with the cargo named by `CARGO` when run as `cargo deadmod`.
+fn cargo_passes(crate_root: &Path, run_tests: bool) -> Result<bool> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut commands = vec![("check", &["--all-targets", "--quiet"][..])];
+    if run_tests {
+        commands.push(("test", &["--quiet"][..]));
+    }
+
+    for (cmd, args) in commands {
+        let status = Command::new(&cargo)
+            .arg(cmd)
+            .args(args)
+            .current_dir(crate_root)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .with_context(|| {
+                format!("Failed to run `cargo {}` in {}", cmd, crate_root.display())
+            })?;
+
+        if !status.success() {
+            println!("[VERIFY] cargo {} failed", cmd);
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Crate with `count` dead modules all declared from main.rs.
+    fn dead_crate(name: &str, count: usize) -> (PathBuf, HashMap<String, ModuleInfo>) {
+        let dir = std::env::temp_dir()
+            .join("deadmod_verify_test")
+            .join(format!("{}_{}", name, std::process::id()));
+        if dir.exists() {
+            fs::remove_dir_all(&dir).ok();
+        }
+        fs::create_dir_all(dir.join("src")).unwrap();
+
+        let mut main_info = ModuleInfo::new(dir.join("src/main.rs"));
+        let mut mods = HashMap::new();
+        let mut decls = String::new();
+        for i in 0..count {
+            let name = format!("m{}", i);
+            fs::write(dir.join(format!("src/{}.rs", name)), "// dead\n").unwrap();
+            decls.push_str(&format!("mod {};\n", name));
+            main_info.refs.insert(name.clone());
+            mods.insert(
+                name.clone(),
+                ModuleInfo::new(dir.join(format!("src/{}.rs", name))),
+            );
+        }
+        fs::write(
+            dir.join("src/main.rs"),
+            format!("{}fn main() {{}}\n", decls),
+        )
+        .unwrap();
+        mods.insert("main".to_string(), main_info);
+        (dir, mods)
+    }
+
+    #[test]
+    fn test_verify_keeps_everything_when_build_passes() {
+        let (dir, mods) = dead_crate("pass", 3);
+        let dead = vec!["m0", "m1", "m2"];
+
+        let result =
+            fix_and_verify_with(&dir, &dead, &mods, &FixOptions::new(), |_| Ok(true)).unwrap();
+        assert_eq!(result.kept.len(), 3);
+        assert!(result.rolled_back.is_empty());
+        assert_eq!(result.checks_run, 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_verify_bisects_to_culprits() {
+        let (dir, mods) = dead_crate("bisect", 8);
+        let dead: Vec<&str> = vec!["m0", "m1", "m2", "m3", "m4", "m5", "m6", "m7"];
+
+        // "Build" fails whenever m2.rs or m5.rs is missing
+        let result = fix_and_verify_with(&dir, &dead, &mods, &FixOptions::new(), |root| {
+            Ok(root.join("src/m2.rs").exists() && root.join("src/m5.rs").exists())
+        })
+        .unwrap();
+
+        assert_eq!(result.rolled_back, vec!["m2", "m5"]);
+        assert_eq!(result.kept.len(), 6);
+        assert!(dir.join("src/m2.rs").exists());
+        assert!(dir.join("src/m5.rs").exists());
+        assert!(!dir.join("src/m0.rs").exists());
+
+        let main = fs::read_to_string(dir.join("src/main.rs")).unwrap();
+        assert!(main.contains("mod m2;"));
+        assert!(main.contains("mod m5;"));
+        assert!(!main.contains("mod m0;"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_verify_rejects_broken_baseline() {
+        let (dir, mods) = dead_crate("baseline", 2);
+        let dead = vec!["m0", "m1"];
+
+        let err =
+            fix_and_verify_with(&dir, &dead, &mods, &FixOptions::new(), |_| Ok(false)).unwrap_err();
+        assert!(err.to_string().contains("does not build"));
+        // Everything restored
+        assert!(dir.join("src/m0.rs").exists());
+        assert!(dir.join("src/m1.rs").exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_verify_rejects_dry_run() {
+        let (dir, mods) = dead_crate("dry", 1);
+        let options = FixOptions::new().dry_run(true);
+        assert!(fix_and_verify_with(&dir, &["m0"], &mods, &options, |_| Ok(true)).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -5,13 +5,17 @@
 //! - Path calls: module::foo()
 //! - Method calls: obj.method()
 //! - Associated function calls: Type::func()
+//! - Functions passed as arguments: tokio::spawn(worker), .then(process)
+//!   (see [`function_args`])
 //!
 //! NASA-grade resilience: handles malformed AST gracefully.
 
 use std::collections::HashSet;
 use std::path::Path;
 
-use syn::{visit::Visit, Expr, File};
+use syn::{visit::Visit, Expr, File, Macro};
+
+use crate::common::{function_args, visit_macro_args};
 
 /// Information about a function call site.
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -35,6 +39,18 @@ impl CallExtractor {
             calls: HashSet::with_capacity(64),
         }
     }
+
+    /// Functions passed by name to a call, called later by the callee.
+    fn record_function_args<'a>(&mut self, args: impl IntoIterator<Item = &'a Expr>) {
+        for path in function_args(args) {
+            let segments: Vec<_> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+            self.calls.insert(CallSite {
+                name: segments[segments.len() - 1].clone(),
+                path: (segments.len() > 1).then(|| segments.join("::")),
+                is_method_call: false,
+            });
+        }
+    }
 }
 
 impl<'ast> Visit<'ast> for CallExtractor {
@@ -64,6 +80,7 @@ impl<'ast> Visit<'ast> for CallExtractor {
                         });
                     }
                 }
+                self.record_function_args(&call.args);
             }
 
             // Method calls: obj.method() or Type::method()
@@ -73,6 +90,7 @@ impl<'ast> Visit<'ast> for CallExtractor {
                     path: None,
                     is_method_call: true,
                 });
+                self.record_function_args(&method.args);
             }
 
             _ => {}
@@ -81,6 +99,27 @@ impl<'ast> Visit<'ast> for CallExtractor {
         // Continue visiting nested expressions
         syn::visit::visit_expr(self, node);
     }
+
+    // Calls inside macro arguments: info!("{}", foo())
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac, |v, found| {
+            if !found.is_call {
+                return;
+            }
+            let segments: Vec<_> = found
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect();
+            v.calls.insert(CallSite {
+                name: found.name(),
+                path: (segments.len() > 1 && !found.is_method).then(|| segments.join("::")),
+                is_method_call: found.is_method,
+            });
+        });
+        syn::visit::visit_macro(self, mac);
+    }
 }
 
 /// Extract all function calls from file content.
@@ -189,6 +228,60 @@ fn main() {
         assert!(names.contains("deep"));
     }
 
+    #[test]
+    fn test_extract_calls_in_macros() {
+        let content = r#"
+fn main() {
+    info!("built {}", helper());
+    let things = vec![make_thing()];
+    route!(GET => handlers::index(req));
+}
+"#;
+        let calls = extract_calls(&PathBuf::from("test.rs"), content);
+        assert!(calls.iter().any(|c| c.name == "helper"));
+        assert!(calls.iter().any(|c| c.name == "make_thing"));
+        let index = calls.iter().find(|c| c.name == "index").unwrap();
+        assert_eq!(index.path, Some("handlers::index".to_string()));
+    }
+
+    #[test]
+    fn test_extract_async_calls() {
+        let content = r#"
+async fn serve(rx: Receiver) -> Result<()> {
+    tokio::spawn(async move { handle_conn().await });
+    tokio::spawn(worker);
+    let futs: FuturesUnordered<_> = ids.iter().map(|id| fetch(*id)).collect();
+    stream.then(process_item).for_each(Self::record).await;
+    load_config().await?.validate()?;
+    tokio::select! {
+        Some(msg) = rx.recv() => on_msg(msg).await,
+        _ = shutdown_signal() => {}
+    }
+    Ok(())
+}
+"#;
+        let calls = extract_calls(&PathBuf::from("test.rs"), content);
+        for name in [
+            "handle_conn",
+            "worker",
+            "fetch",
+            "process_item",
+            "record",
+            "load_config",
+            "validate",
+            "on_msg",
+            "shutdown_signal",
+        ] {
+            assert!(calls.iter().any(|c| c.name == name), "{} not found", name);
+        }
+        let record = calls.iter().find(|c| c.name == "record").unwrap();
+        assert_eq!(
+            (record.path.as_deref(), record.is_method_call),
+            (Some("Self::record"), false)
+        );
+        assert!(!calls.iter().any(|c| c.name == "await" || c.name == "id"));
+    }
+
     #[test]
     fn test_malformed_file_resilient() {
         let content = "fn main( { broken }";
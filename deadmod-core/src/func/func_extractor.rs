@@ -11,9 +11,15 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use syn::{visit::Visit, Attribute, File, ImplItem, ImplItemFn, Item, ItemFn, ItemImpl, ItemMod, Visibility};
+use syn::{
+    visit::Visit, Attribute, Block, File, ImplItem, ImplItemFn, Item, ItemFn, ItemImpl, ItemMod,
+    Signature, Visibility,
+};
 
-use crate::common::visibility_str;
+use crate::common::{
+    attribute_paths, deprecation, has_cfg_attr, is_cfg_test, is_doc_hidden, keep_annotation,
+    visibility_str, Confidence, Deprecation, FnMetrics, ImplSpan, Keep,
+};
 
 /// Information about a single function.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,14 +32,45 @@ pub struct FunctionInfo {
     pub visibility: String,
     /// Source file path
     pub file: String,
+    /// 1-based line of the function's name
+    #[serde(default)]
+    pub line: usize,
     /// Whether this is a method (inside impl block)
     pub is_method: bool,
     /// The type this method belongs to (if is_method)
     pub impl_type: Option<String>,
+    /// The `impl` block this method is defined in (if is_method)
+    #[serde(default)]
+    pub impl_span: Option<ImplSpan>,
     /// Whether this function has #[test] attribute
     pub is_test: bool,
+    /// Whether this function is compiled only for tests: inside a
+    /// `#[cfg(test)]` module or impl, or `#[cfg(test)]` itself
+    #[serde(default)]
+    pub test_only: bool,
     /// Whether this function has #[no_mangle] attribute (FFI entry point)
     pub is_no_mangle: bool,
+    /// Whether this function has #[cfg] or #[cfg_attr] attributes
+    #[serde(default)]
+    pub has_cfg: bool,
+    /// Whether this function has #[doc(hidden)]
+    #[serde(default)]
+    pub doc_hidden: bool,
+    /// `#[deprecated]` details, if the function is deprecated
+    #[serde(default)]
+    pub deprecated: Option<Deprecation>,
+    /// `#[deadmod::keep]` details, if the function is kept on purpose
+    #[serde(default)]
+    pub keep: Option<Keep>,
+    /// Attribute paths, for [`LivenessRules`](crate::common::LivenessRules)
+    #[serde(default)]
+    pub attributes: Vec<String>,
+    /// Confidence that the function is dead (set by [`super::FuncGraph::analyze`])
+    #[serde(default)]
+    pub confidence: Confidence,
+    /// Body size, parameter count and estimated complexity
+    #[serde(default)]
+    pub metrics: FnMetrics,
 }
 
 /// AST visitor that extracts all function declarations.
@@ -42,6 +79,12 @@ struct FunctionExtractor {
     results: Vec<FunctionInfo>,
     current_mod: Vec<String>,
     current_impl: Option<String>,
+    /// Span of the enclosing impl block
+    impl_span: Option<ImplSpan>,
+    /// `#[deadmod::keep]` on the enclosing impl block
+    impl_keep: Option<Keep>,
+    /// Enclosing `#[cfg(test)]` modules and impl blocks
+    test_depth: usize,
 }
 
 impl FunctionExtractor {
@@ -51,6 +94,9 @@ impl FunctionExtractor {
             results: Vec::with_capacity(32), // Pre-allocate for typical file
             current_mod: Vec::new(),
             current_impl: None,
+            impl_span: None,
+            impl_keep: None,
+            test_depth: 0,
         }
     }
 
@@ -70,7 +116,16 @@ impl FunctionExtractor {
         })
     }
 
-    fn record_function(&mut self, name: &str, vis: &Visibility, is_method: bool, attrs: &[Attribute]) {
+    fn record_function(
+        &mut self,
+        sig: &Signature,
+        body: &Block,
+        vis: &Visibility,
+        is_method: bool,
+        attrs: &[Attribute],
+    ) {
+        let name = sig.ident.to_string();
+        let name = name.as_str();
         let is_test = Self::has_attribute(attrs, "test");
         let is_no_mangle = Self::has_attribute(attrs, "no_mangle");
 
@@ -79,10 +134,20 @@ impl FunctionExtractor {
             full_path: self.build_full_path(name),
             visibility: visibility_str(vis).to_string(),
             file: self.file_path.clone(),
+            line: sig.ident.span().start().line,
             is_method,
             impl_type: self.current_impl.clone(),
+            impl_span: self.impl_span.clone(),
             is_test,
+            test_only: self.test_depth > 0 || is_test || is_cfg_test(attrs),
             is_no_mangle,
+            has_cfg: has_cfg_attr(attrs),
+            doc_hidden: is_doc_hidden(attrs),
+            deprecated: deprecation(attrs),
+            keep: keep_annotation(attrs).or_else(|| self.impl_keep.clone()),
+            attributes: attribute_paths(attrs),
+            confidence: Confidence::default(),
+            metrics: FnMetrics::of(sig, Some(body)),
         });
     }
 }
@@ -92,37 +157,70 @@ impl<'ast> Visit<'ast> for FunctionExtractor {
         match item {
             // Handle inline modules: mod foo { ... }
             Item::Mod(ItemMod {
+                attrs,
                 ident,
                 content: Some((_, items)),
                 ..
             }) => {
+                let test_mod = is_cfg_test(attrs);
+                self.test_depth += usize::from(test_mod);
                 self.current_mod.push(ident.to_string());
                 for i in items {
                     self.visit_item(i);
                 }
                 self.current_mod.pop();
+                self.test_depth -= usize::from(test_mod);
             }
 
             // Free functions: fn foo() { ... }
-            Item::Fn(ItemFn { sig, vis, attrs, .. }) => {
-                self.record_function(&sig.ident.to_string(), vis, false, attrs);
+            Item::Fn(ItemFn {
+                sig,
+                vis,
+                attrs,
+                block,
+            }) => {
+                self.record_function(sig, block, vis, false, attrs);
             }
 
             // Impl blocks: impl Foo { ... } or impl Trait for Foo { ... }
-            Item::Impl(ItemImpl {
-                self_ty, items, ..
-            }) => {
+            Item::Impl(
+                item_impl @ ItemImpl {
+                    self_ty,
+                    items,
+                    attrs,
+                    trait_,
+                    ..
+                },
+            ) => {
                 // Extract type name for the impl block
                 let type_name = extract_type_name(self_ty);
+                let trait_name = trait_
+                    .as_ref()
+                    .and_then(|(_, path, _)| path.segments.last())
+                    .map(|s| s.ident.to_string());
+                self.impl_span = Some(ImplSpan::of(item_impl, trait_name.as_deref(), &type_name));
                 self.current_impl = Some(type_name);
+                self.impl_keep = keep_annotation(attrs);
+                let test_impl = is_cfg_test(attrs);
+                self.test_depth += usize::from(test_impl);
 
                 for impl_item in items {
-                    if let ImplItem::Fn(ImplItemFn { sig, vis, attrs, .. }) = impl_item {
-                        self.record_function(&sig.ident.to_string(), vis, true, attrs);
+                    if let ImplItem::Fn(ImplItemFn {
+                        sig,
+                        vis,
+                        attrs,
+                        block,
+                        ..
+                    }) = impl_item
+                    {
+                        self.record_function(sig, block, vis, true, attrs);
                     }
                 }
 
+                self.test_depth -= usize::from(test_impl);
                 self.current_impl = None;
+                self.impl_span = None;
+                self.impl_keep = None;
             }
 
             _ => {
@@ -199,6 +297,14 @@ pub fn public_func() {}
 
         assert_eq!(funcs[1].name, "public_func");
         assert_eq!(funcs[1].visibility, "pub");
+        assert_eq!(
+            funcs[1].metrics,
+            FnMetrics {
+                lines: 1,
+                params: 0,
+                complexity: 1
+            }
+        );
     }
 
     #[test]
@@ -221,6 +327,11 @@ impl Foo {
 
         assert_eq!(funcs[1].name, "public_method");
         assert_eq!(funcs[1].full_path, "Foo::public_method");
+
+        let span = funcs[0].impl_span.as_ref().unwrap();
+        assert_eq!((span.start_line, span.end_line), (4, 7));
+        assert_eq!(span.label, "impl Foo");
+        assert_eq!(funcs[1].impl_span.as_ref(), Some(span));
     }
 
     #[test]
@@ -299,6 +410,63 @@ fn regular_fn() {}
         assert!(!regular.is_no_mangle);
     }
 
+    #[test]
+    fn test_extract_lifecycle_attributes() {
+        let content = r#"
+#[doc(hidden)]
+pub fn __macro_support() {}
+
+#[deprecated(since = "0.2.0", note = "use new_api")]
+pub fn old_api() {}
+"#;
+        let funcs = extract_functions(&PathBuf::from("test.rs"), content);
+
+        let hidden = funcs.iter().find(|f| f.name == "__macro_support").unwrap();
+        assert!(hidden.doc_hidden);
+        assert!(hidden.deprecated.is_none());
+
+        let old = funcs.iter().find(|f| f.name == "old_api").unwrap();
+        assert!(!old.doc_hidden);
+        assert_eq!(
+            old.deprecated.as_ref().unwrap().since.as_deref(),
+            Some("0.2.0")
+        );
+    }
+
+    #[test]
+    fn test_extract_keep_annotation() {
+        let content = r#"
+#[deadmod::keep(reason = "called from the plugin loader")]
+fn load_hook() {}
+
+#[deadmod::keep]
+impl Registry {
+    fn register() {}
+}
+
+fn plain() {}
+"#;
+        let funcs = extract_functions(&PathBuf::from("test.rs"), content);
+
+        let hook = funcs.iter().find(|f| f.name == "load_hook").unwrap();
+        assert_eq!(
+            hook.keep.as_ref().unwrap().reason.as_deref(),
+            Some("called from the plugin loader")
+        );
+        assert!(funcs
+            .iter()
+            .find(|f| f.name == "register")
+            .unwrap()
+            .keep
+            .is_some());
+        assert!(funcs
+            .iter()
+            .find(|f| f.name == "plain")
+            .unwrap()
+            .keep
+            .is_none());
+    }
+
     #[test]
     fn test_multiple_attributes() {
         let content = r#"
@@ -320,4 +488,38 @@ mod tests {
         let nested_test = funcs.iter().find(|f| f.name == "nested_test").unwrap();
         assert!(nested_test.is_test);
     }
+
+    #[test]
+    fn test_test_only_functions() {
+        let content = r#"
+fn production() {}
+
+#[cfg(test)]
+fn gated_helper() {}
+
+#[cfg(test)]
+mod tests {
+    fn setup() {}
+
+    mod nested {
+        fn deep_helper() {}
+    }
+
+    #[test]
+    fn it_works() {}
+}
+
+#[cfg(test)]
+impl Fixture {
+    fn build() {}
+}
+"#;
+        let funcs = extract_functions(&PathBuf::from("test.rs"), content);
+        let test_only = |name: &str| funcs.iter().find(|f| f.name == name).unwrap().test_only;
+
+        assert!(!test_only("production"));
+        for name in ["gated_helper", "setup", "deep_helper", "it_works", "build"] {
+            assert!(test_only(name), "{} should be test-only", name);
+        }
+    }
 }
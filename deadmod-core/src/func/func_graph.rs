@@ -4,31 +4,72 @@
 //!
 //! Entry points (roots) are:
 //! - `main()` function
-//! - `pub` functions (externally visible)
+//! - `pub` functions (externally visible), except `#[doc(hidden)]` ones when
+//!   the [`HiddenApiPolicy`] says they are not public API, and all of them
+//!   when disabled via [`FuncGraph::with_pub_entry_points`]
 //! - `#[test]` functions
 //! - `#[no_mangle]` functions
+//! - `#[deadmod::keep]` functions (listed in [`FuncAnalysisResult::kept`])
+//! - Functions kept alive by the linker or a registry (`#[ctor]`,
+//!   `#[linkme::distributed_slice]`, `inventory::submit!`), see [`LivenessRules`]
+//!
+//! Test-only functions (in `#[cfg(test)]` modules) are only reported with
+//! [`FuncGraph::with_test_helpers`].
 //!
 //! Performance characteristics:
 //! - Graph build: O(|F| + |C|) where F = functions, C = calls
 //! - Reachability: O(|F| + |E|) single BFS traversal
+//!
+//! Nodes are keyed by interned [`Symbol`]s, which also index the node
+//! list, so edges and the BFS work on `u32`s instead of path strings.
 
 use std::collections::{HashMap, HashSet, VecDeque};
 
 use super::func_extractor::FunctionInfo;
+use crate::common::{
+    dead_impls, Confidence, ConfidenceSignals, DeadImpl, HiddenApiPolicy, Interner, KeptItem,
+    LivenessRules, Symbol,
+};
 
 /// Result of function-level dead code analysis.
 #[derive(Debug, Clone)]
 pub struct FuncAnalysisResult {
     /// All functions found in the codebase
     pub all_functions: Vec<FunctionInfo>,
+    /// Functions reachability started from (sorted full paths)
+    pub entry_points: Vec<String>,
     /// Functions reachable from entry points
     pub reachable: HashSet<String>,
     /// Dead (unreachable) functions
     pub dead: Vec<FunctionInfo>,
+    /// Functions kept alive by `#[deadmod::keep]`
+    pub kept: Vec<KeptItem>,
+    /// `impl` blocks all of whose methods are dead
+    pub dead_impls: Vec<DeadImpl>,
     /// Statistics
     pub stats: FuncStats,
 }
 
+impl FuncAnalysisResult {
+    /// Drop dead functions below `min` confidence and update the dead counts.
+    pub fn retain_confidence(&mut self, min: Confidence) {
+        self.dead.retain(|f| f.confidence >= min);
+        self.dead_impls.retain(|i| i.confidence >= min);
+        self.stats.dead_count = self.dead.len();
+        self.stats.public_dead = self
+            .dead
+            .iter()
+            .filter(|f| f.visibility.starts_with("pub"))
+            .count();
+        self.stats.private_dead = self.stats.dead_count - self.stats.public_dead;
+    }
+
+    /// Dead functions marked `#[deprecated]`, i.e. scheduled for removal.
+    pub fn scheduled_for_removal(&self) -> impl Iterator<Item = &FunctionInfo> {
+        self.dead.iter().filter(|f| f.deprecated.is_some())
+    }
+}
+
 /// Statistics about function analysis.
 #[derive(Debug, Clone, Default)]
 pub struct FuncStats {
@@ -41,22 +82,114 @@ pub struct FuncStats {
 
 /// Function call graph for dead code detection.
 pub struct FuncGraph {
-    /// Map from full_path to FunctionInfo
-    nodes: HashMap<String, FunctionInfo>,
-    /// Edges: caller -> set of callee names
-    edges: HashMap<String, HashSet<String>>,
-    /// Reverse lookup: function name -> set of full paths with that name
-    name_to_paths: HashMap<String, HashSet<String>>,
+    /// Node keys (full paths); a key's symbol indexes `nodes`
+    symbols: Interner,
+    /// Functions, by node symbol
+    nodes: Vec<FunctionInfo>,
+    /// Edges: caller -> set of callees
+    edges: HashMap<Symbol, HashSet<Symbol>>,
+    /// Reverse lookup: function name -> nodes with that name
+    name_to_paths: HashMap<String, HashSet<Symbol>>,
+    /// Identifiers mentioned inside macro invocations (confidence signal)
+    macro_mentions: HashSet<String>,
+    /// Treatment of `#[doc(hidden)] pub` functions
+    hidden_policy: HiddenApiPolicy,
+    /// Whether `pub` functions are entry points
+    pub_entry_points: bool,
+    /// If set, only functions in these files can be entry points
+    entry_files: Option<HashSet<String>>,
+    /// Attributes that make a function live
+    liveness: LivenessRules,
+    /// Identifiers passed to registry macros
+    registered: HashSet<String>,
+    /// Whether dead test-only functions are reported
+    test_helpers: bool,
 }
 
 impl FuncGraph {
     /// Create a new empty function graph.
     pub fn new() -> Self {
         Self {
-            nodes: HashMap::new(),
+            symbols: Interner::new(),
+            nodes: Vec::new(),
             edges: HashMap::new(),
             name_to_paths: HashMap::new(),
+            macro_mentions: HashSet::new(),
+            hidden_policy: HiddenApiPolicy::default(),
+            pub_entry_points: true,
+            entry_files: None,
+            liveness: LivenessRules::default(),
+            registered: HashSet::new(),
+            test_helpers: false,
+        }
+    }
+
+    /// Report test helpers no test reaches: dead functions compiled only
+    /// for tests (see [`FunctionInfo::test_only`]). Off by default, since
+    /// test code is never dead production code.
+    pub fn with_test_helpers(mut self, enabled: bool) -> Self {
+        self.test_helpers = enabled;
+        self
+    }
+
+    /// Decide whether `#[doc(hidden)] pub` functions count as public API.
+    pub fn with_hidden_policy(mut self, policy: HiddenApiPolicy) -> Self {
+        self.hidden_policy = policy;
+        self
+    }
+
+    /// Decide whether `pub` functions are entry points, see
+    /// [`PubPolicy::pub_is_reachable`](crate::common::PubPolicy::pub_is_reachable).
+    pub fn with_pub_entry_points(mut self, enabled: bool) -> Self {
+        self.pub_entry_points = enabled;
+        self
+    }
+
+    /// Only consider functions defined in `files` as entry points, e.g. the
+    /// files of custom root modules (`--roots`).
+    pub fn with_entry_files(mut self, files: HashSet<String>) -> Self {
+        self.entry_files = Some(files);
+        self
+    }
+
+    /// Treat functions carrying one of `rules.attributes` as entry points.
+    pub fn with_liveness(mut self, rules: LivenessRules) -> Self {
+        self.liveness = rules;
+        self
+    }
+
+    /// Treat functions named in registry macros (`inventory::submit!`) as
+    /// entry points, see [`LivenessRules::registered_names`].
+    pub fn with_registered(mut self, names: HashSet<String>) -> Self {
+        self.registered = names;
+        self
+    }
+
+    /// Provide identifiers found inside macro invocations.
+    ///
+    /// Dead functions whose name is mentioned in a macro are reported with
+    /// lower confidence, since calls inside macro bodies are tracked only best-effort.
+    pub fn with_macro_mentions(mut self, mentions: HashSet<String>) -> Self {
+        self.macro_mentions = mentions;
+        self
+    }
+
+    /// Score how confident we are that an unreachable function is dead.
+    fn confidence_for(&self, info: &FunctionInfo) -> Confidence {
+        // Excluded hidden items are scored as crate-internal
+        let visibility = if info.doc_hidden && self.hidden_policy == HiddenApiPolicy::Exclude {
+            "pub(crate)"
+        } else {
+            &info.visibility
+        };
+
+        ConfidenceSignals {
+            visibility,
+            has_cfg: info.has_cfg,
+            in_macro: self.macro_mentions.contains(&info.name),
+            ..Default::default()
         }
+        .score()
     }
 
     /// Build the function call graph from extracted data.
@@ -70,39 +203,70 @@ impl FuncGraph {
     ) -> Self {
         let mut graph = Self::new();
 
-        // Add all functions as nodes
+        // Inline test modules repeat paths across files (`tests::setup`), so
+        // test-only functions sharing a path are keyed by file too
+        let mut path_counts: HashMap<&str, usize> = HashMap::new();
         for func in functions {
-            graph
-                .nodes
-                .insert(func.full_path.clone(), func.clone());
-
-            // Build reverse lookup
-            graph
-                .name_to_paths
-                .entry(func.name.clone())
-                .or_default()
-                .insert(func.full_path.clone());
+            *path_counts.entry(func.full_path.as_str()).or_insert(0) += 1;
         }
+        graph.symbols = Interner::with_capacity(functions.len());
+        graph.nodes.reserve(functions.len());
+        let ids: Vec<Symbol> = functions
+            .iter()
+            .map(|func| {
+                let id = if func.test_only && path_counts[func.full_path.as_str()] > 1 {
+                    graph
+                        .symbols
+                        .intern(&format!("{}@{}", func.full_path, func.file))
+                } else {
+                    graph.symbols.intern(&func.full_path)
+                };
+
+                // Add the function as a node (a repeated key keeps the last one)
+                if id.index() == graph.nodes.len() {
+                    graph.nodes.push(func.clone());
+                } else {
+                    graph.nodes[id.index()] = func.clone();
+                }
+
+                // Build reverse lookup
+                graph
+                    .name_to_paths
+                    .entry(func.name.clone())
+                    .or_default()
+                    .insert(id);
+                id
+            })
+            .collect();
 
         // Build edges based on calls
-        for func in functions {
+        for (func, &id) in functions.iter().zip(&ids) {
             if let Some(calls) = file_calls.get(&func.file) {
                 let mut func_edges = HashSet::new();
 
                 for call_name in calls {
                     // Find all functions matching this call name
                     if let Some(targets) = graph.name_to_paths.get(call_name) {
-                        for target in targets {
+                        // A test helper defined in the calling file shadows
+                        // same-named helpers elsewhere
+                        let node = |t: Symbol| &graph.nodes[t.index()];
+                        let is_local_helper =
+                            |t: Symbol| node(t).test_only && node(t).file == func.file;
+                        let local_helper = targets.iter().any(|&t| is_local_helper(t));
+                        for &target in targets {
+                            if local_helper && node(target).test_only && !is_local_helper(target) {
+                                continue;
+                            }
                             // Skip self-references
-                            if target != &func.full_path {
-                                func_edges.insert(target.clone());
+                            if target != id {
+                                func_edges.insert(target);
                             }
                         }
                     }
                 }
 
                 if !func_edges.is_empty() {
-                    graph.edges.insert(func.full_path.clone(), func_edges);
+                    graph.edges.insert(id, func_edges);
                 }
             }
         }
@@ -114,34 +278,61 @@ impl FuncGraph {
     ///
     /// Entry points are:
     /// - `main` function
-    /// - Public functions (`pub`)
+    /// - Public functions (`pub`), subject to the `#[doc(hidden)]` policy
     /// - `#[test]` functions (test entry points)
     /// - `#[no_mangle]` functions (FFI/external entry points)
-    fn find_entry_points(&self) -> HashSet<&str> {
-        let mut roots = HashSet::new();
+    /// - `#[deadmod::keep]` functions
+    /// - Functions with a liveness attribute or named in a registry macro
+    fn find_entry_points(&self, entry_files: Option<&HashSet<String>>) -> Vec<Symbol> {
+        let mut roots = Vec::new();
+
+        for (id, func) in self.symbols.iter().map(|(id, _)| id).zip(&self.nodes) {
+            // Custom roots: nothing outside their files is an entry point
+            if let Some(files) = entry_files {
+                if !files.contains(&func.file) {
+                    continue;
+                }
+            }
 
-        for (path, func) in &self.nodes {
             // main() is always an entry point
             if func.name == "main" {
-                roots.insert(path.as_str());
+                roots.push(id);
                 continue;
             }
 
-            // Public functions are entry points
-            if func.visibility.starts_with("pub") {
-                roots.insert(path.as_str());
+            // Public functions are entry points (unless the pub policy says otherwise)
+            if self.pub_entry_points
+                && func.visibility.starts_with("pub")
+                && self.hidden_policy.is_public_api(func.doc_hidden)
+            {
+                roots.push(id);
                 continue;
             }
 
             // #[test] functions are entry points (called by test harness)
             if func.is_test {
-                roots.insert(path.as_str());
+                roots.push(id);
                 continue;
             }
 
             // #[no_mangle] functions are entry points (can be called from FFI)
             if func.is_no_mangle {
-                roots.insert(path.as_str());
+                roots.push(id);
+                continue;
+            }
+
+            // #[deadmod::keep] functions are kept on purpose
+            if func.keep.is_some() {
+                roots.push(id);
+                continue;
+            }
+
+            // #[ctor], #[distributed_slice], inventory::submit! etc. are
+            // invoked without being referenced by name
+            if self.liveness.alive_attribute(&func.attributes).is_some()
+                || self.registered.contains(&func.name)
+            {
+                roots.push(id);
                 continue;
             }
         }
@@ -154,23 +345,41 @@ impl FuncGraph {
     /// This is a multi-source BFS that finds all functions reachable
     /// from any entry point in O(|F| + |E|) time.
     pub fn compute_reachable(&self) -> HashSet<String> {
-        let entry_points = self.find_entry_points();
+        self.reachable_from(self.entry_files.as_ref())
+    }
+
+    /// Functions reachable when only functions in `files` can be entry
+    /// points, e.g. one binary target's root file.
+    pub fn compute_reachable_from_files(&self, files: &HashSet<String>) -> HashSet<String> {
+        self.reachable_from(Some(files))
+    }
+
+    fn reachable_from(&self, entry_files: Option<&HashSet<String>>) -> HashSet<String> {
+        let visited = self.reachable_mask(entry_files);
+        self.symbols
+            .iter()
+            .filter(|(sym, _)| visited[sym.index()])
+            .map(|(_, path)| path.to_string())
+            .collect()
+    }
 
-        let mut visited: HashSet<String> = HashSet::with_capacity(self.nodes.len());
-        let mut queue: VecDeque<&str> = VecDeque::new();
+    /// Reachability of every node, by symbol.
+    fn reachable_mask(&self, entry_files: Option<&HashSet<String>>) -> Vec<bool> {
+        let mut visited = vec![false; self.nodes.len()];
+        let mut queue: VecDeque<Symbol> = VecDeque::new();
 
         // Initialize with all entry points
-        for root in entry_points {
-            if visited.insert(root.to_string()) {
+        for root in self.find_entry_points(entry_files) {
+            if !std::mem::replace(&mut visited[root.index()], true) {
                 queue.push_back(root);
             }
         }
 
         // BFS traversal
         while let Some(current) = queue.pop_front() {
-            if let Some(callees) = self.edges.get(current) {
-                for callee in callees {
-                    if visited.insert(callee.clone()) {
+            if let Some(callees) = self.edges.get(&current) {
+                for &callee in callees {
+                    if !std::mem::replace(&mut visited[callee.index()], true) {
                         queue.push_back(callee);
                     }
                 }
@@ -182,31 +391,40 @@ impl FuncGraph {
 
     /// Find all dead (unreachable) functions.
     pub fn find_dead(&self) -> Vec<&FunctionInfo> {
-        let reachable = self.compute_reachable();
+        let reachable = self.reachable_mask(self.entry_files.as_ref());
 
         self.nodes
             .iter()
-            .filter(|(path, _)| !reachable.contains(*path))
-            .map(|(_, info)| info)
+            .zip(reachable)
+            .filter(|(info, reachable)| !reachable && (self.test_helpers || !info.test_only))
+            .map(|(info, _)| info)
             .collect()
     }
 
     /// Perform complete analysis and return structured result.
     pub fn analyze(&self) -> FuncAnalysisResult {
-        let reachable = self.compute_reachable();
+        let mask = self.reachable_mask(self.entry_files.as_ref());
+        let reachable: HashSet<String> = self
+            .symbols
+            .iter()
+            .filter(|(sym, _)| mask[sym.index()])
+            .map(|(_, path)| path.to_string())
+            .collect();
 
         let mut dead = Vec::new();
         let mut public_dead = 0;
         let mut private_dead = 0;
 
-        for (path, info) in &self.nodes {
-            if !reachable.contains(path) {
+        for (info, &reachable) in self.nodes.iter().zip(&mask) {
+            if !reachable && (self.test_helpers || !info.test_only) {
                 if info.visibility.starts_with("pub") {
                     public_dead += 1;
                 } else {
                     private_dead += 1;
                 }
-                dead.push(info.clone());
+                let mut info = info.clone();
+                info.confidence = self.confidence_for(&info);
+                dead.push(info);
             }
         }
 
@@ -215,10 +433,45 @@ impl FuncGraph {
 
         let dead_count = dead.len();
 
+        let mut kept: Vec<KeptItem> = self
+            .nodes
+            .iter()
+            .filter_map(|f| {
+                f.keep
+                    .as_ref()
+                    .map(|keep| KeptItem::new(&f.full_path, &f.file, keep))
+            })
+            .collect();
+        kept.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.name.cmp(&b.name)));
+
+        let mut entry_points: Vec<String> = self
+            .find_entry_points(self.entry_files.as_ref())
+            .into_iter()
+            .map(|sym| self.symbols.resolve(sym).to_string())
+            .collect();
+        entry_points.sort();
+
+        let dead_impls = dead_impls(
+            self.nodes
+                .iter()
+                .filter_map(|f| Some((f.file.as_str(), f.impl_span.as_ref()?))),
+            dead.iter().filter_map(|f| {
+                Some((
+                    f.file.as_str(),
+                    f.impl_span.as_ref()?,
+                    f.name.as_str(),
+                    f.confidence,
+                ))
+            }),
+        );
+
         FuncAnalysisResult {
-            all_functions: self.nodes.values().cloned().collect(),
+            all_functions: self.nodes.clone(),
+            entry_points,
             reachable,
             dead,
+            kept,
+            dead_impls,
             stats: FuncStats {
                 total_functions: self.nodes.len(),
                 reachable_count: self.nodes.len() - dead_count,
@@ -256,10 +509,20 @@ mod tests {
             full_path: full_path.to_string(),
             visibility: vis.to_string(),
             file: file.to_string(),
+            line: 0,
             is_method: false,
             impl_type: None,
+            impl_span: None,
             is_test: false,
+            test_only: false,
             is_no_mangle: false,
+            has_cfg: false,
+            doc_hidden: false,
+            deprecated: None,
+            keep: None,
+            attributes: Vec::new(),
+            confidence: Confidence::default(),
+            metrics: Default::default(),
         }
     }
 
@@ -269,10 +532,20 @@ mod tests {
             full_path: full_path.to_string(),
             visibility: "private".to_string(),
             file: file.to_string(),
+            line: 0,
             is_method: false,
             impl_type: None,
+            impl_span: None,
             is_test: true,
+            test_only: true,
             is_no_mangle: false,
+            has_cfg: false,
+            doc_hidden: false,
+            deprecated: None,
+            keep: None,
+            attributes: Vec::new(),
+            confidence: Confidence::default(),
+            metrics: Default::default(),
         }
     }
 
@@ -282,10 +555,20 @@ mod tests {
             full_path: full_path.to_string(),
             visibility: "private".to_string(),
             file: file.to_string(),
+            line: 0,
             is_method: false,
             impl_type: None,
+            impl_span: None,
             is_test: false,
+            test_only: false,
             is_no_mangle: true,
+            has_cfg: false,
+            doc_hidden: false,
+            deprecated: None,
+            keep: None,
+            attributes: Vec::new(),
+            confidence: Confidence::default(),
+            metrics: Default::default(),
         }
     }
 
@@ -325,10 +608,27 @@ mod tests {
         let result = graph.analyze();
 
         // main and public_api are entry points, helper is dead
+        assert_eq!(result.entry_points, ["main", "public_api"]);
         assert_eq!(result.stats.dead_count, 1);
         assert_eq!(result.dead[0].name, "helper");
     }
 
+    #[test]
+    fn test_pub_entry_points_disabled() {
+        let funcs = vec![
+            make_func("main", "main", "private", "main.rs"),
+            make_func("called", "called", "pub", "lib.rs"),
+            make_func("uncalled", "uncalled", "pub", "lib.rs"),
+        ];
+        let calls = HashMap::from([("main.rs".to_string(), HashSet::from(["called".to_string()]))]);
+
+        let result = FuncGraph::build(&funcs, &calls)
+            .with_pub_entry_points(false)
+            .analyze();
+        assert_eq!(result.stats.dead_count, 1);
+        assert_eq!(result.dead[0].name, "uncalled");
+    }
+
     #[test]
     fn test_transitive_reachability() {
         let funcs = vec![
@@ -377,6 +677,142 @@ mod tests {
         assert!(result.dead.iter().any(|f| f.name == "island_b"));
     }
 
+    #[test]
+    fn test_dead_function_confidence() {
+        let mut gated = make_func("gated", "gated", "private", "main.rs");
+        gated.has_cfg = true;
+        let funcs = vec![
+            make_func("main", "main", "private", "main.rs"),
+            make_func("dead", "dead", "private", "main.rs"),
+            make_func("in_macro", "in_macro", "private", "main.rs"),
+            gated,
+        ];
+
+        let graph = FuncGraph::build(&funcs, &HashMap::new())
+            .with_macro_mentions(HashSet::from(["in_macro".to_string()]));
+        let result = graph.analyze();
+
+        let confidence = |name: &str| {
+            result
+                .dead
+                .iter()
+                .find(|f| f.name == name)
+                .map(|f| f.confidence)
+                .unwrap()
+        };
+        assert_eq!(confidence("dead"), Confidence::High);
+        assert_eq!(confidence("gated"), Confidence::Medium);
+        assert_eq!(confidence("in_macro"), Confidence::Low);
+    }
+
+    #[test]
+    fn test_doc_hidden_policy() {
+        let hidden = FunctionInfo {
+            doc_hidden: true,
+            ..make_func("__private_api", "__private_api", "pub", "lib.rs")
+        };
+        let funcs = vec![hidden, make_func("api", "api", "pub", "lib.rs")];
+        let calls = HashMap::new();
+
+        let dead_names = |policy| {
+            let result = FuncGraph::build(&funcs, &calls)
+                .with_hidden_policy(policy)
+                .analyze();
+            result
+                .dead
+                .into_iter()
+                .map(|f| (f.name, f.confidence))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(dead_names(HiddenApiPolicy::Public).is_empty());
+        assert_eq!(
+            dead_names(HiddenApiPolicy::Downgrade),
+            vec![("__private_api".to_string(), Confidence::Low)]
+        );
+        assert_eq!(
+            dead_names(HiddenApiPolicy::Exclude),
+            vec![("__private_api".to_string(), Confidence::High)]
+        );
+    }
+
+    #[test]
+    fn test_scheduled_for_removal() {
+        let deprecated = FunctionInfo {
+            deprecated: Some(crate::common::Deprecation {
+                since: Some("0.3.0".to_string()),
+                note: None,
+            }),
+            ..make_func("old", "old", "private", "lib.rs")
+        };
+        let funcs = vec![deprecated, make_func("dead", "dead", "private", "lib.rs")];
+        let result = FuncGraph::build(&funcs, &HashMap::new()).analyze();
+
+        assert_eq!(result.dead.len(), 2);
+        let scheduled: Vec<&str> = result
+            .scheduled_for_removal()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(scheduled, vec!["old"]);
+    }
+
+    #[test]
+    fn test_keep_annotation_is_entry_point() {
+        let kept = FunctionInfo {
+            keep: Some(crate::common::Keep {
+                reason: Some("FFI callback".to_string()),
+            }),
+            ..make_func("callback", "callback", "private", "lib.rs")
+        };
+        let funcs = vec![
+            kept,
+            make_func("helper", "helper", "private", "util.rs"),
+            make_func("dead", "dead", "private", "other.rs"),
+        ];
+        let mut calls = HashMap::new();
+        calls.insert("lib.rs".to_string(), HashSet::from(["helper".to_string()]));
+        let result = FuncGraph::build(&funcs, &calls).analyze();
+
+        let dead: Vec<&str> = result.dead.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(dead, vec!["dead"]);
+        assert_eq!(result.kept.len(), 1);
+        assert_eq!(result.kept[0].name, "callback");
+        assert_eq!(result.kept[0].reason.as_deref(), Some("FFI callback"));
+    }
+
+    #[test]
+    fn test_liveness_attributes_and_registry() {
+        let ctor = FunctionInfo {
+            attributes: vec!["ctor::ctor".to_string()],
+            ..make_func("init_logging", "init_logging", "private", "lib.rs")
+        };
+        let custom = FunctionInfo {
+            attributes: vec!["plugin::register".to_string()],
+            ..make_func("plugin", "plugin", "private", "lib.rs")
+        };
+        let funcs = vec![
+            ctor,
+            custom,
+            make_func("submitted", "submitted", "private", "lib.rs"),
+            make_func("dead", "dead", "private", "lib.rs"),
+        ];
+
+        let dead_names = |rules: LivenessRules| {
+            let result = FuncGraph::build(&funcs, &HashMap::new())
+                .with_liveness(rules)
+                .with_registered(HashSet::from(["submitted".to_string()]))
+                .analyze();
+            result.dead.into_iter().map(|f| f.name).collect::<Vec<_>>()
+        };
+
+        assert_eq!(dead_names(LivenessRules::default()), vec!["dead", "plugin"]);
+        let rules = LivenessRules::default().with_extra(LivenessRules {
+            attributes: vec!["plugin::register".to_string()],
+            registry_macros: Vec::new(),
+        });
+        assert_eq!(dead_names(rules), vec!["dead"]);
+    }
+
     #[test]
     fn test_method_detection() {
         let funcs = vec![
@@ -386,20 +822,40 @@ mod tests {
                 full_path: "Foo::new".to_string(),
                 visibility: "pub".to_string(),
                 file: "foo.rs".to_string(),
+                line: 0,
                 is_method: true,
                 impl_type: Some("Foo".to_string()),
+                impl_span: None,
                 is_test: false,
+                test_only: false,
                 is_no_mangle: false,
+                has_cfg: false,
+                doc_hidden: false,
+                deprecated: None,
+                keep: None,
+                attributes: Vec::new(),
+                confidence: Confidence::default(),
+                metrics: Default::default(),
             },
             FunctionInfo {
                 name: "unused_method".to_string(),
                 full_path: "Foo::unused_method".to_string(),
                 visibility: "private".to_string(),
                 file: "foo.rs".to_string(),
+                line: 0,
                 is_method: true,
                 impl_type: Some("Foo".to_string()),
+                impl_span: None,
                 is_test: false,
+                test_only: false,
                 is_no_mangle: false,
+                has_cfg: false,
+                doc_hidden: false,
+                deprecated: None,
+                keep: None,
+                attributes: Vec::new(),
+                confidence: Confidence::default(),
+                metrics: Default::default(),
             },
         ];
 
@@ -414,6 +870,30 @@ mod tests {
         assert_eq!(result.dead[0].name, "unused_method");
     }
 
+    #[test]
+    fn test_dead_impl_blocks() {
+        let content = "
+struct Foo;
+impl Foo {
+    fn a(&self) {}
+    fn b(&self) {}
+}
+impl Foo {
+    fn used(&self) {}
+    fn unused(&self) {}
+}
+fn main() { Foo.used(); }
+";
+        let funcs = super::super::extract_functions(std::path::Path::new("main.rs"), content);
+        let calls = HashMap::from([("main.rs".to_string(), HashSet::from(["used".to_string()]))]);
+        let result = FuncGraph::build(&funcs, &calls).analyze();
+
+        assert_eq!(result.stats.dead_count, 3);
+        assert_eq!(result.dead_impls.len(), 1);
+        assert_eq!(result.dead_impls[0].span.start_line, 3);
+        assert_eq!(result.dead_impls[0].methods, ["a", "b"]);
+    }
+
     #[test]
     fn test_test_functions_are_entry_points() {
         let funcs = vec![
@@ -433,6 +913,47 @@ mod tests {
         assert_eq!(result.stats.dead_count, 0);
     }
 
+    #[test]
+    fn test_entry_files_restrict_entry_points() {
+        let funcs = vec![
+            make_func("main", "main", "private", "main.rs"),
+            make_func("serve", "serve", "pub", "api.rs"),
+            make_func("run", "run", "pub", "cli.rs"),
+            make_func("parse", "parse", "private", "util.rs"),
+        ];
+
+        let mut calls = HashMap::new();
+        calls.insert("api.rs".to_string(), HashSet::from(["parse".to_string()]));
+
+        let result = FuncGraph::build(&funcs, &calls)
+            .with_entry_files(HashSet::from(["api.rs".to_string()]))
+            .analyze();
+
+        let mut dead: Vec<_> = result.dead.iter().map(|f| f.name.as_str()).collect();
+        dead.sort();
+        assert_eq!(dead, vec!["main", "run"]);
+    }
+
+    #[test]
+    fn test_compute_reachable_from_files() {
+        let funcs = vec![
+            make_func("main", "main", "private", "main.rs"),
+            make_func("tool_main", "tool_main", "pub", "tool.rs"),
+            make_func("parse", "parse", "private", "util.rs"),
+        ];
+
+        let mut calls = HashMap::new();
+        calls.insert("tool.rs".to_string(), HashSet::from(["parse".to_string()]));
+
+        let graph = FuncGraph::build(&funcs, &calls);
+        let main_only = graph.compute_reachable_from_files(&HashSet::from(["main.rs".to_string()]));
+        assert_eq!(main_only, HashSet::from(["main".to_string()]));
+
+        let tool = graph.compute_reachable_from_files(&HashSet::from(["tool.rs".to_string()]));
+        assert!(tool.contains("parse"));
+        assert!(!tool.contains("main"));
+    }
+
     #[test]
     fn test_no_mangle_functions_are_entry_points() {
         let funcs = vec![
@@ -472,4 +993,35 @@ mod tests {
         assert_eq!(result.stats.private_dead, 1);
         assert_eq!(result.stats.public_dead, 0);
     }
+
+    #[test]
+    fn test_test_helpers_kept_apart_and_opt_in() {
+        let helper = |file: &str| FunctionInfo {
+            test_only: true,
+            ..make_func("setup", "tests::setup", "private", file)
+        };
+        let funcs = vec![
+            make_test_func("it_works", "tests::it_works", "a.rs"),
+            helper("a.rs"),
+            make_test_func("it_works", "tests::it_works", "b.rs"),
+            helper("b.rs"),
+        ];
+        // Only a.rs's test calls its helper
+        let calls = HashMap::from([("a.rs".to_string(), HashSet::from(["setup".to_string()]))]);
+
+        // Same-named helpers in different files are distinct nodes
+        let graph = FuncGraph::build(&funcs, &calls);
+        assert_eq!(graph.analyze().stats.total_functions, 4);
+
+        assert!(graph.analyze().dead.is_empty());
+        let dead = FuncGraph::build(&funcs, &calls)
+            .with_test_helpers(true)
+            .analyze()
+            .dead;
+        assert_eq!(dead.len(), 1);
+        assert_eq!(
+            (dead[0].full_path.as_str(), dead[0].file.as_str()),
+            ("tests::setup", "b.rs")
+        );
+    }
 }
@@ -18,6 +18,8 @@ use syn::{
     LifetimeParam, TypeParam, WhereClause, WherePredicate,
 };
 
+use crate::common::{keep_annotation, Keep};
+
 /// Information about a declared generic parameter.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeclaredGeneric {
@@ -31,8 +33,14 @@ pub struct DeclaredGeneric {
     pub parent_kind: ParentKind,
     /// Source file path
     pub file: String,
+    /// 1-based line of the parameter's name
+    #[serde(default)]
+    pub line: usize,
     /// Trait bounds on this generic (e.g., ["Debug", "Clone"])
     pub bounds: Vec<String>,
+    /// `#[deadmod::keep]` details from the parent item, if it is kept on purpose
+    #[serde(default)]
+    pub keep: Option<Keep>,
 }
 
 /// The kind of generic parameter.
@@ -74,7 +82,13 @@ impl GenericExtractor {
         }
     }
 
-    fn record(&mut self, parent: &str, parent_kind: ParentKind, gp: &GenericParam) {
+    fn record(
+        &mut self,
+        parent: &str,
+        parent_kind: ParentKind,
+        gp: &GenericParam,
+        keep: &Option<Keep>,
+    ) {
         match gp {
             GenericParam::Type(TypeParam { ident, bounds, .. }) => {
                 let bound_names: Vec<String> = bounds
@@ -94,7 +108,9 @@ impl GenericExtractor {
                     parent: parent.to_string(),
                     parent_kind,
                     file: self.file_path.clone(),
+                    line: ident.span().start().line,
                     bounds: bound_names,
+                    keep: keep.clone(),
                 });
             }
 
@@ -105,7 +121,9 @@ impl GenericExtractor {
                     parent: parent.to_string(),
                     parent_kind,
                     file: self.file_path.clone(),
+                    line: lifetime.apostrophe.start().line,
                     bounds: Vec::new(),
+                    keep: keep.clone(),
                 });
             }
 
@@ -116,7 +134,9 @@ impl GenericExtractor {
                     parent: parent.to_string(),
                     parent_kind,
                     file: self.file_path.clone(),
+                    line: cp.ident.span().start().line,
                     bounds: Vec::new(),
+                    keep: keep.clone(),
                 });
             }
         }
@@ -208,37 +228,56 @@ impl GenericExtractor {
 impl<'ast> Visit<'ast> for GenericExtractor {
     fn visit_item(&mut self, item: &'ast Item) {
         match item {
-            Item::Fn(ItemFn { sig, .. }) => {
+            Item::Fn(ItemFn { sig, attrs, .. }) => {
                 let parent = sig.ident.to_string();
+                let keep = keep_annotation(attrs);
                 for gp in &sig.generics.params {
-                    self.record(&parent, ParentKind::Function, gp);
+                    self.record(&parent, ParentKind::Function, gp, &keep);
                 }
                 // Process where clause bounds
                 self.process_where_clause(sig.generics.where_clause.as_ref(), &parent);
             }
 
-            Item::Struct(ItemStruct { ident, generics, .. }) => {
+            Item::Struct(ItemStruct {
+                ident,
+                generics,
+                attrs,
+                ..
+            }) => {
                 let parent = ident.to_string();
+                let keep = keep_annotation(attrs);
                 for gp in &generics.params {
-                    self.record(&parent, ParentKind::Struct, gp);
+                    self.record(&parent, ParentKind::Struct, gp, &keep);
                 }
                 // Process where clause bounds
                 self.process_where_clause(generics.where_clause.as_ref(), &parent);
             }
 
-            Item::Enum(ItemEnum { ident, generics, .. }) => {
+            Item::Enum(ItemEnum {
+                ident,
+                generics,
+                attrs,
+                ..
+            }) => {
                 let parent = ident.to_string();
+                let keep = keep_annotation(attrs);
                 for gp in &generics.params {
-                    self.record(&parent, ParentKind::Enum, gp);
+                    self.record(&parent, ParentKind::Enum, gp, &keep);
                 }
                 // Process where clause bounds
                 self.process_where_clause(generics.where_clause.as_ref(), &parent);
             }
 
-            Item::Trait(ItemTrait { ident, generics, .. }) => {
+            Item::Trait(ItemTrait {
+                ident,
+                generics,
+                attrs,
+                ..
+            }) => {
                 let parent = ident.to_string();
+                let keep = keep_annotation(attrs);
                 for gp in &generics.params {
-                    self.record(&parent, ParentKind::Trait, gp);
+                    self.record(&parent, ParentKind::Trait, gp, &keep);
                 }
                 // Process where clause bounds
                 self.process_where_clause(generics.where_clause.as_ref(), &parent);
@@ -248,6 +287,7 @@ impl<'ast> Visit<'ast> for GenericExtractor {
                 generics,
                 self_ty,
                 trait_,
+                attrs,
                 ..
             }) => {
                 // Use trait name if it's a trait impl, otherwise use the type name
@@ -260,8 +300,9 @@ impl<'ast> Visit<'ast> for GenericExtractor {
                     Self::extract_type_name(self_ty)
                 };
 
+                let keep = keep_annotation(attrs);
                 for gp in &generics.params {
-                    self.record(&parent, ParentKind::Impl, gp);
+                    self.record(&parent, ParentKind::Impl, gp, &keep);
                 }
                 // Process where clause bounds
                 self.process_where_clause(generics.where_clause.as_ref(), &parent);
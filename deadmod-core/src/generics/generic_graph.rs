@@ -6,11 +6,14 @@
 //! - Unused trait bounds (T: Debug where Debug is never utilized)
 //!
 //! Analysis is performed per-parent item to ensure accurate detection.
+//! Parameters of `#[deadmod::keep]` items are never reported; the items are
+//! listed in [`GenericAnalysisResult::kept`].
 
 use std::collections::{HashMap, HashSet};
 
 use super::generic_extractor::{DeclaredGeneric, GenericExtractionResult, GenericKind, ParentKind};
 use super::generic_usage::GenericUsageResult;
+use crate::common::{Confidence, ConfidenceSignals, KeptItem, Resolution};
 
 /// A dead generic parameter that was declared but never used.
 #[derive(Debug, Clone)]
@@ -25,8 +28,12 @@ pub struct DeadGeneric {
     pub parent_kind: ParentKind,
     /// Source file
     pub file: String,
+    /// 1-based line of the parameter's name
+    pub line: usize,
     /// Unused bounds on this generic (if any)
     pub unused_bounds: Vec<String>,
+    /// Confidence that the parameter is really unused
+    pub confidence: Confidence,
 }
 
 /// Statistics about generic analysis.
@@ -45,10 +52,23 @@ pub struct GenericStats {
 pub struct GenericAnalysisResult {
     /// All dead generics found
     pub dead: Vec<DeadGeneric>,
+    /// Items whose parameters are kept by `#[deadmod::keep]`
+    pub kept: Vec<KeptItem>,
     /// Statistics
     pub stats: GenericStats,
 }
 
+impl GenericAnalysisResult {
+    /// Drop dead generics below `min` confidence and update the dead counts.
+    pub fn retain_confidence(&mut self, min: Confidence) {
+        self.dead.retain(|d| d.confidence >= min);
+        let count = |kind: GenericKind| self.dead.iter().filter(|d| d.kind == kind).count();
+        self.stats.dead_types = count(GenericKind::Type);
+        self.stats.dead_lifetimes = count(GenericKind::Lifetime);
+        self.stats.dead_consts = count(GenericKind::Const);
+    }
+}
+
 /// Graph for analyzing generic parameter usage.
 #[derive(Default)]
 pub struct GenericGraph {
@@ -111,14 +131,21 @@ impl GenericGraph {
         let mut dead = Vec::new();
 
         for decl in &self.declared {
-            if !self.is_generic_used(decl) {
+            if decl.keep.is_none() && !self.is_generic_used(decl) {
                 dead.push(DeadGeneric {
                     name: decl.name.clone(),
                     kind: decl.kind,
                     parent: decl.parent.clone(),
                     parent_kind: decl.parent_kind,
                     file: decl.file.clone(),
+                    line: decl.line,
                     unused_bounds: decl.bounds.clone(), // All bounds are unused if generic is unused
+                    // Usage is matched per parent scope by name, not type-checked
+                    confidence: ConfidenceSignals {
+                        resolution: Resolution::Heuristic,
+                        ..Default::default()
+                    }
+                    .score(),
                 });
             }
         }
@@ -158,7 +185,20 @@ impl GenericGraph {
             }
         }
 
-        GenericAnalysisResult { dead, stats }
+        // One entry per kept parent item, not per parameter
+        let mut seen = HashSet::new();
+        let kept = self
+            .declared
+            .iter()
+            .filter(|d| seen.insert((&d.file, &d.parent)))
+            .filter_map(|d| {
+                d.keep
+                    .as_ref()
+                    .map(|keep| KeptItem::new(&d.parent, &d.file, keep))
+            })
+            .collect();
+
+        GenericAnalysisResult { dead, kept, stats }
     }
 
     /// Get the total number of declared generics.
@@ -196,9 +236,32 @@ fn foo<T, U>(x: T) -> T {
 
         let dead_u = result.dead.iter().find(|d| d.name == "U").unwrap();
         assert_eq!(dead_u.parent, "foo");
+        assert_eq!(dead_u.confidence, Confidence::Medium);
         assert!(matches!(dead_u.kind, GenericKind::Type));
     }
 
+    #[test]
+    fn test_kept_item_generics_are_not_dead() {
+        let content = r#"
+#[deadmod::keep(reason = "phantom marker for FFI layout")]
+struct Handle<T> {
+    raw: usize,
+}
+
+fn unused<U>() {}
+"#;
+        let result = analyze_code(content);
+
+        assert_eq!(result.dead.len(), 1);
+        assert_eq!(result.dead[0].name, "U");
+        assert_eq!(result.kept.len(), 1);
+        assert_eq!(result.kept[0].name, "Handle");
+        assert_eq!(
+            result.kept[0].reason.as_deref(),
+            Some("phantom marker for FFI layout")
+        );
+    }
+
     #[test]
     fn test_unused_lifetime() {
         let content = r#"
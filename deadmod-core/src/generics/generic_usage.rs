@@ -16,10 +16,12 @@ use std::path::Path;
 
 use syn::{
     visit::Visit, AngleBracketedGenericArguments, Expr, Field, File, FnArg, GenericArgument, Item,
-    ItemEnum, ItemFn, ItemImpl, ItemStruct, ItemTrait, Pat, PathArguments, ReturnType, Signature,
-    Type,
+    ItemEnum, ItemFn, ItemImpl, ItemStruct, ItemTrait, Macro, Pat, PathArguments, ReturnType,
+    Signature, Type,
 };
 
+use crate::common::visit_macro_args;
+
 /// Information about generic/lifetime usages within a parent item.
 #[derive(Debug, Clone, Default)]
 pub struct ParentUsages {
@@ -326,6 +328,16 @@ impl<'ast> Visit<'ast> for GenericUsageExtractor {
         }
         syn::visit::visit_local(self, local);
     }
+
+    // Type parameters inside macro arguments: assert!(size_of::<T>() > 0)
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac, |v, found| {
+            if found.path.segments.len() == 1 {
+                v.record_type(&found.name());
+            }
+        });
+        syn::visit::visit_macro(self, mac);
+    }
 }
 
 /// Extract a readable type name from a syn::Type.
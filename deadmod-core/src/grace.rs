@@ -0,0 +1,363 @@
+//! Grace period for recently added dead code.
+//!
+//! Scaffolding for work in progress is often unused for a while: a new
+//! function lands a commit before its caller does. With a grace period of
+//! N days, findings on lines `git blame` dates within the last N days are
+//! moved out of the failing results into [`RecentItem`]s, which reports list
+//! as information. Uncommitted lines and untracked files count as added now.
+//! Outside a git work tree nothing is graced.
+//!
+//! Findings without a line number are dated by their declaration: the first
+//! line of the file declaring the item's name (`fn name`, `const NAME`, a
+//! variant or match arm starting a line).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::builder::{DeadItem, DeadItemKind};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// A finding inside the grace period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentItem {
+    #[serde(flatten)]
+    pub item: DeadItem,
+    /// Date the line was added (`YYYY-MM-DD`, UTC)
+    pub added: String,
+}
+
+/// Decides which findings are recent enough to be graced.
+#[derive(Debug)]
+pub struct GracePeriod {
+    days: u32,
+    /// Unix time the period is counted back from
+    now: i64,
+    /// History of each file looked at (`None`: not in a git work tree)
+    blame: Mutex<HashMap<PathBuf, Option<FileHistory>>>,
+}
+
+/// A file's content and the author time of each of its lines.
+#[derive(Debug)]
+struct FileHistory {
+    source: String,
+    times: Vec<i64>,
+}
+
+impl GracePeriod {
+    /// Grace period of `days` days, ending now.
+    pub fn new(days: u32) -> Self {
+        Self::ending_at(days, chrono::Utc::now().timestamp())
+    }
+
+    /// Grace period of `days` days, ending at Unix time `now`.
+    pub fn ending_at(days: u32, now: i64) -> Self {
+        Self {
+            days,
+            now,
+            blame: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Length of the period in days.
+    pub fn days(&self) -> u32 {
+        self.days
+    }
+
+    /// When `item` was added, if within the period.
+    pub fn added_within(&self, item: &DeadItem) -> Option<i64> {
+        let time = {
+            let mut blame = self.blame.lock().unwrap_or_else(|e| e.into_inner());
+            let history = blame
+                .entry(item.file.clone())
+                .or_insert_with(|| file_history(&item.file, self.now))
+                .as_ref()?;
+            let line = match item.line {
+                0 => declaration_line(&history.source, item)?,
+                line => line,
+            };
+            *history.times.get(line.checked_sub(1)?)?
+        };
+        (self.now - time < i64::from(self.days) * SECONDS_PER_DAY).then_some(time)
+    }
+
+    /// `item` as a [`RecentItem`], if added within the period.
+    pub fn recent(&self, item: &DeadItem) -> Option<RecentItem> {
+        self.added_within(item).map(|time| RecentItem {
+            item: item.clone(),
+            added: format_date(time),
+        })
+    }
+
+    /// Move the findings added within the period out of `items`.
+    pub fn take_recent(&self, items: &mut Vec<DeadItem>) -> Vec<RecentItem> {
+        let mut recent = Vec::new();
+        items.retain(|item| match self.recent(item) {
+            Some(found) => {
+                recent.push(found);
+                false
+            }
+            None => true,
+        });
+        recent
+    }
+}
+
+/// 1-based line declaring `item` in `source`, by name; the first match wins.
+///
+/// For findings without a line of their own (`DeadItem::line` is 0).
+pub fn declaration_line(source: &str, item: &DeadItem) -> Option<usize> {
+    let last = |name: &str| name.rsplit("::").next().unwrap_or(name).trim().to_string();
+    let (keywords, name): (&[&str], String) = match item.kind {
+        DeadItemKind::Module => return Some(1),
+        DeadItemKind::Function | DeadItemKind::Method | DeadItemKind::TraitMethod => {
+            (&["fn"], last(&item.name))
+        }
+        DeadItemKind::Constant => (&["const"], last(&item.name)),
+        DeadItemKind::Static => (&["static mut", "static"], last(&item.name)),
+        DeadItemKind::Macro => (&["macro_rules!", "macro", "fn"], last(&item.name)),
+        // `T in parent`: the parent's declaration
+        DeadItemKind::TypeParam
+        | DeadItemKind::Lifetime
+        | DeadItemKind::ConstParam
+        | DeadItemKind::Parameter => {
+            let parent = item
+                .name
+                .split_once(" in ")
+                .map_or(item.name.as_str(), |(_, p)| p);
+            (
+                &["fn", "struct", "enum", "union", "trait", "type", "impl"],
+                last(parent),
+            )
+        }
+        DeadItemKind::EnumVariant | DeadItemKind::Field => (&[], last(&item.name)),
+        DeadItemKind::MatchArm => {
+            let head = item
+                .name
+                .split(['(', '{', ' ', '|'])
+                .next()
+                .unwrap_or_default();
+            let head = last(head);
+            return source
+                .lines()
+                .position(|line| {
+                    line.split_once("=>").is_some_and(|(pattern, _)| {
+                        pattern.match_indices(&*head).any(|(at, _)| {
+                            !pattern[..at].ends_with(is_ident_char)
+                                && starts_with_word(&pattern[at..], &head)
+                        })
+                    })
+                })
+                .map(|i| i + 1);
+        }
+        DeadItemKind::Impl => return None,
+    };
+    if name.is_empty() {
+        return None;
+    }
+    source
+        .lines()
+        .position(|line| {
+            if keywords.is_empty() {
+                return starts_with_word(line.trim_start(), &name);
+            }
+            keywords.iter().any(|keyword| {
+                line.match_indices(keyword).any(|(at, _)| {
+                    let before = line[..at].chars().next_back();
+                    let after = skip_generics(&line[at + keyword.len()..]);
+                    !before.is_some_and(is_ident_char)
+                        && after.starts_with(char::is_whitespace)
+                        && starts_with_word(after.trim_start(), &name)
+                })
+            })
+        })
+        .map(|i| i + 1)
+}
+
+/// `text` after a leading `<...>` parameter list (`impl<T> Wrap<T>`).
+fn skip_generics(text: &str) -> &str {
+    if !text.starts_with('<') {
+        return text;
+    }
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            return &text[i + 1..];
+        }
+    }
+    text
+}
+
+/// Whether `text` starts with the identifier `word`.
+fn starts_with_word(text: &str, word: &str) -> bool {
+    text.strip_prefix(word)
+        .is_some_and(|rest| !rest.starts_with(is_ident_char))
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// `time` as `YYYY-MM-DD`.
+fn format_date(time: i64) -> String {
+    DateTime::from_timestamp(time, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Content of `file` and the author time of each line, from `git blame`.
+/// Lines not yet committed get `now`.
+fn file_history(file: &Path, now: i64) -> Option<FileHistory> {
+    let source = std::fs::read_to_string(file).ok()?;
+    let dir = file
+        .parent()
+        .filter(|d| !d.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let name = file.file_name()?;
+    let output = Command::new("git")
+        .args(["blame", "--line-porcelain", "--"])
+        .arg(name)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if output.status.success() {
+        let times = parse_blame(&String::from_utf8_lossy(&output.stdout), now);
+        return Some(FileHistory { source, times });
+    }
+
+    // Untracked files are new in their entirety; outside a work tree there is no history
+    let inside = Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !inside.status.success() {
+        return None;
+    }
+    let times = vec![now; source.lines().count()];
+    Some(FileHistory { source, times })
+}
+
+/// Author time of each line in `git blame --line-porcelain` output.
+pub fn parse_blame(porcelain: &str, now: i64) -> Vec<i64> {
+    let mut times = Vec::new();
+    let mut uncommitted = false;
+    let mut time = now;
+    for line in porcelain.lines() {
+        if line.starts_with('\t') {
+            times.push(if uncommitted { now } else { time });
+        } else if let Some(value) = line.strip_prefix("author-time ") {
+            time = value.trim().parse().unwrap_or(now);
+        } else if let Some((sha, _)) = line.split_once(' ') {
+            if sha.len() == 40 && sha.bytes().all(|b| b.is_ascii_hexdigit()) {
+                uncommitted = sha.bytes().all(|b| b == b'0');
+            }
+        }
+    }
+    times
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLAME: &str = "\
+1111111111111111111111111111111111111111 1 1 1
+author A
+author-time 1000000
+filename src/a.rs
+\tfn old() {}
+2222222222222222222222222222222222222222 2 2 1
+author B
+author-time 2000000
+filename src/a.rs
+\tfn new() {}
+0000000000000000000000000000000000000000 3 3 1
+author Not Committed Yet
+author-time 2500000
+filename src/a.rs
+\tfn wip() {}
+";
+
+    #[test]
+    fn test_parse_blame() {
+        assert_eq!(
+            parse_blame(BLAME, 3_000_000),
+            [1_000_000, 2_000_000, 3_000_000]
+        );
+    }
+
+    #[test]
+    fn test_take_recent() {
+        let now = 20 * SECONDS_PER_DAY;
+        let grace = GracePeriod::ending_at(14, now);
+        let file = PathBuf::from("src/a.rs");
+        let history = FileHistory {
+            source: "fn f1() {}\nconst F2: u8 = 0;\npub fn f3() {}\n".to_string(),
+            times: vec![0, 10 * SECONDS_PER_DAY, now],
+        };
+        grace
+            .blame
+            .lock()
+            .unwrap()
+            .insert(file.clone(), Some(history));
+
+        let item = |name: &str, kind, line| DeadItem {
+            id: format!("id-{}", name),
+            name: name.to_string(),
+            file: file.clone(),
+            line,
+            kind,
+            confidence: crate::Confidence::High,
+            module_path: "crate".to_string(),
+        };
+        let mut items = vec![
+            item("crate::f1", DeadItemKind::Function, 0),
+            item("F2", DeadItemKind::Constant, 0),
+            item("impl Foo", DeadItemKind::Impl, 3),
+            item("missing", DeadItemKind::Function, 0),
+        ];
+        let recent = grace.take_recent(&mut items);
+
+        let names: Vec<&str> = items.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["crate::f1", "missing"]);
+        let recent: Vec<(&str, &str)> = recent
+            .iter()
+            .map(|r| (r.item.name.as_str(), r.added.as_str()))
+            .collect();
+        assert_eq!(recent, [("F2", "1970-01-11"), ("impl Foo", "1970-01-21")]);
+    }
+
+    #[test]
+    fn test_declaration_line() {
+        let source = "enum Mode {\n    Fast,\n}\nstatic mut COUNT: u8 = 0;\nimpl<T> Wrap<T> {\n    pub(crate) fn get_all(&self) {}\n    fn get(&self) {\n        match m {\n            Mode::Fast => {}\n        }\n    }\n}\n";
+        let line = |name: &str, kind| {
+            let item = DeadItem {
+                id: String::new(),
+                name: name.to_string(),
+                file: PathBuf::new(),
+                line: 0,
+                kind,
+                confidence: crate::Confidence::High,
+                module_path: String::new(),
+            };
+            declaration_line(source, &item)
+        };
+        assert_eq!(line("Mode::Fast", DeadItemKind::EnumVariant), Some(2));
+        assert_eq!(line("COUNT", DeadItemKind::Static), Some(4));
+        assert_eq!(line("<Wrap as Get>::get", DeadItemKind::Method), Some(7));
+        assert_eq!(line("Mode::Fast", DeadItemKind::MatchArm), Some(9));
+        assert_eq!(line("T in Wrap", DeadItemKind::TypeParam), Some(5));
+        assert_eq!(line("gone", DeadItemKind::Function), None);
+    }
+}
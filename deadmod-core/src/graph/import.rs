@@ -0,0 +1,267 @@
+//! Module graphs read back from `--export-modgraph` JSON.
+//!
+//! An export carries each module's key, file, module path and references,
+//! which is all that dead module detection, metrics, cycles, layer checks
+//! and visualizations read. Importing one lets `deadmod analyze-graph` run
+//! them where the source tree is not available: a team can share the graph
+//! instead of the code.
+//!
+//! Exports record the root modules they were analyzed from under `roots`.
+//! For exports without that key, every live module is a root, which
+//! reproduces the dead modules the export recorded.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::parse::{EdgeKind, ModuleInfo, CRATE_ROOT_PATH};
+
+/// A module graph read back from an export.
+#[derive(Debug, Clone)]
+pub struct ImportedGraph {
+    /// Modules by key, with their files, module paths and references
+    pub modules: HashMap<String, ModuleInfo>,
+    /// Root modules to analyze from, sorted
+    pub roots: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ExportedGraph {
+    nodes: Vec<ExportedNode>,
+    edges: Vec<ExportedEdge>,
+    #[serde(default)]
+    roots: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct ExportedNode {
+    id: usize,
+    name: String,
+    module_path: String,
+    #[serde(rename = "crate")]
+    krate: Option<String>,
+    file: String,
+    dead: bool,
+    #[serde(default)]
+    test: bool,
+}
+
+#[derive(Deserialize)]
+struct ExportedEdge {
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    kinds: BTreeSet<EdgeKind>,
+}
+
+/// Read a graph written by `--export-modgraph` or `--modgraph-viz`.
+pub fn import_module_graph(json: &str) -> Result<ImportedGraph> {
+    let graph: ExportedGraph = serde_json::from_str(json).context("Invalid module graph JSON")?;
+    let by_id: HashMap<usize, &ExportedNode> =
+        graph.nodes.iter().map(|node| (node.id, node)).collect();
+
+    let mut modules: HashMap<String, ModuleInfo> = graph
+        .nodes
+        .iter()
+        .filter(|node| !node.test)
+        .map(|node| {
+            let mut info = ModuleInfo::new(PathBuf::from(&node.file));
+            info.module_path = local_module_path(node);
+            info.krate = node.krate.clone();
+            (node.name.clone(), info)
+        })
+        .collect();
+
+    for edge in &graph.edges {
+        let (Some(from), Some(to)) = (by_id.get(&edge.from), by_id.get(&edge.to)) else {
+            bail!(
+                "Edge {} -> {} names a node missing from the graph",
+                edge.from,
+                edge.to
+            );
+        };
+        let Some(info) = modules.get_mut(&from.name) else {
+            continue;
+        };
+        // Inline test modules are exported as nodes tied to their parent
+        if to.test {
+            if let Some(name) = to.name.strip_prefix(&format!("{}::", from.name)) {
+                info.test_mods.insert(name.to_string());
+            }
+            continue;
+        }
+        info.refs.insert(to.name.clone());
+        info.ref_kinds
+            .entry(to.name.clone())
+            .or_default()
+            .extend(&edge.kinds);
+    }
+
+    let mut roots = graph.roots.unwrap_or_else(|| {
+        graph
+            .nodes
+            .iter()
+            .filter(|node| !node.test && !node.dead)
+            .map(|node| node.name.clone())
+            .collect()
+    });
+    roots.sort();
+    Ok(ImportedGraph { modules, roots })
+}
+
+/// `ModuleInfo::module_path` of a node: its reported module path without
+/// the crate prefix workspace exports add.
+fn local_module_path(node: &ExportedNode) -> String {
+    match node.krate {
+        Some(ref krate) if node.module_path == *krate => CRATE_ROOT_PATH.to_string(),
+        Some(ref krate) => node
+            .module_path
+            .strip_prefix(&format!("{}::", krate))
+            .unwrap_or(&node.module_path)
+            .to_string(),
+        None => node.module_path.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::metrics::module_metrics;
+    use crate::graph::{build_graph, module_graph_to_visualizer_json, reachable_from_roots};
+    use crate::Deadmod;
+    use std::collections::HashSet;
+
+    fn exported(roots: Option<&[&str]>) -> (HashMap<String, ModuleInfo>, String) {
+        let mods = Deadmod::from_sources([
+            ("src/main.rs", "mod net;\nmod util;\nfn main() { net::connect(); }\n#[cfg(test)]\nmod tests {}"),
+            ("src/net.rs", "pub fn connect() {}"),
+            ("src/util.rs", "pub fn pad() {}"),
+            ("src/legacy.rs", "use crate::net;"),
+        ]
+        .map(|(file, src)| (PathBuf::from(file), src.to_string())))
+        .analyze()
+        .unwrap()
+        .modules;
+
+        let graph = build_graph(&mods);
+        let reachable = reachable_from_roots(&graph, ["main"]);
+        let mut json = module_graph_to_visualizer_json(&mods, &reachable);
+        if let Some(roots) = roots {
+            json["roots"] = serde_json::json!(roots);
+        }
+        (mods, json.to_string())
+    }
+
+    fn dead(modules: &HashMap<String, ModuleInfo>, roots: &[String]) -> Vec<String> {
+        let graph = build_graph(modules);
+        let reachable = reachable_from_roots(&graph, roots.iter().map(String::as_str));
+        let mut dead: Vec<String> = modules
+            .keys()
+            .filter(|m| !reachable.contains(m.as_str()))
+            .cloned()
+            .collect();
+        dead.sort();
+        dead
+    }
+
+    #[test]
+    fn test_import_module_graph_round_trip() {
+        let (mods, json) = exported(Some(&["main"]));
+        let imported = import_module_graph(&json).unwrap();
+
+        assert_eq!(imported.roots, vec!["main".to_string()]);
+        assert_eq!(
+            imported.modules.keys().collect::<HashSet<_>>(),
+            mods.keys().collect::<HashSet<_>>()
+        );
+        let main = &imported.modules["main"];
+        assert_eq!(
+            main.refs,
+            HashSet::from(["net".to_string(), "util".to_string()])
+        );
+        assert_eq!(
+            main.ref_kinds["net"],
+            BTreeSet::from([EdgeKind::Mod, EdgeKind::Path])
+        );
+        assert_eq!(main.test_mods, BTreeSet::from(["tests".to_string()]));
+        assert_eq!(main.module_path, mods["main"].module_path);
+        assert_eq!(
+            dead(&imported.modules, &imported.roots),
+            vec!["legacy".to_string()]
+        );
+
+        // Re-exporting the import gives the same graph
+        let graph = build_graph(&imported.modules);
+        let reachable = reachable_from_roots(&graph, ["main"]);
+        let again = module_graph_to_visualizer_json(&imported.modules, &reachable);
+        let original: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(again["nodes"], original["nodes"]);
+        assert_eq!(again["stats"], original["stats"]);
+    }
+
+    #[test]
+    fn test_import_module_graph_without_roots() {
+        let (_, json) = exported(None);
+        let imported = import_module_graph(&json).unwrap();
+
+        assert_eq!(
+            imported.roots,
+            vec!["main".to_string(), "net".to_string(), "util".to_string()]
+        );
+        assert_eq!(
+            dead(&imported.modules, &imported.roots),
+            vec!["legacy".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_same_stem_modules_are_not_crates() {
+        let mods = Deadmod::from_sources(
+            [
+                ("src/lib.rs", "pub mod a;\npub mod b;"),
+                ("src/a/mod.rs", "pub mod utils;"),
+                ("src/a/utils.rs", ""),
+                ("src/b/mod.rs", "pub mod utils;"),
+                ("src/b/utils.rs", ""),
+            ]
+            .map(|(file, src)| (PathBuf::from(file), src.to_string())),
+        )
+        .analyze()
+        .unwrap()
+        .modules;
+        let reachable = reachable_from_roots(&build_graph(&mods), ["lib"]);
+        let json = module_graph_to_visualizer_json(&mods, &reachable);
+        let node = json["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|node| node["name"] == "a::utils")
+            .unwrap();
+        assert_eq!(node["crate"], serde_json::Value::Null);
+        assert_eq!(node["module_path"], "a::utils");
+
+        // analyze-graph sees the same module paths and nesting as metrics
+        let imported = import_module_graph(&json.to_string()).unwrap();
+        let utils = &imported.modules["a::utils"];
+        assert_eq!(utils.module_path, "a::utils");
+        assert_eq!(utils.krate, None);
+        assert_eq!(
+            module_metrics(&imported.modules)
+                .get("a::utils")
+                .unwrap()
+                .nesting,
+            module_metrics(&mods).get("a::utils").unwrap().nesting
+        );
+    }
+
+    #[test]
+    fn test_import_module_graph_rejects_bad_input() {
+        assert!(import_module_graph("{}").is_err());
+        let dangling = r#"{"nodes": [{"id": 0, "name": "main", "module_path": "crate", "crate": null,
+            "file": "src/main.rs", "dead": false}], "edges": [{"from": 0, "to": 7}]}"#;
+        let err = import_module_graph(dangling).unwrap_err();
+        assert!(err.to_string().contains("0 -> 7"), "{}", err);
+    }
+}
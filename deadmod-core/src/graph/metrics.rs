@@ -0,0 +1,230 @@
+//! Architecture metrics of the module dependency graph.
+//!
+//! For each module, from the same graph reachability runs on:
+//! - **Fan-in** (afferent coupling, Ca): modules that depend on it
+//! - **Fan-out** (efferent coupling, Ce): modules it depends on
+//! - **Instability**: Ce / (Ca + Ce), from 0 (everything depends on it,
+//!   hard to change) to 1 (depends on others, nothing depends on it)
+//! - **Nesting**: module path segments below the crate root (`crate` 0,
+//!   `net::tcp` 2); not the distance from an entry point
+//! - **Cycle**: the dependency cycle it is part of, if any
+//!
+//! Self-references are not counted as coupling (but are cycles, as in
+//! [`find_cycles`]).
+
+use std::collections::{BTreeMap, HashMap};
+
+use petgraph::graphmap::DiGraphMap;
+use petgraph::Direction;
+use serde::Serialize;
+
+use super::{build_graph, find_cycles};
+use crate::parse::{ModuleInfo, CRATE_ROOT_PATH};
+
+/// Coupling metrics of one module.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModuleMetrics {
+    /// Afferent coupling: modules depending on this one
+    pub fan_in: usize,
+    /// Efferent coupling: modules this one depends on
+    pub fan_out: usize,
+    /// `fan_out / (fan_in + fan_out)`; 0 for a module with no coupling
+    pub instability: f64,
+    /// Module path segments below the crate root
+    pub nesting: usize,
+    /// Index into [`GraphMetrics::cycles`] of the cycle containing the module
+    pub cycle: Option<usize>,
+}
+
+/// Metrics of every module, with the cycles they refer to.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GraphMetrics {
+    /// Metrics by module key
+    pub modules: BTreeMap<String, ModuleMetrics>,
+    /// Dependency cycles, as sorted module keys (see [`find_cycles`])
+    pub cycles: Vec<Vec<String>>,
+}
+
+impl GraphMetrics {
+    /// Metrics of the modules in `g`; `nesting` gives each module's
+    /// [`module_nesting`] (0 for modules it does not list).
+    pub fn compute(g: &DiGraphMap<&str, ()>, nesting: &HashMap<&str, usize>) -> Self {
+        let cycles: Vec<Vec<String>> = find_cycles(g)
+            .into_iter()
+            .map(|scc| scc.into_iter().map(String::from).collect())
+            .collect();
+        let cycle_of: HashMap<&str, usize> = cycles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, cycle)| cycle.iter().map(move |m| (m.as_str(), i)))
+            .collect();
+
+        let modules = g
+            .nodes()
+            .map(|node| {
+                let coupled = |dir| {
+                    g.neighbors_directed(node, dir)
+                        .filter(|&n| n != node)
+                        .count()
+                };
+                let fan_in = coupled(Direction::Incoming);
+                let fan_out = coupled(Direction::Outgoing);
+                let instability = if fan_in + fan_out == 0 {
+                    0.0
+                } else {
+                    fan_out as f64 / (fan_in + fan_out) as f64
+                };
+                let metrics = ModuleMetrics {
+                    fan_in,
+                    fan_out,
+                    instability,
+                    nesting: nesting.get(node).copied().unwrap_or(0),
+                    cycle: cycle_of.get(node).copied(),
+                };
+                (node.to_string(), metrics)
+            })
+            .collect();
+
+        Self { modules, cycles }
+    }
+
+    /// Metrics of the module keyed `name`.
+    pub fn get(&self, name: &str) -> Option<&ModuleMetrics> {
+        self.modules.get(name)
+    }
+
+    /// Deepest module nesting.
+    pub fn max_nesting(&self) -> usize {
+        self.modules.values().map(|m| m.nesting).max().unwrap_or(0)
+    }
+
+    /// Mean instability over modules with any coupling.
+    pub fn mean_instability(&self) -> f64 {
+        let coupled: Vec<f64> = self
+            .modules
+            .values()
+            .filter(|m| m.fan_in + m.fan_out > 0)
+            .map(|m| m.instability)
+            .collect();
+        if coupled.is_empty() {
+            0.0
+        } else {
+            coupled.iter().sum::<f64>() / coupled.len() as f64
+        }
+    }
+}
+
+/// Number of segments of `module_path` below the crate root: how deeply the
+/// module is nested in the `mod` tree, whatever path reaches it.
+pub fn module_nesting(module_path: &str) -> usize {
+    if module_path == CRATE_ROOT_PATH {
+        0
+    } else {
+        module_path.split("::").count()
+    }
+}
+
+/// Metrics of every module in `mods`.
+pub fn module_metrics(mods: &HashMap<String, ModuleInfo>) -> GraphMetrics {
+    let graph = build_graph(mods);
+    let nesting: HashMap<&str, usize> = mods
+        .iter()
+        .map(|(name, info)| (name.as_str(), module_nesting(&info.module_path)))
+        .collect();
+    GraphMetrics::compute(&graph, &nesting)
+}
+
+/// Formats metrics as a plain-text table, most depended-on modules first.
+pub fn format_metrics_plain(metrics: &GraphMetrics) -> String {
+    if metrics.modules.is_empty() {
+        return "No modules found.\n".to_string();
+    }
+
+    let mut rows: Vec<(&String, &ModuleMetrics)> = metrics.modules.iter().collect();
+    rows.sort_by(|a, b| b.1.fan_in.cmp(&a.1.fan_in).then_with(|| a.0.cmp(b.0)));
+    let width = rows
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0)
+        .max("MODULE".len());
+
+    let mut out = format!(
+        "{:<width$}  {:>6}  {:>7}  {:>11}  {:>7}  CYCLE\n",
+        "MODULE", "FAN-IN", "FAN-OUT", "INSTABILITY", "NESTING"
+    );
+    for (name, m) in rows {
+        let cycle = m
+            .cycle
+            .map_or_else(|| "-".to_string(), |i| format!("#{}", i + 1));
+        out.push_str(&format!(
+            "{:<width$}  {:>6}  {:>7}  {:>11.2}  {:>7}  {}\n",
+            name, m.fan_in, m.fan_out, m.instability, m.nesting, cycle
+        ));
+    }
+
+    out.push_str(&format!(
+        "\n{} modules, max nesting {}, mean instability {:.2}, {} cycle(s)\n",
+        metrics.modules.len(),
+        metrics.max_nesting(),
+        metrics.mean_instability(),
+        metrics.cycles.len()
+    ));
+    for (i, cycle) in metrics.cycles.iter().enumerate() {
+        out.push_str(&format!("  #{}: {}\n", i + 1, cycle.join(" <-> ")));
+    }
+    out
+}
+
+/// Formats metrics in JSON format.
+pub fn format_metrics_json(metrics: &GraphMetrics) -> String {
+    serde_json::to_string_pretty(metrics).unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+
+    fn module(module_path: &str, refs: &[&str]) -> ModuleInfo {
+        let mut info = ModuleInfo::new(PathBuf::from(format!("src/{}.rs", module_path)));
+        info.module_path = module_path.to_string();
+        info.refs = refs.iter().map(|r| r.to_string()).collect::<HashSet<_>>();
+        info
+    }
+
+    #[test]
+    fn test_module_metrics() {
+        let mods: HashMap<String, ModuleInfo> = [
+            ("lib", module("crate", &["net", "util"])),
+            ("net", module("net", &["net_tcp", "util"])),
+            ("net_tcp", module("net::tcp", &["net"])),
+            ("util", module("util", &[])),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+
+        let metrics = module_metrics(&mods);
+        let lib = metrics.get("lib").unwrap();
+        assert_eq!(
+            (lib.fan_in, lib.fan_out, lib.instability, lib.nesting),
+            (0, 2, 1.0, 0)
+        );
+        let util = metrics.get("util").unwrap();
+        assert_eq!((util.fan_in, util.fan_out, util.instability), (2, 0, 0.0));
+        let net = metrics.get("net").unwrap();
+        assert_eq!((net.fan_in, net.fan_out), (2, 2));
+        assert_eq!(metrics.get("net_tcp").unwrap().nesting, 2);
+
+        assert_eq!(metrics.cycles, [["net", "net_tcp"]]);
+        assert_eq!(net.cycle, Some(0));
+        assert_eq!(lib.cycle, None);
+        assert_eq!(metrics.max_nesting(), 2);
+
+        let plain = format_metrics_plain(&metrics);
+        assert!(plain.lines().nth(1).unwrap().starts_with("net "));
+        assert!(plain.contains("#1: net <-> net_tcp"));
+    }
+}
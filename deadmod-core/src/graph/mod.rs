@@ -0,0 +1,951 @@
+//! Graph construction and reachability analysis using BFS.
+//!
+//! Performance characteristics:
+//! - Graph build: O(|V| + |E|) where V = modules, E = dependencies
+//! - Multi-source reachability: O(|V| + |E|) single traversal
+//!
+//! The multi-source BFS pattern eliminates redundant traversals when
+//! analyzing from multiple entry points (main, lib, binaries).
+//!
+//! Cycle detection uses Tarjan's strongly connected components: O(|V| + |E|).
+//!
+//! Edges respect module privacy: a reference only becomes an edge if every
+//! module on the target's parent chain is visible from the referrer (see
+//! [`ModuleVisibility`]).
+//!
+//! Coupling metrics over the same graph live in [`metrics`]; graphs read
+//! back from exports in [`import`].
+
+pub mod import;
+pub mod metrics;
+
+use crate::common::GraphTraversal;
+use crate::parse::{ModuleInfo, Visibility, CRATE_ROOT_PATH};
+use crate::report::join_module_path;
+use anyhow::{bail, Result};
+use petgraph::algo::tarjan_scc;
+use petgraph::graphmap::{DiGraphMap, NodeTrait, UnGraphMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Builds the dependency graph (DiGraphMap) from module information.
+///
+/// References to modules hidden from the referrer by a private or
+/// `pub(super)` ancestor are not edges: the name cannot resolve to them.
+///
+/// Uses `DiGraphMap<&str, ()>` for memory efficiency:
+/// - String slices avoid ownership/cloning overhead
+/// - Unit type `()` for edges minimizes memory footprint
+pub fn build_graph(mods: &HashMap<String, ModuleInfo>) -> DiGraphMap<&str, ()> {
+    let mut g = DiGraphMap::new();
+    let visibility = ModuleVisibility::new(mods);
+
+    // 1. Add all nodes
+    for name in mods.keys() {
+        g.add_node(name.as_str());
+    }
+
+    // 2. Add all edges (dependencies)
+    for (name, info) in mods {
+        for dep in &info.refs {
+            if mods.contains_key(dep) && visibility.permits(name, dep) {
+                g.add_edge(name.as_str(), dep.as_str(), ());
+            }
+        }
+    }
+
+    g
+}
+
+/// Module privacy along declaration chains.
+///
+/// A module declared with `mod x;` is visible inside its parent only,
+/// `pub(super) mod x;` inside the grandparent, and `pub`/`pub(crate)`/
+/// `pub(in ..)` crate-wide. A module is visible from a referrer only if the
+/// whole chain up to the crate root is: `pub mod api;` inside a private
+/// `mod inner;` is still hidden outside `inner`'s parent. Modules whose
+/// declaration was not seen (binaries, `#[path]` mods, older caches) are
+/// treated as visible.
+pub struct ModuleVisibility<'a> {
+    mods: &'a HashMap<String, ModuleInfo>,
+    by_path: HashMap<String, &'a ModuleInfo>,
+}
+
+impl<'a> ModuleVisibility<'a> {
+    /// Index modules by their qualified module path.
+    pub fn new(mods: &'a HashMap<String, ModuleInfo>) -> Self {
+        let by_path = mods
+            .iter()
+            .map(|(name, info)| (qualified_module_path(name, info), info))
+            .collect();
+        Self { mods, by_path }
+    }
+
+    /// Whether module `to` is visible from module `from` (both map keys).
+    pub fn permits(&self, from: &str, to: &str) -> bool {
+        let (Some(from_info), Some(to_info)) = (self.mods.get(from), self.mods.get(to)) else {
+            return true;
+        };
+        let from_path = qualified_module_path(from, from_info);
+        let mut current = qualified_module_path(to, to_info);
+
+        while let Some((parent, child)) = split_parent(&current) {
+            let Some(parent_info) = self.by_path.get(parent) else {
+                break;
+            };
+            let scope = match parent_info.mod_decls.get(child) {
+                Some(Visibility::Private) => Some(parent),
+                Some(Visibility::PubSuper) => {
+                    Some(split_parent(parent).map_or(CRATE_ROOT_PATH, |(p, _)| p))
+                }
+                _ => None,
+            };
+            if scope.is_some_and(|scope| !is_within(&from_path, scope)) {
+                return false;
+            }
+            current = parent.to_string();
+        }
+        true
+    }
+}
+
+/// `(parent, last segment)` of a module path; `None` for the crate root.
+fn split_parent(path: &str) -> Option<(&str, &str)> {
+    if path == CRATE_ROOT_PATH {
+        return None;
+    }
+    Some(path.rsplit_once("::").unwrap_or((CRATE_ROOT_PATH, path)))
+}
+
+/// Whether module path `path` is `scope` or nested inside it.
+fn is_within(path: &str, scope: &str) -> bool {
+    scope == CRATE_ROOT_PATH
+        || path == scope
+        || path
+            .strip_prefix(scope)
+            .is_some_and(|rest| rest.starts_with("::"))
+}
+
+/// Performs Multi-Source BFS to find all modules reachable from a set of roots.
+///
+/// This is the optimal approach for finding reachability from multiple entry points:
+/// - Complexity: O(|V| + |E|) regardless of number of roots
+/// - Eliminates redundant traversals compared to calling single-source BFS N times
+///
+/// # Arguments
+/// * `g` - The dependency graph
+/// * `roots` - Iterator of root module names (entry points)
+///
+/// # Returns
+/// Set of all module names reachable from any root
+///
+/// # Logging
+/// Logs a warning for any root not found in the graph (helpful for debugging).
+pub fn reachable_from_roots<'a>(
+    g: &DiGraphMap<&'a str, ()>,
+    roots: impl IntoIterator<Item = &'a str>,
+) -> HashSet<&'a str> {
+    let roots = roots.into_iter().filter(|&root| {
+        // Log warning for missing roots (helpful for debugging configuration issues)
+        let found = g.contains_node(root);
+        if !found {
+            eprintln!("[WARN] Root module not found in graph: '{}'", root);
+        }
+        found
+    });
+
+    // Single multi-source BFS shared with the call graph
+    GraphTraversal::reachable_from(g, roots)
+}
+
+impl<N: NodeTrait> GraphTraversal for DiGraphMap<N, ()> {
+    type Node = N;
+
+    fn neighbors(&self, node: &N) -> Vec<N> {
+        DiGraphMap::neighbors(self, *node).collect()
+    }
+
+    fn contains_node(&self, node: &N) -> bool {
+        DiGraphMap::contains_node(self, *node)
+    }
+}
+
+/// Performs BFS to find all modules reachable from a single root.
+///
+/// Preserved for backwards compatibility. Internally delegates to `reachable_from_roots`.
+///
+/// For multiple roots, prefer `reachable_from_roots` directly to avoid
+/// redundant O(|V| + |E|) traversals.
+/// Per-target reachability: which targets (roots) reach which items.
+///
+/// Merging all roots hides code used by only some binaries; this keeps one
+/// reachable set per target so such code can be told apart from code that no
+/// target uses.
+#[derive(Debug, Clone, Default)]
+pub struct ReachabilityMatrix {
+    /// Target name → items it reaches
+    pub targets: BTreeMap<String, HashSet<String>>,
+}
+
+impl ReachabilityMatrix {
+    /// Build from one reachable set per target.
+    pub fn from_sets(sets: impl IntoIterator<Item = (String, HashSet<String>)>) -> Self {
+        Self {
+            targets: sets.into_iter().collect(),
+        }
+    }
+
+    /// Targets that reach `item`, in name order.
+    pub fn targets_reaching(&self, item: &str) -> Vec<&str> {
+        self.targets
+            .iter()
+            .filter(|(_, reached)| reached.contains(item))
+            .map(|(target, _)| target.as_str())
+            .collect()
+    }
+
+    /// Items reached by no target, sorted.
+    pub fn unreached<'a>(&self, items: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let mut out: Vec<String> = items
+            .into_iter()
+            .filter(|item| {
+                self.targets
+                    .values()
+                    .all(|reached| !reached.contains(*item))
+            })
+            .map(String::from)
+            .collect();
+        out.sort();
+        out
+    }
+
+    /// Items reached by some but not all targets, with the targets reaching them.
+    pub fn partially_reached<'a>(
+        &self,
+        items: impl IntoIterator<Item = &'a str>,
+    ) -> BTreeMap<String, Vec<String>> {
+        items
+            .into_iter()
+            .filter_map(|item| {
+                let reaching = self.targets_reaching(item);
+                (!reaching.is_empty() && reaching.len() < self.targets.len()).then(|| {
+                    (
+                        item.to_string(),
+                        reaching.into_iter().map(String::from).collect(),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Module reachability computed separately from each root.
+pub fn module_reachability_matrix<'a>(
+    g: &DiGraphMap<&'a str, ()>,
+    roots: impl IntoIterator<Item = &'a str>,
+) -> ReachabilityMatrix {
+    ReachabilityMatrix::from_sets(
+        roots
+            .into_iter()
+            .filter(|r| g.contains_node(r))
+            .map(|root| {
+                let reached = reachable_from_root(g, root)
+                    .into_iter()
+                    .map(String::from)
+                    .collect();
+                (root.to_string(), reached)
+            }),
+    )
+}
+
+pub fn reachable_from_root<'a>(g: &DiGraphMap<&'a str, ()>, root: &'a str) -> HashSet<&'a str> {
+    reachable_from_roots(g, std::iter::once(root))
+}
+
+/// Finds dependency cycles as strongly connected components.
+///
+/// Returns every SCC with more than one module, plus single modules that
+/// depend on themselves. Members are sorted within each cycle, and cycles
+/// are sorted by their first member, for deterministic output.
+pub fn find_cycles<'a>(g: &DiGraphMap<&'a str, ()>) -> Vec<Vec<&'a str>> {
+    let mut cycles: Vec<Vec<&'a str>> = tarjan_scc(g)
+        .into_iter()
+        .filter(|scc| scc.len() > 1 || g.contains_edge(scc[0], scc[0]))
+        .map(|mut scc| {
+            scc.sort_unstable();
+            scc
+        })
+        .collect();
+
+    cycles.sort();
+    cycles
+}
+
+/// Nodes within `depth` hops of `focus` along `edges`, followed in either
+/// direction (what `focus` uses and what uses it), `focus` included.
+///
+/// Works on module and call graph edges alike; used to export one node's
+/// neighborhood instead of a whole graph (`--focus`, `--depth`).
+pub fn subgraph<'a>(
+    edges: impl IntoIterator<Item = (&'a str, &'a str)>,
+    focus: &'a str,
+    depth: usize,
+) -> HashSet<&'a str> {
+    let neighbors: UnGraphMap<&str, ()> = UnGraphMap::from_edges(edges);
+
+    let mut seen = HashSet::from([focus]);
+    let mut frontier = vec![focus];
+    for _ in 0..depth {
+        frontier = frontier
+            .iter()
+            .flat_map(|&node| neighbors.neighbors(node))
+            .filter(|node| seen.insert(node))
+            .collect();
+        if frontier.is_empty() {
+            break;
+        }
+    }
+    seen
+}
+
+/// The modules of `mods` within `depth` dependency hops of the module
+/// `focus` (see [`subgraph`]).
+///
+/// `focus` is a module key or module path (`net::tcp`, `crate::net::tcp`).
+/// Fails when it names no module.
+pub fn focus_modules(
+    mods: &HashMap<String, ModuleInfo>,
+    focus: &str,
+    depth: usize,
+) -> Result<HashMap<String, ModuleInfo>> {
+    let path = focus.strip_prefix("crate::").unwrap_or(focus);
+    let Some(key) = mods
+        .iter()
+        .find(|(key, info)| *key == focus || qualified_module_path(key, info) == path)
+        .map(|(key, _)| key.as_str())
+    else {
+        bail!("No module '{}' to focus on", focus);
+    };
+
+    let graph = build_graph(mods);
+    let keep = subgraph(
+        graph.all_edges().map(|(from, to, _)| (from, to)),
+        key,
+        depth,
+    );
+    Ok(mods
+        .iter()
+        .filter(|(name, _)| keep.contains(name.as_str()))
+        .map(|(name, info)| (name.clone(), info.clone()))
+        .collect())
+}
+
+/// Workspace member of a module combined into a workspace graph.
+///
+/// Workspace smart mode prefixes every module key with its crate directory
+/// name and records the crate on the module. Single-crate modules return
+/// `None`, even when keyed by a module path (`net::utils`) to tell colliding
+/// files apart.
+pub fn module_crate(info: &ModuleInfo) -> Option<&str> {
+    info.krate.as_deref()
+}
+
+/// Reported module path of the module keyed `name`: `info.module_path`,
+/// prefixed with the crate for workspace-combined names (`crate_a::net`,
+/// or just `crate_a` for its root).
+pub fn qualified_module_path(name: &str, info: &ModuleInfo) -> String {
+    if name == info.module_path {
+        return name.to_string();
+    }
+    match module_crate(info) {
+        Some(krate) if info.module_path == CRATE_ROOT_PATH => krate.to_string(),
+        Some(krate) => format!("{}::{}", krate, info.module_path),
+        None => info.module_path.clone(),
+    }
+}
+
+/// An inline `#[cfg(test)]` module, tied to the file declaring it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineTestModule<'a> {
+    /// Graph key: the parent's key and the module name (`net::tests`,
+    /// `lib::tests`), distinct from `tests/` targets (`tests::it`)
+    pub key: String,
+    /// Key of the declaring module
+    pub parent: &'a str,
+    /// Reported module path (`net::tests`, `crate::tests`)
+    pub module_path: String,
+}
+
+/// Inline test modules of every module, sorted by key. They are graph nodes
+/// for exports only and are never reported as dead modules.
+pub fn inline_test_modules(mods: &HashMap<String, ModuleInfo>) -> Vec<InlineTestModule<'_>> {
+    let mut tests: Vec<InlineTestModule> = mods
+        .iter()
+        .flat_map(|(parent, info)| {
+            info.test_mods.iter().map(move |name| InlineTestModule {
+                key: format!("{}::{}", parent, name),
+                parent: parent.as_str(),
+                module_path: join_module_path(&qualified_module_path(parent, info), name),
+            })
+        })
+        .collect();
+    tests.sort_by(|a, b| a.key.cmp(&b.key));
+    tests
+}
+
+/// Export module dependency graph in visualizer-compatible JSON format.
+///
+/// Output format for PixiJS visualizer:
+/// ```json
+/// {
+///   "nodes": [{ "id": 0, "name": "module_name", "module_path": "net::tcp", "crate": null, "dead": false, "test": false,
+///               "metrics": { "fan_in": 2, "fan_out": 1, "instability": 0.33, "nesting": 2, "cycle": null } }],
+///   "edges": [{ "from": 0, "to": 1, "cross_crate": false, "kinds": ["mod", "use"] }],
+///   "cycles": [["net", "net_tcp"]]
+/// }
+/// ```
+///
+/// `crate` is set for workspace-combined names (see [`module_crate`]).
+/// `kinds` lists every way the source module references the target
+/// ([`crate::parse::EdgeKind`]), including kinds not selected as edges.
+/// Inline `#[cfg(test)]` modules (see [`inline_test_modules`]) follow the
+/// file modules as `"test": true` nodes, each with a `mod` edge from its
+/// parent; they are never dead. `metrics` (see [`metrics::ModuleMetrics`])
+/// is set on file modules; its `cycle` indexes the top-level `cycles`.
+pub fn module_graph_to_visualizer_json(
+    mods: &HashMap<String, ModuleInfo>,
+    reachable: &HashSet<&str>,
+) -> serde_json::Value {
+    // Build name -> numeric ID mapping (sorted for deterministic output)
+    let mut names: Vec<&String> = mods.keys().collect();
+    names.sort();
+    let name_to_id: HashMap<&String, usize> =
+        names.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+    let metrics = metrics::module_metrics(mods);
+
+    // Build nodes with dead status
+    let mut nodes: Vec<serde_json::Value> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let info = &mods[*name];
+            let is_dead = !reachable.contains(name.as_str());
+            serde_json::json!({
+                "id": i,
+                "name": name,
+                "module_path": qualified_module_path(name, info),
+                "crate": module_crate(info),
+                "file": info.path.display().to_string(),
+                "dead": is_dead,
+                "test": false,
+                "metrics": metrics.get(name),
+            })
+        })
+        .collect();
+
+    // Build edges using numeric IDs
+    let visibility = ModuleVisibility::new(mods);
+    let mut edges: Vec<serde_json::Value> = Vec::new();
+    for (name, info) in mods {
+        if let Some(&from_id) = name_to_id.get(name) {
+            for dep in &info.refs {
+                if !visibility.permits(name, dep) {
+                    continue;
+                }
+                if let Some(&to_id) = name_to_id.get(dep) {
+                    edges.push(serde_json::json!({
+                        "from": from_id,
+                        "to": to_id,
+                        "cross_crate": module_crate(info) != module_crate(&mods[dep]),
+                        "kinds": info.ref_kinds.get(dep).cloned().unwrap_or_default(),
+                    }));
+                }
+            }
+        }
+    }
+
+    // Inline test modules, tied to their parent file
+    let test_modules = inline_test_modules(mods);
+    for test in &test_modules {
+        let id = nodes.len();
+        let parent = &mods[test.parent];
+        nodes.push(serde_json::json!({
+            "id": id,
+            "name": test.key,
+            "module_path": test.module_path,
+            "crate": module_crate(parent),
+            "file": parent.path.display().to_string(),
+            "dead": false,
+            "test": true,
+        }));
+        edges.push(serde_json::json!({
+            "from": name_to_id[&test.parent.to_string()],
+            "to": id,
+            "cross_crate": false,
+            "kinds": ["mod"],
+        }));
+    }
+
+    // Count dead modules
+    let dead_count = nodes
+        .iter()
+        .filter(|n| n["dead"].as_bool().unwrap_or(false))
+        .count();
+
+    serde_json::json!({
+        "nodes": nodes,
+        "edges": edges,
+        "cycles": metrics.cycles,
+        "stats": {
+            "total_modules": mods.len(),
+            "total_edges": edges.len(),
+            "dead_modules": dead_count,
+            "test_modules": test_modules.len(),
+            "cycles": metrics.cycles.len(),
+            "max_nesting": metrics.max_nesting(),
+            "mean_instability": metrics.mean_instability(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::EdgeKind;
+    use std::path::PathBuf;
+
+    fn create_module(name: &str, refs: &[&str]) -> (String, ModuleInfo) {
+        let mut info = ModuleInfo::new(PathBuf::from(format!("src/{}.rs", name)));
+        for r in refs {
+            info.refs.insert(r.to_string());
+        }
+        (name.to_string(), info)
+    }
+
+    #[test]
+    fn test_module_reachability_matrix() {
+        let mods: HashMap<String, ModuleInfo> = [
+            create_module("main", &["shared", "net"]),
+            create_module("tool", &["shared", "render"]),
+            create_module("shared", &[]),
+            create_module("net", &[]),
+            create_module("render", &[]),
+            create_module("orphan", &[]),
+        ]
+        .into_iter()
+        .collect();
+
+        let g = build_graph(&mods);
+        let matrix = module_reachability_matrix(&g, ["main", "tool", "missing"]);
+        assert_eq!(matrix.targets.len(), 2);
+        assert_eq!(matrix.targets_reaching("shared"), vec!["main", "tool"]);
+
+        let names = mods.keys().map(String::as_str);
+        assert_eq!(matrix.unreached(names.clone()), vec!["orphan".to_string()]);
+
+        let partial = matrix.partially_reached(names);
+        assert_eq!(partial["net"], vec!["main".to_string()]);
+        assert_eq!(partial["tool"], vec!["tool".to_string()]);
+        assert!(!partial.contains_key("shared"));
+        assert!(!partial.contains_key("orphan"));
+    }
+
+    #[test]
+    fn test_find_cycles() {
+        let mods: HashMap<String, ModuleInfo> = [
+            create_module("main", &["a", "solo"]),
+            create_module("a", &["b"]),
+            create_module("b", &["c"]),
+            create_module("c", &["a"]),
+            create_module("solo", &["solo"]),
+            create_module("leaf", &[]),
+        ]
+        .into_iter()
+        .collect();
+
+        let g = build_graph(&mods);
+        let cycles = find_cycles(&g);
+
+        assert_eq!(cycles, vec![vec!["a", "b", "c"], vec!["solo"]]);
+    }
+
+    #[test]
+    fn test_find_cycles_acyclic() {
+        let mods: HashMap<String, ModuleInfo> =
+            [create_module("main", &["a"]), create_module("a", &[])]
+                .into_iter()
+                .collect();
+
+        assert!(find_cycles(&build_graph(&mods)).is_empty());
+    }
+
+    #[test]
+    fn test_subgraph() {
+        let edges = [
+            ("main", "net"),
+            ("net", "tcp"),
+            ("tcp", "buf"),
+            ("cli", "net"),
+            ("log", "log"),
+        ];
+        fn sorted(set: HashSet<&str>) -> Vec<&str> {
+            let mut nodes: Vec<&str> = set.into_iter().collect();
+            nodes.sort_unstable();
+            nodes
+        }
+
+        assert_eq!(sorted(subgraph(edges, "net", 0)), ["net"]);
+        // Callers and callees alike
+        assert_eq!(
+            sorted(subgraph(edges, "net", 1)),
+            ["cli", "main", "net", "tcp"]
+        );
+        assert_eq!(
+            sorted(subgraph(edges, "net", 2)),
+            ["buf", "cli", "main", "net", "tcp"]
+        );
+        assert_eq!(sorted(subgraph(edges, "missing", 3)), ["missing"]);
+    }
+
+    #[test]
+    fn test_focus_modules() {
+        let mut mods: HashMap<String, ModuleInfo> = [
+            create_module("main", &["net"]),
+            create_module("net", &["tcp"]),
+            create_module("tcp", &[]),
+            create_module("orphan", &[]),
+        ]
+        .into_iter()
+        .collect();
+        mods.get_mut("net").unwrap().module_path = "net".to_string();
+        mods.get_mut("tcp").unwrap().module_path = "net::tcp".to_string();
+
+        let mut focused: Vec<String> = focus_modules(&mods, "crate::net::tcp", 1)
+            .unwrap()
+            .into_keys()
+            .collect();
+        focused.sort();
+        assert_eq!(focused, ["net", "tcp"]);
+        assert_eq!(focus_modules(&mods, "net", 5).unwrap().len(), 3);
+        assert!(focus_modules(&mods, "missing", 1).is_err());
+    }
+
+    #[test]
+    fn test_build_graph_basic() {
+        let mut mods = HashMap::new();
+        mods.insert(
+            "main".to_string(),
+            ModuleInfo::new(PathBuf::from("src/main.rs")),
+        );
+        mods.insert(
+            "utils".to_string(),
+            ModuleInfo::new(PathBuf::from("src/utils.rs")),
+        );
+
+        let g = build_graph(&mods);
+        assert!(g.contains_node("main"));
+        assert!(g.contains_node("utils"));
+    }
+
+    #[test]
+    fn test_reachable_from_root_simple() {
+        let mut mods = HashMap::new();
+        let (name, mut info) = create_module("main", &[]);
+        info.refs.insert("utils".to_string());
+        mods.insert(name, info);
+        mods.insert(create_module("utils", &[]).0, create_module("utils", &[]).1);
+        mods.insert(create_module("dead", &[]).0, create_module("dead", &[]).1);
+
+        let g = build_graph(&mods);
+        let reachable = reachable_from_root(&g, "main");
+
+        assert!(reachable.contains("main"));
+        assert!(reachable.contains("utils"));
+        assert!(!reachable.contains("dead"));
+    }
+
+    #[test]
+    fn test_build_graph_respects_module_privacy() {
+        let module = |path: &str, refs: &[&str], decls: &[(&str, Visibility)]| {
+            let mut info = ModuleInfo::new(PathBuf::from(path));
+            info.refs.extend(refs.iter().map(|r| r.to_string()));
+            info.mod_decls
+                .extend(decls.iter().map(|&(n, v)| (n.to_string(), v)));
+            info
+        };
+        let mods: HashMap<String, ModuleInfo> = [
+            (
+                "lib",
+                module(
+                    "src/lib.rs",
+                    &["outer", "other"],
+                    &[
+                        ("outer", Visibility::Private),
+                        ("other", Visibility::Private),
+                    ],
+                ),
+            ),
+            (
+                "outer",
+                module(
+                    "src/outer.rs",
+                    &["inner", "shared"],
+                    &[
+                        ("inner", Visibility::Private),
+                        ("shared", Visibility::PubCrate),
+                    ],
+                ),
+            ),
+            (
+                "inner",
+                module(
+                    "src/outer/inner.rs",
+                    &["api", "up"],
+                    &[("api", Visibility::Public), ("up", Visibility::PubSuper)],
+                ),
+            ),
+            ("api", module("src/outer/inner/api.rs", &[], &[])),
+            ("up", module("src/outer/inner/up.rs", &[], &[])),
+            ("shared", module("src/outer/shared.rs", &["api"], &[])),
+            (
+                "other",
+                module("src/other.rs", &["api", "shared", "up"], &[]),
+            ),
+        ]
+        .into_iter()
+        .map(|(name, info)| (name.to_string(), info))
+        .collect();
+
+        let g = build_graph(&mods);
+        // `pub mod api` sits behind the private `inner`: visible inside `outer` only
+        assert!(g.contains_edge("inner", "api"));
+        assert!(g.contains_edge("shared", "api"));
+        assert!(!g.contains_edge("other", "api"));
+        // `pub(crate) mod shared` in a top-level module is visible crate-wide
+        assert!(g.contains_edge("other", "shared"));
+        // `pub(super) mod up` widens to `outer`, which is still private to it
+        assert!(!g.contains_edge("other", "up"));
+
+        let visibility = ModuleVisibility::new(&mods);
+        assert!(visibility.permits("shared", "up"));
+        assert!(visibility.permits("other", "missing"));
+    }
+
+    #[test]
+    fn test_reachable_from_roots_multi_source() {
+        let mut mods = HashMap::new();
+
+        // main -> utils
+        let mut main_info = ModuleInfo::new(PathBuf::from("src/main.rs"));
+        main_info.refs.insert("utils".to_string());
+        mods.insert("main".to_string(), main_info);
+
+        // lib -> config
+        let mut lib_info = ModuleInfo::new(PathBuf::from("src/lib.rs"));
+        lib_info.refs.insert("config".to_string());
+        mods.insert("lib".to_string(), lib_info);
+
+        mods.insert(
+            "utils".to_string(),
+            ModuleInfo::new(PathBuf::from("src/utils.rs")),
+        );
+        mods.insert(
+            "config".to_string(),
+            ModuleInfo::new(PathBuf::from("src/config.rs")),
+        );
+        mods.insert(
+            "dead".to_string(),
+            ModuleInfo::new(PathBuf::from("src/dead.rs")),
+        );
+
+        let g = build_graph(&mods);
+
+        // Multi-source BFS from both main and lib
+        let reachable = reachable_from_roots(&g, ["main", "lib"]);
+
+        assert!(reachable.contains("main"));
+        assert!(reachable.contains("lib"));
+        assert!(reachable.contains("utils"));
+        assert!(reachable.contains("config"));
+        assert!(!reachable.contains("dead"));
+    }
+
+    #[test]
+    fn test_reachable_from_roots_missing_root() {
+        let mut mods = HashMap::new();
+        mods.insert(
+            "main".to_string(),
+            ModuleInfo::new(PathBuf::from("src/main.rs")),
+        );
+
+        let g = build_graph(&mods);
+
+        // Include a non-existent root - should be skipped gracefully
+        let reachable = reachable_from_roots(&g, ["main", "nonexistent"]);
+
+        assert!(reachable.contains("main"));
+        assert_eq!(reachable.len(), 1);
+    }
+
+    #[test]
+    fn test_reachable_from_roots_empty() {
+        let mods: HashMap<String, ModuleInfo> = HashMap::new();
+        let g = build_graph(&mods);
+
+        let reachable = reachable_from_roots(&g, std::iter::empty::<&str>());
+        assert!(reachable.is_empty());
+    }
+
+    #[test]
+    fn test_module_graph_to_visualizer_json() {
+        let mut mods = HashMap::new();
+
+        // main -> utils
+        let mut main_info = ModuleInfo::new(PathBuf::from("src/main.rs"));
+        main_info.refs.insert("utils".to_string());
+        mods.insert("main".to_string(), main_info);
+
+        mods.insert(
+            "utils".to_string(),
+            ModuleInfo::new(PathBuf::from("src/utils.rs")),
+        );
+        mods.insert(
+            "dead".to_string(),
+            ModuleInfo::new(PathBuf::from("src/dead.rs")),
+        );
+
+        let g = build_graph(&mods);
+        let reachable = reachable_from_roots(&g, ["main"]);
+        let json = module_graph_to_visualizer_json(&mods, &reachable);
+
+        // Check structure
+        assert!(json["nodes"].is_array());
+        assert!(json["edges"].is_array());
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 3);
+
+        // Check numeric IDs
+        let nodes = json["nodes"].as_array().unwrap();
+        for node in nodes {
+            assert!(node["id"].is_u64());
+            assert!(node["dead"].is_boolean());
+        }
+
+        // Check stats
+        assert_eq!(json["stats"]["total_modules"].as_u64(), Some(3));
+        assert_eq!(json["stats"]["dead_modules"].as_u64(), Some(1));
+        assert!(nodes.iter().all(|n| n["crate"].is_null()));
+
+        // Coupling metrics per node: main -> utils
+        let utils = nodes.iter().find(|n| n["name"] == "utils").unwrap();
+        assert_eq!(utils["metrics"]["fan_in"].as_u64(), Some(1));
+        assert_eq!(utils["metrics"]["instability"].as_f64(), Some(0.0));
+        assert_eq!(json["stats"]["cycles"].as_u64(), Some(0));
+    }
+
+    #[test]
+    fn test_module_graph_to_visualizer_json_workspace_crates() {
+        let mut mods = HashMap::new();
+
+        let mut cli_main = ModuleInfo::new(PathBuf::from("cli/src/main.rs"));
+        cli_main.refs.insert("cli::args".to_string());
+        cli_main.refs.insert("core::lib".to_string());
+        mods.insert("cli::main".to_string(), cli_main);
+        mods.insert(
+            "cli::args".to_string(),
+            ModuleInfo::new(PathBuf::from("cli/src/args.rs")),
+        );
+        mods.insert(
+            "core::lib".to_string(),
+            ModuleInfo::new(PathBuf::from("core/src/lib.rs")),
+        );
+        // Workspace-combined modules carry their crate
+        for (key, info) in &mut mods {
+            info.krate = key.split_once("::").map(|(krate, _)| krate.to_string());
+        }
+
+        let g = build_graph(&mods);
+        let reachable = reachable_from_roots(&g, ["cli::main"]);
+        let json = module_graph_to_visualizer_json(&mods, &reachable);
+
+        let nodes = json["nodes"].as_array().unwrap();
+        let crate_of =
+            |id: &serde_json::Value| nodes[id.as_u64().unwrap() as usize]["crate"].clone();
+        assert_eq!(nodes[2]["name"], "core::lib");
+        assert_eq!(nodes[2]["crate"], "core");
+
+        let edges = json["edges"].as_array().unwrap();
+        assert_eq!(edges.len(), 2);
+        for edge in edges {
+            let cross = crate_of(&edge["from"]) != crate_of(&edge["to"]);
+            assert_eq!(edge["cross_crate"].as_bool(), Some(cross));
+        }
+        assert!(edges.iter().any(|e| e["cross_crate"] == true));
+    }
+
+    #[test]
+    fn test_inline_test_modules_are_live_nodes() {
+        let mut lib = ModuleInfo::new(PathBuf::from("src/lib.rs"));
+        lib.refs.insert("net".to_string());
+        lib.test_mods.insert("tests".to_string());
+        let mut net = ModuleInfo::new(PathBuf::from("src/net.rs"));
+        net.test_mods.insert("tests".to_string());
+        let mut dead = ModuleInfo::new(PathBuf::from("src/dead.rs"));
+        dead.test_mods.insert("tests".to_string());
+        let mods = HashMap::from([
+            ("lib".to_string(), lib),
+            ("net".to_string(), net),
+            ("dead".to_string(), dead),
+        ]);
+
+        let tests = inline_test_modules(&mods);
+        let keys: Vec<&str> = tests.iter().map(|t| t.key.as_str()).collect();
+        assert_eq!(keys, ["dead::tests", "lib::tests", "net::tests"]);
+        assert_eq!(tests[1].module_path, "crate::tests");
+        assert_eq!(tests[2].parent, "net");
+
+        let reachable = reachable_from_roots(&build_graph(&mods), ["lib"]);
+        let json = module_graph_to_visualizer_json(&mods, &reachable);
+        let nodes = json["nodes"].as_array().unwrap();
+        assert_eq!(nodes.len(), 6);
+        assert_eq!(json["stats"]["total_modules"], 3);
+        assert_eq!(json["stats"]["dead_modules"], 1);
+        assert_eq!(json["stats"]["test_modules"], 3);
+        let dead_test = nodes.iter().find(|n| n["name"] == "dead::tests").unwrap();
+        assert_eq!(dead_test["test"], true);
+        assert_eq!(dead_test["dead"], false);
+        assert_eq!(dead_test["file"], "src/dead.rs");
+    }
+
+    #[test]
+    fn test_visualizer_json_edge_kinds() {
+        let mut main_info = ModuleInfo::new(PathBuf::from("src/main.rs"));
+        main_info.refs.insert("utils".to_string());
+        main_info
+            .ref_kinds
+            .insert("utils".to_string(), [EdgeKind::Mod, EdgeKind::Use].into());
+        let mods = HashMap::from([
+            ("main".to_string(), main_info),
+            (
+                "utils".to_string(),
+                ModuleInfo::new(PathBuf::from("src/utils.rs")),
+            ),
+        ]);
+
+        let reachable = reachable_from_roots(&build_graph(&mods), ["main"]);
+        let json = module_graph_to_visualizer_json(&mods, &reachable);
+        assert_eq!(json["edges"][0]["kinds"], serde_json::json!(["mod", "use"]));
+    }
+
+    #[test]
+    fn test_module_crate() {
+        let graph = ModuleInfo::new(PathBuf::from("deadmod-core/src/callgraph/graph.rs"));
+        let combined = crate::workspace::combine_crate_modules([(
+            "deadmod-core".to_string(),
+            HashMap::from([("graph".to_string(), graph.clone())]),
+        )]);
+        assert_eq!(
+            module_crate(&combined["deadmod-core::graph"]),
+            Some("deadmod-core")
+        );
+        assert_eq!(module_crate(&graph), None);
+    }
+}
@@ -0,0 +1,197 @@
+//! Dead code counts of the previous run, for "Δ since last run" footers.
+//!
+//! Each report records its counts in `last-run.json` in the crate's cache
+//! directory ([`crate::cache::cache_dir`]), keyed by report so a
+//! `--dead-func` run never compares against a module report. The next run
+//! of the same report gets a [`RunDelta`] against them.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::builder::AnalysisResult;
+
+/// File in the cache directory holding the last run's counts.
+pub const LAST_RUN_FILE: &str = "last-run.json";
+
+/// Finding counts of one report, by what was counted (`dead_modules`,
+/// `dead_functions`, ...).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunCounts(pub BTreeMap<String, usize>);
+
+impl RunCounts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the count of `what`.
+    pub fn with(mut self, what: &str, count: usize) -> Self {
+        self.0.insert(what.to_string(), count);
+        self
+    }
+
+    /// Dead modules and items of an analysis, by detector.
+    pub fn from_result(result: &AnalysisResult) -> Self {
+        Self::new()
+            .with("dead_modules", result.dead_modules.len())
+            .with("dead_functions", result.dead_functions.len())
+            .with("dead_trait_methods", result.dead_traits.len())
+            .with("dead_constants", result.dead_constants.len())
+            .with("dead_variants", result.dead_enums.len())
+            .with("dead_macros", result.dead_macros.len())
+            .with("dead_generics", result.dead_generics.len())
+            .with("dead_match_arms", result.dead_matcharms.len())
+    }
+}
+
+/// How one count moved since the last run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CountChange {
+    pub previous: usize,
+    pub current: usize,
+    pub change: i64,
+}
+
+/// Counts of a run against the last run of the same report.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct RunDelta(pub BTreeMap<String, CountChange>);
+
+impl RunDelta {
+    /// Changes of the counts in both runs.
+    pub fn between(previous: &RunCounts, current: &RunCounts) -> Self {
+        Self(
+            current
+                .0
+                .iter()
+                .filter_map(|(what, &now)| {
+                    let &before = previous.0.get(what)?;
+                    let change = CountChange {
+                        previous: before,
+                        current: now,
+                        change: now as i64 - before as i64,
+                    };
+                    Some((what.clone(), change))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// `Δ since last run: +2 dead functions, −5 dead modules`, largest moves
+/// first.
+impl fmt::Display for RunDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut moved: Vec<(&String, i64)> = self
+            .0
+            .iter()
+            .map(|(what, c)| (what, c.change))
+            .filter(|&(_, change)| change != 0)
+            .collect();
+        if moved.is_empty() {
+            return f.write_str("Δ since last run: no change");
+        }
+        moved.sort_by_key(|&(_, change)| std::cmp::Reverse(change.unsigned_abs()));
+        let moved: Vec<String> = moved
+            .into_iter()
+            .map(|(what, change)| {
+                let sign = if change > 0 { '+' } else { '−' };
+                format!(
+                    "{}{} {}",
+                    sign,
+                    change.unsigned_abs(),
+                    what.replace('_', " ")
+                )
+            })
+            .collect();
+        write!(f, "Δ since last run: {}", moved.join(", "))
+    }
+}
+
+/// Record `counts` as the last run of `report` in the cache directory
+/// `dir`, returning the delta against the run recorded before (`None` on
+/// the first run). Other reports' counts are kept.
+pub fn record_run(dir: &Path, report: &str, counts: &RunCounts) -> Result<Option<RunDelta>> {
+    let path = dir.join(LAST_RUN_FILE);
+    // An unreadable record only costs the delta
+    let mut runs: BTreeMap<String, RunCounts> = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    let delta = runs
+        .get(report)
+        .map(|previous| RunDelta::between(previous, counts));
+
+    runs.insert(report.to_string(), counts.clone());
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let json = serde_json::to_string_pretty(&runs).context("Failed to serialize run counts")?;
+    std::fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_run_delta() {
+        let dir = std::env::temp_dir().join(format!("deadmod_history_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let first = RunCounts::new()
+            .with("dead_modules", 7)
+            .with("dead_functions", 3);
+        assert_eq!(record_run(&dir, "modules", &first).unwrap(), None);
+        assert_eq!(
+            record_run(
+                &dir,
+                "functions",
+                &RunCounts::new().with("dead_functions", 1)
+            )
+            .unwrap(),
+            None
+        );
+
+        let second = RunCounts::new()
+            .with("dead_modules", 2)
+            .with("dead_functions", 5)
+            .with("dead_macros", 1);
+        let delta = record_run(&dir, "modules", &second).unwrap().unwrap();
+        assert_eq!(delta.0.len(), 2);
+        assert_eq!(
+            delta.0["dead_modules"],
+            CountChange {
+                previous: 7,
+                current: 2,
+                change: -5
+            }
+        );
+        assert_eq!(
+            delta.to_string(),
+            "Δ since last run: −5 dead modules, +2 dead functions"
+        );
+        assert_eq!(
+            serde_json::to_value(&delta).unwrap()["dead_functions"]["change"],
+            2
+        );
+
+        let same = record_run(&dir, "modules", &second).unwrap().unwrap();
+        assert_eq!(same.to_string(), "Δ since last run: no change");
+        // Other reports are left alone
+        let functions = record_run(
+            &dir,
+            "functions",
+            &RunCounts::new().with("dead_functions", 1),
+        )
+        .unwrap();
+        assert_eq!(
+            functions.unwrap().to_string(),
+            "Δ since last run: no change"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,295 @@
+//! HTTP JSON API over a warm [`Daemon`], for editors without LSP support.
+//!
+//! Lightweight plugins (Sublime, Kate) and dashboards can query a running
+//! `deadmod serve` with plain HTTP instead of speaking LSP or JSON-RPC. Every
+//! endpoint maps to a daemon method and answers with its JSON result.
+//!
+//! | Request                    | Daemon method | Result                                |
+//! |----------------------------|---------------|---------------------------------------|
+//! | `GET /analyze[?file=PATH]` | `analyze`     | Summary and findings                  |
+//! | `GET /modules`             | `modules`     | Every module and its verdict          |
+//! | `GET /dead`                | `dead`        | Dead modules and dead items           |
+//! | `GET /graph`               | `graph`       | Module graph in visualizer JSON       |
+//! | `GET /query?name=NAME`     | `query`       | Findings named or owned by `NAME`     |
+//! | `POST /invalidate[?file=]` | `invalidate`  | Drops the warm result                 |
+//! | `POST /shutdown`           | `shutdown`    | Stops the server                      |
+//!
+//! Errors are returned as `{"error": message}` with status 400 (bad
+//! parameters), 403 (foreign origin), 404 (unknown path), 405 (wrong
+//! method) or 500 (analysis failed).
+//!
+//! Any web page the user visits can send requests to `127.0.0.1`, so a
+//! request whose `Origin` or `Host` header names another host is refused
+//! (see [`check_origin`]): browsers always send the page's origin on
+//! cross-origin `POST`s, and a DNS-rebound name arrives as a foreign `Host`.
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+use crate::daemon::{Daemon, ANALYSIS_FAILED, INVALID_PARAMS};
+use crate::error::DeadmodError;
+
+/// Default port for `deadmod serve`.
+pub const DEFAULT_PORT: u16 = 7272;
+
+/// Response to one HTTP request.
+#[derive(Debug, Clone)]
+pub struct HttpReply {
+    /// HTTP status code
+    pub status: u16,
+    /// JSON body
+    pub body: String,
+    /// Whether the client asked the server to stop
+    pub shutdown: bool,
+}
+
+/// Route one request to `daemon`. `url` is the request target, including
+/// any query string.
+pub fn route(daemon: &mut Daemon, method: &str, url: &str) -> HttpReply {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let params = query_params(query);
+
+    let (expected, call) = match path.trim_end_matches('/') {
+        "/analyze" => ("GET", "analyze"),
+        "/modules" => ("GET", "modules"),
+        "/dead" => ("GET", "dead"),
+        "/graph" => ("GET", "graph"),
+        "/query" => ("GET", "query"),
+        "/invalidate" => ("POST", "invalidate"),
+        "/shutdown" => ("POST", "shutdown"),
+        _ => return error_reply(404, format!("Unknown endpoint: {}", path)),
+    };
+    if method != expected {
+        return error_reply(405, format!("{} requires {}", path, expected));
+    }
+    if call == "shutdown" {
+        return HttpReply {
+            status: 200,
+            body: json!({ "shutdown": true }).to_string(),
+            shutdown: true,
+        };
+    }
+
+    match daemon.call(call, &params) {
+        Ok(result) => HttpReply {
+            status: 200,
+            body: result.to_string(),
+            shutdown: false,
+        },
+        Err((code, message)) => {
+            let status = match code {
+                INVALID_PARAMS => 400,
+                ANALYSIS_FAILED => 500,
+                _ => 404,
+            };
+            error_reply(status, message)
+        }
+    }
+}
+
+/// Refuse a request whose `Origin` or `Host` header names a host other than
+/// the loopback names or `bound`, the host the server listens on. Missing
+/// headers are accepted: command-line clients need not send them.
+pub fn check_origin(bound: &str, origin: Option<&str>, host: Option<&str>) -> Option<HttpReply> {
+    let allowed = |authority: &str| {
+        let name = host_name(authority);
+        ["localhost", "127.0.0.1", "[::1]"]
+            .iter()
+            .any(|local| name.eq_ignore_ascii_case(local))
+            || name.eq_ignore_ascii_case(bound)
+    };
+    if let Some(origin) = origin {
+        let authority = origin
+            .strip_prefix("http://")
+            .or_else(|| origin.strip_prefix("https://"));
+        if !authority.is_some_and(allowed) {
+            return Some(error_reply(403, format!("Origin not allowed: {}", origin)));
+        }
+    }
+    if let Some(host) = host {
+        if !allowed(host) {
+            return Some(error_reply(403, format!("Host not allowed: {}", host)));
+        }
+    }
+    None
+}
+
+/// Host part of `host[:port]` or `[v6][:port]`.
+fn host_name(authority: &str) -> &str {
+    match authority.find(']') {
+        Some(end) if authority.starts_with('[') => &authority[..=end],
+        _ => authority.split(':').next().unwrap_or(authority),
+    }
+}
+
+fn error_reply(status: u16, message: String) -> HttpReply {
+    HttpReply {
+        status,
+        body: json!({ "error": message }).to_string(),
+        shutdown: false,
+    }
+}
+
+/// Decode `a=1&b=x%2Fy` into a JSON object of strings.
+fn query_params(query: &str) -> Value {
+    let params: Map<String, Value> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), Value::String(percent_decode(value)))
+        })
+        .collect();
+    if params.is_empty() {
+        Value::Null
+    } else {
+        Value::Object(params)
+    }
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' => match text
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    out.push(byte);
+                    i += 2;
+                }
+                None => out.push(b'%'),
+            },
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Serve `daemon` over HTTP on `addr` (e.g. `127.0.0.1:7272`) until a
+/// `POST /shutdown`.
+///
+/// Requests are handled one at a time on the calling thread, so the warm
+/// result is never analyzed twice concurrently. Requests from a foreign
+/// origin are refused, see [`check_origin`].
+pub fn serve(mut daemon: Daemon, addr: &str) -> Result<()> {
+    let server = tiny_http::Server::http(addr)
+        .map_err(|e| DeadmodError::internal(format!("Cannot listen on {}: {}", addr, e)))?;
+    let content_type = tiny_http::Header::from_bytes("Content-Type", "application/json")
+        .expect("static header is valid");
+
+    let bound = host_name(addr).to_string();
+    for request in server.incoming_requests() {
+        let header = |name: &str| {
+            request
+                .headers()
+                .iter()
+                .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+                .map(|h| h.value.as_str())
+        };
+        let reply = check_origin(&bound, header("Origin"), header("Host"))
+            .unwrap_or_else(|| route(&mut daemon, request.method().as_str(), request.url()));
+        let response = tiny_http::Response::from_string(reply.body)
+            .with_status_code(reply.status)
+            .with_header(content_type.clone());
+        if let Err(e) = request.respond(response) {
+            eprintln!("[WARN] serve: failed to send response: {}", e);
+        }
+        if reply.shutdown {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Deadmod;
+    use std::path::PathBuf;
+
+    fn daemon() -> Daemon {
+        Daemon::new(Deadmod::from_sources([
+            (PathBuf::from("src/lib.rs"), "mod a;".to_string()),
+            (PathBuf::from("src/a.rs"), String::new()),
+            (PathBuf::from("src/b.rs"), String::new()),
+        ]))
+    }
+
+    fn body(reply: &HttpReply) -> Value {
+        serde_json::from_str(&reply.body).unwrap()
+    }
+
+    #[test]
+    fn test_route_endpoints() {
+        let mut daemon = daemon();
+
+        let reply = route(&mut daemon, "GET", "/dead");
+        assert_eq!(reply.status, 200);
+        assert_eq!(body(&reply)["dead_modules"], json!(["b"]));
+
+        let reply = route(&mut daemon, "GET", "/modules/");
+        assert_eq!(body(&reply)["modules"].as_array().unwrap().len(), 3);
+
+        let reply = route(&mut daemon, "GET", "/analyze?file=src%2Fa.rs");
+        assert_eq!(body(&reply)["file"], "src/a.rs");
+
+        let reply = route(&mut daemon, "POST", "/invalidate");
+        assert_eq!(body(&reply)["was_warm"], true);
+
+        let reply = route(&mut daemon, "POST", "/shutdown");
+        assert!(reply.shutdown);
+    }
+
+    #[test]
+    fn test_route_errors() {
+        let mut daemon = daemon();
+        assert_eq!(route(&mut daemon, "GET", "/nope").status, 404);
+        assert_eq!(route(&mut daemon, "POST", "/dead").status, 405);
+        assert_eq!(route(&mut daemon, "GET", "/query").status, 400);
+        assert!(!daemon.is_warm());
+    }
+
+    #[test]
+    fn test_check_origin() {
+        let refused = |origin, host| check_origin("127.0.0.1", origin, host).map(|r| r.status);
+
+        assert_eq!(refused(None, None), None);
+        assert_eq!(refused(None, Some("127.0.0.1:7272")), None);
+        assert_eq!(
+            refused(Some("http://localhost:3000"), Some("localhost:7272")),
+            None
+        );
+        assert_eq!(refused(Some("http://[::1]:7272"), Some("[::1]:7272")), None);
+
+        // A page elsewhere posting to the server, and a DNS-rebound name
+        assert_eq!(
+            refused(Some("https://evil.example"), Some("127.0.0.1:7272")),
+            Some(403)
+        );
+        assert_eq!(refused(Some("null"), None), Some(403));
+        assert_eq!(refused(None, Some("evil.example:7272")), Some(403));
+        assert_eq!(
+            refused(Some("http://127.0.0.1.evil.example"), None),
+            Some(403)
+        );
+
+        // A server bound to a LAN address accepts that address
+        assert_eq!(
+            check_origin("192.168.1.5", None, Some("192.168.1.5:7272")).map(|r| r.status),
+            None
+        );
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("src%2Fmy+file.rs"), "src/my file.rs");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+}
@@ -0,0 +1,369 @@
+//! Deterministic node placement for the HTML and PixiJS viewers.
+//!
+//! The viewers' force simulations start from positions computed here and
+//! embedded in the node JSON, instead of random ones, so the same graph and
+//! seed always render the same picture: screenshots and diffs of generated
+//! pages stay stable. Another seed gives another (equally stable) layout.
+//!
+//! Each cluster is a sunflower (golden-angle spiral) of its nodes, and the
+//! clusters themselves are arranged on a larger sunflower, largest first, so
+//! related nodes start next to each other.
+//!
+//! A static layout goes further: [`force_directed`] runs the whole
+//! simulation here, in parallel, and the viewers draw the final positions
+//! without simulating. Browsers cannot simulate tens of thousands of nodes;
+//! this takes seconds. Repulsion only acts between nodes in neighboring grid
+//! cells, so an iteration is linear in the node and edge count.
+
+use std::collections::{BTreeMap, HashMap};
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+/// Seed used when none is configured.
+pub const DEFAULT_LAYOUT_SEED: u64 = 0;
+
+/// Simulation steps of a static layout, by default.
+pub const DEFAULT_LAYOUT_ITERATIONS: usize = 300;
+
+/// Distance between neighboring nodes, in viewer units.
+const NODE_SPACING: f64 = 60.0;
+
+/// `π (3 - √5)`: successive points of a sunflower spiral never line up.
+const GOLDEN_ANGLE: f64 = 2.399_963_229_728_653;
+
+/// How node positions are computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutOptions {
+    /// Seed of the jitter and cluster rotations
+    pub seed: u64,
+    /// Precompute final positions; viewers then skip their simulation
+    pub static_layout: bool,
+    /// Simulation steps of a static layout
+    pub iterations: usize,
+}
+
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        Self {
+            seed: DEFAULT_LAYOUT_SEED,
+            static_layout: false,
+            iterations: DEFAULT_LAYOUT_ITERATIONS,
+        }
+    }
+}
+
+impl LayoutOptions {
+    /// Lay out with `seed`.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Precompute final positions instead of only the starting ones.
+    pub fn with_static_layout(mut self, enabled: bool) -> Self {
+        self.static_layout = enabled;
+        self
+    }
+
+    /// Run a static layout for `iterations` steps.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+}
+
+/// A point in viewer coordinates (origin at the center), rounded to 0.1.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Position {
+    fn new(x: f64, y: f64) -> Self {
+        Self {
+            x: (x * 10.0).round() / 10.0,
+            y: (y * 10.0).round() / 10.0,
+        }
+    }
+}
+
+/// Initial positions of a graph's nodes and cluster centers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClusterLayout {
+    /// One position per node, in input order
+    pub nodes: Vec<Position>,
+    /// Center of each cluster
+    pub clusters: BTreeMap<String, Position>,
+}
+
+/// Place nodes given the cluster of each, in a stable node order.
+///
+/// The result depends only on the clusters, their order and the seed.
+pub fn cluster_layout(node_clusters: &[&str], options: &LayoutOptions) -> ClusterLayout {
+    let mut members: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, cluster) in node_clusters.iter().enumerate() {
+        members.entry(cluster).or_default().push(i);
+    }
+
+    // Largest clusters nearest the center, ties by name
+    let mut order: Vec<(&str, &[usize])> =
+        members.iter().map(|(c, m)| (*c, m.as_slice())).collect();
+    order.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(b.0)));
+
+    let largest = order.first().map_or(0, |(_, m)| m.len());
+    let cluster_spacing = 2.0 * sunflower_radius(largest) + NODE_SPACING * 2.0;
+
+    let mut rng = SplitMix64(options.seed);
+    let mut layout = ClusterLayout {
+        nodes: vec![Position::new(0.0, 0.0); node_clusters.len()],
+        clusters: BTreeMap::new(),
+    };
+    let rotation = rng.angle();
+    for (rank, (cluster, nodes)) in order.iter().enumerate() {
+        let (cx, cy) = sunflower(rank, cluster_spacing, rotation);
+        layout
+            .clusters
+            .insert(cluster.to_string(), Position::new(cx, cy));
+
+        let phase = rng.angle();
+        for (k, &node) in nodes.iter().enumerate() {
+            let (dx, dy) = sunflower(k, NODE_SPACING, phase);
+            let jitter = NODE_SPACING * 0.2;
+            layout.nodes[node] = Position::new(
+                cx + dx + rng.signed() * jitter,
+                cy + dy + rng.signed() * jitter,
+            );
+        }
+    }
+    layout
+}
+
+/// Positions of a graph's nodes: the seeded [`cluster_layout`], run
+/// through [`force_directed`] for a static layout.
+///
+/// `edges` are pairs of node indices; their direction does not matter.
+pub fn layout_graph(
+    node_clusters: &[&str],
+    edges: &[(usize, usize)],
+    options: &LayoutOptions,
+) -> Vec<Position> {
+    let start = cluster_layout(node_clusters, options).nodes;
+    if options.static_layout {
+        force_directed(&start, edges, options.iterations)
+    } else {
+        start
+    }
+}
+
+/// Fruchterman-Reingold simulation from `start`, for `iterations` steps.
+///
+/// Nodes closer than two spacings repel each other, edges pull their ends
+/// together, and a cooling temperature caps how far a node moves per step.
+/// Forces are computed for all nodes in parallel; the result is the same
+/// for any thread count.
+pub fn force_directed(
+    start: &[Position],
+    edges: &[(usize, usize)],
+    iterations: usize,
+) -> Vec<Position> {
+    let n = start.len();
+    if n < 2 || iterations == 0 {
+        return start.to_vec();
+    }
+
+    let mut adjacency = vec![Vec::new(); n];
+    for &(a, b) in edges {
+        if a != b && a < n && b < n {
+            adjacency[a].push(b);
+            adjacency[b].push(a);
+        }
+    }
+    // Sum forces in the same order however the edges were listed
+    for neighbors in &mut adjacency {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+
+    let k = NODE_SPACING;
+    let cutoff = 2.0 * k;
+    let mut pos: Vec<(f64, f64)> = start.iter().map(|p| (p.x, p.y)).collect();
+    let (min_x, max_x) = pos.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| {
+        (lo.min(p.0), hi.max(p.0))
+    });
+    let (min_y, max_y) = pos.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| {
+        (lo.min(p.1), hi.max(p.1))
+    });
+    let initial_temperature = ((max_x - min_x).max(max_y - min_y) * 0.1).max(k);
+
+    for step in 0..iterations {
+        let temperature = initial_temperature * (1.0 - step as f64 / iterations as f64);
+
+        // Nodes by grid cell, in index order
+        let cell_of =
+            |(x, y): (f64, f64)| ((x / cutoff).floor() as i64, (y / cutoff).floor() as i64);
+        let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (i, &p) in pos.iter().enumerate() {
+            grid.entry(cell_of(p)).or_default().push(i);
+        }
+
+        let moves: Vec<(f64, f64)> = (0..n)
+            .into_par_iter()
+            .map(|i| {
+                let (x, y) = pos[i];
+                let (cx, cy) = cell_of((x, y));
+                let (mut fx, mut fy) = (0.0, 0.0);
+
+                for gx in cx - 1..=cx + 1 {
+                    for gy in cy - 1..=cy + 1 {
+                        for &j in grid.get(&(gx, gy)).into_iter().flatten() {
+                            if j == i {
+                                continue;
+                            }
+                            let (mut dx, mut dy) = (x - pos[j].0, y - pos[j].1);
+                            if dx == 0.0 && dy == 0.0 {
+                                // Coincident nodes: separate by index
+                                (dx, dy) = if i < j { (-0.1, 0.0) } else { (0.1, 0.0) };
+                            }
+                            let dist = dx.hypot(dy);
+                            if dist < cutoff {
+                                let force = k * k / dist;
+                                fx += dx / dist * force;
+                                fy += dy / dist * force;
+                            }
+                        }
+                    }
+                }
+
+                for &j in &adjacency[i] {
+                    let (dx, dy) = (pos[j].0 - x, pos[j].1 - y);
+                    let dist = dx.hypot(dy);
+                    if dist > 0.0 {
+                        let force = dist * dist / k;
+                        fx += dx / dist * force;
+                        fy += dy / dist * force;
+                    }
+                }
+
+                let len = fx.hypot(fy);
+                if len > temperature {
+                    (fx / len * temperature, fy / len * temperature)
+                } else {
+                    (fx, fy)
+                }
+            })
+            .collect();
+
+        for (p, (dx, dy)) in pos.iter_mut().zip(moves) {
+            p.0 += dx;
+            p.1 += dy;
+        }
+    }
+
+    pos.into_iter().map(|(x, y)| Position::new(x, y)).collect()
+}
+
+/// Point `k` of a sunflower spiral with `spacing` between neighbors.
+fn sunflower(k: usize, spacing: f64, phase: f64) -> (f64, f64) {
+    if k == 0 {
+        return (0.0, 0.0);
+    }
+    let r = spacing * (k as f64).sqrt();
+    let theta = k as f64 * GOLDEN_ANGLE + phase;
+    (r * theta.cos(), r * theta.sin())
+}
+
+/// Radius of a sunflower of `n` points.
+fn sunflower_radius(n: usize) -> f64 {
+    NODE_SPACING * (n.saturating_sub(1) as f64).sqrt()
+}
+
+/// Small, fast and portable PRNG: the same seed gives the same sequence on
+/// every platform.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in `[-1, 1)`.
+    fn signed(&mut self) -> f64 {
+        self.unit() * 2.0 - 1.0
+    }
+
+    fn angle(&mut self) -> f64 {
+        self.unit() * std::f64::consts::TAU
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cluster_layout_is_deterministic() {
+        let clusters = ["api", "api", "db", "api", "db", "cli"];
+        let layout = cluster_layout(&clusters, &LayoutOptions::default());
+        assert_eq!(layout.nodes.len(), clusters.len());
+        assert_eq!(layout, cluster_layout(&clusters, &LayoutOptions::default()));
+        assert_ne!(
+            layout,
+            cluster_layout(&clusters, &LayoutOptions::default().with_seed(7))
+        );
+
+        // The largest cluster is centered, its nodes around it
+        assert_eq!(layout.clusters["api"], Position::new(0.0, 0.0));
+        let center = layout.clusters["db"];
+        for node in [2, 4] {
+            let p = layout.nodes[node];
+            assert!(
+                (p.x - center.x).hypot(p.y - center.y) < NODE_SPACING * 2.0,
+                "{:?} {:?}",
+                p,
+                center
+            );
+        }
+    }
+
+    #[test]
+    fn test_force_directed_layout() {
+        let distance = |a: Position, b: Position| (a.x - b.x).hypot(a.y - b.y);
+        // 0 - 1 connected far apart, 2 and 3 on top of each other
+        let start = [
+            Position::new(-1000.0, 0.0),
+            Position::new(1000.0, 0.0),
+            Position::new(0.0, 500.0),
+            Position::new(0.0, 500.0),
+        ];
+        let end = force_directed(&start, &[(0, 1)], 100);
+
+        assert!(distance(end[0], end[1]) < 300.0, "{:?}", end);
+        assert!(distance(end[2], end[3]) > NODE_SPACING / 2.0, "{:?}", end);
+        assert_eq!(end, force_directed(&start, &[(1, 0)], 100));
+        assert_eq!(force_directed(&start, &[], 0), start.to_vec());
+
+        let options = LayoutOptions::default()
+            .with_static_layout(true)
+            .with_iterations(50);
+        let clusters = ["a", "a", "b", "b"];
+        assert_eq!(
+            layout_graph(&clusters, &[(0, 2)], &options),
+            layout_graph(&clusters, &[(0, 2)], &options)
+        );
+        assert_eq!(
+            layout_graph(&clusters, &[(0, 2)], &LayoutOptions::default()),
+            cluster_layout(&clusters, &LayoutOptions::default()).nodes
+        );
+    }
+}
@@ -13,6 +13,9 @@
 //! - **Constant detection**: Find unused `const` and `static` items
 //! - **Enum variant detection**: Find unused enum variants
 //! - **Match arm detection**: Find dead match arms and wildcard masking
+//! - **Parameter detection**: Find function parameters the body never reads
+//! - **Field detection**: Find write-only and unused struct fields
+//! - **Dependency detection**: Find Cargo dependencies no code uses
 //! - **Call graph analysis**: Build and visualize function call graphs
 //! - **Incremental caching**: Only re-parse changed files
 //! - **Workspace support**: Analyze entire Cargo workspaces
@@ -37,11 +40,35 @@
 //!
 //! - [`cache`]: Incremental parsing cache with SHA-256 change detection
 //! - [`parse`]: AST parsing and module dependency extraction
-//! - [`graph`]: Dependency graph construction and reachability analysis
+//! - [`graph`]: Dependency graph construction, reachability analysis and
+//!   coupling metrics
 //! - [`detect`]: Dead module detection logic
 //! - [`scan`]: Parallel file discovery
+//! - [`source`]: Source providers (filesystem scan or in-memory sources)
+//! - [`archive`]: Source snapshots in `.tar`, `.tar.gz` and `.zip` archives
+//! - [`suppressions`]: False positives recorded in `.deadmod/suppressions.toml`
+//! - [`expected`]: Accepted findings in `deadmod-expected.toml`, for regression gates
+//! - [`file_findings`]: Every finding in one file, with item spans (`deadmod file`, the LSP)
+//! - [`parallel`]: Thread pool control (`--jobs`, `DEADMOD_JOBS`)
+//! - [`progress`]: Progress hooks and the terminal progress bar
 //! - [`fix`]: Auto-fix functionality to remove dead code
+//! - [`report`]: Plain/JSON reporting and multi-format output writers
+//! - [`arch`]: Architecture linting (module cycles, layering rules)
+//! - [`layout`]: Seeded node positions and precomputed static layouts for the HTML and PixiJS viewers
+//! - [`theme`]: Color themes of the DOT, HTML and PixiJS graphs (`--theme`)
 //! - [`builder`]: Fluent builder API for configuration
+//! - [`daemon`]: Persistent JSON-RPC analysis daemon over a local socket
+//! - [`dirty`]: Which detectors a file change affects, for re-analysis
+//! - [`watch`]: Debounced, filtered filesystem events for watch mode
+//! - [`http`]: HTTP JSON API over the daemon (`deadmod serve`)
+//! - [`history`]: Dead code counts of the last run, for the delta in report footers
+//! - [`aggregate`]: Folds findings inside dead modules under their module
+//! - [`coverage`]: llvm-cov/grcov import for reachable-but-uncovered functions
+//! - [`dead_deps`]: Cargo dependencies no code uses (`--dead-deps`)
+//! - [`evidence`]: Why a finding was reported (`deadmod explain`)
+//! - [`search`]: Symbol search with liveness over an analyzed crate (`deadmod find`)
+//! - [`removal`]: Textual references that block removing a dead item
+//! - [`synthetic`]: Generated projects for benchmarks and `deadmod bench-selftest`
 //! - [`error`]: Typed error handling
 //!
 //! # Cargo Features
@@ -50,49 +77,117 @@
 //! - `html` (default): Enable HTML visualization output
 //! - `callgraph` (default): Enable function call graph analysis
 //! - `pixi`: Enable WebGL/PixiJS visualization
+//! - `http` (default): Enable the HTTP JSON API server
+//! - `archive` (default): Analyze `.tar`, `.tar.gz` and `.zip` source snapshots
 //! - `full`: Enable all optional features
 
 // Core modules (always available)
+pub mod aggregate;
+pub mod arch;
 pub mod builder;
 pub mod cache;
+pub mod collision;
 pub mod common;
 pub mod config;
+pub mod coverage;
+pub mod daemon;
+pub mod dead_deps;
+pub mod deps;
 pub mod detect;
+pub mod dirty;
+pub mod edition;
 pub mod error;
+pub mod evidence;
+pub mod expected;
+pub mod file_findings;
+pub mod grace;
 pub mod graph;
+pub mod history;
+pub mod layout;
+pub mod limits;
 pub mod logging;
+pub mod parallel;
 pub mod parse;
 pub mod prelude;
+pub mod progress;
+pub mod project_model;
+pub mod removal;
 pub mod report;
 pub mod root;
 pub mod scan;
+pub mod search;
+pub mod source;
+pub mod suppressions;
+pub mod synthetic;
+pub mod theme;
+pub mod watch;
 pub mod workspace;
 
 // Common trait re-exports
 pub use common::GraphTraversal;
 
+// Confidence scoring
+pub use common::{extract_macro_mentions, Confidence, ConfidenceSignals, FnMetrics, Resolution};
+
+// API lifecycle attributes
+pub use common::{
+    deprecation, is_doc_hidden, keep_annotation, Deprecation, HiddenApiPolicy, Keep, KeptItem,
+};
+
+// Linker- and registry-kept items
+pub use common::{
+    attribute_paths, LivenessRules, DEFAULT_ALIVE_ATTRIBUTES, DEFAULT_REGISTRY_MACROS,
+};
+
+// Entry-point policy for pub functions
+pub use common::PubPolicy;
+
+// Stable finding IDs
+pub use common::{finding_id, ignored_ids, module_id, IGNORE_MARKER};
+
+// Whole dead impl blocks
+pub use common::{dead_impls, DeadImpl, ImplSpan};
+
+// Interned graph keys
+pub use common::{Interner, Symbol};
+
 // Feature-gated modules
 #[cfg(feature = "fix")]
 pub mod fix;
 
 #[cfg(feature = "callgraph")]
 pub mod callgraph;
+#[cfg(feature = "callgraph")]
+pub mod combined;
 
 #[cfg(feature = "html")]
 pub mod visualize;
+#[cfg(any(feature = "html", feature = "pixi"))]
+pub(crate) mod visualize_data;
 #[cfg(feature = "html")]
 pub mod visualize_html;
 
 #[cfg(feature = "pixi")]
 pub mod visualize_pixi;
 
+#[cfg(feature = "http")]
+pub mod http;
+
+#[cfg(feature = "cargo-metadata")]
+pub mod metadata;
+
+#[cfg(feature = "archive")]
+pub mod archive;
+
 // Detection modules (always available as core functionality)
 pub mod constants;
 pub mod enums;
+pub mod fields;
 pub mod func;
 pub mod generics;
 pub mod macros;
 pub mod matcharms;
+pub mod params;
 pub mod traits;
 
 // ============================================================================
@@ -102,120 +197,261 @@ pub mod traits;
 // Error types
 pub use error::{DeadmodError, DeadmodResult, IoResultExt};
 
+// Finding explanations
+pub use evidence::{explain, AnalysisEvidence, Exclusion, FindingEvidence, Mention};
+pub use removal::{check_removal, ReferenceKind, RemovalCheck, TextualReference};
+pub use search::{
+    find_symbol, Liveness, MentionRole, SymbolDefinition, SymbolReference, SymbolSearch,
+};
+
+// Per-file findings
+pub use file_findings::{analyze_file, FileFinding, FileFindings, ItemSpan};
+
+// Cross-detector correlation
+pub use aggregate::{
+    collapse_dead_modules, dead_module_files, drop_in_files, generated_files, CollapsedModule,
+};
+
+// Architecture linting
+pub use arch::{
+    check_layers, find_module_cycles, format_cycles_json, format_cycles_plain,
+    format_violations_json, format_violations_plain, LayerRule, LayerRules, LayerViolation,
+    ModuleCycle,
+};
+
 // Builder API
-pub use builder::{AnalysisResult, Deadmod, DeadItem, DeadItemKind};
+pub use builder::{
+    AnalysisResult, Attribution, CrateResult, CrossCrateEdge, DeadItem, DeadItemKind, Deadmod,
+    FindingsRef, WorkspaceAnalysisResult, ANALYSIS_FORMAT_VERSION,
+};
 
 // Cache types
 pub use cache::{
-    incremental_parse, load_cache, save_cache, file_hash,
-    CacheMetadata, CachedModule, CachedVisibility, DeadmodCache,
+    cache_dir, file_hash, incremental_parse, load_cache, load_cache_as, parse_crate, read_cache_as,
+    save_cache, save_cache_as, warm_cache, CacheFormat, CacheMetadata, CachedModule,
+    CachedVisibility, DeadmodCache, ParseMode, WarmStats, CACHE_DIR_ENV_VAR, CACHE_FORMAT_ENV_VAR,
+    LOCK_TIMEOUT,
+};
+
+// Thread pool control
+pub use parallel::{
+    build_thread_pool, jobs_from_env, resolve_jobs, run_with_threads, JOBS_ENV_VAR,
 };
 
 // Configuration
-pub use config::{load_config, DeadmodConfig, OutputConfig};
+pub use config::{load_config, DeadmodConfig, DetectorToggles, OutputConfig};
 
 // Core detection
-pub use detect::find_dead;
+pub use detect::{dead_names, find_dead, DeadModuleReport};
+
+// Selective re-analysis
+pub use dirty::{Detector, DirtyTracker, IncrementalState};
+
+// Watch mode events
+pub use watch::{EventProcessor, FsEvent, WatchBatch};
+
+// Module-name collisions
+pub use collision::{find_collisions, key_modules, ModuleCollision};
 
 // Graph building
 pub use graph::{
-    build_graph, module_graph_to_visualizer_json,
-    reachable_from_root, reachable_from_roots,
+    build_graph, find_cycles, focus_modules, inline_test_modules, module_crate,
+    module_graph_to_visualizer_json, module_reachability_matrix, qualified_module_path,
+    reachable_from_root, reachable_from_roots, subgraph, InlineTestModule, ModuleVisibility,
+    ReachabilityMatrix,
+};
+
+// Module graphs read back from exports
+pub use graph::import::{import_module_graph, ImportedGraph};
+
+// Module graph metrics
+pub use graph::metrics::{
+    format_metrics_json, format_metrics_plain, module_metrics, module_nesting, GraphMetrics,
+    ModuleMetrics,
 };
 
+// Input guardrails
+pub use limits::{parse_size, Deadline, Limits, SkipReason, Skipped};
+
 // Logging
-pub use logging::{init_structured_logging, log_error, log_event, log_info, log_warn};
+pub use logging::{init_logging, LogFormat, LOG_FORMAT_ENV};
 
 // Parsing
 pub use parse::{
-    extract_module_info, extract_uses_and_decls,
-    normalize_path_string, parse_modules, parse_modules_strict,
-    parse_single_module, parse_single_module_strict,
-    path_to_normalized_string,
-    ModuleInfo, ParseResult, Visibility,
+    extract_dependencies, extract_includes, extract_module_info, extract_uses_and_decls,
+    is_target_root_file, module_path_from_file, normalize_path_string, parse_module_source,
+    parse_modules, parse_modules_strict, parse_single_module, parse_single_module_strict,
+    path_to_normalized_string, select_edges, CfgModDecl, EdgeKind, EdgeKinds, IncludeSite,
+    ModuleInfo, ParseResult, Visibility, CRATE_ROOT_PATH, TARGET_DIRS,
 };
 
 // Reporting
-pub use report::{print_json, print_plain};
+pub use report::{
+    format_grouped_json, format_grouped_plain, format_json, format_plain, group_by_module,
+    join_module_path, module_ids, module_path_of, more_summary, normalize_json, print_json,
+    print_plain, render, write_outputs, GroupBy, ItemLimit, ModuleNode, ModuleReport, OutputFormat,
+    OutputSpec, PathFormatter, PathStyle, Truncated,
+};
+pub use report::{Badge, DEFAULT_BADGE_RED_AT};
+
+// Coverage import
+pub use coverage::{load_coverage, reachable_but_uncovered, CoverageData};
+
+// Analysis daemon
+pub use daemon::{default_socket_path, Daemon, Reply, SOCKET_FILE};
 
 // Root detection
-pub use root::find_root_modules;
+pub use root::{find_root_modules, resolve_root_modules, roots_for_crate};
+
+// Usage from dependency and dependent sources
+pub use deps::{dependency_files, dependency_roots};
+
+// Unused Cargo dependencies
+pub use dead_deps::{find_dead_dependencies, DeadDependency, DeadDepsResult, DependencyKind};
+
+// Grace period for recently added findings
+pub use grace::{declaration_line, GracePeriod, RecentItem};
+
+// Run-over-run deltas
+pub use history::{record_run, CountChange, RunCounts, RunDelta, LAST_RUN_FILE};
+
+// Progress reporting
+pub use progress::{Phase, Progress, ProgressEvent, ProgressMode};
+
+// Recorded false positives
+pub use suppressions::{current_author, Suppression, Suppressions, SUPPRESSIONS_FILE};
+
+// Regression gates
+pub use expected::{ExpectedDiff, ExpectedFinding, ExpectedFindings, EXPECTED_FILE};
+
+// Editions
+pub use edition::Edition;
+
+// Cargo workspace layout
+#[cfg(feature = "cargo-metadata")]
+pub use metadata::{
+    package_root_modules, CargoDependency, CargoMetadata, CargoPackage, CargoTarget,
+};
+
+// Source snapshots
+#[cfg(feature = "archive")]
+pub use archive::{ArchiveFormat, ArchiveLimits, ArchiveSource};
+
+// Non-Cargo project descriptions
+pub use project_model::{ProjectCrate, ProjectModel, PROJECT_JSON};
 
 // File scanning and module discovery
 pub use scan::{
-    gather_rs_files, gather_rs_files_with_excludes,
-    discover_modules, get_cluster_tree,
-    DiscoveredModule, ModuleCluster, ModuleDiscovery,
+    apply_limits, discover_modules, gather_rs_files, gather_rs_files_limited,
+    gather_rs_files_with_excludes, get_cluster_tree, is_scanned_path, DiscoveredModule,
+    GeneratedMarkers, ModuleCluster, ModuleDiscovery, DEFAULT_GENERATED_MARKERS,
 };
 
+// Source providers
+pub use source::{FsSource, MemorySource, SourceProvider};
+
 // Workspace analysis
 pub use workspace::{
-    analyze_crate, analyze_workspace, find_all_crates, find_crate_root,
-    is_workspace_root, CrateAnalysis,
+    analyze_crate, analyze_crate_with_roots, analyze_workspace, analyze_workspace_with_roots,
+    combine_crate_modules, combine_crate_modules_with_externs, find_all_crates, find_crate_root,
+    find_workspace_root, is_library_crate, is_workspace_root, locate_crate_root, member_name,
+    split_by_crate, workspace_dependents, CrateAnalysis,
 };
 
 // Feature-gated re-exports
 #[cfg(feature = "fix")]
-pub use fix::{clean_empty_dirs, fix_dead_modules, remove_file, remove_mod_declaration, FixResult};
+pub use fix::{
+    clean_empty_dirs, fix_and_verify, fix_dead_modules, fix_dead_modules_with, fix_dead_params,
+    fix_dead_variants, format_plan_plain, list_transactions, remove_file, remove_mod_declaration,
+    render_diff, render_removed_file, undo_fix, FixOptions, FixResult, PlanStep, PlannedRemoval,
+    RecoveryAction, RecoveryManifest, RemovalPlan, Sandbox, UndoResult, VerifyOptions,
+    VerifyResult,
+};
+
+#[cfg(feature = "http")]
+pub use http::{HttpReply, DEFAULT_PORT};
 
 #[cfg(feature = "callgraph")]
 pub use callgraph::{
-    extract_call_usages, extract_call_usages_resolved, extract_callgraph_functions,
-    extract_callgraph_parallel,
-    collect_use_statements, resolve_call_full, resolve_call_path, segments_to_path,
-    CallGraph, CallGraphAnalysis, CallGraphStats, CallgraphExtractionResult, CallUsageResult,
-    FunctionDef, ModulePathContext, ResolvedCall, UseMap,
-    VisualizerEdge, VisualizerGraph, VisualizerNode, VisualizerStats,
+    collect_use_statements, extract_call_usages, extract_call_usages_resolved,
+    extract_callgraph_functions, extract_callgraph_parallel, resolve_call_full, resolve_call_path,
+    segments_to_path, CallGraph, CallGraphAnalysis, CallGraphAnalysisRef, CallGraphStats,
+    CallUsageResult, CallgraphExtractionResult, DeadFunctionRef, FunctionDef, ModulePathContext,
+    ResolvedCall, SymbolTable, UseMap, VisualizerEdge, VisualizerGraph, VisualizerNode,
+    VisualizerStats, FOLDED_DEAD_ROOT,
 };
+#[cfg(feature = "callgraph")]
+pub use combined::combined_graph_json;
 
 #[cfg(feature = "html")]
-pub use visualize::generate_dot;
+pub use visualize::{generate_dot, generate_dot_with_options, DotClusters, DotOptions, RankDir};
+#[cfg(all(feature = "html", feature = "callgraph"))]
+pub use visualize_html::{generate_html_callgraph, generate_html_callgraph_with_layout};
 #[cfg(feature = "html")]
-pub use visualize_html::generate_html_graph;
+pub use visualize_html::{generate_html_graph, generate_html_graph_with_layout};
 
+#[cfg(all(feature = "pixi", feature = "callgraph"))]
+pub use visualize_pixi::{generate_pixi_callgraph, generate_pixi_callgraph_with_layout};
 #[cfg(feature = "pixi")]
-pub use visualize_pixi::generate_pixi_graph;
+pub use visualize_pixi::{
+    generate_pixi_graph, generate_pixi_graph_clustered, generate_pixi_graph_with_layout,
+    DEFAULT_CLUSTER_THRESHOLD,
+};
+
+// Visualization layout
+pub use layout::{
+    cluster_layout, force_directed, layout_graph, ClusterLayout, LayoutOptions, Position,
+    DEFAULT_LAYOUT_ITERATIONS, DEFAULT_LAYOUT_SEED,
+};
+pub use theme::{Palette, Theme};
 
 // Detection module re-exports
 pub use constants::{
-    extract_const_usage, extract_constants,
-    ConstAnalysisResult, ConstDef, ConstExtractionResult, ConstGraph, ConstStats,
-    ConstUsageResult, DeadConst,
+    extract_const_usage, extract_constants, ConstAnalysisResult, ConstDef, ConstExtractionResult,
+    ConstGraph, ConstStats, ConstUsageResult, DeadConst,
 };
 
 pub use enums::{
-    extract_variant_usage, extract_variants,
-    DeadVariant, EnumAnalysisResult, EnumExtractionResult, EnumGraph, EnumStats,
-    EnumUsageResult, EnumVariantDef,
+    extract_variant_usage, extract_variants, strip_variant_decls, DeadVariant, EnumAnalysisResult,
+    EnumExtractionResult, EnumGraph, EnumStats, EnumUsageResult, EnumVariantDef,
+};
+
+pub use fields::{
+    extract_field_usages, extract_fields, DeadField, DeadFieldKind, DeclaredField,
+    FieldAnalysisResult, FieldExtractionResult, FieldGraph, FieldStats, FieldUsageResult,
 };
 
 pub use func::{
-    extract_call_names, extract_calls, extract_functions, extract_functions_strict,
-    CallSite, FuncAnalysisResult, FuncGraph, FuncStats, FunctionInfo,
+    extract_call_names, extract_calls, extract_functions, extract_functions_strict, CallSite,
+    FuncAnalysisResult, FuncGraph, FuncStats, FunctionInfo,
 };
 
 pub use generics::{
-    extract_generic_usages, extract_declared_generics,
-    DeadGeneric, DeclaredGeneric, GenericAnalysisResult, GenericExtractionResult,
-    GenericGraph, GenericKind, GenericStats, GenericUsageResult, ParentKind, ParentUsages,
+    extract_declared_generics, extract_generic_usages, DeadGeneric, DeclaredGeneric,
+    GenericAnalysisResult, GenericExtractionResult, GenericGraph, GenericKind, GenericStats,
+    GenericUsageResult, ParentKind, ParentUsages,
 };
 
 pub use macros::{
-    extract_macro_usages, extract_macros,
-    DeadMacro, MacroAnalysisResult, MacroDef, MacroExtractionResult,
-    MacroGraph, MacroStats, MacroUsageResult,
+    extract_macro_usages, extract_macros, DeadMacro, ExportedMacroPolicy, MacroAnalysisResult,
+    MacroDef, MacroExtractionResult, MacroGraph, MacroKind, MacroStats, MacroUsageResult,
 };
 
 pub use matcharms::{
-    extract_match_arms, extract_match_usages,
-    DeadArmReason, DeadMatchArm, MatchArm, MatchArmAnalysisResult, MatchArmStats,
-    MatchExtractionResult, MatchGraph, MatchUsageResult,
+    extract_match_arms, extract_match_usages, strip_variant_arms, DeadArmReason, DeadMatchArm,
+    MatchArm, MatchArmAnalysisResult, MatchArmStats, MatchExtractionResult, MatchGraph,
+    MatchUsageResult,
+};
+
+pub use params::{
+    extract_params, rename_unused_params, DeadParam, DeclaredParam, ParamAnalysisResult,
+    ParamExtractionResult, ParamGraph, ParamStats,
 };
 
 pub use traits::{
-    extract_called_method_names, extract_trait_usages, extract_traits,
-    InherentImplMethod, TraitAnalysisResult, TraitExtractionResult, TraitGraph,
-    TraitImplMethod, TraitMethodDef, TraitMethodUsage, TraitStats, UsageKind,
+    extract_called_method_names, extract_trait_usages, extract_traits, BoundKind,
+    InherentImplMethod, TraitAnalysisResult, TraitBound, TraitExtractionResult, TraitGraph,
+    TraitHierarchy, TraitImplMethod, TraitMethodDef, TraitMethodUsage, TraitStats, UsageKind,
 };
 
 #[cfg(test)]
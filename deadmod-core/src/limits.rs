@@ -0,0 +1,204 @@
+//! Guardrails against pathological inputs.
+//!
+//! A multi-hundred-MB generated file or a vendored tree of a million files
+//! can stall a CI job. [`Limits`] caps the size and number of scanned files
+//! and the analysis time; whatever is left out is recorded as [`Skipped`] so
+//! reports say what was not analyzed instead of silently dropping it.
+//!
+//! ```toml
+//! [limits]
+//! max_file_size = 10000000  # bytes
+//! max_files = 20000
+//! timeout = 300             # seconds
+//! ```
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Caps on scanned files and analysis time. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Limits {
+    /// Files larger than this many bytes are skipped
+    pub max_file_size: Option<u64>,
+    /// Only the first this many files (in path order) are analyzed
+    pub max_files: Option<usize>,
+    /// Seconds after which remaining analysis steps are skipped
+    pub timeout: Option<u64>,
+}
+
+impl Limits {
+    /// Skip files larger than `bytes`.
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Analyze at most `count` files.
+    pub fn with_max_files(mut self, count: usize) -> Self {
+        self.max_files = Some(count);
+        self
+    }
+
+    /// Skip remaining analysis steps after `secs` seconds.
+    pub fn with_timeout(mut self, secs: u64) -> Self {
+        self.timeout = Some(secs);
+        self
+    }
+
+    /// Fill limits not set here from `fallback` (e.g. CLI flags over deadmod.toml).
+    pub fn or(self, fallback: Limits) -> Self {
+        Self {
+            max_file_size: self.max_file_size.or(fallback.max_file_size),
+            max_files: self.max_files.or(fallback.max_files),
+            timeout: self.timeout.or(fallback.timeout),
+        }
+    }
+
+    /// Start the timeout clock.
+    pub fn deadline(&self) -> Deadline {
+        Deadline {
+            expires: self
+                .timeout
+                .map(|secs| Instant::now() + Duration::from_secs(secs)),
+            secs: self.timeout.unwrap_or(0),
+        }
+    }
+}
+
+/// A running timeout started by [`Limits::deadline`].
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    expires: Option<Instant>,
+    secs: u64,
+}
+
+impl Deadline {
+    /// Whether the timeout has passed (never, without a timeout).
+    pub fn expired(&self) -> bool {
+        self.expires.is_some_and(|at| Instant::now() >= at)
+    }
+
+    /// Record `input` as skipped if the timeout has passed.
+    ///
+    /// Returns `true` while there is still time left.
+    pub fn check(&self, input: &str, skipped: &mut Vec<Skipped>) -> bool {
+        if !self.expired() {
+            return true;
+        }
+        skipped.push(Skipped {
+            input: input.to_string(),
+            reason: SkipReason::Timeout { secs: self.secs },
+        });
+        false
+    }
+}
+
+/// An input left out of the analysis by a [`Limits`] guardrail.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Skipped {
+    /// File path, or the analysis step that was not run
+    pub input: String,
+    /// Which limit was hit
+    pub reason: SkipReason,
+}
+
+impl fmt::Display for Skipped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.input, self.reason)
+    }
+}
+
+/// Why an input was skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SkipReason {
+    /// The file exceeds `max_file_size`
+    TooLarge { size: u64, limit: u64 },
+    /// More than `max_files` files were found
+    TooManyFiles { limit: usize },
+    /// The analysis ran past `timeout`
+    Timeout { secs: u64 },
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge { size, limit } => {
+                write!(
+                    f,
+                    "{} bytes exceeds the max file size of {} bytes",
+                    size, limit
+                )
+            }
+            Self::TooManyFiles { limit } => write!(f, "beyond the max file count of {}", limit),
+            Self::Timeout { secs } => write!(f, "not run, timeout of {}s reached", secs),
+        }
+    }
+}
+
+/// Parse a byte size: a plain number or one with a `K`, `M` or `G` suffix
+/// (decimal, optional trailing `B`): `500000`, `10MB`, `2g`.
+pub fn parse_size(s: &str) -> Result<u64, String> {
+    let upper = s.trim().to_ascii_uppercase();
+    let digits = upper.strip_suffix('B').unwrap_or(&upper);
+    let (number, scale) = match digits.char_indices().last() {
+        Some((i, 'K')) => (&digits[..i], 1_000),
+        Some((i, 'M')) => (&digits[..i], 1_000_000),
+        Some((i, 'G')) => (&digits[..i], 1_000_000_000),
+        _ => (digits, 1),
+    };
+    number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(scale))
+        .ok_or_else(|| format!("invalid size '{}' (expected bytes, or a K/M/G suffix)", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("500"), Ok(500));
+        assert_eq!(parse_size("10MB"), Ok(10_000_000));
+        assert_eq!(parse_size("2g"), Ok(2_000_000_000));
+        assert_eq!(parse_size("64 KB"), Ok(64_000));
+        assert!(parse_size("lots").is_err());
+        assert!(parse_size("").is_err());
+    }
+
+    #[test]
+    fn test_limits_or() {
+        let cli = Limits::default().with_max_files(10);
+        let config = Limits::default().with_max_files(99).with_timeout(60);
+        assert_eq!(
+            cli.or(config),
+            Limits::default().with_max_files(10).with_timeout(60)
+        );
+    }
+
+    #[test]
+    fn test_deadline() {
+        let mut skipped = Vec::new();
+        assert!(Limits::default()
+            .deadline()
+            .check("functions", &mut skipped));
+        assert!(Limits::default()
+            .with_timeout(3600)
+            .deadline()
+            .check("functions", &mut skipped));
+        assert!(!Limits::default()
+            .with_timeout(0)
+            .deadline()
+            .check("functions", &mut skipped));
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(
+            skipped[0].to_string(),
+            "functions: not run, timeout of 0s reached"
+        );
+    }
+}
@@ -1,62 +1,102 @@
-//! Structured logging for NASA-grade audit trails using **tracing**.
+//! Diagnostic logging through **tracing**, shared by the CLI and the LSP.
 //!
-//! Performance characteristics:
-//! - Non-blocking: tracing macros push events to a queue, not directly to I/O
-//! - Async-compatible: Works efficiently with Rayon's parallel workers
-//! - Rich context: Automatically captures level, timestamp, target, and thread ID
+//! Each pipeline stage runs in a span named after it (`scan`, `parse`,
+//! `extract`, `graph`, `report`), and files are logged as `debug` events
+//! inside them. Spans and events are targeted at their module, so
+//! `RUST_LOG` picks levels per module:
 //!
-//! The JSON subscriber provides machine-readable output for observability platforms.
+//! ```text
+//! RUST_LOG=deadmod_core=info                      # stage timings
+//! RUST_LOG=deadmod_core::cache=debug              # every parsed file
+//! RUST_LOG=info,deadmod_core::builder=debug       # detectors in detail
+//! ```
+//!
+//! Without `RUST_LOG`, only errors are logged. Logs go to stderr, which
+//! keeps stdout for reports (and for the LSP protocol); user-facing
+//! warnings stay `[WARN]` lines on stderr.
 
-use tracing::{error, info, warn};
+use std::fmt;
+use std::io::IsTerminal;
+use std::str::FromStr;
 
-/// Initializes the global tracing collector (subscriber).
-///
-/// This should be called *once* at the beginning of the application's runtime.
-/// It configures structured JSON output to stderr.
-///
-/// # Environment Variables
-/// - `RUST_LOG`: Controls log filtering (e.g., `RUST_LOG=deadmod=debug`)
-pub fn init_structured_logging() {
-    tracing_subscriber::fmt()
-        .json() // Output logs in JSON format
-        .with_ansi(false) // Disable ANSI codes in JSON output
-        .with_level(true) // Include the log level field
-        .with_target(true) // Include the module path (target)
-        .with_current_span(true) // Include tracing span context
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env()) // Allow RUST_LOG env var
-        .with_writer(std::io::stderr) // Write to stderr (keeps stdout clean for tool output)
-        .init();
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable selecting the log format when no flag does.
+pub const LOG_FORMAT_ENV: &str = "DEADMOD_LOG_FORMAT";
+
+/// How log lines are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One JSON object per line, with the span stack, for log collectors
+    #[default]
+    Json,
+    /// Multi-line human-readable output, colored on terminals
+    Pretty,
 }
 
-/// Logs a warning event.
-///
-/// Uses tracing macro for non-blocking, structured output.
-pub fn log_warn(message: &str) {
-    warn!(detail = %message);
+impl LogFormat {
+    /// Every format, in the order they are listed in help output.
+    pub const ALL: [LogFormat; 2] = [LogFormat::Json, LogFormat::Pretty];
+
+    /// Name used in flags.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Pretty => "pretty",
+        }
+    }
+
+    /// Format named by `DEADMOD_LOG_FORMAT`, or the default. An unknown
+    /// name is warned about and ignored.
+    pub fn from_env() -> Self {
+        match std::env::var(LOG_FORMAT_ENV) {
+            Ok(name) => name.parse().unwrap_or_else(|e| {
+                eprintln!("[WARN] {}: {}", LOG_FORMAT_ENV, e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
 }
 
-/// Logs an info event.
-///
-/// Uses tracing macro for non-blocking, structured output.
-pub fn log_info(message: &str) {
-    info!(detail = %message);
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
-/// Logs an error event.
-///
-/// Uses tracing macro for non-blocking, structured output.
-pub fn log_error(message: &str) {
-    error!(detail = %message);
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown log format '{}' (expected json or pretty)", s))
+    }
 }
 
-/// Logs a custom event with a specific event name.
+/// Install the global subscriber: `format` lines on stderr, filtered by
+/// `RUST_LOG`, with a line for each closed span giving its duration.
 ///
-/// Preserved for backwards compatibility with existing call sites.
-/// Maps to appropriate log level based on event name.
-pub fn log_event(event: &str, detail: &str) {
-    match event.to_uppercase().as_str() {
-        "ERROR" => error!(event = %event, detail = %detail),
-        "WARN" | "WARNING" => warn!(event = %event, detail = %detail),
-        _ => info!(event = %event, detail = %detail),
-    }
+/// Call once at startup. When a subscriber is already installed (tests, an
+/// application embedding the library), that one is kept.
+pub fn init_logging(format: LogFormat) {
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_span_events(FmtSpan::CLOSE)
+        .with_target(true)
+        .with_writer(std::io::stderr);
+    let _ = match format {
+        LogFormat::Json => builder
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .try_init(),
+        LogFormat::Pretty => builder
+            .pretty()
+            .with_ansi(std::io::stderr().is_terminal())
+            .try_init(),
+    };
 }
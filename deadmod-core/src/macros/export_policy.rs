@@ -0,0 +1,84 @@
+//! How exported macros unused inside their crate are treated.
+//!
+//! A `#[macro_export]` macro (or `pub macro`, or a proc macro) that its own
+//! crate never invokes may still be the crate's API for downstream crates.
+//! [`ExportedMacroPolicy::WorkspaceCheck`] looks for invocations in the other
+//! workspace members before reporting it; outside a workspace it behaves like
+//! [`ExportedMacroPolicy::Report`].
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Treatment of exported macros with no invocation in their crate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExportedMacroPolicy {
+    /// Never report them: downstream crates may use them
+    AssumeUsed,
+    /// Report them, flagged as exported
+    Report,
+    /// Report them unless another workspace member invokes them
+    #[default]
+    WorkspaceCheck,
+}
+
+impl ExportedMacroPolicy {
+    /// All policies, in documentation order.
+    pub const ALL: [ExportedMacroPolicy; 3] =
+        [Self::AssumeUsed, Self::Report, Self::WorkspaceCheck];
+
+    /// Name used in CLI flags and deadmod.toml.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AssumeUsed => "assume-used",
+            Self::Report => "report",
+            Self::WorkspaceCheck => "workspace-check",
+        }
+    }
+}
+
+impl fmt::Display for ExportedMacroPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ExportedMacroPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|p| p.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "unknown exported macro policy '{}' (expected assume-used, report or workspace-check)",
+                    s
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exported_macro_policy_from_str() {
+        assert_eq!(
+            "assume-used".parse::<ExportedMacroPolicy>(),
+            Ok(ExportedMacroPolicy::AssumeUsed)
+        );
+        assert_eq!(
+            "Workspace-Check".parse::<ExportedMacroPolicy>(),
+            Ok(ExportedMacroPolicy::WorkspaceCheck)
+        );
+        assert!("ignore".parse::<ExportedMacroPolicy>().is_err());
+        assert_eq!(
+            ExportedMacroPolicy::default(),
+            ExportedMacroPolicy::WorkspaceCheck
+        );
+    }
+}
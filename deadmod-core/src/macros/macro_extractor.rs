@@ -2,26 +2,93 @@
 //!
 //! Extracts all macro definitions including:
 //! - `macro_rules!` definitions
+//! - Declarative macros 2.0 (`macro foo {}`)
+//! - Procedural macros (`#[proc_macro]`, `#[proc_macro_attribute]`,
+//!   `#[proc_macro_derive]`)
 //! - `#[macro_export]` exported macros
 //! - Local macros inside modules
 //!
 //! NASA-grade resilience: handles malformed AST gracefully.
 
+use proc_macro2::{Delimiter, Ident, TokenStream, TokenTree};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::path::Path;
-use syn::{visit::Visit, Attribute, File, Item, ItemMacro, ItemMod};
+use syn::{
+    punctuated::Punctuated, visit::Visit, Attribute, File, Item, ItemFn, ItemMacro, ItemMod, Meta,
+    Token,
+};
+
+use crate::common::{has_cfg_attr, keep_annotation, Keep};
+
+/// How a macro is defined, which also decides how it is invoked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MacroKind {
+    /// `macro_rules! name { ... }`, invoked as `name!(...)`
+    #[default]
+    MacroRules,
+    /// `macro name { ... }` (macros 2.0), invoked as `name!(...)`
+    Macro2,
+    /// `#[proc_macro] fn name`, invoked as `name!(...)`
+    ProcMacro,
+    /// `#[proc_macro_attribute] fn name`, used as `#[name]`
+    ProcMacroAttribute,
+    /// `#[proc_macro_derive(Name)]`, used as `#[derive(Name)]`
+    ProcMacroDerive,
+}
+
+impl MacroKind {
+    /// Name used in reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MacroRules => "macro_rules",
+            Self::Macro2 => "macro",
+            Self::ProcMacro => "proc_macro",
+            Self::ProcMacroAttribute => "proc_macro_attribute",
+            Self::ProcMacroDerive => "proc_macro_derive",
+        }
+    }
+
+    /// Whether the macro is defined by a procedural macro function.
+    pub fn is_proc_macro(&self) -> bool {
+        matches!(
+            self,
+            Self::ProcMacro | Self::ProcMacroAttribute | Self::ProcMacroDerive
+        )
+    }
+}
+
+impl fmt::Display for MacroKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// Information about a macro definition.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MacroDef {
-    /// Name of the macro
+    /// Name of the macro (the derive name for `#[proc_macro_derive]`)
     pub name: String,
-    /// Whether the macro has #[macro_export]
+    /// Whether the macro is visible outside the crate: `#[macro_export]`,
+    /// `pub macro`, or any procedural macro
     pub exported: bool,
+    /// Definition kind
+    #[serde(default)]
+    pub kind: MacroKind,
     /// Source file path
     pub file: String,
+    /// 1-based line of the macro's name
+    #[serde(default)]
+    pub line: usize,
     /// Module path (for nested macros)
     pub module_path: String,
+    /// Whether the macro has #[cfg] or #[cfg_attr] attributes
+    #[serde(default)]
+    pub has_cfg: bool,
+    /// `#[deadmod::keep]` details, if the macro is kept on purpose
+    #[serde(default)]
+    pub keep: Option<Keep>,
 }
 
 /// AST visitor that extracts all macro definitions.
@@ -48,14 +115,97 @@ impl MacroExtractor {
         self.current_mod.join("::")
     }
 
-    fn record(&mut self, name: String, exported: bool) {
+    fn record(
+        &mut self,
+        name: &Ident,
+        kind: MacroKind,
+        exported: bool,
+        has_cfg: bool,
+        keep: Option<Keep>,
+    ) {
         self.results.push(MacroDef {
-            name,
+            name: name.to_string(),
             exported,
+            kind,
             file: self.file_path.clone(),
+            line: name.span().start().line,
             module_path: self.build_module_path(),
+            has_cfg,
+            keep,
         });
     }
+
+    /// Record a procedural macro function, if `func` is one.
+    fn record_proc_macro(&mut self, func: &ItemFn) {
+        for attr in &func.attrs {
+            let (kind, name) = if attr.path().is_ident("proc_macro") {
+                (MacroKind::ProcMacro, func.sig.ident.clone())
+            } else if attr.path().is_ident("proc_macro_attribute") {
+                (MacroKind::ProcMacroAttribute, func.sig.ident.clone())
+            } else if attr.path().is_ident("proc_macro_derive") {
+                // #[proc_macro_derive(Name, attributes(helper))]: the first
+                // path is the name users write in #[derive(...)]
+                let derive_name = attr
+                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .ok()
+                    .and_then(|metas| metas.first().and_then(|m| m.path().get_ident().cloned()));
+                match derive_name {
+                    Some(ident) => (MacroKind::ProcMacroDerive, ident),
+                    None => continue,
+                }
+            } else {
+                continue;
+            };
+
+            self.record(
+                &name,
+                kind,
+                true,
+                has_cfg_attr(&func.attrs),
+                keep_annotation(&func.attrs),
+            );
+            return;
+        }
+    }
+}
+
+/// Parse a macros 2.0 definition, which syn keeps as verbatim tokens.
+///
+/// Returns `(name, is_pub, has_cfg)` for `#[attrs] pub(...) macro name ...`.
+fn parse_macro2(tokens: &TokenStream) -> Option<(Ident, bool, bool)> {
+    let mut iter = tokens.clone().into_iter().peekable();
+    let mut has_cfg = false;
+    let mut is_pub = false;
+
+    while let Some(tt) = iter.next() {
+        match tt {
+            // Outer attribute: `#` followed by a bracketed group
+            TokenTree::Punct(p) if p.as_char() == '#' => {
+                if let Some(TokenTree::Group(g)) = iter.next() {
+                    if let Some(TokenTree::Ident(id)) = g.stream().into_iter().next() {
+                        has_cfg |= id == "cfg" || id == "cfg_attr";
+                    }
+                }
+            }
+            TokenTree::Ident(id) if id == "pub" => {
+                is_pub = true;
+                // Skip the `(crate)` / `(in path)` restriction
+                if matches!(iter.peek(), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis)
+                {
+                    iter.next();
+                }
+            }
+            TokenTree::Ident(id) if id == "macro" => {
+                return match iter.next() {
+                    Some(TokenTree::Ident(name)) => Some((name, is_pub, has_cfg)),
+                    _ => None,
+                };
+            }
+            _ => return None,
+        }
+    }
+
+    None
 }
 
 impl<'ast> Visit<'ast> for MacroExtractor {
@@ -67,9 +217,25 @@ impl<'ast> Visit<'ast> for MacroExtractor {
                 attrs,
                 ..
             }) => {
-                self.record(id.to_string(), Self::is_exported(attrs));
+                self.record(
+                    id,
+                    MacroKind::MacroRules,
+                    Self::is_exported(attrs),
+                    has_cfg_attr(attrs),
+                    keep_annotation(attrs),
+                );
             }
 
+            // Handle macro 2.0 definitions (not modelled by syn)
+            Item::Verbatim(tokens) => {
+                if let Some((name, is_pub, has_cfg)) = parse_macro2(tokens) {
+                    self.record(&name, MacroKind::Macro2, is_pub, has_cfg, None);
+                }
+            }
+
+            // Handle procedural macro functions
+            Item::Fn(func) => self.record_proc_macro(func),
+
             // Handle inline modules
             Item::Mod(ItemMod {
                 ident,
@@ -183,6 +349,79 @@ macro_rules! baz {
         assert_eq!(result.len(), 3);
     }
 
+    #[test]
+    fn test_extract_macro2() {
+        let content = r#"
+macro local_two($x:expr) { $x }
+
+#[cfg(feature = "nightly")]
+pub(crate) macro gated {
+    () => {}
+}
+"#;
+        let result = extract_macros(&PathBuf::from("test.rs"), content);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].name, "local_two");
+        assert_eq!(result[0].kind, MacroKind::Macro2);
+        assert!(!result[0].exported);
+        assert_eq!(result[1].name, "gated");
+        assert!(result[1].exported);
+        assert!(result[1].has_cfg);
+    }
+
+    #[test]
+    fn test_extract_proc_macros() {
+        let content = r#"
+use proc_macro::TokenStream;
+
+#[proc_macro]
+pub fn make_answer(_: TokenStream) -> TokenStream { todo!() }
+
+#[proc_macro_attribute]
+pub fn route(_: TokenStream, item: TokenStream) -> TokenStream { item }
+
+#[proc_macro_derive(Builder, attributes(builder))]
+pub fn derive_builder(_: TokenStream) -> TokenStream { todo!() }
+
+pub fn helper() {}
+"#;
+        let result = extract_macros(&PathBuf::from("lib.rs"), content);
+        let kinds: Vec<(&str, MacroKind)> =
+            result.iter().map(|m| (m.name.as_str(), m.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("make_answer", MacroKind::ProcMacro),
+                ("route", MacroKind::ProcMacroAttribute),
+                ("Builder", MacroKind::ProcMacroDerive),
+            ]
+        );
+        assert!(result.iter().all(|m| m.exported && m.kind.is_proc_macro()));
+    }
+
+    #[test]
+    fn test_extract_keep_annotation() {
+        let content = r#"
+#[deadmod::keep(reason = "used by downstream doctests")]
+#[macro_export]
+macro_rules! kept { () => {}; }
+
+macro_rules! plain { () => {}; }
+"#;
+        let result = extract_macros(&PathBuf::from("test.rs"), content);
+        let kept = result.iter().find(|m| m.name == "kept").unwrap();
+        assert_eq!(
+            kept.keep.as_ref().unwrap().reason.as_deref(),
+            Some("used by downstream doctests")
+        );
+        assert!(result
+            .iter()
+            .find(|m| m.name == "plain")
+            .unwrap()
+            .keep
+            .is_none());
+    }
+
     #[test]
     fn test_malformed_resilient() {
         let content = "macro_rules! { broken";
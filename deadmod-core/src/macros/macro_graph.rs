@@ -2,8 +2,16 @@
 //!
 //! Builds a graph of macro definitions and identifies unused macros.
 //!
-//! Entry points (always considered reachable):
-//! - `#[macro_export]` macros (could be used by external crates)
+//! Usages are matched by kind:
+//! - `macro_rules!`, `macro` and `#[proc_macro]` against `name!(...)` calls
+//! - `#[proc_macro_attribute]` against `#[name]` attributes
+//! - `#[proc_macro_derive(Name)]` against `#[derive(Name)]`
+//!
+//! Exported macros (`#[macro_export]`, `pub macro`, proc macros) unused in
+//! their crate follow an [`ExportedMacroPolicy`]: reported flagged as
+//! exported, assumed used, or reported unless invoked by another workspace
+//! member ([`MacroGraph::with_workspace_usages`]). `#[deadmod::keep]` macros
+//! are never reported; they are listed in [`MacroAnalysisResult::kept`].
 //!
 //! Performance characteristics:
 //! - Graph build: O(|M| + |U|) where M = macros, U = usages
@@ -11,20 +19,28 @@
 
 use std::collections::HashSet;
 
-use super::macro_extractor::MacroDef;
+use super::export_policy::ExportedMacroPolicy;
+use super::macro_extractor::{MacroDef, MacroKind};
 use super::macro_usage::MacroUsageResult;
+use crate::common::{Confidence, ConfidenceSignals, KeptItem};
 
 /// A dead macro that was declared but never used.
 #[derive(Debug, Clone)]
 pub struct DeadMacro {
     /// The name of the unused macro
     pub name: String,
-    /// Whether it was marked #[macro_export]
+    /// Whether it is visible outside the crate
     pub exported: bool,
+    /// Definition kind
+    pub kind: MacroKind,
     /// Source file
     pub file: String,
+    /// 1-based line of the macro's name
+    pub line: usize,
     /// Module path
     pub module_path: String,
+    /// Confidence that the macro is really unused
+    pub confidence: Confidence,
 }
 
 /// Statistics about macro analysis.
@@ -34,6 +50,11 @@ pub struct MacroStats {
     pub exported_count: usize,
     pub dead_count: usize,
     pub dead_exported_count: usize,
+    /// Exported macros unused in the crate, not reported under
+    /// [`ExportedMacroPolicy::AssumeUsed`]
+    pub exported_assumed_used: usize,
+    /// Exported macros unused in the crate but invoked by another workspace member
+    pub exported_workspace_used: usize,
 }
 
 /// Result of macro analysis.
@@ -41,10 +62,21 @@ pub struct MacroStats {
 pub struct MacroAnalysisResult {
     /// All dead macros found
     pub dead: Vec<DeadMacro>,
+    /// Macros kept alive by `#[deadmod::keep]`
+    pub kept: Vec<KeptItem>,
     /// Statistics
     pub stats: MacroStats,
 }
 
+impl MacroAnalysisResult {
+    /// Drop dead macros below `min` confidence and update the dead counts.
+    pub fn retain_confidence(&mut self, min: Confidence) {
+        self.dead.retain(|m| m.confidence >= min);
+        self.stats.dead_count = self.dead.len();
+        self.stats.dead_exported_count = self.dead.iter().filter(|m| m.exported).count();
+    }
+}
+
 /// Graph for analyzing macro usage.
 #[derive(Default)]
 pub struct MacroGraph {
@@ -52,40 +84,148 @@ pub struct MacroGraph {
     declared: Vec<MacroDef>,
     /// Set of used macro names
     used: HashSet<String>,
+    /// Set of used attribute names
+    used_attributes: HashSet<String>,
+    /// Set of used derive names
+    used_derives: HashSet<String>,
+    /// Identifiers mentioned inside macro invocations (confidence signal)
+    macro_mentions: HashSet<String>,
+    /// Treatment of exported macros unused in the crate
+    exported_policy: ExportedMacroPolicy,
+    /// Usages in the other workspace members
+    workspace: MacroUsageResult,
 }
 
 impl MacroGraph {
     /// Create a new macro graph from extraction results.
     pub fn new(declared: Vec<MacroDef>, usages: &[MacroUsageResult]) -> Self {
         let mut used = HashSet::new();
+        let mut used_attributes = HashSet::new();
+        let mut used_derives = HashSet::new();
 
         for usage in usages {
             used.extend(usage.used_macros.clone());
+            used_attributes.extend(usage.used_attributes.clone());
+            used_derives.extend(usage.used_derives.clone());
         }
 
-        Self { declared, used }
+        Self {
+            declared,
+            used,
+            used_attributes,
+            used_derives,
+            macro_mentions: HashSet::new(),
+            exported_policy: ExportedMacroPolicy::default(),
+            workspace: MacroUsageResult::default(),
+        }
+    }
+
+    /// Treatment of exported macros unused in the crate (default
+    /// [`ExportedMacroPolicy::WorkspaceCheck`]).
+    pub fn with_exported_policy(mut self, policy: ExportedMacroPolicy) -> Self {
+        self.exported_policy = policy;
+        self
+    }
+
+    /// Provide macro usages from the other workspace members.
+    ///
+    /// Under [`ExportedMacroPolicy::WorkspaceCheck`] they keep exported macros
+    /// alive; private macros cannot be invoked from another crate and
+    /// ignore them.
+    pub fn with_workspace_usages(mut self, usages: &[MacroUsageResult]) -> Self {
+        for usage in usages {
+            self.workspace
+                .used_macros
+                .extend(usage.used_macros.iter().cloned());
+            self.workspace
+                .used_attributes
+                .extend(usage.used_attributes.iter().cloned());
+            self.workspace
+                .used_derives
+                .extend(usage.used_derives.iter().cloned());
+        }
+        self
+    }
+
+    /// Provide identifiers found inside macro invocations.
+    ///
+    /// A dead macro whose name appears inside another macro's tokens may be
+    /// invoked from that macro's expansion, so it gets lower confidence.
+    pub fn with_macro_mentions(mut self, mentions: HashSet<String>) -> Self {
+        self.macro_mentions = mentions;
+        self
     }
 
     /// Check if a macro is used.
     fn is_macro_used(&self, mac: &MacroDef) -> bool {
-        // Check if the macro name appears in the used set
-        self.used.contains(&mac.name)
+        let used = match mac.kind {
+            MacroKind::MacroRules | MacroKind::Macro2 | MacroKind::ProcMacro => &self.used,
+            MacroKind::ProcMacroAttribute => &self.used_attributes,
+            MacroKind::ProcMacroDerive => &self.used_derives,
+        };
+        used.contains(&mac.name)
+    }
+
+    /// Check if another workspace member uses an exported macro.
+    fn is_workspace_used(&self, mac: &MacroDef) -> bool {
+        let used = match mac.kind {
+            MacroKind::MacroRules | MacroKind::Macro2 | MacroKind::ProcMacro => {
+                &self.workspace.used_macros
+            }
+            MacroKind::ProcMacroAttribute => &self.workspace.used_attributes,
+            MacroKind::ProcMacroDerive => &self.workspace.used_derives,
+        };
+        mac.exported && used.contains(&mac.name)
+    }
+
+    /// Exported macros unused in the crate that the policy keeps alive:
+    /// `(assumed used, used by another workspace member)`.
+    fn exported_alive(&self) -> (usize, usize) {
+        let unused = self
+            .declared
+            .iter()
+            .filter(|m| m.exported && m.keep.is_none() && !self.is_macro_used(m));
+        match self.exported_policy {
+            ExportedMacroPolicy::AssumeUsed => (unused.count(), 0),
+            ExportedMacroPolicy::Report => (0, 0),
+            ExportedMacroPolicy::WorkspaceCheck => {
+                (0, unused.filter(|m| self.is_workspace_used(m)).count())
+            }
+        }
+    }
+
+    /// Whether the exported macro policy keeps an unused macro alive.
+    fn is_policy_alive(&self, mac: &MacroDef) -> bool {
+        match self.exported_policy {
+            ExportedMacroPolicy::AssumeUsed => mac.exported,
+            ExportedMacroPolicy::Report => false,
+            ExportedMacroPolicy::WorkspaceCheck => self.is_workspace_used(mac),
+        }
     }
 
     /// Find all dead macros.
     ///
-    /// Note: Exported macros are still reported as dead if unused within the crate,
-    /// but marked as exported so the caller can decide whether to report them.
+    /// Exported macros unused within the crate are reported, marked as
+    /// exported, unless the [`ExportedMacroPolicy`] keeps them alive.
     pub fn find_dead(&self) -> Vec<DeadMacro> {
         let mut dead = Vec::new();
 
         for mac in &self.declared {
-            if !self.is_macro_used(mac) {
+            if mac.keep.is_none() && !self.is_macro_used(mac) && !self.is_policy_alive(mac) {
                 dead.push(DeadMacro {
                     name: mac.name.clone(),
                     exported: mac.exported,
+                    kind: mac.kind,
                     file: mac.file.clone(),
+                    line: mac.line,
                     module_path: mac.module_path.clone(),
+                    confidence: ConfidenceSignals {
+                        visibility: if mac.exported { "pub" } else { "private" },
+                        has_cfg: mac.has_cfg,
+                        in_macro: self.macro_mentions.contains(&mac.name),
+                        ..Default::default()
+                    }
+                    .score(),
                 });
             }
         }
@@ -99,15 +239,28 @@ impl MacroGraph {
     /// Perform complete analysis and return structured result.
     pub fn analyze(&self) -> MacroAnalysisResult {
         let dead = self.find_dead();
+        let (exported_assumed_used, exported_workspace_used) = self.exported_alive();
 
         let stats = MacroStats {
             total_declared: self.declared.len(),
             exported_count: self.declared.iter().filter(|m| m.exported).count(),
             dead_count: dead.len(),
             dead_exported_count: dead.iter().filter(|m| m.exported).count(),
+            exported_assumed_used,
+            exported_workspace_used,
         };
 
-        MacroAnalysisResult { dead, stats }
+        let kept = self
+            .declared
+            .iter()
+            .filter_map(|m| {
+                m.keep
+                    .as_ref()
+                    .map(|keep| KeptItem::new(&m.name, &m.file, keep))
+            })
+            .collect();
+
+        MacroAnalysisResult { dead, kept, stats }
     }
 
     /// Get the total number of declared macros.
@@ -124,8 +277,12 @@ mod tests {
         MacroDef {
             name: name.to_string(),
             exported,
+            kind: MacroKind::MacroRules,
             file: file.to_string(),
+            line: 0,
             module_path: String::new(),
+            has_cfg: false,
+            keep: None,
         }
     }
 
@@ -138,6 +295,7 @@ mod tests {
 
         let usages = vec![MacroUsageResult {
             used_macros: HashSet::from(["used_macro".to_string()]),
+            ..Default::default()
         }];
 
         let graph = MacroGraph::new(declared, &usages);
@@ -170,6 +328,7 @@ mod tests {
 
         let usages = vec![MacroUsageResult {
             used_macros: HashSet::from(["foo".to_string(), "bar".to_string()]),
+            ..Default::default()
         }];
 
         let graph = MacroGraph::new(declared, &usages);
@@ -189,6 +348,7 @@ mod tests {
 
         let usages = vec![MacroUsageResult {
             used_macros: HashSet::from(["m1".to_string()]),
+            ..Default::default()
         }];
 
         let graph = MacroGraph::new(declared, &usages);
@@ -199,4 +359,104 @@ mod tests {
         assert_eq!(result.stats.dead_count, 2);
         assert_eq!(result.stats.dead_exported_count, 2);
     }
+
+    #[test]
+    fn test_proc_macros_matched_by_kind() {
+        let proc_macro = |name: &str, kind: MacroKind| MacroDef {
+            kind,
+            ..make_macro(name, true, "lib.rs")
+        };
+        let declared = vec![
+            proc_macro("route", MacroKind::ProcMacroAttribute),
+            proc_macro("Builder", MacroKind::ProcMacroDerive),
+            proc_macro("Unused", MacroKind::ProcMacroDerive),
+            proc_macro("make_answer", MacroKind::ProcMacro),
+        ];
+
+        // `route!()` does not count as using the attribute macro
+        let usages = vec![MacroUsageResult {
+            used_macros: HashSet::from(["route".to_string(), "make_answer".to_string()]),
+            used_attributes: HashSet::from(["Builder".to_string()]),
+            used_derives: HashSet::from(["Builder".to_string()]),
+        }];
+
+        let dead = MacroGraph::new(declared, &usages).find_dead();
+        let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["Unused", "route"]);
+        assert_eq!(dead[0].kind, MacroKind::ProcMacroDerive);
+    }
+
+    #[test]
+    fn test_kept_macro_is_not_dead() {
+        let mut kept = make_macro("kept", true, "lib.rs");
+        kept.keep = Some(crate::common::Keep::default());
+        let declared = vec![kept, make_macro("unused", false, "lib.rs")];
+
+        let result = MacroGraph::new(declared, &[]).analyze();
+        assert_eq!(result.stats.dead_count, 1);
+        assert_eq!(result.dead[0].name, "unused");
+        assert_eq!(result.kept[0].name, "kept");
+        assert!(result.kept[0].reason.is_none());
+    }
+
+    #[test]
+    fn test_dead_macro_confidence() {
+        let declared = vec![
+            make_macro("local", false, "test.rs"),
+            make_macro("exported", true, "test.rs"),
+            make_macro("inner", false, "test.rs"),
+        ];
+
+        let graph = MacroGraph::new(declared, &[])
+            .with_macro_mentions(HashSet::from(["inner".to_string()]));
+        let dead = graph.find_dead();
+
+        let confidence = |name: &str| dead.iter().find(|d| d.name == name).unwrap().confidence;
+        assert_eq!(confidence("local"), Confidence::High);
+        assert_eq!(confidence("exported"), Confidence::Low);
+        assert_eq!(confidence("inner"), Confidence::Low);
+    }
+
+    #[test]
+    fn test_exported_macro_policies() {
+        let declared = || {
+            vec![
+                make_macro("local", false, "lib.rs"),
+                make_macro("shared", true, "lib.rs"),
+                make_macro("api", true, "lib.rs"),
+            ]
+        };
+        // Another member invokes `shared!` and `local!` (a private macro of its own)
+        let members = vec![MacroUsageResult {
+            used_macros: HashSet::from(["shared".to_string(), "local".to_string()]),
+            ..Default::default()
+        }];
+        let analyze = |policy| {
+            MacroGraph::new(declared(), &[])
+                .with_workspace_usages(&members)
+                .with_exported_policy(policy)
+                .analyze()
+        };
+        let names = |result: &MacroAnalysisResult| {
+            result
+                .dead
+                .iter()
+                .map(|m| m.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let report = analyze(ExportedMacroPolicy::Report);
+        assert_eq!(names(&report), vec!["api", "local", "shared"]);
+        assert_eq!(report.stats.dead_exported_count, 2);
+
+        let check = analyze(ExportedMacroPolicy::WorkspaceCheck);
+        assert_eq!(names(&check), vec!["api", "local"]);
+        assert_eq!(check.stats.dead_exported_count, 1);
+        assert_eq!(check.stats.exported_workspace_used, 1);
+
+        let assume = analyze(ExportedMacroPolicy::AssumeUsed);
+        assert_eq!(names(&assume), vec!["local"]);
+        assert_eq!(assume.stats.exported_assumed_used, 2);
+        assert_eq!(assume.stats.dead_exported_count, 0);
+    }
 }
@@ -5,30 +5,43 @@
 //! - Statement macros: `println!("...")`
 //! - Pattern macros: `matches!(x, pat)`
 //! - Type macros: `vec![]` in type position
-//! - Attribute-like macros
+//! - Attribute macros: `#[route(...)]`
+//! - Derive macros: `#[derive(Builder)]`, including inside `#[cfg_attr]`
+//! - Macros imported by name: `#[macro_use(foo, bar)] extern crate dep;`
 //!
 //! NASA-grade resilience: handles malformed AST gracefully.
 
 use std::collections::HashSet;
 use std::path::Path;
-use syn::{visit::Visit, Expr, File, Item, Macro, Pat, Stmt, Type};
+use syn::{
+    punctuated::Punctuated, visit::Visit, Attribute, Expr, File, Ident, Item, Macro, Meta, Pat,
+    Stmt, Token, Type,
+};
 
 /// Information about macro usages in a file.
 #[derive(Debug, Clone, Default)]
 pub struct MacroUsageResult {
     /// Set of macro names that are invoked
     pub used_macros: HashSet<String>,
+    /// Attribute names (last path segment), for `#[proc_macro_attribute]`
+    pub used_attributes: HashSet<String>,
+    /// Names listed in `#[derive(...)]`, for `#[proc_macro_derive]`
+    pub used_derives: HashSet<String>,
 }
 
 /// AST visitor that extracts all macro usages.
 struct MacroUsageExtractor {
     used: HashSet<String>,
+    attributes: HashSet<String>,
+    derives: HashSet<String>,
 }
 
 impl MacroUsageExtractor {
     fn new() -> Self {
         Self {
             used: HashSet::with_capacity(16),
+            attributes: HashSet::new(),
+            derives: HashSet::new(),
         }
     }
 
@@ -37,6 +50,36 @@ impl MacroUsageExtractor {
             self.used.insert(seg.ident.to_string());
         }
     }
+
+    /// Record an attribute meta, descending into `derive(...)` and
+    /// `cfg_attr(cond, ...)`.
+    fn record_attr_meta(&mut self, meta: &Meta) {
+        let path = meta.path();
+
+        if path.is_ident("derive") || path.is_ident("cfg_attr") {
+            let nested = match meta.require_list().and_then(|list| {
+                list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            }) {
+                Ok(nested) => nested,
+                Err(_) => return,
+            };
+
+            if path.is_ident("derive") {
+                for item in &nested {
+                    if let Some(seg) = item.path().segments.last() {
+                        self.derives.insert(seg.ident.to_string());
+                    }
+                }
+            } else {
+                // First element is the cfg predicate
+                for item in nested.iter().skip(1) {
+                    self.record_attr_meta(item);
+                }
+            }
+        } else if let Some(seg) = path.segments.last() {
+            self.attributes.insert(seg.ident.to_string());
+        }
+    }
 }
 
 impl<'ast> Visit<'ast> for MacroUsageExtractor {
@@ -82,10 +125,31 @@ impl<'ast> Visit<'ast> for MacroUsageExtractor {
         syn::visit::visit_type(self, ty);
     }
 
+    fn visit_attribute(&mut self, attr: &'ast Attribute) {
+        self.record_attr_meta(&attr.meta);
+        syn::visit::visit_attribute(self, attr);
+    }
+
     fn visit_item(&mut self, item: &'ast Item) {
-        // Item-level macros (like derive, etc. handled via macro)
-        if let Item::Macro(item_macro) = item {
-            self.record_macro(&item_macro.mac);
+        match item {
+            // Item-level macros (like derive, etc. handled via macro)
+            Item::Macro(item_macro) => self.record_macro(&item_macro.mac),
+            // 2015-style import: `#[macro_use(foo, bar)] extern crate dep;`
+            Item::ExternCrate(krate) => {
+                for attr in krate
+                    .attrs
+                    .iter()
+                    .filter(|a| a.path().is_ident("macro_use"))
+                {
+                    let names = attr.meta.require_list().and_then(|list| {
+                        list.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)
+                    });
+                    if let Ok(names) = names {
+                        self.used.extend(names.iter().map(Ident::to_string));
+                    }
+                }
+            }
+            _ => {}
         }
 
         syn::visit::visit_item(self, item);
@@ -110,6 +174,8 @@ pub fn extract_macro_usages(path: &Path, content: &str) -> MacroUsageResult {
 
     MacroUsageResult {
         used_macros: extractor.used,
+        used_attributes: extractor.attributes,
+        used_derives: extractor.derives,
     }
 }
 
@@ -195,6 +261,52 @@ fn main() {
         assert!(result.used_macros.contains("format"));
     }
 
+    #[test]
+    fn test_extract_macro_use_extern_crate() {
+        let content = r#"
+#[macro_use(try_opt, bail)]
+extern crate helpers;
+#[macro_use]
+extern crate log;
+"#;
+        let result = extract_macro_usages(&PathBuf::from("lib.rs"), content);
+        assert!(result.used_macros.contains("try_opt"));
+        assert!(result.used_macros.contains("bail"));
+        assert!(!result.used_macros.contains("log"));
+    }
+
+    #[test]
+    fn test_extract_attribute_and_derive_usages() {
+        let content = r#"
+#[derive(Debug, serde::Serialize, Builder)]
+#[cfg_attr(feature = "x", derive(Gated), gated_attr)]
+struct Config {
+    #[builder(default)]
+    name: String,
+}
+
+#[my_crate::route(GET, "/")]
+fn index() {}
+"#;
+        let result = extract_macro_usages(&PathBuf::from("test.rs"), content);
+        for derive in ["Debug", "Serialize", "Builder", "Gated"] {
+            assert!(
+                result.used_derives.contains(derive),
+                "missing derive {}",
+                derive
+            );
+        }
+        for attr in ["route", "builder", "gated_attr"] {
+            assert!(
+                result.used_attributes.contains(attr),
+                "missing attribute {}",
+                attr
+            );
+        }
+        assert!(!result.used_attributes.contains("derive"));
+        assert!(!result.used_attributes.contains("cfg_attr"));
+    }
+
     #[test]
     fn test_malformed_resilient() {
         let content = "fn main() { broken!(";
@@ -1,8 +1,12 @@
 //! Macro analysis for dead macro detection.
 //!
 //! This module provides functionality to detect unused macros:
-//! - `macro_rules!` definitions that are never invoked
-//! - `#[macro_export]` macros that aren't used within the crate
+//! - `macro_rules!` and macros 2.0 (`macro foo {}`) definitions that are
+//!   never invoked
+//! - `#[macro_export]` macros that aren't used within the crate, as set by
+//!   [`ExportedMacroPolicy`]
+//! - Procedural macros (`#[proc_macro]`, `#[proc_macro_attribute]`,
+//!   `#[proc_macro_derive]`) with no call, attribute or derive usage
 //!
 //! # Architecture
 //!
@@ -44,11 +48,13 @@
 //! }
 //! ```
 
+pub mod export_policy;
 pub mod macro_extractor;
 pub mod macro_graph;
 pub mod macro_usage;
 
 // Re-exports for convenience
-pub use macro_extractor::{extract_macros, MacroDef, MacroExtractionResult};
+pub use export_policy::ExportedMacroPolicy;
+pub use macro_extractor::{extract_macros, MacroDef, MacroExtractionResult, MacroKind};
 pub use macro_graph::{DeadMacro, MacroAnalysisResult, MacroGraph, MacroStats};
 pub use macro_usage::{extract_macro_usages, MacroUsageResult};
@@ -0,0 +1,235 @@
+//! Removal of match arms that name removed enum variants.
+//!
+//! Deleting a variant breaks every `match` that still lists it. For each
+//! arm, the top-level `|` alternatives naming a removed variant are dropped;
+//! if none is left the whole arm goes. Exhaustiveness is unaffected, since
+//! the removed variant can no longer be matched anyway.
+
+use std::collections::HashSet;
+
+use syn::spanned::Spanned;
+use syn::{visit::Visit, ExprMatch, File, Item, Pat, UseTree};
+
+use crate::common::SourceEdits;
+
+/// Remove match arms and `|` alternatives naming any `(enum, variant)` pair.
+///
+/// Variants are recognized as `Enum::Variant` (with any prefix),
+/// `Self::Variant`, or a bare `Variant` imported with `use Enum::Variant`
+/// or `use Enum::*`. Returns the new content and the number of arms or
+/// alternatives removed, or `None` when nothing matched or the file does
+/// not parse.
+pub fn strip_variant_arms(content: &str, targets: &[(&str, &str)]) -> Option<(String, usize)> {
+    let ast: File = syn::parse_file(content).ok()?;
+    let mut imported = HashSet::new();
+    for item in &ast.items {
+        if let Item::Use(item_use) = item {
+            collect_imports(&item_use.tree, None, &mut imported);
+        }
+    }
+
+    let mut finder = ArmFinder {
+        edits: SourceEdits::new(content),
+        matcher: VariantMatcher { targets, imported },
+        removed: 0,
+    };
+    finder.visit_file(&ast);
+
+    if finder.removed == 0 {
+        return None;
+    }
+    let removed = finder.removed;
+    Some((finder.edits.apply(), removed))
+}
+
+/// Record `(parent, name)` for every name a `use` tree brings into scope.
+fn collect_imports(tree: &UseTree, parent: Option<String>, out: &mut HashSet<(String, String)>) {
+    match tree {
+        UseTree::Path(path) => collect_imports(&path.tree, Some(path.ident.to_string()), out),
+        UseTree::Name(name) => {
+            if let Some(parent) = parent {
+                out.insert((parent, name.ident.to_string()));
+            }
+        }
+        UseTree::Glob(_) => {
+            if let Some(parent) = parent {
+                out.insert((parent, "*".to_string()));
+            }
+        }
+        UseTree::Group(group) => {
+            for item in &group.items {
+                collect_imports(item, parent.clone(), out);
+            }
+        }
+        UseTree::Rename(_) => {}
+    }
+}
+
+struct VariantMatcher<'t> {
+    targets: &'t [(&'t str, &'t str)],
+    imported: HashSet<(String, String)>,
+}
+
+impl VariantMatcher<'_> {
+    fn matches_path(&self, path: &syn::Path) -> bool {
+        let segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+        match segments.as_slice() {
+            [.., parent, variant] => self
+                .targets
+                .iter()
+                .any(|(e, v)| v == variant && (e == parent || parent == "Self")),
+            [variant] => self.matches_bare(variant),
+            [] => false,
+        }
+    }
+
+    fn matches_bare(&self, variant: &str) -> bool {
+        self.targets.iter().any(|(e, v)| {
+            *v == variant
+                && (self.imported.contains(&(e.to_string(), v.to_string()))
+                    || self.imported.contains(&(e.to_string(), "*".to_string())))
+        })
+    }
+
+    /// Whether `pat` names a target variant anywhere inside it.
+    fn mentions(&self, pat: &Pat) -> bool {
+        let mut visitor = PatMentions {
+            matcher: self,
+            found: false,
+        };
+        visitor.visit_pat(pat);
+        visitor.found
+    }
+}
+
+struct PatMentions<'m, 't> {
+    matcher: &'m VariantMatcher<'t>,
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for PatMentions<'_, '_> {
+    fn visit_pat(&mut self, pat: &'ast Pat) {
+        self.found |= match pat {
+            Pat::Path(p) => self.matcher.matches_path(&p.path),
+            Pat::TupleStruct(p) => self.matcher.matches_path(&p.path),
+            Pat::Struct(p) => self.matcher.matches_path(&p.path),
+            Pat::Ident(p) if p.subpat.is_none() => self.matcher.matches_bare(&p.ident.to_string()),
+            _ => false,
+        };
+        syn::visit::visit_pat(self, pat);
+    }
+}
+
+struct ArmFinder<'a, 't> {
+    edits: SourceEdits<'a>,
+    matcher: VariantMatcher<'t>,
+    removed: usize,
+}
+
+impl<'ast> Visit<'ast> for ArmFinder<'_, '_> {
+    fn visit_expr_match(&mut self, node: &'ast ExprMatch) {
+        self.visit_expr(&node.expr);
+
+        for arm in &node.arms {
+            let cases: Vec<&Pat> = match &arm.pat {
+                Pat::Or(or) => or.cases.iter().collect(),
+                pat => vec![pat],
+            };
+            let dead: Vec<bool> = cases
+                .iter()
+                .map(|case| self.matcher.mentions(case))
+                .collect();
+
+            if dead.iter().all(|&d| d) {
+                let range = self.edits.range(arm.span());
+                self.edits.remove(range);
+                self.removed += 1;
+                continue;
+            }
+
+            for (i, case) in cases.iter().enumerate().filter(|&(i, _)| dead[i]) {
+                // Take the `|` after the case, or before it for the last one
+                let range = match cases.get(i + 1) {
+                    Some(next) => {
+                        self.edits.offset(case.span().start())
+                            ..self.edits.offset(next.span().start())
+                    }
+                    None => {
+                        self.edits.offset(cases[i - 1].span().end())
+                            ..self.edits.offset(case.span().end())
+                    }
+                };
+                self.edits.remove(range);
+                self.removed += 1;
+            }
+            self.visit_arm(arm);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGETS: &[(&str, &str)] = &[("Light", "Amber")];
+
+    #[test]
+    fn test_strip_variant_arms() {
+        let content = "\
+fn describe(light: Light) -> &'static str {
+    match light {
+        Light::Red => \"stop\",
+        // Wait for it
+        Light::Amber => {
+            \"wait\"
+        }
+        Light::Green | crate::Light::Amber => \"go\",
+        Light::Amber | Light::Green => \"go\",
+    }
+}
+";
+        let (stripped, removed) = strip_variant_arms(content, TARGETS).unwrap();
+        assert_eq!(removed, 3);
+        assert_eq!(
+            stripped,
+            "\
+fn describe(light: Light) -> &'static str {
+    match light {
+        Light::Red => \"stop\",
+        // Wait for it
+        Light::Green => \"go\",
+        Light::Green => \"go\",
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_strip_variant_arms_nested_and_imported() {
+        let content = "\
+use Light::*;
+
+impl Light {
+    fn next(self, other: Option<Light>) -> u8 {
+        match (self, other) {
+            (Amber, _) => 1,
+            (_, Some(Self::Amber)) => 2,
+            _ => 3,
+        }
+    }
+}
+";
+        let (stripped, removed) = strip_variant_arms(content, TARGETS).unwrap();
+        assert_eq!(removed, 2);
+        assert!(stripped.contains("            _ => 3,\n"));
+        assert!(!stripped.contains("Amber)"));
+    }
+
+    #[test]
+    fn test_strip_variant_arms_bare_binding_kept() {
+        // Without an import, a bare `Amber` is just a binding
+        let content = "fn f(x: u8) -> u8 { match x { Amber => Amber } }";
+        assert!(strip_variant_arms(content, TARGETS).is_none());
+    }
+}
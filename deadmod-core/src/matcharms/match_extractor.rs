@@ -3,13 +3,17 @@
 //! Extracts all match arms from match expressions including:
 //! - Pattern types (ident, path, tuple struct, struct, wildcard)
 //! - Match arm position within the expression
-//! - File location information
+//! - The enum named by qualified patterns (`Color` in `Color::Red`)
+//! - File location of the arm and of its `match` expression
 //!
 //! NASA-grade resilience: handles malformed AST gracefully.
 
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use syn::{visit::Visit, Expr, File, Pat};
+use syn::spanned::Spanned;
+use syn::{visit::Visit, Attribute, Expr, File, ImplItemFn, ItemFn, ItemImpl, Pat, TraitItemFn};
+
+use crate::common::{keep_annotation, Keep};
 
 /// Information about a match arm.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +22,10 @@ pub struct MatchArm {
     pub pattern: String,
     /// The variant name if this is an enum variant pattern
     pub variant_name: Option<String>,
+    /// Enum the pattern's path names (`Color` in `Color::Red`); `None` for
+    /// unqualified and `Self::` patterns
+    #[serde(default)]
+    pub enum_name: Option<String>,
     /// Whether this is a wildcard pattern (_)
     pub is_wildcard: bool,
     /// Position in the match expression (0-indexed)
@@ -26,6 +34,18 @@ pub struct MatchArm {
     pub total_arms: usize,
     /// Source file path
     pub file: String,
+    /// Line of the arm's pattern (1-indexed)
+    #[serde(default)]
+    pub line: usize,
+    /// Column of the arm's pattern (0-indexed)
+    #[serde(default)]
+    pub column: usize,
+    /// Line of the enclosing `match` expression (1-indexed)
+    #[serde(default)]
+    pub match_line: usize,
+    /// `#[deadmod::keep]` details from the enclosing function or impl block
+    #[serde(default)]
+    pub keep: Option<Keep>,
 }
 
 /// Result of match arm extraction from a file.
@@ -41,6 +61,8 @@ pub struct MatchExtractionResult {
 struct MatchExtractor {
     file_path: String,
     result: MatchExtractionResult,
+    /// `#[deadmod::keep]` of the innermost annotated enclosing item
+    keep: Option<Keep>,
 }
 
 impl MatchExtractor {
@@ -48,7 +70,17 @@ impl MatchExtractor {
         Self {
             file_path,
             result: MatchExtractionResult::default(),
+            keep: None,
+        }
+    }
+
+    /// Enter an item with `attrs`, returning the keep state to restore on exit.
+    fn enter(&mut self, attrs: &[Attribute]) -> Option<Keep> {
+        let outer = self.keep.clone();
+        if let Some(keep) = keep_annotation(attrs) {
+            self.keep = Some(keep);
         }
+        outer
     }
 
     fn extract_pattern_info(pat: &Pat) -> (String, Option<String>, bool) {
@@ -150,6 +182,23 @@ impl MatchExtractor {
             _ => ("<complex>".to_string(), None, false),
         }
     }
+
+    /// Enum qualifying a variant pattern: the second-to-last path segment.
+    fn enum_qualifier(pat: &Pat) -> Option<String> {
+        let path = match pat {
+            Pat::Path(p) => &p.path,
+            Pat::TupleStruct(ts) => &ts.path,
+            Pat::Struct(ps) => &ps.path,
+            Pat::Reference(pr) => return Self::enum_qualifier(&pr.pat),
+            _ => return None,
+        };
+        let segments = path.segments.len();
+        path.segments
+            .iter()
+            .nth(segments.checked_sub(2)?)
+            .map(|s| s.ident.to_string())
+            .filter(|name| name != "Self")
+    }
 }
 
 impl<'ast> Visit<'ast> for MatchExtractor {
@@ -157,23 +206,54 @@ impl<'ast> Visit<'ast> for MatchExtractor {
         if let Expr::Match(m) = expr {
             self.result.match_count += 1;
             let total_arms = m.arms.len();
+            let match_line = m.match_token.span.start().line;
 
             for (position, arm) in m.arms.iter().enumerate() {
                 let (pattern, variant_name, is_wildcard) = Self::extract_pattern_info(&arm.pat);
+                let start = arm.pat.span().start();
 
                 self.result.arms.push(MatchArm {
                     pattern,
                     variant_name,
+                    enum_name: Self::enum_qualifier(&arm.pat),
                     is_wildcard,
                     position,
                     total_arms,
                     file: self.file_path.clone(),
+                    line: start.line,
+                    column: start.column,
+                    match_line,
+                    keep: self.keep.clone(),
                 });
             }
         }
 
         syn::visit::visit_expr(self, expr);
     }
+
+    fn visit_item_fn(&mut self, item: &'ast ItemFn) {
+        let outer = self.enter(&item.attrs);
+        syn::visit::visit_item_fn(self, item);
+        self.keep = outer;
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        let outer = self.enter(&item.attrs);
+        syn::visit::visit_item_impl(self, item);
+        self.keep = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &'ast ImplItemFn) {
+        let outer = self.enter(&item.attrs);
+        syn::visit::visit_impl_item_fn(self, item);
+        self.keep = outer;
+    }
+
+    fn visit_trait_item_fn(&mut self, item: &'ast TraitItemFn) {
+        let outer = self.enter(&item.attrs);
+        syn::visit::visit_trait_item_fn(self, item);
+        self.keep = outer;
+    }
 }
 
 /// Extract all match arms from file content.
@@ -218,6 +298,30 @@ fn f(c: Color) {
         assert_eq!(result.arms[0].variant_name, Some("Red".to_string()));
         assert_eq!(result.arms[1].variant_name, Some("Green".to_string()));
         assert_eq!(result.arms[2].variant_name, Some("Blue".to_string()));
+        assert_eq!(result.arms[0].enum_name.as_deref(), Some("Color"));
+        assert_eq!((result.arms[0].line, result.arms[0].column), (6, 8));
+        assert_eq!(result.arms[2].match_line, 5);
+    }
+
+    #[test]
+    fn test_extract_keep_annotation() {
+        let content = r#"
+#[deadmod::keep(reason = "exhaustive on purpose")]
+fn kept(x: i32) {
+    match x { _ => {} }
+}
+
+fn plain(x: i32) {
+    match x { _ => {} }
+}
+"#;
+        let result = extract_match_arms(&PathBuf::from("test.rs"), content);
+        assert_eq!(result.arms.len(), 2);
+        assert_eq!(
+            result.arms[0].keep.as_ref().unwrap().reason.as_deref(),
+            Some("exhaustive on purpose")
+        );
+        assert!(result.arms[1].keep.is_none());
     }
 
     #[test]
@@ -2,7 +2,12 @@
 //!
 //! Builds a graph of match arms and identifies:
 //! - Unreachable arms (wildcards masking later arms)
-//! - Dead arms (patterns never matched)
+//! - Dead arms: arms of enum variants that are matched but never
+//!   constructed, once linked to the crate's enums with
+//!   [`MatchGraph::correlate_with_enums`]
+//!
+//! Arms inside `#[deadmod::keep]` functions or impl blocks are never
+//! reported; they are listed in [`MatchArmAnalysisResult::kept`] instead.
 //!
 //! Performance characteristics:
 //! - Graph build: O(|A| + |U|) where A = arms, U = usages
@@ -10,24 +15,84 @@
 
 use std::collections::HashSet;
 
+use serde::Serialize;
+
 use super::match_extractor::MatchArm;
 use super::match_usage::MatchUsageResult;
+use crate::common::{Confidence, ConfidenceSignals, KeptItem, Resolution};
+use crate::enums::EnumVariantDef;
 
 /// A potentially dead match arm.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DeadMatchArm {
     /// The pattern that is potentially dead
     pub pattern: String,
     /// Reason why this arm is considered dead
     pub reason: DeadArmReason,
+    /// Enum the arm matches: resolved against the crate's enums after
+    /// [`MatchGraph::correlate_with_enums`], else as qualified in the pattern
+    pub enum_name: Option<String>,
+    /// Variant the arm matches
+    pub variant_name: Option<String>,
     /// Source file
     pub file: String,
+    /// Line of the arm's pattern (1-indexed)
+    pub line: usize,
+    /// Column of the arm's pattern (0-indexed)
+    pub column: usize,
+    /// Line of the enclosing `match` expression (1-indexed)
+    pub match_line: usize,
+    /// Confidence that the arm is really dead
+    pub confidence: Confidence,
+}
+
+impl DeadMatchArm {
+    fn new(arm: &MatchArm, reason: DeadArmReason, confidence: Confidence) -> Self {
+        Self {
+            pattern: arm.pattern.clone(),
+            reason,
+            enum_name: arm.enum_name.clone(),
+            variant_name: arm.variant_name.clone(),
+            file: arm.file.clone(),
+            line: arm.line,
+            column: arm.column,
+            match_line: arm.match_line,
+            confidence,
+        }
+    }
+
+    /// The variant as `Enum::Variant`, if the arm is linked to an enum.
+    pub fn variant_path(&self) -> Option<String> {
+        Some(format!(
+            "{}::{}",
+            self.enum_name.as_ref()?,
+            self.variant_name.as_ref()?
+        ))
+    }
+
+    /// Why the arm is dead, as a sentence fragment for reports.
+    pub fn explanation(&self) -> String {
+        match self.reason {
+            DeadArmReason::NeverUsed => format!(
+                "arm unreachable because variant {} is never constructed",
+                self.variant_path()
+                    .or_else(|| self.variant_name.clone())
+                    .unwrap_or_else(|| self.pattern.clone())
+            ),
+            DeadArmReason::MaskedByWildcard => {
+                "arm unreachable because an earlier wildcard matches first".to_string()
+            }
+            DeadArmReason::NonFinalWildcard => {
+                "wildcard before the last arm masks the arms after it".to_string()
+            }
+        }
+    }
 }
 
 /// Reason why a match arm is considered dead.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum DeadArmReason {
-    /// The pattern's variant is never used/constructed
+    /// The arm's enum variant is matched but never constructed
     NeverUsed,
     /// A wildcard pattern before this arm makes it unreachable
     MaskedByWildcard,
@@ -35,6 +100,21 @@ pub enum DeadArmReason {
     NonFinalWildcard,
 }
 
+impl DeadArmReason {
+    /// Confidence that an arm flagged for this reason is really dead.
+    pub fn confidence(&self) -> Confidence {
+        match self {
+            // Positional masking is exact: rustc reports the same arms
+            Self::MaskedByWildcard => Confidence::High,
+            Self::NeverUsed | Self::NonFinalWildcard => ConfidenceSignals {
+                resolution: Resolution::Heuristic,
+                ..Default::default()
+            }
+            .score(),
+        }
+    }
+}
+
 /// Statistics about match arm analysis.
 #[derive(Debug, Clone, Default)]
 pub struct MatchArmStats {
@@ -50,10 +130,25 @@ pub struct MatchArmStats {
 pub struct MatchArmAnalysisResult {
     /// All potentially dead arms found
     pub dead_arms: Vec<DeadMatchArm>,
+    /// Arms that would be reported but are kept by `#[deadmod::keep]`
+    pub kept: Vec<KeptItem>,
     /// Statistics
     pub stats: MatchArmStats,
 }
 
+impl MatchArmAnalysisResult {
+    /// Drop dead arms below `min` confidence and update the dead counts.
+    pub fn retain_confidence(&mut self, min: Confidence) {
+        self.dead_arms.retain(|a| a.confidence >= min);
+        self.stats.dead_arm_count = self.dead_arms.len();
+        self.stats.masked_arm_count = self
+            .dead_arms
+            .iter()
+            .filter(|a| a.reason == DeadArmReason::MaskedByWildcard)
+            .count();
+    }
+}
+
 /// Graph for analyzing match arm usage.
 #[derive(Default)]
 pub struct MatchGraph {
@@ -61,12 +156,16 @@ pub struct MatchGraph {
     arms: Vec<MatchArm>,
     /// Total match expression count
     match_count: usize,
-    /// Set of used variant names (for future NeverUsed detection)
+    /// Set of used variant names
     #[allow(dead_code)]
     used_variants: HashSet<String>,
-    /// Set of used full paths (for future NeverUsed detection)
+    /// Set of used full paths
     #[allow(dead_code)]
     used_full_paths: HashSet<String>,
+    /// Variant names used in expressions
+    constructed_variants: HashSet<String>,
+    /// The crate's enum variants, see [`MatchGraph::correlate_with_enums`]
+    enums: Vec<EnumVariantDef>,
 }
 
 impl MatchGraph {
@@ -78,10 +177,12 @@ impl MatchGraph {
     ) -> Self {
         let mut used_variants = HashSet::new();
         let mut used_full_paths = HashSet::new();
+        let mut constructed_variants = HashSet::new();
 
         for usage in usages {
             used_variants.extend(usage.used_variants.clone());
             used_full_paths.extend(usage.used_full_paths.clone());
+            constructed_variants.extend(usage.constructed_variants.clone());
         }
 
         Self {
@@ -89,20 +190,70 @@ impl MatchGraph {
             match_count,
             used_variants,
             used_full_paths,
+            constructed_variants,
+            enums: Vec::new(),
         }
     }
 
-    /// Check if a variant pattern is used anywhere (constructed or matched).
-    /// Reserved for future NeverUsed detection.
-    #[allow(dead_code)]
-    fn is_variant_used(&self, variant_name: &str) -> bool {
-        self.used_variants.contains(variant_name)
+    /// Link arms to the enum variants declared in the crate.
+    ///
+    /// Arms then name the enum they match, and arms whose variant is never
+    /// constructed are reported as [`DeadArmReason::NeverUsed`]. Arms of
+    /// enums declared elsewhere (`Option`, dependencies) stay unlinked.
+    pub fn correlate_with_enums(mut self, declared: &[EnumVariantDef]) -> Self {
+        self.enums = declared.to_vec();
+        self
+    }
+
+    /// The declared variant `arm` matches: the only enum with a variant of
+    /// that name, narrowed by the pattern's qualifier if it has one.
+    fn resolve_variant(&self, arm: &MatchArm) -> Option<&EnumVariantDef> {
+        let variant = arm.variant_name.as_deref()?;
+        let mut candidates = self.enums.iter().filter(|v| {
+            v.variant_name == variant && arm.enum_name.as_ref().is_none_or(|e| *e == v.enum_name)
+        });
+        let first = candidates.next()?;
+        candidates
+            .all(|v| v.enum_name == first.enum_name)
+            .then_some(first)
+    }
+
+    /// Arms of linked variants that are matched but never constructed.
+    fn find_unconstructed_arms<'a>(
+        &'a self,
+        reported: &HashSet<(&str, usize, usize)>,
+    ) -> Vec<(DeadMatchArm, &'a MatchArm)> {
+        self.arms
+            .iter()
+            .filter(|arm| {
+                !arm.is_wildcard && !reported.contains(&(arm.file.as_str(), arm.line, arm.column))
+            })
+            .filter_map(|arm| {
+                let variant = self.resolve_variant(arm)?;
+                if variant.keep.is_some()
+                    || self.constructed_variants.contains(&variant.variant_name)
+                {
+                    return None;
+                }
+                let confidence = ConfidenceSignals {
+                    visibility: &variant.visibility,
+                    has_cfg: variant.has_cfg,
+                    resolution: Resolution::Heuristic,
+                    ..Default::default()
+                }
+                .score();
+                let mut dead = DeadMatchArm::new(arm, DeadArmReason::NeverUsed, confidence);
+                dead.enum_name = Some(variant.enum_name.clone());
+                Some((dead, arm))
+            })
+            .collect()
     }
 
     /// Find arms that might be masked by wildcards.
     ///
-    /// Returns arms that come after a wildcard in a match expression.
-    fn find_masked_arms(&self) -> Vec<DeadMatchArm> {
+    /// Returns arms that come after a wildcard in a match expression, split
+    /// into reported arms and arms kept by `#[deadmod::keep]`.
+    fn find_masked_arms(&self) -> (Vec<DeadMatchArm>, Vec<KeptItem>) {
         let mut dead = Vec::new();
 
         // Group arms by file and position to detect wildcard masking
@@ -126,10 +277,38 @@ impl MatchGraph {
         // Process last match expression
         self.check_wildcard_masking(&current_match, &mut dead);
 
-        dead
+        if !self.enums.is_empty() {
+            for (d, arm) in &mut dead {
+                if let Some(variant) = self.resolve_variant(arm) {
+                    d.enum_name = Some(variant.enum_name.clone());
+                }
+            }
+            let reported = dead
+                .iter()
+                .map(|(_, a)| (a.file.as_str(), a.line, a.column))
+                .collect();
+            let unconstructed = self.find_unconstructed_arms(&reported);
+            dead.extend(unconstructed);
+        }
+
+        let (kept, dead): (Vec<_>, Vec<_>) =
+            dead.into_iter().partition(|(_, arm)| arm.keep.is_some());
+        let kept = kept
+            .into_iter()
+            .filter_map(|(d, arm)| {
+                arm.keep
+                    .as_ref()
+                    .map(|keep| KeptItem::new(&d.pattern, &d.file, keep))
+            })
+            .collect();
+        (dead.into_iter().map(|(d, _)| d).collect(), kept)
     }
 
-    fn check_wildcard_masking(&self, arms: &[&MatchArm], dead: &mut Vec<DeadMatchArm>) {
+    fn check_wildcard_masking<'a>(
+        &self,
+        arms: &[&'a MatchArm],
+        dead: &mut Vec<(DeadMatchArm, &'a MatchArm)>,
+    ) {
         let mut found_wildcard = false;
         let mut wildcard_pos = 0;
 
@@ -137,45 +316,48 @@ impl MatchGraph {
             if arm.is_wildcard {
                 if i < arms.len() - 1 {
                     // Wildcard not in final position
-                    dead.push(DeadMatchArm {
-                        pattern: arm.pattern.clone(),
-                        reason: DeadArmReason::NonFinalWildcard,
-                        file: arm.file.clone(),
-                    });
+                    let reason = DeadArmReason::NonFinalWildcard;
+                    dead.push((
+                        DeadMatchArm::new(arm, reason.clone(), reason.confidence()),
+                        arm,
+                    ));
                 }
                 found_wildcard = true;
                 wildcard_pos = i;
             } else if found_wildcard && i > wildcard_pos {
                 // This arm comes after a wildcard
-                dead.push(DeadMatchArm {
-                    pattern: arm.pattern.clone(),
-                    reason: DeadArmReason::MaskedByWildcard,
-                    file: arm.file.clone(),
-                });
+                let reason = DeadArmReason::MaskedByWildcard;
+                dead.push((
+                    DeadMatchArm::new(arm, reason.clone(), reason.confidence()),
+                    arm,
+                ));
             }
         }
     }
 
     /// Find all dead match arms.
     pub fn find_dead(&self) -> Vec<DeadMatchArm> {
-        let mut dead = Vec::new();
+        self.find_dead_and_kept().0
+    }
 
+    fn find_dead_and_kept(&self) -> (Vec<DeadMatchArm>, Vec<KeptItem>) {
         // Find masked arms (wildcards in wrong position)
-        dead.extend(self.find_masked_arms());
-
-        // Note: "NeverUsed" detection would require knowing ALL possible
-        // enum variants, which we don't have from just match arm analysis.
-        // This would need to be combined with enum variant extraction.
+        let (mut dead, kept) = self.find_masked_arms();
 
         // Sort for consistent output
-        dead.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.pattern.cmp(&b.pattern)));
-
-        dead
+        dead.sort_by(|a, b| {
+            a.file
+                .cmp(&b.file)
+                .then_with(|| a.line.cmp(&b.line))
+                .then_with(|| a.pattern.cmp(&b.pattern))
+        });
+
+        (dead, kept)
     }
 
     /// Perform complete analysis and return structured result.
     pub fn analyze(&self) -> MatchArmAnalysisResult {
-        let dead = self.find_dead();
+        let (dead, kept) = self.find_dead_and_kept();
 
         let wildcard_count = self.arms.iter().filter(|a| a.is_wildcard).count();
         let masked_count = dead
@@ -191,7 +373,11 @@ impl MatchGraph {
             masked_arm_count: masked_count,
         };
 
-        MatchArmAnalysisResult { dead_arms: dead, stats }
+        MatchArmAnalysisResult {
+            dead_arms: dead,
+            kept,
+            stats,
+        }
     }
 
     /// Get total number of match arms.
@@ -213,6 +399,11 @@ mod tests {
             position: pos,
             total_arms: total,
             file: file.to_string(),
+            line: pos + 1,
+            column: 0,
+            match_line: 0,
+            enum_name: None,
+            keep: None,
         }
     }
 
@@ -231,6 +422,26 @@ mod tests {
         assert_eq!(result.dead_arms.len(), 0);
     }
 
+    #[test]
+    fn test_kept_arms_are_not_dead() {
+        let mut masked = make_arm("Color::Green", Some("Green"), false, 2, 3, "test.rs");
+        masked.keep = Some(crate::common::Keep {
+            reason: Some("generated code".to_string()),
+        });
+        let arms = vec![
+            make_arm("Color::Red", Some("Red"), false, 0, 3, "test.rs"),
+            make_arm("_", None, true, 1, 3, "test.rs"),
+            masked,
+        ];
+
+        let result = MatchGraph::new(arms, 1, &[]).analyze();
+        assert_eq!(result.dead_arms.len(), 1);
+        assert_eq!(result.dead_arms[0].reason, DeadArmReason::NonFinalWildcard);
+        assert_eq!(result.kept.len(), 1);
+        assert_eq!(result.kept[0].name, "Color::Green");
+        assert_eq!(result.kept[0].reason.as_deref(), Some("generated code"));
+    }
+
     #[test]
     fn test_wildcard_masks_later_arms() {
         let arms = vec![
@@ -247,6 +458,13 @@ mod tests {
         assert!(result.dead_arms.len() >= 2);
         assert!(result.dead_arms.iter().any(|d| d.reason == DeadArmReason::NonFinalWildcard));
         assert!(result.dead_arms.iter().any(|d| d.reason == DeadArmReason::MaskedByWildcard));
+        for arm in &result.dead_arms {
+            let expected = match arm.reason {
+                DeadArmReason::MaskedByWildcard => Confidence::High,
+                _ => Confidence::Medium,
+            };
+            assert_eq!(arm.confidence, expected);
+        }
     }
 
     #[test]
@@ -264,6 +482,7 @@ mod tests {
                 "Blue".to_string(),
             ]),
             used_full_paths: HashSet::new(),
+            constructed_variants: HashSet::new(),
         };
 
         let graph = MatchGraph::new(arms, 1, &[usages]);
@@ -272,6 +491,60 @@ mod tests {
         assert_eq!(result.dead_arms.len(), 0);
     }
 
+    #[test]
+    fn test_correlate_with_enums() {
+        let variant = |enum_name: &str, name: &str| EnumVariantDef {
+            enum_name: enum_name.to_string(),
+            variant_name: name.to_string(),
+            full_name: format!("{}::{}", enum_name, name),
+            file: "test.rs".to_string(),
+            line: 0,
+            module_path: String::new(),
+            visibility: "private".to_string(),
+            has_cfg: false,
+            keep: None,
+        };
+        let declared = vec![
+            variant("Color", "Red"),
+            variant("Color", "Blue"),
+            variant("Shade", "Blue"),
+        ];
+        let mut arms = vec![
+            make_arm("Color::Red", Some("Red"), false, 0, 3, "test.rs"),
+            make_arm("Color::Blue", Some("Blue"), false, 1, 3, "test.rs"),
+            make_arm("Blue", Some("Blue"), false, 2, 3, "test.rs"),
+        ];
+        arms[0].enum_name = Some("Color".to_string());
+        arms[1].enum_name = Some("Color".to_string());
+        let usages = MatchUsageResult {
+            constructed_variants: HashSet::from(["Red".to_string()]),
+            ..Default::default()
+        };
+
+        // Without enums, nothing links arms to variants
+        assert!(
+            MatchGraph::new(arms.clone(), 1, std::slice::from_ref(&usages))
+                .analyze()
+                .dead_arms
+                .is_empty()
+        );
+
+        let result = MatchGraph::new(arms, 1, &[usages])
+            .correlate_with_enums(&declared)
+            .analyze();
+        // The unqualified `Blue` arm is ambiguous between Color and Shade
+        assert_eq!(result.dead_arms.len(), 1);
+        let dead = &result.dead_arms[0];
+        assert_eq!(dead.reason, DeadArmReason::NeverUsed);
+        assert_eq!(dead.variant_path().as_deref(), Some("Color::Blue"));
+        assert_eq!(dead.line, 2);
+        assert_eq!(
+            dead.explanation(),
+            "arm unreachable because variant Color::Blue is never constructed"
+        );
+        assert_eq!(dead.confidence, Confidence::Medium);
+    }
+
     #[test]
     fn test_stats() {
         let arms = vec![
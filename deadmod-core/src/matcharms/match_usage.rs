@@ -5,11 +5,16 @@
 //! - Pattern matching in match/if let/while let
 //! - Path references in expressions
 //!
+//! Variants in expressions are also recorded as constructed: a variant
+//! that is only ever matched can never reach its arm.
+//!
 //! NASA-grade resilience: handles malformed AST gracefully.
 
 use std::collections::HashSet;
 use std::path::Path;
-use syn::{visit::Visit, Expr, File, Pat};
+use syn::{visit::Visit, Expr, File, Macro, Pat};
+
+use crate::common::visit_macro_args;
 
 /// Result of match usage analysis.
 #[derive(Debug, Clone, Default)]
@@ -18,12 +23,16 @@ pub struct MatchUsageResult {
     pub used_variants: HashSet<String>,
     /// Set of full paths used (e.g., "Color::Red")
     pub used_full_paths: HashSet<String>,
+    /// Variant names used in expressions (constructed, compared against)
+    /// rather than only in patterns
+    pub constructed_variants: HashSet<String>,
 }
 
 /// AST visitor that extracts variant usages.
 struct MatchUsageExtractor {
     used_variants: HashSet<String>,
     used_full_paths: HashSet<String>,
+    constructed_variants: HashSet<String>,
 }
 
 impl MatchUsageExtractor {
@@ -31,9 +40,18 @@ impl MatchUsageExtractor {
         Self {
             used_variants: HashSet::with_capacity(32),
             used_full_paths: HashSet::with_capacity(32),
+            constructed_variants: HashSet::with_capacity(32),
         }
     }
 
+    /// Record a path used in an expression.
+    fn record_construction(&mut self, path: &syn::Path) {
+        if let Some(seg) = path.segments.last() {
+            self.constructed_variants.insert(seg.ident.to_string());
+        }
+        self.record_path(path);
+    }
+
     fn record_path(&mut self, path: &syn::Path) {
         // Record the last segment (variant name)
         if let Some(seg) = path.segments.last() {
@@ -112,18 +130,18 @@ impl<'ast> Visit<'ast> for MatchUsageExtractor {
         match expr {
             // Path expressions: Color::Red, Option::Some
             Expr::Path(p) => {
-                self.record_path(&p.path);
+                self.record_construction(&p.path);
             }
 
             // Struct construction: Color::Red { field }
             Expr::Struct(s) => {
-                self.record_path(&s.path);
+                self.record_construction(&s.path);
             }
 
             // Call expressions: Option::Some(value)
             Expr::Call(c) => {
                 if let Expr::Path(p) = &*c.func {
-                    self.record_path(&p.path);
+                    self.record_construction(&p.path);
                 }
             }
 
@@ -137,6 +155,12 @@ impl<'ast> Visit<'ast> for MatchUsageExtractor {
         self.record_pattern(pat);
         syn::visit::visit_pat(self, pat);
     }
+
+    // Variants inside macro arguments: matches!(x, Color::Red)
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac, |v, found| v.record_construction(&found.path));
+        syn::visit::visit_macro(self, mac);
+    }
 }
 
 /// Extract all variant usages from file content.
@@ -158,6 +182,7 @@ pub fn extract_match_usages(path: &Path, content: &str) -> MatchUsageResult {
     MatchUsageResult {
         used_variants: extractor.used_variants,
         used_full_paths: extractor.used_full_paths,
+        constructed_variants: extractor.constructed_variants,
     }
 }
 
@@ -179,6 +204,7 @@ fn f() {
         assert!(result.used_variants.contains("Some"));
         assert!(result.used_full_paths.contains("Color::Red"));
         assert!(result.used_full_paths.contains("Option::Some"));
+        assert!(result.constructed_variants.contains("Red"));
     }
 
     #[test]
@@ -195,6 +221,7 @@ fn f(c: Color) {
         let result = extract_match_usages(&PathBuf::from("test.rs"), content);
         assert!(result.used_variants.contains("Red"));
         assert!(result.used_variants.contains("Green"));
+        assert!(!result.constructed_variants.contains("Red"));
     }
 
     #[test]
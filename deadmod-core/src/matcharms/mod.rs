@@ -45,11 +45,15 @@
 //! }
 //! ```
 
+pub mod arm_fix;
 pub mod match_extractor;
 pub mod match_graph;
 pub mod match_usage;
 
 // Re-exports for convenience
+pub use arm_fix::strip_variant_arms;
 pub use match_extractor::{extract_match_arms, MatchArm, MatchExtractionResult};
-pub use match_graph::{DeadArmReason, DeadMatchArm, MatchArmAnalysisResult, MatchArmStats, MatchGraph};
+pub use match_graph::{
+    DeadArmReason, DeadMatchArm, MatchArmAnalysisResult, MatchArmStats, MatchGraph,
+};
 pub use match_usage::{extract_match_usages, MatchUsageResult};
@@ -0,0 +1,314 @@
+//! Workspace layout from `cargo metadata`.
+//!
+//! Cargo knows which packages belong to a workspace (`members` globs,
+//! `exclude`, nested packages) and where each of their targets lives
+//! (`[[bin]] path = ...`, `autobins = false`, build scripts). Asking it
+//! instead of guessing from the directory tree gives the same answer
+//! `cargo build` works from.
+//!
+//! Runs `cargo metadata --no-deps`, so nothing is downloaded. When invoked
+//! as `cargo deadmod`, the `CARGO` variable names the cargo binary to use.
+//! Only built with the `cargo-metadata` feature; without it, or when cargo
+//! cannot be run, callers fall back to the directory-layout heuristics of
+//! [`crate::root`] and [`crate::workspace`].
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::edition::Edition;
+use crate::parse::{is_target_root_file, module_path_from_file};
+use crate::workspace::member_name;
+
+/// A package as reported by `cargo metadata`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CargoPackage {
+    /// Package name (`[package] name`)
+    pub name: String,
+    /// Opaque package ID, as listed in `workspace_members`
+    pub id: String,
+    /// Absolute path of the package's `Cargo.toml`
+    pub manifest_path: PathBuf,
+    /// Every target, auto-discovered ones included
+    pub targets: Vec<CargoTarget>,
+    /// Declared dependencies, of every kind
+    #[serde(default)]
+    pub dependencies: Vec<CargoDependency>,
+    /// `[features]`: each feature and what it enables
+    #[serde(default)]
+    pub features: BTreeMap<String, Vec<String>>,
+    /// `[package] edition`
+    pub edition: Option<Edition>,
+}
+
+impl CargoPackage {
+    /// Directory holding the package's `Cargo.toml`.
+    pub fn dir(&self) -> PathBuf {
+        self.manifest_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+
+    /// Root modules of the package's targets, named like
+    /// [`find_root_modules`] names them (`lib`, `main`, `cli`,
+    /// `tests::smoke`). Build scripts are included.
+    ///
+    /// [`find_root_modules`]: crate::root::find_root_modules
+    pub fn root_modules(&self) -> HashSet<String> {
+        let dir = self.dir();
+        self.targets
+            .iter()
+            .map(|target| target.root_module(&dir))
+            .collect()
+    }
+
+    /// Name other crates import the library under (`lib.name`, with `-`
+    /// as `_`), if the package has a library.
+    pub fn lib_name(&self) -> Option<String> {
+        self.targets
+            .iter()
+            .find(|target| target.is_lib())
+            .map(|target| target.name.replace('-', "_"))
+    }
+}
+
+/// A dependency as declared in a package's manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CargoDependency {
+    /// Name of the depended-on package
+    pub name: String,
+    /// Name it is imported under (`foo = { package = "bar" }`)
+    pub rename: Option<String>,
+    /// `dev`, `build`, or `None` for normal dependencies
+    pub kind: Option<String>,
+    /// Whether the dependency is only enabled by a feature
+    #[serde(default)]
+    pub optional: bool,
+    /// Directory of a `path` dependency
+    pub path: Option<PathBuf>,
+}
+
+/// A build target of a package.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CargoTarget {
+    /// Target name (`[[bin]] name`)
+    pub name: String,
+    /// `lib`, `bin`, `test`, `bench`, `example`, `custom-build`, ...
+    pub kind: Vec<String>,
+    /// Absolute path of the target's root file
+    pub src_path: PathBuf,
+}
+
+impl CargoTarget {
+    /// Whether this is the package's library (`lib`, `rlib`, `proc-macro`, ...).
+    pub fn is_lib(&self) -> bool {
+        self.kind.iter().any(|kind| {
+            matches!(
+                kind.as_str(),
+                "lib" | "rlib" | "dylib" | "cdylib" | "staticlib" | "proc-macro"
+            )
+        })
+    }
+
+    /// Module name of the target's root file within the package at `dir`.
+    pub fn root_module(&self, dir: &Path) -> String {
+        let file = self.src_path.strip_prefix(dir).unwrap_or(&self.src_path);
+        if is_target_root_file(file) {
+            return module_path_from_file(file);
+        }
+        let parent = file.parent();
+        let parent_name = |p: &Path| p.file_name().map(|n| n.to_string_lossy().into_owned());
+        match file.file_stem().map(|s| s.to_string_lossy()) {
+            // src/bin/<name>/main.rs
+            Some(stem)
+                if stem == "main"
+                    && parent
+                        .and_then(Path::parent)
+                        .and_then(parent_name)
+                        .as_deref()
+                        == Some("bin") =>
+            {
+                parent
+                    .and_then(parent_name)
+                    .unwrap_or_else(|| self.name.clone())
+            }
+            Some(stem) if stem == "mod" => parent
+                .and_then(parent_name)
+                .unwrap_or_else(|| self.name.clone()),
+            Some(stem) => stem.into_owned(),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// The part of `cargo metadata` output deadmod uses.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CargoMetadata {
+    /// Absolute path of the workspace root
+    pub workspace_root: PathBuf,
+    /// Workspace packages (only members, with `--no-deps`)
+    pub packages: Vec<CargoPackage>,
+    /// IDs of the packages that are workspace members
+    pub workspace_members: Vec<String>,
+}
+
+impl CargoMetadata {
+    /// Run `cargo metadata` for the package or workspace at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+        let output = Command::new(&cargo)
+            .args(["metadata", "--no-deps", "--format-version", "1"])
+            .current_dir(path)
+            .output()
+            .with_context(|| format!("Failed to run {}", Path::new(&cargo).display()))?;
+        if !output.status.success() {
+            bail!(
+                "cargo metadata failed in {}: {}",
+                path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Self::parse(&output.stdout)
+    }
+
+    /// Parse `cargo metadata --format-version 1` output.
+    pub fn parse(json: &[u8]) -> Result<Self> {
+        serde_json::from_slice(json).context("Invalid cargo metadata output")
+    }
+
+    /// Packages that are workspace members, in directory order.
+    pub fn members(&self) -> Vec<&CargoPackage> {
+        let ids: HashSet<&str> = self.workspace_members.iter().map(String::as_str).collect();
+        let mut members: Vec<&CargoPackage> = self
+            .packages
+            .iter()
+            .filter(|p| ids.contains(p.id.as_str()))
+            .collect();
+        members.sort_by(|a, b| a.manifest_path.cmp(&b.manifest_path));
+        members
+    }
+
+    /// The member whose package lives in `dir`.
+    pub fn member_at(&self, dir: &Path) -> Option<&CargoPackage> {
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        self.members()
+            .into_iter()
+            .find(|package| package.dir() == dir)
+    }
+
+    /// Dependency edges between members: for each member (by
+    /// [`member_name`]), the idents its code names other members by, mapped
+    /// to their member names. Renamed dependencies use their new name.
+    pub fn member_externs(&self) -> HashMap<String, HashMap<String, String>> {
+        let members = self.members();
+        let by_package: HashMap<&str, &CargoPackage> =
+            members.iter().map(|p| (p.name.as_str(), *p)).collect();
+        members
+            .iter()
+            .map(|package| {
+                let externs = package
+                    .dependencies
+                    .iter()
+                    .filter_map(|dep| {
+                        let target = by_package.get(dep.name.as_str())?;
+                        let ident = match &dep.rename {
+                            Some(rename) => rename.replace('-', "_"),
+                            None => target.lib_name()?,
+                        };
+                        Some((ident, member_name(&target.dir())))
+                    })
+                    .collect();
+                (member_name(&package.dir()), externs)
+            })
+            .collect()
+    }
+}
+
+/// Root modules of the Cargo package at `crate_root`, from its targets.
+///
+/// `None` when `cargo metadata` cannot be run there (cargo missing, no
+/// manifest, a crate outside its workspace's members).
+pub fn package_root_modules(crate_root: &Path) -> Option<HashSet<String>> {
+    if !crate_root.join("Cargo.toml").is_file() {
+        return None;
+    }
+    let meta = CargoMetadata::load(crate_root).ok()?;
+    meta.member_at(crate_root).map(CargoPackage::root_modules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"{
+        "workspace_root": "/ws",
+        "workspace_members": ["path+file:///ws/crates/app#0.1.0", "path+file:///ws/core#0.1.0"],
+        "packages": [
+            {
+                "name": "app",
+                "id": "path+file:///ws/crates/app#0.1.0",
+                "manifest_path": "/ws/crates/app/Cargo.toml",
+                "edition": "2021",
+                "dependencies": [
+                    {"name": "core", "rename": "engine", "kind": null, "path": "/ws/core"},
+                    {"name": "serde", "kind": null, "optional": true}
+                ],
+                "features": {"json": ["dep:serde"]},
+                "targets": [
+                    {"name": "app", "kind": ["bin"], "src_path": "/ws/crates/app/src/main.rs"},
+                    {"name": "gen", "kind": ["bin"], "src_path": "/ws/crates/app/tools/gen.rs"},
+                    {"name": "admin", "kind": ["bin"], "src_path": "/ws/crates/app/src/bin/admin/main.rs"},
+                    {"name": "smoke", "kind": ["test"], "src_path": "/ws/crates/app/tests/smoke.rs"},
+                    {"name": "build-script-build", "kind": ["custom-build"], "src_path": "/ws/crates/app/build.rs"}
+                ]
+            },
+            {
+                "name": "core",
+                "id": "path+file:///ws/core#0.1.0",
+                "manifest_path": "/ws/core/Cargo.toml",
+                "dependencies": [{"name": "app", "kind": "dev"}],
+                "targets": [{"name": "core-lib", "kind": ["lib"], "src_path": "/ws/core/src/lib.rs"}]
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_cargo_metadata() {
+        let meta = CargoMetadata::parse(METADATA.as_bytes()).unwrap();
+        let members: Vec<&str> = meta.members().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(members, ["core", "app"]);
+        assert_eq!(meta.packages[1].dir(), Path::new("/ws/core"));
+
+        let mut roots: Vec<String> = meta.packages[0].root_modules().into_iter().collect();
+        roots.sort();
+        assert_eq!(roots, ["admin", "build", "gen", "main", "tests::smoke"]);
+        assert_eq!(
+            meta.packages[1].root_modules(),
+            HashSet::from(["lib".to_string()])
+        );
+
+        assert!(CargoMetadata::parse(b"{}").is_err());
+    }
+
+    #[test]
+    fn test_member_externs() {
+        let meta = CargoMetadata::parse(METADATA.as_bytes()).unwrap();
+        let app = &meta.packages[0];
+        assert_eq!(app.edition, Some(Edition::E2021));
+        assert_eq!(app.features["json"], ["dep:serde"]);
+        assert_eq!(meta.packages[1].lib_name().as_deref(), Some("core_lib"));
+        assert_eq!(app.lib_name(), None);
+
+        // Renamed dependency on core; core's dev-dependency on app has no library to name
+        let externs = meta.member_externs();
+        assert_eq!(
+            externs["app"],
+            HashMap::from([("engine".to_string(), "core".to_string())])
+        );
+        assert!(externs["core"].is_empty());
+    }
+}
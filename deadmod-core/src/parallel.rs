@@ -0,0 +1,102 @@
+//! Thread pool control for parallel scanning, parsing and extraction.
+//!
+//! All parallel work uses rayon. By default it runs on rayon's global pool;
+//! with an explicit thread count (`--jobs N`, [`Deadmod::with_threads`] or the
+//! `DEADMOD_JOBS` environment variable) it runs on a dedicated pool instead,
+//! so deadmod stays polite on shared CI machines and does not compete with
+//! the global pool of a host process such as the LSP server.
+//!
+//! ```rust,ignore
+//! use deadmod_core::{resolve_jobs, run_with_threads};
+//!
+//! let graph = run_with_threads(resolve_jobs(Some(2)), || build_call_graph(&root))?;
+//! ```
+//!
+//! [`Deadmod::with_threads`]: crate::Deadmod::with_threads
+
+use anyhow::Result;
+
+use crate::error::DeadmodError;
+
+/// Environment variable holding the default worker thread count.
+pub const JOBS_ENV_VAR: &str = "DEADMOD_JOBS";
+
+/// Thread count from `DEADMOD_JOBS`, if set to a positive integer.
+///
+/// Invalid values are ignored with a warning.
+pub fn jobs_from_env() -> Option<usize> {
+    let value = std::env::var(JOBS_ENV_VAR).ok()?;
+    match parse_jobs(&value) {
+        Some(jobs) => Some(jobs),
+        None => {
+            eprintln!(
+                "WARN: Ignoring {}={:?} (expected a positive integer)",
+                JOBS_ENV_VAR, value
+            );
+            None
+        }
+    }
+}
+
+/// Effective thread count: an explicit value wins over `DEADMOD_JOBS`.
+///
+/// `None` means "use rayon's global pool".
+pub fn resolve_jobs(explicit: Option<usize>) -> Option<usize> {
+    explicit.or_else(jobs_from_env)
+}
+
+/// Build a dedicated pool with `threads` workers.
+pub fn build_thread_pool(threads: usize) -> Result<rayon::ThreadPool> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|i| format!("deadmod-worker-{}", i))
+        .build()
+        .map_err(|e| DeadmodError::internal(format!("Failed to build thread pool: {}", e)).into())
+}
+
+/// Run `op` with every rayon call inside it on a dedicated pool of `threads`
+/// workers, or directly on the global pool when `threads` is `None`.
+pub fn run_with_threads<R, F>(threads: Option<usize>, op: F) -> Result<R>
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    match threads {
+        Some(threads) => Ok(build_thread_pool(threads)?.install(op)),
+        None => Ok(op()),
+    }
+}
+
+fn parse_jobs(value: &str) -> Option<usize> {
+    value.trim().parse::<usize>().ok().filter(|&n| n > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_jobs() {
+        assert_eq!(parse_jobs("4"), Some(4));
+        assert_eq!(parse_jobs(" 2 "), Some(2));
+        assert_eq!(parse_jobs("0"), None);
+        assert_eq!(parse_jobs("many"), None);
+    }
+
+    #[test]
+    fn test_resolve_jobs_explicit_wins() {
+        assert_eq!(resolve_jobs(Some(3)), Some(3));
+    }
+
+    #[test]
+    fn test_run_with_threads_uses_dedicated_pool() {
+        let threads = run_with_threads(Some(2), rayon::current_num_threads).unwrap();
+        assert_eq!(threads, 2);
+
+        let name =
+            run_with_threads(Some(1), || std::thread::current().name().map(String::from)).unwrap();
+        assert_eq!(name.as_deref(), Some("deadmod-worker-0"));
+
+        assert_eq!(run_with_threads(None, || 7).unwrap(), 7);
+    }
+}
@@ -0,0 +1,49 @@
+//! Function parameter analysis for dead code detection.
+//!
+//! This module provides functionality to detect dead arguments: function
+//! parameters that the body never reads. Skipped:
+//! - `_`-prefixed parameters (unused on purpose)
+//! - Trait methods and trait impl methods, whose signatures must match
+//! - `extern` functions and proc macros, whose signatures are fixed
+//!
+//! Unused parameters can be renamed to `_name` ([`rename_unused_params`]),
+//! which keeps every caller compiling.
+//!
+//! # Architecture
+//!
+//! ```text
+//! ┌─────────────────────┐
+//! │ param_extractor.rs  │
+//! │  ─────────────────  │
+//! │  Extract parameters │
+//! │  and body reads     │
+//! └──────────┬──────────┘
+//!            ▼
+//! ┌─────────────────────┐     ┌─────────────────────┐
+//! │   param_graph.rs    │     │    param_fix.rs     │
+//! │  ─────────────────  │────▶│  ─────────────────  │
+//! │  Find unused params │     │  Rename to `_param` │
+//! └─────────────────────┘     └─────────────────────┘
+//! ```
+//!
+//! # Example
+//!
+//! ```ignore
+//! use deadmod_core::params::{extract_params, ParamGraph};
+//!
+//! let extraction = extract_params(&path, &content);
+//! let result = ParamGraph::new(&[extraction]).analyze();
+//!
+//! for dead in &result.dead {
+//!     println!("Unused parameter '{}' in {}", dead.name, dead.function);
+//! }
+//! ```
+
+pub mod param_extractor;
+pub mod param_fix;
+pub mod param_graph;
+
+// Re-exports for convenience
+pub use param_extractor::{extract_params, DeclaredParam, ParamExtractionResult};
+pub use param_fix::rename_unused_params;
+pub use param_graph::{DeadParam, ParamAnalysisResult, ParamGraph, ParamStats};
@@ -0,0 +1,358 @@
+//! Function parameter extraction from Rust AST.
+//!
+//! Extracts the parameters of every function whose signature the crate is
+//! free to change:
+//! - Free functions, inherent methods and nested functions
+//! - Not trait methods or trait impl methods, whose signatures are fixed by
+//!   the trait, nor `extern` functions and proc macros
+//!
+//! Only plain bindings (`x: T`, `mut x: T`) are extracted; receivers,
+//! destructuring patterns and `_`-prefixed names are skipped.
+//!
+//! Each parameter records whether the body reads it. Reads are matched by
+//! name: any identifier in the body counts, including inside macro
+//! invocations and inline format arguments (`"{name}"`). Shadowing can
+//! therefore hide an unused parameter, but never invent one.
+//!
+//! NASA-grade resilience: handles malformed AST gracefully.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use serde::{Deserialize, Serialize};
+use syn::{
+    visit::Visit, Attribute, Block, File, FnArg, ImplItemFn, ItemFn, ItemImpl, ItemTrait, Macro,
+    Pat, Signature, Type, Visibility,
+};
+
+use crate::common::{has_cfg_attr, keep_annotation, visibility_str, Keep};
+
+/// Attributes whose functions have a signature fixed by the compiler.
+const FIXED_SIGNATURE_ATTRS: &[&str] = &[
+    "proc_macro",
+    "proc_macro_attribute",
+    "proc_macro_derive",
+    "no_mangle",
+];
+
+/// A function parameter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeclaredParam {
+    /// Parameter name
+    pub name: String,
+    /// Function declaring it: `parse`, or `Parser::parse` for methods
+    pub function: String,
+    /// Position among the function's parameters (0-indexed, receiver not counted)
+    pub position: usize,
+    /// Source file path
+    pub file: String,
+    /// Line of the parameter name (1-indexed)
+    pub line: usize,
+    /// Column of the parameter name (0-indexed)
+    pub column: usize,
+    /// Visibility of the function
+    pub visibility: String,
+    /// Whether the function or the parameter has #[cfg] or #[cfg_attr]
+    #[serde(default)]
+    pub has_cfg: bool,
+    /// Whether the body reads the parameter
+    pub used: bool,
+    /// `#[deadmod::keep]` details from the function or its impl block
+    #[serde(default)]
+    pub keep: Option<Keep>,
+}
+
+/// Result of parameter extraction from a file.
+#[derive(Debug, Clone, Default)]
+pub struct ParamExtractionResult {
+    /// Parameters of every analyzed function
+    pub params: Vec<DeclaredParam>,
+    /// Number of functions analyzed
+    pub function_count: usize,
+}
+
+/// AST visitor that extracts parameters.
+struct ParamExtractor {
+    file_path: String,
+    result: ParamExtractionResult,
+    /// Self type of the enclosing inherent impl
+    impl_type: Option<String>,
+    /// `#[deadmod::keep]` of the innermost annotated enclosing item
+    keep: Option<Keep>,
+}
+
+impl ParamExtractor {
+    fn new(file_path: String) -> Self {
+        Self {
+            file_path,
+            result: ParamExtractionResult::default(),
+            impl_type: None,
+            keep: None,
+        }
+    }
+
+    fn record(&mut self, attrs: &[Attribute], vis: &Visibility, sig: &Signature, block: &Block) {
+        let fixed = sig.abi.is_some()
+            || attrs.iter().any(|a| {
+                FIXED_SIGNATURE_ATTRS
+                    .iter()
+                    .any(|name| a.path().is_ident(name))
+            });
+        if fixed {
+            return;
+        }
+        self.result.function_count += 1;
+
+        let function = match &self.impl_type {
+            Some(ty) => format!("{}::{}", ty, sig.ident),
+            None => sig.ident.to_string(),
+        };
+        let keep = keep_annotation(attrs).or_else(|| self.keep.clone());
+        let mut read: Option<HashSet<String>> = None;
+
+        let typed = sig.inputs.iter().filter_map(|arg| match arg {
+            FnArg::Typed(pt) => Some(pt),
+            FnArg::Receiver(_) => None,
+        });
+        for (position, pt) in typed.enumerate() {
+            let Pat::Ident(pi) = &*pt.pat else {
+                continue;
+            };
+            let name = pi.ident.to_string();
+            if pi.subpat.is_some() || name.starts_with('_') {
+                continue;
+            }
+            let read = read.get_or_insert_with(|| body_names(block));
+            let start = pi.ident.span().start();
+            self.result.params.push(DeclaredParam {
+                used: read.contains(&name),
+                name,
+                function: function.clone(),
+                position,
+                file: self.file_path.clone(),
+                line: start.line,
+                column: start.column,
+                visibility: visibility_str(vis).to_string(),
+                has_cfg: has_cfg_attr(attrs) || has_cfg_attr(&pt.attrs),
+                keep: keep.clone(),
+            });
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ParamExtractor {
+    fn visit_item_fn(&mut self, item: &'ast ItemFn) {
+        self.record(&item.attrs, &item.vis, &item.sig, &item.block);
+        // Nested functions are not methods of an enclosing impl
+        let impl_type = self.impl_type.take();
+        syn::visit::visit_item_fn(self, item);
+        self.impl_type = impl_type;
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        let outer = (self.impl_type.take(), self.keep.clone());
+        if let Some(keep) = keep_annotation(&item.attrs) {
+            self.keep = Some(keep);
+        }
+        if item.trait_.is_none() {
+            self.impl_type = Some(self_type_name(&item.self_ty));
+            syn::visit::visit_item_impl(self, item);
+        } else {
+            // Trait impl signatures must match the trait; only look inside bodies
+            for impl_item in &item.items {
+                if let syn::ImplItem::Fn(method) = impl_item {
+                    self.visit_block(&method.block);
+                }
+            }
+        }
+        (self.impl_type, self.keep) = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &'ast ImplItemFn) {
+        self.record(&item.attrs, &item.vis, &item.sig, &item.block);
+        let impl_type = self.impl_type.take();
+        syn::visit::visit_impl_item_fn(self, item);
+        self.impl_type = impl_type;
+    }
+
+    fn visit_item_trait(&mut self, item: &'ast ItemTrait) {
+        // Trait method signatures are fixed; nested functions in default bodies are not
+        let impl_type = self.impl_type.take();
+        syn::visit::visit_item_trait(self, item);
+        self.impl_type = impl_type;
+    }
+}
+
+/// Last path segment of an impl's self type (`Parser` for `impl<T> a::Parser<T>`).
+fn self_type_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(p) => p
+            .path
+            .segments
+            .last()
+            .map_or_else(|| "<unknown>".to_string(), |s| s.ident.to_string()),
+        Type::Reference(r) => self_type_name(&r.elem),
+        _ => "<unknown>".to_string(),
+    }
+}
+
+/// Every identifier in `block`, plus names in inline format arguments.
+fn body_names(block: &Block) -> HashSet<String> {
+    let mut collector = NameCollector::default();
+    collector.visit_block(block);
+    collector.names
+}
+
+/// Visitor collecting identifiers, including those inside macro tokens.
+#[derive(Default)]
+struct NameCollector {
+    names: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for NameCollector {
+    fn visit_ident(&mut self, ident: &'ast Ident) {
+        self.names
+            .insert(ident.to_string().trim_start_matches("r#").to_string());
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        collect_names(mac.tokens.clone(), &mut self.names);
+        syn::visit::visit_macro(self, mac);
+    }
+}
+
+fn collect_names(tokens: TokenStream, names: &mut HashSet<String>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                names.insert(ident.to_string().trim_start_matches("r#").to_string());
+            }
+            TokenTree::Group(group) => collect_names(group.stream(), names),
+            TokenTree::Literal(lit) => names.extend(format_args(&lit.to_string())),
+            TokenTree::Punct(_) => {}
+        }
+    }
+}
+
+/// Names used as inline format arguments in a string literal: `x` and `w`
+/// in `"{x:>w$}"`. Escaped braces (`{{`) are skipped.
+fn format_args(literal: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = literal;
+    while let Some(open) = rest.find('{') {
+        rest = &rest[open + 1..];
+        if let Some(escaped) = rest.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+        let end = rest.find('}').unwrap_or(rest.len());
+        let spec = &rest[..end];
+        let (arg, format) = spec.split_once(':').unwrap_or((spec, ""));
+        names.push(arg.trim().to_string());
+        names.extend(format.split('$').filter_map(|part| {
+            let name: String = part
+                .chars()
+                .rev()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .collect();
+            (!name.is_empty() && format.contains(&format!("{}$", name))).then_some(name)
+        }));
+        rest = &rest[end..];
+    }
+    names.retain(|n| n.starts_with(|c: char| c.is_alphabetic() || c == '_'));
+    names
+}
+
+/// Extract the parameters of every analyzable function in file content.
+///
+/// On parse error, returns an empty result (resilient behavior).
+pub fn extract_params(path: &Path, content: &str) -> ParamExtractionResult {
+    let ast: File = match syn::parse_file(content) {
+        Ok(ast) => ast,
+        Err(e) => {
+            eprintln!("[WARN] AST parse failed for {}: {}", path.display(), e);
+            return ParamExtractionResult::default();
+        }
+    };
+
+    let mut extractor = ParamExtractor::new(path.display().to_string());
+    extractor.visit_file(&ast);
+    extractor.result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn unused(content: &str) -> Vec<String> {
+        extract_params(&PathBuf::from("test.rs"), content)
+            .params
+            .into_iter()
+            .filter(|p| !p.used)
+            .map(|p| format!("{} in {}", p.name, p.function))
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_unused_params() {
+        let content = r#"
+fn add(a: i32, b: i32, _c: i32, mut d: i32) -> i32 {
+    d += 1;
+    a + d
+}
+
+struct Parser;
+
+impl Parser {
+    fn parse(&self, input: &str, strict: bool) -> usize {
+        println!("{input:>width$}", width = 4);
+        fn nested(depth: u8) {}
+        0
+    }
+}
+
+trait Visit {
+    fn visit(&self, node: u32) {}
+}
+
+impl Visit for Parser {
+    fn visit(&self, node: u32) {}
+}
+
+extern "C" fn callback(code: i32) {}
+"#;
+        assert_eq!(
+            unused(content),
+            ["b in add", "strict in Parser::parse", "depth in nested"]
+        );
+    }
+
+    #[test]
+    fn test_extract_param_location() {
+        let content = "fn f(\n    used: u8,\n    dead: u8,\n) -> u8 {\n    used\n}\n";
+        let result = extract_params(&PathBuf::from("test.rs"), content);
+        assert_eq!(result.function_count, 1);
+        let dead = &result.params[1];
+        assert_eq!(
+            (dead.name.as_str(), dead.position, dead.line, dead.column),
+            ("dead", 1, 3, 4)
+        );
+        assert!(result.params[0].used && !dead.used);
+    }
+
+    #[test]
+    fn test_format_args() {
+        assert_eq!(format_args("\"{x} {{y}} {z:>w$} {0} {}\""), ["x", "z", "w"]);
+    }
+
+    #[test]
+    fn test_malformed_resilient() {
+        let result = extract_params(&PathBuf::from("broken.rs"), "fn f(x: i32 {");
+        assert!(result.params.is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+//! Renaming of unused parameters.
+//!
+//! An unused parameter cannot simply be removed: every caller passes it.
+//! Prefixing it with `_` instead documents that it is unused on purpose,
+//! silences the detector and rustc's `unused_variables` lint, and leaves
+//! callers untouched. Applied by [`crate::fix::fix_dead_params`].
+
+use std::path::Path;
+
+use proc_macro2::LineColumn;
+
+use super::param_extractor::extract_params;
+use crate::common::SourceEdits;
+
+/// Prefix the `(function, parameter)` pairs in `content` with `_`.
+///
+/// Parameters are matched the way [`extract_params`] names them, and only
+/// renamed if the body still does not read them.
+///
+/// Returns the new content and the number of parameters renamed, or `None`
+/// when nothing matched or the file does not parse.
+pub fn rename_unused_params(content: &str, targets: &[(&str, &str)]) -> Option<(String, usize)> {
+    syn::parse_file(content).ok()?;
+    let edits = SourceEdits::new(content);
+    let mut offsets: Vec<usize> = extract_params(Path::new(""), content)
+        .params
+        .iter()
+        .filter(|p| !p.used && targets.contains(&(p.function.as_str(), p.name.as_str())))
+        .map(|p| {
+            edits.offset(LineColumn {
+                line: p.line,
+                column: p.column,
+            })
+        })
+        .collect();
+    if offsets.is_empty() {
+        return None;
+    }
+
+    // Insert back to front so earlier offsets stay valid
+    offsets.sort_unstable_by(|a, b| b.cmp(a));
+    offsets.dedup();
+    let mut out = content.to_string();
+    for &offset in &offsets {
+        out.insert(offset, '_');
+    }
+    Some((out, offsets.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_unused_params() {
+        let content = "fn f(used: u8, mut dead: u8) -> u8 {\n    used\n}\n\nstruct S;\nimpl S {\n    fn g(&self, dead: u8) {}\n}\n";
+        let (renamed, count) =
+            rename_unused_params(content, &[("f", "dead"), ("S::g", "dead"), ("f", "used")])
+                .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            renamed,
+            "fn f(used: u8, mut _dead: u8) -> u8 {\n    used\n}\n\nstruct S;\nimpl S {\n    fn g(&self, _dead: u8) {}\n}\n"
+        );
+        assert!(rename_unused_params(&renamed, &[("f", "dead")]).is_none());
+    }
+}
@@ -0,0 +1,182 @@
+//! Parameter analysis for dead argument detection.
+//!
+//! Reports the parameters their function's body never reads. Parameters of
+//! `#[deadmod::keep]` functions (or functions in kept impl blocks) are never
+//! reported; the functions are listed in [`ParamAnalysisResult::kept`].
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use super::param_extractor::{DeclaredParam, ParamExtractionResult};
+use crate::common::{Confidence, ConfidenceSignals, KeptItem};
+
+/// A function parameter the body never reads.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadParam {
+    /// Parameter name
+    pub name: String,
+    /// Function declaring it (`parse`, `Parser::parse`)
+    pub function: String,
+    /// Position among the function's parameters (0-indexed)
+    pub position: usize,
+    /// Source file
+    pub file: String,
+    /// Line of the parameter name (1-indexed)
+    pub line: usize,
+    /// Column of the parameter name (0-indexed)
+    pub column: usize,
+    /// Visibility of the function
+    pub visibility: String,
+    /// Confidence that the parameter is really unused
+    pub confidence: Confidence,
+}
+
+/// Statistics about parameter analysis.
+#[derive(Debug, Clone, Default)]
+pub struct ParamStats {
+    pub total_functions: usize,
+    pub total_params: usize,
+    pub dead_param_count: usize,
+}
+
+/// Result of parameter analysis.
+#[derive(Debug, Clone)]
+pub struct ParamAnalysisResult {
+    /// All unused parameters found
+    pub dead: Vec<DeadParam>,
+    /// Functions whose parameters are kept by `#[deadmod::keep]`
+    pub kept: Vec<KeptItem>,
+    /// Statistics
+    pub stats: ParamStats,
+}
+
+impl ParamAnalysisResult {
+    /// Drop dead parameters below `min` confidence and update the dead count.
+    pub fn retain_confidence(&mut self, min: Confidence) {
+        self.dead.retain(|p| p.confidence >= min);
+        self.stats.dead_param_count = self.dead.len();
+    }
+}
+
+/// Graph for analyzing parameter usage.
+#[derive(Default)]
+pub struct ParamGraph {
+    /// All extracted parameters
+    declared: Vec<DeclaredParam>,
+    /// Number of functions analyzed
+    function_count: usize,
+}
+
+impl ParamGraph {
+    /// Create a new parameter graph from extraction results.
+    pub fn new(extractions: &[ParamExtractionResult]) -> Self {
+        Self {
+            declared: extractions
+                .iter()
+                .flat_map(|e| e.params.iter().cloned())
+                .collect(),
+            function_count: extractions.iter().map(|e| e.function_count).sum(),
+        }
+    }
+
+    /// Find all unused parameters, sorted by file and position.
+    pub fn find_dead(&self) -> Vec<DeadParam> {
+        let mut dead: Vec<DeadParam> = self
+            .declared
+            .iter()
+            .filter(|p| !p.used && p.keep.is_none())
+            .map(|p| DeadParam {
+                name: p.name.clone(),
+                function: p.function.clone(),
+                position: p.position,
+                file: p.file.clone(),
+                line: p.line,
+                column: p.column,
+                visibility: p.visibility.clone(),
+                confidence: ConfidenceSignals {
+                    visibility: &p.visibility,
+                    has_cfg: p.has_cfg,
+                    ..Default::default()
+                }
+                .score(),
+            })
+            .collect();
+
+        dead.sort_by(|a, b| {
+            a.file
+                .cmp(&b.file)
+                .then_with(|| a.line.cmp(&b.line))
+                .then_with(|| a.column.cmp(&b.column))
+        });
+        dead
+    }
+
+    /// Perform complete analysis and return structured result.
+    pub fn analyze(&self) -> ParamAnalysisResult {
+        let dead = self.find_dead();
+
+        // One entry per kept function, not per parameter
+        let mut seen = HashSet::new();
+        let kept = self
+            .declared
+            .iter()
+            .filter(|p| !p.used)
+            .filter(|p| seen.insert((&p.file, &p.function)))
+            .filter_map(|p| {
+                p.keep
+                    .as_ref()
+                    .map(|keep| KeptItem::new(&p.function, &p.file, keep))
+            })
+            .collect();
+
+        let stats = ParamStats {
+            total_functions: self.function_count,
+            total_params: self.declared.len(),
+            dead_param_count: dead.len(),
+        };
+
+        ParamAnalysisResult { dead, kept, stats }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::extract_params;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_param_graph_confidence_and_keep() {
+        let content = r#"
+fn private(used: u8, dead: u8) -> u8 { used }
+pub fn public(dead: u8) {}
+#[deadmod::keep(reason = "callback signature")]
+fn kept(dead: u8) {}
+fn cfg_gated(#[cfg(unix)] dead: u8) {}
+"#;
+        let extraction = extract_params(&PathBuf::from("test.rs"), content);
+        let mut result = ParamGraph::new(&[extraction]).analyze();
+
+        let dead: Vec<(&str, Confidence)> = result
+            .dead
+            .iter()
+            .map(|p| (p.function.as_str(), p.confidence))
+            .collect();
+        assert_eq!(
+            dead,
+            [
+                ("private", Confidence::High),
+                ("public", Confidence::Low),
+                ("cfg_gated", Confidence::Medium)
+            ]
+        );
+        assert_eq!(result.kept.len(), 1);
+        assert_eq!(result.kept[0].name, "kept");
+        assert_eq!(result.stats.total_functions, 4);
+        assert_eq!(result.stats.total_params, 5);
+
+        result.retain_confidence(Confidence::High);
+        assert_eq!(result.stats.dead_param_count, 1);
+    }
+}
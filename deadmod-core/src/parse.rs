@@ -9,12 +9,23 @@
 
 use anyhow::{Context, Result};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     fs,
     path::{Path, PathBuf},
 };
-use syn::{File, Item, ItemMod, UsePath, UseTree, Visibility as SynVisibility};
+
+use crate::collision::key_modules;
+use crate::common::is_cfg_test;
+use crate::config::load_config;
+use crate::edition::Edition;
+use syn::punctuated::Punctuated;
+use syn::visit::Visit;
+use syn::{
+    Attribute, Expr, File, Item, ItemMod, ItemUse, Lit, Macro, Token, UsePath, UseTree,
+    Visibility as SynVisibility,
+};
 
 /// Rust path keywords that should not be treated as module dependencies.
 const PATH_KEYWORDS: &[&str] = &["self", "super", "crate"];
@@ -24,7 +35,8 @@ const PATH_KEYWORDS: &[&str] = &["self", "super", "crate"];
 const MAX_FILE_SIZE: usize = 10_000_000;
 
 /// Visibility level of a module or item.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Visibility {
     /// Private (default) - `mod foo;`
     #[default]
@@ -70,6 +82,94 @@ impl From<&SynVisibility> for Visibility {
     }
 }
 
+/// Kind of reference that makes one module depend on another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    /// `mod foo;` declaration
+    Mod,
+    /// Private `use foo::...;` import
+    Use,
+    /// `pub use foo::...;` re-export (any `pub(...)` visibility)
+    PubUse,
+    /// Qualified path in code: `foo::bar()`, `crate::foo::Bar`
+    Path,
+    /// `include!("foo.rs")`, always an edge
+    Include,
+}
+
+impl EdgeKind {
+    /// Name as written in deadmod.toml and exports.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EdgeKind::Mod => "mod",
+            EdgeKind::Use => "use",
+            EdgeKind::PubUse => "pub_use",
+            EdgeKind::Path => "path",
+            EdgeKind::Include => "include",
+        }
+    }
+}
+
+/// Reference kinds that create module graph edges.
+///
+/// The default, `mod`, `use` and `pub_use`, matches how `rustc` resolves
+/// modules by name. Adding `path` also counts modules named only in
+/// qualified paths (`net::connect()` without a `use`), trading fewer false
+/// positives for more false negatives. `include!` edges are always kept.
+/// Set in deadmod.toml as `edges = ["mod", "use", "pub_use", "path"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EdgeKinds(BTreeSet<EdgeKind>);
+
+impl Default for EdgeKinds {
+    fn default() -> Self {
+        Self::new([EdgeKind::Mod, EdgeKind::Use, EdgeKind::PubUse])
+    }
+}
+
+impl EdgeKinds {
+    /// Exactly `kinds` (plus `include`).
+    pub fn new(kinds: impl IntoIterator<Item = EdgeKind>) -> Self {
+        Self(kinds.into_iter().collect())
+    }
+
+    /// Every kind, `path` included.
+    pub fn all() -> Self {
+        Self::new([
+            EdgeKind::Mod,
+            EdgeKind::Use,
+            EdgeKind::PubUse,
+            EdgeKind::Path,
+        ])
+    }
+
+    /// The crate's `edges` setting in deadmod.toml, or the default.
+    pub fn for_crate(root: &Path) -> Self {
+        match load_config(root) {
+            Ok(cfg) => cfg.and_then(|c| c.edges).unwrap_or_default(),
+            Err(e) => {
+                eprintln!("[WARN] config load failed: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Whether a reference made through `kinds` is an edge.
+    pub fn selects(&self, kinds: &BTreeSet<EdgeKind>) -> bool {
+        kinds
+            .iter()
+            .any(|kind| *kind == EdgeKind::Include || self.0.contains(kind))
+    }
+}
+
+/// Recompute every module's edges ([`ModuleInfo::refs`]) for `kinds`.
+pub fn select_edges(mods: &mut HashMap<String, ModuleInfo>, kinds: &EdgeKinds) {
+    for info in mods.values_mut() {
+        info.select_edges(kinds);
+    }
+}
+
 /// Normalize a path string to use forward slashes consistently.
 ///
 /// This ensures cross-platform consistency when paths are used as keys,
@@ -86,15 +186,120 @@ pub fn path_to_normalized_string(path: &Path) -> String {
     normalize_path_string(&path.display().to_string())
 }
 
+/// Module path of the crate root (`src/lib.rs`, `src/main.rs`).
+pub const CRATE_ROOT_PATH: &str = "crate";
+
+/// Directories holding Cargo's test, bench and example targets.
+pub const TARGET_DIRS: &[&str] = &["tests", "benches", "examples"];
+
+/// Crate-relative module path of a source file.
+///
+/// Both layouts map to the same path: `src/net/tcp/mod.rs` and
+/// `src/net/tcp.rs` are `net::tcp`. The crate root is [`CRATE_ROOT_PATH`],
+/// binaries are `bin::<name>` (`src/bin/x.rs`, `src/bin/x/main.rs`), and
+/// files in `tests/`, `benches/` and `examples/` keep that directory as their
+/// first segment (`tests/it.rs` and `tests/it/main.rs` are `tests::it`). Any
+/// other file outside `src/` falls back to its stem.
+pub fn module_path_from_file(path: &Path) -> String {
+    let parts: Vec<String> = path
+        .components()
+        .filter_map(|c| match c {
+            std::path::Component::Normal(s) => Some(s.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+
+    let mut segments: Vec<String> = match parts.iter().rposition(|p| p == "src") {
+        Some(src) => parts[src + 1..].to_vec(),
+        None => match parts
+            .iter()
+            .rposition(|p| TARGET_DIRS.contains(&p.as_str()))
+        {
+            Some(dir) => parts[dir..].to_vec(),
+            None => parts.last().cloned().into_iter().collect(),
+        },
+    };
+
+    if let Some(last) = segments.pop() {
+        let stem = last.strip_suffix(".rs").unwrap_or(&last).to_string();
+        let is_bin_main = stem == "main"
+            && segments.len() == 2
+            && (segments[0] == "bin" || TARGET_DIRS.contains(&segments[0].as_str()));
+        let is_root = segments.is_empty() && (stem == "lib" || stem == "main");
+        if stem != "mod" && !is_root && !is_bin_main {
+            segments.push(stem);
+        }
+    }
+
+    if segments.is_empty() {
+        CRATE_ROOT_PATH.to_string()
+    } else {
+        segments.join("::")
+    }
+}
+
+/// Whether `path` is the root of a test, bench or example target:
+/// `tests/x.rs` or `tests/x/main.rs` (likewise under `benches/` and
+/// `examples/`), outside any `src/` directory.
+///
+/// `tests/common/mod.rs` is not a target; it is a module of the targets
+/// declaring `mod common;`.
+pub fn is_target_root_file(path: &Path) -> bool {
+    let parts: Vec<String> = path
+        .components()
+        .filter_map(|c| match c {
+            std::path::Component::Normal(s) => Some(s.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    if parts.iter().any(|p| p == "src") {
+        return false;
+    }
+    let Some(dir) = parts
+        .iter()
+        .rposition(|p| TARGET_DIRS.contains(&p.as_str()))
+    else {
+        return false;
+    };
+    match &parts[dir + 1..] {
+        [file] => file.ends_with(".rs") && file != "mod.rs",
+        [_, file] => file == "main.rs",
+        _ => false,
+    }
+}
+
 /// Stores metadata for a single module file.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleInfo {
     /// Path to the module file
     pub path: PathBuf,
     /// Module name (file stem)
     pub name: String,
-    /// Referenced modules (dependencies)
+    /// Crate-relative module path (`net::tcp::listener`), see [`module_path_from_file`]
+    pub module_path: String,
+    /// Whether another file has the same module path, see [`crate::collision`]
+    pub collision: bool,
+    /// Workspace member the module belongs to, set when crates are combined
+    /// into one graph ([`combine_crate_modules`](crate::combine_crate_modules));
+    /// `None` for a single crate
+    #[serde(default)]
+    pub krate: Option<String>,
+    /// Whether the file carries a generated-code header, see
+    /// [`GeneratedMarkers`](crate::scan::GeneratedMarkers)
+    #[serde(default)]
+    pub generated: bool,
+    /// Referenced modules (dependencies): the graph edges, limited to the
+    /// selected [`EdgeKinds`]
     pub refs: HashSet<String>,
+    /// Every referenced module name with the ways it is referenced,
+    /// whether or not that kind is selected as an edge. Names written with
+    /// a leading `::` (`::net::connect()`) keep it: whether they name a
+    /// module depends on [`ModuleInfo::edition`]
+    #[serde(default)]
+    pub ref_kinds: HashMap<String, BTreeSet<EdgeKind>>,
+    /// Edition of the crate the file belongs to
+    #[serde(default)]
+    pub edition: Edition,
     /// Module's own visibility (if declared via `mod` statement)
     pub visibility: Visibility,
     /// Whether this module has `#[doc(hidden)]`
@@ -103,6 +308,18 @@ pub struct ModuleInfo {
     pub mod_decls: HashMap<String, Visibility>,
     /// Re-exports from this module (`pub use`)
     pub reexports: HashSet<String>,
+    /// `include!` sites in this module
+    pub includes: Vec<IncludeSite>,
+    /// Inline `#[cfg(test)] mod name { ... }` modules declared at the top
+    /// level of the file (test code tied to this file, never dead)
+    #[serde(default)]
+    pub test_mods: BTreeSet<String>,
+    /// External `mod` declarations compiled only under some `#[cfg]`, in
+    /// source order. Every configuration is analyzed at once, so each
+    /// alternative (`#[cfg(unix)] mod sys;` next to
+    /// `#[cfg(windows)] mod sys;`) is a `mod` edge of this module
+    #[serde(default)]
+    pub cfg_mods: Vec<CfgModDecl>,
 }
 
 impl ModuleInfo {
@@ -114,28 +331,218 @@ impl ModuleInfo {
             .unwrap_or_default()
             .to_string_lossy()
             .to_string();
+        let module_path = module_path_from_file(&path);
         Self {
             path,
             name,
+            module_path,
+            collision: false,
+            krate: None,
+            generated: false,
             refs: HashSet::with_capacity(8),
+            ref_kinds: HashMap::with_capacity(8),
+            edition: Edition::default(),
+            test_mods: BTreeSet::new(),
+            cfg_mods: Vec::new(),
             visibility: Visibility::Private,
             doc_hidden: false,
             mod_decls: HashMap::with_capacity(4),
             reexports: HashSet::with_capacity(4),
+            includes: Vec::new(),
         }
     }
 
+    /// Record `include!` sites; each included file counts as referenced by
+    /// this module so it is reachable whenever the includer is.
+    pub fn add_includes(&mut self, includes: Vec<IncludeSite>) {
+        for site in &includes {
+            if let Some(stem) = site.target_stem() {
+                self.ref_kinds
+                    .entry(stem.clone())
+                    .or_default()
+                    .insert(EdgeKind::Include);
+                self.refs.insert(stem);
+            }
+        }
+        self.includes.extend(includes);
+    }
+
+    /// Keep as edges the references made through `kinds`.
+    pub fn select_edges(&mut self, kinds: &EdgeKinds) {
+        let crate_relative = self.edition.crate_relative_absolute_paths();
+        self.refs = self
+            .ref_kinds
+            .iter()
+            .filter(|(_, how)| kinds.selects(how))
+            .filter_map(|(name, _)| match name.strip_prefix("::") {
+                // A crate-root module in 2015, another crate since
+                Some(root) => crate_relative.then(|| root.to_string()),
+                None => Some(name.clone()),
+            })
+            .collect();
+    }
+
     /// Check if this module might be used externally (pub and not doc(hidden)).
     pub fn is_potentially_external(&self) -> bool {
         self.visibility.is_potentially_external() && !self.doc_hidden
     }
 }
 
+/// A `mod name;` declaration gated by `#[cfg(...)]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CfgModDecl {
+    /// Declared module name
+    pub name: String,
+    /// The cfg predicate as written (`windows`, `not(unix)`); several
+    /// `#[cfg]` attributes are combined as `all(..)`
+    pub cfg: String,
+    /// `#[path = "..."]` of the declaration, as written (relative to the
+    /// declaring file's directory)
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+impl CfgModDecl {
+    /// File the declaration loads: its `#[path]` target, resolved against
+    /// the directory of `declaring_file`. `None` without a `#[path]`.
+    pub fn target_file(&self, declaring_file: &Path) -> Option<PathBuf> {
+        let path = self.path.as_deref()?;
+        let dir = declaring_file.parent().unwrap_or(Path::new(""));
+        Some(normalize_lexically(&dir.join(path)))
+    }
+}
+
+/// `path` with `.` and `..` resolved lexically (symlinks are not followed).
+pub(crate) fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir if out.file_name().is_some() => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// An `include!` invocation found in a module.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncludeSite {
+    /// Path as written, with any `OUT_DIR` prefix dropped
+    /// (`generated.rs`, `/bindings.rs`)
+    pub literal: String,
+    /// Literal path resolved against the including file's directory;
+    /// `None` for `OUT_DIR` includes
+    pub resolved: Option<PathBuf>,
+    /// Whether the path is built from `env!("OUT_DIR")` (build-script output)
+    pub out_dir: bool,
+}
+
+impl IncludeSite {
+    /// File stem of the included file, used as its module name.
+    pub fn target_stem(&self) -> Option<String> {
+        Path::new(self.literal.trim_start_matches('/'))
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+    }
+}
+
+/// Collects `include!` sites at any depth (items, statements, expressions).
+struct IncludeVisitor<'a> {
+    dir: Option<&'a Path>,
+    sites: Vec<IncludeSite>,
+}
+
+impl<'ast> Visit<'ast> for IncludeVisitor<'_> {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let is_include = mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "include");
+        if is_include {
+            if let Ok(arg) = mac.parse_body::<Expr>() {
+                if let Some(site) = include_site(&arg, self.dir) {
+                    self.sites.push(site);
+                }
+            }
+        }
+        syn::visit::visit_macro(self, mac);
+    }
+}
+
+/// Interpret the argument of `include!`: a string literal, or
+/// `concat!(env!("OUT_DIR"), "/file.rs")`.
+fn include_site(arg: &Expr, dir: Option<&Path>) -> Option<IncludeSite> {
+    match arg {
+        Expr::Lit(lit) => {
+            let Lit::Str(s) = &lit.lit else { return None };
+            let literal = s.value();
+            Some(IncludeSite {
+                resolved: dir.map(|d| d.join(&literal)),
+                literal,
+                out_dir: false,
+            })
+        }
+        Expr::Macro(m) if m.mac.path.is_ident("concat") => {
+            let parts = m
+                .mac
+                .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+                .ok()?;
+            let mut literal = String::new();
+            let mut out_dir = false;
+            for part in &parts {
+                match part {
+                    Expr::Lit(lit) => {
+                        if let Lit::Str(s) = &lit.lit {
+                            literal.push_str(&s.value());
+                        }
+                    }
+                    Expr::Macro(env) if env.mac.path.is_ident("env") => {
+                        out_dir |= env.mac.tokens.to_string().contains("OUT_DIR");
+                    }
+                    _ => {}
+                }
+            }
+            Some(IncludeSite {
+                resolved: if out_dir {
+                    None
+                } else {
+                    dir.map(|d| d.join(&literal))
+                },
+                literal,
+                out_dir,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Finds `include!` sites in `content`.
+///
+/// Literal paths are resolved relative to `file`'s directory, as rustc does.
+pub fn extract_includes(content: &str, file: &Path) -> Result<Vec<IncludeSite>> {
+    let ast: File = syn::parse_file(content).context("AST parse error")?;
+    Ok(collect_includes(&ast, file))
+}
+
+fn collect_includes(ast: &File, file: &Path) -> Vec<IncludeSite> {
+    let mut visitor = IncludeVisitor {
+        dir: file.parent(),
+        sites: Vec::new(),
+    };
+    visitor.visit_file(ast);
+    visitor.sites
+}
+
 /// Result of parsing a single module - used for granular parallel control.
 #[derive(Debug)]
 pub enum ParseResult {
     /// Successfully parsed module
-    Ok(String, ModuleInfo),
+    /// ModuleInfo is boxed to reduce enum size (clippy::large_enum_variant)
+    Ok(String, Box<ModuleInfo>),
     /// Parse failed (logged, can be skipped)
     Skipped(PathBuf, String),
 }
@@ -206,8 +613,181 @@ fn extract_path_root(tree: &UseTree, refs: &mut HashSet<String>) {
 /// - `use` statements extract only root path components (not nested items)
 pub fn extract_uses_and_decls(content: &str, refs: &mut HashSet<String>) -> Result<()> {
     let ast: File = syn::parse_file(content).context("AST parse error")?;
+    collect_uses_and_decls(ast.items, refs);
+    Ok(())
+}
+
+/// Parses `content` once and fills `info` with its dependencies, the
+/// visibility of its `mod` declarations and its `include!` sites.
+pub fn extract_dependencies(content: &str, info: &mut ModuleInfo) -> Result<()> {
+    let ast: File = syn::parse_file(content).context("AST parse error")?;
+    let includes = collect_includes(&ast, &info.path);
+    collect_mod_decls(&ast.items, &mut info.mod_decls);
+    info.cfg_mods = collect_cfg_mods(&ast.items);
+    info.test_mods = ast
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Mod(m) if m.content.is_some() && is_cfg_test(&m.attrs) => {
+                Some(m.ident.to_string())
+            }
+            _ => None,
+        })
+        .collect();
+    info.ref_kinds = collect_ref_kinds(&ast);
+    info.select_edges(&EdgeKinds::default());
+    info.add_includes(includes);
+    Ok(())
+}
 
-    for item in ast.items {
+/// Every module name `ast` references, with the kinds of reference.
+fn collect_ref_kinds(ast: &File) -> HashMap<String, BTreeSet<EdgeKind>> {
+    let mut kinds: HashMap<String, BTreeSet<EdgeKind>> = HashMap::new();
+    for item in &ast.items {
+        match item {
+            Item::Mod(ItemMod {
+                ident,
+                content: None,
+                ..
+            }) => {
+                kinds
+                    .entry(ident.to_string())
+                    .or_default()
+                    .insert(EdgeKind::Mod);
+            }
+            Item::Use(u) => {
+                let kind = match u.vis {
+                    SynVisibility::Inherited => EdgeKind::Use,
+                    _ => EdgeKind::PubUse,
+                };
+                let mut roots = HashSet::new();
+                extract_path_root(&u.tree, &mut roots);
+                for root in roots {
+                    let root = match u.leading_colon {
+                        Some(_) => format!("::{}", root),
+                        None => root,
+                    };
+                    kinds.entry(root).or_default().insert(kind);
+                }
+            }
+            // `extern crate dep;` (2015) depends on `dep` like `use dep;`
+            Item::ExternCrate(c) if c.ident != "self" => {
+                let kind = match c.vis {
+                    SynVisibility::Inherited => EdgeKind::Use,
+                    _ => EdgeKind::PubUse,
+                };
+                kinds.entry(c.ident.to_string()).or_default().insert(kind);
+            }
+            _ => {}
+        }
+    }
+
+    let mut paths = PathRefCollector {
+        roots: HashSet::new(),
+    };
+    paths.visit_file(ast);
+    for root in paths.roots {
+        kinds.entry(root).or_default().insert(EdgeKind::Path);
+    }
+    kinds
+}
+
+/// Module roots of qualified paths in code: `net` in `net::connect()`,
+/// `crate::net::Conn` or `super::net::X`. Single-segment paths name local
+/// items. `::dep::x` paths are recorded as `::dep`: another crate, or a
+/// crate-root module in the 2015 edition.
+struct PathRefCollector {
+    roots: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for PathRefCollector {
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .skip_while(|s| path.leading_colon.is_none() && PATH_KEYWORDS.contains(&s.as_str()))
+            .collect();
+        if segments.len() >= 2
+            && segments[0].starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        {
+            let root = match path.leading_colon {
+                Some(_) => format!("::{}", segments[0]),
+                None => segments[0].clone(),
+            };
+            self.roots.insert(root);
+        }
+        syn::visit::visit_path(self, path);
+    }
+
+    // Test code keeps no module alive
+    fn visit_item_mod(&mut self, m: &'ast ItemMod) {
+        if !is_cfg_test(&m.attrs) {
+            syn::visit::visit_item_mod(self, m);
+        }
+    }
+
+    // Imports are `use` edges, and attribute paths name no modules
+    fn visit_item_use(&mut self, _: &'ast ItemUse) {}
+    fn visit_attribute(&mut self, _: &'ast Attribute) {}
+}
+
+/// Record the visibility of every external `mod xyz;` declaration.
+fn collect_mod_decls(items: &[Item], decls: &mut HashMap<String, Visibility>) {
+    for item in items {
+        if let Item::Mod(ItemMod {
+            ident,
+            vis,
+            content: None,
+            ..
+        }) = item
+        {
+            decls.insert(ident.to_string(), Visibility::from(vis));
+        }
+    }
+}
+
+/// Every external `mod xyz;` declaration with a `#[cfg]`, in source order.
+fn collect_cfg_mods(items: &[Item]) -> Vec<CfgModDecl> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Mod(m) if m.content.is_none() => {
+                let cfgs: Vec<String> = m
+                    .attrs
+                    .iter()
+                    .filter(|a| a.path().is_ident("cfg"))
+                    .filter_map(|a| a.meta.require_list().ok())
+                    .map(|list| list.tokens.to_string())
+                    .collect();
+                let cfg = match cfgs.len() {
+                    0 => return None,
+                    1 => cfgs.into_iter().next().unwrap_or_default(),
+                    _ => format!("all({})", cfgs.join(", ")),
+                };
+                let path = m
+                    .attrs
+                    .iter()
+                    .filter(|a| a.path().is_ident("path"))
+                    .find_map(|a| match &a.meta.require_name_value().ok()?.value {
+                        Expr::Lit(syn::ExprLit {
+                            lit: Lit::Str(s), ..
+                        }) => Some(s.value()),
+                        _ => None,
+                    });
+                Some(CfgModDecl {
+                    name: m.ident.to_string(),
+                    cfg,
+                    path,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn collect_uses_and_decls(items: Vec<Item>, refs: &mut HashSet<String>) {
+    for item in items {
         match item {
             Item::Mod(ItemMod {
                 ident,
@@ -224,8 +804,6 @@ pub fn extract_uses_and_decls(content: &str, refs: &mut HashSet<String>) -> Resu
             _ => {}
         }
     }
-
-    Ok(())
 }
 
 /// Enhanced parsing that extracts visibility and re-export information.
@@ -328,6 +906,13 @@ pub fn parse_single_module(path: &Path) -> ParseResult {
         }
     };
 
+    parse_module_source(path, &content)
+}
+
+/// Parses module source that has already been loaded (e.g. from memory).
+///
+/// Same checks as [`parse_single_module`], minus the file read.
+pub fn parse_module_source(path: &Path, content: &str) -> ParseResult {
     // Skip files that are too large to prevent memory issues
     if content.len() > MAX_FILE_SIZE {
         return ParseResult::Skipped(
@@ -337,11 +922,11 @@ pub fn parse_single_module(path: &Path) -> ParseResult {
     }
 
     let mut info = ModuleInfo::new(path.to_path_buf());
-    if let Err(e) = extract_uses_and_decls(&content, &mut info.refs) {
+    if let Err(e) = extract_dependencies(content, &mut info) {
         return ParseResult::Skipped(path.to_path_buf(), format!("AST error: {}", e));
     }
 
-    ParseResult::Ok(info.name.clone(), info)
+    ParseResult::Ok(info.name.clone(), Box::new(info))
 }
 
 /// Parses a single module, returning Result for use with `?` operator.
@@ -360,7 +945,7 @@ pub fn parse_single_module_strict(path: &Path) -> Result<(String, ModuleInfo)> {
     );
 
     let mut info = ModuleInfo::new(path.to_path_buf());
-    extract_uses_and_decls(&content, &mut info.refs)
+    extract_dependencies(&content, &mut info)
         .with_context(|| format!("Failed to parse: {}", path.display()))?;
 
     Ok((info.name.clone(), info))
@@ -369,10 +954,10 @@ pub fn parse_single_module_strict(path: &Path) -> Result<(String, ModuleInfo)> {
 /// Reads all files in parallel, parses them, and builds a HashMap of module information.
 /// Includes robust error handling to skip malformed files (lenient mode).
 pub fn parse_modules(files: &[PathBuf]) -> Result<HashMap<String, ModuleInfo>> {
-    let modules = files
+    let modules: Vec<ModuleInfo> = files
         .par_iter()
         .filter_map(|file| match parse_single_module(file) {
-            ParseResult::Ok(name, info) => Some((name, info)),
+            ParseResult::Ok(_, info) => Some(*info),
             ParseResult::Skipped(path, reason) => {
                 eprintln!("WARN: Skipping {}: {}", path.display(), reason);
                 None
@@ -380,7 +965,7 @@ pub fn parse_modules(files: &[PathBuf]) -> Result<HashMap<String, ModuleInfo>> {
         })
         .collect();
 
-    Ok(modules)
+    Ok(key_modules(modules))
 }
 
 /// Parses all files in parallel with strict error handling (fail-fast mode).
@@ -396,7 +981,7 @@ pub fn parse_modules_strict(files: &[PathBuf]) -> Result<HashMap<String, ModuleI
         .into_iter()
         .collect::<Result<Vec<_>>>()?;
 
-    Ok(module_list.into_iter().collect())
+    Ok(key_modules(module_list.into_iter().map(|(_, info)| info)))
 }
 
 #[cfg(test)]
@@ -420,6 +1005,41 @@ mod tests {
                    "C:/Users/test/project/src/main.rs");
     }
 
+    #[test]
+    fn test_module_path_from_file_layouts() {
+        let path = |p: &str| module_path_from_file(Path::new(p));
+        assert_eq!(path("/ws/app/src/lib.rs"), "crate");
+        assert_eq!(path("/ws/app/src/main.rs"), "crate");
+        assert_eq!(
+            path("/ws/app/src/net/tcp/listener.rs"),
+            "net::tcp::listener"
+        );
+        assert_eq!(path("/ws/app/src/net/tcp/mod.rs"), "net::tcp");
+        assert_eq!(path("/ws/app/src/net/tcp.rs"), "net::tcp");
+        assert_eq!(path("/ws/app/src/bin/cli.rs"), "bin::cli");
+        assert_eq!(path("/ws/app/src/bin/cli/main.rs"), "bin::cli");
+        assert_eq!(path("/ws/app/tests/it.rs"), "tests::it");
+        assert_eq!(path("/ws/app/examples/demo/main.rs"), "examples::demo");
+        assert_eq!(path("/ws/app/tests/common/mod.rs"), "tests::common");
+        assert_eq!(path("build.rs"), "build");
+        assert_eq!(
+            ModuleInfo::new(PathBuf::from("src/a/mod.rs")).module_path,
+            "a"
+        );
+    }
+
+    #[test]
+    fn test_is_target_root_file() {
+        let target = |p: &str| is_target_root_file(Path::new(p));
+        assert!(target("/ws/app/tests/it.rs"));
+        assert!(target("/ws/app/benches/parse/main.rs"));
+        assert!(target("examples/demo.rs"));
+        assert!(!target("/ws/app/tests/common/mod.rs"));
+        assert!(!target("/ws/app/tests/common/util.rs"));
+        assert!(!target("/ws/app/src/tests/it.rs"));
+        assert!(!target("/ws/app/src/lib.rs"));
+    }
+
     #[test]
     fn test_normalize_path_string_mixed() {
         assert_eq!(normalize_path_string("src\\api/v1\\handler.rs"), "src/api/v1/handler.rs");
@@ -729,4 +1349,147 @@ mod bar;
 
         std::fs::remove_dir_all(&temp_dir).ok();
     }
+
+    #[test]
+    fn test_extract_includes() {
+        let src = r#"
+            include!("generated.rs");
+            mod bindings {
+                include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+            }
+            fn table() -> &'static [u8] { &include!("../data/table.rs") }
+            fn text() -> &'static str { include_str!("notes.txt") }
+        "#;
+        let sites = extract_includes(src, Path::new("src/lib.rs")).unwrap();
+        assert_eq!(sites.len(), 3);
+
+        assert_eq!(sites[0].literal, "generated.rs");
+        assert_eq!(sites[0].resolved, Some(PathBuf::from("src/generated.rs")));
+        assert!(!sites[0].out_dir);
+
+        assert_eq!(sites[1].literal, "/bindings.rs");
+        assert_eq!(sites[1].resolved, None);
+        assert!(sites[1].out_dir);
+        assert_eq!(sites[1].target_stem().as_deref(), Some("bindings"));
+
+        assert_eq!(
+            sites[2].resolved,
+            Some(PathBuf::from("src/../data/table.rs"))
+        );
+    }
+
+    #[test]
+    fn test_parse_module_source_records_includes() {
+        let ParseResult::Ok(_, info) = parse_module_source(
+            Path::new("src/lib.rs"),
+            "mod a;\ninclude!(\"generated.rs\");",
+        ) else {
+            panic!("parse failed");
+        };
+        assert_eq!(info.includes.len(), 1);
+        assert!(info.refs.contains("a"));
+        assert!(info.refs.contains("generated"));
+        assert_eq!(
+            info.ref_kinds["generated"],
+            BTreeSet::from([EdgeKind::Include])
+        );
+    }
+
+    #[test]
+    fn test_extract_inline_test_modules() {
+        let content = r#"
+mod net;
+
+#[cfg(test)]
+mod tests {
+    fn helper() { net::fake::connect(); }
+}
+
+#[cfg(all(test, unix))]
+mod unix_tests {}
+
+#[cfg(test)]
+mod fixtures;
+
+mod inline {}
+"#;
+        let mut info = ModuleInfo::new(PathBuf::from("src/lib.rs"));
+        extract_dependencies(content, &mut info).unwrap();
+
+        assert_eq!(
+            info.test_mods,
+            BTreeSet::from(["tests".to_string(), "unix_tests".to_string()])
+        );
+        // File-backed test modules are ordinary modules
+        assert!(info.refs.contains("fixtures"));
+        // Paths in test code are not module references
+        assert_eq!(info.ref_kinds["net"], BTreeSet::from([EdgeKind::Mod]));
+    }
+
+    #[test]
+    fn test_extract_ref_kinds() {
+        let content = r#"
+mod net;
+use net::Conn;
+pub use codec::Frame;
+pub(crate) use self::store::Db;
+
+#[derive(helpers::Derive)]
+struct Local;
+
+fn run() -> Conn {
+    metrics::record();
+    crate::net::connect(Local, ::serde::json());
+    Vec::new();
+}
+"#;
+        let mut info = ModuleInfo::new(PathBuf::from("src/lib.rs"));
+        extract_dependencies(content, &mut info).unwrap();
+
+        assert_eq!(
+            info.ref_kinds["net"],
+            BTreeSet::from([EdgeKind::Mod, EdgeKind::Use, EdgeKind::Path])
+        );
+        assert_eq!(info.ref_kinds["codec"], BTreeSet::from([EdgeKind::PubUse]));
+        assert_eq!(info.ref_kinds["store"], BTreeSet::from([EdgeKind::PubUse]));
+        assert_eq!(info.ref_kinds["metrics"], BTreeSet::from([EdgeKind::Path]));
+        // Types, external crates and attribute paths are not module refs
+        for name in ["Vec", "serde", "helpers", "Local"] {
+            assert!(!info.ref_kinds.contains_key(name), "{} recorded", name);
+        }
+
+        // Default edges leave out path-only references
+        let refs: BTreeSet<&str> = info.refs.iter().map(String::as_str).collect();
+        assert_eq!(refs, BTreeSet::from(["codec", "net", "store"]));
+
+        info.select_edges(&EdgeKinds::new([EdgeKind::Mod]));
+        assert_eq!(info.refs, HashSet::from(["net".to_string()]));
+        info.select_edges(&EdgeKinds::all());
+        assert!(info.refs.contains("metrics"));
+    }
+
+    #[test]
+    fn test_absolute_paths_follow_edition() {
+        let content = r#"
+extern crate log;
+use ::codec::Frame;
+
+fn run() {
+    ::net::connect();
+}
+"#;
+        let mut info = ModuleInfo::new(PathBuf::from("src/lib.rs"));
+        extract_dependencies(content, &mut info).unwrap();
+        assert_eq!(info.ref_kinds["::codec"], BTreeSet::from([EdgeKind::Use]));
+        assert_eq!(info.ref_kinds["::net"], BTreeSet::from([EdgeKind::Path]));
+        assert_eq!(info.ref_kinds["log"], BTreeSet::from([EdgeKind::Use]));
+
+        // From 2018 on `::name` is another crate
+        assert_eq!(info.refs, HashSet::from(["log".to_string()]));
+
+        info.edition = Edition::E2015;
+        info.select_edges(&EdgeKinds::all());
+        let refs: BTreeSet<&str> = info.refs.iter().map(String::as_str).collect();
+        assert_eq!(refs, BTreeSet::from(["codec", "log", "net"]));
+    }
 }
@@ -17,11 +17,14 @@ pub use crate::parse::{ModuleInfo, ParseResult};
 pub use crate::graph::{build_graph, reachable_from_root, reachable_from_roots};
 
 // Dead code detection
-pub use crate::detect::find_dead;
+pub use crate::detect::{dead_names, find_dead, DeadModuleReport};
 
 // File scanning
 pub use crate::scan::{gather_rs_files, gather_rs_files_with_excludes};
 
+// Source providers
+pub use crate::source::{MemorySource, SourceProvider};
+
 // Root module detection
 pub use crate::root::find_root_modules;
 
@@ -29,14 +32,17 @@ pub use crate::root::find_root_modules;
 pub use crate::workspace::{analyze_crate, analyze_workspace, CrateAnalysis};
 
 // Caching
-pub use crate::cache::{incremental_parse, load_cache, save_cache, DeadmodCache};
+pub use crate::cache::{incremental_parse, load_cache, save_cache, DeadmodCache, ParseMode};
 
 // Configuration
 pub use crate::config::{load_config, DeadmodConfig};
 
 // Builder API
-pub use crate::builder::{AnalysisResult, Deadmod};
+pub use crate::builder::{AnalysisResult, Deadmod, WorkspaceAnalysisResult};
+
+// Finding confidence
+pub use crate::common::Confidence;
 
 // Fix functionality
 #[cfg(feature = "fix")]
-pub use crate::fix::{clean_empty_dirs, fix_dead_modules, FixResult};
+pub use crate::fix::{clean_empty_dirs, fix_dead_modules, FixOptions, FixResult};
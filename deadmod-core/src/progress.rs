@@ -0,0 +1,390 @@
+//! Progress reporting for long analyses.
+//!
+//! Analysis code reports what it is doing through a [`Progress`] handle:
+//! which crate it is on, and the start, advance and end of each
+//! [`Phase`]. A handle without a hook (the default) does nothing, so code
+//! can report unconditionally. [`Progress::terminal`] draws the events as a
+//! progress bar on stderr, with one summary line per crate in workspaces;
+//! [`ProgressMode`] decides whether to draw it (`auto`: stderr is a
+//! terminal).
+//!
+//! Hooks are called from worker threads during parallel phases.
+
+use std::fmt;
+use std::io::{IsTerminal, Write};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Least time between two redraws of the terminal bar.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Width of the terminal bar in cells.
+const BAR_WIDTH: usize = 24;
+
+/// A step of an analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Listing `.rs` files
+    Scan,
+    /// Parsing files into modules (units: files)
+    Parse,
+    /// One item-level detector, extraction included (units: files)
+    Detect(&'static str),
+    /// Module graph construction and reachability
+    Graph,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scan => f.write_str("scanning files"),
+            Self::Parse => f.write_str("parsing"),
+            Self::Detect(name) => f.write_str(name),
+            Self::Graph => f.write_str("module graph"),
+        }
+    }
+}
+
+/// What an analysis reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressEvent<'a> {
+    /// Analysis of a crate started (workspace members, one after another)
+    CrateStart { name: &'a str },
+    /// Analysis of a crate finished
+    CrateDone { name: &'a str },
+    /// A phase started, with its units of work if known
+    PhaseStart { phase: Phase, total: Option<usize> },
+    /// `n` more units of the phase are done
+    Advance { phase: Phase, n: usize },
+    /// A phase finished
+    PhaseDone { phase: Phase },
+}
+
+type Hook = Arc<dyn Fn(&ProgressEvent) + Send + Sync>;
+
+/// Handle analyses report progress through; cheap to clone.
+#[derive(Clone, Default)]
+pub struct Progress {
+    hook: Option<Hook>,
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Progress")
+            .field("enabled", &self.is_enabled())
+            .finish()
+    }
+}
+
+impl Progress {
+    /// Report every event to `hook`.
+    pub fn new(hook: impl Fn(&ProgressEvent) + Send + Sync + 'static) -> Self {
+        Self {
+            hook: Some(Arc::new(hook)),
+        }
+    }
+
+    /// A handle that reports nothing.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Draw progress on stderr (see the module docs).
+    pub fn terminal() -> Self {
+        let bar = Mutex::new(TerminalBar::default());
+        Self::new(move |event| {
+            let mut bar = bar.lock().unwrap_or_else(|e| e.into_inner());
+            bar.handle(event, &mut std::io::stderr());
+        })
+    }
+
+    /// Whether events go anywhere.
+    pub fn is_enabled(&self) -> bool {
+        self.hook.is_some()
+    }
+
+    pub fn emit(&self, event: ProgressEvent) {
+        if let Some(hook) = &self.hook {
+            hook(&event);
+        }
+    }
+
+    pub fn crate_start(&self, name: &str) {
+        self.emit(ProgressEvent::CrateStart { name });
+    }
+
+    pub fn crate_done(&self, name: &str) {
+        self.emit(ProgressEvent::CrateDone { name });
+    }
+
+    pub fn start(&self, phase: Phase, total: Option<usize>) {
+        self.emit(ProgressEvent::PhaseStart { phase, total });
+    }
+
+    pub fn advance(&self, phase: Phase, n: usize) {
+        self.emit(ProgressEvent::Advance { phase, n });
+    }
+
+    pub fn done(&self, phase: Phase) {
+        self.emit(ProgressEvent::PhaseDone { phase });
+    }
+
+    /// Run `f` as `phase`, with `total` units it advances itself.
+    pub fn phase<T>(&self, phase: Phase, total: Option<usize>, f: impl FnOnce() -> T) -> T {
+        self.start(phase, total);
+        let value = f();
+        self.done(phase);
+        value
+    }
+}
+
+/// When to draw progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProgressMode {
+    /// When stderr is a terminal
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ProgressMode {
+    /// Every mode, in the order they are listed in help output.
+    pub const ALL: [ProgressMode; 3] = [
+        ProgressMode::Auto,
+        ProgressMode::Always,
+        ProgressMode::Never,
+    ];
+
+    /// Name used in flags.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Always => "always",
+            Self::Never => "never",
+        }
+    }
+
+    /// Whether to draw progress in this mode.
+    pub fn enabled(&self) -> bool {
+        match self {
+            Self::Auto => std::io::stderr().is_terminal(),
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+
+    /// [`Progress::terminal`] if enabled, else a handle reporting nothing.
+    pub fn progress(&self) -> Progress {
+        if self.enabled() {
+            Progress::terminal()
+        } else {
+            Progress::none()
+        }
+    }
+}
+
+impl fmt::Display for ProgressMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ProgressMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|m| m.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "unknown progress mode '{}' (expected auto, always or never)",
+                    s
+                )
+            })
+    }
+}
+
+/// State of the terminal bar.
+#[derive(Debug, Default)]
+struct TerminalBar {
+    /// Crate being analyzed and when it started
+    krate: Option<(String, Instant)>,
+    /// Files parsed in the current crate
+    files: usize,
+    /// Current phase, its total and units done
+    phase: Option<(Phase, Option<usize>, usize)>,
+    last_draw: Option<Instant>,
+    /// Whether a bar line is on screen
+    drawn: bool,
+}
+
+impl TerminalBar {
+    fn handle(&mut self, event: &ProgressEvent, out: &mut dyn Write) {
+        match *event {
+            ProgressEvent::CrateStart { name } => {
+                self.krate = Some((name.to_string(), Instant::now()));
+                self.files = 0;
+            }
+            ProgressEvent::CrateDone { name } => {
+                let elapsed = self
+                    .krate
+                    .take()
+                    .map_or(Duration::ZERO, |(_, start)| start.elapsed());
+                self.clear(out);
+                let _ = writeln!(
+                    out,
+                    "  {} ({} files, {:.2}s)",
+                    name,
+                    self.files,
+                    elapsed.as_secs_f64()
+                );
+                self.phase = None;
+                return;
+            }
+            ProgressEvent::PhaseStart { phase, total } => {
+                self.phase = Some((phase, total, 0));
+                self.draw(out);
+                return;
+            }
+            ProgressEvent::Advance { phase, n } => {
+                if let Some((current, _, done)) = &mut self.phase {
+                    if *current == phase {
+                        *done += n;
+                        if phase == Phase::Parse {
+                            self.files += n;
+                        }
+                    }
+                }
+                if self
+                    .last_draw
+                    .is_none_or(|at| at.elapsed() >= REDRAW_INTERVAL)
+                {
+                    self.draw(out);
+                }
+                return;
+            }
+            ProgressEvent::PhaseDone { phase } => {
+                if self.phase.is_some_and(|(current, _, _)| current == phase) {
+                    self.phase = None;
+                }
+            }
+        }
+        // Nothing stays on screen between phases, so other output is not garbled
+        self.clear(out);
+    }
+
+    fn draw(&mut self, out: &mut dyn Write) {
+        let Some((phase, total, done)) = self.phase else {
+            return;
+        };
+        let label = self
+            .krate
+            .as_ref()
+            .map_or(String::new(), |(name, _)| format!("{} ", name));
+        let line = format!("{}{}", label, bar_line(phase, total, done));
+        let _ = write!(out, "\r\x1b[2K{}", line);
+        let _ = out.flush();
+        self.last_draw = Some(Instant::now());
+        self.drawn = true;
+    }
+
+    fn clear(&mut self, out: &mut dyn Write) {
+        if self.drawn {
+            let _ = write!(out, "\r\x1b[2K");
+            let _ = out.flush();
+            self.drawn = false;
+        }
+    }
+}
+
+/// `[#####-----] parsing 12/40`, or `parsing 12` without a total.
+fn bar_line(phase: Phase, total: Option<usize>, done: usize) -> String {
+    match total {
+        Some(total) if total > 0 => {
+            let filled = (done.min(total) * BAR_WIDTH) / total;
+            format!(
+                "[{}{}] {} {}/{}",
+                "#".repeat(filled),
+                "-".repeat(BAR_WIDTH - filled),
+                phase,
+                done.min(total),
+                total
+            )
+        }
+        _ if done > 0 => format!("{} {}", phase, done),
+        _ => format!("{}...", phase),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_events() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let progress =
+            Progress::new(move |event| sink.lock().unwrap().push(format!("{:?}", event)));
+
+        progress.crate_start("core");
+        let parsed = progress.phase(Phase::Parse, Some(2), || {
+            progress.advance(Phase::Parse, 2);
+            2
+        });
+        assert_eq!(parsed, 2);
+        assert_eq!(seen.lock().unwrap().len(), 4);
+        assert!(!Progress::none().is_enabled());
+        Progress::none().crate_done("core");
+
+        assert_eq!("Never".parse::<ProgressMode>(), Ok(ProgressMode::Never));
+        assert!("sometimes".parse::<ProgressMode>().is_err());
+        assert!(!ProgressMode::Never.progress().is_enabled());
+    }
+
+    #[test]
+    fn test_terminal_bar() {
+        assert_eq!(
+            bar_line(Phase::Parse, Some(4), 1),
+            format!("[{}{}] parsing 1/4", "#".repeat(6), "-".repeat(18))
+        );
+        assert_eq!(bar_line(Phase::Scan, None, 0), "scanning files...");
+        assert_eq!(
+            bar_line(Phase::Detect("function analysis"), None, 7),
+            "function analysis 7"
+        );
+
+        let mut bar = TerminalBar::default();
+        let mut out = Vec::new();
+        bar.handle(&ProgressEvent::CrateStart { name: "core" }, &mut out);
+        bar.handle(
+            &ProgressEvent::PhaseStart {
+                phase: Phase::Parse,
+                total: Some(3),
+            },
+            &mut out,
+        );
+        bar.handle(
+            &ProgressEvent::Advance {
+                phase: Phase::Parse,
+                n: 3,
+            },
+            &mut out,
+        );
+        bar.handle(
+            &ProgressEvent::PhaseDone {
+                phase: Phase::Parse,
+            },
+            &mut out,
+        );
+        bar.handle(&ProgressEvent::CrateDone { name: "core" }, &mut out);
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("core [") && out.contains("parsing 0/3"));
+        assert!(out.contains("\r\x1b[2K") && out.contains("  core (3 files, "));
+        assert!(!bar.drawn);
+    }
+}
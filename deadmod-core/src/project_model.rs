@@ -0,0 +1,223 @@
+//! Project descriptions from `rust-project.json`.
+//!
+//! Build systems other than Cargo (Buck, Bazel, custom makefiles) describe
+//! their crates to rust-analyzer in a `rust-project.json`: each crate's root
+//! module, edition, enabled cfgs and dependencies. Reading the same file lets
+//! deadmod find crate roots where there is no `Cargo.toml` to go by.
+//!
+//! Only workspace members are analyzed; crates marked
+//! `"is_workspace_member": false` (the sysroot, vendored dependencies) are
+//! skipped. Relative paths are resolved against the file's directory.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::edition::Edition;
+use crate::workspace::member_name;
+
+/// File name rust-analyzer looks for.
+pub const PROJECT_JSON: &str = "rust-project.json";
+
+#[derive(Debug, Deserialize)]
+struct RawProject {
+    crates: Vec<RawCrate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCrate {
+    display_name: Option<String>,
+    root_module: PathBuf,
+    edition: Option<Edition>,
+    #[serde(default)]
+    cfg: Vec<String>,
+    #[serde(default)]
+    deps: Vec<RawDep>,
+    is_workspace_member: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawDep {
+    #[serde(rename = "crate")]
+    index: usize,
+    name: String,
+}
+
+/// A crate listed in `rust-project.json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectCrate {
+    /// `display_name`, else the name of the crate's directory
+    pub name: String,
+    /// Absolute path of the crate root file
+    pub root_module: PathBuf,
+    /// Directory scanned for the crate's files: the root module's directory,
+    /// or its parent when that is `src/`
+    pub dir: PathBuf,
+    /// Rust edition (2015 when not given)
+    pub edition: Edition,
+    /// Enabled cfgs, as written (`test`, `feature="serde"`)
+    pub cfg: Vec<String>,
+    /// Names the crate's dependencies are imported under, with their index
+    pub deps: Vec<(usize, String)>,
+    /// Whether the crate belongs to the project (not the sysroot or a dependency)
+    pub is_workspace_member: bool,
+}
+
+impl ProjectCrate {
+    /// Module name of the crate root file (`lib` for `src/lib.rs`).
+    pub fn root_name(&self) -> String {
+        match self.root_module.file_stem().map(|s| s.to_string_lossy()) {
+            Some(stem) if stem != "mod" => stem.into_owned(),
+            _ => self
+                .root_module
+                .parent()
+                .map(member_name)
+                .unwrap_or_else(|| "lib".to_string()),
+        }
+    }
+
+    /// Whether `cfg` is enabled: `is_cfg_enabled("test", None)`,
+    /// `is_cfg_enabled("feature", Some("serde"))`.
+    pub fn is_cfg_enabled(&self, key: &str, value: Option<&str>) -> bool {
+        self.cfg.iter().any(|cfg| match cfg.split_once('=') {
+            Some((k, v)) => k.trim() == key && Some(v.trim().trim_matches('"')) == value,
+            None => cfg.trim() == key && value.is_none(),
+        })
+    }
+}
+
+/// The crates of a `rust-project.json`.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectModel {
+    /// Path of the file the model was read from
+    pub path: PathBuf,
+    /// Every listed crate, in file order (dependency indices point here)
+    pub crates: Vec<ProjectCrate>,
+}
+
+impl ProjectModel {
+    /// Read and resolve the `rust-project.json` at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let base = path
+            .canonicalize()
+            .with_context(|| format!("Failed to canonicalize path: {}", path.display()))?
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let model =
+            Self::parse(&text, &base).with_context(|| format!("Invalid {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            ..model
+        })
+    }
+
+    /// Parse `rust-project.json` content, resolving relative paths against `base`.
+    pub fn parse(text: &str, base: &Path) -> Result<Self> {
+        let raw: RawProject = serde_json::from_str(text)?;
+        let crates = raw
+            .crates
+            .into_iter()
+            .map(|krate| {
+                let root_module = base.join(&krate.root_module);
+                let parent = root_module.parent().unwrap_or(base).to_path_buf();
+                let dir = match parent.file_name() {
+                    Some(name) if name == "src" => parent.parent().unwrap_or(&parent).to_path_buf(),
+                    _ => parent,
+                };
+                ProjectCrate {
+                    name: krate.display_name.unwrap_or_else(|| member_name(&dir)),
+                    root_module,
+                    dir,
+                    edition: krate.edition.unwrap_or(Edition::E2015),
+                    cfg: krate.cfg,
+                    deps: krate.deps.into_iter().map(|d| (d.index, d.name)).collect(),
+                    is_workspace_member: krate.is_workspace_member.unwrap_or(true),
+                }
+            })
+            .collect();
+        Ok(Self {
+            path: base.join(PROJECT_JSON),
+            crates,
+        })
+    }
+
+    /// The `rust-project.json` describing `path`: the path itself when it
+    /// names the file, else the file in that directory.
+    pub fn find(path: &Path) -> Option<PathBuf> {
+        if path.is_file() {
+            return (path.file_name()? == PROJECT_JSON).then(|| path.to_path_buf());
+        }
+        let candidate = path.join(PROJECT_JSON);
+        candidate.is_file().then_some(candidate)
+    }
+
+    /// Crates that belong to the project.
+    pub fn members(&self) -> impl Iterator<Item = &ProjectCrate> {
+        self.crates.iter().filter(|krate| krate.is_workspace_member)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT: &str = r#"{
+        "sysroot_src": "/rust/library",
+        "crates": [
+            {
+                "display_name": "core_utils",
+                "root_module": "utils/src/lib.rs",
+                "edition": "2021",
+                "cfg": ["test", "feature=\"serde\""],
+                "deps": []
+            },
+            {
+                "root_module": "tools/gen/main.rs",
+                "deps": [{ "crate": 0, "name": "utils" }]
+            },
+            {
+                "display_name": "vendored",
+                "root_module": "/vendor/dep/lib.rs",
+                "edition": "2018",
+                "is_workspace_member": false
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_project_json() {
+        let model = ProjectModel::parse(PROJECT, Path::new("/repo")).unwrap();
+        assert_eq!(model.crates.len(), 3);
+
+        let utils = &model.crates[0];
+        assert_eq!(utils.name, "core_utils");
+        assert_eq!(utils.root_module, Path::new("/repo/utils/src/lib.rs"));
+        assert_eq!(utils.dir, Path::new("/repo/utils"));
+        assert_eq!(utils.root_name(), "lib");
+        assert!(utils.is_cfg_enabled("test", None));
+        assert!(utils.is_cfg_enabled("feature", Some("serde")));
+        assert!(!utils.is_cfg_enabled("feature", Some("std")));
+
+        let tool = &model.crates[1];
+        assert_eq!((tool.name.as_str(), tool.edition), ("gen", Edition::E2015));
+        assert_eq!(tool.dir, Path::new("/repo/tools/gen"));
+        assert_eq!(tool.root_name(), "main");
+        assert_eq!(tool.deps, [(0, "utils".to_string())]);
+
+        let members: Vec<&str> = model.members().map(|c| c.name.as_str()).collect();
+        assert_eq!(members, ["core_utils", "gen"]);
+    }
+
+    #[test]
+    fn test_invalid_project_json() {
+        assert!(ProjectModel::parse("{}", Path::new("/repo")).is_err());
+        assert!(
+            ProjectModel::parse(r#"{"crates": [{"edition": "2021"}]}"#, Path::new("/")).is_err()
+        );
+    }
+}
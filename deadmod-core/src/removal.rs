@@ -0,0 +1,349 @@
+//! Textual cross-reference check before removing a dead item.
+//!
+//! The AST-based analysis decides that nothing in code resolves to an item.
+//! [`check_removal`] then looks for the references it cannot see, which
+//! still break or change something when the item goes away:
+//! - String literals, for lookups by name at runtime (`"helper"`)
+//! - Intra-doc links in doc comments (`` [`helper`] ``), which rustdoc
+//!   can no longer resolve
+//! - Tokens inside macro invocations, which are never resolved
+//! - Config files in the crate (`*.toml`, `*.json`, `*.yaml`, `*.ron`)
+//!
+//! Plain comments, the definition itself and code that resolved to
+//! something else are not references (see [`explain`]).
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::builder::{AnalysisResult, DeadItemKind};
+use crate::common::module_id;
+use crate::evidence::{explain, search_term, Exclusion};
+use crate::report::PathFormatter;
+use crate::source::SourceProvider;
+
+/// Extensions of config files searched for the name.
+const CONFIG_EXTENSIONS: &[&str] = &["toml", "json", "yaml", "yml", "ron"];
+
+/// Directories never searched for config files.
+const SKIPPED_DIRS: &[&str] = &["target", ".git", ".deadmod", "node_modules"];
+
+/// Where a name appears outside resolved code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    /// Inside a string literal
+    StringLiteral,
+    /// An intra-doc link in a doc comment
+    DocLink,
+    /// A token inside a macro invocation
+    MacroToken,
+    /// A config file
+    ConfigFile,
+}
+
+impl fmt::Display for ReferenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StringLiteral => write!(f, "string literal"),
+            Self::DocLink => write!(f, "intra-doc link"),
+            Self::MacroToken => write!(f, "macro token"),
+            Self::ConfigFile => write!(f, "config file"),
+        }
+    }
+}
+
+/// One textual reference to the item.
+#[derive(Debug, Clone, Serialize)]
+pub struct TextualReference {
+    /// File containing the reference
+    pub file: PathBuf,
+    /// Line number (1-indexed)
+    pub line: usize,
+    /// The line, trimmed
+    pub text: String,
+    /// What kind of reference it is
+    pub kind: ReferenceKind,
+}
+
+/// Whether a dead item can be removed without breaking textual references.
+#[derive(Debug, Clone, Serialize)]
+pub struct RemovalCheck {
+    /// Stable finding ID
+    pub id: String,
+    /// Item kind
+    pub kind: DeadItemKind,
+    /// Name or path of the dead item
+    pub name: String,
+    /// File containing the item
+    pub file: PathBuf,
+    /// Whether nothing references the item
+    pub safe: bool,
+    /// References found, by file and line
+    pub references: Vec<TextualReference>,
+}
+
+impl RemovalCheck {
+    /// Rewrite every file path in `paths`' style, for reporting.
+    pub fn localize_paths(&mut self, paths: &PathFormatter) {
+        if paths.style().is_none() {
+            return;
+        }
+        self.file = PathBuf::from(paths.format(&self.file));
+        for reference in &mut self.references {
+            reference.file = PathBuf::from(paths.format(&reference.file));
+        }
+    }
+}
+
+/// Check whether the dead finding `item` (a finding ID, or its name as
+/// reported) can be removed, reading sources from `source` and config files
+/// from the crate root.
+///
+/// Fails when `item` names no dead finding, or more than one.
+pub fn check_removal(
+    result: &AnalysisResult,
+    item: &str,
+    source: &dyn SourceProvider,
+) -> Result<RemovalCheck> {
+    let id = resolve_id(result, item)?;
+    let Some(evidence) = explain(result, &id, source) else {
+        bail!(
+            "No dead finding '{}' (it is still referenced, or mistyped)",
+            item
+        );
+    };
+
+    let term = search_term(&evidence).unwrap_or_default().to_string();
+    let mut references: Vec<TextualReference> = evidence
+        .mentions
+        .into_iter()
+        .filter_map(|mention| {
+            let kind = match mention.exclusion {
+                Exclusion::StringLiteral => ReferenceKind::StringLiteral,
+                Exclusion::Macro => ReferenceKind::MacroToken,
+                Exclusion::Comment if links_to(&mention.text, &term) => ReferenceKind::DocLink,
+                _ => return None,
+            };
+            Some(TextualReference {
+                file: mention.file,
+                line: mention.line,
+                text: mention.text,
+                kind,
+            })
+        })
+        .collect();
+    if !term.is_empty() {
+        references.extend(config_references(&result.root, &term));
+    }
+    references.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+    Ok(RemovalCheck {
+        id: evidence.id,
+        kind: evidence.kind,
+        name: evidence.name,
+        file: evidence.file,
+        safe: references.is_empty(),
+        references,
+    })
+}
+
+/// The finding ID `item` stands for: an ID, a dead module, or an item name
+/// (`helper`, `Config::load`).
+fn resolve_id(result: &AnalysisResult, item: &str) -> Result<String> {
+    let item = item.trim();
+    let id = item.to_ascii_lowercase();
+    let modules = result
+        .dead_modules
+        .iter()
+        .map(|name| (module_id(name), name.as_str()));
+    let items = result
+        .dead_items()
+        .chain(result.collapsed.iter().flat_map(|m| &m.items))
+        .map(|dead| (dead.id.clone(), dead.name.as_str()));
+    let mut ids: Vec<String> = modules
+        .chain(items)
+        .filter(|(found, name)| *found == id || *name == item)
+        .map(|(found, _)| found)
+        .collect();
+    ids.sort();
+    ids.dedup();
+    match ids.len() {
+        0 => bail!(
+            "No dead finding '{}' (it is still referenced, or mistyped)",
+            item
+        ),
+        1 => Ok(ids.remove(0)),
+        _ => bail!(
+            "'{}' names several findings; pass one of their IDs: {}",
+            item,
+            ids.join(", ")
+        ),
+    }
+}
+
+/// Whether the doc comment `line` has an intra-doc link to `term`:
+/// `` [`term`] ``, `[term()]`, `[crate::a::term]` or `[text]: a::term`.
+fn links_to(line: &str, term: &str) -> bool {
+    if term.is_empty() || !(line.starts_with("///") || line.starts_with("//!")) {
+        return false;
+    }
+    let targets_term = |target: &str| {
+        let target = target.trim().trim_matches('`');
+        let target = target.rsplit_once('@').map_or(target, |(_, path)| path);
+        let target = target.trim_end_matches("()").trim_end_matches('!');
+        target.rsplit("::").next() == Some(term)
+    };
+    let mut rest = line;
+    while let Some(open) = rest.find('[') {
+        let Some(close) = rest[open..].find(']') else {
+            break;
+        };
+        let inside = &rest[open + 1..open + close];
+        let after = &rest[open + close + 1..];
+        let target = after.strip_prefix(':').or_else(|| {
+            after
+                .strip_prefix('(')
+                .and_then(|link| link.split(')').next())
+        });
+        if targets_term(inside) || target.is_some_and(targets_term) {
+            return true;
+        }
+        rest = after;
+    }
+    false
+}
+
+/// Lines of config files under `root` that mention `term` as a word.
+fn config_references(root: &Path, term: &str) -> Vec<TextualReference> {
+    if !root.is_dir() {
+        return Vec::new();
+    }
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mentions = |line: &str| {
+        line.match_indices(term).any(|(at, _)| {
+            !line[..at].ends_with(is_ident) && !line[at + term.len()..].starts_with(is_ident)
+        })
+    };
+
+    let mut references = Vec::new();
+    let files = WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref())
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|ext| CONFIG_EXTENSIONS.contains(&ext.to_string_lossy().as_ref()))
+        });
+    for entry in files {
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        for (index, line) in content.lines().enumerate() {
+            if mentions(line) {
+                references.push(TextualReference {
+                    file: entry.path().to_path_buf(),
+                    line: index + 1,
+                    text: line.trim().to_string(),
+                    kind: ReferenceKind::ConfigFile,
+                });
+            }
+        }
+    }
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Deadmod;
+    use crate::source::MemorySource;
+
+    fn sources() -> Vec<(PathBuf, String)> {
+        vec![
+            (
+                PathBuf::from("src/main.rs"),
+                "mod util;\n\n/// Runs [`util::load`], see also [`reload`].\nfn main() {\n    // helper() used to run here\n    call(\"helper\");\n}\n\nfn call(_: &str) {}\n".to_string(),
+            ),
+            (
+                PathBuf::from("src/util.rs"),
+                "fn helper() {}\n\nfn reload() {}\n\nfn unused() {}\n\npub fn load() {}\n\nfn nested() { let helper = 1; }\n".to_string(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_check_removal_references() {
+        let source = MemorySource::new(sources());
+        let result = Deadmod::from_sources(sources()).all().analyze().unwrap();
+
+        let check = check_removal(&result, "helper", &source).unwrap();
+        assert!(!check.safe);
+        let found: Vec<(&str, usize, ReferenceKind)> = check
+            .references
+            .iter()
+            .map(|r| (r.file.to_str().unwrap(), r.line, r.kind))
+            .collect();
+        assert_eq!(found, [("src/main.rs", 6, ReferenceKind::StringLiteral)]);
+
+        let check = check_removal(&result, "reload", &source).unwrap();
+        assert_eq!(check.references[0].kind, ReferenceKind::DocLink);
+
+        // By ID, nothing references it
+        let id = result
+            .dead_functions
+            .iter()
+            .find(|f| f.name == "unused")
+            .unwrap()
+            .id
+            .clone();
+        let check = check_removal(&result, &id, &source).unwrap();
+        assert!(check.safe && check.references.is_empty());
+
+        assert!(check_removal(&result, "main", &source).is_err());
+    }
+
+    #[test]
+    fn test_links_to() {
+        assert!(links_to("/// See [`helper`].", "helper"));
+        assert!(links_to("//! Calls [crate::util::helper()]", "helper"));
+        assert!(links_to("/// [the helper]: fn@util::helper", "helper"));
+        assert!(links_to("/// Like [this](Config::helper).", "helper"));
+        assert!(!links_to(
+            "/// Not a [`helpers`] link, nor helper",
+            "helper"
+        ));
+        assert!(!links_to("// [`helper`] in a plain comment", "helper"));
+    }
+
+    #[test]
+    fn test_config_references() {
+        let root =
+            std::env::temp_dir().join(format!("deadmod_removal_test_{}", std::process::id()));
+        fs::create_dir_all(root.join("target")).unwrap();
+        fs::write(
+            root.join("deadmod.toml"),
+            "[roots]\nmodules = [\"helper\"]\nother = \"helpers\"\n",
+        )
+        .unwrap();
+        fs::write(root.join("target/out.json"), "{\"helper\": 1}").unwrap();
+
+        let found: Vec<usize> = config_references(&root, "helper")
+            .iter()
+            .map(|r| r.line)
+            .collect();
+        assert_eq!(found, [2]);
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,92 @@
+//! Dead code badges as shields.io endpoint JSON.
+//!
+//! CI writes the badge file with `deadmod --badge-json badge.json` and
+//! publishes it (GitHub Pages, a gist, an artifact URL); the README then
+//! shows it with
+//! `https://img.shields.io/endpoint?url=<published badge.json>`.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::builder::AnalysisResult;
+
+/// Dead code count at which the badge turns red, by default.
+pub const DEFAULT_BADGE_RED_AT: usize = 10;
+
+/// A shields.io endpoint badge
+/// (<https://shields.io/badges/endpoint-badge>).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Badge {
+    /// Always 1
+    pub schema_version: u8,
+    pub label: String,
+    pub message: String,
+    pub color: String,
+}
+
+impl Badge {
+    /// Badge for `modules` dead modules and `functions` dead functions:
+    /// bright green without any, red from `red_at` findings, yellow between.
+    pub fn dead_code(modules: usize, functions: usize, red_at: usize) -> Self {
+        let total = modules + functions;
+        let color = if total == 0 {
+            "brightgreen"
+        } else if total >= red_at {
+            "red"
+        } else {
+            "yellow"
+        };
+        Self {
+            schema_version: 1,
+            label: "dead code".to_string(),
+            message: format!(
+                "{} / {}",
+                plural(modules, "module", "modules"),
+                plural(functions, "fn", "fns")
+            ),
+            color: color.to_string(),
+        }
+    }
+
+    /// Badge for the dead modules and functions of an analysis.
+    pub fn from_result(result: &AnalysisResult, red_at: usize) -> Self {
+        Self::dead_code(
+            result.dead_modules.len(),
+            result.dead_functions.len(),
+            red_at,
+        )
+    }
+
+    /// Write the badge as endpoint JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize badge")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write badge to {}", path.display()))
+    }
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{} {}", count, if count == 1 { one } else { many })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_badge_message_and_color() {
+        let badge = Badge::dead_code(3, 42, DEFAULT_BADGE_RED_AT);
+        assert_eq!(badge.message, "3 modules / 42 fns");
+        assert_eq!(badge.color, "red");
+        assert_eq!(Badge::dead_code(1, 1, 10).message, "1 module / 1 fn");
+        assert_eq!(Badge::dead_code(1, 1, 10).color, "yellow");
+        assert_eq!(Badge::dead_code(0, 0, 10).color, "brightgreen");
+
+        let json = serde_json::to_value(&badge).unwrap();
+        assert_eq!(json["schemaVersion"], 1);
+        assert_eq!(json["label"], "dead code");
+    }
+}
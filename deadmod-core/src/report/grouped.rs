@@ -0,0 +1,393 @@
+//! Findings grouped by owning module.
+//!
+//! Every finding is attributed to a module path such as `crate::net::http`:
+//! the file's module path from [`module_path_of`] joined with the item's
+//! inline `mod` path. [`group_by_module`] then folds the findings of all
+//! detectors into a [`ModuleNode`] tree mirroring the crate's hierarchy.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Component, Path};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use super::limit::ItemLimit;
+use crate::builder::{AnalysisResult, DeadItem};
+
+/// Path segment used for the crate root module.
+pub const CRATE_ROOT: &str = "crate";
+
+/// How a report groups its findings (`--group-by`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// One tree node per module path
+    Module,
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "module" => Ok(Self::Module),
+            _ => Err(format!("unknown grouping '{}' (expected module)", s)),
+        }
+    }
+}
+
+/// Module path of a source file, relative to the crate `root`.
+///
+/// `src/lib.rs` and `src/main.rs` are the crate root, `src/net/mod.rs` and
+/// `src/net.rs` are both `crate::net`. Files outside `src/` keep their
+/// directory as the first segment (`tests/it.rs` is `crate::tests::it`).
+pub fn module_path_of(root: &Path, file: &Path) -> String {
+    let relative = file.strip_prefix(root).unwrap_or(file);
+    let mut segments: Vec<String> = relative
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(s) => Some(s.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+
+    if segments.first().map(String::as_str) == Some("src") {
+        segments.remove(0);
+    }
+
+    if let Some(last) = segments.pop() {
+        let stem = last.strip_suffix(".rs").unwrap_or(&last);
+        let is_root = segments.is_empty() && (stem == "lib" || stem == "main");
+        if stem != "mod" && !is_root {
+            segments.push(stem.to_string());
+        }
+    }
+
+    join_module_path(CRATE_ROOT, &segments.join("::"))
+}
+
+/// Join a file's module path with an item's inline module path.
+pub fn join_module_path(file_module: &str, inline: &str) -> String {
+    if inline.is_empty() {
+        file_module.to_string()
+    } else {
+        format!("{}::{}", file_module, inline)
+    }
+}
+
+/// One module in the grouped report.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModuleNode {
+    /// Full module path (`crate::net::http`)
+    pub path: String,
+    /// Whether the whole module is unreachable
+    pub dead: bool,
+    /// Findings attributed directly to this module
+    pub items: Vec<DeadItem>,
+    /// Findings folded into this dead module (not counted in [`total`](Self::total))
+    pub collapsed: usize,
+    /// Child modules keyed by their last path segment
+    pub children: BTreeMap<String, ModuleNode>,
+}
+
+impl ModuleNode {
+    fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Number of findings in this module and all descendants
+    /// (a dead module counts as one finding).
+    pub fn total(&self) -> usize {
+        usize::from(self.dead)
+            + self.items.len()
+            + self.children.values().map(ModuleNode::total).sum::<usize>()
+    }
+
+    /// Last segment of the module path.
+    pub fn name(&self) -> &str {
+        self.path.rsplit("::").next().unwrap_or(&self.path)
+    }
+
+    /// Get or create the node for `path`, creating intermediate modules.
+    fn entry(&mut self, path: &str) -> &mut ModuleNode {
+        let rest = path
+            .strip_prefix(self.path.as_str())
+            .map(|r| r.trim_start_matches("::"))
+            .unwrap_or(path);
+        let mut node = self;
+        for segment in rest.split("::").filter(|s| !s.is_empty()) {
+            let child_path = format!("{}::{}", node.path, segment);
+            node = node
+                .children
+                .entry(segment.to_string())
+                .or_insert_with(|| ModuleNode::new(child_path));
+        }
+        node
+    }
+
+    /// A copy keeping only the items `limit` lists across the whole tree,
+    /// and the number of items left out. Dead modules are always kept.
+    pub fn limited(&self, limit: &ItemLimit) -> (ModuleNode, usize) {
+        let mut all = Vec::new();
+        self.collect_items(&mut all);
+        let truncated = limit.apply(all, |item| (item.confidence, 0));
+        let shown: HashSet<*const DeadItem> = truncated
+            .shown
+            .iter()
+            .map(|item| *item as *const _)
+            .collect();
+
+        let mut tree = self.filtered(&shown);
+        tree.prune();
+        (tree, truncated.hidden)
+    }
+
+    fn collect_items<'a>(&'a self, out: &mut Vec<&'a DeadItem>) {
+        out.extend(&self.items);
+        for child in self.children.values() {
+            child.collect_items(out);
+        }
+    }
+
+    fn filtered(&self, shown: &HashSet<*const DeadItem>) -> ModuleNode {
+        ModuleNode {
+            path: self.path.clone(),
+            dead: self.dead,
+            items: self
+                .items
+                .iter()
+                .filter(|item| shown.contains(&(*item as *const _)))
+                .cloned()
+                .collect(),
+            collapsed: self.collapsed,
+            children: self
+                .children
+                .iter()
+                .map(|(name, child)| (name.clone(), child.filtered(shown)))
+                .collect(),
+        }
+    }
+
+    fn prune(&mut self) {
+        self.children.retain(|_, child| {
+            child.prune();
+            child.total() > 0
+        });
+    }
+}
+
+/// Group every finding in `result` under its owning module.
+///
+/// Returns the crate root node; modules without findings are pruned.
+pub fn group_by_module(result: &AnalysisResult) -> ModuleNode {
+    let mut tree = ModuleNode::new(CRATE_ROOT);
+
+    for name in &result.dead_modules {
+        if let Some(info) = result.modules.get(name) {
+            tree.entry(&module_path_of(&result.root, &info.path)).dead = true;
+        }
+    }
+
+    for module in &result.collapsed {
+        tree.entry(&module.module_path).collapsed += module.items.len();
+    }
+
+    for item in result.dead_items() {
+        tree.entry(&item.module_path).items.push(item.clone());
+    }
+
+    tree.prune();
+    tree
+}
+
+/// Format a grouped report as an indented tree.
+pub fn format_grouped_plain(tree: &ModuleNode) -> String {
+    if tree.total() == 0 {
+        return "No dead code found.\n".to_string();
+    }
+
+    let mut out = format!("DEAD CODE BY MODULE ({}):\n", tree.total());
+    write_node(&mut out, tree, 0);
+    out
+}
+
+fn write_node(out: &mut String, node: &ModuleNode, depth: usize) {
+    let indent = "  ".repeat(depth);
+    let marker = match (node.dead, node.collapsed) {
+        (true, 0) => " [dead module]".to_string(),
+        (true, n) => format!(" [dead module, {} items inside]", n),
+        _ => String::new(),
+    };
+    out.push_str(&format!(
+        "{}{} ({}){}\n",
+        indent,
+        node.name(),
+        node.total(),
+        marker
+    ));
+    for item in &node.items {
+        let line = if item.line > 0 {
+            format!(":{}", item.line)
+        } else {
+            String::new()
+        };
+        out.push_str(&format!(
+            "{}  - [{}] {} ({}{}) [confidence: {}] [id: {}]\n",
+            indent,
+            item.kind,
+            item.name,
+            item.file.display(),
+            line,
+            item.confidence,
+            item.id
+        ));
+    }
+    for child in node.children.values() {
+        write_node(out, child, depth + 1);
+    }
+}
+
+/// Format a grouped report as JSON.
+pub fn format_grouped_json(tree: &ModuleNode) -> String {
+    let value = serde_json::json!({
+        "total": tree.total(),
+        "root": tree,
+    });
+    match serde_json::to_string_pretty(&value) {
+        Ok(json) => json,
+        Err(e) => {
+            eprintln!("[WARN] JSON serialization failed: {}", e);
+            format!("{{\"total\": {}}}", tree.total())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::DeadItemKind;
+    use crate::common::Confidence;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_module_path_of() {
+        let root = Path::new("/ws/app");
+        let path = |p: &str| module_path_of(root, &root.join(p));
+        assert_eq!(path("src/lib.rs"), "crate");
+        assert_eq!(path("src/main.rs"), "crate");
+        assert_eq!(path("src/net.rs"), "crate::net");
+        assert_eq!(path("src/net/mod.rs"), "crate::net");
+        assert_eq!(path("src/net/http.rs"), "crate::net::http");
+        assert_eq!(path("src/net/main.rs"), "crate::net::main");
+        assert_eq!(path("tests/it.rs"), "crate::tests::it");
+        assert_eq!(
+            module_path_of(Path::new(""), Path::new("src/a.rs")),
+            "crate::a"
+        );
+    }
+
+    #[test]
+    fn test_group_by_from_str() {
+        assert_eq!("module".parse::<GroupBy>(), Ok(GroupBy::Module));
+        assert!("file".parse::<GroupBy>().is_err());
+    }
+
+    #[test]
+    fn test_join_module_path() {
+        assert_eq!(join_module_path("crate::a", ""), "crate::a");
+        assert_eq!(join_module_path("crate::a", "inner"), "crate::a::inner");
+    }
+
+    fn item(name: &str, module_path: &str, kind: DeadItemKind) -> DeadItem {
+        DeadItem {
+            id: String::new(),
+            name: name.to_string(),
+            file: PathBuf::from("src/x.rs"),
+            line: 0,
+            kind,
+            confidence: Confidence::High,
+            module_path: module_path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_group_by_module() {
+        let result = AnalysisResult {
+            root: PathBuf::from(""),
+            total_modules: 0,
+            reachable_modules: Vec::new(),
+            dead_modules: Vec::new(),
+            collisions: Vec::new(),
+            skipped: Vec::new(),
+            dead_functions: vec![
+                item("helper", "crate::net::http", DeadItemKind::Function),
+                item("top", "crate", DeadItemKind::Function),
+            ],
+            dead_traits: Vec::new(),
+            dead_constants: vec![item("LIMIT", "crate::net", DeadItemKind::Constant)],
+            dead_enums: Vec::new(),
+            dead_macros: Vec::new(),
+            dead_generics: Vec::new(),
+            dead_matcharms: Vec::new(),
+            dead_impls: Vec::new(),
+            kept: Vec::new(),
+            recent: Vec::new(),
+            collapsed: Vec::new(),
+            modules: HashMap::new(),
+            evidence: Default::default(),
+        };
+
+        let tree = group_by_module(&result);
+        assert_eq!(tree.total(), 3);
+        assert_eq!(tree.items.len(), 1);
+        let net = &tree.children["net"];
+        assert_eq!(net.path, "crate::net");
+        assert_eq!(net.total(), 2);
+        assert_eq!(net.children["http"].items[0].name, "helper");
+
+        let plain = format_grouped_plain(&tree);
+        assert!(plain.starts_with("DEAD CODE BY MODULE (3):"));
+        assert!(plain.contains("\n  net (2)\n"));
+        assert!(plain.contains("[function] helper"));
+
+        let (limited, hidden) = tree.limited(&ItemLimit::new(Some(1), false));
+        assert_eq!((limited.total(), hidden), (1, 2));
+        assert!(limited.children.is_empty());
+
+        let json: serde_json::Value = serde_json::from_str(&format_grouped_json(&tree)).unwrap();
+        assert_eq!(json["total"], 3);
+        assert_eq!(
+            json["root"]["children"]["net"]["children"]["http"]["items"][0]["kind"],
+            "function"
+        );
+    }
+
+    #[test]
+    fn test_group_by_module_collapsed() {
+        let result = crate::builder::Deadmod::from_sources([
+            (PathBuf::from("src/lib.rs"), "pub fn api() {}".to_string()),
+            (
+                PathBuf::from("src/old.rs"),
+                "fn a() {}\nfn b() {}".to_string(),
+            ),
+        ])
+        .include_functions(true)
+        .collapse(true)
+        .analyze()
+        .unwrap();
+
+        let tree = group_by_module(&result);
+        assert_eq!(tree.total(), 1);
+        assert_eq!(tree.children["old"].collapsed, 2);
+        assert!(format_grouped_plain(&tree).contains("old (1) [dead module, 2 items inside]"));
+    }
+
+    #[test]
+    fn test_group_by_module_empty() {
+        let tree = ModuleNode::new(CRATE_ROOT);
+        assert_eq!(format_grouped_plain(&tree), "No dead code found.\n");
+    }
+}
@@ -0,0 +1,126 @@
+//! Truncation of long finding lists in plain output.
+//!
+//! On large or pathological crates a detector can report thousands of
+//! findings, more than a terminal is useful for. An [`ItemLimit`] caps each
+//! plain-text list and ends it with a summary of what was left out; JSON
+//! output is never truncated. With [`ItemLimit::top`], lists are ordered by
+//! priority first (confidence, then size), so the cap keeps the findings
+//! most worth looking at. Ties keep their original order, so the selection
+//! is deterministic.
+
+use std::cmp::Reverse;
+
+use crate::common::Confidence;
+
+/// How much of each finding list plain output shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemLimit {
+    /// Most items listed per list (`None`: all)
+    pub max_items: Option<usize>,
+    /// List the highest-priority items first
+    pub top: bool,
+}
+
+impl ItemLimit {
+    /// Show at most `max_items` items per list, highest priority first if `top`.
+    pub fn new(max_items: Option<usize>, top: bool) -> Self {
+        Self { max_items, top }
+    }
+
+    /// Whether lists are reordered or cut at all.
+    pub fn is_active(&self) -> bool {
+        self.max_items.is_some() || self.top
+    }
+
+    /// The items of `items` to list. `rank` gives each item's confidence
+    /// and size (lines, bytes; 0 if unknown), used to order them under
+    /// [`top`](Self::top).
+    pub fn apply<'a, T: ?Sized + 'a>(
+        &self,
+        items: impl IntoIterator<Item = &'a T>,
+        rank: impl Fn(&T) -> (Confidence, usize),
+    ) -> Truncated<'a, T> {
+        let mut shown: Vec<&T> = items.into_iter().collect();
+        if self.top {
+            shown.sort_by_key(|item| {
+                let (confidence, size) = rank(item);
+                (Reverse(confidence), Reverse(size))
+            });
+        }
+        let hidden = match self.max_items {
+            Some(max) if shown.len() > max => {
+                let hidden = shown.len() - max;
+                shown.truncate(max);
+                hidden
+            }
+            _ => 0,
+        };
+        Truncated { shown, hidden }
+    }
+}
+
+/// The listed part of a finding list.
+#[derive(Debug, Clone)]
+pub struct Truncated<'a, T: ?Sized> {
+    /// Items to list, in order
+    pub shown: Vec<&'a T>,
+    /// Number of items left out
+    pub hidden: usize,
+}
+
+impl<T: ?Sized> Truncated<'_, T> {
+    /// `… and 1234 more (see JSON export)`, if anything was left out.
+    pub fn summary(&self) -> Option<String> {
+        more_summary(self.hidden)
+    }
+}
+
+impl<'a, 'b, T: ?Sized> IntoIterator for &'b Truncated<'a, T> {
+    type Item = &'a T;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'b, &'a T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.shown.iter().copied()
+    }
+}
+
+/// `… and {hidden} more (see JSON export)`, if `hidden` is not zero.
+pub fn more_summary(hidden: usize) -> Option<String> {
+    (hidden > 0).then(|| format!("… and {} more (see JSON export)", hidden))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_limit() {
+        let items = [
+            ("a", Confidence::Low, 50),
+            ("b", Confidence::High, 3),
+            ("c", Confidence::High, 40),
+            ("d", Confidence::Medium, 9),
+            ("e", Confidence::High, 3),
+        ];
+        let rank = |item: &(&str, Confidence, usize)| (item.1, item.2);
+        let names = |t: &Truncated<(&'static str, Confidence, usize)>| {
+            t.shown.iter().map(|i| i.0).collect::<Vec<_>>()
+        };
+
+        let all = ItemLimit::default().apply(&items, rank);
+        assert_eq!(
+            (names(&all), all.hidden),
+            (vec!["a", "b", "c", "d", "e"], 0)
+        );
+        assert_eq!(all.summary(), None);
+
+        let capped = ItemLimit::new(Some(2), false).apply(&items, rank);
+        assert_eq!((names(&capped), capped.hidden), (vec!["a", "b"], 3));
+        assert_eq!(capped.summary().unwrap(), "… and 3 more (see JSON export)");
+
+        // Confidence, then size; ties keep their order
+        let top = ItemLimit::new(Some(3), true).apply(&items, rank);
+        assert_eq!((names(&top), top.hidden), (vec!["c", "b", "e"], 2));
+        assert!(!ItemLimit::default().is_active());
+    }
+}
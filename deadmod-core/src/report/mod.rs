@@ -0,0 +1,79 @@
+//! Output formatting - plaintext and JSON.
+//!
+//! The [`writer`] submodule renders a single analysis run into any number of
+//! output formats (plain, JSON, DOT, HTML, PixiJS) and routes each to stdout
+//! or a file. The [`grouped`] submodule arranges findings from all detectors
+//! into a tree keyed by module path. The [`paths`] submodule writes file
+//! paths in one style across all of them, [`limit`] caps long finding
+//! lists in plain output, and [`normalize`] makes JSON output stable for
+//! snapshots. [`badge`] summarizes a run as a shields.io endpoint badge.
+
+pub mod badge;
+pub mod grouped;
+pub mod limit;
+pub mod normalize;
+pub mod paths;
+pub mod writer;
+
+pub use badge::{Badge, DEFAULT_BADGE_RED_AT};
+pub use grouped::{
+    format_grouped_json, format_grouped_plain, group_by_module, join_module_path, module_path_of,
+    GroupBy, ModuleNode,
+};
+pub use limit::{more_summary, ItemLimit, Truncated};
+pub use normalize::normalize_json;
+pub use paths::{PathFormatter, PathStyle};
+pub use writer::{render, write_outputs, ModuleReport, OutputFormat, OutputSpec};
+
+use std::collections::BTreeMap;
+
+use serde_json::json;
+
+use crate::common::module_id;
+
+/// Stable ID of each dead module, keyed by module name.
+pub fn module_ids(dead: &[&str]) -> BTreeMap<String, String> {
+    dead.iter().map(|m| (m.to_string(), module_id(m))).collect()
+}
+
+/// Prints dead modules in plain text format.
+pub fn print_plain(dead: &[&str]) {
+    print!("{}", format_plain(dead));
+}
+
+/// Formats dead modules in plain text format.
+pub fn format_plain(dead: &[&str]) -> String {
+    if dead.is_empty() {
+        return "No dead modules found.\n".to_string();
+    }
+
+    let mut out = format!("DEAD MODULES ({}):\n", dead.len());
+    for m in dead {
+        out.push_str("- ");
+        out.push_str(m);
+        out.push('\n');
+    }
+    out
+}
+
+/// Prints dead modules in JSON format.
+///
+/// Falls back to simple format if serialization fails (should never happen
+/// with string arrays, but NASA-grade means handling all cases).
+pub fn print_json(dead: &[&str]) {
+    println!("{}", format_json(dead));
+}
+
+/// Formats dead modules in JSON format.
+///
+/// Uses the same fallback as [`print_json`] if serialization fails.
+pub fn format_json(dead: &[&str]) -> String {
+    match serde_json::to_string_pretty(&json!({ "dead": dead, "ids": module_ids(dead) })) {
+        Ok(json) => json,
+        Err(e) => {
+            // Fallback: output in a simpler format
+            eprintln!("[WARN] JSON serialization failed: {}", e);
+            format!("{{\"dead\": {:?}}}", dead)
+        }
+    }
+}
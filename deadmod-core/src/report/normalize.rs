@@ -0,0 +1,106 @@
+//! Normalized JSON for snapshot tests and committed reports.
+//!
+//! [`normalize_json`] rewrites a JSON report so that it changes only when
+//! the findings do, not with the machine, the checkout location, the day
+//! or the order files were parsed in:
+//! - Paths under the crate root become relative to it (`src/net.rs`)
+//! - Dates (`added` of recent findings and suppressions) are removed
+//! - Every array is sorted
+//!
+//! Finding IDs hash the kind, module path and name, never a file path, so
+//! they are already stable and left as they are.
+
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+/// Object keys holding dates.
+const DATE_KEYS: &[&str] = &["added"];
+
+/// Normalize `value` in place; paths are made relative to `root`.
+pub fn normalize_json(value: &mut Value, root: &Path) {
+    normalize(value, &root_prefixes(root));
+}
+
+/// `root` as given and canonicalized, each with a trailing `/`, longest first.
+fn root_prefixes(root: &Path) -> Vec<String> {
+    let mut prefixes: Vec<String> = [Some(root.to_path_buf()), root.canonicalize().ok()]
+        .into_iter()
+        .flatten()
+        .map(|path| {
+            let path = path.display().to_string().replace('\\', "/");
+            format!("{}/", path.trim_end_matches('/'))
+        })
+        .filter(|prefix| prefix != "/")
+        .collect();
+    prefixes.push("./".to_string());
+    prefixes.sort_by_key(|prefix| std::cmp::Reverse(prefix.len()));
+    prefixes.dedup();
+    prefixes
+}
+
+fn normalize(value: &mut Value, prefixes: &[String]) {
+    match value {
+        Value::String(s) => *s = relative(s, prefixes),
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                normalize(item, prefixes);
+            }
+            items.sort_by_cached_key(Value::to_string);
+        }
+        Value::Object(map) => {
+            let entries = std::mem::take(map);
+            *map = entries
+                .into_iter()
+                .filter(|(key, _)| !DATE_KEYS.contains(&key.as_str()))
+                .map(|(key, mut value)| {
+                    normalize(&mut value, prefixes);
+                    (relative(&key, prefixes), value)
+                })
+                .collect::<Map<String, Value>>();
+        }
+        _ => {}
+    }
+}
+
+/// `s` without a leading root prefix.
+fn relative(s: &str, prefixes: &[String]) -> String {
+    prefixes
+        .iter()
+        .find_map(|prefix| s.strip_prefix(prefix.as_str()))
+        .unwrap_or(s)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize_json() {
+        let mut value = json!({
+            "dead": [
+                { "file": "/work/app/src/b.rs", "id": "b1", "lines": [3, 1] },
+                { "file": "/work/app/src/a.rs", "id": "a1", "added": "2026-01-02" },
+            ],
+            "recent": [{ "file": "./src/c.rs", "added": "2026-03-04" }],
+            "by_file": { "/work/app/src/a.rs": 1 },
+            "outside": "/work/other/src/x.rs",
+        });
+        normalize_json(&mut value, Path::new("/work/app"));
+
+        assert_eq!(
+            value,
+            json!({
+                "dead": [
+                    { "file": "src/a.rs", "id": "a1" },
+                    { "file": "src/b.rs", "id": "b1", "lines": [1, 3] },
+                ],
+                "recent": [{ "file": "src/c.rs" }],
+                "by_file": { "src/a.rs": 1 },
+                "outside": "/work/other/src/x.rs",
+            })
+        );
+    }
+}
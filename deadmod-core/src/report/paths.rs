@@ -0,0 +1,209 @@
+//! How file paths are written in reports.
+//!
+//! Scanned paths keep the form the crate root was given in: `./src/net.rs`
+//! after `deadmod .`, `/home/me/app/src/net.rs` after `deadmod ~/app`. A
+//! [`PathStyle`] makes every output write them one way, whatever the
+//! invocation. [`PathFormatter`] is the one helper outputs go through; with
+//! no style set, paths are written as scanned.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::parse::{normalize_path_string, ModuleInfo};
+
+/// Form of file paths in reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PathStyle {
+    /// Absolute paths
+    Absolute,
+    /// Relative to the current directory (`../lib/src/a.rs` outside it)
+    Relative,
+    /// Relative to the crate root (`src/a.rs`); absolute outside the crate
+    Crate,
+}
+
+impl PathStyle {
+    /// Every style, in the order they are listed in help output.
+    pub const ALL: [PathStyle; 3] = [PathStyle::Absolute, PathStyle::Relative, PathStyle::Crate];
+
+    /// Name used in flags and deadmod.toml.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Absolute => "absolute",
+            Self::Relative => "relative",
+            Self::Crate => "crate",
+        }
+    }
+}
+
+impl fmt::Display for PathStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PathStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|p| p.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "unknown path style '{}' (expected absolute, relative or crate)",
+                    s
+                )
+            })
+    }
+}
+
+/// Writes file paths in one [`PathStyle`].
+#[derive(Debug, Clone, Default)]
+pub struct PathFormatter {
+    style: Option<PathStyle>,
+    /// Absolute crate root
+    crate_root: PathBuf,
+    /// Absolute current directory
+    cwd: PathBuf,
+}
+
+impl PathFormatter {
+    /// Formatter for reports on the crate at `crate_root`; `None` writes
+    /// paths as scanned.
+    pub fn new(style: Option<PathStyle>, crate_root: &Path) -> Self {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        Self {
+            style,
+            crate_root: absolute(crate_root, &cwd),
+            cwd,
+        }
+    }
+
+    /// Style in use (`None`: as scanned).
+    pub fn style(&self) -> Option<PathStyle> {
+        self.style
+    }
+
+    /// `path` in the formatter's style. Styled paths use forward slashes.
+    pub fn format(&self, path: impl AsRef<Path>) -> String {
+        let path = path.as_ref();
+        let Some(style) = self.style else {
+            return path.display().to_string();
+        };
+        let path = absolute(path, &self.cwd);
+        let styled = match style {
+            PathStyle::Absolute => path,
+            PathStyle::Relative => relative(&path, &self.cwd),
+            PathStyle::Crate => match path.strip_prefix(&self.crate_root) {
+                Ok(rest) if rest.as_os_str().is_empty() => PathBuf::from("."),
+                Ok(rest) => rest.to_path_buf(),
+                Err(_) => path,
+            },
+        };
+        normalize_path_string(&styled.to_string_lossy())
+    }
+
+    /// Copy of `mods` with every file path formatted, for graph outputs.
+    /// Module keys and everything else are left alone.
+    pub fn localize_modules(
+        &self,
+        mods: &HashMap<String, ModuleInfo>,
+    ) -> HashMap<String, ModuleInfo> {
+        mods.iter()
+            .map(|(name, info)| {
+                let mut info = info.clone();
+                if self.style.is_some() {
+                    info.path = PathBuf::from(self.format(&info.path));
+                }
+                (name.clone(), info)
+            })
+            .collect()
+    }
+}
+
+/// `path` made absolute against `cwd`, with `.` and `..` resolved lexically
+/// (symlinks are not followed, and the file need not exist).
+fn absolute(path: &Path, cwd: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in cwd.join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Absolute `path` relative to absolute `base`, climbing with `..` as needed.
+fn relative(path: &Path, base: &Path) -> PathBuf {
+    let path: Vec<Component> = path.components().collect();
+    let base: Vec<Component> = base.components().collect();
+    let common = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    // Different drives or roots: nothing to be relative to
+    if common == 0 {
+        return path.iter().collect();
+    }
+    let mut out: PathBuf = base[common..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .collect();
+    out.extend(&path[common..]);
+    if out.as_os_str().is_empty() {
+        out.push(".");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formatter(style: Option<PathStyle>) -> PathFormatter {
+        PathFormatter {
+            style,
+            crate_root: PathBuf::from("/work/app"),
+            cwd: PathBuf::from("/work"),
+        }
+    }
+
+    #[test]
+    fn test_path_styles() {
+        let file = Path::new("app/./src/net.rs");
+        assert_eq!(formatter(None).format(file), "app/./src/net.rs");
+        assert_eq!(
+            formatter(Some(PathStyle::Absolute)).format(file),
+            "/work/app/src/net.rs"
+        );
+        assert_eq!(
+            formatter(Some(PathStyle::Relative)).format(file),
+            "app/src/net.rs"
+        );
+        assert_eq!(formatter(Some(PathStyle::Crate)).format(file), "src/net.rs");
+
+        // Outside the current directory and the crate
+        let other = Path::new("/lib/src/a.rs");
+        assert_eq!(
+            formatter(Some(PathStyle::Relative)).format(other),
+            "../lib/src/a.rs"
+        );
+        assert_eq!(
+            formatter(Some(PathStyle::Crate)).format(other),
+            "/lib/src/a.rs"
+        );
+    }
+
+    #[test]
+    fn test_parse_path_style() {
+        assert_eq!("Crate".parse::<PathStyle>(), Ok(PathStyle::Crate));
+        assert!("cwd".parse::<PathStyle>().is_err());
+    }
+}
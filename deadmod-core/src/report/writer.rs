@@ -0,0 +1,978 @@
+//! Multi-format report writers.
+//!
+//! A single analysis run can be emitted in several formats at once instead of
+//! re-running the analyzer per format:
+//!
+//! ```rust,ignore
+//! use deadmod_core::report::{write_outputs, ModuleReport, OutputSpec};
+//!
+//! let specs: Vec<OutputSpec> = vec![
+//!     "format=json,path=dead.json".parse()?,
+//!     "format=html,path=graph.html".parse()?,
+//!     "plain".parse()?, // stdout
+//! ];
+//! write_outputs(&specs, &ModuleReport::new(&mods, &reachable, &dead))?;
+//! ```
+//!
+//! # Spec Syntax
+//!
+//! `format=<fmt>[,path=<file>]` or just `<fmt>`. Without a `path` the output
+//! goes to stdout. Supported formats: `plain`, `json`, `dot`, `html`, `pixi`.
+//! `dot` and `html` require the `html` feature, `pixi` the `pixi` feature.
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::json;
+
+use super::limit::{more_summary, ItemLimit};
+use super::normalize::normalize_json;
+use crate::collision::find_collisions;
+use crate::common::{module_id, Confidence};
+use crate::detect::find_dead;
+use crate::graph::{focus_modules, qualified_module_path};
+use crate::history::RunDelta;
+use crate::layout::LayoutOptions;
+use crate::limits::Skipped;
+use crate::parse::ModuleInfo;
+use crate::theme::Theme;
+use crate::workspace::CrateAnalysis;
+
+/// Output format understood by the report writer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    /// Human-readable text report
+    Plain,
+    /// Machine-readable JSON report
+    Json,
+    /// Graphviz DOT module graph
+    Dot,
+    /// Interactive HTML Canvas visualization
+    Html,
+    /// GPU-accelerated PixiJS WebGL visualization
+    Pixi,
+}
+
+impl OutputFormat {
+    /// All formats, in the order they are listed in help output.
+    pub const ALL: [OutputFormat; 5] = [
+        OutputFormat::Plain,
+        OutputFormat::Json,
+        OutputFormat::Dot,
+        OutputFormat::Html,
+        OutputFormat::Pixi,
+    ];
+
+    /// Canonical name used in `--output` specs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Plain => "plain",
+            Self::Json => "json",
+            Self::Dot => "dot",
+            Self::Html => "html",
+            Self::Pixi => "pixi",
+        }
+    }
+
+    /// Whether this build of deadmod-core can render the format.
+    pub fn is_available(&self) -> bool {
+        match self {
+            Self::Plain | Self::Json => true,
+            Self::Dot | Self::Html => cfg!(feature = "html"),
+            Self::Pixi => cfg!(feature = "pixi"),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "plain" | "text" => Ok(Self::Plain),
+            "json" => Ok(Self::Json),
+            "dot" | "graphviz" => Ok(Self::Dot),
+            "html" => Ok(Self::Html),
+            "pixi" | "html-pixi" => Ok(Self::Pixi),
+            other => Err(format!(
+                "unknown output format '{}' (expected one of: {})",
+                other,
+                OutputFormat::ALL.map(|f| f.as_str()).join(", ")
+            )),
+        }
+    }
+}
+
+/// A single requested output: a format and an optional destination file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputSpec {
+    /// Format to render
+    pub format: OutputFormat,
+    /// Destination file (`None` = stdout)
+    pub path: Option<PathBuf>,
+}
+
+impl OutputSpec {
+    /// Create a spec writing `format` to stdout.
+    pub fn stdout(format: OutputFormat) -> Self {
+        Self { format, path: None }
+    }
+
+    /// Create a spec writing `format` to the given file.
+    pub fn file(format: OutputFormat, path: impl Into<PathBuf>) -> Self {
+        Self {
+            format,
+            path: Some(path.into()),
+        }
+    }
+}
+
+impl FromStr for OutputSpec {
+    type Err = String;
+
+    /// Parse `format=json,path=out.json`, `json,path=out.json` or `json`.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut format = None;
+        let mut path = None;
+
+        for (i, part) in s.split(',').enumerate() {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            match part.split_once('=') {
+                Some(("format", value)) => format = Some(value.parse()?),
+                Some(("path", value)) if !value.trim().is_empty() => {
+                    path = Some(PathBuf::from(value.trim()))
+                }
+                Some(("path", _)) => return Err("empty path in output spec".to_string()),
+                Some((key, _)) => {
+                    return Err(format!(
+                        "unknown key '{}' in output spec (expected 'format' or 'path')",
+                        key
+                    ))
+                }
+                // Bare value in first position is shorthand for `format=<value>`
+                None if i == 0 => format = Some(part.parse()?),
+                None => return Err(format!("malformed output spec segment '{}'", part)),
+            }
+        }
+
+        let format = format.ok_or_else(|| format!("output spec '{}' has no format", s))?;
+        Ok(Self { format, path })
+    }
+}
+
+impl fmt::Display for OutputSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "format={}", self.format)?;
+        if let Some(ref path) = self.path {
+            write!(f, ",path={}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Results of one module analysis run, shared by every writer.
+#[derive(Debug, Clone, Copy)]
+pub struct ModuleReport<'a> {
+    /// All parsed modules
+    pub mods: &'a HashMap<String, ModuleInfo>,
+    /// Modules reachable from the entry points
+    pub reachable: &'a HashSet<String>,
+    /// Dead modules, sorted
+    pub dead: &'a [&'a str],
+    /// Number of crates when reporting a combined workspace graph
+    pub workspace_crates: Option<usize>,
+    /// Per-member results of a workspace report (see [`crate::split_by_crate`])
+    pub crate_analyses: &'a [CrateAnalysis],
+    /// Inputs left out by scan limits
+    pub skipped: &'a [Skipped],
+    /// Module count above which the `pixi` graph starts clustered
+    /// (`None` = the viewer's default)
+    pub pixi_cluster_threshold: Option<usize>,
+    /// Initial node positions of the `html` and `pixi` graphs
+    pub layout: LayoutOptions,
+    /// Colors of the `dot`, `html` and `pixi` graphs
+    pub theme: Theme,
+    /// Clusters, direction and node budget of the `dot` graph (its theme
+    /// is [`Self::theme`])
+    #[cfg(feature = "html")]
+    pub dot: crate::visualize::DotOptions,
+    /// How many dead modules plain output lists
+    pub item_limit: ItemLimit,
+    /// Crate root JSON output is normalized against (see [`normalize_json`])
+    pub normalize_root: Option<&'a Path>,
+    /// Module and hop count graph formats are limited to (see [`focus_modules`])
+    pub focus: Option<(&'a str, usize)>,
+    /// Entry points reachability was checked from, listed in each JSON
+    /// explanation
+    pub entry_points: &'a [String],
+    /// Counts against the last run (see [`crate::history`])
+    pub delta: Option<&'a RunDelta>,
+}
+
+impl<'a> ModuleReport<'a> {
+    /// Create a report for a single crate.
+    pub fn new(
+        mods: &'a HashMap<String, ModuleInfo>,
+        reachable: &'a HashSet<String>,
+        dead: &'a [&'a str],
+    ) -> Self {
+        Self {
+            mods,
+            reachable,
+            dead,
+            workspace_crates: None,
+            crate_analyses: &[],
+            skipped: &[],
+            pixi_cluster_threshold: None,
+            layout: LayoutOptions::default(),
+            theme: Theme::default(),
+            #[cfg(feature = "html")]
+            dot: crate::visualize::DotOptions::default(),
+            item_limit: ItemLimit::default(),
+            normalize_root: None,
+            focus: None,
+            entry_points: &[],
+            delta: None,
+        }
+    }
+
+    /// Mark the report as covering a workspace of `crates` crates.
+    pub fn with_workspace_crates(mut self, crates: usize) -> Self {
+        self.workspace_crates = Some(crates);
+        self
+    }
+
+    /// Break workspace JSON down into one section per member crate.
+    pub fn with_crate_analyses(mut self, crates: &'a [CrateAnalysis]) -> Self {
+        self.crate_analyses = crates;
+        self
+    }
+
+    /// List inputs that were left out by [`crate::limits::Limits`].
+    pub fn with_skipped(mut self, skipped: &'a [Skipped]) -> Self {
+        self.skipped = skipped;
+        self
+    }
+
+    /// Start the `pixi` graph collapsed into cluster super-nodes when it has
+    /// more than `threshold` modules.
+    pub fn with_pixi_cluster_threshold(mut self, threshold: usize) -> Self {
+        self.pixi_cluster_threshold = Some(threshold);
+        self
+    }
+
+    /// Seed the initial layout of the `html` and `pixi` graphs.
+    pub fn with_layout(mut self, layout: LayoutOptions) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Color the `dot`, `html` and `pixi` graphs with `theme`.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Shape the `dot` graph with `options`.
+    #[cfg(feature = "html")]
+    pub fn with_dot_options(mut self, options: crate::visualize::DotOptions) -> Self {
+        self.dot = options;
+        self
+    }
+
+    /// Cap the dead module list of plain output (see [`ItemLimit`]).
+    pub fn with_item_limit(mut self, limit: ItemLimit) -> Self {
+        self.item_limit = limit;
+        self
+    }
+
+    /// Normalize JSON output against the crate `root` for snapshots.
+    pub fn with_normalized_json(mut self, root: &'a Path) -> Self {
+        self.normalize_root = Some(root);
+        self
+    }
+
+    /// Record the entry points (module keys) the analysis started from.
+    pub fn with_entry_points(mut self, entry_points: &'a [String]) -> Self {
+        self.entry_points = entry_points;
+        self
+    }
+
+    /// End plain output with the change since the last run, and add it to
+    /// JSON as `delta`.
+    pub fn with_delta(mut self, delta: &'a RunDelta) -> Self {
+        self.delta = Some(delta);
+        self
+    }
+
+    /// Limit graph formats (`dot`, `html`, `pixi`) to the modules within
+    /// `depth` hops of `module`; plain and JSON output stay complete.
+    pub fn with_focus(mut self, module: &'a str, depth: usize) -> Self {
+        self.focus = Some((module, depth));
+        self
+    }
+
+    /// Modules graph formats draw: all of them, or the focused neighborhood.
+    ///
+    /// Fails when the focus names no module.
+    pub fn graph_mods(&self) -> Result<Cow<'a, HashMap<String, ModuleInfo>>> {
+        Ok(match self.focus {
+            Some((module, depth)) => Cow::Owned(focus_modules(self.mods, module, depth)?),
+            None => Cow::Borrowed(self.mods),
+        })
+    }
+
+    /// Reported identity of the module keyed `key`: its crate-relative
+    /// module path, prefixed with the crate name in workspace mode
+    /// (`crate_a::net::tcp`, or just `crate_a` for the crate root).
+    /// Falls back to `key` for modules missing from `mods`.
+    pub fn module_path(&self, key: &str) -> String {
+        self.mods
+            .get(key)
+            .map_or_else(|| key.to_string(), |info| qualified_module_path(key, info))
+    }
+
+    /// Dead modules by module path, sorted.
+    pub fn dead_paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self.dead.iter().map(|m| self.module_path(m)).collect();
+        paths.sort();
+        paths
+    }
+
+    /// Why each dead module is dead, sorted by module path: the modules
+    /// still referencing it (all dead themselves) and the entry points
+    /// none of which reach it.
+    pub fn explanations(&self) -> Vec<serde_json::Value> {
+        let reachable: HashSet<&str> = self.reachable.iter().map(String::as_str).collect();
+        let referenced_by: HashMap<&str, Vec<&str>> = find_dead(self.mods, &reachable)
+            .into_iter()
+            .map(|report| (report.name, report.referenced_by))
+            .collect();
+        let entry_points: Vec<String> = self
+            .entry_points
+            .iter()
+            .map(|m| self.module_path(m))
+            .collect();
+
+        let mut explanations: Vec<(String, serde_json::Value)> = self
+            .dead
+            .iter()
+            .map(|key| {
+                let module = self.module_path(key);
+                let referrers: Vec<String> = referenced_by
+                    .get(key)
+                    .into_iter()
+                    .flatten()
+                    .map(|m| self.module_path(m))
+                    .collect();
+                let value = json!({
+                    "module": module,
+                    "referenced_by": referrers,
+                    "entry_points": entry_points,
+                });
+                (module, value)
+            })
+            .collect();
+        explanations.sort_by(|a, b| a.0.cmp(&b.0));
+        explanations.into_iter().map(|(_, value)| value).collect()
+    }
+}
+
+/// Render a report in the given format.
+///
+/// Returns an error if the format was compiled out via cargo features.
+pub fn render(format: OutputFormat, report: &ModuleReport<'_>) -> Result<String> {
+    if !format.is_available() {
+        return Err(anyhow!(
+            "output format '{}' is not available in this build (missing cargo feature)",
+            format
+        ));
+    }
+
+    Ok(match format {
+        OutputFormat::Plain => render_plain(report),
+        OutputFormat::Json => render_json(report)?,
+        #[cfg(feature = "html")]
+        OutputFormat::Dot => {
+            let options = report.dot.with_theme(report.theme);
+            crate::visualize::generate_dot_with_options(
+                &*report.graph_mods()?,
+                report.reachable,
+                &options,
+            )
+        }
+        #[cfg(feature = "html")]
+        OutputFormat::Html => crate::visualize_html::generate_html_graph_with_layout(
+            &*report.graph_mods()?,
+            report.reachable,
+            &report.layout,
+            report.theme,
+        ),
+        #[cfg(feature = "pixi")]
+        OutputFormat::Pixi => crate::visualize_pixi::generate_pixi_graph_with_layout(
+            &*report.graph_mods()?,
+            report.reachable,
+            report
+                .pixi_cluster_threshold
+                .unwrap_or(crate::visualize_pixi::DEFAULT_CLUSTER_THRESHOLD),
+            &report.layout,
+            report.theme,
+        ),
+        #[allow(unreachable_patterns)]
+        _ => unreachable!("availability checked above"),
+    })
+}
+
+fn render_plain(report: &ModuleReport<'_>) -> String {
+    let (listed, hidden) = listed_dead(report);
+    let more = more_summary(hidden);
+    let Some(crates) = report.workspace_crates else {
+        let mut out = if hidden == 0 {
+            let dead: Vec<&str> = listed.iter().map(String::as_str).collect();
+            super::format_plain(&dead)
+        } else {
+            let mut out = format!("DEAD MODULES ({}):\n", report.dead.len());
+            for m in &listed {
+                out.push_str(&format!("- {}\n", m));
+            }
+            out.extend(more.map(|line| line + "\n"));
+            out
+        };
+        push_collisions(&mut out, report);
+        push_skipped(&mut out, report);
+        push_delta(&mut out, report);
+        return out;
+    };
+
+    let mut out = String::from("=== Workspace Analysis ===\n\n");
+    out.push_str(&format!("Crates: {}\n", crates));
+    out.push_str(&format!("Total modules: {}\n", report.mods.len()));
+    out.push_str(&format!("Reachable: {}\n", report.reachable.len()));
+    out.push_str(&format!("Dead: {}\n\n", report.dead.len()));
+
+    if report.dead.is_empty() {
+        out.push_str("No dead modules found.\n");
+    } else {
+        out.push_str("DEAD MODULES:\n");
+        for m in &listed {
+            out.push_str(&format!("  - {}\n", m));
+        }
+        out.extend(more.map(|line| format!("  {}\n", line)));
+    }
+    push_collisions(&mut out, report);
+    push_skipped(&mut out, report);
+    push_delta(&mut out, report);
+    out
+}
+
+/// Dead module paths plain output lists under the report's item limit,
+/// and the number left out. Larger files rank first under `--top`.
+fn listed_dead(report: &ModuleReport<'_>) -> (Vec<String>, usize) {
+    let paths = report.dead_paths();
+    if !report.item_limit.is_active() {
+        return (paths, 0);
+    }
+
+    let sizes: HashMap<String, u64> = report
+        .dead
+        .iter()
+        .map(|key| {
+            let size = report
+                .mods
+                .get(*key)
+                .and_then(|info| fs::metadata(&info.path).ok())
+                .map_or(0, |meta| meta.len());
+            (report.module_path(key), size)
+        })
+        .collect();
+    let truncated = report.item_limit.apply(&paths, |path| {
+        (
+            Confidence::High,
+            sizes.get(path).copied().unwrap_or(0) as usize,
+        )
+    });
+    (
+        truncated.shown.into_iter().cloned().collect(),
+        truncated.hidden,
+    )
+}
+
+/// Append the modules that got no verdict because their path is ambiguous.
+fn push_collisions(out: &mut String, report: &ModuleReport<'_>) {
+    let collisions = find_collisions(report.mods);
+    if collisions.is_empty() {
+        return;
+    }
+
+    out.push_str(&format!(
+        "\nMODULE COLLISIONS ({}, not analyzed):\n",
+        collisions.len()
+    ));
+    for collision in &collisions {
+        let files: Vec<String> = collision
+            .files
+            .iter()
+            .map(|f| f.display().to_string())
+            .collect();
+        out.push_str(&format!(
+            "- {}: {}\n",
+            collision.module_path,
+            files.join(", ")
+        ));
+    }
+}
+
+/// Append the inputs that were left out by scan limits.
+fn push_skipped(out: &mut String, report: &ModuleReport<'_>) {
+    if report.skipped.is_empty() {
+        return;
+    }
+
+    out.push_str(&format!(
+        "\nSKIPPED ({}, not analyzed):\n",
+        report.skipped.len()
+    ));
+    for skipped in report.skipped {
+        out.push_str(&format!("- {}\n", skipped));
+    }
+}
+
+/// Append the change since the last run.
+fn push_delta(out: &mut String, report: &ModuleReport<'_>) {
+    if let Some(delta) = report.delta {
+        out.push_str(&format!("\n{}\n", delta));
+    }
+}
+
+fn render_json(report: &ModuleReport<'_>) -> Result<String> {
+    let paths = report.dead_paths();
+    let dead: Vec<&str> = paths.iter().map(String::as_str).collect();
+    let collisions = find_collisions(report.mods);
+    let Some(crates) = report.workspace_crates else {
+        let value = json!({
+            "dead": dead,
+            "ids": super::module_ids(&dead),
+            "explanations": report.explanations(),
+            "collisions": collisions,
+            "skipped": report.skipped,
+        });
+        return pretty_json(value, report);
+    };
+
+    let value = json!({
+        "workspace": true,
+        "crates": crates,
+        "total_modules": report.mods.len(),
+        "reachable": report.reachable.len(),
+        "dead_count": report.dead.len(),
+        "dead_modules": dead,
+        "ids": super::module_ids(&dead),
+        "by_crate": crate_sections(report),
+        "explanations": report.explanations(),
+        "collisions": collisions,
+        "skipped": report.skipped,
+    });
+    pretty_json(value, report)
+}
+
+/// One JSON section per member crate: its root, module counts and dead
+/// modules, so workspace reports can be split by crate ownership.
+fn crate_sections(report: &ModuleReport<'_>) -> Vec<serde_json::Value> {
+    report
+        .crate_analyses
+        .iter()
+        .map(|krate| {
+            let mut items: Vec<(String, String)> = krate
+                .dead_modules
+                .iter()
+                .map(|module| {
+                    let key = format!("{}::{}", krate.name, module);
+                    let path = report.mods.get(&key).map(|info| info.path.display().to_string()).unwrap_or_default();
+                    (report.module_path(&key), path)
+                })
+                .collect();
+            items.sort();
+            let items: Vec<serde_json::Value> = items
+                .into_iter()
+                .map(|(module, path)| json!({ "id": module_id(&module), "module": module, "path": path }))
+                .collect();
+            json!({
+                "crate": krate.name,
+                "root": krate.root.display().to_string(),
+                "counts": {
+                    "modules": krate.total_modules,
+                    "reachable": krate.reachable_modules.len(),
+                    "dead": krate.dead_modules.len(),
+                },
+                "items": items,
+            })
+        })
+        .collect()
+}
+
+fn pretty_json(mut value: serde_json::Value, report: &ModuleReport<'_>) -> Result<String> {
+    if let Some(delta) = report.delta {
+        value["delta"] = json!(delta);
+    }
+    if let Some(root) = report.normalize_root {
+        normalize_json(&mut value, root);
+    }
+    serde_json::to_string_pretty(&value).context("Failed to serialize report to JSON")
+}
+
+/// Render every spec and write it to its destination.
+///
+/// All formats are rendered before anything is written, so an unavailable
+/// format never leaves a partial set of files behind. Paths are written
+/// as given; callers are responsible for validating untrusted paths.
+pub fn write_outputs(specs: &[OutputSpec], report: &ModuleReport<'_>) -> Result<()> {
+    let _span = tracing::info_span!("report", outputs = specs.len()).entered();
+    let rendered = specs
+        .iter()
+        .map(|spec| render(spec.format, report).map(|out| (spec, out)))
+        .collect::<Result<Vec<_>>>()?;
+
+    for (spec, out) in rendered {
+        match spec.path {
+            Some(ref path) => {
+                fs::write(path, &out).with_context(|| {
+                    format!(
+                        "Failed to write {} output to {}",
+                        spec.format,
+                        path.display()
+                    )
+                })?;
+                eprintln!(
+                    "[deadmod] {} output written → {}",
+                    spec.format,
+                    path.display()
+                );
+            }
+            None if out.ends_with('\n') => print!("{}", out),
+            None => println!("{}", out),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::limits::SkipReason;
+
+    #[test]
+    fn test_parse_spec_full() {
+        let spec: OutputSpec = "format=json,path=out.json".parse().unwrap();
+        assert_eq!(spec, OutputSpec::file(OutputFormat::Json, "out.json"));
+    }
+
+    #[test]
+    fn test_parse_spec_shorthand() {
+        let spec: OutputSpec = "dot".parse().unwrap();
+        assert_eq!(spec, OutputSpec::stdout(OutputFormat::Dot));
+
+        let spec: OutputSpec = "html,path=graph.html".parse().unwrap();
+        assert_eq!(spec, OutputSpec::file(OutputFormat::Html, "graph.html"));
+    }
+
+    #[test]
+    fn test_parse_spec_errors() {
+        assert!("format=yaml".parse::<OutputSpec>().is_err());
+        assert!("path=out.json".parse::<OutputSpec>().is_err());
+        assert!("format=json,dest=x".parse::<OutputSpec>().is_err());
+        assert!("format=json,path=".parse::<OutputSpec>().is_err());
+    }
+
+    #[test]
+    fn test_spec_display_roundtrip() {
+        let spec = OutputSpec::file(OutputFormat::Pixi, "g.html");
+        let parsed: OutputSpec = spec.to_string().parse().unwrap();
+        assert_eq!(parsed, spec);
+    }
+
+    #[test]
+    fn test_render_plain_and_json() {
+        let mods = HashMap::new();
+        let reachable = HashSet::new();
+        let dead = ["a", "b"];
+        let report = ModuleReport::new(&mods, &reachable, &dead);
+
+        let plain = render(OutputFormat::Plain, &report).unwrap();
+        assert!(plain.contains("DEAD MODULES (2):"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &report).unwrap()).unwrap();
+        assert_eq!(json["dead"], json!(["a", "b"]));
+        assert_eq!(json["ids"]["a"], crate::common::module_id("a"));
+
+        // JSON always lists every module
+        let limited = report.with_item_limit(ItemLimit::new(Some(1), false));
+        let plain = render(OutputFormat::Plain, &limited).unwrap();
+        assert_eq!(
+            plain,
+            "DEAD MODULES (2):\n- a\n… and 1 more (see JSON export)\n"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &limited).unwrap()).unwrap();
+        assert_eq!(json["dead"], json!(["a", "b"]));
+    }
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn test_render_focused_graph() {
+        let mut mods = HashMap::new();
+        for (name, refs) in [("main", &["net"][..]), ("net", &[]), ("orphan", &[])] {
+            let mut info = ModuleInfo::new(PathBuf::from(format!("src/{}.rs", name)));
+            info.refs.extend(refs.iter().map(|r| r.to_string()));
+            mods.insert(name.to_string(), info);
+        }
+        let reachable: HashSet<String> = ["main".to_string(), "net".to_string()].into();
+        let dead = ["orphan"];
+
+        let report = ModuleReport::new(&mods, &reachable, &dead).with_focus("net", 1);
+        let dot = render(OutputFormat::Dot, &report).unwrap();
+        assert!(dot.contains("\"main\" -> \"net\""));
+        assert!(!dot.contains("orphan"));
+        // Reports are not focused
+        assert!(render(OutputFormat::Plain, &report)
+            .unwrap()
+            .contains("orphan"));
+
+        let report = ModuleReport::new(&mods, &reachable, &dead).with_focus("missing", 1);
+        assert!(render(OutputFormat::Dot, &report).is_err());
+    }
+
+    #[test]
+    fn test_render_json_explanations() {
+        let mut mods = HashMap::new();
+        for (name, refs) in [
+            ("main", &["net"][..]),
+            ("net", &[]),
+            ("old", &["legacy"]),
+            ("legacy", &[]),
+        ] {
+            let mut info = ModuleInfo::new(PathBuf::from(format!("src/{}.rs", name)));
+            info.refs.extend(refs.iter().map(|r| r.to_string()));
+            mods.insert(name.to_string(), info);
+        }
+        let reachable: HashSet<String> = ["main".to_string(), "net".to_string()].into();
+        let dead = ["legacy", "old"];
+        let entry_points = ["main".to_string()];
+        let report = ModuleReport::new(&mods, &reachable, &dead).with_entry_points(&entry_points);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &report).unwrap()).unwrap();
+        assert_eq!(
+            json["explanations"],
+            json!([
+                {"module": "legacy", "referenced_by": ["old"], "entry_points": ["crate"]},
+                {"module": "old", "referenced_by": [], "entry_points": ["crate"]},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_render_workspace_json() {
+        let mods = HashMap::new();
+        let reachable = HashSet::new();
+        let dead = ["crate_a::x"];
+        let report = ModuleReport::new(&mods, &reachable, &dead).with_workspace_crates(2);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &report).unwrap()).unwrap();
+        assert_eq!(json["crates"], 2);
+        assert_eq!(json["dead_modules"], json!(["crate_a::x"]));
+        assert_eq!(json["by_crate"], json!([]));
+    }
+
+    #[test]
+    fn test_render_workspace_json_by_crate() {
+        let mut mods = HashMap::new();
+        mods.insert(
+            "crate_a::lib".to_string(),
+            ModuleInfo::new(PathBuf::from("crate_a/src/lib.rs")),
+        );
+        mods.insert(
+            "crate_a::old".to_string(),
+            ModuleInfo::new(PathBuf::from("crate_a/src/old.rs")),
+        );
+        // Workspace-combined modules carry their crate
+        for (key, info) in &mut mods {
+            info.krate = key.split_once("::").map(|(krate, _)| krate.to_string());
+        }
+        let reachable: HashSet<String> = ["crate_a::lib".to_string()].into();
+        let dead = ["crate_a::old"];
+        let members = [
+            ("crate_a".to_string(), PathBuf::from("crate_a")),
+            ("crate_b".to_string(), PathBuf::from("crate_b")),
+        ];
+        let crates = crate::workspace::split_by_crate(&members, &mods, &reachable, &dead);
+        let report = ModuleReport::new(&mods, &reachable, &dead)
+            .with_workspace_crates(2)
+            .with_crate_analyses(&crates);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &report).unwrap()).unwrap();
+        let section = &json["by_crate"][0];
+        assert_eq!(section["crate"], "crate_a");
+        assert_eq!(section["root"], "crate_a");
+        assert_eq!(
+            section["counts"],
+            json!({ "modules": 2, "reachable": 1, "dead": 1 })
+        );
+        assert_eq!(section["items"][0]["module"], "crate_a::old");
+        assert_eq!(section["items"][0]["path"], "crate_a/src/old.rs");
+        assert_eq!(section["items"][0]["id"], json["ids"]["crate_a::old"]);
+        assert_eq!(json["by_crate"][1]["counts"]["modules"], 0);
+    }
+
+    #[test]
+    fn test_render_reports_module_paths() {
+        let mut mods = HashMap::new();
+        mods.insert(
+            "tcp".to_string(),
+            ModuleInfo::new(PathBuf::from("src/net/tcp/mod.rs")),
+        );
+        mods.insert(
+            "listener".to_string(),
+            ModuleInfo::new(PathBuf::from("src/net/tcp/listener.rs")),
+        );
+        let reachable = HashSet::new();
+        let dead = ["listener", "tcp"];
+        let report = ModuleReport::new(&mods, &reachable, &dead);
+
+        let plain = render(OutputFormat::Plain, &report).unwrap();
+        assert!(plain.contains("- net::tcp\n"));
+        assert!(plain.contains("- net::tcp::listener\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &report).unwrap()).unwrap();
+        assert_eq!(json["dead"], json!(["net::tcp", "net::tcp::listener"]));
+        assert_eq!(
+            json["ids"]["net::tcp"],
+            crate::common::module_id("net::tcp")
+        );
+
+        let mut ws = HashMap::new();
+        ws.insert(
+            "crate_a::lib".to_string(),
+            ModuleInfo::new(PathBuf::from("crate_a/src/lib.rs")),
+        );
+        ws.insert(
+            "crate_a::tcp".to_string(),
+            ModuleInfo::new(PathBuf::from("crate_a/src/net/tcp.rs")),
+        );
+        // Workspace-combined modules carry their crate
+        for (key, info) in &mut ws {
+            info.krate = key.split_once("::").map(|(krate, _)| krate.to_string());
+        }
+        let report = ModuleReport::new(&ws, &reachable, &[]);
+        assert_eq!(report.module_path("crate_a::lib"), "crate_a");
+        assert_eq!(report.module_path("crate_a::tcp"), "crate_a::net::tcp");
+        assert_eq!(report.module_path("missing"), "missing");
+    }
+
+    #[test]
+    fn test_render_reports_collisions() {
+        let mods = crate::collision::key_modules([
+            ModuleInfo::new(PathBuf::from("src/lib.rs")),
+            ModuleInfo::new(PathBuf::from("src/net.rs")),
+            ModuleInfo::new(PathBuf::from("src/net/mod.rs")),
+        ]);
+        let reachable = HashSet::new();
+        let report = ModuleReport::new(&mods, &reachable, &[]);
+
+        let plain = render(OutputFormat::Plain, &report).unwrap();
+        assert!(plain.contains("MODULE COLLISIONS (1, not analyzed):"));
+        assert!(plain.contains("- net: src/net/mod.rs, src/net.rs"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &report).unwrap()).unwrap();
+        assert_eq!(json["collisions"][0]["module_path"], "net");
+        assert_eq!(json["collisions"][0]["files"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_render_reports_skipped() {
+        let mods = HashMap::new();
+        let reachable = HashSet::new();
+        let skipped = [Skipped {
+            input: "src/generated.rs".to_string(),
+            reason: SkipReason::TooLarge {
+                size: 2_000,
+                limit: 1_000,
+            },
+        }];
+        let report = ModuleReport::new(&mods, &reachable, &[]).with_skipped(&skipped);
+
+        let plain = render(OutputFormat::Plain, &report).unwrap();
+        assert!(plain.contains("SKIPPED (1, not analyzed):"));
+        assert!(plain
+            .contains("- src/generated.rs: 2000 bytes exceeds the max file size of 1000 bytes"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &report).unwrap()).unwrap();
+        assert_eq!(json["skipped"][0]["input"], "src/generated.rs");
+        assert_eq!(json["skipped"][0]["reason"]["kind"], "too_large");
+    }
+
+    #[test]
+    fn test_render_reports_delta() {
+        let mods = HashMap::new();
+        let reachable = HashSet::new();
+        let previous = crate::RunCounts::new().with("dead_modules", 3);
+        let delta = RunDelta::between(&previous, &crate::RunCounts::new().with("dead_modules", 1));
+        let report = ModuleReport::new(&mods, &reachable, &["a"]).with_delta(&delta);
+
+        let plain = render(OutputFormat::Plain, &report).unwrap();
+        assert!(plain.ends_with("\nΔ since last run: −2 dead modules\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &report).unwrap()).unwrap();
+        assert_eq!(
+            json["delta"]["dead_modules"],
+            json!({ "previous": 3, "current": 1, "change": -2 })
+        );
+    }
+
+    #[test]
+    fn test_write_outputs_multiple_files() {
+        let dir = std::env::temp_dir().join(format!("deadmod_writer_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let mods = HashMap::new();
+        let reachable = HashSet::new();
+        let dead = ["dead"];
+        let report = ModuleReport::new(&mods, &reachable, &dead);
+
+        let specs = vec![
+            OutputSpec::file(OutputFormat::Json, dir.join("out.json")),
+            OutputSpec::file(OutputFormat::Plain, dir.join("out.txt")),
+        ];
+        write_outputs(&specs, &report).unwrap();
+
+        assert!(fs::read_to_string(dir.join("out.json"))
+            .unwrap()
+            .contains("\"dead\""));
+        assert!(fs::read_to_string(dir.join("out.txt"))
+            .unwrap()
+            .contains("- dead"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
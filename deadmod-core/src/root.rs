@@ -7,19 +7,36 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
+use crate::parse::{is_target_root_file, module_path_from_file, TARGET_DIRS};
+
 /// Detect all valid Cargo root modules for a crate.
 ///
 /// NASA-grade resilience: never panics, returns empty set on any error.
 ///
-/// Returns module names (e.g. "main", "lib", "convert_cli").
+/// Returns module names (e.g. "main", "lib", "convert_cli", "tests::smoke").
 ///
 /// This implements Cargo's full entrypoint logic:
 /// - src/main.rs
 /// - src/lib.rs
 /// - src/bin/*.rs
 /// - src/bin/<name>/main.rs
+/// - tests/, benches/ and examples/ targets (`*.rs`, `<name>/main.rs`),
+///   named by module path
+///
+/// Targets outside `src/` are roots of their own: they only reach the
+/// crate's modules through its public API, but the modules they declare
+/// (`tests/common/mod.rs`) are theirs.
+///
+/// With the `cargo-metadata` feature, the targets `cargo metadata` lists
+/// are used instead, which covers custom `lib.path`/`[[bin]] path` entries
+/// and build scripts; the layout rules above apply when cargo cannot be run.
 pub fn find_root_modules(crate_root: &Path) -> HashSet<String> {
-    let mut out = HashSet::new();
+    #[cfg(feature = "cargo-metadata")]
+    if let Some(roots) = crate::metadata::package_root_modules(crate_root) {
+        return roots;
+    }
+
+    let mut out = find_target_roots(crate_root);
 
     let src = crate_root.join("src");
     if !src.exists() {
@@ -68,6 +85,60 @@ pub fn find_root_modules(crate_root: &Path) -> HashSet<String> {
     out
 }
 
+/// Test, bench and example targets of a crate, named by module path
+/// (`tests::it`, `examples::demo`).
+fn find_target_roots(crate_root: &Path) -> HashSet<String> {
+    let mut out = HashSet::new();
+
+    for dir in TARGET_DIRS {
+        let Ok(entries) = fs::read_dir(crate_root.join(dir)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            // tests/name.rs, or tests/name/main.rs
+            let root_file = if path.is_dir() {
+                path.join("main.rs")
+            } else {
+                path
+            };
+            if root_file.is_file() && is_target_root_file(&root_file) {
+                out.insert(module_path_from_file(&root_file));
+            }
+        }
+    }
+
+    out
+}
+
+/// Root modules for reachability: `custom` if non-empty, else [`find_root_modules`].
+///
+/// Custom roots (`--roots`, [`Deadmod::with_roots`]) replace Cargo's entry
+/// points entirely, answering "what would be dead if only these existed?".
+///
+/// [`Deadmod::with_roots`]: crate::Deadmod::with_roots
+pub fn resolve_root_modules(crate_root: &Path, custom: &[String]) -> HashSet<String> {
+    if custom.is_empty() {
+        find_root_modules(crate_root)
+    } else {
+        custom.iter().cloned().collect()
+    }
+}
+
+/// Custom roots that apply to `crate_name` in a workspace.
+///
+/// `crate::module` entries select a module of one crate, plain `module`
+/// entries apply to every crate.
+pub fn roots_for_crate(custom: &[String], crate_name: &str) -> HashSet<String> {
+    custom
+        .iter()
+        .filter_map(|root| match root.split_once("::") {
+            Some((krate, module)) => (krate == crate_name).then(|| module.to_string()),
+            None => Some(root.clone()),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,4 +257,51 @@ mod tests {
         assert!(roots.contains("fast_tool"));
         assert!(roots.contains("tool_folder"));
     }
+
+    #[test]
+    fn test_find_root_modules_with_targets() {
+        let temp_dir = create_temp_dir("targets");
+        create_file(&temp_dir.join("src").join("lib.rs"), "");
+        create_file(&temp_dir.join("tests").join("it.rs"), "mod common;");
+        create_file(&temp_dir.join("tests").join("common").join("mod.rs"), "");
+        create_file(&temp_dir.join("benches").join("parse").join("main.rs"), "");
+        create_file(&temp_dir.join("examples").join("demo.rs"), "fn main() {}");
+
+        let roots = find_root_modules(&temp_dir);
+        let mut roots: Vec<&str> = roots.iter().map(String::as_str).collect();
+        roots.sort_unstable();
+        assert_eq!(
+            roots,
+            ["benches::parse", "examples::demo", "lib", "tests::it"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_root_modules_custom_overrides() {
+        let temp_dir = create_temp_dir("custom_roots");
+        create_file(&temp_dir.join("src/main.rs"), "fn main() {}");
+
+        let roots = resolve_root_modules(&temp_dir, &[]);
+        assert!(roots.contains("main"));
+
+        let roots = resolve_root_modules(&temp_dir, &["api".to_string()]);
+        assert_eq!(roots, HashSet::from(["api".to_string()]));
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    #[test]
+    fn test_roots_for_crate() {
+        let custom = vec![
+            "server::api".to_string(),
+            "cli::main".to_string(),
+            "lib".to_string(),
+        ];
+        let roots = roots_for_crate(&custom, "server");
+        assert_eq!(roots, HashSet::from(["api".to_string(), "lib".to_string()]));
+        assert_eq!(
+            roots_for_crate(&custom, "other"),
+            HashSet::from(["lib".to_string()])
+        );
+    }
 }
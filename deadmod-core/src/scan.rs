@@ -15,10 +15,15 @@
 
 use anyhow::{Context, Result};
 use rayon::prelude::*;
+use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use tracing::{debug, info_span};
 use walkdir::WalkDir;
 
+use crate::config::load_config;
+use crate::limits::{Limits, SkipReason, Skipped};
+
 /// Directories to exclude by default (standard Rust project conventions).
 const EXCLUDED_DIRS: &[&str] = &["target", ".git", "node_modules", ".cargo"];
 
@@ -44,9 +49,10 @@ fn is_excluded_dir(entry: &walkdir::DirEntry, excludes: &HashSet<&str>) -> bool
 ///
 /// Automatically excludes `target/`, `.git/`, `node_modules/`, and `.cargo/`.
 pub fn gather_rs_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let _span = info_span!("scan", root = %root.display()).entered();
     let excludes: HashSet<&str> = EXCLUDED_DIRS.iter().copied().collect();
 
-    WalkDir::new(root)
+    let files = WalkDir::new(root)
         .into_iter()
         // CRITICAL: filter_entry prunes entire subtrees before iteration
         // This runs sequentially but prevents thousands of unnecessary entries
@@ -65,7 +71,12 @@ pub fn gather_rs_files(root: &Path) -> Result<Vec<PathBuf>> {
             Err(e) => Some(Err(e.into())),
         })
         .collect::<Result<Vec<_>>>()
-        .context(format!("Failed to gather .rs files from {}", root.display()))
+        .context(format!(
+            "Failed to gather .rs files from {}",
+            root.display()
+        ))?;
+    debug!(files = files.len(), "scanned");
+    Ok(files)
 }
 
 /// Gathers all .rs files with custom exclusion patterns using early pruning.
@@ -99,6 +110,164 @@ pub fn gather_rs_files_with_excludes(root: &Path, excludes: &[&str]) -> Result<V
         .context(format!("Failed to gather .rs files from {}", root.display()))
 }
 
+/// Whether the scan would gather `path`: a `.rs` file under `root` with no
+/// excluded directory (the defaults and `excludes`) between them.
+///
+/// Checks names only, so it also answers for files that no longer exist,
+/// as needed for filesystem events.
+pub fn is_scanned_path(root: &Path, path: &Path, excludes: &[&str]) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return false;
+    };
+    let mut dirs = relative.parent().into_iter().flat_map(Path::components);
+    path.extension().is_some_and(|ext| ext == "rs")
+        && !dirs.any(|dir| {
+            let name = dir.as_os_str();
+            EXCLUDED_DIRS
+                .iter()
+                .chain(excludes)
+                .any(|excluded| name == *excluded)
+        })
+}
+
+/// Gathers all .rs files under `root`, leaving out those beyond `limits`.
+///
+/// Returns the kept files in path order, and a [`Skipped`] entry for each
+/// file left out.
+pub fn gather_rs_files_limited(
+    root: &Path,
+    limits: &Limits,
+) -> Result<(Vec<PathBuf>, Vec<Skipped>)> {
+    Ok(apply_limits(gather_rs_files(root)?, limits))
+}
+
+/// Drop files larger than `max_file_size`, then keep the first `max_files`
+/// in path order.
+pub fn apply_limits(mut files: Vec<PathBuf>, limits: &Limits) -> (Vec<PathBuf>, Vec<Skipped>) {
+    files.sort();
+    let mut skipped = Vec::new();
+
+    if let Some(limit) = limits.max_file_size {
+        files.retain(|file| match std::fs::metadata(file) {
+            Ok(meta) if meta.len() > limit => {
+                skipped.push(Skipped {
+                    input: file.display().to_string(),
+                    reason: SkipReason::TooLarge {
+                        size: meta.len(),
+                        limit,
+                    },
+                });
+                false
+            }
+            _ => true,
+        });
+    }
+
+    if let Some(limit) = limits.max_files.filter(|&limit| files.len() > limit) {
+        skipped.extend(files.drain(limit..).map(|file| Skipped {
+            input: file.display().to_string(),
+            reason: SkipReason::TooManyFiles { limit },
+        }));
+    }
+
+    (files, skipped)
+}
+
+// ============================================================================
+// Generated Code
+// ============================================================================
+
+/// Header markers of generated files (`@generated` also covers prost and
+/// tonic output), matched case-insensitively.
+pub const DEFAULT_GENERATED_MARKERS: &[&str] = &[
+    "@generated",
+    "do not edit",
+    "automatically generated by",
+    "generated by the protocol buffer compiler",
+    "this file is generated by rust-protobuf",
+];
+
+/// Leading lines searched for a marker; generated headers sit at the top.
+const GENERATED_HEADER_LINES: usize = 20;
+
+/// Markers identifying generated files.
+///
+/// Generated files are still parsed and still count as usage sources, so
+/// they keep the code they call alive; only findings inside them are not
+/// reported (see [`ModuleInfo::generated`](crate::parse::ModuleInfo::generated)).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct GeneratedMarkers {
+    /// Substrings that mark a file as generated when found in its header
+    pub markers: Vec<String>,
+}
+
+impl Default for GeneratedMarkers {
+    fn default() -> Self {
+        Self {
+            markers: DEFAULT_GENERATED_MARKERS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl GeneratedMarkers {
+    /// The defaults plus the markers from `extra`.
+    pub fn with_extra(mut self, extra: GeneratedMarkers) -> Self {
+        for marker in extra.markers {
+            if !self.markers.contains(&marker) {
+                self.markers.push(marker);
+            }
+        }
+        self
+    }
+
+    /// The defaults plus the `[generated]` markers of the crate's
+    /// deadmod.toml.
+    pub fn for_crate(root: &Path) -> Self {
+        match load_config(root) {
+            Ok(cfg) => match cfg.and_then(|c| c.generated) {
+                Some(extra) => Self::default().with_extra(extra),
+                None => Self::default(),
+            },
+            Err(e) => {
+                eprintln!("[WARN] config load failed: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// First marker found in the header of `content`: its leading comment
+    /// and attribute lines, at most the first 20 lines.
+    pub fn find(&self, content: &str) -> Option<&str> {
+        let header: String = content
+            .lines()
+            .take(GENERATED_HEADER_LINES)
+            .map(str::trim_start)
+            .take_while(|line| {
+                line.is_empty()
+                    || line.starts_with("//")
+                    || line.starts_with("/*")
+                    || line.starts_with('*')
+                    || line.starts_with("#!")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .to_lowercase();
+        self.markers
+            .iter()
+            .find(|marker| header.contains(&marker.to_lowercase()))
+            .map(String::as_str)
+    }
+
+    /// Whether `content` is a generated file.
+    pub fn matches(&self, content: &str) -> bool {
+        self.find(content).is_some()
+    }
+}
+
 // ============================================================================
 // Filesystem-based Module Discovery
 // ============================================================================
@@ -409,6 +578,59 @@ mod discovery_tests {
         dir
     }
 
+    #[test]
+    fn test_is_scanned_path() {
+        let root = Path::new("/ws");
+        assert!(is_scanned_path(root, Path::new("/ws/src/lib.rs"), &[]));
+        assert!(!is_scanned_path(
+            root,
+            Path::new("/ws/target/debug/build/out.rs"),
+            &[]
+        ));
+        assert!(!is_scanned_path(
+            root,
+            Path::new("/ws/fixtures/a.rs"),
+            &["fixtures"]
+        ));
+        assert!(!is_scanned_path(root, Path::new("/ws/src/notes.md"), &[]));
+        assert!(!is_scanned_path(
+            root,
+            Path::new("/elsewhere/src/lib.rs"),
+            &[]
+        ));
+        // Only directories are matched, like the walk's pruning
+        assert!(is_scanned_path(root, Path::new("/ws/src/target.rs"), &[]));
+    }
+
+    #[test]
+    fn test_gather_rs_files_limited() {
+        let dir = std::env::temp_dir().join(format!("deadmod_scan_limits_{}", std::process::id()));
+        let src = dir.join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("a.rs"), "").unwrap();
+        fs::write(src.join("b.rs"), "").unwrap();
+        fs::write(src.join("c.rs"), "").unwrap();
+        fs::write(src.join("generated.rs"), "x".repeat(2_000)).unwrap();
+
+        let limits = Limits::default()
+            .with_max_file_size(1_000)
+            .with_max_files(2);
+        let (files, skipped) = gather_rs_files_limited(&dir, &limits).unwrap();
+        assert_eq!(files, vec![src.join("a.rs"), src.join("b.rs")]);
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(
+            skipped[0].reason,
+            SkipReason::TooLarge {
+                size: 2_000,
+                limit: 1_000
+            }
+        );
+        assert_eq!(skipped[1].input, src.join("c.rs").display().to_string());
+        assert_eq!(skipped[1].reason, SkipReason::TooManyFiles { limit: 2 });
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_discover_modules() {
         let dir = create_test_project();
@@ -449,4 +671,24 @@ mod discovery_tests {
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn test_generated_markers_match_header_only() {
+        let markers = GeneratedMarkers::default();
+        assert_eq!(
+            markers.find("// @generated by build.rs\n\npub struct Msg;"),
+            Some("@generated")
+        );
+        assert!(
+            markers.matches("#![allow(clippy::all)]\n// This file is @generated by prost-build.\n")
+        );
+        assert!(markers.matches("/*\n * DO NOT EDIT.\n */\nfn f() {}"));
+        // A marker after the first item is ordinary text, not a header
+        assert!(!markers.matches("fn f() {}\n// do not edit this by hand\n"));
+
+        let custom = markers.with_extra(GeneratedMarkers {
+            markers: vec!["Code generated by sqlc".to_string()],
+        });
+        assert!(custom.matches("// code generated by SQLC v1.2\n"));
+    }
 }
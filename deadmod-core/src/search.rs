@@ -0,0 +1,345 @@
+//! Symbol search over an analyzed crate (`deadmod find`).
+//!
+//! [`find_symbol`] matches a name or path against the modules, functions,
+//! methods, constants and statics deadmod already extracts, and reports
+//! each definition with its liveness verdict, plus every line that mentions
+//! the name: grep, with the token classification of [`crate::evidence`]
+//! telling code from comments, strings and macro bodies.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::builder::{AnalysisResult, Attribution, DeadItem, DeadItemKind};
+use crate::common::{module_id, Confidence};
+use crate::constants::{extract_constants, DeadConst};
+use crate::evidence::{mentions_in, Exclusion};
+use crate::func::extract_functions;
+use crate::grace::declaration_line;
+use crate::report::{module_path_of, PathFormatter};
+use crate::source::SourceProvider;
+
+/// What the analysis concluded about a definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Liveness {
+    /// Reachable or referenced
+    Alive,
+    /// Reported as dead code
+    Dead,
+    /// Dead, but kept by `#[deadmod::keep]`
+    Kept,
+    /// Dead, but added within the grace period
+    Recent,
+}
+
+impl fmt::Display for Liveness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Alive => "alive",
+            Self::Dead => "dead",
+            Self::Kept => "kept",
+            Self::Recent => "recent",
+        })
+    }
+}
+
+/// A definition matching the query.
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolDefinition {
+    /// Stable ID, as findings of the item are reported
+    pub id: String,
+    pub kind: DeadItemKind,
+    /// Full path (`crate::net::Client::connect`)
+    pub path: String,
+    pub file: PathBuf,
+    /// Line number (1-indexed, 0 if unknown)
+    pub line: usize,
+    pub liveness: Liveness,
+}
+
+/// How a line mentions the name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MentionRole {
+    /// Code: a call, a path or an import
+    Code,
+    /// A token inside a macro invocation
+    Macro,
+    /// A comment or doc comment
+    Comment,
+    /// A string literal
+    StringLiteral,
+}
+
+impl fmt::Display for MentionRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Code => "code",
+            Self::Macro => "macro",
+            Self::Comment => "comment",
+            Self::StringLiteral => "string",
+        })
+    }
+}
+
+/// A line mentioning the name, outside its definitions.
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolReference {
+    pub file: PathBuf,
+    /// Line number (1-indexed)
+    pub line: usize,
+    /// The line, trimmed
+    pub text: String,
+    pub role: MentionRole,
+}
+
+/// Definitions and references found for one query.
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolSearch {
+    pub query: String,
+    /// Sorted by path, then file and line
+    pub definitions: Vec<SymbolDefinition>,
+    /// Sorted by file and line
+    pub references: Vec<SymbolReference>,
+}
+
+impl SymbolSearch {
+    /// Rewrite every file path in `paths`' style, for reporting.
+    pub fn localize_paths(&mut self, paths: &PathFormatter) {
+        if paths.style().is_none() {
+            return;
+        }
+        let localize = |path: &mut PathBuf| *path = PathBuf::from(paths.format(&*path));
+        self.definitions
+            .iter_mut()
+            .for_each(|d| localize(&mut d.file));
+        self.references
+            .iter_mut()
+            .for_each(|r| localize(&mut r.file));
+    }
+}
+
+/// Search the crate of `result` for `query`: a name (`connect`) or a path
+/// (`net::Client::connect`, `crate::net`), matched against the end of each
+/// definition's full path. Sources are read from `source`.
+pub fn find_symbol(
+    result: &AnalysisResult,
+    query: &str,
+    source: &dyn SourceProvider,
+) -> SymbolSearch {
+    let query = query
+        .trim()
+        .trim_start_matches("crate::")
+        .trim_end_matches('!');
+    let matches = |path: &str| {
+        !query.is_empty()
+            && (path.strip_prefix("crate::") == Some(query)
+                || path.ends_with(&format!("::{}", query)))
+    };
+
+    let dead: HashSet<&str> = result
+        .dead_items()
+        .chain(result.collapsed.iter().flat_map(|m| &m.items))
+        .map(|item| item.id.as_str())
+        .collect();
+    let recent: HashSet<&str> = result.recent.iter().map(|r| r.item.id.as_str()).collect();
+    let kept: HashSet<(&str, &str)> = result
+        .kept
+        .iter()
+        .map(|k| (k.name.as_str(), k.file.as_str()))
+        .collect();
+    let liveness = |item: &DeadItem| {
+        if dead.contains(item.id.as_str()) {
+            Liveness::Dead
+        } else if recent.contains(item.id.as_str()) {
+            Liveness::Recent
+        } else if kept.contains(&(item.name.as_str(), &*item.file.to_string_lossy())) {
+            Liveness::Kept
+        } else {
+            Liveness::Alive
+        }
+    };
+
+    let mut files: Vec<PathBuf> = result
+        .modules
+        .values()
+        .map(|info| info.path.clone())
+        .collect();
+    files.sort();
+    files.dedup();
+
+    let mut definitions = Vec::new();
+    for (name, info) in &result.modules {
+        let path = module_path_of(&result.root, &info.path);
+        if matches(&path) {
+            let liveness = if result.dead_modules.contains(name) {
+                Liveness::Dead
+            } else {
+                Liveness::Alive
+            };
+            definitions.push(SymbolDefinition {
+                id: module_id(name),
+                kind: DeadItemKind::Module,
+                path,
+                file: info.path.clone(),
+                line: 0,
+                liveness,
+            });
+        }
+    }
+
+    let attr = Attribution::new(&result.root);
+    let term = query.rsplit("::").next().unwrap_or(query);
+    let mut references = Vec::new();
+    for file in &files {
+        let Ok(content) = source.read(file) else {
+            continue;
+        };
+        if !content.contains(term) {
+            continue;
+        }
+        let functions = extract_functions(file, &content).into_iter().map(|f| {
+            let tail = f
+                .impl_type
+                .iter()
+                .map(String::as_str)
+                .chain([f.name.as_str()])
+                .collect::<Vec<_>>();
+            (attr.function(&f), tail.join("::"))
+        });
+        let constants = extract_constants(file, &content).into_iter().map(|c| {
+            let constant = DeadConst {
+                name: c.name.clone(),
+                is_static: c.is_static,
+                visibility: c.visibility,
+                file: c.file,
+                line: c.line,
+                module_path: c.module_path,
+                impl_type: c.impl_type,
+                confidence: Confidence::default(),
+            };
+            (attr.constant(&constant), c.name)
+        });
+        for (item, tail) in functions.chain(constants) {
+            let path = format!("{}::{}", item.module_path, tail);
+            if matches(&path) {
+                definitions.push(SymbolDefinition {
+                    line: declaration_line(&content, &item).unwrap_or(0),
+                    liveness: liveness(&item),
+                    id: item.id,
+                    kind: item.kind,
+                    path,
+                    file: item.file,
+                });
+            }
+        }
+
+        references.extend(
+            mentions_in(file, &content, term)
+                .into_iter()
+                .filter_map(|mention| {
+                    let role = match mention.exclusion {
+                        Exclusion::Declaration => return None,
+                        Exclusion::Comment => MentionRole::Comment,
+                        Exclusion::StringLiteral => MentionRole::StringLiteral,
+                        Exclusion::Macro => MentionRole::Macro,
+                        _ => MentionRole::Code,
+                    };
+                    Some(SymbolReference {
+                        file: mention.file,
+                        line: mention.line,
+                        text: mention.text,
+                        role,
+                    })
+                }),
+        );
+    }
+
+    definitions.sort_by(|a, b| (&a.path, &a.file, a.line).cmp(&(&b.path, &b.file, b.line)));
+    SymbolSearch {
+        query: query.to_string(),
+        definitions,
+        references,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Deadmod;
+    use crate::source::MemorySource;
+
+    #[test]
+    fn test_find_symbol() {
+        let sources = [
+            (
+                PathBuf::from("src/lib.rs"),
+                "pub mod net;\n/// See `connect`\npub fn api() { net::connect(); }".to_string(),
+            ),
+            (
+                PathBuf::from("src/net.rs"),
+                "pub fn connect() {}\nfn ping() { log!(connect); }\nconst RETRIES: u8 = 3;"
+                    .to_string(),
+            ),
+            (PathBuf::from("src/old.rs"), "fn connect() {}".to_string()),
+        ];
+        let source = MemorySource::new(sources.clone());
+        let result = Deadmod::from_sources(sources).all().analyze().unwrap();
+
+        let found = find_symbol(&result, "connect", &source);
+        let definitions: Vec<_> = found
+            .definitions
+            .iter()
+            .map(|d| (d.kind, d.path.as_str(), d.line, d.liveness))
+            .collect();
+        assert_eq!(
+            definitions,
+            vec![
+                (
+                    DeadItemKind::Function,
+                    "crate::net::connect",
+                    1,
+                    Liveness::Alive
+                ),
+                (
+                    DeadItemKind::Function,
+                    "crate::old::connect",
+                    1,
+                    Liveness::Alive
+                ),
+            ]
+        );
+        let references: Vec<_> = found.references.iter().map(|r| (r.line, r.role)).collect();
+        assert_eq!(
+            references,
+            vec![
+                (2, MentionRole::Comment),
+                (3, MentionRole::Code),
+                (2, MentionRole::Macro)
+            ]
+        );
+
+        // Paths narrow the match; modules and constants are indexed too
+        let net = find_symbol(&result, "crate::net::connect", &source);
+        assert_eq!(net.definitions.len(), 1);
+        let old = find_symbol(&result, "old", &source);
+        assert_eq!(
+            (old.definitions[0].kind, old.definitions[0].liveness),
+            (DeadItemKind::Module, Liveness::Dead)
+        );
+        assert_eq!(
+            find_symbol(&result, "net::RETRIES", &source).definitions[0].kind,
+            DeadItemKind::Constant
+        );
+        assert_eq!(
+            find_symbol(&result, "ping", &source).definitions[0].liveness,
+            Liveness::Dead
+        );
+        assert!(find_symbol(&result, "nothing", &source)
+            .definitions
+            .is_empty());
+    }
+}
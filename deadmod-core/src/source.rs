@@ -0,0 +1,322 @@
+//! Source providers: where analysis pipelines get their Rust files from.
+//!
+//! The default [`FsSource`] scans a crate directory and parses through the
+//! incremental cache. [`MemorySource`] serves `(path, content)` pairs held in
+//! memory, so tests, REPLs, web services and the WASM build can run a full
+//! analysis without touching the filesystem:
+//!
+//! ```rust,ignore
+//! use deadmod_core::prelude::*;
+//!
+//! let result = Deadmod::from_sources([
+//!     ("src/main.rs".into(), "mod used;\nfn main() {}".to_string()),
+//!     ("src/used.rs".into(), String::new()),
+//!     ("src/dead.rs".into(), String::new()),
+//! ])
+//! .analyze()?;
+//!
+//! assert_eq!(result.dead_modules, vec!["dead"]);
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+
+use crate::cache::{self, ParseMode};
+use crate::collision::key_modules;
+use crate::error::DeadmodError;
+use crate::limits::{Limits, Skipped};
+use crate::parse::{
+    is_target_root_file, module_path_from_file, parse_module_source, ModuleInfo, ParseResult,
+};
+use crate::progress::{Phase, Progress};
+use crate::root::find_root_modules;
+use crate::scan::{gather_rs_files_limited, GeneratedMarkers};
+
+/// A set of Rust source files to analyze.
+///
+/// Implementors only need to list and read files; module parsing and entry
+/// point detection have default implementations built on those two.
+pub trait SourceProvider: fmt::Debug + Send + Sync {
+    /// Crate root that file paths are relative to (may be empty).
+    fn root(&self) -> &Path;
+
+    /// List every `.rs` file, in a stable order.
+    fn files(&self) -> Result<Vec<PathBuf>>;
+
+    /// Read the content of one listed file.
+    fn read(&self, path: &Path) -> Result<String>;
+
+    /// Parse every file into module information (lenient: bad files are
+    /// skipped with a warning).
+    fn parse_modules(&self) -> Result<HashMap<String, ModuleInfo>> {
+        let files = self.files()?;
+        let markers = GeneratedMarkers::default();
+        let modules: Vec<ModuleInfo> = files
+            .par_iter()
+            .filter_map(|file| {
+                let result = match self.read(file) {
+                    Ok(content) => {
+                        let mut result = parse_module_source(file, &content);
+                        if let ParseResult::Ok(_, info) = &mut result {
+                            info.generated = markers.matches(&content);
+                        }
+                        result
+                    }
+                    Err(e) => ParseResult::Skipped(file.clone(), format!("I/O error: {:#}", e)),
+                };
+                match result {
+                    ParseResult::Ok(_, info) => Some(*info),
+                    ParseResult::Skipped(path, reason) => {
+                        eprintln!("WARN: Skipping {}: {}", path.display(), reason);
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Ok(key_modules(modules))
+    }
+
+    /// Cargo entry point modules (`main`, `lib`, binaries).
+    fn root_modules(&self) -> HashSet<String> {
+        self.files()
+            .map(|files| root_modules_from_paths(self.root(), &files))
+            .unwrap_or_default()
+    }
+
+    /// Files left out of [`SourceProvider::files`] by input [`Limits`].
+    fn skipped(&self) -> Vec<Skipped> {
+        Vec::new()
+    }
+}
+
+/// Derive Cargo entry points from a file list, mirroring
+/// [`find_root_modules`] without touching the filesystem.
+///
+/// Top-level `main.rs` / `lib.rs` (no `src/` prefix) are accepted too, which
+/// is convenient for single-file snippets.
+fn root_modules_from_paths(root: &Path, files: &[PathBuf]) -> HashSet<String> {
+    let mut out = HashSet::new();
+
+    for file in files {
+        let rel = file.strip_prefix(root).unwrap_or(file);
+        let parts: Vec<String> = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+        let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
+
+        match parts.as_slice() {
+            ["src", entry @ ("main.rs" | "lib.rs")] | [entry @ ("main.rs" | "lib.rs")] => {
+                out.insert(entry.trim_end_matches(".rs").to_string());
+            }
+            ["src", "bin", name, "main.rs"] => {
+                out.insert(name.to_string());
+            }
+            ["src", "bin", file] if file.ends_with(".rs") => {
+                out.insert(file.trim_end_matches(".rs").to_string());
+            }
+            _ if is_target_root_file(file) => {
+                out.insert(module_path_from_file(file));
+            }
+            _ => {}
+        }
+    }
+
+    out
+}
+
+/// Files scanned from a crate directory, parsed through the incremental cache.
+#[derive(Debug, Clone)]
+pub struct FsSource {
+    root: PathBuf,
+    mode: ParseMode,
+    limits: Limits,
+    progress: Progress,
+    /// Scan result, kept so repeated listings agree with the reported skips
+    listing: OnceLock<(Vec<PathBuf>, Vec<Skipped>)>,
+}
+
+impl FsSource {
+    /// Scan the crate at `root` (cache enabled).
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            mode: ParseMode::Cached,
+            limits: Limits::default(),
+            progress: Progress::none(),
+            listing: OnceLock::new(),
+        }
+    }
+
+    /// Reuse cached parse results for unchanged files.
+    pub fn with_cache(mut self, enabled: bool) -> Self {
+        self.mode = if enabled {
+            ParseMode::Cached
+        } else {
+            ParseMode::Uncached
+        };
+        self
+    }
+
+    /// Choose how the cache is used and files are read (see [`ParseMode`]).
+    pub fn with_parse_mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Leave out files beyond the size and count `limits`.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self.listing = OnceLock::new();
+        self
+    }
+
+    /// Report scanning and parsing to `progress`.
+    pub fn with_progress(mut self, progress: Progress) -> Self {
+        self.progress = progress;
+        self
+    }
+}
+
+impl SourceProvider for FsSource {
+    fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        if let Some((files, _)) = self.listing.get() {
+            return Ok(files.clone());
+        }
+        let listing = self
+            .progress
+            .phase(Phase::Scan, None, || {
+                gather_rs_files_limited(&self.root, &self.limits)
+            })
+            .context("Failed to gather .rs files")?;
+        Ok(self.listing.get_or_init(|| listing).0.clone())
+    }
+
+    fn read(&self, path: &Path) -> Result<String> {
+        fs::read_to_string(path).map_err(|e| DeadmodError::io(path, e).into())
+    }
+
+    fn parse_modules(&self) -> Result<HashMap<String, ModuleInfo>> {
+        let files = self.files()?;
+        cache::parse_crate(&self.root, &files, self.mode, &self.progress)
+            .context("Failed to parse modules")
+    }
+
+    fn root_modules(&self) -> HashSet<String> {
+        find_root_modules(&self.root)
+    }
+
+    fn skipped(&self) -> Vec<Skipped> {
+        self.listing
+            .get()
+            .map(|(_, skipped)| skipped.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// In-memory source files. Never reads or writes the filesystem.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    root: PathBuf,
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl MemorySource {
+    /// Build from `(path, content)` pairs; later duplicates win.
+    pub fn new(sources: impl IntoIterator<Item = (PathBuf, String)>) -> Self {
+        Self {
+            root: PathBuf::new(),
+            files: sources.into_iter().collect(),
+        }
+    }
+
+    /// Set the virtual crate root that paths are relative to.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = root.into();
+        self
+    }
+}
+
+impl SourceProvider for MemorySource {
+    fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        Ok(self
+            .files
+            .keys()
+            .filter(|p| p.extension().is_some_and(|ext| ext == "rs"))
+            .cloned()
+            .collect())
+    }
+
+    fn read(&self, path: &Path) -> Result<String> {
+        self.files.get(path).cloned().ok_or_else(|| {
+            DeadmodError::io(
+                path,
+                std::io::Error::new(std::io::ErrorKind::NotFound, "not in memory source"),
+            )
+            .into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory(files: &[(&str, &str)]) -> MemorySource {
+        MemorySource::new(files.iter().map(|(p, c)| (PathBuf::from(p), c.to_string())))
+    }
+
+    #[test]
+    fn test_memory_source_parse_and_roots() {
+        let source = memory(&[
+            ("src/main.rs", "mod used;\nfn main() {}"),
+            ("src/used.rs", "pub fn helper() {}"),
+            ("src/bin/tool.rs", "fn main() {}"),
+            ("src/bin/server/main.rs", "fn main() {}"),
+            ("README.md", "# not rust"),
+        ]);
+
+        assert_eq!(source.files().unwrap().len(), 4);
+
+        let mods = source.parse_modules().unwrap();
+        assert!(mods["main"].refs.contains("used"));
+        assert!(mods.contains_key("used"));
+
+        let roots = source.root_modules();
+        for name in ["main", "tool", "server"] {
+            assert!(roots.contains(name), "missing root {}", name);
+        }
+        assert!(!roots.contains("used"));
+    }
+
+    #[test]
+    fn test_memory_source_skips_invalid_files() {
+        let source = memory(&[("lib.rs", "mod ok;"), ("broken.rs", "fn (")]);
+        let mods = source.parse_modules().unwrap();
+        assert!(mods.contains_key("lib"));
+        assert!(!mods.contains_key("broken"));
+        assert!(source.root_modules().contains("lib"));
+        assert!(source.read(Path::new("missing.rs")).is_err());
+    }
+
+    #[test]
+    fn test_memory_source_with_root() {
+        let source = memory(&[("/virtual/crate/src/lib.rs", "")]).with_root("/virtual/crate");
+        assert!(source.root_modules().contains("lib"));
+    }
+}
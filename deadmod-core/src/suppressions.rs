@@ -0,0 +1,236 @@
+//! False positives recorded in `.deadmod/suppressions.toml`.
+//!
+//! `// deadmod:ignore <id>` comments suppress a finding from the source; for
+//! findings nobody wants to annotate (generated code, vendored files, a
+//! reviewer's call), `deadmod mark-false-positive <id> --reason "..."`
+//! records the finding here instead, with who marked it and why:
+//!
+//! ```toml
+//! [[suppression]]
+//! id = "3fa9c2d1e07b"
+//! kind = "function"
+//! name = "only_called_via_ffi"
+//! file = "src/ffi.rs"
+//! reason = "called from the C plugin host"
+//! author = "Jane Doe"
+//! added = "2026-01-15"
+//! ```
+//!
+//! Suppressed IDs are dropped from every report, like ignore comments.
+//! Reasons go stale as code changes, so suppressions older than a review
+//! period are listed again ([`Suppressions::due_for_review`]).
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Suppressions file, relative to the crate root.
+pub const SUPPRESSIONS_FILE: &str = ".deadmod/suppressions.toml";
+
+/// A finding marked as a false positive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suppression {
+    /// Finding ID
+    pub id: String,
+    /// Finding kind (`function`, `module`, ...), as reported when marked
+    pub kind: String,
+    /// Item name, as reported when marked
+    pub name: String,
+    /// File of the item, relative to the crate root
+    pub file: String,
+    /// Why the finding is a false positive
+    pub reason: String,
+    /// Who marked it
+    pub author: String,
+    /// Date it was marked (`YYYY-MM-DD`)
+    pub added: String,
+}
+
+impl Suppression {
+    /// A suppression of the finding `id`, marked today.
+    pub fn new(id: &str, kind: &str, name: &str, file: &str, reason: &str, author: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            kind: kind.to_string(),
+            name: name.to_string(),
+            file: file.to_string(),
+            reason: reason.to_string(),
+            author: author.to_string(),
+            added: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+        }
+    }
+
+    /// Days between the date it was marked and `today`, if `added` is a date.
+    pub fn age_days(&self, today: NaiveDate) -> Option<i64> {
+        let added = NaiveDate::parse_from_str(&self.added, "%Y-%m-%d").ok()?;
+        Some((today - added).num_days())
+    }
+}
+
+/// The suppressions of a crate.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suppressions {
+    #[serde(default, rename = "suppression")]
+    pub entries: Vec<Suppression>,
+}
+
+impl Suppressions {
+    /// Path of the suppressions file of the crate at `crate_root`.
+    pub fn path(crate_root: &Path) -> PathBuf {
+        crate_root.join(SUPPRESSIONS_FILE)
+    }
+
+    /// Suppressions of the crate at `crate_root`; none if the file is missing.
+    pub fn load(crate_root: &Path) -> Result<Self> {
+        let path = Self::path(crate_root);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read suppressions from {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Invalid suppressions file {}", path.display()))
+    }
+
+    /// Write the suppressions of the crate at `crate_root`.
+    pub fn save(&self, crate_root: &Path) -> Result<()> {
+        let path = Self::path(crate_root);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let content = toml::to_string(self).context("Failed to serialize suppressions")?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write suppressions to {}", path.display()))
+    }
+
+    /// Record `suppression`, replacing an earlier one of the same ID.
+    /// Returns whether one was replaced.
+    pub fn add(&mut self, suppression: Suppression) -> bool {
+        match self.entries.iter_mut().find(|s| s.id == suppression.id) {
+            Some(existing) => {
+                *existing = suppression;
+                true
+            }
+            None => {
+                self.entries.push(suppression);
+                false
+            }
+        }
+    }
+
+    /// The suppression of finding `id`.
+    pub fn get(&self, id: &str) -> Option<&Suppression> {
+        self.entries.iter().find(|s| s.id == id)
+    }
+
+    /// Suppressed finding IDs.
+    pub fn ids(&self) -> HashSet<String> {
+        self.entries.iter().map(|s| s.id.clone()).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Suppressions marked more than `days` days ago, oldest first.
+    pub fn due_now(&self, days: u32) -> Vec<&Suppression> {
+        self.due_for_review(days, chrono::Utc::now().date_naive())
+    }
+
+    /// Suppressions marked more than `days` days before `today`, oldest
+    /// first. Entries without a readable date are always due.
+    pub fn due_for_review(&self, days: u32, today: NaiveDate) -> Vec<&Suppression> {
+        let mut due: Vec<&Suppression> = self
+            .entries
+            .iter()
+            .filter(|s| s.age_days(today).is_none_or(|age| age > i64::from(days)))
+            .collect();
+        due.sort_by(|a, b| a.added.cmp(&b.added).then_with(|| a.id.cmp(&b.id)));
+        due
+    }
+}
+
+/// Name to record as a suppression's author: `git config user.name` in
+/// `crate_root`, else the `USER`/`USERNAME` environment variable.
+pub fn current_author(crate_root: &Path) -> String {
+    Command::new("git")
+        .args(["config", "user.name"])
+        .current_dir(crate_root)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suppression(id: &str, added: &str) -> Suppression {
+        Suppression {
+            id: id.to_string(),
+            kind: "function".to_string(),
+            name: format!("f_{}", id),
+            file: "src/lib.rs".to_string(),
+            reason: "called from C".to_string(),
+            author: "Jane Doe".to_string(),
+            added: added.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_suppressions_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("deadmod_suppressions_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(Suppressions::load(&dir).unwrap().is_empty());
+
+        let mut suppressions = Suppressions::default();
+        assert!(!suppressions.add(suppression("aaaaaaaaaaaa", "2026-01-01")));
+        assert!(!suppressions.add(suppression("bbbbbbbbbbbb", "2026-03-01")));
+        let mut updated = suppression("aaaaaaaaaaaa", "2026-02-01");
+        updated.reason = "reflection".to_string();
+        assert!(suppressions.add(updated));
+        suppressions.save(&dir).unwrap();
+
+        let loaded = Suppressions::load(&dir).unwrap();
+        assert_eq!(loaded, suppressions);
+        assert_eq!(loaded.entries.len(), 2);
+        assert_eq!(loaded.get("aaaaaaaaaaaa").unwrap().reason, "reflection");
+        assert!(loaded.ids().contains("bbbbbbbbbbbb"));
+        let content = std::fs::read_to_string(Suppressions::path(&dir)).unwrap();
+        assert!(content.contains("[[suppression]]"));
+
+        std::fs::write(Suppressions::path(&dir), "suppression = 3").unwrap();
+        assert!(Suppressions::load(&dir).is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_due_for_review() {
+        let suppressions = Suppressions {
+            entries: vec![
+                suppression("bbbbbbbbbbbb", "2026-03-01"),
+                suppression("aaaaaaaaaaaa", "2026-01-01"),
+                suppression("cccccccccccc", "someday"),
+            ],
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 4, 1).unwrap();
+        let due: Vec<&str> = suppressions
+            .due_for_review(60, today)
+            .iter()
+            .map(|s| s.id.as_str())
+            .collect();
+        assert_eq!(due, ["aaaaaaaaaaaa", "cccccccccccc"]);
+        assert_eq!(suppressions.entries[0].age_days(today), Some(31));
+        assert_eq!(suppressions.due_for_review(0, today).len(), 3);
+    }
+}
@@ -0,0 +1,252 @@
+//! Synthetic projects for benchmarks and the throughput self-test.
+//!
+//! [`SyntheticSpec`] describes a binary crate of a given size; [`sources`]
+//! renders it in memory and [`generate`] writes it to disk. Every module
+//! mixes the constructs the detectors look at (functions calling each
+//! other, a trait impl, an enum matched on, constants, a macro, a generic
+//! function), and a known share of them is planted dead, so a run can be
+//! checked as well as timed:
+//! - `dead_fn_*` functions nobody calls
+//! - one `UNUSED_*` constant per module
+//! - one never-constructed `Kind*::Unused` variant per module
+//! - `src/orphan.rs`, a file no `mod` declaration reaches
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+/// Shape of a synthetic project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyntheticSpec {
+    /// Total number of free functions, dead ones included
+    pub functions: usize,
+    /// Functions per module file
+    pub functions_per_module: usize,
+    /// One function in this many is dead (0: none)
+    pub dead_every: usize,
+}
+
+impl SyntheticSpec {
+    /// A project of `functions` functions, 20 per module, one in ten dead.
+    pub fn new(functions: usize) -> Self {
+        Self {
+            functions,
+            functions_per_module: 20,
+            dead_every: 10,
+        }
+    }
+
+    /// Number of module files besides `main.rs` and the orphan.
+    pub fn module_count(&self) -> usize {
+        self.functions
+            .div_ceil(self.functions_per_module.max(1))
+            .max(1)
+    }
+}
+
+/// What [`sources`] planted in a project.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SyntheticProject {
+    /// `.rs` files, orphan included
+    pub files: usize,
+    /// Lines of Rust
+    pub lines: usize,
+    /// Free functions (`entry` and `main` excluded)
+    pub functions: usize,
+    /// Planted dead functions
+    pub dead_functions: usize,
+    /// Planted dead constants
+    pub dead_constants: usize,
+    /// Planted dead enum variants
+    pub dead_variants: usize,
+    /// Planted dead modules
+    pub dead_modules: usize,
+}
+
+/// Render the project of `spec` as `(path, content)` pairs relative to the
+/// crate root, `Cargo.toml` first.
+pub fn sources(spec: &SyntheticSpec) -> (Vec<(PathBuf, String)>, SyntheticProject) {
+    let modules = spec.module_count();
+    let per_module = spec.functions_per_module.max(1);
+    let mut files = Vec::with_capacity(modules + 3);
+    let mut project = SyntheticProject::default();
+
+    files.push((
+        PathBuf::from("Cargo.toml"),
+        "[package]\nname = \"synthetic\"\nversion = \"0.1.0\"\nedition = \"2021\"\n".to_string(),
+    ));
+
+    let mut main = String::new();
+    for m in 0..modules {
+        let _ = writeln!(main, "mod m{};", m);
+    }
+    main.push_str("\nfn main() {\n    let mut total = 0;\n");
+    for m in 0..modules {
+        let _ = writeln!(main, "    total += m{}::entry();", m);
+    }
+    main.push_str("    println!(\"{}\", total);\n}\n");
+    files.push((PathBuf::from("src/main.rs"), main));
+
+    let mut remaining = spec.functions;
+    for m in 0..modules {
+        let count = remaining.min(per_module);
+        remaining -= count;
+        let (content, dead) = module_source(m, count, spec.dead_every);
+        project.functions += count;
+        project.dead_functions += dead;
+        project.dead_constants += 1;
+        project.dead_variants += 1;
+        files.push((PathBuf::from(format!("src/m{}.rs", m)), content));
+    }
+
+    files.push((
+        PathBuf::from("src/orphan.rs"),
+        "pub fn orphaned() -> usize {\n    0\n}\n".to_string(),
+    ));
+    project.dead_modules = 1;
+
+    project.files = files.len() - 1;
+    project.lines = files
+        .iter()
+        .filter(|(path, _)| path.extension().is_some_and(|ext| ext == "rs"))
+        .map(|(_, content)| content.lines().count())
+        .sum();
+    (files, project)
+}
+
+/// Source of module `m` with `count` functions, and how many are dead.
+fn module_source(m: usize, count: usize, dead_every: usize) -> (String, usize) {
+    let is_dead = |i: usize| dead_every > 0 && i % dead_every == dead_every - 1;
+    let live: Vec<usize> = (0..count).filter(|&i| !is_dead(i)).collect();
+    let mut s = String::with_capacity(count * 160 + 1024);
+
+    let _ = write!(
+        s,
+        "//! Synthetic module {m}.
+
+const LIMIT_{m}: usize = {m};
+const UNUSED_{m}: usize = 0;
+
+enum Kind{m} {{
+    Small,
+    Large,
+    Unused,
+}}
+
+pub struct Item{m} {{
+    pub value: usize,
+}}
+
+pub trait Visit{m} {{
+    fn visit(&self) -> usize;
+}}
+
+impl Visit{m} for Item{m} {{
+    fn visit(&self) -> usize {{
+        self.value
+    }}
+}}
+
+macro_rules! twice_{m} {{
+    ($e:expr) => {{
+        $e * 2
+    }};
+}}
+
+fn classify(x: usize) -> Kind{m} {{
+    if x > LIMIT_{m} {{ Kind{m}::Large }} else {{ Kind{m}::Small }}
+}}
+
+fn pick<T: Copy>(a: T, _b: T) -> T {{
+    a
+}}
+
+pub fn entry() -> usize {{
+    let item = Item{m} {{ value: pick(1, 2) }};
+    item.visit() + {first}
+}}
+",
+        first = match live.first() {
+            Some(i) => format!("f{}_{}(LIMIT_{})", m, i, m),
+            None => "0".to_string(),
+        },
+    );
+
+    for (n, &i) in live.iter().enumerate() {
+        let next = match live.get(n + 1) {
+            Some(j) => format!("f{}_{}(x + 1)", m, j),
+            None => "x".to_string(),
+        };
+        let _ = write!(
+            s,
+            "
+fn f{m}_{i}(x: usize) -> usize {{
+    match classify(x) {{
+        Kind{m}::Small => twice_{m}!({next}),
+        Kind{m}::Large => {next},
+        _ => 0,
+    }}
+}}
+"
+        );
+    }
+
+    let dead = count - live.len();
+    for i in (0..count).filter(|&i| is_dead(i)) {
+        let _ = write!(
+            s,
+            "\nfn dead_fn_{m}_{i}(x: usize) -> usize {{\n    x + {i}\n}}\n"
+        );
+    }
+    (s, dead)
+}
+
+/// Write the project of `spec` under `root`.
+pub fn generate(root: &Path, spec: &SyntheticSpec) -> Result<SyntheticProject> {
+    let (files, project) = sources(spec);
+    fs::create_dir_all(root.join("src"))
+        .with_context(|| format!("Failed to create {}", root.display()))?;
+    for (path, content) in files {
+        let path = root.join(path);
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(project)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Deadmod;
+
+    #[test]
+    fn test_planted_dead_code_is_found() {
+        let spec = SyntheticSpec {
+            functions: 45,
+            functions_per_module: 20,
+            dead_every: 10,
+        };
+        let (files, project) = sources(&spec);
+        assert_eq!(project.files, 5);
+        assert_eq!(project.functions, 45);
+        assert_eq!(project.dead_functions, 4);
+
+        let sources = files
+            .into_iter()
+            .filter(|(path, _)| path.starts_with("src"));
+        let result = Deadmod::from_sources(sources).all().analyze().unwrap();
+
+        assert_eq!(result.dead_modules, ["orphan"]);
+        let dead_fns: Vec<&str> = result
+            .dead_functions
+            .iter()
+            .map(|f| f.name.as_str())
+            .filter(|name| name.starts_with("dead_fn_"))
+            .collect();
+        assert_eq!(dead_fns.len(), project.dead_functions);
+        assert_eq!(result.dead_constants.len(), project.dead_constants);
+        assert_eq!(result.dead_enums.len(), project.dead_variants);
+    }
+}
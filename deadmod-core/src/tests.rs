@@ -55,7 +55,7 @@ fn test_simple_dead_module() {
     for r in &roots {
         reachable.extend(reachable_from_root(&g, r.as_str()));
     }
-    let mut dead: Vec<_> = find_dead(&mods, &reachable);
+    let mut dead: Vec<_> = dead_names(&find_dead(&mods, &reachable));
     dead.sort();
 
     assert_eq!(dead, vec!["a"], "module a should be dead; b is declared");
@@ -107,7 +107,7 @@ fn test_use_path_imports() {
     for r in &roots {
         reachable.extend(reachable_from_root(&g, r.as_str()));
     }
-    let dead = find_dead(&mods, &reachable);
+    let dead = dead_names(&find_dead(&mods, &reachable));
 
     // The separate src/a.rs is dead because it's not referenced
     // The nested::inner::a is a different module path
@@ -190,7 +190,7 @@ fn test_group_renames() {
     for r in &roots {
         reachable.extend(reachable_from_root(&g, r.as_str()));
     }
-    let dead = find_dead(&mods, &reachable);
+    let dead = dead_names(&find_dead(&mods, &reachable));
 
     // All modules should be reachable: main -> foo -> a, b
     assert!(!dead.contains(&"foo"));
@@ -235,7 +235,7 @@ fn test_lib_root_detection() {
     for r in &roots {
         reachable.extend(reachable_from_root(&g, r.as_str()));
     }
-    let mut dead: Vec<_> = find_dead(&mods, &reachable);
+    let mut dead: Vec<_> = dead_names(&find_dead(&mods, &reachable));
     dead.sort();
 
     assert_eq!(dead, vec!["dead"], "Module 'dead' should be unreachable");
@@ -259,7 +259,7 @@ fn test_deeply_nested_dead_module() {
     for r in &roots {
         reachable.extend(reachable_from_root(&g, r.as_str()));
     }
-    let mut dead: Vec<_> = find_dead(&mods, &reachable);
+    let mut dead: Vec<_> = dead_names(&find_dead(&mods, &reachable));
     dead.sort();
 
     assert!(mods.contains_key("dead_c"));
@@ -301,10 +301,18 @@ fn test_config_not_found() {
 // Test 9: Logging Module
 #[test]
 fn test_logging_does_not_panic() {
-    log_info("test info");
-    log_warn("test warn");
-    log_error("test error");
-    log_event("CUSTOM", "custom detail");
+    // A second install keeps the first subscriber instead of panicking
+    init_logging(LogFormat::Json);
+    init_logging(LogFormat::Pretty);
+    tracing::info_span!("scan").in_scope(|| tracing::debug!(file = "src/lib.rs", "test event"));
+}
+
+#[test]
+fn test_log_format_names() {
+    assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+    assert_eq!(" Pretty ".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+    assert!("xml".parse::<LogFormat>().is_err());
+    assert_eq!(LogFormat::Pretty.to_string(), "pretty");
 }
 
 // ============================================================================
@@ -0,0 +1,285 @@
+//! Color themes of the graph visualizations (`--theme`).
+//!
+//! A [`Theme`] names a [`Palette`]: the colors of the HTML and PixiJS pages
+//! (page chrome, nodes, edges, clusters) and of DOT node fills. The default
+//! dark theme encodes reachability as green and red, which deuteranopes
+//! cannot tell apart; `colorblind-safe` uses the Okabe–Ito blue and orange
+//! instead, and `high-contrast` pairs pure colors on black.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// A named color palette.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// Light text on navy, green and red nodes
+    #[default]
+    Dark,
+    /// Dark text on white
+    Light,
+    /// Pure colors on black, white outlines
+    HighContrast,
+    /// Dark, with blue and orange nodes (Okabe–Ito palette)
+    ColorblindSafe,
+}
+
+impl Theme {
+    /// Every theme, in the order they are listed in help output.
+    pub const ALL: [Theme; 4] = [
+        Theme::Dark,
+        Theme::Light,
+        Theme::HighContrast,
+        Theme::ColorblindSafe,
+    ];
+
+    /// Name used in flags.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::HighContrast => "high-contrast",
+            Self::ColorblindSafe => "colorblind-safe",
+        }
+    }
+
+    /// The theme's colors.
+    pub fn palette(&self) -> &'static Palette {
+        match self {
+            Self::Dark => &DARK,
+            Self::Light => &LIGHT,
+            Self::HighContrast => &HIGH_CONTRAST,
+            Self::ColorblindSafe => &COLORBLIND_SAFE,
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "unknown theme '{}' (expected dark, light, high-contrast or colorblind-safe)",
+                    s
+                )
+            })
+    }
+}
+
+/// Colors of one theme, as `#rrggbb`.
+///
+/// Serialized with camelCase keys for the viewers' scripts; their
+/// stylesheets read the same colors as CSS variables
+/// ([`Palette::css_variables`]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Palette {
+    /// Page background
+    pub background: &'static str,
+    /// Behind the WebGL canvas
+    pub backdrop: &'static str,
+    /// Header, panels and tooltips
+    pub surface: &'static str,
+    /// Panel borders and hovered buttons
+    pub border: &'static str,
+    /// Headings and active controls
+    pub accent: &'static str,
+    pub text: &'static str,
+    /// Labels on colored boxes, the selected node's outline
+    pub text_strong: &'static str,
+    /// Secondary text
+    pub muted: &'static str,
+    /// Placeholders and fallback cluster color
+    pub faint: &'static str,
+    /// Reachable nodes
+    pub alive: &'static str,
+    pub alive_stroke: &'static str,
+    /// Dead nodes
+    pub dead: &'static str,
+    pub dead_stroke: &'static str,
+    /// Inline `#[cfg(test)]` modules (DOT)
+    pub test: &'static str,
+    /// Highlighted nodes and edges
+    pub highlight: &'static str,
+    /// Workspace crate groups and cross-crate edges
+    pub crate_group: &'static str,
+    pub edge: &'static str,
+    /// Labels drawn on nodes
+    pub node_text: &'static str,
+    /// Command boxes in the inspector
+    pub code_background: &'static str,
+    pub code_surface: &'static str,
+    pub code_border: &'static str,
+    pub code_text: &'static str,
+    /// Colors assigned to clusters in turn
+    pub clusters: [&'static str; 10],
+}
+
+impl Palette {
+    /// The palette as CSS custom properties (`--dead: #F08080;`), one per
+    /// line, for a `:root` rule.
+    pub fn css_variables(&self) -> String {
+        [
+            ("background", self.background),
+            ("backdrop", self.backdrop),
+            ("surface", self.surface),
+            ("border", self.border),
+            ("accent", self.accent),
+            ("text", self.text),
+            ("text-strong", self.text_strong),
+            ("muted", self.muted),
+            ("faint", self.faint),
+            ("alive", self.alive),
+            ("dead", self.dead),
+            ("crate-group", self.crate_group),
+            ("code-background", self.code_background),
+            ("code-surface", self.code_surface),
+            ("code-border", self.code_border),
+            ("code-text", self.code_text),
+        ]
+        .iter()
+        .map(|(name, color)| format!("--{}: {};", name, color))
+        .collect::<Vec<_>>()
+        .join("\n            ")
+    }
+}
+
+const DARK: Palette = Palette {
+    background: "#1a1a2e",
+    backdrop: "#0d0d1a",
+    surface: "#16213e",
+    border: "#0f3460",
+    accent: "#e94560",
+    text: "#eeeeee",
+    text_strong: "#ffffff",
+    muted: "#aaaaaa",
+    faint: "#666666",
+    alive: "#90EE90",
+    alive_stroke: "#44aa44",
+    dead: "#F08080",
+    dead_stroke: "#cc4444",
+    test: "#ADD8E6",
+    highlight: "#f7be16",
+    crate_group: "#9b59b6",
+    edge: "#646464",
+    node_text: "#1a1a2e",
+    code_background: "#0d1117",
+    code_surface: "#21262d",
+    code_border: "#30363d",
+    code_text: "#c9d1d9",
+    clusters: [
+        "#e94560", "#0f3460", "#533483", "#16c79a", "#f7be16", "#ff6b6b", "#4ecdc4", "#45b7d1",
+        "#96c93d", "#dfe6e9",
+    ],
+};
+
+const LIGHT: Palette = Palette {
+    background: "#f6f8fa",
+    backdrop: "#ffffff",
+    surface: "#ffffff",
+    border: "#d0d7de",
+    accent: "#0969da",
+    text: "#1f2328",
+    text_strong: "#000000",
+    muted: "#57606a",
+    faint: "#8c959f",
+    alive: "#4ac26b",
+    alive_stroke: "#1a7f37",
+    dead: "#ff8182",
+    dead_stroke: "#cf222e",
+    test: "#80ccff",
+    highlight: "#d4a72c",
+    crate_group: "#a475f9",
+    edge: "#8c959f",
+    node_text: "#1f2328",
+    code_background: "#f6f8fa",
+    code_surface: "#eaeef2",
+    code_border: "#d0d7de",
+    code_text: "#1f2328",
+    clusters: [
+        "#0969da", "#bf3989", "#1a7f37", "#bc4c00", "#8250df", "#cf222e", "#1b7c83", "#9a6700",
+        "#57606a", "#4d2d00",
+    ],
+};
+
+const HIGH_CONTRAST: Palette = Palette {
+    background: "#000000",
+    backdrop: "#000000",
+    surface: "#000000",
+    border: "#ffffff",
+    accent: "#ffff00",
+    text: "#ffffff",
+    text_strong: "#ffffff",
+    muted: "#e0e0e0",
+    faint: "#bdbdbd",
+    alive: "#00bfff",
+    alive_stroke: "#ffffff",
+    dead: "#ffff00",
+    dead_stroke: "#ffffff",
+    test: "#c0c0c0",
+    highlight: "#00ff00",
+    crate_group: "#ff00ff",
+    edge: "#ffffff",
+    node_text: "#000000",
+    code_background: "#000000",
+    code_surface: "#000000",
+    code_border: "#ffffff",
+    code_text: "#ffffff",
+    clusters: [
+        "#ffffff", "#ffff00", "#00ffff", "#ff00ff", "#00ff00", "#ff8000", "#80c0ff", "#ff80c0",
+        "#c0ff80", "#c0c0c0",
+    ],
+};
+
+const COLORBLIND_SAFE: Palette = Palette {
+    alive: "#56B4E9",
+    alive_stroke: "#0072B2",
+    dead: "#E69F00",
+    dead_stroke: "#D55E00",
+    test: "#CC79A7",
+    highlight: "#F0E442",
+    crate_group: "#CC79A7",
+    clusters: [
+        "#E69F00", "#56B4E9", "#009E73", "#F0E442", "#0072B2", "#D55E00", "#CC79A7", "#999999",
+        "#DDDDDD", "#882255",
+    ],
+    ..DARK
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_names() {
+        for theme in Theme::ALL {
+            assert_eq!(theme.as_str().parse::<Theme>(), Ok(theme));
+        }
+        assert_eq!(" High-Contrast ".parse::<Theme>(), Ok(Theme::HighContrast));
+        assert!("solarized".parse::<Theme>().is_err());
+        assert_eq!(Theme::default().palette().dead, "#F08080");
+
+        let palette = Theme::ColorblindSafe.palette();
+        assert_eq!(
+            (palette.alive, palette.background),
+            ("#56B4E9", DARK.background)
+        );
+        assert!(palette.css_variables().contains("--dead: #E69F00;"));
+        assert_eq!(
+            serde_json::to_value(palette).unwrap()["deadStroke"],
+            "#D55E00"
+        );
+    }
+}
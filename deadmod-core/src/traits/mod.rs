@@ -3,6 +3,7 @@
 //! This module provides functionality to detect dead methods:
 //! - Trait definitions with required vs provided methods
 //! - Trait impl blocks (`impl Trait for Type`)
+//! - The trait hierarchy: supertraits and blanket impls
 //! - Inherent impl blocks (`impl Type { fn method() {} }`)
 //! - Method call detection for all methods
 //! - Dead method detection via reachability analysis
@@ -58,9 +59,10 @@ pub mod trait_usage;
 
 // Re-exports for convenience
 pub use trait_extractor::{
-    extract_traits, InherentImplMethod, TraitExtractionResult, TraitImplMethod, TraitMethodDef,
+    extract_traits, BoundKind, InherentImplMethod, TraitBound, TraitExtractionResult,
+    TraitImplMethod, TraitMethodDef,
 };
-pub use trait_graph::{TraitAnalysisResult, TraitGraph, TraitStats};
+pub use trait_graph::{TraitAnalysisResult, TraitGraph, TraitHierarchy, TraitStats};
 pub use trait_usage::{
     extract_called_method_names, extract_trait_usages, TraitMethodUsage, UsageKind,
 };
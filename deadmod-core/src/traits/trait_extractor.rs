@@ -3,18 +3,22 @@
 //! Extracts:
 //! - Trait definitions with their methods (required vs provided)
 //! - Trait impl blocks (`impl Trait for Type`)
+//! - Supertraits (`trait Sub: Super`) and blanket impls
+//!   (`impl<T: Bound> Trait for T`)
 //! - Method visibility and signatures
 //!
 //! NASA-grade resilience: handles malformed AST gracefully.
 
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use syn::punctuated::Punctuated;
 use syn::{
-    visit::Visit, File, ImplItem, ImplItemFn, Item, ItemImpl, ItemMod, ItemTrait, TraitItem,
-    TraitItemFn, Visibility,
+    visit::Visit, File, GenericParam, Generics, ImplItem, ImplItemFn, Item, ItemImpl, ItemMod,
+    ItemTrait, Signature, TraitBoundModifier, TraitItem, TraitItemFn, Type, TypeParamBound,
+    Visibility, WherePredicate,
 };
 
-use crate::common::visibility_str;
+use crate::common::{has_cfg_attr, keep_annotation, visibility_str, Confidence, ImplSpan, Keep};
 
 /// Information about a method defined in a trait.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +35,18 @@ pub struct TraitMethodDef {
     pub is_required: bool,
     /// Source file path
     pub file: String,
+    /// 1-based line of the method's name
+    #[serde(default)]
+    pub line: usize,
+    /// Whether the method or its enclosing item has #[cfg] attributes
+    #[serde(default)]
+    pub has_cfg: bool,
+    /// `#[deadmod::keep]` on the method or its enclosing item
+    #[serde(default)]
+    pub keep: Option<Keep>,
+    /// Confidence that the method is dead (set by [`super::TraitGraph::analyze`])
+    #[serde(default)]
+    pub confidence: Confidence,
 }
 
 /// Information about a method implemented for a trait.
@@ -46,6 +62,21 @@ pub struct TraitImplMethod {
     pub full_id: String,
     /// Source file path
     pub file: String,
+    /// 1-based line of the method's name
+    #[serde(default)]
+    pub line: usize,
+    /// The `impl` block the method is defined in
+    #[serde(default)]
+    pub impl_span: Option<ImplSpan>,
+    /// Whether the method or its enclosing item has #[cfg] attributes
+    #[serde(default)]
+    pub has_cfg: bool,
+    /// `#[deadmod::keep]` on the method or its enclosing item
+    #[serde(default)]
+    pub keep: Option<Keep>,
+    /// Confidence that the method is dead (set by [`super::TraitGraph::analyze`])
+    #[serde(default)]
+    pub confidence: Confidence,
 }
 
 /// Information about an inherent impl method (impl Type { fn method() {} }).
@@ -65,6 +96,40 @@ pub struct InherentImplMethod {
     pub file: String,
     /// Module path
     pub module_path: String,
+    /// The `impl` block the method is defined in
+    #[serde(default)]
+    pub impl_span: Option<ImplSpan>,
+    /// Whether the method or its enclosing item has #[cfg] attributes
+    #[serde(default)]
+    pub has_cfg: bool,
+    /// `#[deadmod::keep]` on the method or its enclosing item
+    #[serde(default)]
+    pub keep: Option<Keep>,
+    /// Confidence that the method is dead (set by [`super::TraitGraph::analyze`])
+    #[serde(default)]
+    pub confidence: Confidence,
+}
+
+/// How a trait comes to require another trait of its implementors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoundKind {
+    /// `trait Sub: Super` or `trait Sub where Self: Super`
+    Supertrait,
+    /// `impl<T: Bound> Trait for T`: every `Bound` type implements `Trait`
+    BlanketImpl,
+}
+
+/// An edge of the trait hierarchy: implementors of `trait_name` are
+/// `bound` implementors too.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraitBound {
+    /// The subtrait, or the trait a blanket impl implements
+    pub trait_name: String,
+    /// The supertrait, or the bound of the blanket impl's type parameter
+    pub bound: String,
+    pub kind: BoundKind,
+    /// Source file path
+    pub file: String,
 }
 
 /// Result of trait extraction from a file.
@@ -76,6 +141,10 @@ pub struct TraitExtractionResult {
     pub impl_methods: Vec<TraitImplMethod>,
     /// All inherent impl methods found (impl Type { fn method() {} })
     pub inherent_methods: Vec<InherentImplMethod>,
+    /// Names of the traits defined, methods or not
+    pub trait_names: Vec<String>,
+    /// Supertrait and blanket impl edges
+    pub bounds: Vec<TraitBound>,
 }
 
 /// AST visitor that extracts trait definitions and implementations.
@@ -103,40 +172,65 @@ impl TraitExtractor {
     fn record_trait_method(
         &mut self,
         trait_name: &str,
-        method_name: &str,
+        sig: &Signature,
         vis: &Visibility,
         is_required: bool,
+        has_cfg: bool,
+        keep: Option<Keep>,
     ) {
-        let full_path = self.build_path(&[trait_name, method_name]);
+        let method_name = sig.ident.to_string();
+        let full_path = self.build_path(&[trait_name, &method_name]);
 
         self.result.trait_methods.push(TraitMethodDef {
             trait_name: trait_name.to_string(),
-            method_name: method_name.to_string(),
+            method_name,
             full_path,
             visibility: visibility_str(vis).to_string(),
             is_required,
             file: self.file_path.clone(),
+            line: sig.ident.span().start().line,
+            has_cfg,
+            keep,
+            confidence: Confidence::default(),
         });
     }
 
-    fn record_impl_method(&mut self, trait_name: &str, type_name: &str, method_name: &str) {
+    fn record_impl_method(
+        &mut self,
+        trait_name: &str,
+        type_name: &str,
+        sig: &Signature,
+        impl_span: &ImplSpan,
+        has_cfg: bool,
+        keep: Option<Keep>,
+    ) {
+        let method_name = sig.ident.to_string();
         let full_id = format!("impl {} for {} :: {}", trait_name, type_name, method_name);
 
         self.result.impl_methods.push(TraitImplMethod {
             trait_name: trait_name.to_string(),
             type_name: type_name.to_string(),
-            method_name: method_name.to_string(),
+            method_name,
             full_id,
             file: self.file_path.clone(),
+            line: sig.ident.span().start().line,
+            impl_span: Some(impl_span.clone()),
+            has_cfg,
+            keep,
+            confidence: Confidence::default(),
         });
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn record_inherent_method(
         &mut self,
         type_name: &str,
         method_name: &str,
         vis: &Visibility,
         is_static: bool,
+        impl_span: &ImplSpan,
+        has_cfg: bool,
+        keep: Option<Keep>,
     ) {
         let full_id = format!("{}::{}", type_name, method_name);
 
@@ -148,9 +242,23 @@ impl TraitExtractor {
             is_static,
             file: self.file_path.clone(),
             module_path: self.build_path(&[]),
+            impl_span: Some(impl_span.clone()),
+            has_cfg,
+            keep,
+            confidence: Confidence::default(),
         });
     }
 
+    fn record_bounds(&mut self, trait_name: &str, bounds: Vec<String>, kind: BoundKind) {
+        for bound in bounds {
+            self.result.bounds.push(TraitBound {
+                trait_name: trait_name.to_string(),
+                bound,
+                kind,
+                file: self.file_path.clone(),
+            });
+        }
+    }
 }
 
 impl<'ast> Visit<'ast> for TraitExtractor {
@@ -174,26 +282,47 @@ impl<'ast> Visit<'ast> for TraitExtractor {
                 ident,
                 items,
                 vis,
+                attrs: trait_attrs,
+                supertraits,
+                generics,
                 ..
             }) => {
                 let trait_name = ident.to_string();
+                let trait_cfg = has_cfg_attr(trait_attrs);
+                let trait_keep = keep_annotation(trait_attrs);
+
+                self.result.trait_names.push(trait_name.clone());
+                let mut bounds = trait_bound_names(supertraits);
+                bounds.extend(where_bounds(generics, "Self"));
+                self.record_bounds(&trait_name, bounds, BoundKind::Supertrait);
 
                 for trait_item in items {
-                    if let TraitItem::Fn(TraitItemFn { sig, default, .. }) = trait_item {
-                        let method_name = sig.ident.to_string();
+                    if let TraitItem::Fn(TraitItemFn {
+                        sig,
+                        default,
+                        attrs,
+                        ..
+                    }) = trait_item
+                    {
                         let is_required = default.is_none();
-                        self.record_trait_method(&trait_name, &method_name, vis, is_required);
+                        let has_cfg = trait_cfg || has_cfg_attr(attrs);
+                        let keep = keep_annotation(attrs).or_else(|| trait_keep.clone());
+                        self.record_trait_method(&trait_name, sig, vis, is_required, has_cfg, keep);
                     }
                 }
             }
 
             // Trait implementations: impl Trait for Type { ... }
-            Item::Impl(ItemImpl {
-                trait_: Some((_, trait_path, _)),
-                self_ty,
-                items,
-                ..
-            }) => {
+            Item::Impl(
+                item_impl @ ItemImpl {
+                    trait_: Some((_, trait_path, _)),
+                    self_ty,
+                    items,
+                    attrs: impl_attrs,
+                    generics,
+                    ..
+                },
+            ) => {
                 // Extract trait name from path
                 let trait_name = trait_path
                     .segments
@@ -201,35 +330,70 @@ impl<'ast> Visit<'ast> for TraitExtractor {
                     .map(|s| s.ident.to_string())
                     .unwrap_or_else(|| "<unknown>".to_string());
 
+                if let Some(bounds) = blanket_bounds(generics, self_ty) {
+                    self.record_bounds(&trait_name, bounds, BoundKind::BlanketImpl);
+                }
+
                 // Extract type name
                 let type_name = extract_type_name(self_ty);
 
+                let impl_span = ImplSpan::of(item_impl, Some(&trait_name), &type_name);
+                let impl_cfg = has_cfg_attr(impl_attrs);
+                let impl_keep = keep_annotation(impl_attrs);
+
                 // Record all implemented methods
                 for impl_item in items {
-                    if let ImplItem::Fn(ImplItemFn { sig, .. }) = impl_item {
-                        let method_name = sig.ident.to_string();
-                        self.record_impl_method(&trait_name, &type_name, &method_name);
+                    if let ImplItem::Fn(ImplItemFn { sig, attrs, .. }) = impl_item {
+                        let has_cfg = impl_cfg || has_cfg_attr(attrs);
+                        let keep = keep_annotation(attrs).or_else(|| impl_keep.clone());
+                        self.record_impl_method(
+                            &trait_name,
+                            &type_name,
+                            sig,
+                            &impl_span,
+                            has_cfg,
+                            keep,
+                        );
                     }
                 }
             }
 
             // Inherent implementations: impl Type { fn method() {} }
-            Item::Impl(ItemImpl {
-                trait_: None,
-                self_ty,
-                items,
-                ..
-            }) => {
+            Item::Impl(
+                item_impl @ ItemImpl {
+                    trait_: None,
+                    self_ty,
+                    items,
+                    attrs: impl_attrs,
+                    ..
+                },
+            ) => {
                 let type_name = extract_type_name(self_ty);
+                let impl_span = ImplSpan::of(item_impl, None, &type_name);
+                let impl_cfg = has_cfg_attr(impl_attrs);
+                let impl_keep = keep_annotation(impl_attrs);
 
                 for impl_item in items {
-                    if let ImplItem::Fn(ImplItemFn { sig, vis, .. }) = impl_item {
+                    if let ImplItem::Fn(ImplItemFn {
+                        sig, vis, attrs, ..
+                    }) = impl_item
+                    {
                         let method_name = sig.ident.to_string();
                         // Check if method has a self receiver
                         let is_static = !sig.inputs.iter().any(|arg| {
                             matches!(arg, syn::FnArg::Receiver(_))
                         });
-                        self.record_inherent_method(&type_name, &method_name, vis, is_static);
+                        let has_cfg = impl_cfg || has_cfg_attr(attrs);
+                        let keep = keep_annotation(attrs).or_else(|| impl_keep.clone());
+                        self.record_inherent_method(
+                            &type_name,
+                            &method_name,
+                            vis,
+                            is_static,
+                            &impl_span,
+                            has_cfg,
+                            keep,
+                        );
                     }
                 }
             }
@@ -242,6 +406,54 @@ impl<'ast> Visit<'ast> for TraitExtractor {
     }
 }
 
+/// Trait names in a bound list, without `?Sized` and lifetimes.
+fn trait_bound_names(bounds: &Punctuated<TypeParamBound, syn::Token![+]>) -> Vec<String> {
+    bounds
+        .iter()
+        .filter_map(|bound| match bound {
+            TypeParamBound::Trait(tb) if !matches!(tb.modifier, TraitBoundModifier::Maybe(_)) => {
+                tb.path.segments.last().map(|s| s.ident.to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Trait bounds the where clause puts on the type named `ident`.
+fn where_bounds(generics: &Generics, ident: &str) -> Vec<String> {
+    let Some(where_clause) = &generics.where_clause else {
+        return Vec::new();
+    };
+    where_clause
+        .predicates
+        .iter()
+        .filter_map(|predicate| match predicate {
+            WherePredicate::Type(pt) if extract_type_name(&pt.bounded_ty) == ident => {
+                Some(trait_bound_names(&pt.bounds))
+            }
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+/// Bounds of the type parameter a blanket impl (`impl<T: A + B> Trait for T`)
+/// is for; `None` when the impl is for a concrete type.
+fn blanket_bounds(generics: &Generics, self_ty: &Type) -> Option<Vec<String>> {
+    let Type::Path(type_path) = self_ty else {
+        return None;
+    };
+    let ident = type_path.path.get_ident()?.to_string();
+    let param = generics.params.iter().find_map(|param| match param {
+        GenericParam::Type(tp) if tp.ident == ident => Some(tp),
+        _ => None,
+    })?;
+
+    let mut bounds = trait_bound_names(&param.bounds);
+    bounds.extend(where_bounds(generics, &ident));
+    Some(bounds)
+}
+
 /// Extract a readable type name from a syn::Type.
 fn extract_type_name(ty: &syn::Type) -> String {
     match ty {
@@ -422,6 +634,68 @@ impl MyStruct {
         assert!(static_m.is_static);
     }
 
+    #[test]
+    fn test_extract_keep_annotation() {
+        let content = r#"
+#[deadmod::keep(reason = "extension point")]
+trait Hooks {
+    fn before(&self) {}
+}
+
+impl Hooks for App {
+    #[deadmod::keep]
+    fn before(&self) {}
+}
+
+impl App {
+    fn plain(&self) {}
+}
+"#;
+        let result = extract_traits(&PathBuf::from("test.rs"), content);
+        assert_eq!(
+            result.trait_methods[0]
+                .keep
+                .as_ref()
+                .unwrap()
+                .reason
+                .as_deref(),
+            Some("extension point")
+        );
+        assert_eq!(result.impl_methods[0].keep, Some(Keep::default()));
+        assert!(result.inherent_methods[0].keep.is_none());
+    }
+
+    #[test]
+    fn test_extract_supertraits_and_blanket_impls() {
+        let content = r#"
+trait Named { fn name(&self) -> String; }
+trait Shape: Named + Clone + ?Sized where Self: Send { fn area(&self) -> f64; }
+trait Describe { fn describe(&self) -> String; }
+impl<T: Shape + 'static> Describe for T where T: Sync {
+    fn describe(&self) -> String { self.name() }
+}
+impl Describe for Vec<u8> { fn describe(&self) -> String { String::new() } }
+"#;
+        let result = extract_traits(&PathBuf::from("test.rs"), content);
+        assert_eq!(result.trait_names, ["Named", "Shape", "Describe"]);
+
+        let bounds: Vec<(&str, &str, BoundKind)> = result
+            .bounds
+            .iter()
+            .map(|b| (b.trait_name.as_str(), b.bound.as_str(), b.kind))
+            .collect();
+        assert_eq!(
+            bounds,
+            [
+                ("Shape", "Named", BoundKind::Supertrait),
+                ("Shape", "Clone", BoundKind::Supertrait),
+                ("Shape", "Send", BoundKind::Supertrait),
+                ("Describe", "Shape", BoundKind::BlanketImpl),
+                ("Describe", "Sync", BoundKind::BlanketImpl),
+            ]
+        );
+    }
+
     #[test]
     fn test_inherent_impl_full_id() {
         let content = r#"
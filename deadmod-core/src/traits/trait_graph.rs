@@ -6,15 +6,30 @@
 //! - Required trait methods (no default body) - must be implemented
 //! - Public trait methods that could be called externally
 //! - Methods called from main() or pub functions
+//! - `#[deadmod::keep]` methods (listed in [`TraitAnalysisResult::kept`])
+//!
+//! `impl` blocks whose methods are all dead are also reported whole, in
+//! [`TraitAnalysisResult::dead_impls`].
+//!
+//! Calls naming a trait (`<T as Trait>::method()`, operators) reach the
+//! method in the whole [`TraitHierarchy`] of that trait: its supertraits,
+//! the traits built on it, and traits blanket-implemented for its
+//! implementors. Default methods and their overrides in impls share that
+//! liveness. A qualified call to a trait whose hierarchy is fully defined in
+//! the analyzed code only keeps that hierarchy alive; otherwise it keeps
+//! every method of the same name alive, like a plain method call.
 //!
 //! Performance characteristics:
 //! - Graph build: O(|M| + |U|) where M = methods, U = usages
 //! - Reachability: O(|M| + |E|) single BFS traversal
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
-use super::trait_extractor::{InherentImplMethod, TraitExtractionResult, TraitImplMethod, TraitMethodDef};
-use super::trait_usage::TraitMethodUsage;
+use super::trait_extractor::{
+    InherentImplMethod, TraitBound, TraitExtractionResult, TraitImplMethod, TraitMethodDef,
+};
+use super::trait_usage::{TraitMethodUsage, UsageKind};
+use crate::common::{dead_impls, Confidence, ConfidenceSignals, DeadImpl, KeptItem};
 
 /// Result of trait method dead code analysis.
 #[derive(Debug, Clone)]
@@ -31,10 +46,28 @@ pub struct TraitAnalysisResult {
     pub dead_impl_methods: Vec<TraitImplMethod>,
     /// Dead (unreachable) inherent impl methods
     pub dead_inherent_methods: Vec<InherentImplMethod>,
+    /// Trait and inherent `impl` blocks whose methods are all dead
+    pub dead_impls: Vec<DeadImpl>,
+    /// Methods kept alive by `#[deadmod::keep]`
+    pub kept: Vec<KeptItem>,
     /// Statistics
     pub stats: TraitStats,
 }
 
+impl TraitAnalysisResult {
+    /// Drop dead methods below `min` confidence and update the dead counts.
+    pub fn retain_confidence(&mut self, min: Confidence) {
+        self.dead_trait_methods.retain(|m| m.confidence >= min);
+        self.dead_impl_methods.retain(|m| m.confidence >= min);
+        self.dead_inherent_methods.retain(|m| m.confidence >= min);
+        self.dead_impls.retain(|d| d.confidence >= min);
+        self.stats.dead_trait_method_count = self.dead_trait_methods.len();
+        self.stats.dead_impl_method_count = self.dead_impl_methods.len();
+        self.stats.dead_inherent_method_count = self.dead_inherent_methods.len();
+        self.stats.dead_impl_block_count = self.dead_impls.len();
+    }
+}
+
 /// Statistics about trait method analysis.
 #[derive(Debug, Clone, Default)]
 pub struct TraitStats {
@@ -44,10 +77,88 @@ pub struct TraitStats {
     pub dead_trait_method_count: usize,
     pub dead_impl_method_count: usize,
     pub dead_inherent_method_count: usize,
+    pub dead_impl_block_count: usize,
     pub required_methods: usize,
     pub provided_methods: usize,
 }
 
+/// Supertrait and blanket impl relations between traits.
+#[derive(Debug, Clone, Default)]
+pub struct TraitHierarchy {
+    /// Traits defined in the analyzed code
+    defined: HashSet<String>,
+    /// Trait -> traits its implementors implement too
+    supertraits: HashMap<String, BTreeSet<String>>,
+    /// Trait -> traits whose implementors implement it
+    subtraits: HashMap<String, BTreeSet<String>>,
+}
+
+impl TraitHierarchy {
+    /// Record a trait defined in the analyzed code.
+    pub fn add_trait(&mut self, trait_name: &str) {
+        self.defined.insert(trait_name.to_string());
+    }
+
+    /// Record a supertrait or blanket impl edge.
+    pub fn add_bound(&mut self, bound: &TraitBound) {
+        self.supertraits
+            .entry(bound.trait_name.clone())
+            .or_default()
+            .insert(bound.bound.clone());
+        self.subtraits
+            .entry(bound.bound.clone())
+            .or_default()
+            .insert(bound.trait_name.clone());
+    }
+
+    /// All traits `trait_name` requires, transitively.
+    pub fn supertraits(&self, trait_name: &str) -> BTreeSet<&str> {
+        closure(&self.supertraits, trait_name)
+    }
+
+    /// All traits requiring `trait_name`, transitively.
+    pub fn subtraits(&self, trait_name: &str) -> BTreeSet<&str> {
+        closure(&self.subtraits, trait_name)
+    }
+
+    /// `trait_name` with its supertraits and subtraits: the traits a call
+    /// naming `trait_name` may dispatch into.
+    pub fn related<'a>(&'a self, trait_name: &'a str) -> BTreeSet<&'a str> {
+        let mut related = self.supertraits(trait_name);
+        related.extend(self.subtraits(trait_name));
+        related.insert(trait_name);
+        related
+    }
+
+    /// Whether `trait_name` and all its supertraits are defined in the
+    /// analyzed code, so every method a call through it can reach is known.
+    pub fn is_closed(&self, trait_name: &str) -> bool {
+        self.defined.contains(trait_name)
+            && self
+                .supertraits(trait_name)
+                .iter()
+                .all(|t| self.defined.contains(*t))
+    }
+}
+
+/// Traits reachable from `start` over `edges`, without `start` itself
+/// unless it is part of a cycle.
+fn closure<'a>(edges: &'a HashMap<String, BTreeSet<String>>, start: &str) -> BTreeSet<&'a str> {
+    let mut seen = BTreeSet::new();
+    let mut stack: Vec<&str> = edges
+        .get(start)
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    while let Some(next) = stack.pop() {
+        if seen.insert(next) {
+            stack.extend(edges.get(next).into_iter().flatten().map(String::as_str));
+        }
+    }
+    seen
+}
+
 /// Trait method call graph for dead code detection.
 pub struct TraitGraph {
     /// Map from full_path to TraitMethodDef
@@ -60,6 +171,10 @@ pub struct TraitGraph {
     called_methods: HashSet<String>,
     /// Map from trait_name::method_name to usages
     method_usages: HashMap<String, Vec<TraitMethodUsage>>,
+    /// Identifiers mentioned inside macro invocations (confidence signal)
+    macro_mentions: HashSet<String>,
+    /// Supertraits and blanket impls
+    hierarchy: TraitHierarchy,
 }
 
 impl TraitGraph {
@@ -71,9 +186,31 @@ impl TraitGraph {
             inherent_methods: HashMap::new(),
             called_methods: HashSet::new(),
             method_usages: HashMap::new(),
+            macro_mentions: HashSet::new(),
+            hierarchy: TraitHierarchy::default(),
         }
     }
 
+    /// Provide identifiers found inside macro invocations.
+    ///
+    /// Dead methods whose name is mentioned in a macro are reported with
+    /// lower confidence.
+    pub fn with_macro_mentions(mut self, mentions: HashSet<String>) -> Self {
+        self.macro_mentions = mentions;
+        self
+    }
+
+    /// Score how confident we are that an uncalled method is dead.
+    fn confidence_for(&self, method_name: &str, visibility: &str, has_cfg: bool) -> Confidence {
+        ConfidenceSignals {
+            visibility,
+            has_cfg,
+            in_macro: self.macro_mentions.contains(method_name),
+            ..Default::default()
+        }
+        .score()
+    }
+
     /// Build the trait method graph from extracted data.
     ///
     /// # Arguments
@@ -104,18 +241,41 @@ impl TraitGraph {
                     .inherent_methods
                     .insert(inherent_method.full_id.clone(), inherent_method.clone());
             }
+
+            for trait_name in &extraction.trait_names {
+                graph.hierarchy.add_trait(trait_name);
+            }
+            for bound in &extraction.bounds {
+                graph.hierarchy.add_bound(bound);
+            }
         }
 
         // Collect all method calls
         for usage_set in usages {
             for usage in usage_set {
-                graph.called_methods.insert(usage.method_name.clone());
+                // `std::ops::Add` is the `Add` trait
+                let trait_name = usage
+                    .trait_name
+                    .as_deref()
+                    .map(|name| name.rsplit("::").next().unwrap_or(name));
+
+                // `a + b` says nothing about methods named `add` outside Add,
+                // nor `<T as Trait>::m()` about `m` outside a known hierarchy
+                let by_name = match usage.usage_kind {
+                    UsageKind::Operator => false,
+                    UsageKind::QualifiedCall => {
+                        !trait_name.is_some_and(|t| graph.hierarchy.is_closed(t))
+                    }
+                    _ => true,
+                };
+                if by_name {
+                    graph.called_methods.insert(usage.method_name.clone());
+                }
 
                 // Track specific usages for more precise analysis
-                let key = if let Some(ref trait_name) = usage.trait_name {
-                    format!("{}::{}", trait_name, usage.method_name)
-                } else {
-                    usage.method_name.clone()
+                let key = match trait_name {
+                    Some(trait_name) => format!("{}::{}", trait_name, usage.method_name),
+                    None => usage.method_name.clone(),
                 };
 
                 graph
@@ -129,19 +289,34 @@ impl TraitGraph {
         graph
     }
 
+    /// Supertraits and blanket impls of the analyzed code.
+    pub fn hierarchy(&self) -> &TraitHierarchy {
+        &self.hierarchy
+    }
+
+    /// Whether a call naming `trait_name` or a related trait (see
+    /// [`TraitHierarchy::related`]) reaches `method_name`.
+    fn called_through_hierarchy(&self, trait_name: &str, method_name: &str) -> bool {
+        self.hierarchy.related(trait_name).into_iter().any(|t| {
+            self.method_usages
+                .contains_key(&format!("{}::{}", t, method_name))
+        })
+    }
+
     /// Determine if a trait method is reachable.
     ///
     /// A trait method is reachable if:
     /// - It's a required method (must be implemented)
     /// - It's actually called somewhere in the codebase
-    /// - It has a qualified call like `<T as Trait>::method()`
+    /// - It has a qualified call like `<T as Trait>::method()`, naming its
+    ///   trait or a related one
     ///
     /// Note: Public trait methods are NOT automatically considered reachable,
     /// as uncalled public methods in a crate should still be reported as dead.
     /// Library entry points should be handled at a higher level if needed.
     fn is_method_reachable(&self, method: &TraitMethodDef) -> bool {
         // Required methods are always "alive" - implementors must provide them
-        if method.is_required {
+        if method.is_required || method.keep.is_some() {
             return true;
         }
 
@@ -150,13 +325,8 @@ impl TraitGraph {
             return true;
         }
 
-        // Check for qualified calls like Trait::method
-        let qualified_name = format!("{}::{}", method.trait_name, method.method_name);
-        if self.method_usages.contains_key(&qualified_name) {
-            return true;
-        }
-
-        false
+        // Check for qualified calls like Trait::method, through supertraits too
+        self.called_through_hierarchy(&method.trait_name, &method.method_name)
     }
 
     /// Determine if an impl method is reachable.
@@ -164,7 +334,23 @@ impl TraitGraph {
     /// An impl method is reachable if:
     /// - The trait method it implements is required
     /// - The method is called somewhere
+    /// - It is called through syntax (`a + b` calls `Add::add`) or a
+    ///   qualified call naming its trait or a related one, or implicitly
+    ///   (`Drop::drop`)
     fn is_impl_method_reachable(&self, impl_method: &TraitImplMethod) -> bool {
+        if impl_method.keep.is_some() {
+            return true;
+        }
+
+        // Drop glue calls drop() whenever a value goes out of scope
+        if impl_method.trait_name == "Drop" && impl_method.method_name == "drop" {
+            return true;
+        }
+
+        if self.called_through_hierarchy(&impl_method.trait_name, &impl_method.method_name) {
+            return true;
+        }
+
         // Find the corresponding trait method definition
         let trait_method_key = format!("{}::{}", impl_method.trait_name, impl_method.method_name);
 
@@ -203,6 +389,10 @@ impl TraitGraph {
     /// - The method is called somewhere
     /// - The method is called with Type::method syntax
     fn is_inherent_method_reachable(&self, method: &InherentImplMethod) -> bool {
+        if method.keep.is_some() {
+            return true;
+        }
+
         // Check if called by simple name
         if self.called_methods.contains(&method.method_name) {
             return true;
@@ -229,19 +419,29 @@ impl TraitGraph {
         let mut dead_trait_methods: Vec<TraitMethodDef> = self
             .find_dead_trait_methods()
             .into_iter()
-            .cloned()
+            .map(|m| TraitMethodDef {
+                confidence: self.confidence_for(&m.method_name, &m.visibility, m.has_cfg),
+                ..m.clone()
+            })
             .collect();
 
+        // Impl methods inherit the trait's reach, so visibility is not a signal
         let mut dead_impl_methods: Vec<TraitImplMethod> = self
             .find_dead_impl_methods()
             .into_iter()
-            .cloned()
+            .map(|m| TraitImplMethod {
+                confidence: self.confidence_for(&m.method_name, "", m.has_cfg),
+                ..m.clone()
+            })
             .collect();
 
         let mut dead_inherent_methods: Vec<InherentImplMethod> = self
             .find_dead_inherent_methods()
             .into_iter()
-            .cloned()
+            .map(|m| InherentImplMethod {
+                confidence: self.confidence_for(&m.method_name, &m.visibility, m.has_cfg),
+                ..m.clone()
+            })
             .collect();
 
         // Sort for consistent output
@@ -256,6 +456,56 @@ impl TraitGraph {
         let dead_impl_count = dead_impl_methods.len();
         let dead_inherent_count = dead_inherent_methods.len();
 
+        let all_in_impls = self
+            .impl_methods
+            .values()
+            .filter_map(|m| Some((m.file.as_str(), m.impl_span.as_ref()?)))
+            .chain(
+                self.inherent_methods
+                    .values()
+                    .filter_map(|m| Some((m.file.as_str(), m.impl_span.as_ref()?))),
+            );
+        let dead_in_impls = dead_impl_methods
+            .iter()
+            .filter_map(|m| {
+                Some((
+                    m.file.as_str(),
+                    m.impl_span.as_ref()?,
+                    m.method_name.as_str(),
+                    m.confidence,
+                ))
+            })
+            .chain(dead_inherent_methods.iter().filter_map(|m| {
+                Some((
+                    m.file.as_str(),
+                    m.impl_span.as_ref()?,
+                    m.method_name.as_str(),
+                    m.confidence,
+                ))
+            }));
+        let dead_impls = dead_impls(all_in_impls, dead_in_impls);
+
+        let mut kept: Vec<KeptItem> = self
+            .trait_methods
+            .values()
+            .filter_map(|m| {
+                m.keep
+                    .as_ref()
+                    .map(|keep| KeptItem::new(&m.full_path, &m.file, keep))
+            })
+            .chain(self.impl_methods.values().filter_map(|m| {
+                m.keep
+                    .as_ref()
+                    .map(|keep| KeptItem::new(&m.full_id, &m.file, keep))
+            }))
+            .chain(self.inherent_methods.values().filter_map(|m| {
+                m.keep
+                    .as_ref()
+                    .map(|keep| KeptItem::new(&m.full_id, &m.file, keep))
+            }))
+            .collect();
+        kept.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.name.cmp(&b.name)));
+
         TraitAnalysisResult {
             all_trait_methods: self.trait_methods.values().cloned().collect(),
             all_impl_methods: self.impl_methods.values().cloned().collect(),
@@ -270,9 +520,12 @@ impl TraitGraph {
                 dead_trait_method_count: dead_trait_count,
                 dead_impl_method_count: dead_impl_count,
                 dead_inherent_method_count: dead_inherent_count,
+                dead_impl_block_count: dead_impls.len(),
                 required_methods,
                 provided_methods,
             },
+            dead_impls,
+            kept,
         }
     }
 
@@ -316,6 +569,10 @@ mod tests {
             visibility: vis.to_string(),
             is_required,
             file: file.to_string(),
+            line: 0,
+            has_cfg: false,
+            keep: None,
+            confidence: Confidence::default(),
         }
     }
 
@@ -331,6 +588,11 @@ mod tests {
             method_name: method_name.to_string(),
             full_id: format!("impl {} for {} :: {}", trait_name, type_name, method_name),
             file: file.to_string(),
+            line: 0,
+            has_cfg: false,
+            keep: None,
+            confidence: Confidence::default(),
+            impl_span: None,
         }
     }
 
@@ -343,6 +605,7 @@ mod tests {
             ],
             impl_methods: vec![],
             inherent_methods: vec![],
+            ..Default::default()
         };
 
         let graph = TraitGraph::build(&[extraction], &[]);
@@ -364,6 +627,7 @@ mod tests {
             ],
             impl_methods: vec![],
             inherent_methods: vec![],
+            ..Default::default()
         };
 
         let usage = TraitMethodUsage {
@@ -398,6 +662,7 @@ mod tests {
             )],
             impl_methods: vec![make_impl_method("MyTrait", "MyStruct", "required", "impl.rs")],
             inherent_methods: vec![],
+            ..Default::default()
         };
 
         let graph = TraitGraph::build(&[extraction], &[]);
@@ -419,6 +684,7 @@ mod tests {
                 make_impl_method("T", "A", "provided", "test.rs"),
             ],
             inherent_methods: vec![],
+            ..Default::default()
         };
 
         let graph = TraitGraph::build(&[extraction], &[]);
@@ -440,6 +706,7 @@ mod tests {
                 make_impl_method("Foo", "TypeC", "bar", "c.rs"),
             ],
             inherent_methods: vec![],
+            ..Default::default()
         };
 
         let graph = TraitGraph::build(&[extraction], &[]);
@@ -461,6 +728,7 @@ mod tests {
             ],
             impl_methods: vec![],
             inherent_methods: vec![],
+            ..Default::default()
         };
 
         // Only called_method is actually used
@@ -488,6 +756,7 @@ mod tests {
             ],
             impl_methods: vec![],
             inherent_methods: vec![],
+            ..Default::default()
         };
 
         // Method is called with qualified path: MyTrait::qualified_call
@@ -521,9 +790,46 @@ mod tests {
             is_static,
             file: file.to_string(),
             module_path: String::new(),
+            has_cfg: false,
+            keep: None,
+            confidence: Confidence::default(),
+            impl_span: None,
         }
     }
 
+    #[test]
+    fn test_dead_method_confidence() {
+        let extraction = TraitExtractionResult {
+            trait_methods: vec![make_trait_method(
+                "MyTrait", "provided", "pub", false, "test.rs",
+            )],
+            impl_methods: vec![],
+            inherent_methods: vec![
+                make_inherent_method("Foo", "helper", "private", false, "foo.rs"),
+                make_inherent_method("Foo", "via_macro", "private", false, "foo.rs"),
+            ],
+            ..Default::default()
+        };
+
+        let graph = TraitGraph::build(&[extraction], &[])
+            .with_macro_mentions(HashSet::from(["via_macro".to_string()]));
+        let result = graph.analyze();
+
+        assert_eq!(result.dead_trait_methods[0].confidence, Confidence::Low);
+        let helper = result
+            .dead_inherent_methods
+            .iter()
+            .find(|m| m.method_name == "helper")
+            .unwrap();
+        assert_eq!(helper.confidence, Confidence::High);
+        let via_macro = result
+            .dead_inherent_methods
+            .iter()
+            .find(|m| m.method_name == "via_macro")
+            .unwrap();
+        assert_eq!(via_macro.confidence, Confidence::Low);
+    }
+
     #[test]
     fn test_inherent_method_uncalled_is_dead() {
         let extraction = TraitExtractionResult {
@@ -533,6 +839,7 @@ mod tests {
                 make_inherent_method("MyType", "called_method", "pub", false, "test.rs"),
                 make_inherent_method("MyType", "uncalled_method", "pub", false, "test.rs"),
             ],
+            ..Default::default()
         };
 
         let usage = TraitMethodUsage {
@@ -551,6 +858,30 @@ mod tests {
         assert_eq!(result.dead_inherent_methods[0].method_name, "uncalled_method");
     }
 
+    #[test]
+    fn test_kept_methods_are_not_dead() {
+        let mut kept = make_inherent_method("Plugin", "on_load", "pub", false, "test.rs");
+        kept.keep = Some(crate::common::Keep {
+            reason: Some("invoked via dlsym".to_string()),
+        });
+        let extraction = TraitExtractionResult {
+            trait_methods: vec![],
+            impl_methods: vec![],
+            inherent_methods: vec![
+                kept,
+                make_inherent_method("Plugin", "unused", "pub", false, "test.rs"),
+            ],
+            ..Default::default()
+        };
+
+        let result = TraitGraph::build(&[extraction], &[]).analyze();
+        assert_eq!(result.stats.dead_inherent_method_count, 1);
+        assert_eq!(result.dead_inherent_methods[0].method_name, "unused");
+        assert_eq!(result.kept.len(), 1);
+        assert_eq!(result.kept[0].name, "Plugin::on_load");
+        assert_eq!(result.kept[0].reason.as_deref(), Some("invoked via dlsym"));
+    }
+
     #[test]
     fn test_inherent_static_method() {
         let extraction = TraitExtractionResult {
@@ -560,6 +891,7 @@ mod tests {
                 make_inherent_method("Factory", "new", "pub", true, "test.rs"),
                 make_inherent_method("Factory", "unused_static", "pub", true, "test.rs"),
             ],
+            ..Default::default()
         };
 
         let usage = TraitMethodUsage {
@@ -577,4 +909,131 @@ mod tests {
         assert_eq!(result.dead_inherent_methods.len(), 1);
         assert_eq!(result.dead_inherent_methods[0].method_name, "unused_static");
     }
+
+    #[test]
+    fn test_impl_block_with_all_methods_dead() {
+        let source = "\
+pub struct Unused;
+pub struct Partial;
+
+impl Unused {
+    pub fn a(&self) {}
+    pub fn b(&self) {}
+}
+
+impl Partial {
+    pub fn used(&self) {}
+    pub fn unused(&self) {}
+}
+
+fn main() { Partial.used(); }
+";
+        let path = std::path::Path::new("lib.rs");
+        let extraction = super::super::extract_traits(path, source);
+        let usages = super::super::extract_trait_usages(path, source);
+        let result = TraitGraph::build(&[extraction], &[usages]).analyze();
+
+        assert_eq!(result.stats.dead_impl_block_count, 1);
+        let block = &result.dead_impls[0];
+        assert_eq!(block.span.label, "impl Unused");
+        assert_eq!((block.span.start_line, block.span.end_line), (4, 7));
+        assert_eq!(block.methods, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_operator_impls_are_alive() {
+        let source = "\
+use std::ops::{Add, Mul, Deref};
+pub struct V(f64);
+pub struct Guard(V);
+
+impl Add for V { type Output = V; fn add(self, o: V) -> V { V(self.0 + o.0) } }
+impl Mul for V { type Output = V; fn mul(self, o: V) -> V { o } }
+impl Deref for Guard { type Target = V; fn deref(&self) -> &V { &self.0 } }
+impl Drop for Guard { fn drop(&mut self) {} }
+
+pub struct Bag;
+impl Bag { pub fn add(&self) {} }
+
+fn main() { let s = V(1.0) + V(2.0); }
+";
+        let path = std::path::Path::new("lib.rs");
+        let extraction = super::super::extract_traits(path, source);
+        let usages = super::super::extract_trait_usages(path, source);
+        let result = TraitGraph::build(&[extraction], &[usages]).analyze();
+
+        let dead: Vec<String> = result
+            .dead_impl_methods
+            .iter()
+            .map(|m| format!("{}::{}", m.trait_name, m.method_name))
+            .collect();
+        // `+` uses Add::add, `self.0` auto-derefs and Drop runs implicitly
+        assert_eq!(dead, ["Mul::mul"]);
+        // An operator does not keep unrelated methods of the same name alive
+        assert!(result
+            .dead_inherent_methods
+            .iter()
+            .any(|m| m.method_name == "add"));
+    }
+
+    #[test]
+    fn test_calls_through_trait_hierarchy() {
+        let source = "\
+pub trait Named { fn name(&self) -> String { String::new() } fn unused_default(&self) {} }
+pub trait Shape: Named { fn area(&self) -> f64; }
+pub trait Describe { fn describe(&self) -> String { String::new() } }
+impl<T: Shape> Describe for T { fn describe(&self) -> String { String::new() } }
+
+pub struct Square;
+impl Named for Square { fn name(&self) -> String { String::new() } }
+impl Shape for Square { fn area(&self) -> f64 { 1.0 } }
+
+pub struct Other;
+impl Other { pub fn name(&self) {} pub fn unrelated(&self) {} }
+
+pub trait Counter: Iterator {}
+pub struct Ticks;
+impl Iterator for Ticks { type Item = u8; fn next(&mut self) -> Option<u8> { None } }
+
+fn main() {
+    let _ = <Square as Shape>::name(&Square);
+    let _ = <Square as Describe>::describe(&Square);
+    let _ = <Ticks as Counter>::next(&mut Ticks);
+}
+";
+        let path = std::path::Path::new("lib.rs");
+        let extraction = super::super::extract_traits(path, source);
+        let usages = super::super::extract_trait_usages(path, source);
+        let graph = TraitGraph::build(&[extraction], &[usages]);
+
+        let related = graph.hierarchy().related("Named");
+        assert_eq!(
+            related.into_iter().collect::<Vec<_>>(),
+            ["Describe", "Named", "Shape"]
+        );
+        assert!(graph.hierarchy().is_closed("Describe"));
+        assert!(!graph.hierarchy().is_closed("Counter"));
+
+        let result = graph.analyze();
+        let dead_trait: Vec<&str> = result
+            .dead_trait_methods
+            .iter()
+            .map(|m| m.full_path.as_str())
+            .collect();
+        // `name` through the subtrait, `describe` through the blanket impl
+        assert_eq!(dead_trait, ["Named::unused_default"]);
+        assert!(
+            result.dead_impl_methods.is_empty(),
+            "{:?}",
+            result.dead_impl_methods
+        );
+        // A call through a fully known hierarchy says nothing about Other::name;
+        // Counter's supertrait is foreign, so `next` counts by name
+        let dead_inherent: Vec<&str> = result
+            .dead_inherent_methods
+            .iter()
+            .map(|m| m.full_id.as_str())
+            .collect();
+        assert_eq!(dead_inherent, ["Other::name", "Other::unrelated"]);
+    }
 }
@@ -5,13 +5,21 @@
 //! - `Type::method()` - associated function calls
 //! - `<Type as Trait>::method()` - qualified path calls
 //! - `<Trait>::method()` - direct trait method calls
+//! - operator syntax - `a + b` uses `Add::add`, `a[i]` uses `Index::index`,
+//!   `*x` and auto-deref use `Deref::deref`, `for` uses `Iterator::next`
+//!
+//! Operator usages carry the trait name but no type: operand types are not
+//! known without type inference, so any `a + b` keeps every `Add::add` impl
+//! alive (heuristic).
 //!
 //! NASA-grade resilience: handles malformed AST gracefully.
 
 use std::collections::HashSet;
 use std::path::Path;
 
-use syn::{visit::Visit, Expr, ExprMethodCall, ExprPath, File, QSelf};
+use syn::{visit::Visit, BinOp, Expr, ExprMethodCall, ExprPath, File, Macro, QSelf, UnOp};
+
+use crate::common::visit_macro_args;
 
 /// Information about a trait method usage site.
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -35,6 +43,65 @@ pub enum UsageKind {
     AssociatedCall,
     /// <Type as Trait>::method() - fully qualified call
     QualifiedCall,
+    /// a + b, a[i], *x, for x in y - method called through syntax
+    Operator,
+}
+
+/// Trait methods invoked by a binary operator.
+fn binary_op_methods(op: &BinOp) -> &'static [(&'static str, &'static str)] {
+    match op {
+        BinOp::Add(_) => &[("Add", "add")],
+        BinOp::Sub(_) => &[("Sub", "sub")],
+        BinOp::Mul(_) => &[("Mul", "mul")],
+        BinOp::Div(_) => &[("Div", "div")],
+        BinOp::Rem(_) => &[("Rem", "rem")],
+        BinOp::BitAnd(_) => &[("BitAnd", "bitand")],
+        BinOp::BitOr(_) => &[("BitOr", "bitor")],
+        BinOp::BitXor(_) => &[("BitXor", "bitxor")],
+        BinOp::Shl(_) => &[("Shl", "shl")],
+        BinOp::Shr(_) => &[("Shr", "shr")],
+        BinOp::AddAssign(_) => &[("AddAssign", "add_assign")],
+        BinOp::SubAssign(_) => &[("SubAssign", "sub_assign")],
+        BinOp::MulAssign(_) => &[("MulAssign", "mul_assign")],
+        BinOp::DivAssign(_) => &[("DivAssign", "div_assign")],
+        BinOp::RemAssign(_) => &[("RemAssign", "rem_assign")],
+        BinOp::BitAndAssign(_) => &[("BitAndAssign", "bitand_assign")],
+        BinOp::BitOrAssign(_) => &[("BitOrAssign", "bitor_assign")],
+        BinOp::BitXorAssign(_) => &[("BitXorAssign", "bitxor_assign")],
+        BinOp::ShlAssign(_) => &[("ShlAssign", "shl_assign")],
+        BinOp::ShrAssign(_) => &[("ShrAssign", "shr_assign")],
+        BinOp::Eq(_) => &[("PartialEq", "eq")],
+        BinOp::Ne(_) => &[("PartialEq", "ne"), ("PartialEq", "eq")],
+        // The provided lt/le/gt/ge all go through partial_cmp
+        BinOp::Lt(_) => &[("PartialOrd", "lt"), ("PartialOrd", "partial_cmp")],
+        BinOp::Le(_) => &[("PartialOrd", "le"), ("PartialOrd", "partial_cmp")],
+        BinOp::Gt(_) => &[("PartialOrd", "gt"), ("PartialOrd", "partial_cmp")],
+        BinOp::Ge(_) => &[("PartialOrd", "ge"), ("PartialOrd", "partial_cmp")],
+        _ => &[],
+    }
+}
+
+/// Trait methods invoked by other expression syntax.
+fn syntax_methods(expr: &Expr) -> &'static [(&'static str, &'static str)] {
+    const DEREF: &[(&str, &str)] = &[("Deref", "deref"), ("DerefMut", "deref_mut")];
+    match expr {
+        Expr::Binary(binary) => binary_op_methods(&binary.op),
+        Expr::Unary(unary) => match unary.op {
+            UnOp::Neg(_) => &[("Neg", "neg")],
+            UnOp::Not(_) => &[("Not", "not")],
+            UnOp::Deref(_) => DEREF,
+            _ => &[],
+        },
+        // Mutability of the place is not known: count both
+        Expr::Index(_) => &[("Index", "index"), ("IndexMut", "index_mut")],
+        // Method calls and field access auto-deref through smart pointers
+        Expr::MethodCall(_) | Expr::Field(_) => DEREF,
+        Expr::ForLoop(_) => &[("IntoIterator", "into_iter"), ("Iterator", "next")],
+        // `?` converts the error with From::from
+        Expr::Try(_) => &[("From", "from")],
+        Expr::Await(_) => &[("IntoFuture", "into_future"), ("Future", "poll")],
+        _ => &[],
+    }
 }
 
 /// AST visitor that extracts trait method usages.
@@ -119,9 +186,46 @@ impl<'ast> Visit<'ast> for TraitUsageExtractor {
             _ => {}
         }
 
+        for (trait_name, method) in syntax_methods(node) {
+            self.usages.insert(TraitMethodUsage {
+                method_name: method.to_string(),
+                trait_name: Some(trait_name.to_string()),
+                type_name: None,
+                usage_kind: UsageKind::Operator,
+            });
+        }
+
         // Continue visiting nested expressions
         syn::visit::visit_expr(self, node);
     }
+
+    // Method calls inside macro arguments: debug!("{}", x.describe())
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac, |v, found| {
+            let segments: Vec<_> = found
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect();
+            if found.is_method {
+                v.usages.insert(TraitMethodUsage {
+                    method_name: found.name(),
+                    trait_name: None,
+                    type_name: None,
+                    usage_kind: UsageKind::MethodCall,
+                });
+            } else if found.is_call && segments.len() >= 2 {
+                v.usages.insert(TraitMethodUsage {
+                    method_name: found.name(),
+                    trait_name: None,
+                    type_name: Some(segments[..segments.len() - 1].join("::")),
+                    usage_kind: UsageKind::AssociatedCall,
+                });
+            }
+        });
+        syn::visit::visit_macro(self, mac);
+    }
 }
 
 /// Extract a string representation from a syn::Type.
@@ -259,6 +363,41 @@ fn main() {
         assert_eq!(usage.type_name, Some("module::inner::Type".to_string()));
     }
 
+    #[test]
+    fn test_operator_usages() {
+        let content = r#"
+fn main() {
+    let total = a + b;
+    v[0] -= -x;
+    if total != *limit { for p in points {} }
+    let y = read()?;
+}
+"#;
+        let usages = extract_trait_usages(&PathBuf::from("test.rs"), content);
+        let operators: HashSet<String> = usages
+            .iter()
+            .filter(|u| u.usage_kind == UsageKind::Operator)
+            .map(|u| format!("{}::{}", u.trait_name.as_deref().unwrap(), u.method_name))
+            .collect();
+
+        for used in [
+            "Add::add",
+            "SubAssign::sub_assign",
+            "Neg::neg",
+            "Index::index",
+            "IndexMut::index_mut",
+            "PartialEq::ne",
+            "PartialEq::eq",
+            "Deref::deref",
+            "Iterator::next",
+            "From::from",
+        ] {
+            assert!(operators.contains(used), "missing {}", used);
+        }
+        assert!(!operators.contains("Mul::mul"));
+        assert!(!operators.contains("PartialOrd::partial_cmp"));
+    }
+
     #[test]
     fn test_malformed_resilient() {
         let content = "fn main( { obj.broken }";
@@ -2,16 +2,190 @@
 //!
 //! Optimized for memory efficiency with pre-allocated buffers
 //! and the `std::fmt::Write` trait for clean string formatting.
+//!
+//! [`DotOptions`] keeps large graphs readable: modules grouped in one
+//! `subgraph cluster_*` per directory or crate, the layout direction, and a
+//! node budget that keeps dead modules and the best-connected live ones.
+
+use crate::graph::{inline_test_modules, module_crate, qualified_module_path};
+use crate::parse::{EdgeKind, ModuleInfo};
+use crate::theme::{Palette, Theme};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Write};
+use std::path::{Component, Path};
+use std::str::FromStr;
+
+/// Direction of the DOT layout (`rankdir`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RankDir {
+    /// Left to right
+    #[default]
+    LeftRight,
+    /// Top to bottom
+    TopBottom,
+    /// Right to left
+    RightLeft,
+    /// Bottom to top
+    BottomTop,
+}
+
+impl RankDir {
+    /// Every direction, in the order they are listed in help output.
+    pub const ALL: [RankDir; 4] = [
+        RankDir::LeftRight,
+        RankDir::TopBottom,
+        RankDir::RightLeft,
+        RankDir::BottomTop,
+    ];
+
+    /// Graphviz name, also used in flags.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LeftRight => "LR",
+            Self::TopBottom => "TB",
+            Self::RightLeft => "RL",
+            Self::BottomTop => "BT",
+        }
+    }
+}
+
+impl fmt::Display for RankDir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RankDir {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|r| r.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown rank direction '{}' (expected LR, TB, RL or BT)", s))
+    }
+}
+
+/// How DOT output groups modules into clusters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DotClusters {
+    /// No clusters
+    #[default]
+    None,
+    /// One cluster per source directory
+    Directory,
+    /// One cluster per workspace crate (none for a single crate)
+    Crate,
+}
+
+impl DotClusters {
+    /// Every grouping, in the order they are listed in help output.
+    pub const ALL: [DotClusters; 3] = [
+        DotClusters::None,
+        DotClusters::Directory,
+        DotClusters::Crate,
+    ];
+
+    /// Name used in flags.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Directory => "directory",
+            Self::Crate => "crate",
+        }
+    }
+}
 
-use crate::parse::ModuleInfo;
-use std::collections::{HashMap, HashSet};
-use std::fmt::Write;
+impl fmt::Display for DotClusters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DotClusters {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "unknown DOT clustering '{}' (expected none, directory or crate)",
+                    s
+                )
+            })
+    }
+}
+
+/// Shape of [`generate_dot_with_options`] output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DotOptions {
+    /// Grouping of modules into `subgraph cluster_*` blocks
+    pub clusters: DotClusters,
+    /// Layout direction
+    pub rankdir: RankDir,
+    /// Label edges with how the module is referenced (`mod,use`)
+    pub edge_labels: bool,
+    /// Most modules drawn; the rest are summarized in one note node
+    pub max_nodes: Option<usize>,
+    /// Node fill colors
+    pub theme: Theme,
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        Self {
+            clusters: DotClusters::None,
+            rankdir: RankDir::LeftRight,
+            edge_labels: true,
+            max_nodes: None,
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl DotOptions {
+    /// Group modules by `clusters`.
+    pub fn with_clusters(mut self, clusters: DotClusters) -> Self {
+        self.clusters = clusters;
+        self
+    }
+
+    /// Lay the graph out in direction `rankdir`.
+    pub fn with_rankdir(mut self, rankdir: RankDir) -> Self {
+        self.rankdir = rankdir;
+        self
+    }
+
+    /// Label edges with their reference kinds, or leave them bare.
+    pub fn with_edge_labels(mut self, enabled: bool) -> Self {
+        self.edge_labels = enabled;
+        self
+    }
+
+    /// Draw at most `max` modules.
+    pub fn with_max_nodes(mut self, max: usize) -> Self {
+        self.max_nodes = Some(max);
+        self
+    }
+
+    /// Fill nodes with `theme`'s colors.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
 
 /// Generate a Graphviz DOT representation of the module graph.
 ///
-/// - reachable modules are lightgreen
-/// - dead modules are lightcoral
-/// - edges represent "use" and "mod" dependencies
+/// - reachable modules are lightgreen (`#90EE90`)
+/// - dead modules are lightcoral (`#F08080`), with a dashed outline
+/// - inline `#[cfg(test)]` modules are lightblue (`#ADD8E6`), with a dashed
+///   edge from the module declaring them
+/// - edges represent the selected dependencies, labeled with how the
+///   module is referenced (`mod,use`) when known; references without a
+///   `mod` declaration are drawn dotted
 ///
 /// Performance optimizations:
 /// - Pre-allocated string buffer based on graph size heuristics
@@ -20,6 +194,16 @@ use std::fmt::Write;
 ///
 /// This is visually rich but simple enough for Graphviz to render on all platforms.
 pub fn generate_dot(mods: &HashMap<String, ModuleInfo>, reachable: &HashSet<String>) -> String {
+    generate_dot_with_options(mods, reachable, &DotOptions::default())
+}
+
+/// [`generate_dot`] with clusters, direction, node budget and colors from
+/// `options`. Modules and edges are written in name order.
+pub fn generate_dot_with_options(
+    mods: &HashMap<String, ModuleInfo>,
+    reachable: &HashSet<String>,
+    options: &DotOptions,
+) -> String {
     // Estimate capacity: ~80 bytes/node + ~40 bytes/edge + 150 bytes header/footer
     let node_count = mods.len();
     let edge_count: usize = mods.values().map(|info| info.refs.len()).sum();
@@ -28,7 +212,7 @@ pub fn generate_dot(mods: &HashMap<String, ModuleInfo>, reachable: &HashSet<Stri
     let mut dot = String::with_capacity(estimated_capacity);
 
     // Build DOT string using Write trait for efficient formatting
-    let result = write_dot_content(&mut dot, mods, reachable);
+    let result = write_dot_content(&mut dot, mods, reachable, options);
 
     // Handle unlikely write errors gracefully (NASA-grade resilience)
     if let Err(e) = result {
@@ -39,43 +223,230 @@ pub fn generate_dot(mods: &HashMap<String, ModuleInfo>, reachable: &HashSet<Stri
     dot
 }
 
+/// Modules drawn under a node budget: dead modules first, then the
+/// best-connected reachable ones, ties broken by name.
+fn shown_modules<'a>(
+    mods: &'a HashMap<String, ModuleInfo>,
+    reachable: &HashSet<String>,
+    max_nodes: Option<usize>,
+) -> Vec<&'a String> {
+    let mut names: Vec<&String> = mods.keys().collect();
+    names.sort_unstable();
+    let Some(max) = max_nodes.filter(|&max| max < names.len()) else {
+        return names;
+    };
+
+    let mut degree: HashMap<&str, usize> = HashMap::new();
+    for (name, info) in mods {
+        for dep in info.refs.iter().filter(|dep| mods.contains_key(*dep)) {
+            *degree.entry(name).or_default() += 1;
+            *degree.entry(dep).or_default() += 1;
+        }
+    }
+    let mut ranked = names;
+    ranked.sort_by_key(|name| {
+        let degree = degree.get(name.as_str()).copied().unwrap_or(0);
+        (reachable.contains(*name), std::cmp::Reverse(degree))
+    });
+    ranked.truncate(max);
+    ranked.sort_unstable();
+    ranked
+}
+
+/// Cluster of the module `info`, as (key, label), if any.
+fn cluster_of(info: &ModuleInfo, clusters: DotClusters) -> Option<(String, String)> {
+    match clusters {
+        DotClusters::None => None,
+        DotClusters::Crate => {
+            module_crate(info).map(|krate| (krate.to_string(), krate.to_string()))
+        }
+        DotClusters::Directory => {
+            let dir = info.path.parent()?;
+            let label = directory_label(dir);
+            let label = match module_crate(info) {
+                Some(krate) => format!("{}/{}", krate, label),
+                None => label,
+            };
+            Some((dir.display().to_string(), label))
+        }
+    }
+}
+
+/// `dir` from its last `src` component on (`src/net`), or its name.
+fn directory_label(dir: &Path) -> String {
+    let parts: Vec<String> = dir
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    match parts.iter().rposition(|part| part == "src") {
+        Some(at) => parts[at..].join("/"),
+        None => parts.last().cloned().unwrap_or_else(|| ".".to_string()),
+    }
+}
+
+/// Escape `s` for a double-quoted DOT string.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// DOT id of the "+N more modules" node: not a module path, and made
+/// unique should a module key still match it.
+fn omitted_node_id(mods: &HashMap<String, ModuleInfo>) -> String {
+    let mut id = String::from("__deadmod_omitted__");
+    while mods.contains_key(&id) {
+        id.push('_');
+    }
+    id
+}
+
 /// Internal function to write DOT content using the Write trait.
 fn write_dot_content(
     dot: &mut String,
     mods: &HashMap<String, ModuleInfo>,
     reachable: &HashSet<String>,
+    options: &DotOptions,
 ) -> std::fmt::Result {
+    let palette: &Palette = options.theme.palette();
+
     // Graph header
     writeln!(dot, "digraph deadmod {{")?;
-    writeln!(dot, "  rankdir=LR;")?;
+    writeln!(dot, "  rankdir={};", options.rankdir)?;
     writeln!(
         dot,
         "  node [shape=box, style=filled, fontname=\"JetBrains Mono\"];"
     )?;
     writeln!(dot)?;
 
-    // 1. NODES: Determine color based on reachability
-    for name in mods.keys() {
-        let color = if reachable.contains(name) {
-            "lightgreen" // Reachable module
+    let shown = shown_modules(mods, reachable, options.max_nodes);
+    let shown_set: HashSet<&str> = shown.iter().map(|name| name.as_str()).collect();
+    let test_modules: Vec<_> = inline_test_modules(mods)
+        .into_iter()
+        .filter(|test| shown_set.contains(test.parent))
+        .collect();
+
+    // 1. NODES: Determine color based on reachability, grouped by cluster
+    let mut statements: BTreeMap<Option<(String, String)>, Vec<String>> = BTreeMap::new();
+    for name in shown.iter().copied() {
+        let info = &mods[name];
+        let statement = if reachable.contains(name) {
+            // Reachable module
+            format!(
+                "\"{}\" [label=\"{}\", fillcolor=\"{}\"];",
+                escape(name),
+                escape(&qualified_module_path(name, info)),
+                palette.alive
+            )
         } else {
-            "lightcoral" // Dead module
+            // Dead module
+            format!(
+                "\"{}\" [label=\"{}\", fillcolor=\"{}\", style=\"filled,dashed\", color=\"{}\", penwidth=2];",
+                escape(name),
+                escape(&qualified_module_path(name, info)),
+                palette.dead,
+                palette.dead_stroke
+            )
+        };
+        statements
+            .entry(cluster_of(info, options.clusters))
+            .or_default()
+            .push(statement);
+    }
+    for test in &test_modules {
+        let statement = format!(
+            "\"{}\" [label=\"{}\", fillcolor=\"{}\"];",
+            escape(&test.key),
+            escape(&test.module_path),
+            palette.test
+        );
+        let cluster = cluster_of(&mods[test.parent], options.clusters);
+        statements.entry(cluster).or_default().push(statement);
+    }
+
+    // Clusters first; the unclustered group (`None`) sorts first in the
+    // map, so it is skipped here and written after them
+    for (i, (cluster, nodes)) in statements
+        .iter()
+        .filter(|(cluster, _)| cluster.is_some())
+        .enumerate()
+    {
+        let Some((_, label)) = cluster else {
+            continue;
         };
-        writeln!(dot, "  \"{}\" [fillcolor={}];", name, color)?;
+        writeln!(dot, "  subgraph \"cluster_{}\" {{", i)?;
+        writeln!(dot, "    label=\"{}\";", escape(label))?;
+        writeln!(dot, "    style=\"rounded,dashed\";")?;
+        for node in nodes {
+            writeln!(dot, "    {}", node)?;
+        }
+        writeln!(dot, "  }}")?;
+    }
+    for node in statements.get(&None).into_iter().flatten() {
+        writeln!(dot, "  {}", node)?;
+    }
+
+    let omitted = mods.len() - shown.len();
+    if omitted > 0 {
+        writeln!(
+            dot,
+            "  \"{}\" [label=\"+{} more modules\", shape=note, style=dashed];",
+            escape(&omitted_node_id(mods)),
+            omitted
+        )?;
     }
 
     writeln!(dot)?;
 
     // 2. EDGES: Draw dependencies
-    for (name, info) in mods {
-        for dep in &info.refs {
-            // Only draw edges to modules that exist in our graph
-            if mods.contains_key(dep) {
-                writeln!(dot, "  \"{}\" -> \"{}\";", name, dep)?;
+    for name in shown.iter().copied() {
+        let mut deps: Vec<&String> = mods[name].refs.iter().collect();
+        deps.sort_unstable();
+        for dep in deps {
+            // Only draw edges to modules that are drawn
+            if !shown_set.contains(dep.as_str()) {
+                continue;
+            }
+            let mut attrs = Vec::new();
+            match mods[name].ref_kinds.get(dep) {
+                Some(kinds) if !kinds.is_empty() => {
+                    if options.edge_labels {
+                        let label: Vec<&str> = kinds.iter().map(|k| k.as_str()).collect();
+                        attrs.push(format!("label=\"{}\"", label.join(",")));
+                    }
+                    if !kinds.contains(&EdgeKind::Mod) {
+                        attrs.push("style=dotted".to_string());
+                    }
+                }
+                _ => {}
+            }
+            if attrs.is_empty() {
+                writeln!(dot, "  \"{}\" -> \"{}\";", escape(name), escape(dep))?;
+            } else {
+                writeln!(
+                    dot,
+                    "  \"{}\" -> \"{}\" [{}];",
+                    escape(name),
+                    escape(dep),
+                    attrs.join(", ")
+                )?;
             }
         }
     }
 
+    for test in &test_modules {
+        writeln!(
+            dot,
+            "  \"{}\" -> \"{}\" [style=dashed];",
+            escape(test.parent),
+            escape(&test.key)
+        )?;
+    }
+
     writeln!(dot, "}}")?;
     Ok(())
 }
@@ -99,6 +470,9 @@ mod tests {
         let mut mods = HashMap::new();
         let mut main_info = ModuleInfo::new(PathBuf::from("src/main.rs"));
         main_info.refs.insert("utils".to_string());
+        main_info
+            .ref_kinds
+            .insert("utils".to_string(), [EdgeKind::Mod, EdgeKind::Use].into());
         mods.insert("main".to_string(), main_info);
         mods.insert(
             "utils".to_string(),
@@ -121,11 +495,25 @@ mod tests {
         assert!(dot.contains("\"dead\""));
 
         // Check edge exists
-        assert!(dot.contains("\"main\" -> \"utils\""));
+        assert!(dot.contains("\"main\" -> \"utils\" [label=\"mod,use\"];"));
+
+        // Nodes are labelled by module path
+        assert!(dot.contains("\"main\" [label=\"crate\""));
 
         // Check colors
-        assert!(dot.contains("lightgreen")); // for reachable
-        assert!(dot.contains("lightcoral")); // for dead
+        assert!(dot.contains("fillcolor=\"#90EE90\"")); // for reachable
+        assert!(dot.contains("fillcolor=\"#F08080\"")); // for dead
+
+        let accessible = generate_dot_with_options(
+            &mods,
+            &reachable,
+            &DotOptions::default().with_theme(Theme::ColorblindSafe),
+        );
+        assert!(
+            accessible.contains("fillcolor=\"#56B4E9\"")
+                && accessible.contains("fillcolor=\"#E69F00\"")
+        );
+        assert!(!accessible.contains("#F08080"));
     }
 
     #[test]
@@ -135,4 +523,83 @@ mod tests {
         let dot = generate_dot(&mods, &reachable);
         assert!(dot.contains("JetBrains Mono"));
     }
+
+    #[test]
+    fn test_generate_dot_options() {
+        let mut mods = HashMap::new();
+        let mut lib = ModuleInfo::new(PathBuf::from("a/src/lib.rs"));
+        lib.refs
+            .extend(["a::net".to_string(), "a::net::tcp".to_string()]);
+        lib.ref_kinds
+            .insert("a::net".to_string(), [EdgeKind::Mod].into());
+        lib.ref_kinds
+            .insert("a::net::tcp".to_string(), [EdgeKind::Path].into());
+        mods.insert("a::lib".to_string(), lib);
+        mods.insert(
+            "a::net".to_string(),
+            ModuleInfo::new(PathBuf::from("a/src/net.rs")),
+        );
+        mods.insert(
+            "a::net::tcp".to_string(),
+            ModuleInfo::new(PathBuf::from("a/src/net/tcp.rs")),
+        );
+        mods.insert(
+            "b::lib".to_string(),
+            ModuleInfo::new(PathBuf::from("b/src/lib.rs")),
+        );
+        mods.insert(
+            "b::old\"x".to_string(),
+            ModuleInfo::new(PathBuf::from("b/src/old.rs")),
+        );
+        // Workspace-combined modules carry their crate
+        for (key, info) in &mut mods {
+            info.krate = key.split_once("::").map(|(krate, _)| krate.to_string());
+        }
+        let reachable: HashSet<String> = ["a::lib", "a::net", "a::net::tcp", "b::lib"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let options = DotOptions::default()
+            .with_clusters(DotClusters::Directory)
+            .with_rankdir(RankDir::TopBottom);
+        let dot = generate_dot_with_options(&mods, &reachable, &options);
+        assert!(dot.contains("rankdir=TB;"));
+        assert_eq!(dot.matches("subgraph").count(), 3);
+        assert!(dot.contains("label=\"a/src\";") && dot.contains("label=\"a/src/net\";"));
+        // Names are escaped; dead modules are outlined
+        assert!(dot.contains(r#""b::old\"x" [label="#));
+        assert!(dot.contains("style=\"filled,dashed\", color=\"#cc4444\""));
+        // Edges without a `mod` declaration are dotted
+        assert!(dot.contains(r#""a::lib" -> "a::net" [label="mod"];"#));
+        assert!(dot.contains(r#""a::lib" -> "a::net::tcp" [label="path", style=dotted];"#));
+        assert_eq!(dot, generate_dot_with_options(&mods, &reachable, &options));
+
+        let crates = generate_dot_with_options(
+            &mods,
+            &reachable,
+            &DotOptions::default()
+                .with_clusters(DotClusters::Crate)
+                .with_edge_labels(false),
+        );
+        assert_eq!(crates.matches("subgraph").count(), 2);
+        assert!(crates.contains(r#""a::lib" -> "a::net";"#));
+
+        // The dead module and the best-connected live one are kept
+        let capped =
+            generate_dot_with_options(&mods, &reachable, &DotOptions::default().with_max_nodes(2));
+        assert!(capped.contains("\"a::lib\" [") && capped.contains("\"b::old\\\"x\" ["));
+        assert!(!capped.contains("\"a::net\" [") && !capped.contains(" -> "));
+        assert!(capped.contains(r#""__deadmod_omitted__" [label="+3 more modules""#));
+        mods.insert(
+            "__deadmod_omitted__".to_string(),
+            ModuleInfo::new(PathBuf::from("c/src/lib.rs")),
+        );
+        assert_eq!(omitted_node_id(&mods), "__deadmod_omitted___");
+        assert_eq!(escape("a\r\n\"b\""), r#"a\r\n\"b\""#);
+
+        assert_eq!("tb".parse::<RankDir>(), Ok(RankDir::TopBottom));
+        assert_eq!("Crate".parse::<DotClusters>(), Ok(DotClusters::Crate));
+        assert!("up".parse::<RankDir>().is_err());
+    }
 }
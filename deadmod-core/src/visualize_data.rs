@@ -0,0 +1,106 @@
+//! Graph data embedded in the HTML and PixiJS visualizers.
+//!
+//! Both viewers inline their nodes, edges and clusters as JavaScript
+//! literals. The data is built from these typed structs and serialized with
+//! `serde_json`, then passed through [`script_json`] so that module names and
+//! paths holding quotes, control characters or `</script>` stay data and can
+//! neither break the page nor inject markup.
+
+use serde::Serialize;
+
+use crate::common::Confidence;
+use crate::graph::metrics::ModuleMetrics;
+
+/// A module or function node.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GraphNode<'a> {
+    /// Module key or full function path, referenced by edges
+    pub id: &'a str,
+    /// Short name drawn next to the node
+    pub label: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module_path: Option<&'a str>,
+    /// Fill color (HTML viewer only; the PixiJS viewer colors by status)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<&'a str>,
+    /// `reachable` or `dead`
+    pub status: &'a str,
+    pub path: &'a str,
+    pub cluster: &'a str,
+    /// Top-level directory, for the PixiJS viewer's nested clusters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_cluster: Option<&'a str>,
+    /// Workspace crate, or `null` outside workspace mode
+    #[serde(rename = "crate")]
+    pub krate: Option<&'a str>,
+    pub ref_count: usize,
+    pub inbound_count: usize,
+    pub visibility: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<&'a ModuleMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Confidence>,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A dependency or call between two nodes, by id.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GraphEdge<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    /// Whether the edge leaves its workspace crate (module graphs only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cross_crate: Option<bool>,
+}
+
+/// A cluster of nodes, with the index its color is picked by.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GraphCluster<'a> {
+    pub id: &'a str,
+    pub index: usize,
+}
+
+/// Serialize `value` as a JavaScript literal safe inside an inline
+/// `<script>`: `<`, `>` and `&` are written as `\u` escapes, which keeps
+/// `</script>` and `<!--` out of the page, as are the line separators
+/// JavaScript once rejected in string literals.
+pub(crate) fn script_json<T: Serialize + ?Sized>(value: &T) -> String {
+    let json = serde_json::to_string(value).expect("graph data serializes to JSON");
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c {
+            '<' => escaped.push_str("\\u003c"),
+            '>' => escaped.push_str("\\u003e"),
+            '&' => escaped.push_str("\\u0026"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_json_escapes() {
+        let edge = GraphEdge {
+            from: "a\"\n\u{1}",
+            to: "</script><b>&\u{2028}",
+            cross_crate: None,
+        };
+        let json = script_json(&[edge]);
+
+        assert_eq!(
+            json,
+            r#"[{"from":"a\"\n\u0001","to":"\u003c/script\u003e\u003cb\u003e\u0026\u2028"}]"#
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0]["to"], "</script><b>&\u{2028}");
+    }
+}
@@ -6,21 +6,72 @@
 //! Performance characteristics:
 //! - Pre-allocated buffers based on graph size heuristics
 //! - Single-pass iteration over nodes and edges
-//! - Minimal string allocations via capacity estimation
+//! - Graph data serialized once, escaped for the inline script
 //! - Barnes-Hut optimization for O(n log n) force simulation
 //!
 //! Features:
 //! - Offline capable (no CDN dependencies)
 //! - Color-coded nodes (green = reachable, red = dead)
 //! - Module clustering with gravity
+//! - Collapsible crate super-clusters in workspace mode
 //! - Edge bundling with Bézier curves
 //! - Inspector panel with module stats
 //! - Zoom, pan, drag interactions
 //! - Dark theme optimized for developers
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
+use crate::graph::metrics::module_metrics;
+use crate::graph::{module_crate, qualified_module_path};
+use crate::layout::{layout_graph, LayoutOptions};
 use crate::parse::ModuleInfo;
+use crate::theme::Theme;
+use crate::visualize_data::{script_json, GraphCluster, GraphEdge, GraphNode};
+
+#[cfg(feature = "callgraph")]
+use crate::callgraph::VisualizerGraph;
+
+/// Labels for the kind of node a rendered page shows.
+struct Subject {
+    /// Page title after "Deadmod - "
+    title: &'static str,
+    /// Capitalized singular, used for headings
+    heading: &'static str,
+    /// Lowercase singular, used in running text
+    noun: &'static str,
+    /// Whether dead nodes are whole files that can be deleted
+    removable: bool,
+}
+
+const MODULES: Subject = Subject {
+    title: "Module Dependency Graph",
+    heading: "Module",
+    noun: "module",
+    removable: true,
+};
+
+#[cfg(feature = "callgraph")]
+const FUNCTIONS: Subject = Subject {
+    title: "Function Call Graph",
+    heading: "Function",
+    noun: "function",
+    removable: false,
+};
+
+/// Serialized graph data and header stats for [`render_page`].
+struct PageData {
+    total: usize,
+    reachable_count: usize,
+    dead_count: usize,
+    cluster_count: usize,
+    nodes_json: String,
+    edges_json: String,
+    clusters_json: String,
+    crates_json: String,
+    /// Positions are final: draw without simulating
+    static_layout: bool,
+    theme: Theme,
+}
 
 /// Generate an interactive HTML visualization of the module graph.
 ///
@@ -31,16 +82,36 @@ use crate::parse::ModuleInfo;
 ///
 /// - reachable modules: green
 /// - dead modules: red
+///
+/// Node tooltips include the module's coupling metrics (see
+/// [`crate::graph::metrics`]).
 pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashSet<String>) -> String {
+    generate_html_graph_with_layout(mods, reachable, &LayoutOptions::default(), Theme::default())
+}
+
+/// [`generate_html_graph`] with positions from `layout`, colored by
+/// `theme`.
+///
+/// Modules, edges and clusters are written in name order, so the same
+/// graph and seed always produce the same page. With a static layout the
+/// page draws the precomputed positions and never simulates.
+pub fn generate_html_graph_with_layout(
+    mods: &HashMap<String, ModuleInfo>,
+    reachable: &HashSet<String>,
+    layout: &LayoutOptions,
+    theme: Theme,
+) -> String {
     // Estimate edge count for capacity pre-allocation
     let edge_count: usize = mods.values().map(|info| info.refs.len()).sum();
 
-    // Pre-allocate with capacity (~100 bytes per node, ~50 bytes per edge)
-    let mut nodes = Vec::with_capacity(mods.len());
-    let mut edges = Vec::with_capacity(edge_count);
-
     // Collect unique parent modules for clustering
-    let mut clusters: HashSet<String> = HashSet::new();
+    let mut clusters: BTreeSet<&str> = BTreeSet::new();
+    // Workspace crates become super-clusters around their modules
+    let mut crates: Vec<&str> = mods.values().filter_map(module_crate).collect();
+    crates.sort_unstable();
+    crates.dedup();
+
+    let metrics = module_metrics(mods);
 
     // Build inbound reference counts
     let mut inbound_counts: HashMap<String, usize> = HashMap::new();
@@ -50,91 +121,262 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         }
     }
 
-    // Build nodes JSON with pre-allocated string
-    for (name, info) in mods {
-        let color = if reachable.contains(name) {
-            "#90EE90" // lightgreen
-        } else {
-            "#F08080" // lightcoral
-        };
+    let mut names: Vec<&String> = mods.keys().collect();
+    names.sort_unstable();
 
-        let status = if reachable.contains(name) {
-            "reachable"
+    // Extract parent module for clustering (scoped by crate in workspace mode)
+    let node_clusters: Vec<String> = names
+        .iter()
+        .map(|name| {
+            let parent = extract_parent_module(&mods[*name].path.display().to_string());
+            match module_crate(&mods[*name]) {
+                Some(krate) => format!("{}::{}", krate, parent),
+                None => parent,
+            }
+        })
+        .collect();
+    let index: HashMap<&String, usize> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (*name, i))
+        .collect();
+    let index_edges: Vec<(usize, usize)> = names
+        .iter()
+        .enumerate()
+        .flat_map(|(i, name)| {
+            mods[*name]
+                .refs
+                .iter()
+                .filter_map(|r| index.get(r))
+                .map(move |&j| (i, j))
+        })
+        .collect();
+    let positions = layout_graph(
+        &node_clusters.iter().map(String::as_str).collect::<Vec<_>>(),
+        &index_edges,
+        layout,
+    );
+
+    // Strings the nodes borrow
+    let module_paths: Vec<String> = names
+        .iter()
+        .map(|name| qualified_module_path(name, &mods[*name]))
+        .collect();
+    let paths: Vec<String> = names
+        .iter()
+        .map(|name| mods[*name].path.display().to_string())
+        .collect();
+    let visibilities: Vec<String> = names
+        .iter()
+        .map(|name| format!("{:?}", mods[*name].visibility).to_lowercase())
+        .collect();
+
+    let palette = theme.palette();
+    let mut nodes: Vec<GraphNode> = Vec::with_capacity(mods.len());
+    for (i, name) in names.iter().copied().enumerate() {
+        let info = &mods[name];
+        let (color, status) = if reachable.contains(name) {
+            (palette.alive, "reachable")
         } else {
-            "dead"
+            (palette.dead, "dead")
         };
 
-        // Extract parent module for clustering
-        let cluster = extract_parent_module(&info.path.display().to_string());
-        clusters.insert(cluster.clone());
-
-        // Escape for JSON - strip Windows extended-length path prefix
-        let path_str = info.path.display().to_string();
-        let path_clean = path_str.strip_prefix(r"\\?\").unwrap_or(&path_str);
-        let path_escaped = path_clean.replace('\\', "\\\\").replace('"', "\\\"");
-
-        // Module metadata
-        let ref_count = info.refs.len();
-        let inbound_count = inbound_counts.get(name).copied().unwrap_or(0);
-        let visibility = format!("{:?}", info.visibility).to_lowercase();
-
-        nodes.push(format!(
-            r#"{{ "id": "{}", "label": "{}", "color": "{}", "status": "{}", "path": "{}", "cluster": "{}", "refCount": {}, "inboundCount": {}, "visibility": "{}" }}"#,
-            name, name, color, status, path_escaped, cluster, ref_count, inbound_count, visibility
-        ));
+        let cluster = &node_clusters[i];
+        clusters.insert(cluster.as_str());
+
+        nodes.push(GraphNode {
+            id: name,
+            label: module_paths[i]
+                .rsplit("::")
+                .next()
+                .unwrap_or(&module_paths[i]),
+            module_path: Some(&module_paths[i]),
+            color: Some(color),
+            status,
+            // Strip Windows extended-length path prefix
+            path: paths[i].strip_prefix(r"\\?\").unwrap_or(&paths[i]),
+            cluster,
+            top_cluster: None,
+            krate: module_crate(info),
+            ref_count: info.refs.len(),
+            inbound_count: inbound_counts.get(name).copied().unwrap_or(0),
+            visibility: visibilities[i].as_str(),
+            metrics: metrics.get(name),
+            confidence: None,
+            x: positions[i].x,
+            y: positions[i].y,
+        });
     }
 
-    // Build edges JSON
-    for (src, info) in mods {
-        for dst in &info.refs {
+    let mut edges: Vec<GraphEdge> = Vec::with_capacity(edge_count);
+    for src in names.iter().copied() {
+        let mut refs: Vec<&String> = mods[src].refs.iter().collect();
+        refs.sort_unstable();
+        for dst in refs {
             if mods.contains_key(dst) {
-                edges.push(format!(r#"{{ "from": "{}", "to": "{}" }}"#, src, dst));
+                let cross_crate = module_crate(&mods[src]) != module_crate(&mods[dst]);
+                edges.push(GraphEdge {
+                    from: src,
+                    to: dst,
+                    cross_crate: Some(cross_crate),
+                });
             }
         }
     }
 
-    // Build clusters JSON
-    let clusters_json: String = clusters
-        .iter()
+    let clusters: Vec<GraphCluster> = clusters
+        .into_iter()
         .enumerate()
-        .map(|(i, c)| format!(r#"{{ "id": "{}", "index": {} }}"#, c, i))
-        .collect::<Vec<_>>()
-        .join(",\n    ");
+        .map(|(index, id)| GraphCluster { id, index })
+        .collect();
 
-    // Pre-allocate JSON arrays with capacity
-    let nodes_capacity = nodes.iter().map(|s| s.len()).sum::<usize>() + nodes.len() * 6 + 2;
-    let edges_capacity = edges.iter().map(|s| s.len()).sum::<usize>() + edges.len() * 6 + 2;
+    // Count stats
+    let total = mods.len();
+    let dead_count = mods.keys().filter(|k| !reachable.contains(*k)).count();
+    let reachable_count = total - dead_count;
 
-    let mut nodes_json = String::with_capacity(nodes_capacity);
-    let mut edges_json = String::with_capacity(edges_capacity);
+    render_page(
+        &MODULES,
+        PageData {
+            total,
+            reachable_count,
+            dead_count,
+            cluster_count: clusters.len(),
+            nodes_json: script_json(&nodes),
+            edges_json: script_json(&edges),
+            clusters_json: script_json(&clusters),
+            crates_json: script_json(&crates),
+            static_layout: layout.static_layout,
+            theme,
+        },
+    )
+}
 
-    nodes_json.push('[');
-    for (i, node) in nodes.iter().enumerate() {
-        if i > 0 { nodes_json.push_str(",\n    "); }
-        nodes_json.push_str(node);
-    }
-    nodes_json.push(']');
+/// Generate an interactive HTML visualization of a function call graph.
+///
+/// Same viewer as [`generate_html_graph`], with one node per function
+/// clustered by module and colored by reachability.
+#[cfg(feature = "callgraph")]
+pub fn generate_html_callgraph(graph: &VisualizerGraph) -> String {
+    generate_html_callgraph_with_layout(graph, &LayoutOptions::default(), Theme::default())
+}
 
-    edges_json.push('[');
-    for (i, edge) in edges.iter().enumerate() {
-        if i > 0 { edges_json.push_str(",\n    "); }
-        edges_json.push_str(edge);
+/// [`generate_html_callgraph`] with positions from `layout`, colored by
+/// `theme`.
+#[cfg(feature = "callgraph")]
+pub fn generate_html_callgraph_with_layout(
+    graph: &VisualizerGraph,
+    layout: &LayoutOptions,
+    theme: Theme,
+) -> String {
+    let clusters: Vec<&str> = graph
+        .nodes
+        .iter()
+        .map(|node| node.module.as_str())
+        .collect();
+    let edges: Vec<(usize, usize)> = graph
+        .edges
+        .iter()
+        .map(|edge| (edge.from, edge.to))
+        .collect();
+    let positions = layout_graph(&clusters, &edges, layout);
+    let palette = theme.palette();
+
+    let mut ref_counts = vec![0usize; graph.nodes.len()];
+    let mut inbound_counts = vec![0usize; graph.nodes.len()];
+    for edge in &graph.edges {
+        ref_counts[edge.from] += 1;
+        inbound_counts[edge.to] += 1;
     }
-    edges_json.push(']');
 
-    // Count stats
-    let total = mods.len();
-    let dead_count = mods.keys().filter(|k| !reachable.contains(*k)).count();
-    let reachable_count = total - dead_count;
+    let nodes: Vec<GraphNode> = graph
+        .nodes
+        .iter()
+        .zip(&positions)
+        .map(|(node, position)| {
+            let (color, status) = if node.dead {
+                (palette.dead, "dead")
+            } else {
+                (palette.alive, "reachable")
+            };
+            let path = node.file.strip_prefix(r"\\?\").unwrap_or(&node.file);
+            let visibility = if node.visibility.starts_with("pub") {
+                "public"
+            } else {
+                "private"
+            };
+
+            GraphNode {
+                id: &node.full_path,
+                label: &node.name,
+                module_path: None,
+                color: Some(color),
+                status,
+                path,
+                cluster: &node.module,
+                top_cluster: None,
+                krate: None,
+                ref_count: ref_counts[node.id],
+                inbound_count: inbound_counts[node.id],
+                visibility,
+                metrics: None,
+                confidence: node.confidence,
+                x: position.x,
+                y: position.y,
+            }
+        })
+        .collect();
 
+    let edges: Vec<GraphEdge> = graph
+        .edges
+        .iter()
+        .map(|edge| GraphEdge {
+            from: &graph.nodes[edge.from].full_path,
+            to: &graph.nodes[edge.to].full_path,
+            cross_crate: None,
+        })
+        .collect();
+
+    let clusters: Vec<GraphCluster> = graph
+        .modules
+        .iter()
+        .enumerate()
+        .map(|(index, id)| GraphCluster { id, index })
+        .collect();
+
+    let total = graph.stats.total_functions;
+    let dead_count = graph.stats.dead_functions;
+
+    render_page(
+        &FUNCTIONS,
+        PageData {
+            total,
+            reachable_count: total - dead_count,
+            dead_count,
+            cluster_count: graph.modules.len(),
+            nodes_json: script_json(&nodes),
+            edges_json: script_json(&edges),
+            clusters_json: script_json(&clusters),
+            crates_json: "[]".to_string(),
+            static_layout: layout.static_layout,
+            theme,
+        },
+    )
+}
+
+/// Fill the viewer template with graph data.
+fn render_page(subject: &Subject, data: PageData) -> String {
     format!(
         r##"<!DOCTYPE html>
 <html lang="en">
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Deadmod - Module Dependency Graph</title>
+    <title>Deadmod - {title}</title>
     <style>
+        :root {{
+            {theme_css}
+        }}
         * {{
             margin: 0;
             padding: 0;
@@ -142,8 +384,8 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         }}
         body {{
             font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
-            background: #1a1a2e;
-            color: #eee;
+            background: var(--background);
+            color: var(--text);
             overflow: hidden;
         }}
         #header {{
@@ -152,8 +394,8 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             left: 0;
             right: 0;
             height: 50px;
-            background: #16213e;
-            border-bottom: 1px solid #0f3460;
+            background: var(--surface);
+            border-bottom: 1px solid var(--border);
             display: flex;
             align-items: center;
             padding: 0 20px;
@@ -163,18 +405,18 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         #header h1 {{
             font-size: 18px;
             font-weight: 600;
-            color: #e94560;
+            color: var(--accent);
         }}
         .stat {{
             font-size: 13px;
-            color: #aaa;
+            color: var(--muted);
         }}
         .stat-value {{
             font-weight: bold;
             margin-left: 5px;
         }}
-        .stat-value.green {{ color: #90EE90; }}
-        .stat-value.red {{ color: #F08080; }}
+        .stat-value.green {{ color: var(--alive); }}
+        .stat-value.red {{ color: var(--dead); }}
         #canvas-container {{
             position: fixed;
             top: 50px;
@@ -187,8 +429,8 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         }}
         #tooltip {{
             position: fixed;
-            background: #16213e;
-            border: 1px solid #0f3460;
+            background: var(--surface);
+            border: 1px solid var(--border);
             border-radius: 8px;
             padding: 12px 16px;
             font-size: 13px;
@@ -204,7 +446,7 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         }}
         #tooltip h3 {{
             margin-bottom: 8px;
-            color: #e94560;
+            color: var(--accent);
             font-size: 15px;
         }}
         #tooltip .status {{
@@ -217,22 +459,22 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             margin-bottom: 8px;
         }}
         #tooltip .status.reachable {{
-            background: rgba(144, 238, 144, 0.2);
-            color: #90EE90;
+            background: color-mix(in srgb, var(--alive) 20%, transparent);
+            color: var(--alive);
         }}
         #tooltip .status.dead {{
-            background: rgba(240, 128, 128, 0.2);
-            color: #F08080;
+            background: color-mix(in srgb, var(--dead) 20%, transparent);
+            color: var(--dead);
         }}
         #tooltip .path {{
-            color: #888;
+            color: var(--muted);
             font-family: 'Consolas', monospace;
             font-size: 11px;
             word-break: break-all;
         }}
         #tooltip .refs {{
             margin-top: 8px;
-            color: #aaa;
+            color: var(--muted);
         }}
         #controls {{
             position: fixed;
@@ -248,24 +490,24 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             height: 40px;
             border: none;
             border-radius: 8px;
-            background: #16213e;
-            color: #eee;
+            background: var(--surface);
+            color: var(--text);
             font-size: 18px;
             cursor: pointer;
             transition: background 0.2s;
         }}
         #controls button:hover {{
-            background: #0f3460;
+            background: var(--border);
         }}
         #controls button.active {{
-            background: #e94560;
+            background: var(--accent);
         }}
         #legend {{
             position: fixed;
             bottom: 80px;
             left: 20px;
-            background: #16213e;
-            border: 1px solid #0f3460;
+            background: var(--surface);
+            border: 1px solid var(--border);
             border-radius: 8px;
             padding: 12px 16px;
             font-size: 12px;
@@ -273,7 +515,7 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         }}
         #legend h4 {{
             margin-bottom: 8px;
-            color: #e94560;
+            color: var(--accent);
         }}
         .legend-item {{
             display: flex;
@@ -293,24 +535,24 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             right: 0;
             width: 300px;
             bottom: 0;
-            background: #16213e;
-            border-left: 1px solid #0f3460;
+            background: var(--surface);
+            border-left: 1px solid var(--border);
             padding: 20px;
             overflow-y: auto;
             z-index: 1000;
         }}
         #inspector h2 {{
-            color: #e94560;
+            color: var(--accent);
             font-size: 16px;
             margin-bottom: 15px;
             padding-bottom: 10px;
-            border-bottom: 1px solid #0f3460;
+            border-bottom: 1px solid var(--border);
         }}
         #inspector .section {{
             margin-bottom: 20px;
         }}
         #inspector .section h3 {{
-            color: #aaa;
+            color: var(--muted);
             font-size: 11px;
             text-transform: uppercase;
             letter-spacing: 1px;
@@ -318,34 +560,34 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         }}
         #inspector .value {{
             font-size: 14px;
-            color: #fff;
+            color: var(--text-strong);
             margin-bottom: 5px;
         }}
         #inspector .stat-row {{
             display: flex;
             justify-content: space-between;
             padding: 8px 0;
-            border-bottom: 1px solid #0f3460;
+            border-bottom: 1px solid var(--border);
         }}
         #inspector .stat-label {{
-            color: #888;
+            color: var(--muted);
         }}
         #inspector .stat-num {{
             font-weight: bold;
         }}
-        #inspector .stat-num.green {{ color: #90EE90; }}
-        #inspector .stat-num.red {{ color: #F08080; }}
+        #inspector .stat-num.green {{ color: var(--alive); }}
+        #inspector .stat-num.red {{ color: var(--dead); }}
         #inspector .cluster-tag {{
             display: inline-block;
             padding: 4px 10px;
-            background: rgba(233, 69, 96, 0.2);
-            color: #e94560;
+            background: color-mix(in srgb, var(--accent) 20%, transparent);
+            color: var(--accent);
             border-radius: 4px;
             font-size: 12px;
             margin-top: 5px;
         }}
         #inspector .empty {{
-            color: #666;
+            color: var(--faint);
             font-style: italic;
         }}
         #inspector .dep-list {{
@@ -355,16 +597,16 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         #inspector .dep-item {{
             padding: 4px 8px;
             margin: 2px 0;
-            background: rgba(255,255,255,0.05);
+            background: color-mix(in srgb, var(--text) 5%, transparent);
             border-radius: 4px;
             font-size: 12px;
             cursor: pointer;
         }}
         #inspector .dep-item:hover {{
-            background: rgba(233, 69, 96, 0.2);
+            background: color-mix(in srgb, var(--accent) 20%, transparent);
         }}
         #inspector .dep-item.dead {{
-            color: #F08080;
+            color: var(--dead);
         }}
         /* Action Buttons */
         #inspector .actions {{
@@ -378,40 +620,40 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             align-items: center;
             gap: 8px;
             padding: 10px 14px;
-            background: rgba(233, 69, 96, 0.15);
-            border: 1px solid rgba(233, 69, 96, 0.3);
+            background: color-mix(in srgb, var(--accent) 15%, transparent);
+            border: 1px solid color-mix(in srgb, var(--accent) 30%, transparent);
             border-radius: 6px;
-            color: #e94560;
+            color: var(--accent);
             font-size: 12px;
             cursor: pointer;
             transition: all 0.2s;
         }}
         #inspector .action-btn:hover {{
-            background: rgba(233, 69, 96, 0.25);
-            border-color: rgba(233, 69, 96, 0.5);
+            background: color-mix(in srgb, var(--accent) 25%, transparent);
+            border-color: color-mix(in srgb, var(--accent) 50%, transparent);
         }}
         #inspector .action-btn.success {{
-            background: rgba(144, 238, 144, 0.15);
-            border-color: rgba(144, 238, 144, 0.3);
-            color: #90EE90;
+            background: color-mix(in srgb, var(--alive) 15%, transparent);
+            border-color: color-mix(in srgb, var(--alive) 30%, transparent);
+            color: var(--alive);
         }}
         #inspector .action-btn.danger {{
-            background: rgba(240, 128, 128, 0.15);
-            border-color: rgba(240, 128, 128, 0.3);
-            color: #F08080;
+            background: color-mix(in srgb, var(--dead) 15%, transparent);
+            border-color: color-mix(in srgb, var(--dead) 30%, transparent);
+            color: var(--dead);
         }}
         #inspector .action-btn .icon {{
             font-size: 14px;
         }}
         #inspector .cmd-box {{
-            background: #0d1117;
-            border: 1px solid #30363d;
+            background: var(--code-background);
+            border: 1px solid var(--code-border);
             border-radius: 6px;
             padding: 10px;
             margin-top: 8px;
             font-family: 'Consolas', 'Monaco', monospace;
             font-size: 11px;
-            color: #c9d1d9;
+            color: var(--code-text);
             word-break: break-all;
             position: relative;
         }}
@@ -420,16 +662,16 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             top: 6px;
             right: 6px;
             padding: 4px 8px;
-            background: #21262d;
-            border: 1px solid #30363d;
+            background: var(--code-surface);
+            border: 1px solid var(--code-border);
             border-radius: 4px;
-            color: #8b949e;
+            color: var(--muted);
             font-size: 10px;
             cursor: pointer;
         }}
         #inspector .cmd-box .copy-btn:hover {{
-            background: #30363d;
-            color: #c9d1d9;
+            background: var(--code-border);
+            color: var(--code-text);
         }}
         #inspector .badge {{
             display: inline-block;
@@ -441,23 +683,23 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             margin-left: 5px;
         }}
         #inspector .badge.pub {{
-            background: rgba(144, 238, 144, 0.2);
-            color: #90EE90;
+            background: color-mix(in srgb, var(--alive) 20%, transparent);
+            color: var(--alive);
         }}
         #inspector .badge.priv {{
-            background: rgba(100, 100, 100, 0.2);
-            color: #888;
+            background: color-mix(in srgb, var(--faint) 20%, transparent);
+            color: var(--muted);
         }}
         /* Toast notification */
         #toast {{
             position: fixed;
             bottom: 80px;
             right: 320px;
-            background: #16213e;
-            border: 1px solid #0f3460;
+            background: var(--surface);
+            border: 1px solid var(--border);
             border-radius: 8px;
             padding: 12px 20px;
-            color: #90EE90;
+            color: var(--alive);
             font-size: 13px;
             opacity: 0;
             transform: translateY(20px);
@@ -491,23 +733,28 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         <button id="reset" title="Reset View">R</button>
         <button id="toggle-bundling" title="Toggle Edge Bundling">B</button>
         <button id="toggle-clusters" title="Toggle Cluster Gravity">C</button>
+        <button id="toggle-crates" title="Collapse/Expand Crates" style="display: none;">K</button>
         <button id="clear-highlight" title="Clear Highlight (Esc)">✕</button>
     </div>
 
     <div id="legend">
         <h4>Legend</h4>
         <div class="legend-item">
-            <div class="legend-color" style="background: #90EE90;"></div>
-            <span>Reachable module</span>
+            <div class="legend-color" style="background: var(--alive);"></div>
+            <span>Reachable {noun}</span>
         </div>
         <div class="legend-item">
-            <div class="legend-color" style="background: #F08080;"></div>
-            <span>Dead module</span>
+            <div class="legend-color" style="background: var(--dead);"></div>
+            <span>Dead {noun}</span>
+        </div>
+        <div class="legend-item" id="legend-cross-crate" style="display: none;">
+            <div class="legend-color" style="background: none; border: 2px dashed var(--crate-group);"></div>
+            <span>Cross-crate edge</span>
         </div>
     </div>
 
     <div id="inspector">
-        <h2>Module Inspector</h2>
+        <h2>{heading} Inspector</h2>
         <div id="inspector-content">
             <p class="empty">Click a node to inspect</p>
         </div>
@@ -518,7 +765,20 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         // Data
         const nodes = {nodes_json};
         const edges = {edges_json};
-        const clusters = [{clusters_json}];
+        const clusters = {clusters_json};
+        const removable = {removable};
+        const crates = {crates_json};
+        // Positions were computed by deadmod: no simulation in the browser
+        const staticLayout = {static_layout};
+
+        // Theme colors, and with alpha (`#rrggbbaa`)
+        const theme = {theme_json};
+        const alpha = (color, a) => color + Math.round(a * 255).toString(16).padStart(2, '0');
+
+        // Names and paths are data: escape them in markup, and pass them to
+        // inline handlers as attribute-escaped string literals
+        const esc = s => String(s).replace(/[&<>"']/g, c => ({{ '&': '&amp;', '<': '&lt;', '>': '&gt;', '"': '&quot;', "'": '&#39;' }})[c]);
+        const arg = s => esc(JSON.stringify(String(s)));
 
         // Settings
         let edgeBundling = true;
@@ -538,11 +798,8 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         let lastMouse = {{ x: 0, y: 0 }};
         let selectedNode = null;
 
-        // Cluster colors (generated palette)
-        const clusterColors = [
-            '#e94560', '#0f3460', '#533483', '#16c79a', '#f7be16',
-            '#ff6b6b', '#4ecdc4', '#45b7d1', '#96c93d', '#dfe6e9'
-        ];
+        // Cluster colors (from the theme)
+        const clusterColors = theme.clusters;
 
         // Assign cluster colors
         const clusterColorMap = {{}};
@@ -574,15 +831,50 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             }});
         }}
 
-        // Node positions and velocities
+        // Crate super-clusters (workspace mode): center, bounds, collapse state
+        const crateGroups = {{}};
+        const collapsedCrates = new Set();
+        function updateCrateGroups() {{
+            crates.forEach(name => {{
+                crateGroups[name] = {{
+                    name, x: 0, y: 0, count: 0, dead: 0,
+                    minX: Infinity, minY: Infinity, maxX: -Infinity, maxY: -Infinity,
+                    header: crateGroups[name]?.header
+                }};
+            }});
+            Object.values(nodeMap).forEach(n => {{
+                const g = crateGroups[n.crate];
+                if (!g) return;
+                g.x += n.x;
+                g.y += n.y;
+                g.count++;
+                if (n.status === 'dead') g.dead++;
+                g.minX = Math.min(g.minX, n.x);
+                g.minY = Math.min(g.minY, n.y);
+                g.maxX = Math.max(g.maxX, n.x);
+                g.maxY = Math.max(g.maxY, n.y);
+            }});
+            Object.values(crateGroups).forEach(g => {{
+                if (g.count > 0) {{
+                    g.x /= g.count;
+                    g.y /= g.count;
+                }}
+            }});
+        }}
+
+        // Nodes inside a collapsed crate are drawn as the crate itself
+        function isHidden(n) {{
+            return !!n.crate && collapsedCrates.has(n.crate);
+        }}
+        function endpoint(n) {{
+            return isHidden(n) ? crateGroups[n.crate] : n;
+        }}
+
+        // Node positions (seeded layout computed by deadmod) and velocities
         const nodeMap = {{}};
-        nodes.forEach((n, i) => {{
-            const angle = (i / nodes.length) * Math.PI * 2;
-            const radius = Math.min(300, nodes.length * 15);
+        nodes.forEach(n => {{
             nodeMap[n.id] = {{
                 ...n,
-                x: Math.cos(angle) * radius + Math.random() * 50,
-                y: Math.sin(angle) * radius + Math.random() * 50,
                 vx: 0,
                 vy: 0,
                 radius: 30
@@ -627,6 +919,7 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             const world = toWorld(mx, my);
             for (const id in nodeMap) {{
                 const n = nodeMap[id];
+                if (isHidden(n)) continue;
                 const dx = world.x - n.x;
                 const dy = world.y - n.y;
                 if (dx * dx + dy * dy < n.radius * n.radius) {{
@@ -636,6 +929,28 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             return null;
         }}
 
+        function getCrateAt(mx, my) {{
+            for (const name of crates) {{
+                const h = crateGroups[name]?.header;
+                if (h && mx >= h.x && mx <= h.x + h.w && my >= h.y && my <= h.y + h.h) {{
+                    return name;
+                }}
+            }}
+            return null;
+        }}
+
+        window.toggleCrate = function(name) {{
+            if (collapsedCrates.has(name)) {{
+                collapsedCrates.delete(name);
+            }} else {{
+                collapsedCrates.add(name);
+                if (selectedNode && selectedNode.crate === name) {{
+                    selectedNode = null;
+                    updateInspector(null);
+                }}
+            }}
+        }};
+
         // Physics simulation with Barnes-Hut optimization hint
         function simulate() {{
             const allNodes = Object.values(nodeMap);
@@ -702,6 +1017,18 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                 }});
             }}
 
+            // Crate gravity - keep each crate's modules together
+            if (crates.length > 1) {{
+                updateCrateGroups();
+                allNodes.forEach(n => {{
+                    const g = crateGroups[n.crate];
+                    if (g && g.count > 1) {{
+                        n.vx += (g.x - n.x) * 0.001;
+                        n.vy += (g.y - n.y) * 0.001;
+                    }}
+                }});
+            }}
+
             // Apply velocities with damping
             allNodes.forEach(n => {{
                 if (n === dragNode) return;
@@ -747,7 +1074,7 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                     if (center.count > 1) {{
                         const p = toScreen(center.x, center.y);
                         const r = Math.sqrt(center.count) * 50 * scale;
-                        ctx.fillStyle = clusterColorMap[id] || '#666';
+                        ctx.fillStyle = clusterColorMap[id] || theme.faint;
                         ctx.beginPath();
                         ctx.arc(p.x, p.y, r, 0, Math.PI * 2);
                         ctx.fill();
@@ -756,12 +1083,57 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                 ctx.globalAlpha = 1;
             }}
 
-            // Draw edges
+            // Draw crate super-clusters: dashed boxes with a clickable header
+            if (crates.length > 1) {{
+                const pad = 50;
+                ctx.font = `bold ${{Math.max(11, 13 * scale)}}px 'Segoe UI', sans-serif`;
+                ctx.textAlign = 'left';
+                ctx.textBaseline = 'middle';
+                crates.forEach(name => {{
+                    const g = crateGroups[name];
+                    if (!g || g.count === 0) return;
+                    if (collapsedCrates.has(name)) {{
+                        g.header = null;
+                        return;
+                    }}
+                    const tl = toScreen(g.minX - pad, g.minY - pad);
+                    const br = toScreen(g.maxX + pad, g.maxY + pad);
+                    ctx.strokeStyle = alpha(theme.crateGroup, 0.6);
+                    ctx.lineWidth = 1.5;
+                    ctx.setLineDash([8, 6]);
+                    ctx.beginPath();
+                    ctx.roundRect(tl.x, tl.y, br.x - tl.x, br.y - tl.y, 12);
+                    ctx.stroke();
+                    ctx.setLineDash([]);
+
+                    const text = `▾ ${{name}}`;
+                    const w = ctx.measureText(text).width + 16;
+                    const h = Math.max(18, 22 * scale);
+                    g.header = {{ x: tl.x, y: tl.y - h, w, h }};
+                    ctx.fillStyle = alpha(theme.crateGroup, 0.85);
+                    ctx.beginPath();
+                    ctx.roundRect(tl.x, tl.y - h, w, h, 6);
+                    ctx.fill();
+                    ctx.fillStyle = theme.textStrong;
+                    ctx.fillText(text, tl.x + 8, tl.y - h / 2);
+                }});
+            }}
+
+            // Draw edges (edges into collapsed crates end at the crate, once per pair)
             ctx.lineWidth = 1.5 * scale;
+            const drawnCrateEdges = new Set();
             edges.forEach(e => {{
-                const a = nodeMap[e.from];
-                const b = nodeMap[e.to];
-                if (!a || !b) return;
+                const src = nodeMap[e.from];
+                const dst = nodeMap[e.to];
+                if (!src || !dst) return;
+                const a = endpoint(src);
+                const b = endpoint(dst);
+                if (!a || !b || a === b) return;
+                if (a !== src || b !== dst) {{
+                    const key = `${{a === src ? e.from : 'crate:' + a.name}}>${{b === dst ? e.to : 'crate:' + b.name}}`;
+                    if (drawnCrateEdges.has(key)) return;
+                    drawnCrateEdges.add(key);
+                }}
                 const p1 = toScreen(a.x, a.y);
                 const p2 = toScreen(b.x, b.y);
 
@@ -772,15 +1144,20 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
 
                 // Edge color based on dead status or highlight
                 if (edgeHighlighted && highlightedNodes.size > 0) {{
-                    ctx.strokeStyle = 'rgba(247, 190, 22, 0.8)';
+                    ctx.strokeStyle = alpha(theme.highlight, 0.8);
                     ctx.lineWidth = 2.5 * scale;
+                }} else if (e.crossCrate) {{
+                    ctx.strokeStyle = alpha(theme.crateGroup, 0.8);
+                    ctx.lineWidth = 2 * scale;
                 }} else if (a.status === 'dead' || b.status === 'dead') {{
-                    ctx.strokeStyle = 'rgba(240, 128, 128, 0.4)';
+                    ctx.strokeStyle = alpha(theme.dead, 0.4);
                     ctx.lineWidth = 1.5 * scale;
                 }} else {{
-                    ctx.strokeStyle = 'rgba(100, 100, 100, 0.6)';
+                    ctx.strokeStyle = alpha(theme.edge, 0.6);
                     ctx.lineWidth = 1.5 * scale;
                 }}
+                // Cross-crate edges are dashed regardless of color
+                ctx.setLineDash(e.crossCrate ? [6 * scale, 4 * scale] : []);
 
                 const cp = getBundleControlPoint(a, b);
 
@@ -796,6 +1173,7 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                     ctx.lineTo(p2.x, p2.y);
                 }}
                 ctx.stroke();
+                ctx.setLineDash([]);
 
                 // Draw arrow
                 const angle = Math.atan2(p2.y - p1.y, p2.x - p1.x);
@@ -815,8 +1193,33 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                 ctx.globalAlpha = 1;
             }});
 
+            // Draw collapsed crates as single nodes
+            crates.forEach(name => {{
+                const g = crateGroups[name];
+                if (!g || g.count === 0 || !collapsedCrates.has(name)) return;
+                const p = toScreen(g.x, g.y);
+                const r = 45 * scale;
+                ctx.fillStyle = g.dead === g.count ? theme.dead : theme.crateGroup;
+                ctx.strokeStyle = g.dead > 0 ? theme.deadStroke : theme.textStrong;
+                ctx.lineWidth = 2 * scale;
+                ctx.beginPath();
+                ctx.roundRect(p.x - r, p.y - r / 2, r * 2, r, 10 * scale);
+                ctx.fill();
+                ctx.stroke();
+                g.header = {{ x: p.x - r, y: p.y - r / 2, w: r * 2, h: r }};
+
+                ctx.fillStyle = theme.textStrong;
+                ctx.font = `bold ${{Math.max(10, 12 * scale)}}px 'Segoe UI', sans-serif`;
+                ctx.textAlign = 'center';
+                ctx.textBaseline = 'middle';
+                ctx.fillText(`▸ ${{name}}`, p.x, p.y - 7 * scale);
+                ctx.font = `${{Math.max(9, 10 * scale)}}px 'Segoe UI', sans-serif`;
+                ctx.fillText(`${{g.count}} {noun}s, ${{g.dead}} dead`, p.x, p.y + 9 * scale);
+            }});
+
             // Draw nodes
             Object.values(nodeMap).forEach(n => {{
+                if (isHidden(n)) return;
                 const p = toScreen(n.x, n.y);
                 const r = n.radius * scale;
 
@@ -832,13 +1235,13 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
 
                 // Node border (highlight if selected or in highlighted set)
                 if (n === selectedNode) {{
-                    ctx.strokeStyle = '#fff';
+                    ctx.strokeStyle = theme.textStrong;
                     ctx.lineWidth = 3 * scale;
                 }} else if (highlightedNodes.has(n.id) && highlightedNodes.size > 0) {{
-                    ctx.strokeStyle = '#f7be16';
+                    ctx.strokeStyle = theme.highlight;
                     ctx.lineWidth = 3 * scale;
                 }} else {{
-                    ctx.strokeStyle = n.status === 'dead' ? '#c44' : '#4a4';
+                    ctx.strokeStyle = n.status === 'dead' ? theme.deadStroke : theme.aliveStroke;
                     ctx.lineWidth = 2 * scale;
                 }}
                 ctx.stroke();
@@ -853,7 +1256,7 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                 }}
 
                 // Node label
-                ctx.fillStyle = '#1a1a2e';
+                ctx.fillStyle = theme.nodeText;
                 ctx.font = `${{Math.max(10, 12 * scale)}}px 'Segoe UI', sans-serif`;
                 ctx.textAlign = 'center';
                 ctx.textBaseline = 'middle';
@@ -872,13 +1275,17 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             const inRefs = inbound[node.id] || [];
 
             tooltip.innerHTML = `
-                <h3>${{node.label}}</h3>
+                <h3>${{esc(node.modulePath || node.label)}}</h3>
                 <span class="status ${{node.status}}">${{node.status}}</span>
-                <div class="path">${{node.path}}</div>
+                <div class="path">${{esc(node.path)}}</div>
                 <div class="refs">
-                    <strong>Imports:</strong> ${{refs.length ? refs.join(', ') : 'none'}}<br>
-                    <strong>Imported by:</strong> ${{inRefs.length ? inRefs.join(', ') : 'none'}}
+                    <strong>Imports:</strong> ${{refs.length ? refs.map(esc).join(', ') : 'none'}}<br>
+                    <strong>Imported by:</strong> ${{inRefs.length ? inRefs.map(esc).join(', ') : 'none'}}
                 </div>
+                ${{node.metrics ? `<div class="refs">
+                    <strong>Fan-in / fan-out:</strong> ${{node.metrics.fan_in}} / ${{node.metrics.fan_out}}<br>
+                    <strong>Instability:</strong> ${{node.metrics.instability.toFixed(2)}}, <strong>nesting:</strong> ${{node.metrics.nesting}}${{node.metrics.cycle !== null ? ', <strong>in a cycle</strong>' : ''}}
+                </div>` : ''}}
             `;
 
             // Position tooltip
@@ -910,17 +1317,17 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
 
             inspector.innerHTML = `
                 <div class="section">
-                    <h3>Module</h3>
+                    <h3>{heading}</h3>
                     <div class="value">
-                        ${{node.label}}
+                        ${{esc(node.modulePath || node.label)}}
                         <span class="badge ${{node.visibility === 'public' ? 'pub' : 'priv'}}">${{node.visibility || 'private'}}</span>
                     </div>
-                    <span class="cluster-tag">${{node.cluster}}</span>
+                    <span class="cluster-tag">${{esc(node.cluster)}}</span>
                 </div>
 
                 <div class="section">
                     <h3>Status</h3>
-                    <div class="value" style="color: ${{node.status === 'dead' ? '#F08080' : '#90EE90'}}">${{node.status.toUpperCase()}}</div>
+                    <div class="value" style="color: ${{node.status === 'dead' ? theme.dead : theme.alive}}">${{node.status.toUpperCase()}}</div>
                 </div>
 
                 <div class="section">
@@ -948,7 +1355,7 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                     <div class="dep-list">
                         ${{deps.length ? deps.map(d => `
                             <div class="dep-item ${{nodeMap[d]?.status === 'dead' ? 'dead' : ''}}"
-                                 onclick="window.selectNode('${{d}}')">${{d}}</div>
+                                 onclick="window.selectNode(${{arg(d)}})">${{esc(d)}}</div>
                         `).join('') : '<span class="empty">None</span>'}}
                     </div>
                 </div>
@@ -958,7 +1365,7 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                     <div class="dep-list">
                         ${{dependents.length ? dependents.map(d => `
                             <div class="dep-item ${{nodeMap[d]?.status === 'dead' ? 'dead' : ''}}"
-                                 onclick="window.selectNode('${{d}}')">${{d}}</div>
+                                 onclick="window.selectNode(${{arg(d)}})">${{esc(d)}}</div>
                         `).join('') : '<span class="empty">None</span>'}}
                     </div>
                 </div>
@@ -966,25 +1373,25 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                 <div class="section">
                     <h3>Path</h3>
                     <div class="cmd-box">
-                        ${{node.path}}
-                        <button class="copy-btn" onclick="window.copyToClipboard('${{node.path.replace(/\\/g, '\\\\\\\\')}}')">Copy</button>
+                        ${{esc(node.path)}}
+                        <button class="copy-btn" onclick="window.copyToClipboard(${{arg(node.path)}})">Copy</button>
                     </div>
                 </div>
 
                 <div class="section">
                     <h3>Actions</h3>
                     <div class="actions">
-                        <button class="action-btn" onclick="window.copyToClipboard('${{node.path.replace(/\\/g, '\\\\\\\\')}}')">
+                        <button class="action-btn" onclick="window.copyToClipboard(${{arg(node.path)}})">
                             <span class="icon">📋</span> Copy Path
                         </button>
-                        <button class="action-btn" onclick="window.copyDeadmodCommand('${{node.id}}')">
+                        <button class="action-btn" onclick="window.copyDeadmodCommand(${{arg(node.id)}})">
                             <span class="icon">⚡</span> Copy Deadmod Command
                         </button>
-                        <button class="action-btn success" onclick="window.highlightConnections('${{node.id}}')">
+                        <button class="action-btn success" onclick="window.highlightConnections(${{arg(node.id)}})">
                             <span class="icon">🔍</span> Highlight Connections
                         </button>
-                        ${{node.status === 'dead' ? `
-                        <button class="action-btn danger" onclick="window.showRemoveCommand('${{node.path.replace(/\\/g, '\\\\\\\\')}}')">
+                        ${{node.status === 'dead' && removable ? `
+                        <button class="action-btn danger" onclick="window.showRemoveCommand(${{arg(node.path)}})">
                             <span class="icon">🗑️</span> Show Remove Command
                         </button>
                         ` : ''}}
@@ -1015,7 +1422,7 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                 document.body.appendChild(toast);
             }}
             toast.textContent = message;
-            toast.style.color = type === 'success' ? '#90EE90' : '#F08080';
+            toast.style.color = type === 'success' ? theme.alive : theme.dead;
             toast.classList.add('visible');
             clearTimeout(toastTimeout);
             toastTimeout = setTimeout(() => toast.classList.remove('visible'), 2000);
@@ -1057,7 +1464,7 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             highlightedNodes.add(moduleId);
             deps.forEach(d => highlightedNodes.add(d));
             dependents.forEach(d => highlightedNodes.add(d));
-            showToast(`Highlighted ${{highlightedNodes.size}} connected modules`);
+            showToast(`Highlighted ${{highlightedNodes.size}} connected {noun}s`);
         }};
 
         // Show remove command for dead module
@@ -1076,6 +1483,12 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             const mx = e.clientX - rect.left;
             const my = e.clientY - rect.top;
 
+            const crateName = getCrateAt(mx, my);
+            if (crateName) {{
+                window.toggleCrate(crateName);
+                return;
+            }}
+
             const node = getNodeAt(mx, my);
             if (node) {{
                 dragNode = node;
@@ -1170,6 +1583,20 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         }};
         clusterBtn.classList.toggle('active', clusterGravity);
 
+        // Collapse or expand all crate super-clusters
+        const cratesBtn = document.getElementById('toggle-crates');
+        if (crates.length > 1) {{
+            cratesBtn.style.display = '';
+            document.getElementById('legend-cross-crate').style.display = '';
+        }}
+        cratesBtn.onclick = () => {{
+            const collapseAll = collapsedCrates.size < crates.length;
+            crates.forEach(name => {{
+                if (collapsedCrates.has(name) !== collapseAll) window.toggleCrate(name);
+            }});
+            cratesBtn.classList.toggle('active', collapsedCrates.size > 0);
+        }};
+
         // Clear highlights
         const clearBtn = document.getElementById('clear-highlight');
         window.clearHighlights = function() {{
@@ -1187,7 +1614,11 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
 
         // Animation loop
         function loop() {{
-            simulate();
+            if (staticLayout) {{
+                updateClusterCenters();
+            }} else {{
+                simulate();
+            }}
             draw();
             requestAnimationFrame(loop);
         }}
@@ -1199,13 +1630,21 @@ pub fn generate_html_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
     </script>
 </body>
 </html>"##,
-        total = total,
-        reachable_count = reachable_count,
-        dead_count = dead_count,
-        cluster_count = clusters.len(),
-        nodes_json = nodes_json,
-        edges_json = edges_json,
-        clusters_json = clusters_json
+        title = subject.title,
+        heading = subject.heading,
+        noun = subject.noun,
+        removable = subject.removable,
+        total = data.total,
+        reachable_count = data.reachable_count,
+        dead_count = data.dead_count,
+        cluster_count = data.cluster_count,
+        nodes_json = data.nodes_json,
+        edges_json = data.edges_json,
+        clusters_json = data.clusters_json,
+        crates_json = data.crates_json,
+        static_layout = data.static_layout,
+        theme_css = data.theme.palette().css_variables(),
+        theme_json = script_json(data.theme.palette())
     )
 }
 
@@ -1282,6 +1721,7 @@ mod tests {
         assert!(html.contains("#F08080")); // dead color
         assert!(html.contains("Reachable:<span class=\"stat-value green\">2</span>"));
         assert!(html.contains("Dead:<span class=\"stat-value red\">1</span>"));
+        assert!(html.contains(r#""metrics":{"fan_in":1,"fan_out":0,"instability":0.0"#));
     }
 
     #[test]
@@ -1315,6 +1755,56 @@ mod tests {
         assert!(html.contains("\\\\"));
     }
 
+    #[test]
+    fn test_generate_html_graph_escapes_markup() {
+        let mut mods = HashMap::new();
+        let hostile = "src/\"quoted\"\n</script><img src=x onerror=alert(1)>.rs";
+        mods.insert(
+            "evil".to_string(),
+            crate::parse::ModuleInfo::new(PathBuf::from(hostile)),
+        );
+
+        let html = generate_html_graph(&mods, &HashSet::new());
+
+        // One closing tag: the page's own
+        assert_eq!(html.matches("</script>").count(), 1);
+        assert!(!html.contains("<img"));
+        let start = html.find("const nodes = ").unwrap() + "const nodes = ".len();
+        let end = start + html[start..].find(";\n").unwrap();
+        let nodes: serde_json::Value = serde_json::from_str(&html[start..end]).unwrap();
+        assert_eq!(nodes[0]["path"], hostile);
+        assert!(html.contains("const esc = "));
+    }
+
+    #[test]
+    fn test_generate_html_graph_themes() {
+        let mut mods = HashMap::new();
+        mods.insert(
+            "main".to_string(),
+            crate::parse::ModuleInfo::new(PathBuf::from("src/main.rs")),
+        );
+        mods.insert(
+            "dead".to_string(),
+            crate::parse::ModuleInfo::new(PathBuf::from("src/dead.rs")),
+        );
+        let reachable: HashSet<String> = ["main".to_string()].into();
+
+        let html = generate_html_graph(&mods, &reachable);
+        assert!(html.contains("--dead: #F08080;"));
+        assert!(html.contains(r##""color":"#90EE90""##));
+
+        let html = generate_html_graph_with_layout(
+            &mods,
+            &reachable,
+            &LayoutOptions::default(),
+            Theme::ColorblindSafe,
+        );
+        assert!(html.contains("--alive: #56B4E9;") && html.contains("--dead: #E69F00;"));
+        assert!(html.contains(r##""color":"#E69F00""##));
+        assert!(html.contains(r##"const theme = {"background":"#1a1a2e""##));
+        assert!(!html.contains("#90EE90") && !html.contains("#F08080"));
+    }
+
     #[test]
     fn test_generate_html_graph_has_inspector() {
         let mods = HashMap::new();
@@ -1358,4 +1848,161 @@ mod tests {
         assert_eq!(extract_parent_module("/path/to/src/module/file.rs"), "module");
         assert_eq!(extract_parent_module("C:\\project\\src\\config.rs"), "config");
     }
+
+    #[test]
+    fn test_generate_html_graph_workspace_crates() {
+        let mut mods = HashMap::new();
+        let mut reachable = HashSet::new();
+
+        let mut cli_main = crate::parse::ModuleInfo::new(PathBuf::from("cli/src/main.rs"));
+        cli_main.refs.insert("core::lib".to_string());
+        cli_main.krate = Some("cli".to_string());
+        mods.insert("cli::main".to_string(), cli_main);
+        let mut core_lib = crate::parse::ModuleInfo::new(PathBuf::from("core/src/lib.rs"));
+        core_lib.krate = Some("core".to_string());
+        mods.insert("core::lib".to_string(), core_lib);
+        reachable.insert("cli::main".to_string());
+        reachable.insert("core::lib".to_string());
+
+        let html = generate_html_graph(&mods, &reachable);
+
+        assert!(html.contains(r#"const crates = ["cli","core"];"#));
+        assert!(html.contains(r#""crate":"core""#));
+        assert!(html.contains(r#""from":"cli::main","to":"core::lib","crossCrate":true"#));
+        assert!(html.contains("toggle-crates"));
+    }
+
+    #[test]
+    fn test_generate_html_graph_same_stem_modules_are_not_crates() {
+        let mods = crate::Deadmod::from_sources(
+            [
+                ("src/lib.rs", "pub mod a;\npub mod b;"),
+                ("src/a/mod.rs", "pub mod utils;"),
+                ("src/a/utils.rs", ""),
+                ("src/b/mod.rs", "pub mod utils;"),
+                ("src/b/utils.rs", ""),
+            ]
+            .map(|(file, src)| (PathBuf::from(file), src.to_string())),
+        )
+        .analyze()
+        .unwrap()
+        .modules;
+        assert!(mods.contains_key("a::utils"));
+        let reachable: HashSet<String> = mods.keys().cloned().collect();
+
+        let html = generate_html_graph(&mods, &reachable);
+
+        assert!(html.contains("const crates = [];"));
+        assert!(!html.contains(r#""crate":"a""#));
+        assert!(html.contains(r#""crossCrate":false"#));
+        assert!(!html.contains(r#""crossCrate":true"#));
+    }
+
+    #[test]
+    fn test_generate_html_graph_is_deterministic() {
+        let graph = |names: &[&str]| {
+            let mut mods = HashMap::new();
+            for name in names {
+                let mut info =
+                    crate::parse::ModuleInfo::new(PathBuf::from(format!("src/net/{}.rs", name)));
+                info.refs
+                    .extend(names.iter().map(|n| n.to_string()).filter(|n| n != name));
+                mods.insert(name.to_string(), info);
+            }
+            mods
+        };
+        let reachable: HashSet<String> = ["a".to_string()].into();
+        let seeded = LayoutOptions::default().with_seed(42);
+
+        let html = generate_html_graph_with_layout(
+            &graph(&["a", "b", "c", "d"]),
+            &reachable,
+            &seeded,
+            Theme::default(),
+        );
+        assert!(!html.contains("Math.random"));
+        assert!(html.contains(r#""x":"#) && html.contains(r#""y":"#));
+        assert_eq!(
+            html,
+            generate_html_graph_with_layout(
+                &graph(&["d", "c", "b", "a"]),
+                &reachable,
+                &seeded,
+                Theme::default()
+            )
+        );
+        assert_ne!(
+            html,
+            generate_html_graph(&graph(&["a", "b", "c", "d"]), &reachable)
+        );
+        assert!(html.contains("const staticLayout = false;"));
+
+        let fixed = seeded.with_static_layout(true);
+        let html = generate_html_graph_with_layout(
+            &graph(&["a", "b", "c", "d"]),
+            &reachable,
+            &fixed,
+            Theme::default(),
+        );
+        assert!(html.contains("const staticLayout = true;"));
+        assert_eq!(
+            html,
+            generate_html_graph_with_layout(
+                &graph(&["d", "c", "b", "a"]),
+                &reachable,
+                &fixed,
+                Theme::default()
+            )
+        );
+    }
+
+    #[cfg(feature = "callgraph")]
+    fn sample_callgraph() -> VisualizerGraph {
+        use crate::callgraph::{VisualizerEdge, VisualizerNode, VisualizerStats};
+
+        let node = |id: usize, name: &str, dead: bool| VisualizerNode {
+            id,
+            name: name.to_string(),
+            full_path: format!("app::api::{}", name),
+            file: "src/api/routes.rs".to_string(),
+            module: "routes".to_string(),
+            dead,
+            confidence: None,
+            visibility: "pub".to_string(),
+            is_method: false,
+            metrics: Default::default(),
+        };
+
+        VisualizerGraph {
+            nodes: vec![
+                node(0, "handle", false),
+                node(1, "parse", false),
+                node(2, "legacy", true),
+            ],
+            edges: vec![VisualizerEdge { from: 0, to: 1 }],
+            modules: vec!["routes".to_string()],
+            stats: VisualizerStats {
+                total_functions: 3,
+                total_edges: 1,
+                dead_functions: 1,
+                total_modules: 1,
+            },
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "callgraph")]
+    fn test_generate_html_callgraph() {
+        let html = generate_html_callgraph(&sample_callgraph());
+
+        assert!(html.contains(r#""id":"app::api::legacy""#));
+        assert!(html.contains(r#""status":"dead""#));
+        assert!(html.contains(r#""cluster":"routes""#));
+        assert!(html.contains("const removable = false;"));
+        assert!(html.contains("Deadmod - Function Call Graph"));
+        assert!(html.contains("Function Inspector"));
+        assert!(html.contains("Dead function"));
+        assert!(html.contains(r#""from":"app::api::handle","to":"app::api::parse""#));
+        assert!(html.contains(r#""refCount":1"#));
+    }
 }
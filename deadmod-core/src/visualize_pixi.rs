@@ -7,14 +7,72 @@
 //! - Force-directed layout with Barnes-Hut optimization
 //! - Module clustering with color-coded groups and visual bounding boxes
 //! - Hierarchical cluster visualization (nested directory structure)
+//! - Collapsible crate super-clusters in workspace mode
+//! - Level of detail for huge graphs: above a node threshold, modules start
+//!   collapsed into per-cluster super-nodes that expand on click
 //! - Edge bundling with Bézier curves
 //! - Inspector panel with detailed statistics
 //! - Responsive zoom/pan/drag
 //! - Dark theme optimized for developers
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
+use crate::graph::{module_crate, qualified_module_path};
+use crate::layout::{layout_graph, LayoutOptions};
 use crate::parse::ModuleInfo;
+use crate::theme::Theme;
+use crate::visualize_data::{script_json, GraphCluster, GraphEdge, GraphNode};
+
+#[cfg(feature = "callgraph")]
+use crate::callgraph::VisualizerGraph;
+
+/// Labels for the kind of node a rendered page shows.
+struct Subject {
+    /// Page title after "Deadmod - "
+    title: &'static str,
+    /// Capitalized singular, used for headings
+    heading: &'static str,
+    /// Lowercase singular, used in running text
+    noun: &'static str,
+    /// Whether dead nodes are whole files that can be deleted
+    removable: bool,
+}
+
+const MODULES: Subject = Subject {
+    title: "PixiJS WebGL Graph",
+    heading: "Module",
+    noun: "module",
+    removable: true,
+};
+
+#[cfg(feature = "callgraph")]
+const FUNCTIONS: Subject = Subject {
+    title: "PixiJS WebGL Call Graph",
+    heading: "Function",
+    noun: "function",
+    removable: false,
+};
+
+/// Serialized graph data and header stats for [`render_page`].
+struct PageData {
+    total: usize,
+    reachable_count: usize,
+    dead_count: usize,
+    edge_count: usize,
+    nodes_json: String,
+    edges_json: String,
+    clusters_json: String,
+    crates_json: String,
+    /// Pre-clustered level-of-detail structure, or `null` for a flat graph
+    lod_json: String,
+    /// Positions are final: start with the simulation paused
+    static_layout: bool,
+    theme: Theme,
+}
+
+/// Node count above which [`generate_pixi_graph`] starts collapsed into
+/// cluster super-nodes.
+pub const DEFAULT_CLUSTER_THRESHOLD: usize = 1500;
 
 /// Generate a PixiJS WebGL visualization of the module graph.
 ///
@@ -23,12 +81,52 @@ use crate::parse::ModuleInfo;
 ///
 /// - reachable modules: green
 /// - dead modules: red
+///
+/// Graphs with more than [`DEFAULT_CLUSTER_THRESHOLD`] modules start
+/// collapsed into cluster super-nodes; see [`generate_pixi_graph_clustered`].
 pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashSet<String>) -> String {
+    generate_pixi_graph_clustered(mods, reachable, DEFAULT_CLUSTER_THRESHOLD)
+}
+
+/// Generate a PixiJS WebGL visualization, pre-clustering graphs with more
+/// than `threshold` modules.
+///
+/// Above the threshold the page also carries one super-node per cluster
+/// (module and dead counts) and the edges between clusters aggregated with
+/// their multiplicity. Clusters start collapsed and expand on click, and
+/// module sprites are only created once their cluster is expanded, so
+/// monorepo-sized graphs stay interactive.
+pub fn generate_pixi_graph_clustered(
+    mods: &HashMap<String, ModuleInfo>,
+    reachable: &HashSet<String>,
+    threshold: usize,
+) -> String {
+    generate_pixi_graph_with_layout(
+        mods,
+        reachable,
+        threshold,
+        &LayoutOptions::default(),
+        Theme::default(),
+    )
+}
+
+/// [`generate_pixi_graph_clustered`] with positions from `layout`, colored
+/// by `theme`.
+///
+/// Modules, edges and clusters are written in name order, so the same
+/// graph and seed always produce the same page. With a static layout the
+/// page draws the precomputed positions with the simulation paused.
+pub fn generate_pixi_graph_with_layout(
+    mods: &HashMap<String, ModuleInfo>,
+    reachable: &HashSet<String>,
+    threshold: usize,
+    layout: &LayoutOptions,
+    theme: Theme,
+) -> String {
     let edge_count: usize = mods.values().map(|info| info.refs.len()).sum();
 
-    let mut nodes = Vec::with_capacity(mods.len());
-    let mut edges = Vec::with_capacity(edge_count);
-    let mut clusters: HashSet<String> = HashSet::new();
+    let mut clusters: BTreeSet<&str> = BTreeSet::new();
+    let mut cluster_of: HashMap<&str, String> = HashMap::with_capacity(mods.len());
 
     // Build inbound reference counts
     let mut inbound_counts: HashMap<String, usize> = HashMap::new();
@@ -38,96 +136,363 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         }
     }
 
-    // Collect top-level clusters for hierarchical grouping
-    let mut top_clusters: HashSet<String> = HashSet::new();
+    // Workspace crates become collapsible super-clusters
+    let mut crates: Vec<&str> = mods.values().filter_map(module_crate).collect();
+    crates.sort_unstable();
+    crates.dedup();
 
-    for (name, info) in mods {
-        let status = if reachable.contains(name) { "reachable" } else { "dead" };
-        let path_str = info.path.display().to_string();
-
-        // For workspace mode: extract crate name from module name (e.g., "deadmod-core::lib")
-        // For single crate: use path-based extraction
-        let (cluster, top_cluster) = if name.contains("::") {
-            // Workspace mode: module name has crate prefix
-            let parts: Vec<&str> = name.split("::").collect();
-            let crate_name = parts[0].to_string();
-            // Full cluster path includes crate name
-            let full_cluster = if parts.len() > 2 {
-                format!("{}::{}", crate_name, parts[1])
-            } else {
-                crate_name.clone()
-            };
-            (full_cluster, crate_name)
-        } else {
-            // Single crate mode: use path-based extraction
-            (extract_parent_module(&path_str), extract_top_cluster(&path_str))
-        };
-
-        clusters.insert(cluster.clone());
-        top_clusters.insert(top_cluster.clone());
-
-        // Strip Windows extended-length path prefix
-        let path_clean = path_str.strip_prefix(r"\\?\").unwrap_or(&path_str);
-        let path_escaped = path_clean.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut names: Vec<&String> = mods.keys().collect();
+    names.sort_unstable();
 
-        // Short label for display (without crate prefix if present)
-        let label = if name.contains("::") {
-            name.split("::").last().unwrap_or(name).to_string()
-        } else {
-            name.clone()
-        };
-
-        // Module metadata
-        let ref_count = info.refs.len();
-        let inbound_count = inbound_counts.get(name).copied().unwrap_or(0);
-        let visibility = format!("{:?}", info.visibility).to_lowercase();
-
-        // Include topCluster for hierarchical visualization
-        nodes.push(format!(
-            r#"{{ "id": "{}", "label": "{}", "status": "{}", "path": "{}", "cluster": "{}", "topCluster": "{}", "refCount": {}, "inboundCount": {}, "visibility": "{}" }}"#,
-            name, label, status, path_escaped, cluster, top_cluster, ref_count, inbound_count, visibility
-        ));
+    // For workspace mode: cluster by crate and top module (e.g., "deadmod-core::lib")
+    // For single crate: use path-based extraction
+    let node_clusters: Vec<(String, String)> = names
+        .iter()
+        .map(|name| {
+            if let Some(crate_name) = module_crate(&mods[*name]) {
+                // Workspace mode: module name has crate prefix
+                let local = name
+                    .strip_prefix(crate_name)
+                    .and_then(|rest| rest.strip_prefix("::"))
+                    .unwrap_or(name);
+                // Full cluster path includes crate name
+                let full_cluster = match local.split_once("::") {
+                    Some((top, _)) => format!("{}::{}", crate_name, top),
+                    None => crate_name.to_string(),
+                };
+                (full_cluster, crate_name.to_string())
+            } else {
+                // Single crate mode: use path-based extraction
+                let path_str = mods[*name].path.display().to_string();
+                (
+                    extract_parent_module(&path_str),
+                    extract_top_cluster(&path_str),
+                )
+            }
+        })
+        .collect();
+    let index: HashMap<&String, usize> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (*name, i))
+        .collect();
+    let index_edges: Vec<(usize, usize)> = names
+        .iter()
+        .enumerate()
+        .flat_map(|(i, name)| {
+            mods[*name]
+                .refs
+                .iter()
+                .filter_map(|r| index.get(r))
+                .map(move |&j| (i, j))
+        })
+        .collect();
+    let positions = layout_graph(
+        &node_clusters
+            .iter()
+            .map(|(cluster, _)| cluster.as_str())
+            .collect::<Vec<_>>(),
+        &index_edges,
+        layout,
+    );
+
+    // Strings the nodes borrow
+    let module_paths: Vec<String> = names
+        .iter()
+        .map(|name| qualified_module_path(name, &mods[*name]))
+        .collect();
+    let paths: Vec<String> = names
+        .iter()
+        .map(|name| mods[*name].path.display().to_string())
+        .collect();
+    let visibilities: Vec<String> = names
+        .iter()
+        .map(|name| format!("{:?}", mods[*name].visibility).to_lowercase())
+        .collect();
+
+    let mut nodes: Vec<GraphNode> = Vec::with_capacity(mods.len());
+    for (i, name) in names.iter().copied().enumerate() {
+        let info = &mods[name];
+        let (cluster, top_cluster) = &node_clusters[i];
+
+        clusters.insert(cluster.as_str());
+        cluster_of.insert(name, cluster.clone());
+
+        nodes.push(GraphNode {
+            id: name,
+            // Short label for display: last segment of the module path
+            label: module_paths[i]
+                .rsplit("::")
+                .next()
+                .unwrap_or(&module_paths[i]),
+            module_path: Some(&module_paths[i]),
+            color: None,
+            status: if reachable.contains(name) {
+                "reachable"
+            } else {
+                "dead"
+            },
+            // Strip Windows extended-length path prefix
+            path: paths[i].strip_prefix(r"\\?\").unwrap_or(&paths[i]),
+            cluster,
+            // Top cluster for hierarchical visualization
+            top_cluster: Some(top_cluster),
+            krate: module_crate(info),
+            ref_count: info.refs.len(),
+            inbound_count: inbound_counts.get(name).copied().unwrap_or(0),
+            visibility: visibilities[i].as_str(),
+            metrics: None,
+            confidence: None,
+            x: positions[i].x,
+            y: positions[i].y,
+        });
     }
 
-    for (src, info) in mods {
-        for dst in &info.refs {
+    let mut edges: Vec<GraphEdge> = Vec::with_capacity(edge_count);
+    for src in names.iter().copied() {
+        let mut refs: Vec<&String> = mods[src].refs.iter().collect();
+        refs.sort_unstable();
+        for dst in refs {
             if mods.contains_key(dst) {
-                edges.push(format!(r#"{{ "from": "{}", "to": "{}" }}"#, src, dst));
+                let cross_crate = module_crate(&mods[src]) != module_crate(&mods[dst]);
+                edges.push(GraphEdge {
+                    from: src,
+                    to: dst,
+                    cross_crate: Some(cross_crate),
+                });
             }
         }
     }
 
-    let clusters_json: String = clusters
-        .iter()
+    let clusters: Vec<GraphCluster> = clusters
+        .into_iter()
         .enumerate()
-        .map(|(i, c)| format!(r#"{{ "id": "{}", "index": {} }}"#, c, i))
-        .collect::<Vec<_>>()
-        .join(",\n    ");
-
-    let nodes_json = format!("[{}]", nodes.join(",\n    "));
-    let edges_json = format!("[{}]", edges.join(",\n    "));
+        .map(|(index, id)| GraphCluster { id, index })
+        .collect();
 
     let total = mods.len();
     let dead_count = mods.keys().filter(|k| !reachable.contains(*k)).count();
     let reachable_count = total - dead_count;
+    let lod_json = if total > threshold {
+        script_json(&level_of_detail(mods, reachable, &cluster_of, threshold))
+    } else {
+        "null".to_string()
+    };
+
+    render_page(
+        &MODULES,
+        PageData {
+            total,
+            reachable_count,
+            dead_count,
+            edge_count,
+            nodes_json: script_json(&nodes),
+            edges_json: script_json(&edges),
+            clusters_json: script_json(&clusters),
+            crates_json: script_json(&crates),
+            lod_json,
+            static_layout: layout.static_layout,
+            theme,
+        },
+    )
+}
 
+/// Cluster super-nodes and aggregated inter-cluster edges for a graph too
+/// large to lay out module by module.
+///
+/// ```json
+/// {
+///   "threshold": 1500,
+///   "clusters": [{ "id": "api", "crate": null, "count": 40, "dead": 3, "internalEdges": 52 }],
+///   "edges": [{ "from": "api", "to": "db", "count": 7, "crossCrate": false }]
+/// }
+/// ```
+fn level_of_detail(
+    mods: &HashMap<String, ModuleInfo>,
+    reachable: &HashSet<String>,
+    cluster_of: &HashMap<&str, String>,
+    threshold: usize,
+) -> serde_json::Value {
+    // cluster -> (crate, modules, dead modules, edges inside the cluster)
+    let mut groups: BTreeMap<&str, (Option<&str>, usize, usize, usize)> = BTreeMap::new();
+    let mut between: BTreeMap<(&str, &str), (usize, bool)> = BTreeMap::new();
+    for (name, info) in mods {
+        let cluster = cluster_of[name.as_str()].as_str();
+        let group = groups
+            .entry(cluster)
+            .or_insert((module_crate(info), 0, 0, 0));
+        group.1 += 1;
+        if !reachable.contains(name) {
+            group.2 += 1;
+        }
+        for dst in info.refs.iter().filter(|dst| mods.contains_key(*dst)) {
+            let dst_cluster = cluster_of[dst.as_str()].as_str();
+            if dst_cluster == cluster {
+                groups.get_mut(cluster).expect("inserted above").3 += 1;
+            } else {
+                let edge = between.entry((cluster, dst_cluster)).or_insert((0, false));
+                edge.0 += 1;
+                edge.1 |= module_crate(info) != module_crate(&mods[dst]);
+            }
+        }
+    }
+
+    let clusters: Vec<serde_json::Value> = groups
+        .into_iter()
+        .map(|(id, (krate, count, dead, internal))| {
+            serde_json::json!({
+                "id": id,
+                "crate": krate,
+                "count": count,
+                "dead": dead,
+                "internalEdges": internal,
+            })
+        })
+        .collect();
+    let edges: Vec<serde_json::Value> = between
+        .into_iter()
+        .map(|((from, to), (count, cross_crate))| {
+            serde_json::json!({ "from": from, "to": to, "count": count, "crossCrate": cross_crate })
+        })
+        .collect();
+    serde_json::json!({ "threshold": threshold, "clusters": clusters, "edges": edges })
+}
+
+/// Generate a PixiJS WebGL visualization of a function call graph.
+///
+/// One node per function, clustered by module (nested under the top-level
+/// directory) and colored by reachability.
+#[cfg(feature = "callgraph")]
+pub fn generate_pixi_callgraph(graph: &VisualizerGraph) -> String {
+    generate_pixi_callgraph_with_layout(graph, &LayoutOptions::default(), Theme::default())
+}
+
+/// [`generate_pixi_callgraph`] with positions from `layout`, colored by
+/// `theme`.
+#[cfg(feature = "callgraph")]
+pub fn generate_pixi_callgraph_with_layout(
+    graph: &VisualizerGraph,
+    layout: &LayoutOptions,
+    theme: Theme,
+) -> String {
+    let clusters: Vec<&str> = graph
+        .nodes
+        .iter()
+        .map(|node| node.module.as_str())
+        .collect();
+    let edges: Vec<(usize, usize)> = graph
+        .edges
+        .iter()
+        .map(|edge| (edge.from, edge.to))
+        .collect();
+    let positions = layout_graph(&clusters, &edges, layout);
+
+    let mut ref_counts = vec![0usize; graph.nodes.len()];
+    let mut inbound_counts = vec![0usize; graph.nodes.len()];
+    for edge in &graph.edges {
+        ref_counts[edge.from] += 1;
+        inbound_counts[edge.to] += 1;
+    }
+
+    let top_clusters: Vec<String> = graph
+        .nodes
+        .iter()
+        .map(|node| extract_top_cluster(node.file.strip_prefix(r"\\?\").unwrap_or(&node.file)))
+        .collect();
+
+    let nodes: Vec<GraphNode> = graph
+        .nodes
+        .iter()
+        .zip(&positions)
+        .map(|(node, position)| {
+            let path = node.file.strip_prefix(r"\\?\").unwrap_or(&node.file);
+            let visibility = if node.visibility.starts_with("pub") {
+                "public"
+            } else {
+                "private"
+            };
+
+            GraphNode {
+                id: &node.full_path,
+                label: &node.name,
+                module_path: None,
+                color: None,
+                status: if node.dead { "dead" } else { "reachable" },
+                path,
+                cluster: &node.module,
+                top_cluster: Some(&top_clusters[node.id]),
+                krate: None,
+                ref_count: ref_counts[node.id],
+                inbound_count: inbound_counts[node.id],
+                visibility,
+                metrics: None,
+                confidence: node.confidence,
+                x: position.x,
+                y: position.y,
+            }
+        })
+        .collect();
+
+    let edges: Vec<GraphEdge> = graph
+        .edges
+        .iter()
+        .map(|edge| GraphEdge {
+            from: &graph.nodes[edge.from].full_path,
+            to: &graph.nodes[edge.to].full_path,
+            cross_crate: None,
+        })
+        .collect();
+
+    let clusters: Vec<GraphCluster> = graph
+        .modules
+        .iter()
+        .enumerate()
+        .map(|(index, id)| GraphCluster { id, index })
+        .collect();
+
+    let total = graph.stats.total_functions;
+    let dead_count = graph.stats.dead_functions;
+
+    render_page(
+        &FUNCTIONS,
+        PageData {
+            total,
+            reachable_count: total - dead_count,
+            dead_count,
+            edge_count: graph.edges.len(),
+            nodes_json: script_json(&nodes),
+            edges_json: script_json(&edges),
+            clusters_json: script_json(&clusters),
+            crates_json: "[]".to_string(),
+            lod_json: "null".to_string(),
+            static_layout: layout.static_layout,
+            theme,
+        },
+    )
+}
+
+/// Fill the WebGL viewer template with graph data.
+fn render_page(subject: &Subject, data: PageData) -> String {
     format!(
         r##"<!DOCTYPE html>
 <html lang="en">
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Deadmod - PixiJS WebGL Graph</title>
+    <title>Deadmod - {title}</title>
     <!-- Security: SRI hash ensures CDN integrity -->
     <script src="https://cdnjs.cloudflare.com/ajax/libs/pixi.js/7.3.2/pixi.min.js"
             integrity="sha384-3wqglGMjR9U1G89jiQ7NAkDL25fFnegjLwujmJu5o5AvOwEAf/ysfl8uBtBw7Eum"
             crossorigin="anonymous"></script>
     <style>
+        :root {{
+            {theme_css}
+        }}
         * {{ margin: 0; padding: 0; box-sizing: border-box; }}
         body {{
             font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
-            background: #0d0d1a;
-            color: #eee;
+            background: var(--backdrop);
+            color: var(--text);
             overflow: hidden;
         }}
         #header {{
@@ -136,8 +501,8 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             left: 0;
             right: 0;
             height: 50px;
-            background: #16213e;
-            border-bottom: 1px solid #0f3460;
+            background: var(--surface);
+            border-bottom: 1px solid var(--border);
             display: flex;
             align-items: center;
             padding: 0 20px;
@@ -147,10 +512,10 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         #header h1 {{
             font-size: 18px;
             font-weight: 600;
-            color: #e94560;
+            color: var(--accent);
         }}
         #header .badge {{
-            background: #e94560;
+            background: var(--accent);
             color: white;
             padding: 2px 8px;
             border-radius: 4px;
@@ -159,14 +524,14 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         }}
         .stat {{
             font-size: 13px;
-            color: #aaa;
+            color: var(--muted);
         }}
         .stat-value {{
             font-weight: bold;
             margin-left: 5px;
         }}
-        .stat-value.green {{ color: #90EE90; }}
-        .stat-value.red {{ color: #F08080; }}
+        .stat-value.green {{ color: var(--alive); }}
+        .stat-value.red {{ color: var(--dead); }}
         #canvas-container {{
             position: fixed;
             top: 50px;
@@ -188,15 +553,15 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             height: 44px;
             border: none;
             border-radius: 10px;
-            background: rgba(22, 33, 62, 0.9);
-            color: #eee;
+            background: color-mix(in srgb, var(--surface) 90%, transparent);
+            color: var(--text);
             font-size: 18px;
             cursor: pointer;
             transition: all 0.2s;
             backdrop-filter: blur(10px);
         }}
-        #controls button:hover {{ background: #0f3460; transform: scale(1.05); }}
-        #controls button.active {{ background: #e94560; }}
+        #controls button:hover {{ background: var(--border); transform: scale(1.05); }}
+        #controls button.active {{ background: var(--accent); }}
         /* Inspector Panel */
         #inspector {{
             position: fixed;
@@ -204,25 +569,25 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             right: 0;
             width: 320px;
             bottom: 0;
-            background: #16213e;
-            border-left: 1px solid #0f3460;
+            background: var(--surface);
+            border-left: 1px solid var(--border);
             padding: 20px;
             overflow-y: auto;
             z-index: 1000;
         }}
         #inspector h2 {{
-            color: #e94560;
+            color: var(--accent);
             font-size: 16px;
             margin-bottom: 15px;
             padding-bottom: 12px;
-            border-bottom: 1px solid #0f3460;
+            border-bottom: 1px solid var(--border);
             display: flex;
             align-items: center;
             gap: 10px;
         }}
         #inspector .section {{ margin-bottom: 20px; }}
         #inspector .section h3 {{
-            color: #888;
+            color: var(--muted);
             font-size: 10px;
             text-transform: uppercase;
             letter-spacing: 1.5px;
@@ -230,76 +595,76 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         }}
         #inspector .value {{
             font-size: 15px;
-            color: #fff;
+            color: var(--text-strong);
             margin-bottom: 6px;
         }}
         #inspector .stat-row {{
             display: flex;
             justify-content: space-between;
             padding: 10px 0;
-            border-bottom: 1px solid rgba(255,255,255,0.05);
+            border-bottom: 1px solid color-mix(in srgb, var(--text) 5%, transparent);
         }}
-        #inspector .stat-label {{ color: #666; }}
+        #inspector .stat-label {{ color: var(--faint); }}
         #inspector .stat-num {{ font-weight: bold; }}
-        #inspector .stat-num.green {{ color: #90EE90; }}
-        #inspector .stat-num.red {{ color: #F08080; }}
+        #inspector .stat-num.green {{ color: var(--alive); }}
+        #inspector .stat-num.red {{ color: var(--dead); }}
         #inspector .cluster-tag {{
             display: inline-block;
             padding: 5px 12px;
-            background: rgba(233, 69, 96, 0.15);
-            color: #e94560;
+            background: color-mix(in srgb, var(--accent) 15%, transparent);
+            color: var(--accent);
             border-radius: 6px;
             font-size: 12px;
             margin-top: 6px;
         }}
-        #inspector .empty {{ color: #555; font-style: italic; }}
+        #inspector .empty {{ color: var(--faint); font-style: italic; }}
         #inspector .dep-list {{ max-height: 160px; overflow-y: auto; }}
         #inspector .dep-item {{
             padding: 6px 10px;
             margin: 3px 0;
-            background: rgba(255,255,255,0.03);
+            background: color-mix(in srgb, var(--text) 3%, transparent);
             border-radius: 6px;
             font-size: 12px;
             cursor: pointer;
             transition: background 0.15s;
         }}
-        #inspector .dep-item:hover {{ background: rgba(233, 69, 96, 0.2); }}
-        #inspector .dep-item.dead {{ color: #F08080; }}
+        #inspector .dep-item:hover {{ background: color-mix(in srgb, var(--accent) 20%, transparent); }}
+        #inspector .dep-item.dead {{ color: var(--dead); }}
         /* Action Buttons */
         #inspector .actions {{ display: flex; flex-direction: column; gap: 8px; margin-top: 10px; }}
         #inspector .action-btn {{
             display: flex; align-items: center; gap: 8px;
             padding: 10px 14px;
-            background: rgba(233, 69, 96, 0.15);
-            border: 1px solid rgba(233, 69, 96, 0.3);
+            background: color-mix(in srgb, var(--accent) 15%, transparent);
+            border: 1px solid color-mix(in srgb, var(--accent) 30%, transparent);
             border-radius: 6px;
-            color: #e94560;
+            color: var(--accent);
             font-size: 12px;
             cursor: pointer;
             transition: all 0.2s;
         }}
-        #inspector .action-btn:hover {{ background: rgba(233, 69, 96, 0.25); border-color: rgba(233, 69, 96, 0.5); }}
-        #inspector .action-btn.success {{ background: rgba(144, 238, 144, 0.15); border-color: rgba(144, 238, 144, 0.3); color: #90EE90; }}
-        #inspector .action-btn.danger {{ background: rgba(240, 128, 128, 0.15); border-color: rgba(240, 128, 128, 0.3); color: #F08080; }}
+        #inspector .action-btn:hover {{ background: color-mix(in srgb, var(--accent) 25%, transparent); border-color: color-mix(in srgb, var(--accent) 50%, transparent); }}
+        #inspector .action-btn.success {{ background: color-mix(in srgb, var(--alive) 15%, transparent); border-color: color-mix(in srgb, var(--alive) 30%, transparent); color: var(--alive); }}
+        #inspector .action-btn.danger {{ background: color-mix(in srgb, var(--dead) 15%, transparent); border-color: color-mix(in srgb, var(--dead) 30%, transparent); color: var(--dead); }}
         #inspector .cmd-box {{
-            background: #0d1117; border: 1px solid #30363d; border-radius: 6px;
+            background: var(--code-background); border: 1px solid var(--code-border); border-radius: 6px;
             padding: 10px; margin-top: 8px; font-family: 'Consolas', monospace;
-            font-size: 11px; color: #c9d1d9; word-break: break-all; position: relative;
+            font-size: 11px; color: var(--code-text); word-break: break-all; position: relative;
         }}
         #inspector .cmd-box .copy-btn {{
             position: absolute; top: 6px; right: 6px; padding: 4px 8px;
-            background: #21262d; border: 1px solid #30363d; border-radius: 4px;
-            color: #8b949e; font-size: 10px; cursor: pointer;
+            background: var(--code-surface); border: 1px solid var(--code-border); border-radius: 4px;
+            color: var(--muted); font-size: 10px; cursor: pointer;
         }}
-        #inspector .cmd-box .copy-btn:hover {{ background: #30363d; color: #c9d1d9; }}
+        #inspector .cmd-box .copy-btn:hover {{ background: var(--code-border); color: var(--code-text); }}
         #inspector .badge {{ display: inline-block; padding: 2px 8px; border-radius: 4px; font-size: 10px; font-weight: bold; text-transform: uppercase; margin-left: 5px; }}
-        #inspector .badge.pub {{ background: rgba(144, 238, 144, 0.2); color: #90EE90; }}
-        #inspector .badge.priv {{ background: rgba(100, 100, 100, 0.2); color: #888; }}
+        #inspector .badge.pub {{ background: color-mix(in srgb, var(--alive) 20%, transparent); color: var(--alive); }}
+        #inspector .badge.priv {{ background: color-mix(in srgb, var(--faint) 20%, transparent); color: var(--muted); }}
         /* Toast */
         #toast {{
-            position: fixed; bottom: 80px; right: 340px; background: #16213e;
-            border: 1px solid #0f3460; border-radius: 8px; padding: 12px 20px;
-            color: #90EE90; font-size: 13px; opacity: 0; transform: translateY(20px);
+            position: fixed; bottom: 80px; right: 340px; background: var(--surface);
+            border: 1px solid var(--border); border-radius: 8px; padding: 12px 20px;
+            color: var(--alive); font-size: 13px; opacity: 0; transform: translateY(20px);
             transition: all 0.3s; z-index: 3000;
         }}
         #toast.visible {{ opacity: 1; transform: translateY(0); }}
@@ -337,13 +702,15 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         <button id="toggle-bundling" title="Edge Bundling" class="active">B</button>
         <button id="toggle-clusters" title="Cluster Gravity" class="active">C</button>
         <button id="toggle-boxes" title="Cluster Boxes" class="active">□</button>
+        <button id="toggle-crates" title="Collapse/Expand Crates" style="display: none;">K</button>
+        <button id="toggle-lod" title="Collapse All Clusters" style="display: none;">L</button>
         <button id="toggle-sim" title="Pause Simulation">⏸</button>
         <button id="clear-highlight" title="Clear Highlight (Esc)">✕</button>
     </div>
     <div id="toast"></div>
 
     <div id="inspector">
-        <h2>📋 Module Inspector</h2>
+        <h2>📋 {heading} Inspector</h2>
         <div id="inspector-content">
             <p class="empty">Click a node to inspect</p>
         </div>
@@ -356,18 +723,32 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         const nodes = {nodes_json};
         console.log('Deadmod: Loaded', nodes.length, 'nodes');
         const edges = {edges_json};
-        const clusters = [{clusters_json}];
+        const clusters = {clusters_json};
+        const removable = {removable};
+        const crates = {crates_json};
+        const lod = {lod_json};
+
+        // Theme colors, and as PixiJS numbers
+        const theme = {theme_json};
+        const hex = color => parseInt(color.slice(1), 16);
+
+        // Names and paths are data: escape them in markup, and pass them to
+        // inline handlers as attribute-escaped string literals
+        const esc = s => String(s).replace(/[&<>"']/g, c => ({{ '&': '&amp;', '<': '&lt;', '>': '&gt;', '"': '&quot;', "'": '&#39;' }})[c]);
+        const arg = s => esc(JSON.stringify(String(s)));
 
         // Settings
         let edgeBundling = true;
         let clusterGravity = true;
-        let simRunning = true;
+        // Positions computed by deadmod are final: start paused
+        const staticLayout = {static_layout};
+        let simRunning = !staticLayout;
 
         // PixiJS setup (v7.x constructor initialization)
         const container = document.getElementById('canvas-container');
         const app = new PIXI.Application({{
             resizeTo: container,
-            backgroundColor: 0x0d0d1a,
+            backgroundColor: hex(theme.backdrop),
             antialias: true,
             resolution: window.devicePixelRatio || 1,
             autoDensity: true,
@@ -384,16 +765,18 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         worldContainer.addChild(clusterGraphics);  // Draw clusters first (behind)
         worldContainer.addChild(edgeGraphics);
         worldContainer.addChild(nodeContainer);
+        const crateLayer = new PIXI.Container();  // Crate headers and collapsed crates (on top)
+        worldContainer.addChild(crateLayer);
         app.stage.addChild(worldContainer);
 
         // Toggle for cluster boxes visibility
         let showClusterBoxes = true;
 
         // Colors
-        const ALIVE_COLOR = 0x90EE90;
-        const DEAD_COLOR = 0xF08080;
-        const CLUSTER_COLORS = [0xe94560, 0x0f3460, 0x533483, 0x16c79a, 0xf7be16,
-                                0xff6b6b, 0x4ecdc4, 0x45b7d1, 0x96c93d, 0xdfe6e9];
+        const ALIVE_COLOR = hex(theme.alive);
+        const DEAD_COLOR = hex(theme.dead);
+        const CRATE_COLOR = hex(theme.crateGroup);
+        const CLUSTER_COLORS = theme.clusters.map(hex);
 
         const clusterColorMap = {{}};
         clusters.forEach((c, i) => {{ clusterColorMap[c.id] = CLUSTER_COLORS[i % CLUSTER_COLORS.length]; }});
@@ -404,27 +787,61 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         let selectedNode = null;
         let highlightedNodes = new Set();
 
-        // Initialize nodes
-        nodes.forEach((n, i) => {{
-            const angle = (i / nodes.length) * Math.PI * 2;
-            const radius = Math.min(400, nodes.length * 12);
+        // Level of detail (huge graphs): clusters start collapsed into
+        // super-nodes, at the center of their modules' seeded positions
+        const collapsedClusters = new Set(lod ? lod.clusters.map(c => c.id) : []);
+        const clusterGroups = {{}};
+        const clusterMembers = {{}};
+        if (lod) {{
+            const centers = {{}};
+            nodes.forEach(n => {{
+                const c = centers[n.cluster] || (centers[n.cluster] = {{ x: 0, y: 0, count: 0 }});
+                c.x += n.x;
+                c.y += n.y;
+                c.count++;
+            }});
+            lod.clusters.forEach(c => {{
+                const center = centers[c.id] || {{ x: 0, y: 0, count: 1 }};
+                clusterGroups[c.id] = {{
+                    ...c,
+                    key: 'cluster:' + c.id,
+                    cluster: c.id,
+                    isCluster: true,
+                    radius: Math.min(90, 18 + Math.sqrt(c.count) * 4),
+                    x: center.x / center.count,
+                    y: center.y / center.count,
+                    vx: 0, vy: 0,
+                }};
+                clusterMembers[c.id] = [];
+            }});
+        }}
+
+        // Initialize nodes at their seeded positions, remembering their offset
+        // from a collapsed cluster to expand it the same way every time
+        nodes.forEach(n => {{
+            const home = clusterGroups[n.cluster];
             nodeMap[n.id] = {{
                 ...n,
-                x: Math.cos(angle) * radius + (Math.random() - 0.5) * 100,
-                y: Math.sin(angle) * radius + (Math.random() - 0.5) * 100,
+                dx: home ? n.x - home.x : 0,
+                dy: home ? n.y - home.y : 0,
                 vx: 0, vy: 0,
             }};
+            if (home) clusterMembers[n.cluster].push(n.id);
+
+            // Sprites of clustered nodes are created on first expansion
+            if (!lod) createSprite(n);
+        }});
 
-            // Create sprite
+        function createSprite(n) {{
             const g = new PIXI.Graphics();
             const color = n.status === 'dead' ? DEAD_COLOR : ALIVE_COLOR;
             g.beginFill(color, 0.9);
-            g.lineStyle(2, n.status === 'dead' ? 0xcc4444 : 0x44aa44);
+            g.lineStyle(2, n.status === 'dead' ? hex(theme.deadStroke) : hex(theme.aliveStroke));
             g.drawRoundedRect(-30, -12, 60, 24, 6);
             g.endFill();
 
             // Cluster dot
-            const clusterColor = clusterColorMap[n.cluster] || 0x666666;
+            const clusterColor = clusterColorMap[n.cluster] || hex(theme.faint);
             g.beginFill(clusterColor);
             g.drawCircle(22, -6, 4);
             g.endFill();
@@ -433,7 +850,7 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             const label = new PIXI.Text(n.label.length > 10 ? n.label.slice(0, 8) + '..' : n.label, {{
                 fontFamily: 'Segoe UI',
                 fontSize: 11,
-                fill: 0x1a1a2e,
+                fill: hex(theme.nodeText),
                 fontWeight: 'bold',
             }});
             label.anchor.set(0.5);
@@ -445,7 +862,12 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
 
             nodeContainer.addChild(g);
             nodeSprites[n.id] = g;
-        }});
+        }}
+
+        function ensureSprite(id) {{
+            if (!nodeSprites[id]) createSprite(nodeMap[id]);
+            nodeSprites[id].visible = !isHidden(nodeMap[id]);
+        }}
 
         // Adjacency
         const inbound = {{}};
@@ -456,6 +878,148 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             if (outbound[e.from]) outbound[e.from].push(e.to);
         }});
 
+        // Crate super-clusters (workspace mode): clickable header when expanded,
+        // a single stand-in node when collapsed
+        const collapsedCrates = new Set();
+        const crateGroups = {{}};
+        crates.forEach(name => {{
+            const members = nodes.filter(n => n.crate === name);
+            const dead = members.filter(n => n.status === 'dead').length;
+
+            const proxy = new PIXI.Graphics();
+            proxy.beginFill(dead === members.length ? DEAD_COLOR : CRATE_COLOR, 0.95);
+            proxy.lineStyle(2, dead > 0 ? hex(theme.deadStroke) : hex(theme.textStrong));
+            proxy.drawRoundedRect(-60, -22, 120, 44, 10);
+            proxy.endFill();
+            const proxyText = new PIXI.Text(`▸ ${{name}}\n${{members.length}} {noun}s, ${{dead}} dead`, {{
+                fontFamily: 'Segoe UI',
+                fontSize: 11,
+                fill: hex(theme.textStrong),
+                fontWeight: 'bold',
+                align: 'center',
+            }});
+            proxyText.anchor.set(0.5);
+            proxy.addChild(proxyText);
+            proxy.visible = false;
+
+            const header = new PIXI.Text(`▾ ${{name}}`, {{
+                fontFamily: 'Segoe UI',
+                fontSize: 12,
+                fill: hex(theme.textStrong),
+                fontWeight: 'bold',
+            }});
+
+            [proxy, header].forEach(obj => {{
+                obj.eventMode = 'static';
+                obj.cursor = 'pointer';
+                obj.on('pointerdown', () => toggleCrate(name));
+                crateLayer.addChild(obj);
+            }});
+            crateGroups[name] = {{ key: 'crate:' + name, proxy, header, x: 0, y: 0, minX: 0, minY: 0, maxX: 0, maxY: 0, count: 0 }};
+        }});
+
+        // Collapsed cluster super-nodes, sized by module count
+        Object.values(clusterGroups).forEach(c => {{
+            const proxy = new PIXI.Graphics();
+            proxy.beginFill(c.dead === c.count ? DEAD_COLOR : (clusterColorMap[c.id] || hex(theme.faint)), 0.9);
+            proxy.lineStyle(2, c.dead > 0 ? hex(theme.deadStroke) : hex(theme.textStrong));
+            proxy.drawCircle(0, 0, c.radius);
+            proxy.endFill();
+            const text = new PIXI.Text(`${{c.id}}\n${{c.count}} {noun}s, ${{c.dead}} dead`, {{
+                fontFamily: 'Segoe UI',
+                fontSize: 11,
+                fill: hex(theme.textStrong),
+                fontWeight: 'bold',
+                align: 'center',
+            }});
+            text.anchor.set(0.5);
+            proxy.addChild(text);
+            proxy.eventMode = 'static';
+            proxy.cursor = 'pointer';
+            proxy.on('pointerdown', () => toggleCluster(c.id));
+            nodeContainer.addChild(proxy);
+            c.sprite = proxy;
+        }});
+
+        // Where a node currently sits: its collapsed cluster, or itself
+        function body(n) {{
+            return collapsedClusters.has(n.cluster) ? clusterGroups[n.cluster] : n;
+        }}
+
+        function updateCrateGroups() {{
+            Object.values(crateGroups).forEach(g => {{
+                g.x = 0; g.y = 0; g.count = 0;
+                g.minX = Infinity; g.minY = Infinity; g.maxX = -Infinity; g.maxY = -Infinity;
+            }});
+            Object.values(nodeMap).forEach(n => {{
+                const g = crateGroups[n.crate];
+                if (!g) return;
+                const p = body(n);
+                g.x += p.x; g.y += p.y; g.count++;
+                g.minX = Math.min(g.minX, p.x - 40);
+                g.minY = Math.min(g.minY, p.y - 20);
+                g.maxX = Math.max(g.maxX, p.x + 40);
+                g.maxY = Math.max(g.maxY, p.y + 20);
+            }});
+            Object.entries(crateGroups).forEach(([name, g]) => {{
+                if (g.count > 0) {{ g.x /= g.count; g.y /= g.count; }}
+                const collapsed = collapsedCrates.has(name);
+                g.proxy.visible = collapsed && g.count > 0;
+                g.proxy.position.set(g.x, g.y);
+                g.header.visible = !collapsed && g.count > 0 && crates.length > 1;
+                g.header.position.set(g.minX - 25, g.minY - 50);
+            }});
+        }}
+
+        // Nodes inside a collapsed crate are drawn as the crate itself, nodes
+        // inside a collapsed cluster as the cluster
+        function isHidden(n) {{
+            return (!!n.crate && collapsedCrates.has(n.crate)) || collapsedClusters.has(n.cluster);
+        }}
+        function endpoint(n) {{
+            if (n.crate && collapsedCrates.has(n.crate)) return crateGroups[n.crate];
+            return body(n);
+        }}
+
+        function refreshVisibility() {{
+            Object.entries(nodeSprites).forEach(([id, sprite]) => {{ sprite.visible = !isHidden(nodeMap[id]); }});
+            Object.values(clusterGroups).forEach(c => {{ c.sprite.visible = endpoint(c) === c; }});
+        }}
+
+        function toggleCrate(name) {{
+            if (collapsedCrates.has(name)) {{
+                collapsedCrates.delete(name);
+            }} else {{
+                collapsedCrates.add(name);
+            }}
+            refreshVisibility();
+        }}
+
+        // Expanding lays a cluster's modules out around it; collapsing gathers
+        // them back into one super-node at their center
+        function toggleCluster(id) {{
+            const c = clusterGroups[id];
+            if (!c) return;
+            const members = clusterMembers[id].map(m => nodeMap[m]);
+            if (collapsedClusters.has(id)) {{
+                collapsedClusters.delete(id);
+                members.forEach(n => {{
+                    n.x = c.x + n.dx;
+                    n.y = c.y + n.dy;
+                    n.vx = 0; n.vy = 0;
+                    ensureSprite(n.id);
+                }});
+            }} else if (members.length > 0) {{
+                collapsedClusters.add(id);
+                c.x = members.reduce((sum, n) => sum + n.x, 0) / members.length;
+                c.y = members.reduce((sum, n) => sum + n.y, 0) / members.length;
+                c.vx = 0; c.vy = 0;
+            }}
+            refreshVisibility();
+        }}
+        window.toggleCluster = toggleCluster;
+        refreshVisibility();
+
         // Cluster centers and bounding boxes
         const clusterCenters = {{}};
         const clusterBounds = {{}};  // Store bounding boxes per top-level cluster
@@ -473,18 +1037,19 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             }});
 
             Object.values(nodeMap).forEach(n => {{
+                const p = body(n);
                 // Update cluster centers
                 if (clusterCenters[n.cluster]) {{
-                    clusterCenters[n.cluster].x += n.x;
-                    clusterCenters[n.cluster].y += n.y;
+                    clusterCenters[n.cluster].x += p.x;
+                    clusterCenters[n.cluster].y += p.y;
                     clusterCenters[n.cluster].count++;
                 }}
                 // Update top-level cluster bounds
                 if (clusterBounds[n.topCluster]) {{
-                    clusterBounds[n.topCluster].minX = Math.min(clusterBounds[n.topCluster].minX, n.x - 40);
-                    clusterBounds[n.topCluster].minY = Math.min(clusterBounds[n.topCluster].minY, n.y - 20);
-                    clusterBounds[n.topCluster].maxX = Math.max(clusterBounds[n.topCluster].maxX, n.x + 40);
-                    clusterBounds[n.topCluster].maxY = Math.max(clusterBounds[n.topCluster].maxY, n.y + 20);
+                    clusterBounds[n.topCluster].minX = Math.min(clusterBounds[n.topCluster].minX, p.x - 40);
+                    clusterBounds[n.topCluster].minY = Math.min(clusterBounds[n.topCluster].minY, p.y - 20);
+                    clusterBounds[n.topCluster].maxX = Math.max(clusterBounds[n.topCluster].maxX, p.x + 40);
+                    clusterBounds[n.topCluster].maxY = Math.max(clusterBounds[n.topCluster].maxY, p.y + 20);
                     clusterBounds[n.topCluster].count++;
                 }}
             }});
@@ -499,9 +1064,9 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             if (!showClusterBoxes) return;
 
             Object.entries(clusterBounds).forEach(([name, bounds]) => {{
-                if (bounds.count === 0 || name === 'root') return;
+                if (bounds.count === 0 || name === 'root' || collapsedCrates.has(name)) return;
 
-                const color = clusterColorMap[name] || 0x333366;
+                const color = clusterColorMap[name] || hex(theme.faint);
                 const padding = 25;
                 const x = bounds.minX - padding;
                 const y = bounds.minY - padding;
@@ -527,7 +1092,10 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         function simulate() {{
             if (!simRunning) return;
 
-            const allNodes = Object.values(nodeMap);
+            // Collapsed clusters move as one body in place of their modules
+            const allNodes = Object.values(nodeMap)
+                .filter(n => !collapsedClusters.has(n.cluster))
+                .concat(Object.values(clusterGroups).filter(c => collapsedClusters.has(c.id)));
             if (clusterGravity) updateClusterCenters();
 
             // Repulsion
@@ -544,16 +1112,24 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                 }}
             }}
 
-            // Edge attraction
-            edges.forEach(e => {{
-                const a = nodeMap[e.from], b = nodeMap[e.to];
-                if (!a || !b) return;
+            // Edge attraction (once per aggregated edge between collapsed clusters)
+            const attract = (a, b, rest) => {{
                 let dx = b.x - a.x, dy = b.y - a.y;
                 let dist = Math.sqrt(dx * dx + dy * dy) || 1;
-                let force = (dist - 120) * 0.04;
+                let force = (dist - rest) * 0.04;
                 let fx = (dx / dist) * force, fy = (dy / dist) * force;
                 a.vx += fx; a.vy += fy;
                 b.vx -= fx; b.vy -= fy;
+            }};
+            edges.forEach(e => {{
+                if (!nodeMap[e.from] || !nodeMap[e.to]) return;
+                const a = body(nodeMap[e.from]), b = body(nodeMap[e.to]);
+                if (a === b || (a.isCluster && b.isCluster)) return;
+                attract(a, b, 120);
+            }});
+            if (lod) lod.edges.forEach(e => {{
+                const a = clusterGroups[e.from], b = clusterGroups[e.to];
+                if (collapsedClusters.has(e.from) && collapsedClusters.has(e.to)) attract(a, b, a.radius + b.radius + 120);
             }});
 
             // Center gravity
@@ -573,6 +1149,17 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                 }});
             }}
 
+            // Crate gravity - keep each crate's modules together
+            if (crates.length > 1) {{
+                allNodes.forEach(n => {{
+                    const g = crateGroups[n.crate];
+                    if (g && g.count > 1) {{
+                        n.vx += (g.x - n.x) * 0.001;
+                        n.vy += (g.y - n.y) * 0.001;
+                    }}
+                }});
+            }}
+
             // Apply
             allNodes.forEach(n => {{
                 n.vx *= 0.88; n.vy *= 0.88;
@@ -584,52 +1171,77 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         function drawEdges() {{
             edgeGraphics.clear();
 
+            // Aggregated edges between collapsed clusters, wider the more
+            // module edges they stand for
+            if (lod) lod.edges.forEach(e => {{
+                const a = clusterGroups[e.from], b = clusterGroups[e.to];
+                if (endpoint(a) !== a || endpoint(b) !== b) return;
+                const alpha = highlightedNodes.size === 0 ? 0.6 : 0.05;
+                edgeGraphics.lineStyle(Math.min(8, 1.5 + Math.log2(e.count)), e.crossCrate ? CRATE_COLOR : hex(theme.edge), alpha);
+                drawLink(a, b);
+            }});
+
+            // Edges into collapsed crates or clusters end there, once per pair
+            const drawnGroupEdges = new Set();
             edges.forEach(e => {{
-                const a = nodeMap[e.from], b = nodeMap[e.to];
-                if (!a || !b) return;
+                const src = nodeMap[e.from], dst = nodeMap[e.to];
+                if (!src || !dst) return;
+                const a = endpoint(src), b = endpoint(dst);
+                if (!a || !b || a === b || (a.isCluster && b.isCluster)) return;
+                if (a !== src || b !== dst) {{
+                    const key = `${{a === src ? e.from : a.key}}>${{b === dst ? e.to : b.key}}`;
+                    if (drawnGroupEdges.has(key)) return;
+                    drawnGroupEdges.add(key);
+                }}
 
                 // Highlighting logic for edges
                 const edgeHighlighted = highlightedNodes.size === 0 ||
                     (highlightedNodes.has(e.from) && highlightedNodes.has(e.to));
 
-                const isDead = a.status === 'dead' || b.status === 'dead';
-                const alpha = edgeHighlighted ? (isDead ? 0.4 : 0.5) : 0.05;
-                const color = edgeHighlighted && highlightedNodes.size > 0 ? 0xf7be16 : (isDead ? 0xF08080 : 0x555555);
-                const lineWidth = edgeHighlighted && highlightedNodes.size > 0 ? 2.5 : 1.5;
+                const isDead = src.status === 'dead' || dst.status === 'dead';
+                const alpha = edgeHighlighted ? (e.crossCrate ? 0.8 : (isDead ? 0.4 : 0.5)) : 0.05;
+                // Cross-crate edges stand out in the crate color
+                const baseColor = e.crossCrate ? CRATE_COLOR : (isDead ? hex(theme.dead) : hex(theme.edge));
+                const color = edgeHighlighted && highlightedNodes.size > 0 ? hex(theme.highlight) : baseColor;
+                const lineWidth = edgeHighlighted && highlightedNodes.size > 0 ? 2.5 : (e.crossCrate ? 2.5 : 1.5);
                 edgeGraphics.lineStyle(lineWidth, color, alpha);
+                drawLink(a, b);
+            }});
+        }}
 
-                if (edgeBundling) {{
-                    const mx = (a.x + b.x) / 2, my = (a.y + b.y) / 2;
-                    let cpx, cpy;
-                    if (a.cluster === b.cluster && clusterCenters[a.cluster]) {{
-                        const c = clusterCenters[a.cluster];
-                        cpx = mx + (c.x - mx) * 0.35;
-                        cpy = my + (c.y - my) * 0.35;
-                    }} else {{
-                        cpx = mx * 0.75; cpy = my * 0.75;
-                    }}
-                    edgeGraphics.moveTo(a.x, a.y);
-                    edgeGraphics.quadraticCurveTo(cpx, cpy, b.x, b.y);
+        function drawLink(a, b) {{
+            if (edgeBundling) {{
+                const mx = (a.x + b.x) / 2, my = (a.y + b.y) / 2;
+                let cpx, cpy;
+                if (a.cluster === b.cluster && clusterCenters[a.cluster]) {{
+                    const c = clusterCenters[a.cluster];
+                    cpx = mx + (c.x - mx) * 0.35;
+                    cpy = my + (c.y - my) * 0.35;
                 }} else {{
-                    edgeGraphics.moveTo(a.x, a.y);
-                    edgeGraphics.lineTo(b.x, b.y);
+                    cpx = mx * 0.75; cpy = my * 0.75;
                 }}
+                edgeGraphics.moveTo(a.x, a.y);
+                edgeGraphics.quadraticCurveTo(cpx, cpy, b.x, b.y);
+            }} else {{
+                edgeGraphics.moveTo(a.x, a.y);
+                edgeGraphics.lineTo(b.x, b.y);
+            }}
 
-                // Arrow
-                const angle = Math.atan2(b.y - a.y, b.x - a.x);
-                const dist = Math.sqrt((b.x - a.x) ** 2 + (b.y - a.y) ** 2);
-                const ratio = Math.max(0, (dist - 35) / dist);
-                const ax = a.x + (b.x - a.x) * ratio;
-                const ay = a.y + (b.y - a.y) * ratio;
-                const al = 8;
-                edgeGraphics.moveTo(ax, ay);
-                edgeGraphics.lineTo(ax - al * Math.cos(angle - 0.4), ay - al * Math.sin(angle - 0.4));
-                edgeGraphics.moveTo(ax, ay);
-                edgeGraphics.lineTo(ax - al * Math.cos(angle + 0.4), ay - al * Math.sin(angle + 0.4));
-            }});
+            // Arrow
+            const angle = Math.atan2(b.y - a.y, b.x - a.x);
+            const dist = Math.sqrt((b.x - a.x) ** 2 + (b.y - a.y) ** 2);
+            const ratio = Math.max(0, (dist - (b.radius || 30) - 5) / dist);
+            const ax = a.x + (b.x - a.x) * ratio;
+            const ay = a.y + (b.y - a.y) * ratio;
+            const al = 8;
+            edgeGraphics.moveTo(ax, ay);
+            edgeGraphics.lineTo(ax - al * Math.cos(angle - 0.4), ay - al * Math.sin(angle - 0.4));
+            edgeGraphics.moveTo(ax, ay);
+            edgeGraphics.lineTo(ax - al * Math.cos(angle + 0.4), ay - al * Math.sin(angle + 0.4));
         }}
 
         function updateNodePositions() {{
+            Object.values(clusterGroups).forEach(c => c.sprite.position.set(c.x, c.y));
             Object.entries(nodeMap).forEach(([id, n]) => {{
                 const sprite = nodeSprites[id];
                 if (sprite) {{
@@ -658,6 +1270,7 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         function selectNode(id) {{
             const node = nodeMap[id];
             if (!node) return;
+            if (collapsedClusters.has(node.cluster)) toggleCluster(node.cluster);
             selectedNode = node;
 
             const deps = outbound[id] || [];
@@ -667,16 +1280,16 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
 
             document.getElementById('inspector-content').innerHTML = `
                 <div class="section">
-                    <h3>Module</h3>
+                    <h3>{heading}</h3>
                     <div class="value">
-                        ${{node.label}}
+                        ${{esc(node.modulePath || node.label)}}
                         <span class="badge ${{node.visibility === 'public' ? 'pub' : 'priv'}}">${{node.visibility || 'private'}}</span>
                     </div>
-                    <span class="cluster-tag">${{node.cluster}}</span>
+                    <span class="cluster-tag">${{esc(node.cluster)}}</span>
                 </div>
                 <div class="section">
                     <h3>Status</h3>
-                    <div class="value" style="color: ${{node.status === 'dead' ? '#F08080' : '#90EE90'}}">${{node.status.toUpperCase()}}</div>
+                    <div class="value" style="color: ${{node.status === 'dead' ? theme.dead : theme.alive}}">${{node.status.toUpperCase()}}</div>
                 </div>
                 <div class="section">
                     <h3>Statistics</h3>
@@ -688,36 +1301,41 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                 <div class="section">
                     <h3>Dependencies (${{deps.length}})</h3>
                     <div class="dep-list">
-                        ${{deps.length ? deps.map(d => `<div class="dep-item ${{nodeMap[d]?.status === 'dead' ? 'dead' : ''}}" onclick="window.selectNode('${{d}}')">${{d}}</div>`).join('') : '<span class="empty">None</span>'}}
+                        ${{deps.length ? deps.map(d => `<div class="dep-item ${{nodeMap[d]?.status === 'dead' ? 'dead' : ''}}" onclick="window.selectNode(${{arg(d)}})">${{esc(d)}}</div>`).join('') : '<span class="empty">None</span>'}}
                     </div>
                 </div>
                 <div class="section">
                     <h3>Dependents (${{dependents.length}})</h3>
                     <div class="dep-list">
-                        ${{dependents.length ? dependents.map(d => `<div class="dep-item ${{nodeMap[d]?.status === 'dead' ? 'dead' : ''}}" onclick="window.selectNode('${{d}}')">${{d}}</div>`).join('') : '<span class="empty">None</span>'}}
+                        ${{dependents.length ? dependents.map(d => `<div class="dep-item ${{nodeMap[d]?.status === 'dead' ? 'dead' : ''}}" onclick="window.selectNode(${{arg(d)}})">${{esc(d)}}</div>`).join('') : '<span class="empty">None</span>'}}
                     </div>
                 </div>
                 <div class="section">
                     <h3>Path</h3>
                     <div class="cmd-box">
-                        ${{node.path}}
-                        <button class="copy-btn" onclick="window.copyToClipboard('${{node.path.replace(/\\/g, '\\\\\\\\')}}')">Copy</button>
+                        ${{esc(node.path)}}
+                        <button class="copy-btn" onclick="window.copyToClipboard(${{arg(node.path)}})">Copy</button>
                     </div>
                 </div>
                 <div class="section">
                     <h3>Actions</h3>
                     <div class="actions">
-                        <button class="action-btn" onclick="window.copyToClipboard('${{node.path.replace(/\\/g, '\\\\\\\\')}}')">
+                        <button class="action-btn" onclick="window.copyToClipboard(${{arg(node.path)}})">
                             <span class="icon">📋</span> Copy Path
                         </button>
-                        <button class="action-btn" onclick="window.copyDeadmodCommand('${{node.id}}')">
+                        <button class="action-btn" onclick="window.copyDeadmodCommand(${{arg(node.id)}})">
                             <span class="icon">⚡</span> Copy Deadmod Command
                         </button>
-                        <button class="action-btn success" onclick="window.highlightConnections('${{node.id}}')">
+                        <button class="action-btn success" onclick="window.highlightConnections(${{arg(node.id)}})">
                             <span class="icon">🔍</span> Highlight Connections
                         </button>
-                        ${{node.status === 'dead' ? `
-                        <button class="action-btn danger" onclick="window.showRemoveCommand('${{node.path.replace(/\\/g, '\\\\\\\\')}}')">
+                        ${{lod ? `
+                        <button class="action-btn" onclick="window.toggleCluster(${{arg(node.cluster)}})">
+                            <span class="icon">📦</span> Collapse Cluster
+                        </button>
+                        ` : ''}}
+                        ${{node.status === 'dead' && removable ? `
+                        <button class="action-btn danger" onclick="window.showRemoveCommand(${{arg(node.path)}})">
                             <span class="icon">🗑️</span> Show Remove Command
                         </button>
                         ` : ''}}
@@ -736,7 +1354,7 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         function showToast(message, type = 'success') {{
             const toast = document.getElementById('toast');
             toast.textContent = message;
-            toast.style.color = type === 'success' ? '#90EE90' : '#F08080';
+            toast.style.color = type === 'success' ? theme.alive : theme.dead;
             toast.classList.add('visible');
             clearTimeout(toastTimeout);
             toastTimeout = setTimeout(() => toast.classList.remove('visible'), 2000);
@@ -767,7 +1385,7 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
             highlightedNodes.add(moduleId);
             (outbound[moduleId] || []).forEach(d => highlightedNodes.add(d));
             (inbound[moduleId] || []).forEach(d => highlightedNodes.add(d));
-            showToast(`Highlighted ${{highlightedNodes.size}} connected modules`);
+            showToast(`Highlighted ${{highlightedNodes.size}} connected {noun}s`);
         }};
 
         // Clear highlights
@@ -833,10 +1451,29 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
         const clusterBtn = document.getElementById('toggle-clusters');
         clusterBtn.onclick = () => {{ clusterGravity = !clusterGravity; clusterBtn.classList.toggle('active', clusterGravity); }};
 
+        const cratesBtn = document.getElementById('toggle-crates');
+        if (crates.length > 1) cratesBtn.style.display = '';
+        cratesBtn.onclick = () => {{
+            const collapseAll = collapsedCrates.size < crates.length;
+            crates.forEach(name => {{
+                if (collapsedCrates.has(name) !== collapseAll) toggleCrate(name);
+            }});
+            cratesBtn.classList.toggle('active', collapsedCrates.size > 0);
+        }};
+
+        const lodBtn = document.getElementById('toggle-lod');
+        if (lod) lodBtn.style.display = '';
+        lodBtn.onclick = () => {{
+            Object.keys(clusterGroups).forEach(id => {{
+                if (!collapsedClusters.has(id)) toggleCluster(id);
+            }});
+        }};
+
         const boxBtn = document.getElementById('toggle-boxes');
         boxBtn.onclick = () => {{ showClusterBoxes = !showClusterBoxes; boxBtn.classList.toggle('active', showClusterBoxes); }};
 
         const simBtn = document.getElementById('toggle-sim');
+        simBtn.textContent = simRunning ? '⏸' : '▶';
         simBtn.onclick = () => {{
             simRunning = !simRunning;
             simBtn.textContent = simRunning ? '⏸' : '▶';
@@ -860,6 +1497,7 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
 
         app.ticker.add(() => {{
             simulate();
+            if (crates.length > 0) updateCrateGroups();
             drawClusterBoxes();  // Draw cluster bounding boxes
             drawEdges();
             updateNodePositions();
@@ -873,6 +1511,7 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
                 lastTime = now;
             }}
         }});
+        if (lod) showToast(`${{nodes.length}} {noun}s grouped into ${{lod.clusters.length}} clusters - click one to expand`);
         console.log('Deadmod: Visualization started successfully');
         }} catch (err) {{
             console.error('Deadmod ERROR:', err);
@@ -882,13 +1521,22 @@ pub fn generate_pixi_graph(mods: &HashMap<String, ModuleInfo>, reachable: &HashS
     </script>
 </body>
 </html>"##,
-        total = total,
-        reachable_count = reachable_count,
-        dead_count = dead_count,
-        edge_count = edge_count,
-        nodes_json = nodes_json,
-        edges_json = edges_json,
-        clusters_json = clusters_json
+        title = subject.title,
+        heading = subject.heading,
+        noun = subject.noun,
+        removable = subject.removable,
+        total = data.total,
+        reachable_count = data.reachable_count,
+        dead_count = data.dead_count,
+        edge_count = data.edge_count,
+        nodes_json = data.nodes_json,
+        edges_json = data.edges_json,
+        clusters_json = data.clusters_json,
+        crates_json = data.crates_json,
+        lod_json = data.lod_json,
+        static_layout = data.static_layout,
+        theme_css = data.theme.palette().css_variables(),
+        theme_json = script_json(data.theme.palette())
     )
 }
 
@@ -905,8 +1553,7 @@ fn extract_parent_module(path: &str) -> String {
         if *part == "src" && i + 1 < parts.len() {
             // Collect path segments after src/
             let mut segments: Vec<&str> = Vec::new();
-            for j in (i + 1)..parts.len() {
-                let segment = parts[j];
+            for &segment in &parts[i + 1..] {
                 // Skip the final .rs file
                 if segment.ends_with(".rs") {
                     // If this is the first segment and it's a .rs file (e.g., src/lib.rs)
@@ -1006,7 +1653,221 @@ mod tests {
         assert!(html.contains("\"main\""));
         assert!(html.contains("\"utils\""));
         assert!(html.contains("\"dead\""));
-        assert!(html.contains("0x90EE90")); // alive color
-        assert!(html.contains("0xF08080")); // dead color
+        assert!(html.contains(r##""alive":"#90EE90""##)); // alive color
+        assert!(html.contains(r##""dead":"#F08080""##)); // dead color
+    }
+
+    #[test]
+    fn test_generate_pixi_graph_themes() {
+        let mut mods = HashMap::new();
+        mods.insert(
+            "main".to_string(),
+            crate::parse::ModuleInfo::new(PathBuf::from("src/main.rs")),
+        );
+
+        let layout = LayoutOptions::default();
+        let html = generate_pixi_graph_with_layout(
+            &mods,
+            &HashSet::new(),
+            DEFAULT_CLUSTER_THRESHOLD,
+            &layout,
+            Theme::Light,
+        );
+        assert!(html.contains("--backdrop: #ffffff;"));
+        assert!(html.contains(r##""dead":"#ff8182""##));
+        assert!(html.contains("backgroundColor: hex(theme.backdrop)"));
+    }
+
+    #[test]
+    fn test_generate_pixi_graph_workspace_crates() {
+        let mut mods = HashMap::new();
+        let mut reachable = HashSet::new();
+
+        let mut cli_main = crate::parse::ModuleInfo::new(PathBuf::from("cli/src/main.rs"));
+        cli_main.refs.insert("core::lib".to_string());
+        cli_main.krate = Some("cli".to_string());
+        mods.insert("cli::main".to_string(), cli_main);
+        let mut core_lib = crate::parse::ModuleInfo::new(PathBuf::from("core/src/lib.rs"));
+        core_lib.krate = Some("core".to_string());
+        mods.insert("core::lib".to_string(), core_lib);
+        reachable.insert("cli::main".to_string());
+        reachable.insert("core::lib".to_string());
+
+        let html = generate_pixi_graph(&mods, &reachable);
+
+        assert!(html.contains(r#"const crates = ["cli","core"];"#));
+        assert!(html.contains(r#""crate":"core""#));
+        assert!(html.contains(r#""from":"cli::main","to":"core::lib","crossCrate":true"#));
+        assert!(html.contains("toggle-crates"));
+    }
+
+    #[test]
+    fn test_generate_pixi_graph_same_stem_modules_are_not_crates() {
+        let mods = crate::Deadmod::from_sources(
+            [
+                ("src/lib.rs", "pub mod a;\npub mod b;"),
+                ("src/a/mod.rs", "pub mod utils;"),
+                ("src/a/utils.rs", ""),
+                ("src/b/mod.rs", "pub mod utils;"),
+                ("src/b/utils.rs", ""),
+            ]
+            .map(|(file, src)| (PathBuf::from(file), src.to_string())),
+        )
+        .analyze()
+        .unwrap()
+        .modules;
+        assert!(mods.contains_key("a::utils"));
+        let reachable: HashSet<String> = mods.keys().cloned().collect();
+
+        let html = generate_pixi_graph(&mods, &reachable);
+
+        assert!(html.contains("const crates = [];"));
+        assert!(!html.contains(r#""crate":"a""#));
+        assert!(html.contains(r#""crossCrate":false"#));
+        assert!(!html.contains(r#""crossCrate":true"#));
+    }
+
+    #[test]
+    fn test_generate_pixi_graph_is_deterministic() {
+        let mut mods = HashMap::new();
+        for name in ["a", "b", "c"] {
+            mods.insert(
+                name.to_string(),
+                crate::parse::ModuleInfo::new(PathBuf::from(format!("src/{}.rs", name))),
+            );
+        }
+        let reachable = HashSet::new();
+        let seeded = LayoutOptions::default().with_seed(42);
+
+        let html = generate_pixi_graph_with_layout(
+            &mods,
+            &reachable,
+            DEFAULT_CLUSTER_THRESHOLD,
+            &seeded,
+            Theme::default(),
+        );
+        assert!(!html.contains("Math.random"));
+        assert!(html.contains(r#""x":"#));
+        let rehashed: HashMap<_, _> = mods.clone().into_iter().collect();
+        assert_eq!(
+            html,
+            generate_pixi_graph_with_layout(
+                &rehashed,
+                &reachable,
+                DEFAULT_CLUSTER_THRESHOLD,
+                &seeded,
+                Theme::default()
+            )
+        );
+        assert!(html.contains("const staticLayout = false;"));
+
+        let fixed = seeded.with_static_layout(true);
+        let html = generate_pixi_graph_with_layout(
+            &mods,
+            &reachable,
+            DEFAULT_CLUSTER_THRESHOLD,
+            &fixed,
+            Theme::default(),
+        );
+        assert!(html.contains("const staticLayout = true;"));
+    }
+
+    fn embedded_lod(html: &str) -> serde_json::Value {
+        let start = html.find("const lod = ").unwrap() + "const lod = ".len();
+        let end = start + html[start..].find(";\n").unwrap();
+        serde_json::from_str(&html[start..end]).unwrap()
+    }
+
+    #[test]
+    fn test_generate_pixi_graph_level_of_detail() {
+        let mut mods = HashMap::new();
+        let mut main_info = crate::parse::ModuleInfo::new(PathBuf::from("src/main.rs"));
+        main_info
+            .refs
+            .extend(["a".to_string(), "b".to_string(), "c".to_string()]);
+        mods.insert("main".to_string(), main_info);
+        let mut a = crate::parse::ModuleInfo::new(PathBuf::from("src/api/a.rs"));
+        a.refs.extend(["b".to_string(), "c".to_string()]);
+        mods.insert("a".to_string(), a);
+        mods.insert(
+            "b".to_string(),
+            crate::parse::ModuleInfo::new(PathBuf::from("src/api/b.rs")),
+        );
+        mods.insert(
+            "c".to_string(),
+            crate::parse::ModuleInfo::new(PathBuf::from("src/db/c.rs")),
+        );
+        let reachable: HashSet<String> = ["main", "a", "b"].iter().map(|s| s.to_string()).collect();
+
+        // At or under the threshold the graph stays flat
+        assert!(embedded_lod(&generate_pixi_graph(&mods, &reachable)).is_null());
+        assert!(embedded_lod(&generate_pixi_graph_clustered(&mods, &reachable, 4)).is_null());
+
+        let lod = embedded_lod(&generate_pixi_graph_clustered(&mods, &reachable, 3));
+        assert_eq!(lod["threshold"], 3);
+        assert_eq!(
+            lod["clusters"],
+            serde_json::json!([
+                { "id": "api", "crate": null, "count": 2, "dead": 0, "internalEdges": 1 },
+                { "id": "db", "crate": null, "count": 1, "dead": 1, "internalEdges": 0 },
+                { "id": "main", "crate": null, "count": 1, "dead": 0, "internalEdges": 0 },
+            ])
+        );
+        assert_eq!(
+            lod["edges"],
+            serde_json::json!([
+                { "from": "api", "to": "db", "count": 1, "crossCrate": false },
+                { "from": "main", "to": "api", "count": 2, "crossCrate": false },
+                { "from": "main", "to": "db", "count": 1, "crossCrate": false },
+            ])
+        );
+    }
+
+    #[cfg(feature = "callgraph")]
+    fn sample_callgraph() -> VisualizerGraph {
+        use crate::callgraph::{VisualizerEdge, VisualizerNode, VisualizerStats};
+
+        let node = |id: usize, name: &str, dead: bool| VisualizerNode {
+            id,
+            name: name.to_string(),
+            full_path: format!("app::api::{}", name),
+            file: "src/api/routes.rs".to_string(),
+            module: "routes".to_string(),
+            dead,
+            confidence: None,
+            visibility: "pub".to_string(),
+            is_method: false,
+            metrics: Default::default(),
+        };
+
+        VisualizerGraph {
+            nodes: vec![
+                node(0, "handle", false),
+                node(1, "parse", false),
+                node(2, "legacy", true),
+            ],
+            edges: vec![VisualizerEdge { from: 0, to: 1 }],
+            modules: vec!["routes".to_string()],
+            stats: VisualizerStats {
+                total_functions: 3,
+                total_edges: 1,
+                dead_functions: 1,
+                total_modules: 1,
+            },
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "callgraph")]
+    fn test_generate_pixi_callgraph() {
+        let html = generate_pixi_callgraph(&sample_callgraph());
+
+        assert!(html.contains(r#""id":"app::api::legacy""#));
+        assert!(html.contains(r#""status":"dead""#));
+        assert!(html.contains(r#""cluster":"routes""#));
+        assert!(html.contains("const removable = false;"));
+        assert!(html.contains("PixiJS WebGL Call Graph"));
+        assert!(html.contains(r#""topCluster":"api""#));
+        assert!(html.contains(r#""from":"app::api::handle","to":"app::api::parse""#));
     }
 }
@@ -0,0 +1,361 @@
+//! Filesystem event handling for watch mode.
+//!
+//! Watchers report every write, and editors write often: a save can be a
+//! truncate, a write and a rename, and a branch switch touches hundreds of
+//! files at once. [`EventProcessor`] turns that stream into batches worth
+//! one re-analysis each:
+//!
+//! - Events are coalesced until none arrived for the debounce interval,
+//!   and a steady stream still flushes after a maximum delay
+//! - Files the scan would not gather (`target/`, excluded directories,
+//!   non-`.rs` files, paths outside the root) are dropped, using the scan's
+//!   own rules ([`is_scanned_path`])
+//! - Deletions, and the old side of renames, are listed so their modules
+//!   can be dropped from the graph ([`WatchBatch::remove_modules`])
+//! - A changed `Cargo.toml` or `deadmod.toml` asks for a config reload
+//!
+//! The processor does no I/O and takes the time as an argument, so any
+//! watcher backend can feed it and tests can drive it.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::parse::ModuleInfo;
+use crate::scan::is_scanned_path;
+
+/// Files whose change reloads the configuration.
+const CONFIG_FILES: [&str; 2] = ["Cargo.toml", "deadmod.toml"];
+
+/// A filesystem event, as reported by a watcher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FsEvent {
+    /// A file was created or written
+    Changed(PathBuf),
+    /// A file or directory was deleted
+    Removed(PathBuf),
+    /// A file or directory was moved
+    Renamed { from: PathBuf, to: PathBuf },
+}
+
+/// Coalesced events, ready for one re-analysis.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WatchBatch {
+    /// Source files created or modified, to re-parse
+    pub changed: BTreeSet<PathBuf>,
+    /// Source files, and possibly directories, that are gone
+    pub removed: BTreeSet<PathBuf>,
+    /// Whether a `Cargo.toml` or `deadmod.toml` changed
+    pub reload_config: bool,
+}
+
+impl WatchBatch {
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty() && !self.reload_config
+    }
+
+    /// Whether `file` was removed, itself or with a removed directory.
+    pub fn is_removed(&self, file: &Path) -> bool {
+        self.removed.iter().any(|gone| file.starts_with(gone))
+    }
+
+    /// Drop the modules whose file was removed from `modules`, returning
+    /// their keys, sorted.
+    pub fn remove_modules(&self, modules: &mut HashMap<String, ModuleInfo>) -> Vec<String> {
+        let mut removed: Vec<String> = modules
+            .iter()
+            .filter(|(_, info)| self.is_removed(&info.path))
+            .map(|(name, _)| name.clone())
+            .collect();
+        removed.sort();
+        for name in &removed {
+            modules.remove(name);
+        }
+        removed
+    }
+}
+
+/// Coalesces watcher events into [`WatchBatch`]es.
+#[derive(Debug, Clone)]
+pub struct EventProcessor {
+    root: PathBuf,
+    excludes: Vec<String>,
+    debounce: Duration,
+    max_delay: Duration,
+    pending: WatchBatch,
+    first_event: Option<Instant>,
+    last_event: Option<Instant>,
+}
+
+impl EventProcessor {
+    /// Quiet time after the last event before a batch is released.
+    pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(200);
+    /// Longest a batch is held back while events keep arriving.
+    pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(2);
+
+    /// A processor for events under `root`, which should be canonical like
+    /// the paths watchers report.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            excludes: Vec::new(),
+            debounce: Self::DEFAULT_DEBOUNCE,
+            max_delay: Self::DEFAULT_MAX_DELAY,
+            pending: WatchBatch::default(),
+            first_event: None,
+            last_event: None,
+        }
+    }
+
+    /// Directory names to ignore besides the scan's defaults, as given to
+    /// [`gather_rs_files_with_excludes`](crate::scan::gather_rs_files_with_excludes).
+    pub fn with_excludes(mut self, excludes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.excludes = excludes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Quiet time after the last event before a batch is released.
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Longest a batch is held back while events keep arriving.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Record `event`, received at `now`.
+    pub fn push(&mut self, event: FsEvent, now: Instant) {
+        let relevant = match event {
+            FsEvent::Changed(path) => self.changed(path),
+            FsEvent::Removed(path) => self.removed(path),
+            FsEvent::Renamed { from, to } => {
+                let removed = self.removed(from);
+                self.changed(to) | removed
+            }
+        };
+        if relevant {
+            self.first_event.get_or_insert(now);
+            self.last_event = Some(now);
+        }
+    }
+
+    /// The pending batch, once no event arrived for the debounce interval
+    /// or the first pending one is older than the maximum delay.
+    pub fn poll(&mut self, now: Instant) -> Option<WatchBatch> {
+        if now < self.next_deadline()? {
+            return None;
+        }
+        self.first_event = None;
+        self.last_event = None;
+        Some(std::mem::take(&mut self.pending))
+    }
+
+    /// When [`poll`](Self::poll) will release the pending batch, if there
+    /// is one: the time a watch loop can sleep until.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let quiet = self.last_event? + self.debounce;
+        Some(quiet.min(self.first_event? + self.max_delay))
+    }
+
+    fn changed(&mut self, path: PathBuf) -> bool {
+        if self.is_config(&path) {
+            self.pending.reload_config = true;
+            return true;
+        }
+        if !self.is_scanned(&path) {
+            return false;
+        }
+        // Deleted, then written again: editors save by replacing the file
+        self.pending.removed.remove(&path);
+        self.pending.changed.insert(path);
+        true
+    }
+
+    fn removed(&mut self, path: PathBuf) -> bool {
+        if self.is_config(&path) {
+            self.pending.reload_config = true;
+            return true;
+        }
+        // Gone paths cannot be checked for being directories; take those
+        // without an extension as possibly one
+        let maybe_dir = path.extension().is_none()
+            && is_scanned_path(&self.root, &path.join("mod.rs"), &self.exclude_names());
+        if !self.is_scanned(&path) && !maybe_dir {
+            return false;
+        }
+        self.pending
+            .changed
+            .retain(|changed| !changed.starts_with(&path));
+        self.pending.removed.insert(path);
+        true
+    }
+
+    fn is_scanned(&self, path: &Path) -> bool {
+        is_scanned_path(&self.root, path, &self.exclude_names())
+    }
+
+    /// A `Cargo.toml` or `deadmod.toml` in a scanned directory.
+    fn is_config(&self, path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| CONFIG_FILES.contains(&name))
+            && self.is_scanned(&path.with_file_name("lib.rs"))
+    }
+
+    fn exclude_names(&self) -> Vec<&str> {
+        self.excludes.iter().map(String::as_str).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(start: Instant, ms: u64) -> Instant {
+        start + Duration::from_millis(ms)
+    }
+
+    fn paths(list: &[&str]) -> BTreeSet<PathBuf> {
+        list.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn test_coalesces_until_quiet() {
+        let start = Instant::now();
+        let mut events = EventProcessor::new("/ws");
+        assert_eq!(events.poll(start), None);
+        assert_eq!(events.next_deadline(), None);
+
+        events.push(FsEvent::Changed("/ws/src/a.rs".into()), at(start, 0));
+        events.push(FsEvent::Changed("/ws/src/a.rs".into()), at(start, 50));
+        events.push(FsEvent::Changed("/ws/src/b.rs".into()), at(start, 150));
+        assert_eq!(events.next_deadline(), Some(at(start, 350)));
+        assert_eq!(events.poll(at(start, 300)), None);
+
+        let batch = events.poll(at(start, 350)).unwrap();
+        assert_eq!(batch.changed, paths(&["/ws/src/a.rs", "/ws/src/b.rs"]));
+        assert!(batch.removed.is_empty() && !batch.reload_config);
+        assert_eq!(events.poll(at(start, 1_000)), None);
+    }
+
+    #[test]
+    fn test_steady_stream_flushes_after_max_delay() {
+        let start = Instant::now();
+        let mut events = EventProcessor::new("/ws")
+            .with_debounce(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(500));
+        for ms in (0..=600).step_by(50) {
+            events.push(
+                FsEvent::Changed(format!("/ws/src/f{}.rs", ms).into()),
+                at(start, ms),
+            );
+            if ms == 450 {
+                assert_eq!(events.poll(at(start, ms)), None);
+            }
+            if ms == 500 {
+                assert_eq!(events.poll(at(start, ms)).unwrap().changed.len(), 11);
+            }
+        }
+        assert_eq!(events.poll(at(start, 700)).unwrap().changed.len(), 2);
+    }
+
+    #[test]
+    fn test_ignores_what_the_scan_ignores() {
+        let start = Instant::now();
+        let mut events = EventProcessor::new("/ws").with_excludes(["fixtures"]);
+        let ignored = [
+            "/ws/target/debug/build/out.rs",
+            "/ws/fixtures/bad.rs",
+            "/ws/src/notes.md",
+            "/elsewhere/lib.rs",
+        ];
+        for path in ignored {
+            events.push(FsEvent::Changed(path.into()), start);
+        }
+        events.push(FsEvent::Removed("/ws/.git/index".into()), start);
+        events.push(FsEvent::Removed("/ws/target/debug".into()), start);
+        events.push(FsEvent::Changed("/ws/target/Cargo.toml".into()), start);
+        assert_eq!(events.next_deadline(), None);
+        assert_eq!(events.poll(at(start, 1_000)), None);
+    }
+
+    #[test]
+    fn test_removals_and_renames_drop_modules() {
+        let start = Instant::now();
+        let mut events = EventProcessor::new("/ws");
+        events.push(FsEvent::Changed("/ws/src/old.rs".into()), start);
+        events.push(FsEvent::Removed("/ws/src/old.rs".into()), start);
+        events.push(
+            FsEvent::Renamed {
+                from: "/ws/src/net.rs".into(),
+                to: "/ws/src/net.rs.bak".into(),
+            },
+            start,
+        );
+        events.push(
+            FsEvent::Renamed {
+                from: "/ws/src/a.rs".into(),
+                to: "/ws/src/b.rs".into(),
+            },
+            start,
+        );
+        events.push(FsEvent::Removed("/ws/src/legacy".into()), start);
+        // Saved by replacing the file: still there
+        events.push(FsEvent::Removed("/ws/src/main.rs".into()), start);
+        events.push(FsEvent::Changed("/ws/src/main.rs".into()), start);
+
+        let batch = events.poll(at(start, 1_000)).unwrap();
+        assert_eq!(batch.changed, paths(&["/ws/src/b.rs", "/ws/src/main.rs"]));
+        assert_eq!(
+            batch.removed,
+            paths(&[
+                "/ws/src/a.rs",
+                "/ws/src/legacy",
+                "/ws/src/net.rs",
+                "/ws/src/old.rs"
+            ])
+        );
+
+        let files = [
+            "main.rs",
+            "net.rs",
+            "legacy/mod.rs",
+            "legacy/db.rs",
+            "util.rs",
+        ];
+        let mut modules: HashMap<String, ModuleInfo> = files
+            .into_iter()
+            .map(|file| {
+                let info = ModuleInfo::new(Path::new("/ws/src").join(file));
+                (file.trim_end_matches(".rs").replace('/', "::"), info)
+            })
+            .collect();
+        let removed = batch.remove_modules(&mut modules);
+        assert_eq!(removed, vec!["legacy::db", "legacy::mod", "net"]);
+        let mut left: Vec<&String> = modules.keys().collect();
+        left.sort();
+        assert_eq!(left, vec!["main", "util"]);
+    }
+
+    #[test]
+    fn test_config_changes_reload() {
+        let start = Instant::now();
+        let mut events = EventProcessor::new("/ws");
+        events.push(FsEvent::Changed("/ws/deadmod.toml".into()), start);
+        let batch = events.poll(at(start, 1_000)).unwrap();
+        assert!(batch.reload_config && batch.changed.is_empty());
+
+        let saved = FsEvent::Renamed {
+            from: "/ws/core/Cargo.toml.tmp".into(),
+            to: "/ws/core/Cargo.toml".into(),
+        };
+        events.push(saved, start);
+        assert!(events.poll(at(start, 1_000)).unwrap().reload_config);
+
+        events.push(FsEvent::Changed("/ws/Cargo.lock".into()), start);
+        assert_eq!(events.poll(at(start, 1_000)), None);
+    }
+}
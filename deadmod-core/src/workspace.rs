@@ -4,64 +4,38 @@
 //!
 //! Supports:
 //! - Automatic workspace detection via `[workspace]` in Cargo.toml
-//! - Crate discovery via `cargo metadata` or fallback directory scan
+//! - Crate discovery via `cargo metadata` (`cargo-metadata` feature) or
+//!   fallback directory scan
 //! - Per-crate analysis with fault tolerance
 //! - Combined reporting across all workspace members
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use rayon::prelude::*;
-use serde::Deserialize;
-
-use crate::{
-    build_graph, cache, find_dead, find_root_modules, gather_rs_files, reachable_from_roots,
-    visualize,
-};
-
-/// Minimal subset of `cargo metadata` output we need.
-#[derive(Debug, Deserialize)]
-struct CargoMetadata {
-    #[allow(dead_code)]
-    workspace_root: String,
-    packages: Vec<CargoPackage>,
-}
 
-#[derive(Debug, Deserialize)]
-struct CargoPackage {
-    #[allow(dead_code)]
-    id: String,
-    manifest_path: String,
-}
+use crate::error::{DeadmodError, DeadmodResult};
+#[cfg(feature = "cargo-metadata")]
+use crate::metadata::CargoMetadata;
+use crate::parse::{EdgeKind, ModuleInfo};
+use crate::root::roots_for_crate;
+use crate::source::{FsSource, SourceProvider};
+use crate::{build_graph, dead_names, find_dead, reachable_from_roots, visualize};
 
 /// Result of analyzing a single crate.
 #[derive(Debug, Clone)]
 pub struct CrateAnalysis {
     pub name: String,
     pub root: PathBuf,
+    /// Modules parsed in the crate
+    pub total_modules: usize,
     pub dead_modules: Vec<String>,
     pub reachable_modules: Vec<String>,
     pub dot_output: String,
 }
 
-/// Try using `cargo metadata` for workspace discovery.
-/// This is the most reliable method as it respects Cargo.toml workspace config.
-fn try_cargo_metadata(path: &Path) -> Option<CargoMetadata> {
-    let output = std::process::Command::new("cargo")
-        .args(["metadata", "--no-deps", "--format-version", "1"])
-        .current_dir(path)
-        .output()
-        .ok()?;
-
-    if !output.status.success() {
-        return None;
-    }
-
-    serde_json::from_slice(&output.stdout).ok()
-}
-
 /// Detect if a root is a Cargo workspace.
 pub fn is_workspace_root(root: &Path) -> bool {
     let cargo_toml = root.join("Cargo.toml");
@@ -83,6 +57,17 @@ pub fn is_workspace_root(root: &Path) -> bool {
     text.contains("[workspace]")
 }
 
+/// Find the enclosing Cargo workspace root, starting at `path` itself.
+///
+/// Returns `None` when the path is not inside a workspace.
+pub fn find_workspace_root(path: &Path) -> Option<PathBuf> {
+    let canonical = path.canonicalize().ok()?;
+    canonical
+        .ancestors()
+        .find(|dir| is_workspace_root(dir))
+        .map(Path::to_path_buf)
+}
+
 /// Find the crate root from a given path.
 ///
 /// Search strategy:
@@ -128,21 +113,32 @@ pub fn find_crate_root(path: &Path) -> Option<PathBuf> {
     Some(canonical)
 }
 
+/// [`find_crate_root`], failing with [`DeadmodError::CrateRootNotFound`]
+/// (the path as given, and absolute when it is relative) when the path
+/// does not resolve.
+pub fn locate_crate_root(path: &Path) -> DeadmodResult<PathBuf> {
+    find_crate_root(path).ok_or_else(|| {
+        let mut searched = vec![path.to_path_buf()];
+        if path.is_relative() {
+            searched.extend(std::env::current_dir().ok().map(|cwd| cwd.join(path)));
+        }
+        DeadmodError::crate_root_not_found(searched)
+    })
+}
+
 /// Find all crate roots in a workspace.
 /// Prefers `cargo metadata` when available, falls back to directory scan.
 pub fn find_all_crates(root: &Path) -> Result<Vec<PathBuf>> {
-    // Try cargo metadata first (most reliable)
-    if let Some(meta) = try_cargo_metadata(root) {
-        let mut crates = Vec::new();
-        for pkg in meta.packages {
-            let manifest = PathBuf::from(&pkg.manifest_path);
-            if let Some(parent) = manifest.parent() {
-                crates.push(parent.to_path_buf());
-            }
-        }
-        if !crates.is_empty() {
-            return Ok(crates);
+    #[cfg(feature = "cargo-metadata")]
+    match CargoMetadata::load(root) {
+        Ok(meta) => {
+            return Ok(meta
+                .members()
+                .into_iter()
+                .map(|package| package.dir())
+                .collect())
         }
+        Err(e) => eprintln!("[WARN] {:#} (scanning directories instead)", e),
     }
 
     // Fallback: manual directory scan
@@ -169,6 +165,7 @@ pub fn find_all_crates(root: &Path) -> Result<Vec<PathBuf>> {
             }
         }
     }
+    crates.sort();
 
     Ok(crates)
 }
@@ -186,29 +183,219 @@ fn parse_crate_name(cargo_toml: &str) -> String {
     "unknown".to_string()
 }
 
+/// Whether the crate at `crate_root` has a library target
+/// (`src/lib.rs` or a `[lib]` section).
+pub fn is_library_crate(crate_root: &Path) -> bool {
+    crate_root.join("src/lib.rs").is_file()
+        || read_manifest(crate_root).is_some_and(|manifest| manifest.get("lib").is_some())
+}
+
+/// Names of the other workspace members that depend on the crate at
+/// `crate_root` (normal, dev or build dependencies), sorted.
+///
+/// Returns `None` when the crate is not part of a workspace.
+pub fn workspace_dependents(crate_root: &Path) -> Option<Vec<String>> {
+    let workspace = find_workspace_root(crate_root)?;
+    let own_root = crate_root.canonicalize().ok()?;
+
+    #[cfg(feature = "cargo-metadata")]
+    if let Ok(meta) = CargoMetadata::load(&workspace) {
+        let name = &meta.member_at(&own_root)?.name;
+        let mut dependents: Vec<String> = meta
+            .members()
+            .into_iter()
+            .filter(|package| {
+                package.name != *name && package.dependencies.iter().any(|d| d.name == *name)
+            })
+            .map(|package| package.name.clone())
+            .collect();
+        dependents.sort();
+        return Some(dependents);
+    }
+
+    let name = package_name(&read_manifest(&own_root)?)?;
+
+    let mut dependents: Vec<String> = find_all_crates(&workspace)
+        .ok()?
+        .into_iter()
+        .filter(|member| member.canonicalize().ok().as_deref() != Some(own_root.as_path()))
+        .filter_map(|member| read_manifest(&member))
+        .filter(|manifest| depends_on(manifest, &name))
+        .filter_map(|manifest| package_name(&manifest))
+        .collect();
+    dependents.sort();
+    dependents.dedup();
+    Some(dependents)
+}
+
+/// Name a workspace member goes by in combined graphs: its directory name.
+pub fn member_name(crate_root: &Path) -> String {
+    crate_root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Merge the module maps of workspace members into one graph keyed
+/// `crate::module`.
+///
+/// References stay within their crate, except that `use other_crate::...`
+/// becomes an edge to that member's `lib` module (a cross-crate edge).
+/// Members are named by their directory name.
+pub fn combine_crate_modules(
+    crates: impl IntoIterator<Item = (String, HashMap<String, ModuleInfo>)>,
+) -> HashMap<String, ModuleInfo> {
+    combine_crate_modules_with_externs(crates, &HashMap::new())
+}
+
+/// Like [`combine_crate_modules`], with the dependency edges Cargo reports:
+/// `externs` maps a member to the idents it imports other members under
+/// (`CargoMetadata::member_externs`), so only declared dependencies link,
+/// under their real or renamed names. Members missing from `externs` link
+/// by directory name.
+pub fn combine_crate_modules_with_externs(
+    crates: impl IntoIterator<Item = (String, HashMap<String, ModuleInfo>)>,
+    externs: &HashMap<String, HashMap<String, String>>,
+) -> HashMap<String, ModuleInfo> {
+    let crates: Vec<(String, HashMap<String, ModuleInfo>)> = crates.into_iter().collect();
+    let lib_idents: HashMap<String, String> = crates
+        .iter()
+        .map(|(name, _)| (name.replace('-', "_"), name.clone()))
+        .collect();
+
+    let mut combined = HashMap::new();
+    for (crate_name, mods) in crates {
+        let idents = externs.get(&crate_name).unwrap_or(&lib_idents);
+        for (name, mut info) in mods {
+            let prefix = |r: &String| match idents.get(r) {
+                Some(other) if *other != crate_name => format!("{}::lib", other),
+                _ => format!("{}::{}", crate_name, r),
+            };
+            info.refs = info.refs.iter().map(prefix).collect();
+            let mut prefixed_kinds: HashMap<String, BTreeSet<EdgeKind>> = HashMap::new();
+            for (r, kinds) in &info.ref_kinds {
+                prefixed_kinds.entry(prefix(r)).or_default().extend(kinds);
+            }
+            info.ref_kinds = prefixed_kinds;
+            info.krate = Some(crate_name.clone());
+            combined.insert(format!("{}::{}", crate_name, name), info);
+        }
+    }
+    combined
+}
+
+/// Split a combined workspace analysis (see [`combine_crate_modules`]) back
+/// into one [`CrateAnalysis`] per member, in `members` order.
+///
+/// `members` are (name, root) pairs; `reachable` and `dead` hold combined
+/// `crate::module` keys. Module names in the results are crate-relative, as
+/// in [`analyze_crate`], and each DOT graph only has the crate's modules.
+pub fn split_by_crate(
+    members: &[(String, PathBuf)],
+    mods: &HashMap<String, ModuleInfo>,
+    reachable: &HashSet<String>,
+    dead: &[&str],
+) -> Vec<CrateAnalysis> {
+    let mut by_crate: HashMap<&str, HashMap<String, ModuleInfo>> = HashMap::new();
+    for (name, info) in mods {
+        if let Some((krate, module)) = name.split_once("::") {
+            by_crate
+                .entry(krate)
+                .or_default()
+                .insert(module.to_string(), info.clone());
+        }
+    }
+
+    members
+        .iter()
+        .map(|(name, root)| {
+            let crate_mods = by_crate.remove(name.as_str()).unwrap_or_default();
+            let prefix = format!("{}::", name);
+            let local = |key: &str| key.strip_prefix(&prefix).map(String::from);
+
+            let mut dead_modules: Vec<String> = dead.iter().filter_map(|key| local(key)).collect();
+            dead_modules.sort();
+            let reachable_set: HashSet<String> =
+                reachable.iter().filter_map(|key| local(key)).collect();
+            let mut reachable_modules: Vec<String> = reachable_set.iter().cloned().collect();
+            reachable_modules.sort();
+
+            CrateAnalysis {
+                name: name.clone(),
+                root: root.clone(),
+                total_modules: crate_mods.len(),
+                dead_modules,
+                reachable_modules,
+                dot_output: visualize::generate_dot(&crate_mods, &reachable_set),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn read_manifest(crate_root: &Path) -> Option<toml::Value> {
+    let text = fs::read_to_string(crate_root.join("Cargo.toml")).ok()?;
+    toml::from_str(&text).ok()
+}
+
+fn package_name(manifest: &toml::Value) -> Option<String> {
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Whether `manifest` lists `name` in any dependency table, including
+/// `[target.'cfg(..)'.dependencies]` and renamed (`package = "..."`) entries.
+fn depends_on(manifest: &toml::Value, name: &str) -> bool {
+    const TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+    let in_table = |table: &toml::Value| {
+        TABLES
+            .iter()
+            .filter_map(|t| table.get(t)?.as_table())
+            .any(|deps| {
+                deps.iter().any(|(key, spec)| {
+                    key == name || spec.get("package").and_then(|p| p.as_str()) == Some(name)
+                })
+            })
+    };
+
+    in_table(manifest)
+        || manifest
+            .get("target")
+            .and_then(|t| t.as_table())
+            .is_some_and(|targets| targets.values().any(in_table))
+}
+
 /// Analyze a single crate and return the analysis result.
 ///
 /// This is fault-tolerant: parse errors in individual files are logged but don't
 /// cause the entire analysis to fail.
 pub fn analyze_crate(crate_root: &Path) -> Result<CrateAnalysis> {
+    analyze_crate_with_roots(crate_root, &[])
+}
+
+/// Like [`analyze_crate`], but reachability starts from the `custom` roots
+/// that apply to this crate (see [`roots_for_crate`]) when any are given.
+pub fn analyze_crate_with_roots(crate_root: &Path, custom: &[String]) -> Result<CrateAnalysis> {
     let manifest = crate_root.join("Cargo.toml");
     let cargo_toml = fs::read_to_string(&manifest)
         .with_context(|| format!("Failed to read Cargo.toml at {}", manifest.display()))?;
     let crate_name = parse_crate_name(&cargo_toml);
 
-    // 1. Gather all .rs files
-    let files = gather_rs_files(crate_root)
-        .with_context(|| format!("Failed to gather files for crate {}", crate_name))?;
-
-    // 2. Load cache
-    let cached = cache::load_cache(crate_root);
-
-    // 3. Parse modules (incremental)
-    let mods = cache::incremental_parse(crate_root, &files, cached)
+    // 1-3. Gather and parse all .rs files (incremental)
+    let source = FsSource::new(crate_root);
+    let mods = source
+        .parse_modules()
         .with_context(|| format!("Failed to parse modules for crate {}", crate_name))?;
 
-    // 4. Find root modules (entry points)
-    let root_mods = find_root_modules(crate_root);
+    // 4. Find root modules (entry points, unless overridden)
+    let root_mods = if custom.is_empty() {
+        source.root_modules()
+    } else {
+        roots_for_crate(custom, &crate_name)
+    };
 
     // 5. Build graph and find reachable modules (single O(|V|+|E|) traversal)
     let graph = build_graph(&mods);
@@ -219,7 +406,7 @@ pub fn analyze_crate(crate_root: &Path) -> Result<CrateAnalysis> {
     let reachable: HashSet<&str> = reachable_from_roots(&graph, valid_roots);
 
     // 6. Find dead modules
-    let mut dead = find_dead(&mods, &reachable);
+    let mut dead = dead_names(&find_dead(&mods, &reachable));
     dead.sort();
 
     // 7. Generate DOT visualization
@@ -229,6 +416,7 @@ pub fn analyze_crate(crate_root: &Path) -> Result<CrateAnalysis> {
     Ok(CrateAnalysis {
         name: crate_name,
         root: crate_root.to_path_buf(),
+        total_modules: mods.len(),
         dead_modules: dead.into_iter().map(String::from).collect(),
         reachable_modules: reachable_owned.into_iter().collect(),
         dot_output: dot,
@@ -247,6 +435,11 @@ pub fn analyze_crate(crate_root: &Path) -> Result<CrateAnalysis> {
 /// - Runtime: O(T_longest_crate) instead of O(sum of all crates)
 /// - Scales horizontally with available CPU cores
 pub fn analyze_workspace(root: &Path) -> Result<Vec<CrateAnalysis>> {
+    analyze_workspace_with_roots(root, &[])
+}
+
+/// Like [`analyze_workspace`], with custom roots (`crate::module` or `module`).
+pub fn analyze_workspace_with_roots(root: &Path, custom: &[String]) -> Result<Vec<CrateAnalysis>> {
     // 1. Safe workspace scanning (Sequential - I/O bound)
     let crates = match find_all_crates(root) {
         Ok(c) => c,
@@ -280,7 +473,7 @@ pub fn analyze_workspace(root: &Path) -> Result<Vec<CrateAnalysis>> {
     let results: Vec<CrateAnalysis> = crates
         .into_par_iter()
         .filter_map(|crate_root| {
-            match analyze_crate(&crate_root) {
+            match analyze_crate_with_roots(&crate_root, custom) {
                 Ok(analysis) => Some(analysis),
                 Err(e) => {
                     // Failure: Log error but continue (Bulkhead Pattern)
@@ -352,6 +545,42 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_find_workspace_root_from_member() {
+        let ws = create_temp_dir("ws_root_find");
+        create_file(&ws.join("Cargo.toml"), "[workspace]\nmembers = [\"core\"]");
+        create_file(&ws.join("core/Cargo.toml"), "[package]\nname = \"core\"");
+        create_file(&ws.join("core/src/lib.rs"), "");
+
+        let expected = ws.canonicalize().unwrap();
+        assert_eq!(
+            find_workspace_root(&ws.join("core")),
+            Some(expected.clone())
+        );
+        assert_eq!(find_workspace_root(&ws), Some(expected));
+
+        fs::remove_dir_all(&ws).ok();
+    }
+
+    #[test]
+    fn test_locate_crate_root_missing_path() {
+        let dir = create_temp_dir("locate_root");
+        create_file(&dir.join("src/lib.rs"), "");
+        assert_eq!(
+            locate_crate_root(&dir).unwrap(),
+            dir.canonicalize().unwrap()
+        );
+
+        let missing = dir.join("nowhere");
+        let err = locate_crate_root(&missing).unwrap_err();
+        assert!(
+            matches!(err, DeadmodError::CrateRootNotFound { ref searched_paths } if searched_paths[0] == missing)
+        );
+        assert!(err.hint().is_some());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_parse_crate_name() {
         let toml = r#"
@@ -375,7 +604,7 @@ version = "0.1.0"
     }
 
     #[test]
-    fn test_find_all_crates_fallback() {
+    fn test_find_all_crates() {
         let ws = create_temp_dir("find_crates");
 
         // Create workspace structure
@@ -392,9 +621,9 @@ version = "0.1.0"
         create_file(&ws.join("core/src/lib.rs"), "");
         create_file(&ws.join("cli/src/main.rs"), "fn main() {}");
 
-        // This will use fallback since cargo metadata won't work in temp dir
         let crates = find_all_crates(&ws).unwrap();
-        assert_eq!(crates.len(), 2);
+        let names: Vec<String> = crates.iter().map(|dir| member_name(dir)).collect();
+        assert_eq!(names, ["cli", "core"]);
 
         fs::remove_dir_all(&ws).ok();
     }
@@ -420,6 +649,29 @@ version = "0.1.0"
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_analyze_crate_with_roots() {
+        let dir = create_temp_dir("analyze_roots");
+
+        create_file(&dir.join("Cargo.toml"), "[package]\nname = \"app\"");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        create_file(&dir.join("src/main.rs"), "mod api; mod cli; fn main() {}");
+        create_file(&dir.join("src/api.rs"), "mod db;");
+        create_file(&dir.join("src/db.rs"), "");
+        create_file(&dir.join("src/cli.rs"), "");
+
+        let result = analyze_crate_with_roots(&dir, &["app::api".to_string()]).unwrap();
+        let mut dead = result.dead_modules.clone();
+        dead.sort();
+        assert_eq!(dead, vec!["cli".to_string(), "main".to_string()]);
+
+        // Roots qualified with another crate leave nothing reachable here
+        let result = analyze_crate_with_roots(&dir, &["other::api".to_string()]).unwrap();
+        assert_eq!(result.dead_modules.len(), 4);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_analyze_workspace_multiple_crates() {
         let ws = create_temp_dir("analyze_ws");
@@ -451,4 +703,41 @@ version = "0.1.0"
 
         fs::remove_dir_all(&ws).ok();
     }
+    #[test]
+    fn test_split_by_crate() {
+        let module = |path: &str, refs: &[&str]| {
+            let mut info = ModuleInfo::new(PathBuf::from(path));
+            info.refs.extend(refs.iter().map(|r| r.to_string()));
+            info
+        };
+        let core = HashMap::from([
+            ("lib".to_string(), module("core/src/lib.rs", &["net"])),
+            ("net".to_string(), module("core/src/net.rs", &[])),
+            ("legacy".to_string(), module("core/src/legacy.rs", &[])),
+        ]);
+        let cli = HashMap::from([("main".to_string(), module("cli/src/main.rs", &[]))]);
+        let mods = combine_crate_modules([("core".to_string(), core), ("cli".to_string(), cli)]);
+        let reachable: HashSet<String> = ["core::lib", "core::net", "cli::main"]
+            .map(String::from)
+            .into();
+        let members = [
+            ("core".to_string(), PathBuf::from("core")),
+            ("cli".to_string(), PathBuf::from("cli")),
+        ];
+
+        let results = split_by_crate(&members, &mods, &reachable, &["core::legacy"]);
+        assert_eq!(results[0].name, "core");
+        assert_eq!(results[0].root, PathBuf::from("core"));
+        assert_eq!(results[0].total_modules, 3);
+        assert_eq!(results[0].dead_modules, vec!["legacy".to_string()]);
+        assert_eq!(
+            results[0].reachable_modules,
+            vec!["lib".to_string(), "net".to_string()]
+        );
+        assert!(
+            results[0].dot_output.contains("legacy") && !results[0].dot_output.contains("main")
+        );
+        assert_eq!(results[1].total_modules, 1);
+        assert!(results[1].dead_modules.is_empty());
+    }
 }
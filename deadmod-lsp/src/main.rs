@@ -1,32 +1,263 @@
 //! Deadmod LSP Server - Real-time dead module detection for Rust.
 //!
 //! Provides IDE integration with:
-//! - Live diagnostics on file open/save
-//! - Warning markers on dead modules
+//! - Live diagnostics on file open/save, or debounced on change
+//! - Warning markers on dead modules and, if enabled, on dead functions,
+//!   constants, enum variants and macros at their definitions
 //! - Hover information
+//! - Editor settings from `initializationOptions` and
+//!   `workspace/didChangeConfiguration` (see [`Settings`])
 //!
 //! NASA-grade resilience: never panics, handles all errors gracefully.
 
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
 
 use anyhow::Result;
+use serde::Deserialize;
+use serde_json::Value;
 use tokio::sync::RwLock;
 use tower_lsp::jsonrpc::Result as LspResult;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
 use deadmod_core::{
-    build_graph, cache, find_crate_root, find_dead, find_root_modules, gather_rs_files,
-    reachable_from_roots,
+    build_graph, cache, find_collisions, find_crate_root, find_dead, find_root_modules,
+    gather_rs_files, init_logging, load_config, module_id, reachable_from_roots, resolve_jobs,
+    run_with_threads, DeadItem, Deadmod, DeadmodConfig, DetectorToggles, IncrementalState,
+    ItemSpan, LogFormat, PathFormatter,
 };
 
+/// A kind of diagnostic the server can publish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum LspDetector {
+    /// Modules not reachable from any entry point
+    Modules,
+    /// Files sharing a module path
+    Collisions,
+    /// Unused functions and methods
+    Functions,
+    /// Unused constants and statics
+    Constants,
+    /// Enum variants never constructed
+    Enums,
+    /// Unused macros
+    Macros,
+}
+
+/// Detectors that report items rather than modules.
+const ITEM_DETECTORS: [LspDetector; 4] = [
+    LspDetector::Functions,
+    LspDetector::Constants,
+    LspDetector::Enums,
+    LspDetector::Macros,
+];
+
+/// Severity of dead code diagnostics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    Error,
+    #[default]
+    Warning,
+    Information,
+    Hint,
+}
+
+impl Severity {
+    fn to_lsp(self) -> DiagnosticSeverity {
+        match self {
+            Self::Error => DiagnosticSeverity::ERROR,
+            Self::Warning => DiagnosticSeverity::WARNING,
+            Self::Information => DiagnosticSeverity::INFORMATION,
+            Self::Hint => DiagnosticSeverity::HINT,
+        }
+    }
+}
+
+/// When analysis runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum AnalyzeOn {
+    /// When a Rust file is opened or saved
+    #[default]
+    Save,
+    /// Also when a Rust file changes, once edits pause for `debounceMs`
+    Change,
+}
+
+/// Editor settings, sent as `initializationOptions` and with
+/// `workspace/didChangeConfiguration`, either at the top level or under a
+/// `deadmod` key. Missing fields keep their defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct Settings {
+    /// Crate-relative file globs to publish nothing for (`*` matches any sequence)
+    ignore: Vec<String>,
+    /// Diagnostics to publish
+    detectors: Vec<LspDetector>,
+    /// Severity of dead code diagnostics
+    severity: Severity,
+    /// When analysis runs
+    analyze_on: AnalyzeOn,
+    /// Quiet time after a change before it is analyzed, in milliseconds
+    debounce_ms: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            ignore: Vec::new(),
+            detectors: vec![LspDetector::Modules, LspDetector::Collisions],
+            severity: Severity::default(),
+            analyze_on: AnalyzeOn::default(),
+            debounce_ms: 500,
+        }
+    }
+}
+
+impl Settings {
+    /// Parse settings sent by the editor; `null` gives the defaults.
+    fn from_value(value: Value) -> Result<Self> {
+        let value = match value {
+            Value::Object(mut map) if map.contains_key("deadmod") => {
+                map.remove("deadmod").unwrap_or_default()
+            }
+            other => other,
+        };
+        if value.is_null() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    fn runs(&self, detector: LspDetector) -> bool {
+        self.detectors.contains(&detector)
+    }
+
+    /// These settings without the detectors deadmod.toml's `[detectors]`
+    /// table turns off; the crate's configuration wins over the editor's.
+    fn restricted(mut self, toggles: &DetectorToggles) -> Self {
+        self.detectors.retain(|detector| match detector {
+            LspDetector::Modules => toggles.modules,
+            LspDetector::Collisions => true,
+            LspDetector::Functions => toggles.functions,
+            LspDetector::Constants => toggles.constants,
+            LspDetector::Enums => toggles.enums,
+            LspDetector::Macros => toggles.macros,
+        });
+        self
+    }
+
+    /// Whether `file` matches an ignore glob, relative to `crate_root`.
+    fn is_ignored(&self, crate_root: &Path, file: &Path) -> bool {
+        let relative = file.strip_prefix(crate_root).unwrap_or(file);
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        self.ignore.iter().any(|glob| glob_matches(glob, &relative))
+    }
+}
+
+/// Match `text` against `glob`, where `*` matches any sequence (`/` included).
+fn glob_matches(glob: &str, text: &str) -> bool {
+    let glob = glob.as_bytes();
+    let text = text.as_bytes();
+    let (mut g, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if g < glob.len() && glob[g] == b'*' {
+            backtrack = Some((g, t));
+            g += 1;
+        } else if g < glob.len() && glob[g] == text[t] {
+            g += 1;
+            t += 1;
+        } else if let Some((star_g, star_t)) = backtrack {
+            // Let the last `*` swallow one more character
+            g = star_g + 1;
+            t = star_t + 1;
+            backtrack = Some((star_g, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    glob[g..].iter().all(|&c| c == b'*')
+}
+
+/// Range of `item`'s name on its definition line in `content` (see
+/// [`ItemSpan::locate`]). Columns are UTF-16 code units.
+fn item_range(content: &str, item: &DeadItem) -> Range {
+    let span = ItemSpan::locate(content, item);
+    let text = content.lines().nth(span.start_line - 1).unwrap_or_default();
+    let utf16 = |column: usize| {
+        text.chars()
+            .take(column)
+            .map(char::len_utf16)
+            .sum::<usize>() as u32
+    };
+    let line = (span.start_line - 1) as u32;
+    Range {
+        start: Position {
+            line,
+            character: utf16(span.start_column),
+        },
+        end: Position {
+            line,
+            character: utf16(span.end_column),
+        },
+    }
+}
+
+/// Diagnostic for the dead module `module_path` defined in `uri`.
+fn module_diagnostic(uri: &Url, module_path: &str, severity: Severity) -> Diagnostic {
+    Diagnostic {
+        range: Range {
+            start: Position {
+                line: 0,
+                character: 0,
+            },
+            end: Position {
+                line: 0,
+                character: 100,
+            },
+        },
+        severity: Some(severity.to_lsp()),
+        code: Some(NumberOrString::String("deadmod::module".to_string())),
+        code_description: None,
+        source: Some("deadmod".to_string()),
+        message: format!(
+            "Dead module: `{}` is not reachable from any entry point",
+            module_path
+        ),
+        related_information: Some(vec![DiagnosticRelatedInformation {
+            location: Location {
+                uri: uri.clone(),
+                range: Range::default(),
+            },
+            message: "This module is not imported by any reachable module".to_string(),
+        }]),
+        tags: Some(vec![DiagnosticTag::UNNECESSARY]),
+        data: Some(serde_json::json!({ "id": module_id(module_path) })),
+    }
+}
+
 /// Deadmod Language Server state.
 struct DeadmodLsp {
     client: Client,
     /// Cached workspace root path.
     workspace_root: Arc<RwLock<Option<PathBuf>>>,
+    /// Dedicated analysis pool size from `DEADMOD_JOBS` (global pool if unset).
+    jobs: Option<usize>,
+    /// Current editor settings.
+    settings: Arc<RwLock<Settings>>,
+    /// Number of changes seen, so a debounced analysis only runs for the latest.
+    changes: Arc<AtomicU64>,
+    /// Item detector state per crate root, so unchanged detectors are not re-run.
+    incremental: Arc<Mutex<HashMap<PathBuf, IncrementalState>>>,
 }
 
 impl DeadmodLsp {
@@ -34,6 +265,29 @@ impl DeadmodLsp {
         Self {
             client,
             workspace_root: Arc::new(RwLock::new(None)),
+            jobs: resolve_jobs(None),
+            settings: Arc::new(RwLock::new(Settings::default())),
+            changes: Arc::new(AtomicU64::new(0)),
+            incremental: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Replace the settings with those sent by the editor, keeping the
+    /// current ones if they are invalid.
+    async fn update_settings(&self, value: Value) -> bool {
+        match Settings::from_value(value) {
+            Ok(settings) => {
+                *self.settings.write().await = settings;
+                true
+            }
+            Err(e) => {
+                self.log_error(&format!(
+                    "Invalid deadmod settings, keeping the current ones: {}",
+                    e
+                ))
+                .await;
+                false
+            }
         }
     }
 
@@ -52,7 +306,8 @@ impl DeadmodLsp {
         let crate_root = match find_crate_root(&file_path) {
             Some(r) => r,
             None => {
-                self.log_info("No Cargo.toml found, skipping analysis").await;
+                self.log_info("No Cargo.toml found, skipping analysis")
+                    .await;
                 return;
             }
         };
@@ -63,8 +318,28 @@ impl DeadmodLsp {
             *root = Some(crate_root.clone());
         }
 
-        // Run deadmod analysis
-        match self.compute_diagnostics(&crate_root).await {
+        self.analyze_crate(&crate_root).await;
+    }
+
+    /// Analyze the crate at `crate_root` and publish its diagnostics.
+    async fn analyze_crate(&self, crate_root: &Path) {
+        let config = match load_config(crate_root) {
+            Ok(cfg) => cfg,
+            Err(e) => {
+                self.log_error(&format!("Ignoring deadmod.toml: {:#}", e))
+                    .await;
+                None
+            }
+        };
+        let toggles = config
+            .as_ref()
+            .and_then(|c| c.detectors)
+            .unwrap_or_default();
+        let settings = self.settings.read().await.clone().restricted(&toggles);
+        match self
+            .compute_diagnostics(crate_root, config.as_ref(), &settings)
+            .await
+        {
             Ok(file_diagnostics) => {
                 // Publish diagnostics for each file
                 for (file_uri, diagnostics) in file_diagnostics {
@@ -79,16 +354,18 @@ impl DeadmodLsp {
         }
     }
 
-    /// Compute diagnostics for all dead modules.
+    /// Compute diagnostics for all dead modules and items, as `settings` ask.
     async fn compute_diagnostics(
         &self,
-        crate_root: &std::path::Path,
+        crate_root: &Path,
+        config: Option<&DeadmodConfig>,
+        settings: &Settings,
     ) -> Result<HashMap<Url, Vec<Diagnostic>>> {
-        // Gather files
-        let files = gather_rs_files(crate_root)?;
-
-        // Parse modules (without cache for simplicity in LSP)
-        let mods = cache::incremental_parse(crate_root, &files, None)?;
+        // Gather and parse files (without cache for simplicity in LSP)
+        let mods = run_with_threads(self.jobs, || {
+            let files = gather_rs_files(crate_root)?;
+            cache::incremental_parse(crate_root, &files, None)
+        })??;
 
         // Build graph
         let graph = build_graph(&mods);
@@ -102,7 +379,11 @@ impl DeadmodLsp {
         let reachable: HashSet<&str> = reachable_from_roots(&graph, valid_roots);
 
         // Find dead modules
-        let dead = find_dead(&mods, &reachable);
+        let dead = if settings.runs(LspDetector::Modules) {
+            find_dead(&mods, &reachable)
+        } else {
+            Vec::new()
+        };
 
         // Build diagnostics per file
         let mut result: HashMap<Url, Vec<Diagnostic>> = HashMap::new();
@@ -115,56 +396,138 @@ impl DeadmodLsp {
         }
 
         // Add diagnostics for dead modules
-        for module_name in &dead {
-            if let Some(info) = mods.get(*module_name) {
+        for report in &dead {
+            if let Some(info) = mods.get(report.name) {
+                if settings.is_ignored(crate_root, &info.path) {
+                    continue;
+                }
                 if let Ok(uri) = Url::from_file_path(&info.path) {
-                    let diagnostic = Diagnostic {
-                        range: Range {
-                            start: Position {
-                                line: 0,
-                                character: 0,
-                            },
-                            end: Position {
-                                line: 0,
-                                character: 100,
-                            },
-                        },
-                        severity: Some(DiagnosticSeverity::WARNING),
-                        code: Some(NumberOrString::String("deadmod".to_string())),
-                        code_description: None,
-                        source: Some("deadmod".to_string()),
-                        message: format!(
-                            "Dead module: `{}` is not reachable from any entry point",
-                            module_name
-                        ),
-                        related_information: Some(vec![DiagnosticRelatedInformation {
-                            location: Location {
-                                uri: uri.clone(),
-                                range: Range::default(),
-                            },
-                            message: "This module is not imported by any reachable module"
-                                .to_string(),
-                        }]),
-                        tags: Some(vec![DiagnosticTag::UNNECESSARY]),
-                        data: None,
-                    };
-
+                    let diagnostic = module_diagnostic(&uri, &info.module_path, settings.severity);
                     result.entry(uri).or_default().push(diagnostic);
                 }
             }
         }
 
+        // Files sharing a module path get no verdict; say so instead
+        let style = config.and_then(|cfg| cfg.output.as_ref()?.paths);
+        let paths = PathFormatter::new(style, crate_root);
+        let collisions = if settings.runs(LspDetector::Collisions) {
+            find_collisions(&mods)
+        } else {
+            Vec::new()
+        };
+        for collision in collisions {
+            for file in &collision.files {
+                if settings.is_ignored(crate_root, file) {
+                    continue;
+                }
+                let Ok(uri) = Url::from_file_path(file) else {
+                    continue;
+                };
+                let others: Vec<String> = collision
+                    .files
+                    .iter()
+                    .filter(|other| *other != file)
+                    .map(|other| paths.format(other))
+                    .collect();
+                let diagnostic = Diagnostic {
+                    range: Range::default(),
+                    severity: Some(DiagnosticSeverity::INFORMATION),
+                    code: Some(NumberOrString::String("module-collision".to_string())),
+                    source: Some("deadmod".to_string()),
+                    message: format!(
+                        "Module path `{}` is also defined by {}; dead module analysis skipped",
+                        collision.module_path,
+                        others.join(", ")
+                    ),
+                    ..Default::default()
+                };
+                result.entry(uri).or_default().push(diagnostic);
+            }
+        }
+
+        // Dead items, at their definitions; analysis blocks, so keep it off
+        // the async runtime
+        let items = {
+            let incremental = Arc::clone(&self.incremental);
+            let crate_root = crate_root.to_path_buf();
+            let settings = settings.clone();
+            tokio::task::spawn_blocking(move || {
+                Self::item_findings(&incremental, &crate_root, &settings)
+            })
+            .await??
+        };
+        let mut sources: HashMap<PathBuf, Option<String>> = HashMap::new();
+        for (code, item) in &items {
+            if settings.is_ignored(crate_root, &item.file) {
+                continue;
+            }
+            let Ok(uri) = Url::from_file_path(&item.file) else {
+                continue;
+            };
+            let content = sources
+                .entry(item.file.clone())
+                .or_insert_with(|| std::fs::read_to_string(&item.file).ok());
+            let diagnostic = Diagnostic {
+                range: item_range(content.as_deref().unwrap_or_default(), item),
+                severity: Some(settings.severity.to_lsp()),
+                code: Some(NumberOrString::String(code.to_string())),
+                source: Some("deadmod".to_string()),
+                message: format!("Dead {}: `{}` is never used", item.kind, item.name),
+                tags: Some(vec![DiagnosticTag::UNNECESSARY]),
+                data: Some(serde_json::json!({ "id": item.id })),
+                ..Default::default()
+            };
+            result.entry(uri).or_default().push(diagnostic);
+        }
+
         // Log summary
         self.log_info(&format!(
-            "Analysis complete: {} modules, {} dead",
+            "Analysis complete: {} modules, {} dead, {} dead items",
             mods.len(),
-            dead.len()
+            dead.len(),
+            items.len()
         ))
         .await;
 
         Ok(result)
     }
 
+    /// Dead items found by the enabled item detectors, each with its
+    /// diagnostic code. Detectors whose inputs did not change since the
+    /// crate was last analyzed reuse their findings.
+    fn item_findings(
+        incremental: &Mutex<HashMap<PathBuf, IncrementalState>>,
+        crate_root: &Path,
+        settings: &Settings,
+    ) -> Result<Vec<(&'static str, DeadItem)>> {
+        if !ITEM_DETECTORS
+            .iter()
+            .any(|detector| settings.runs(*detector))
+        {
+            return Ok(Vec::new());
+        }
+        let builder = Deadmod::new(crate_root)
+            .include_functions(settings.runs(LspDetector::Functions))
+            .include_constants(settings.runs(LspDetector::Constants))
+            .include_enums(settings.runs(LspDetector::Enums))
+            .include_macros(settings.runs(LspDetector::Macros));
+
+        let result = {
+            let mut states = incremental.lock().unwrap_or_else(PoisonError::into_inner);
+            builder.analyze_incremental(states.entry(crate_root.to_path_buf()).or_default())?
+        };
+        Ok([
+            ("deadmod::function", result.dead_functions),
+            ("deadmod::const", result.dead_constants),
+            ("deadmod::variant", result.dead_enums),
+            ("deadmod::macro", result.dead_macros),
+        ]
+        .into_iter()
+        .flat_map(|(code, items)| items.into_iter().map(move |item| (code, item)))
+        .collect())
+    }
+
     async fn log_info(&self, message: &str) {
         self.client.log_message(MessageType::INFO, message).await;
     }
@@ -177,6 +540,10 @@ impl DeadmodLsp {
 #[tower_lsp::async_trait]
 impl LanguageServer for DeadmodLsp {
     async fn initialize(&self, params: InitializeParams) -> LspResult<InitializeResult> {
+        if let Some(options) = params.initialization_options {
+            self.update_settings(options).await;
+        }
+
         // Store workspace root if provided
         if let Some(root_uri) = params.root_uri {
             if let Ok(path) = root_uri.to_file_path() {
@@ -217,6 +584,17 @@ impl LanguageServer for DeadmodLsp {
         Ok(())
     }
 
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        if !self.update_settings(params.settings).await {
+            return;
+        }
+        // Re-publish the last analyzed crate under the new settings
+        let root = self.workspace_root.read().await.clone();
+        if let Some(crate_root) = root.filter(|root| root.join("Cargo.toml").exists()) {
+            self.analyze_crate(&crate_root).await;
+        }
+    }
+
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
         let uri = params.text_document.uri;
         if uri.path().ends_with(".rs") {
@@ -231,9 +609,22 @@ impl LanguageServer for DeadmodLsp {
         }
     }
 
-    async fn did_change(&self, _params: DidChangeTextDocumentParams) {
-        // We could run analysis on change, but that might be too aggressive.
-        // For now, we only analyze on save.
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        let uri = params.text_document.uri;
+        let (analyze_on, debounce_ms) = {
+            let settings = self.settings.read().await;
+            (settings.analyze_on, settings.debounce_ms)
+        };
+        if analyze_on != AnalyzeOn::Change || !uri.path().ends_with(".rs") {
+            return;
+        }
+
+        // Analyze once no further change arrives within the debounce interval
+        let change = self.changes.fetch_add(1, Ordering::SeqCst) + 1;
+        tokio::time::sleep(Duration::from_millis(debounce_ms)).await;
+        if self.changes.load(Ordering::SeqCst) == change {
+            self.run_analysis(uri).await;
+        }
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
@@ -251,6 +642,9 @@ async fn main() {
         eprintln!("[PANIC] deadmod-lsp internal error: {}", info);
     }));
 
+    // Same diagnostic logs as the CLI, on stderr (stdout carries the protocol)
+    init_logging(LogFormat::from_env());
+
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
@@ -269,4 +663,114 @@ mod tests {
         // Can't really test without filesystem, but function should not panic
         let _ = find_crate_root(&path);
     }
+
+    #[test]
+    fn test_settings_from_value() {
+        assert_eq!(
+            Settings::from_value(Value::Null).unwrap(),
+            Settings::default()
+        );
+
+        let nested = serde_json::json!({
+            "deadmod": {
+                "ignore": ["src/generated/*"],
+                "detectors": ["modules"],
+                "severity": "hint",
+                "analyzeOn": "change",
+                "debounceMs": 250,
+                "serverPath": "deadmod-lsp"
+            }
+        });
+        let settings = Settings::from_value(nested).unwrap();
+        assert_eq!(settings.detectors, [LspDetector::Modules]);
+        assert_eq!(settings.severity.to_lsp(), DiagnosticSeverity::HINT);
+        assert_eq!(
+            (settings.analyze_on, settings.debounce_ms),
+            (AnalyzeOn::Change, 250)
+        );
+
+        // Top level, partial: the rest keeps its defaults
+        let settings = Settings::from_value(serde_json::json!({ "severity": "error" })).unwrap();
+        assert_eq!(settings.severity, Severity::Error);
+        assert!(settings.runs(LspDetector::Collisions));
+        assert_eq!(settings.analyze_on, AnalyzeOn::Save);
+
+        assert!(Settings::from_value(serde_json::json!({ "detectors": ["everything"] })).is_err());
+    }
+
+    #[test]
+    fn test_settings_restricted_by_config() {
+        let settings = Settings::from_value(
+            serde_json::json!({ "detectors": ["modules", "collisions", "functions", "macros"] }),
+        )
+        .unwrap();
+        let toggles = DetectorToggles {
+            modules: false,
+            macros: false,
+            ..DetectorToggles::default()
+        };
+        let settings = settings.restricted(&toggles);
+        assert_eq!(
+            settings.detectors,
+            [LspDetector::Collisions, LspDetector::Functions]
+        );
+    }
+
+    #[test]
+    fn test_settings_ignore_globs() {
+        let settings = Settings {
+            ignore: vec!["src/generated/*".to_string(), "*_pb.rs".to_string()],
+            ..Settings::default()
+        };
+        let root = Path::new("/work/app");
+        assert!(settings.is_ignored(root, Path::new("/work/app/src/generated/api/mod.rs")));
+        assert!(settings.is_ignored(root, Path::new("/work/app/src/proto/user_pb.rs")));
+        assert!(!settings.is_ignored(root, Path::new("/work/app/src/net.rs")));
+        assert!(!settings.is_ignored(root, Path::new("/work/other/src/generated/x.rs")));
+    }
+
+    #[test]
+    fn test_item_range() {
+        let content = "mod a;\n/* é */ pub fn renew() {}\n/* é */ pub fn new() {}\n";
+        let item = |name: &str, line| DeadItem {
+            id: "fn-1".to_string(),
+            name: name.to_string(),
+            file: PathBuf::from("/work/app/src/lib.rs"),
+            line,
+            kind: deadmod_core::DeadItemKind::Method,
+            confidence: deadmod_core::Confidence::High,
+            module_path: "crate".to_string(),
+        };
+        let span = |range: Range| (range.start.line, range.start.character, range.end.character);
+
+        // The whole word, in UTF-16 columns
+        assert_eq!(
+            span(item_range(content, &item("Config::new", 3))),
+            (2, 15, 18)
+        );
+        // Name not on the line: the whole line
+        assert_eq!(span(item_range(content, &item("gone", 1))), (0, 0, 6));
+        // No line: the first mention
+        assert_eq!(span(item_range(content, &item("renew", 0))), (1, 15, 20));
+
+        let settings =
+            Settings::from_value(serde_json::json!({ "detectors": ["functions", "macros"] }))
+                .unwrap();
+        assert!(settings.runs(LspDetector::Macros) && !settings.runs(LspDetector::Modules));
+    }
+
+    #[test]
+    fn test_module_diagnostic() {
+        let uri = Url::from_file_path("/work/app/src/old.rs").unwrap();
+        let diagnostic = module_diagnostic(&uri, "crate::old", Severity::Hint);
+        assert_eq!(
+            diagnostic.code,
+            Some(NumberOrString::String("deadmod::module".to_string()))
+        );
+        assert_eq!(
+            diagnostic.data,
+            Some(serde_json::json!({ "id": module_id("crate::old") }))
+        );
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::HINT));
+    }
 }
@@ -70,9 +70,13 @@ export function activate(context: vscode.ExtensionContext): void {
   // Client options - configure for Rust files
   const clientOptions: LanguageClientOptions = {
     documentSelector: [{ scheme: "file", language: "rust" }],
+    // Server settings: ignore globs, detectors, severity, analyzeOn, debounceMs
+    initializationOptions: vscode.workspace.getConfiguration("deadmod"),
     synchronize: {
       // Watch for changes to Rust files
       fileEvents: vscode.workspace.createFileSystemWatcher("**/*.rs"),
+      // Send `deadmod.*` setting changes to the server
+      configurationSection: "deadmod",
     },
     outputChannelName: "Deadmod",
   };